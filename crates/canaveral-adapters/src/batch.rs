@@ -0,0 +1,498 @@
+//! Batch publishing across every package in a workspace
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tracing::{debug, info, instrument, warn};
+
+use canaveral_core::error::Result;
+use canaveral_core::monorepo::{
+    DependencyGraph, DiscoveredPackage, FailureStrategy, PackageDiscovery, SkipReason, Workspace,
+};
+
+use crate::publish::PublishOptions;
+use crate::registry::AdapterRegistry;
+use crate::traits::PackageAdapter;
+
+/// Options for publishing a whole workspace
+#[derive(Debug, Clone, Default)]
+pub struct WorkspacePublishOptions {
+    /// Options passed to each adapter's `publish_with_options`
+    pub publish: PublishOptions,
+    /// What to do when a package fails validation or publishing
+    pub failure_strategy: FailureStrategy,
+    /// Only publish these packages and their dependents (if set)
+    pub affected: Option<HashSet<String>>,
+}
+
+impl WorkspacePublishOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the per-package publish options
+    pub fn with_publish_options(mut self, options: PublishOptions) -> Self {
+        self.publish = options;
+        self
+    }
+
+    /// Set the failure strategy
+    pub fn with_failure_strategy(mut self, strategy: FailureStrategy) -> Self {
+        self.failure_strategy = strategy;
+        self
+    }
+
+    /// Restrict publishing to changed packages and everything depending on them
+    pub fn with_affected(mut self, packages: impl IntoIterator<Item = String>) -> Self {
+        self.affected = Some(packages.into_iter().collect());
+        self
+    }
+}
+
+/// What happened to a single package during a batch publish
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublishStatus {
+    /// Package was published (or dry-run published)
+    Published,
+    /// `validate_publishable` reported errors, so publishing was not attempted
+    ValidationFailed(Vec<String>),
+    /// The adapter failed to publish the package
+    Failed(String),
+    /// Package was skipped before validation
+    Skipped(SkipReason),
+    /// Not attempted because an earlier package failed
+    Aborted,
+}
+
+/// Per-package result of [`AdapterRegistry::publish_workspace`]
+#[derive(Debug, Clone)]
+pub struct PublishOutcome {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Package directory
+    pub path: PathBuf,
+    /// Name of the adapter that handled the package, if one was found
+    pub adapter: Option<&'static str>,
+    /// Outcome of the publish
+    pub status: PublishStatus,
+}
+
+impl PublishOutcome {
+    /// Whether the package was published
+    pub fn is_published(&self) -> bool {
+        self.status == PublishStatus::Published
+    }
+
+    /// Whether the package failed validation or publishing
+    pub fn is_failure(&self) -> bool {
+        matches!(
+            self.status,
+            PublishStatus::ValidationFailed(_) | PublishStatus::Failed(_)
+        )
+    }
+}
+
+impl AdapterRegistry {
+    /// Detect, order, validate and publish every package under `root`
+    ///
+    /// Packages are published dependencies-first. A root without a
+    /// recognised workspace is treated as a single package.
+    #[instrument(skip_all, fields(root = %root.display()))]
+    pub fn publish_workspace(
+        &self,
+        root: &Path,
+        options: &WorkspacePublishOptions,
+    ) -> Result<Vec<PublishOutcome>> {
+        let packages = self.discover_packages(root)?;
+        self.publish_packages(&packages, options)
+    }
+
+    /// Publish already-discovered packages in dependency order
+    pub fn publish_packages(
+        &self,
+        packages: &[DiscoveredPackage],
+        options: &WorkspacePublishOptions,
+    ) -> Result<Vec<PublishOutcome>> {
+        let graph = DependencyGraph::build(packages)?;
+        graph.validate()?;
+
+        let selected = options.affected.as_ref().map(|changed| {
+            let mut selected = changed.clone();
+            for name in changed {
+                selected.extend(graph.get_affected(name));
+            }
+            selected
+        });
+
+        let mut outcomes = Vec::new();
+        let mut failed: HashSet<String> = HashSet::new();
+        let mut aborted = false;
+
+        for name in graph.sorted() {
+            let Some(pkg) = packages.iter().find(|p| &p.name == name) else {
+                continue;
+            };
+
+            if selected.as_ref().is_some_and(|s| !s.contains(name)) {
+                continue;
+            }
+
+            let adapter = self.adapter_for(pkg);
+            let mut outcome = PublishOutcome {
+                name: pkg.name.clone(),
+                version: pkg.version.clone(),
+                path: pkg.path.clone(),
+                adapter: adapter.as_ref().map(|a| a.name()),
+                status: PublishStatus::Published,
+            };
+
+            outcome.status = if aborted {
+                PublishStatus::Aborted
+            } else if pkg.private {
+                PublishStatus::Skipped(SkipReason::Private)
+            } else if let Some(dep) = pkg
+                .workspace_dependencies
+                .iter()
+                .find(|d| failed.contains(*d))
+                .filter(|_| options.failure_strategy == FailureStrategy::SkipDependents)
+            {
+                failed.insert(pkg.name.clone());
+                PublishStatus::Skipped(SkipReason::DependencyFailed(dep.clone()))
+            } else {
+                match adapter {
+                    Some(adapter) => publish_one(adapter.as_ref(), pkg, &options.publish),
+                    None => PublishStatus::Failed(format!(
+                        "no adapter for package type '{}'",
+                        pkg.package_type
+                    )),
+                }
+            };
+
+            if outcome.is_failure() {
+                warn!(package = %pkg.name, status = ?outcome.status, "package publish failed");
+                failed.insert(pkg.name.clone());
+                if options.failure_strategy == FailureStrategy::StopOnFailure {
+                    aborted = true;
+                }
+            }
+
+            outcomes.push(outcome);
+        }
+
+        info!(
+            published = outcomes.iter().filter(|o| o.is_published()).count(),
+            failed = outcomes.iter().filter(|o| o.is_failure()).count(),
+            "workspace publish finished"
+        );
+        Ok(outcomes)
+    }
+
+    /// Discover the packages under a workspace root
+    ///
+    /// Each package's workspace dependencies come from its adapter's
+    /// [`PackageAdapter::workspace_dependencies`]. A root without a
+    /// recognised workspace is a single package.
+    pub fn discover_packages(&self, root: &Path) -> Result<Vec<DiscoveredPackage>> {
+        if let Some(workspace) = Workspace::detect(root)? {
+            if !workspace.is_single_package {
+                return PackageDiscovery::new(workspace).discover(|pkg, names| {
+                    match self.adapter_for(pkg) {
+                        Some(adapter) => adapter.workspace_dependencies(&pkg.path, names),
+                        None => Ok(Vec::new()),
+                    }
+                });
+            }
+        }
+
+        let packages = match self.detect(root) {
+            Some(adapter) => vec![DiscoveredPackage::from_info(
+                adapter.get_info(root)?,
+                Vec::new(),
+            )],
+            None => Vec::new(),
+        };
+        debug!(count = packages.len(), "discovered single package");
+        Ok(packages)
+    }
+
+    /// Resolve the adapter for a package by type, falling back to detection
+    pub(crate) fn adapter_for(&self, pkg: &DiscoveredPackage) -> Option<Arc<dyn PackageAdapter>> {
+        self.get(&pkg.package_type)
+            .or_else(|| self.detect(&pkg.path))
+    }
+}
+
+/// Validate and publish a single package
+fn publish_one(
+    adapter: &dyn PackageAdapter,
+    pkg: &DiscoveredPackage,
+    options: &PublishOptions,
+) -> PublishStatus {
+    debug!(package = %pkg.name, adapter = adapter.name(), "publishing package");
+
+    match adapter.validate_publishable(&pkg.path) {
+        Ok(validation) if validation.is_blocking() => {
+            return PublishStatus::ValidationFailed(validation.errors);
+        }
+        Err(e) => return PublishStatus::ValidationFailed(vec![e.to_string()]),
+        Ok(_) => {}
+    }
+
+    match adapter.publish_with_options(&pkg.path, options) {
+        Ok(()) => PublishStatus::Published,
+        Err(e) => PublishStatus::Failed(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    use canaveral_core::error::AdapterError;
+    use canaveral_core::types::PackageInfo;
+
+    use crate::publish::ValidationResult;
+
+    /// Adapter that records publish calls and fails for configured paths
+    struct MockAdapter {
+        published: Arc<Mutex<Vec<String>>>,
+        invalid: Vec<&'static str>,
+        failing: Vec<&'static str>,
+    }
+
+    impl MockAdapter {
+        fn new(published: Arc<Mutex<Vec<String>>>) -> Self {
+            Self {
+                published,
+                invalid: Vec::new(),
+                failing: Vec::new(),
+            }
+        }
+
+        fn package(path: &Path) -> String {
+            path.file_name().unwrap().to_string_lossy().to_string()
+        }
+    }
+
+    impl PackageAdapter for MockAdapter {
+        fn name(&self) -> &'static str {
+            "mock"
+        }
+
+        fn default_registry(&self) -> &'static str {
+            "https://registry.invalid"
+        }
+
+        fn detect(&self, path: &Path) -> bool {
+            path.join("mock.toml").exists()
+        }
+
+        fn get_info(&self, path: &Path) -> Result<PackageInfo> {
+            Ok(PackageInfo::new(
+                Self::package(path),
+                "1.0.0",
+                "mock",
+                path.join("mock.toml"),
+            ))
+        }
+
+        fn get_version(&self, _path: &Path) -> Result<String> {
+            Ok("1.0.0".to_string())
+        }
+
+        fn set_version(&self, _path: &Path, _version: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn validate_publishable(&self, path: &Path) -> Result<ValidationResult> {
+            if self.invalid.contains(&Self::package(path).as_str()) {
+                return Ok(ValidationResult::fail("missing license"));
+            }
+            Ok(ValidationResult::pass())
+        }
+
+        fn publish_with_options(&self, path: &Path, _options: &PublishOptions) -> Result<()> {
+            let name = Self::package(path);
+            if self.failing.contains(&name.as_str()) {
+                return Err(
+                    AdapterError::PublishFailed(format!("registry rejected {}", name)).into(),
+                );
+            }
+            self.published.lock().unwrap().push(name);
+            Ok(())
+        }
+
+        fn manifest_names(&self) -> &[&str] {
+            &["mock.toml"]
+        }
+    }
+
+    fn package(name: &str, deps: &[&str]) -> DiscoveredPackage {
+        DiscoveredPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            path: PathBuf::from("/workspace").join(name),
+            manifest_path: PathBuf::from("/workspace").join(name).join("mock.toml"),
+            package_type: "mock".to_string(),
+            private: false,
+            workspace_dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        }
+    }
+
+    fn registry(adapter: MockAdapter) -> AdapterRegistry {
+        let mut registry = AdapterRegistry::empty();
+        registry.register(adapter);
+        registry
+    }
+
+    fn status<'a>(outcomes: &'a [PublishOutcome], name: &str) -> &'a PublishStatus {
+        &outcomes.iter().find(|o| o.name == name).unwrap().status
+    }
+
+    #[test]
+    fn test_publishes_in_dependency_order() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry(MockAdapter::new(published.clone()));
+        let packages = vec![
+            package("app", &["core", "utils"]),
+            package("utils", &["core"]),
+            package("core", &[]),
+        ];
+
+        let outcomes = registry
+            .publish_packages(&packages, &WorkspacePublishOptions::new())
+            .unwrap();
+
+        assert!(outcomes.iter().all(|o| o.is_published()));
+        assert_eq!(outcomes[0].adapter, Some("mock"));
+        assert_eq!(*published.lock().unwrap(), vec!["core", "utils", "app"]);
+    }
+
+    #[test]
+    fn test_validation_failure_aborts() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let mut adapter = MockAdapter::new(published.clone());
+        adapter.invalid = vec!["core"];
+        let registry = registry(adapter);
+        let packages = vec![package("core", &[]), package("app", &["core"])];
+
+        let outcomes = registry
+            .publish_packages(&packages, &WorkspacePublishOptions::new())
+            .unwrap();
+
+        assert_eq!(
+            status(&outcomes, "core"),
+            &PublishStatus::ValidationFailed(vec!["missing license".to_string()])
+        );
+        assert_eq!(status(&outcomes, "app"), &PublishStatus::Aborted);
+        assert!(published.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_continue_on_error() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let mut adapter = MockAdapter::new(published.clone());
+        adapter.failing = vec!["core"];
+        let registry = registry(adapter);
+        let packages = vec![
+            package("core", &[]),
+            package("app", &["core"]),
+            package("standalone", &[]),
+        ];
+
+        let options =
+            WorkspacePublishOptions::new().with_failure_strategy(FailureStrategy::SkipDependents);
+        let outcomes = registry.publish_packages(&packages, &options).unwrap();
+
+        assert!(matches!(
+            status(&outcomes, "core"),
+            PublishStatus::Failed(_)
+        ));
+        assert_eq!(
+            status(&outcomes, "app"),
+            &PublishStatus::Skipped(SkipReason::DependencyFailed("core".to_string()))
+        );
+        assert_eq!(status(&outcomes, "standalone"), &PublishStatus::Published);
+        assert_eq!(*published.lock().unwrap(), vec!["standalone"]);
+
+        published.lock().unwrap().clear();
+        let options =
+            WorkspacePublishOptions::new().with_failure_strategy(FailureStrategy::ContinueAll);
+        let outcomes = registry.publish_packages(&packages, &options).unwrap();
+
+        assert!(matches!(
+            status(&outcomes, "core"),
+            PublishStatus::Failed(_)
+        ));
+        assert_eq!(status(&outcomes, "app"), &PublishStatus::Published);
+        assert_eq!(published.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_affected_filter_includes_dependents() {
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry(MockAdapter::new(published.clone()));
+        let packages = vec![
+            package("core", &[]),
+            package("app", &["utils"]),
+            package("utils", &[]),
+        ];
+
+        let options = WorkspacePublishOptions::new().with_affected(["utils".to_string()]);
+        let outcomes = registry.publish_packages(&packages, &options).unwrap();
+
+        let names: Vec<_> = outcomes.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, vec!["utils", "app"]);
+    }
+
+    #[test]
+    fn test_publish_workspace_single_package() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("mock.toml"), "").unwrap();
+
+        let published = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry(MockAdapter::new(published.clone()));
+
+        let outcomes = registry
+            .publish_workspace(temp.path(), &WorkspacePublishOptions::new())
+            .unwrap();
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].is_published());
+        assert_eq!(published.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_discover_packages_uses_adapter_dependencies() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "root", "private": true, "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        for (dir, manifest) in [
+            ("core", r#"{"name": "@my/core", "version": "1.0.0"}"#),
+            (
+                "utils",
+                r#"{"name": "@my/utils", "version": "1.0.0", "peerDependencies": {"@my/core": "workspace:^"}}"#,
+            ),
+        ] {
+            let path = root.join("packages").join(dir);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(path.join("package.json"), manifest).unwrap();
+        }
+
+        let packages = AdapterRegistry::new().discover_packages(root).unwrap();
+
+        let utils = packages.iter().find(|p| p.name == "@my/utils").unwrap();
+        assert_eq!(utils.workspace_dependencies, ["@my/core"]);
+        let core = packages.iter().find(|p| p.name == "@my/core").unwrap();
+        assert!(core.workspace_dependencies.is_empty());
+    }
+}
@@ -0,0 +1,345 @@
+//! `cargo binstall` metadata for prebuilt release binaries
+//!
+//! `cargo binstall` finds a crate's prebuilt binaries through
+//! `[package.metadata.binstall]`: `pkg-url` is a template for the download
+//! URL of each target's archive, `pkg-fmt` the archive format and `bin-dir`
+//! the binary's path inside it. Templates use `{ name }`, `{ version }`,
+//! `{ target }`, `{ repo }`, `{ archive-suffix }`, `{ archive-format }`,
+//! `{ binary-ext }` and `{ bin }`. If the assets attached to a release are
+//! named differently from what the template produces, installs fall back to
+//! building from source, so the names are checked before publishing.
+
+use std::path::Path;
+
+use canaveral_core::error::{AdapterError, Result};
+use toml_edit::{value, DocumentMut, Item, Table};
+
+use crate::publish::ValidationResult;
+
+/// Archive formats `cargo binstall` understands
+const PKG_FORMATS: &[&str] = &["tar", "tbz2", "tgz", "txz", "tzstd", "zip", "bin"];
+
+/// Placeholders `cargo binstall` fills in
+const PLACEHOLDERS: &[&str] = &[
+    "name",
+    "version",
+    "target",
+    "repo",
+    "archive-suffix",
+    "archive-format",
+    "format",
+    "binary-ext",
+    "bin",
+    "target-family",
+    "target-arch",
+    "target-libc",
+    "target-vendor",
+];
+
+/// The `[package.metadata.binstall]` section of a Cargo.toml
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinstallMetadata {
+    /// Download URL template of a target's archive
+    pub pkg_url: String,
+    /// Archive format
+    pub pkg_fmt: String,
+    /// Path template of the binary inside the archive
+    pub bin_dir: Option<String>,
+}
+
+impl BinstallMetadata {
+    /// Metadata for `name-target` archives attached to `v{version}` GitHub
+    /// releases of the crate's repository
+    pub fn github_releases(pkg_fmt: impl Into<String>) -> Self {
+        Self {
+            pkg_url:
+                "{ repo }/releases/download/v{ version }/{ name }-{ target }{ archive-suffix }"
+                    .to_string(),
+            pkg_fmt: pkg_fmt.into(),
+            bin_dir: Some("{ bin }{ binary-ext }".to_string()),
+        }
+    }
+
+    /// Read the metadata from the Cargo.toml at `path`, if it has any
+    pub fn from_manifest(path: &Path) -> Result<Option<Self>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+        let manifest: toml::Value = toml::from_str(&content)
+            .map_err(|e| AdapterError::ManifestParseError(e.to_string()))?;
+
+        let Some(section) = manifest
+            .get("package")
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("binstall"))
+        else {
+            return Ok(None);
+        };
+        let field = |key: &str| {
+            section
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let pkg_url = field("pkg-url").ok_or_else(|| {
+            AdapterError::ManifestParseError(
+                "[package.metadata.binstall] has no pkg-url".to_string(),
+            )
+        })?;
+
+        Ok(Some(Self {
+            pkg_url,
+            pkg_fmt: field("pkg-fmt").unwrap_or_else(|| "tgz".to_string()),
+            bin_dir: field("bin-dir"),
+        }))
+    }
+
+    /// Write the metadata into the Cargo.toml at `path`, keeping its formatting
+    pub fn write_to_manifest(&self, path: &Path) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+        let mut doc: DocumentMut = content
+            .parse()
+            .map_err(|e: toml_edit::TomlError| AdapterError::ManifestParseError(e.to_string()))?;
+
+        let package = doc
+            .get_mut("package")
+            .and_then(Item::as_table_mut)
+            .ok_or_else(|| {
+                AdapterError::ManifestParseError("No [package] section found".to_string())
+            })?;
+        let metadata = package
+            .entry("metadata")
+            .or_insert_with(|| {
+                let mut table = Table::new();
+                table.set_implicit(true);
+                Item::Table(table)
+            })
+            .as_table_mut()
+            .ok_or_else(|| {
+                AdapterError::ManifestParseError("package.metadata is not a table".to_string())
+            })?;
+
+        let mut binstall = Table::new();
+        binstall["pkg-url"] = value(&self.pkg_url);
+        binstall["pkg-fmt"] = value(&self.pkg_fmt);
+        if let Some(bin_dir) = &self.bin_dir {
+            binstall["bin-dir"] = value(bin_dir);
+        }
+        metadata["binstall"] = Item::Table(binstall);
+
+        std::fs::write(path, doc.to_string())
+            .map_err(|e| AdapterError::ManifestUpdateError(e.to_string()).into())
+    }
+
+    /// Problems with the templates or format that would break installs
+    ///
+    /// These are warnings: a broken template only costs `cargo binstall`
+    /// users a build from source, it doesn't block publishing.
+    pub fn validate(&self) -> ValidationResult {
+        let mut result = ValidationResult::pass();
+        if !PKG_FORMATS.contains(&self.pkg_fmt.as_str()) {
+            result.add_warning(format!(
+                "binstall pkg-fmt '{}' is not one of: {}",
+                self.pkg_fmt,
+                PKG_FORMATS.join(", ")
+            ));
+        }
+        for (key, template) in [
+            ("pkg-url", Some(&self.pkg_url)),
+            ("bin-dir", self.bin_dir.as_ref()),
+        ] {
+            let Some(template) = template else { continue };
+            for placeholder in placeholders(template) {
+                if !PLACEHOLDERS.contains(&placeholder) {
+                    result.add_warning(format!(
+                        "binstall {} uses unknown placeholder '{{ {} }}'",
+                        key, placeholder
+                    ));
+                }
+            }
+        }
+        if !placeholders(&self.pkg_url).any(|p| p == "target") {
+            result.add_warning(
+                "binstall pkg-url has no { target }, so every target gets the same archive",
+            );
+        }
+        result
+    }
+
+    /// Release asset names the template expects for `target`
+    ///
+    /// Formats with several common suffixes, like `.tgz` and `.tar.gz`,
+    /// give one name per suffix.
+    pub fn asset_names(&self, name: &str, version: &str, target: &str) -> Vec<String> {
+        archive_suffixes(&self.pkg_fmt)
+            .iter()
+            .map(|suffix| {
+                let url = render(&self.pkg_url, |placeholder| match placeholder {
+                    "name" | "bin" => Some(name.to_string()),
+                    "version" => Some(version.to_string()),
+                    "target" => Some(target.to_string()),
+                    "repo" => Some(String::new()),
+                    "archive-suffix" => Some(suffix.to_string()),
+                    "archive-format" | "format" => Some(self.pkg_fmt.clone()),
+                    "binary-ext" => Some(binary_ext(target).to_string()),
+                    _ => None,
+                });
+                url.rsplit('/').next().unwrap_or_default().to_string()
+            })
+            .collect()
+    }
+
+    /// Check that `assets` holds an archive for every target in `targets`
+    ///
+    /// Each target without a matching asset gets a warning naming the file
+    /// the template expects.
+    pub fn check_assets(
+        &self,
+        name: &str,
+        version: &str,
+        targets: &[String],
+        assets: &[String],
+    ) -> ValidationResult {
+        let mut result = ValidationResult::pass();
+        let asset_names: Vec<&str> = assets
+            .iter()
+            .map(|asset| asset.rsplit(['/', '\\']).next().unwrap_or(asset))
+            .collect();
+        for target in targets {
+            let expected = self.asset_names(name, version, target);
+            if !expected.iter().any(|e| asset_names.contains(&e.as_str())) {
+                result.add_warning(format!(
+                    "No release asset matches the binstall pkg-url for {}: expected {}",
+                    target,
+                    expected.join(" or ")
+                ));
+            }
+        }
+        result
+    }
+}
+
+/// Names of the `{ ... }` placeholders in `template`
+fn placeholders(template: &str) -> impl Iterator<Item = &str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name.trim()))
+}
+
+/// Fill in the placeholders of `template`, leaving unknown ones as they are
+fn render(template: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let placeholder = &rest[start..start + end + 1];
+        match lookup(placeholder[1..placeholder.len() - 1].trim()) {
+            Some(value) => out.push_str(&value),
+            None => out.push_str(placeholder),
+        }
+        rest = &rest[start + end + 1..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// File suffixes of archives in `pkg_fmt`
+fn archive_suffixes(pkg_fmt: &str) -> &'static [&'static str] {
+    match pkg_fmt {
+        "tar" => &[".tar"],
+        "tbz2" => &[".tbz2", ".tar.bz2"],
+        "tgz" => &[".tgz", ".tar.gz"],
+        "txz" => &[".txz", ".tar.xz"],
+        "tzstd" => &[".tzstd", ".tzst", ".tar.zst"],
+        "zip" => &[".zip"],
+        _ => &[""],
+    }
+}
+
+/// Executable suffix on `target`
+fn binary_ext(target: &str) -> &'static str {
+    if target.contains("windows") {
+        ".exe"
+    } else {
+        ""
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_generate_metadata() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Cargo.toml");
+        std::fs::write(
+            &path,
+            "[package]\nname = \"mytool\" # the CLI\nversion = \"1.2.0\"\n\n[dependencies]\n",
+        )
+        .unwrap();
+
+        let metadata = BinstallMetadata::github_releases("tgz");
+        assert!(metadata.validate().warnings.is_empty());
+        metadata.write_to_manifest(&path).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.contains("name = \"mytool\" # the CLI"));
+        assert!(content.contains("[package.metadata.binstall]"));
+        assert_eq!(
+            BinstallMetadata::from_manifest(&path).unwrap(),
+            Some(metadata.clone())
+        );
+
+        assert_eq!(
+            metadata.asset_names("mytool", "1.2.0", "x86_64-unknown-linux-gnu"),
+            [
+                "mytool-x86_64-unknown-linux-gnu.tgz",
+                "mytool-x86_64-unknown-linux-gnu.tar.gz"
+            ]
+        );
+        let zip = BinstallMetadata {
+            pkg_url: "{ repo }/releases/download/v{ version }/{ name }-v{ version }-{ target }{ archive-suffix }".to_string(),
+            pkg_fmt: "zip".to_string(),
+            bin_dir: None,
+        };
+        assert_eq!(
+            zip.asset_names("mytool", "1.2.0", "x86_64-pc-windows-msvc"),
+            ["mytool-v1.2.0-x86_64-pc-windows-msvc.zip"]
+        );
+    }
+
+    #[test]
+    fn test_asset_name_mismatch() {
+        let metadata = BinstallMetadata::github_releases("tgz");
+        let targets = vec![
+            "x86_64-unknown-linux-gnu".to_string(),
+            "aarch64-apple-darwin".to_string(),
+        ];
+        let assets = vec![
+            "dist/mytool-x86_64-unknown-linux-gnu.tar.gz".to_string(),
+            "dist/mytool-aarch64-macos.tgz".to_string(),
+        ];
+
+        let result = metadata.check_assets("mytool", "1.2.0", &targets, &assets);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("aarch64-apple-darwin"));
+        assert!(result.warnings[0].contains("mytool-aarch64-apple-darwin.tgz"));
+
+        let broken = BinstallMetadata {
+            pkg_url: "{ repo }/download/{ name }-{ triple }.tgz".to_string(),
+            pkg_fmt: "rar".to_string(),
+            bin_dir: None,
+        };
+        let result = broken.validate();
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 3);
+        assert!(result.warnings.iter().any(|w| w.contains("'rar'")));
+        assert!(result.warnings.iter().any(|w| w.contains("'{ triple }'")));
+    }
+}
@@ -167,6 +167,45 @@ impl CargoToml {
         None
     }
 
+    /// Workspace packages the manifest at `path` depends on
+    ///
+    /// Path dependencies and `workspace = true` dependencies count, from the
+    /// normal, dev and build tables including target-specific ones. Renamed
+    /// dependencies are matched by their `package` name.
+    pub fn workspace_dependencies(path: &Path, known_packages: &[String]) -> Result<Vec<String>> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+        let doc: toml::Table = toml::from_str(&content)
+            .map_err(|e| AdapterError::ManifestParseError(e.to_string()))?;
+
+        let targets = doc
+            .get("target")
+            .and_then(|t| t.as_table())
+            .into_iter()
+            .flat_map(|t| t.values().filter_map(|v| v.as_table()));
+        let mut deps: Vec<String> = std::iter::once(&doc)
+            .chain(targets)
+            .flat_map(|table| {
+                ["dependencies", "dev-dependencies", "build-dependencies"]
+                    .into_iter()
+                    .filter_map(|key| table.get(key).and_then(|d| d.as_table()))
+            })
+            .flat_map(|deps| deps.iter())
+            .filter_map(|(key, spec)| {
+                // Plain version strings come from the registry
+                let spec = spec.as_table()?;
+                let local = spec.contains_key("path")
+                    || spec.get("workspace").and_then(|w| w.as_bool()) == Some(true);
+                let name = spec.get("package").and_then(|p| p.as_str()).unwrap_or(key);
+                (local && known_packages.iter().any(|k| k == name)).then(|| name.to_string())
+            })
+            .collect();
+
+        deps.sort();
+        deps.dedup();
+        Ok(deps)
+    }
+
     /// Update version in Cargo.toml (preserves formatting using toml_edit)
     pub fn update_version(path: &Path, version: &str) -> Result<()> {
         let content = std::fs::read_to_string(path)
@@ -291,6 +330,44 @@ serde = "1.0"
         assert!(updated.contains("[dependencies]"));
     }
 
+    #[test]
+    fn test_workspace_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Cargo.toml");
+
+        std::fs::write(
+            &path,
+            r#"
+[package]
+name = "cli"
+version = "1.0.0"
+
+[dependencies]
+core = { path = "../core" }
+git = { workspace = true }
+serde = "1.0"
+store = { package = "app-store", path = "../store" }
+utils = { version = "0.3" }
+
+[dev-dependencies]
+core = { path = "../core", features = ["testing"] }
+
+[target.'cfg(windows)'.build-dependencies]
+winbuild = { path = "../winbuild" }
+"#,
+        )
+        .unwrap();
+
+        let known: Vec<String> = ["core", "git", "app-store", "utils", "winbuild", "unused"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(
+            CargoToml::workspace_dependencies(&path, &known).unwrap(),
+            ["app-store", "core", "git", "winbuild"]
+        );
+    }
+
     #[test]
     fn test_workspace_detection() {
         let temp = TempDir::new().unwrap();
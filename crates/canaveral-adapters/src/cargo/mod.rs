@@ -1,5 +1,6 @@
 //! Cargo package adapter
 
+mod binstall;
 mod manifest;
 
 use std::path::{Path, PathBuf};
@@ -7,21 +8,73 @@ use std::process::Command;
 
 use tracing::{debug, info};
 
+use canaveral_core::config::PackageConfig;
 use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
+use crate::lock::with_manifest_lock;
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::published::{RegistryProtocol, RegistryVersions, CRATES_INDEX_URL};
+use crate::traits::{PackageAdapter, RequiredTool};
+pub use binstall::BinstallMetadata;
 pub use manifest::CargoToml;
 
 /// Cargo package adapter
-pub struct CargoAdapter;
+pub struct CargoAdapter {
+    published: RegistryVersions,
+    /// Target triples prebuilt binaries are released for
+    release_targets: Vec<String>,
+    /// Release asset files holding the prebuilt binaries
+    release_assets: Vec<String>,
+    /// Only detect the crate with this name
+    package_name: Option<String>,
+}
 
 impl CargoAdapter {
     /// Create a new Cargo adapter
     pub fn new() -> Self {
-        Self
+        Self {
+            published: RegistryVersions::new(RegistryProtocol::CratesIndex, CRATES_INDEX_URL),
+            release_targets: Vec::new(),
+            release_assets: Vec::new(),
+            package_name: None,
+        }
+    }
+
+    /// Adapter for a configured `cargo` package with `release_assets`
+    ///
+    /// Returns `None` for other packages, which use the built-in adapter.
+    pub fn from_config(package: &PackageConfig) -> Option<Self> {
+        if package.package_type != "cargo" {
+            return None;
+        }
+        let assets = package.release_assets.as_ref()?;
+        Some(
+            Self::new()
+                .with_release_assets(assets.targets.clone(), assets.assets.clone())
+                .with_package_name(&package.name),
+        )
+    }
+
+    /// Read published versions from the sparse index at `url` instead of crates.io
+    pub fn with_index_url(mut self, url: impl Into<String>) -> Self {
+        self.published = RegistryVersions::new(RegistryProtocol::CratesIndex, url);
+        self
+    }
+
+    /// Check the `cargo binstall` metadata against the prebuilt binaries
+    /// released for `targets`, found in the `assets` files
+    pub fn with_release_assets(mut self, targets: Vec<String>, assets: Vec<String>) -> Self {
+        self.release_targets = targets;
+        self.release_assets = assets;
+        self
+    }
+
+    /// Only detect the crate named `name`
+    pub fn with_package_name(mut self, name: impl Into<String>) -> Self {
+        self.package_name = Some(name.into());
+        self
     }
 
     /// Get the Cargo.toml path
@@ -45,6 +98,10 @@ impl PackageAdapter for CargoAdapter {
         "https://crates.io"
     }
 
+    fn published_versions(&self, _path: &Path, name: &str) -> Result<Vec<semver::Version>> {
+        self.published.versions(name)
+    }
+
     fn detect(&self, path: &Path) -> bool {
         let manifest = self.manifest_path(path);
         if !manifest.exists() {
@@ -54,7 +111,11 @@ impl PackageAdapter for CargoAdapter {
 
         // Match packages and workspace roots
         let found = if let Ok(toml) = CargoToml::load_from_path(&manifest) {
-            toml.package.is_some() || toml.workspace.is_some()
+            match (&self.package_name, &toml.package) {
+                (Some(name), Some(package)) => package.name == *name,
+                (Some(_), None) => false,
+                (None, package) => package.is_some() || toml.workspace.is_some(),
+            }
         } else {
             false
         };
@@ -66,6 +127,15 @@ impl PackageAdapter for CargoAdapter {
         &["Cargo.toml"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[RequiredTool::new(
+            "cargo",
+            &["--version"],
+            "Install Rust with rustup: https://rustup.rs",
+        )];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let manifest_path = self.manifest_path(path);
         let manifest = CargoToml::load_from_path(&manifest_path)?;
@@ -112,43 +182,18 @@ impl PackageAdapter for CargoAdapter {
     fn set_version(&self, path: &Path, version: &str) -> Result<()> {
         info!(adapter = "cargo", version, path = %path.display(), "setting version");
         let manifest_path = self.manifest_path(path);
-        CargoToml::update_version(&manifest_path, version)
+        with_manifest_lock(&manifest_path, || {
+            CargoToml::update_version(&manifest_path, version)
+        })
     }
 
     fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
         info!(adapter = "cargo", path = %path.display(), dry_run = options.dry_run, "publishing package");
         let mut cmd = Command::new("cargo");
-        cmd.arg("publish");
+        cmd.args(publish_args(&options.clone().with_legacy_extras()));
+        cmd.envs(&options.env);
         cmd.current_dir(path);
 
-        if options.dry_run {
-            cmd.arg("--dry-run");
-        }
-
-        // Registry
-        if let Some(ref registry) = options.registry {
-            cmd.arg("--registry").arg(registry);
-        }
-
-        // Token (if provided via extra options)
-        if let Some(token) = options.extra.get("token") {
-            cmd.arg("--token").arg(token);
-        }
-
-        // Allow dirty (if specified)
-        if options
-            .extra
-            .get("allow_dirty")
-            .is_some_and(|v| v == "true")
-        {
-            cmd.arg("--allow-dirty");
-        }
-
-        // No verify (if specified)
-        if options.extra.get("no_verify").is_some_and(|v| v == "true") {
-            cmd.arg("--no-verify");
-        }
-
         let output = cmd.output().map_err(|e| AdapterError::CommandFailed {
             command: "cargo publish".to_string(),
             reason: e.to_string(),
@@ -156,6 +201,10 @@ impl PackageAdapter for CargoAdapter {
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
+            if options.skip_existing && is_already_published(&stderr) {
+                info!(adapter = "cargo", path = %path.display(), "version already published, skipping");
+                return Ok(());
+            }
             return Err(AdapterError::PublishFailed(stderr.to_string()).into());
         }
 
@@ -233,6 +282,26 @@ impl PackageAdapter for CargoAdapter {
             }
         }
 
+        // Check prebuilt binaries can be found by cargo binstall
+        match BinstallMetadata::from_manifest(&self.manifest_path(path)) {
+            Ok(Some(metadata)) => {
+                result.merge(metadata.validate());
+                result.merge(metadata.check_assets(
+                    &package.name,
+                    &package.version,
+                    &self.release_targets,
+                    &self.release_assets,
+                ));
+            }
+            Ok(None) if !self.release_targets.is_empty() => {
+                result.add_warning(
+                    "No [package.metadata.binstall] section, cargo binstall will build from source",
+                );
+            }
+            Ok(None) => {}
+            Err(e) => result.add_warning(format!("Cannot read binstall metadata: {}", e)),
+        }
+
         // Run cargo check for syntax/dependency validation
         let check_output = Command::new("cargo")
             .args(["check", "--quiet"])
@@ -271,6 +340,14 @@ impl PackageAdapter for CargoAdapter {
         Ok(creds_path.exists() || alt_path.exists())
     }
 
+    fn supports_fmt(&self) -> bool {
+        true
+    }
+
+    fn supports_lint(&self) -> bool {
+        true
+    }
+
     fn fmt(&self, path: &Path, check: bool) -> Result<()> {
         let mut cmd = Command::new("cargo");
         cmd.arg("fmt").arg("--all").current_dir(path);
@@ -390,6 +467,35 @@ impl PackageAdapter for CargoAdapter {
         Ok(())
     }
 
+    fn workspace_dependencies(
+        &self,
+        path: &Path,
+        known_packages: &[String],
+    ) -> Result<Vec<String>> {
+        CargoToml::workspace_dependencies(&self.manifest_path(path), known_packages)
+    }
+
+    fn list_publish_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let output = Command::new("cargo")
+            .args(["package", "--list", "--allow-dirty"])
+            .current_dir(path)
+            .output()
+            .map_err(|e| AdapterError::CommandFailed {
+                command: "cargo package --list".to_string(),
+                reason: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(AdapterError::CommandFailed {
+                command: "cargo package --list".to_string(),
+                reason: String::from_utf8_lossy(&output.stderr).to_string(),
+            }
+            .into());
+        }
+
+        Ok(parse_package_list(&String::from_utf8_lossy(&output.stdout)))
+    }
+
     fn pack(&self, path: &Path) -> Result<Option<PathBuf>> {
         let output = Command::new("cargo")
             .args(["package", "--list"])
@@ -443,11 +549,125 @@ impl PackageAdapter for CargoAdapter {
     }
 }
 
+/// Arguments for `cargo publish`
+fn publish_args(options: &PublishOptions) -> Vec<String> {
+    let mut args = vec!["publish".to_string()];
+
+    if options.dry_run {
+        args.push("--dry-run".to_string());
+    }
+    if let Some(ref registry) = options.registry {
+        args.extend(["--registry".to_string(), registry.clone()]);
+    }
+    // Token (if provided via extra options)
+    if let Some(token) = options.extra.get("token") {
+        args.extend(["--token".to_string(), token.clone()]);
+    }
+    if options.allow_dirty {
+        args.push("--allow-dirty".to_string());
+    }
+    if options.skip_verify {
+        args.push("--no-verify".to_string());
+    }
+
+    args
+}
+
+/// Whether `cargo publish` failed because the version is already published
+fn is_already_published(stderr: &str) -> bool {
+    stderr.contains("already uploaded") || stderr.contains("already exists")
+}
+
+/// File paths from `cargo package --list` (one per line)
+fn parse_package_list(output: &str) -> Vec<PathBuf> {
+    output
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use canaveral_test_support::{MockResponse, MockServer};
     use tempfile::TempDir;
 
+    #[test]
+    fn test_published_versions_from_index() {
+        let index = [
+            r#"{"name":"canaveral-core","vers":"0.1.0","deps":[],"cksum":"a","features":{},"yanked":false}"#,
+            r#"{"name":"canaveral-core","vers":"0.2.0","deps":[],"cksum":"b","features":{},"yanked":true}"#,
+            r#"{"name":"canaveral-core","vers":"0.1.1","deps":[],"cksum":"c","features":{},"yanked":false}"#,
+        ]
+        .join("\n");
+        let server = MockServer::serve(vec![
+            MockResponse::status("200 OK", index),
+            MockResponse::status("404 Not Found", String::new()),
+            MockResponse::status("500 Internal Server Error", String::new()),
+        ]);
+        let adapter = CargoAdapter::new().with_index_url(server.url());
+
+        let versions = adapter
+            .published_versions(Path::new("."), "canaveral-core")
+            .unwrap();
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        assert_eq!(versions, ["0.1.0", "0.1.1"]);
+
+        assert!(adapter
+            .published_versions(Path::new("."), "unpublished")
+            .unwrap()
+            .is_empty());
+        assert!(adapter
+            .published_versions(Path::new("."), "broken")
+            .is_err());
+
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .map(|r| r.path.as_str())
+                .collect::<Vec<_>>(),
+            [
+                "/ca/na/canaveral-core",
+                "/un/pu/unpublished",
+                "/br/ok/broken"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_publish_args() {
+        let args = publish_args(&PublishOptions::new().allow_dirty(true));
+        assert_eq!(args, ["publish", "--allow-dirty"]);
+
+        let args = publish_args(
+            &PublishOptions::new()
+                .dry_run(true)
+                .skip_verify(true)
+                .with_extra("token", "secret")
+                .with_extra("unknown", "ignored"),
+        );
+        assert_eq!(
+            args,
+            ["publish", "--dry-run", "--token", "secret", "--no-verify"]
+        );
+
+        assert!(is_already_published(
+            "error: crate version `1.0.0` is already uploaded"
+        ));
+    }
+
+    #[test]
+    fn test_parse_package_list() {
+        let output =
+            ".cargo_vcs_info.json\nCargo.lock\nCargo.toml\nCargo.toml.orig\nsrc/lib.rs\n\n";
+        let files = parse_package_list(output);
+        assert_eq!(files.len(), 5);
+        assert_eq!(files[4], PathBuf::from("src/lib.rs"));
+    }
+
     #[test]
     fn test_detect() {
         let adapter = CargoAdapter::new();
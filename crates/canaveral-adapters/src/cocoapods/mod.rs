@@ -0,0 +1,299 @@
+//! CocoaPods adapter
+//!
+//! iOS and macOS libraries are described by a podspec and published to the
+//! CocoaPods trunk with `pod trunk push`. The version lives in the podspec,
+//! or in a separate file the podspec reads.
+
+mod podspec;
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::{debug, info};
+
+use canaveral_core::error::{AdapterError, Result};
+use canaveral_core::types::PackageInfo;
+
+use crate::credentials::CredentialProvider;
+use crate::lock::with_manifest_lock;
+use crate::publish::{PublishOptions, ValidationResult};
+use crate::traits::{PackageAdapter, RequiredTool};
+
+pub use podspec::{Podspec, VersionSource};
+
+/// Environment variable `pod trunk` reads its session token from
+const TRUNK_TOKEN_VAR: &str = "COCOAPODS_TRUNK_TOKEN";
+
+/// CocoaPods adapter
+pub struct CocoaPodsAdapter;
+
+impl CocoaPodsAdapter {
+    /// Create a new CocoaPods adapter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Get the podspec path
+    fn manifest_path(&self, path: &Path) -> Result<PathBuf> {
+        Podspec::find(path)
+            .ok_or_else(|| AdapterError::ManifestNotFound(path.join("*.podspec")).into())
+    }
+
+    /// Run `pod lib lint` on the podspec at `podspec`
+    fn lint_podspec(&self, path: &Path, podspec: &Path, options: &PublishOptions) -> Result<()> {
+        let mut cmd = Command::new("pod");
+        cmd.args(["lib", "lint"]).arg(podspec);
+        cmd.args(verify_args(options));
+        cmd.envs(&options.env);
+        cmd.current_dir(path);
+
+        let output = cmd.output().map_err(|e| AdapterError::CommandFailed {
+            command: "pod lib lint".to_string(),
+            reason: e.to_string(),
+        })?;
+
+        if !output.status.success() {
+            return Err(AdapterError::CommandFailed {
+                command: "pod lib lint".to_string(),
+                reason: lint_output(&output),
+            }
+            .into());
+        }
+        Ok(())
+    }
+}
+
+/// Flags shared by `pod lib lint` and `pod trunk push`
+fn verify_args(options: &PublishOptions) -> Vec<&'static str> {
+    let mut args = Vec::new();
+    if options.extra_flag("allow_warnings") {
+        args.push("--allow-warnings");
+    }
+    if options.skip_verify {
+        args.extend(["--skip-import-validation", "--skip-tests"]);
+    }
+    args
+}
+
+/// `pod` prints lint failures on stdout, so include both streams
+fn lint_output(output: &std::process::Output) -> String {
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    format!("{}{}", stdout.trim_end(), stderr)
+        .trim()
+        .to_string()
+}
+
+/// Whether `pod trunk push` failed because the version already exists
+fn is_already_published(output: &str) -> bool {
+    output.contains("Unable to accept duplicate entry")
+}
+
+impl Default for CocoaPodsAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PackageAdapter for CocoaPodsAdapter {
+    fn name(&self) -> &'static str {
+        "cocoapods"
+    }
+
+    fn default_registry(&self) -> &'static str {
+        "https://trunk.cocoapods.org"
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        let found = Podspec::find(path).is_some();
+        debug!(adapter = "cocoapods", path = %path.display(), found, "detecting package");
+        found
+    }
+
+    fn manifest_names(&self) -> &[&str] {
+        &["*.podspec", "*.podspec.json"]
+    }
+
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[RequiredTool::new(
+            "pod",
+            &["--version"],
+            "gem install cocoapods",
+        )];
+        TOOLS
+    }
+
+    fn get_info(&self, path: &Path) -> Result<PackageInfo> {
+        let manifest_path = self.manifest_path(path)?;
+        let podspec = Podspec::load_from_path(&manifest_path)?;
+
+        Ok(PackageInfo {
+            name: podspec.name,
+            version: podspec.version,
+            package_type: "cocoapods".to_string(),
+            manifest_path,
+            private: false,
+        })
+    }
+
+    fn get_version(&self, path: &Path) -> Result<String> {
+        let version = Podspec::load_from_path(&self.manifest_path(path)?)?.version;
+        debug!(adapter = "cocoapods", version = %version, "read version");
+        Ok(version)
+    }
+
+    fn set_version(&self, path: &Path, version: &str) -> Result<()> {
+        info!(adapter = "cocoapods", version, path = %path.display(), "setting version");
+        let manifest_path = self.manifest_path(path)?;
+        with_manifest_lock(&manifest_path, || {
+            Podspec::update_version(&manifest_path, version)
+        })
+    }
+
+    fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
+        info!(adapter = "cocoapods", path = %path.display(), dry_run = options.dry_run, "publishing package");
+        let podspec = self.manifest_path(path)?;
+
+        if options.dry_run {
+            // Trunk has no dry run; linting runs the same checks as a push
+            return self.lint_podspec(path, &podspec, options);
+        }
+
+        let mut cmd = Command::new("pod");
+        cmd.args(["trunk", "push"]).arg(&podspec);
+        cmd.args(verify_args(options));
+        cmd.envs(&options.env);
+
+        // Token from extra options, or whatever check_auth found
+        let token = match options.extra.get("token") {
+            Some(token) => Some(token.clone()),
+            None => CredentialProvider::new()
+                .get("cocoapods")?
+                .and_then(|c| c.as_token().map(str::to_string)),
+        };
+        if let Some(token) = token {
+            cmd.env(TRUNK_TOKEN_VAR, token);
+        }
+        cmd.current_dir(path);
+
+        let output = cmd.output().map_err(|e| AdapterError::CommandFailed {
+            command: "pod trunk push".to_string(),
+            reason: e.to_string(),
+        })?;
+
+        if !output.status.success() {
+            let output = lint_output(&output);
+            if options.skip_existing && is_already_published(&output) {
+                info!(adapter = "cocoapods", path = %path.display(), "version already published, skipping");
+                return Ok(());
+            }
+            return Err(AdapterError::PublishFailed(output).into());
+        }
+
+        Ok(())
+    }
+
+    fn validate_publishable(&self, path: &Path) -> Result<ValidationResult> {
+        debug!(adapter = "cocoapods", path = %path.display(), "validating publishable");
+        let mut result = ValidationResult::pass();
+
+        let podspec_path = match Podspec::find(path) {
+            Some(p) => p,
+            None => {
+                result.add_error("No .podspec or .podspec.json found");
+                return Ok(result);
+            }
+        };
+
+        let podspec = match Podspec::load_from_path(&podspec_path) {
+            Ok(p) => p,
+            Err(e) => {
+                result.add_error(format!("Cannot parse podspec: {}", e));
+                return Ok(result);
+            }
+        };
+
+        if podspec.name.is_empty() {
+            result.add_error("Pod name is not set");
+        }
+
+        if semver::Version::parse(&podspec.version).is_err() {
+            result.add_warning(format!("Version '{}' is not valid semver", podspec.version));
+        }
+
+        // Lint the podspec the way trunk will
+        let pod_check = Command::new("pod").arg("--version").output();
+        if pod_check.map(|o| o.status.success()).unwrap_or(false) {
+            if let Err(e) = self.lint_podspec(path, &podspec_path, &PublishOptions::new()) {
+                result.add_error(format!("Podspec does not pass linting: {}", e));
+            }
+        } else {
+            result.add_warning("CocoaPods is not installed, skipped 'pod lib lint'");
+        }
+
+        Ok(result)
+    }
+
+    fn check_auth(&self, credentials: &mut CredentialProvider) -> Result<bool> {
+        debug!(adapter = "cocoapods", "checking authentication");
+        if credentials.has_credentials("cocoapods") {
+            return Ok(true);
+        }
+
+        // Sessions registered with `pod trunk register` live in ~/.netrc
+        let output = Command::new("pod").args(["trunk", "me"]).output();
+        Ok(output.map(|o| o.status.success()).unwrap_or(false))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_detect_and_info() {
+        let temp = TempDir::new().unwrap();
+        let adapter = CocoaPodsAdapter::new();
+        assert!(!adapter.detect(temp.path()));
+
+        std::fs::write(
+            temp.path().join("Rocket.podspec"),
+            "Pod::Spec.new do |spec|\n  spec.name = \"Rocket\"\n  spec.version = \"0.4.0\"\nend\n",
+        )
+        .unwrap();
+        assert!(adapter.detect(temp.path()));
+
+        let info = adapter.get_info(temp.path()).unwrap();
+        assert_eq!(info.name, "Rocket");
+        assert_eq!(info.version, "0.4.0");
+        assert_eq!(info.package_type, "cocoapods");
+
+        adapter.set_version(temp.path(), "0.5.0").unwrap();
+        assert_eq!(adapter.get_version(temp.path()).unwrap(), "0.5.0");
+    }
+
+    #[test]
+    fn test_publish_args() {
+        let options = PublishOptions::new();
+        assert!(verify_args(&options).is_empty());
+
+        let mut options = PublishOptions::new().with_extra("allow_warnings", "true");
+        options.skip_verify = true;
+        assert_eq!(
+            verify_args(&options),
+            [
+                "--allow-warnings",
+                "--skip-import-validation",
+                "--skip-tests"
+            ]
+        );
+
+        assert!(is_already_published(
+            "[!] Unable to accept duplicate entry for: Rocket (0.5.0)"
+        ));
+        assert!(!is_already_published(
+            "[!] The spec did not pass validation"
+        ));
+    }
+}
@@ -0,0 +1,349 @@
+//! Podspec parsing
+//!
+//! Podspecs come in two forms: a Ruby DSL (`Foo.podspec`) and its JSON
+//! rendering (`Foo.podspec.json`). Only the fields needed for releases are
+//! read. In the Ruby form the version is either a string literal
+//! (`s.version = '1.2.3'`) or read from a separate file
+//! (`s.version = File.read('VERSION').strip`), in which case that file is
+//! what gets rewritten.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use canaveral_core::error::{AdapterError, Result};
+
+use crate::lock::with_manifest_lock;
+
+fn name_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?m)^\s*\w+\.name\s*=\s*['"]([^'"]+)['"]"#).expect("Invalid regex")
+    })
+}
+
+fn version_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r#"(?m)^(\s*\w+\.version\s*=\s*['"])([^'"]+)(['"])"#).expect("Invalid regex")
+    })
+}
+
+/// `s.version = File.read('VERSION')`, also with `File.join(__dir__, ...)`
+/// or `File.expand_path(..., __dir__)` around the path
+fn version_file_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r#"(?m)^\s*\w+\.version\s*=\s*File\.read\(\s*(?:File\.join\(\s*__dir__\s*,\s*|File\.expand_path\(\s*)?['"]([^'"]+)['"]"#,
+        )
+        .expect("Invalid regex")
+    })
+}
+
+fn json_version_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r#"("version"\s*:\s*")([^"]*)(")"#).expect("Invalid regex"))
+}
+
+/// Where a podspec's version is stored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionSource {
+    /// In the podspec itself
+    Inline,
+    /// In a separate file the podspec reads
+    File(PathBuf),
+}
+
+/// Parsed podspec
+#[derive(Debug, Clone)]
+pub struct Podspec {
+    /// Pod name
+    pub name: String,
+    /// Pod version
+    pub version: String,
+    /// Where the version is stored
+    pub version_source: VersionSource,
+}
+
+impl Podspec {
+    /// Find the podspec in `dir`: the first `*.podspec` or `*.podspec.json`
+    /// by name
+    pub fn find(dir: &Path) -> Option<PathBuf> {
+        let mut podspecs: Vec<PathBuf> = std::fs::read_dir(dir)
+            .ok()?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_file() && is_podspec(path))
+            .collect();
+        podspecs.sort();
+        podspecs.into_iter().next()
+    }
+
+    /// Load a podspec from a file path
+    pub fn load_from_path(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+
+        if is_json(path) {
+            return Self::parse_json(&content);
+        }
+
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut podspec = Self::parse_ruby(&content, dir)?;
+        if let VersionSource::File(version_file) = &podspec.version_source {
+            let version = std::fs::read_to_string(version_file).map_err(|e| {
+                AdapterError::ManifestParseError(format!(
+                    "Failed to read version file {}: {}",
+                    version_file.display(),
+                    e
+                ))
+            })?;
+            podspec.version = version.trim().to_string();
+        }
+        Ok(podspec)
+    }
+
+    /// Parse a Ruby DSL podspec, resolving a version file against `dir`
+    ///
+    /// A version read from a file is left empty.
+    pub fn parse_ruby(content: &str, dir: &Path) -> Result<Self> {
+        let name = name_regex()
+            .captures(content)
+            .map(|c| c[1].to_string())
+            .ok_or_else(|| AdapterError::ManifestParseError("Podspec has no name".to_string()))?;
+
+        if let Some(captures) = version_regex().captures(content) {
+            return Ok(Self {
+                name,
+                version: captures[2].to_string(),
+                version_source: VersionSource::Inline,
+            });
+        }
+        if let Some(captures) = version_file_regex().captures(content) {
+            return Ok(Self {
+                name,
+                version: String::new(),
+                version_source: VersionSource::File(dir.join(&captures[1])),
+            });
+        }
+
+        Err(AdapterError::ManifestParseError(
+            "Podspec version is neither a string literal nor read from a file".to_string(),
+        )
+        .into())
+    }
+
+    /// Parse a JSON podspec
+    pub fn parse_json(content: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| AdapterError::ManifestParseError(e.to_string()))?;
+        let field = |key: &str| {
+            value
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .ok_or_else(|| AdapterError::ManifestParseError(format!("Podspec has no {}", key)))
+        };
+
+        Ok(Self {
+            name: field("name")?,
+            version: field("version")?,
+            version_source: VersionSource::Inline,
+        })
+    }
+
+    /// Update the version of the podspec at `path`
+    ///
+    /// The podspec is rewritten in place, keeping its formatting; a version
+    /// read from a separate file is written to that file instead.
+    pub fn update_version(path: &Path, version: &str) -> Result<()> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+
+        if is_json(path) {
+            let updated = set_json_version(&content, version)?;
+            return write(path, &updated);
+        }
+
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        match Self::parse_ruby(&content, dir)?.version_source {
+            VersionSource::Inline => {
+                let updated = version_regex()
+                    .replace(&content, |c: &regex::Captures| {
+                        format!("{}{}{}", &c[1], version, &c[3])
+                    })
+                    .into_owned();
+                write(path, &updated)
+            }
+            VersionSource::File(version_file) => with_manifest_lock(&version_file, || {
+                // Keep the file's trailing newline, if it had one
+                let existing = std::fs::read_to_string(&version_file).unwrap_or_default();
+                let updated = if existing.ends_with('\n') {
+                    format!("{}\n", version)
+                } else {
+                    version.to_string()
+                };
+                write(&version_file, &updated)
+            }),
+        }
+    }
+}
+
+/// Whether `path` is named like a podspec
+fn is_podspec(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(".podspec") || n.ends_with(".podspec.json"))
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+}
+
+/// Replace the top-level `"version"` value of a JSON podspec
+///
+/// Nested objects can have their own `"version"` keys, so the first one
+/// holding the current version is replaced and the result checked.
+fn set_json_version(content: &str, version: &str) -> Result<String> {
+    let current = Podspec::parse_json(content)?.version;
+    let Some(captures) = json_version_regex()
+        .captures_iter(content)
+        .find(|c| c[2] == current)
+    else {
+        return Err(AdapterError::ManifestUpdateError("Podspec has no version".to_string()).into());
+    };
+
+    let range = captures.get(2).expect("version group").range();
+    let updated = format!(
+        "{}{}{}",
+        &content[..range.start],
+        version,
+        &content[range.end..]
+    );
+    if Podspec::parse_json(&updated)?.version != version {
+        return Err(AdapterError::ManifestUpdateError(
+            "Could not locate the podspec's top-level version".to_string(),
+        )
+        .into());
+    }
+    Ok(updated)
+}
+
+fn write(path: &Path, content: &str) -> Result<()> {
+    std::fs::write(path, content)
+        .map_err(|e| AdapterError::ManifestUpdateError(e.to_string()).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lock::{ManifestLock, DEFAULT_LOCK_TIMEOUT};
+    use tempfile::TempDir;
+
+    const DSL: &str = r#"Pod::Spec.new do |s|
+  s.name             = 'Rocket'
+  s.version          = '1.2.3'
+  s.swift_version    = '5.9'
+  s.source           = { :git => 'https://github.com/acme/Rocket.git', :tag => s.version.to_s }
+  s.ios.deployment_target = '15.0'
+end
+"#;
+
+    const JSON: &str = r#"{
+  "name": "Rocket",
+  "version": "1.2.3",
+  "source": {
+    "git": "https://github.com/acme/Rocket.git",
+    "tag": "1.2.3"
+  },
+  "swift_versions": ["5.9"]
+}
+"#;
+
+    #[test]
+    fn test_dsl_version() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Rocket.podspec");
+        std::fs::write(&path, DSL).unwrap();
+        assert_eq!(Podspec::find(temp.path()), Some(path.clone()));
+
+        let podspec = Podspec::load_from_path(&path).unwrap();
+        assert_eq!(podspec.name, "Rocket");
+        assert_eq!(podspec.version, "1.2.3");
+        assert_eq!(podspec.version_source, VersionSource::Inline);
+
+        Podspec::update_version(&path, "2.0.0-beta.1").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(content, DSL.replace("'1.2.3'", "'2.0.0-beta.1'"));
+        // Other version-like fields are untouched
+        assert!(content.contains("s.swift_version    = '5.9'"));
+        assert_eq!(
+            Podspec::load_from_path(&path).unwrap().version,
+            "2.0.0-beta.1"
+        );
+    }
+
+    #[test]
+    fn test_json_version() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Rocket.podspec.json");
+        std::fs::write(&path, JSON).unwrap();
+        assert_eq!(Podspec::find(temp.path()), Some(path.clone()));
+
+        let podspec = Podspec::load_from_path(&path).unwrap();
+        assert_eq!(podspec.name, "Rocket");
+        assert_eq!(podspec.version, "1.2.3");
+
+        Podspec::update_version(&path, "1.3.0").unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        // Only the top-level version changes; formatting and key order stay
+        assert_eq!(
+            content,
+            JSON.replacen("\"version\": \"1.2.3\"", "\"version\": \"1.3.0\"", 1)
+        );
+        assert_eq!(Podspec::load_from_path(&path).unwrap().version, "1.3.0");
+    }
+
+    #[test]
+    fn test_version_from_file() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Rocket.podspec");
+        let dsl = DSL.replace("'1.2.3'", "File.read(File.join(__dir__, 'VERSION')).strip");
+        std::fs::write(&path, &dsl).unwrap();
+        std::fs::write(temp.path().join("VERSION"), "1.2.3\n").unwrap();
+
+        let podspec = Podspec::load_from_path(&path).unwrap();
+        assert_eq!(podspec.version, "1.2.3");
+        assert_eq!(
+            podspec.version_source,
+            VersionSource::File(temp.path().join("VERSION"))
+        );
+
+        Podspec::update_version(&path, "1.4.0").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("VERSION")).unwrap(),
+            "1.4.0\n"
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), dsl);
+    }
+
+    #[test]
+    fn test_version_file_write_waits_for_lock() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Rocket.podspec");
+        let version_file = temp.path().join("VERSION");
+        let dsl = DSL.replace("'1.2.3'", "File.read('VERSION').strip");
+        std::fs::write(&path, dsl).unwrap();
+        std::fs::write(&version_file, "1.2.3\n").unwrap();
+
+        let held = ManifestLock::acquire(&version_file, DEFAULT_LOCK_TIMEOUT).unwrap();
+        let writer = std::thread::spawn(move || Podspec::update_version(&path, "1.4.0"));
+        std::thread::sleep(std::time::Duration::from_millis(100));
+        assert_eq!(std::fs::read_to_string(&version_file).unwrap(), "1.2.3\n");
+
+        drop(held);
+        writer.join().unwrap().unwrap();
+        assert_eq!(std::fs::read_to_string(&version_file).unwrap(), "1.4.0\n");
+    }
+}
@@ -100,6 +100,11 @@ impl CredentialProvider {
                     return Ok(Some(Credential::Token(token)));
                 }
             }
+            "cocoapods" | "trunk" => {
+                if let Ok(token) = env::var("COCOAPODS_TRUNK_TOKEN") {
+                    return Ok(Some(Credential::Token(token)));
+                }
+            }
             _ => {}
         }
 
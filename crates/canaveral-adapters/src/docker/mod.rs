@@ -15,8 +15,9 @@ use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
+use crate::lock::{ManifestLock, DEFAULT_LOCK_TIMEOUT};
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::traits::{PackageAdapter, RequiredTool};
 
 /// Docker image adapter
 pub struct DockerAdapter {
@@ -96,6 +97,15 @@ impl PackageAdapter for DockerAdapter {
         &["Dockerfile"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[RequiredTool::new(
+            "docker",
+            &["--version"],
+            "Install Docker from https://docs.docker.com/get-docker",
+        )];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let (name, version) = parser::parse_image_info(path)?;
 
@@ -123,6 +133,7 @@ impl PackageAdapter for DockerAdapter {
             );
         }
 
+        let _lock = ManifestLock::acquire(&dockerfile, DEFAULT_LOCK_TIMEOUT)?;
         let content = std::fs::read_to_string(&dockerfile)?;
         let mut lines: Vec<String> = content.lines().map(String::from).collect();
         let mut found = false;
@@ -313,6 +324,10 @@ impl PackageAdapter for DockerAdapter {
         Ok(false)
     }
 
+    fn supports_lint(&self) -> bool {
+        true
+    }
+
     fn lint(&self, path: &Path) -> Result<()> {
         let dockerfile = self.dockerfile_path(path);
         if !dockerfile.exists() {
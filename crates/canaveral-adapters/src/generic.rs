@@ -0,0 +1,331 @@
+//! Generic regex-based version adapter
+//!
+//! For projects without a recognized manifest, such as a C header with
+//! `#define APP_VERSION "1.2.3"` or a CMake `project(app VERSION 1.2.3)`.
+//! The version is read from any text file with a regex capturing it, and
+//! written back either in place or through a template.
+
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use tracing::{debug, info};
+
+use canaveral_core::config::PackageConfig;
+use canaveral_core::error::{AdapterError, Result};
+use canaveral_core::types::PackageInfo;
+
+use crate::lock::with_manifest_lock;
+use crate::publish::PublishOptions;
+use crate::traits::PackageAdapter;
+
+/// Placeholder for the version in write templates
+pub const VERSION_PLACEHOLDER: &str = "{version}";
+
+/// Adapter reading and writing the version in an arbitrary text file
+#[derive(Debug, Clone)]
+pub struct GenericAdapter {
+    /// Version file, relative to the package directory
+    file: PathBuf,
+    /// Pattern whose single capture group is the version
+    pattern: Regex,
+    /// Replacement for the whole match, with `{version}` placeholders
+    template: Option<String>,
+    /// Package name reported by `get_info`
+    package_name: Option<String>,
+}
+
+impl GenericAdapter {
+    /// Create an adapter reading the version from `file` with `pattern`
+    ///
+    /// The pattern must have exactly one capture group, matching the
+    /// version. Without a template, only that group is replaced on write.
+    pub fn new(file: impl Into<PathBuf>, pattern: &str) -> Result<Self> {
+        let pattern = Regex::new(pattern).map_err(|e| {
+            AdapterError::ManifestParseError(format!("invalid version pattern: {}", e))
+        })?;
+        let groups = pattern.captures_len() - 1;
+        if groups != 1 {
+            return Err(AdapterError::ManifestParseError(format!(
+                "version pattern '{}' must have exactly one capture group, found {}",
+                pattern, groups
+            ))
+            .into());
+        }
+
+        Ok(Self {
+            file: file.into(),
+            pattern,
+            template: None,
+            package_name: None,
+        })
+    }
+
+    /// Adapter for a configured package with a `version_source`
+    pub fn from_config(package: &PackageConfig) -> Result<Option<Self>> {
+        let Some(source) = &package.version_source else {
+            return Ok(None);
+        };
+        let adapter = Self::new(&source.file, &source.pattern)?.with_package_name(&package.name);
+        Ok(Some(match &source.template {
+            Some(template) => adapter.with_template(template),
+            None => adapter,
+        }))
+    }
+
+    /// Replace the whole match with `template` on write
+    ///
+    /// `{version}` in the template is replaced with the new version.
+    pub fn with_template(mut self, template: impl Into<String>) -> Self {
+        self.template = Some(template.into());
+        self
+    }
+
+    /// Set the package name (defaults to the package directory name)
+    pub fn with_package_name(mut self, name: impl Into<String>) -> Self {
+        self.package_name = Some(name.into());
+        self
+    }
+
+    /// The version file, relative to the package directory
+    pub fn file(&self) -> &Path {
+        &self.file
+    }
+
+    /// Get the version file path
+    fn manifest_path(&self, path: &Path) -> PathBuf {
+        path.join(&self.file)
+    }
+
+    fn read(&self, path: &Path) -> Result<(PathBuf, String)> {
+        let manifest_path = self.manifest_path(path);
+        if !manifest_path.exists() {
+            return Err(AdapterError::ManifestNotFound(manifest_path).into());
+        }
+        let content = std::fs::read_to_string(&manifest_path)?;
+        Ok((manifest_path, content))
+    }
+
+    /// Extract the version from file content
+    pub fn extract_version(&self, content: &str) -> Result<String> {
+        self.pattern
+            .captures(content)
+            .and_then(|c| c.get(1))
+            .map(|m| m.as_str().to_string())
+            .ok_or_else(|| {
+                AdapterError::ManifestParseError(format!(
+                    "version pattern '{}' does not match {}",
+                    self.pattern,
+                    self.file.display()
+                ))
+                .into()
+            })
+    }
+
+    /// Replace the version in file content
+    ///
+    /// Only the first match is replaced. Fails if the updated content
+    /// doesn't read back as `version`, so a template that breaks the
+    /// pattern is caught before anything is written.
+    pub fn replace_version(&self, content: &str, version: &str) -> Result<String> {
+        let captures = self.pattern.captures(content).ok_or_else(|| {
+            AdapterError::ManifestUpdateError(format!(
+                "version pattern '{}' does not match {}",
+                self.pattern,
+                self.file.display()
+            ))
+        })?;
+
+        let (range, replacement) = match &self.template {
+            Some(template) => (
+                captures.get(0).expect("group 0 always matches").range(),
+                template.replace(VERSION_PLACEHOLDER, version),
+            ),
+            None => {
+                let group = captures.get(1).ok_or_else(|| {
+                    AdapterError::ManifestUpdateError(format!(
+                        "version group of pattern '{}' did not participate in the match",
+                        self.pattern
+                    ))
+                })?;
+                (group.range(), version.to_string())
+            }
+        };
+
+        let mut updated = String::with_capacity(content.len());
+        updated.push_str(&content[..range.start]);
+        updated.push_str(&replacement);
+        updated.push_str(&content[range.end..]);
+
+        match self.extract_version(&updated) {
+            Ok(written) if written == version => Ok(updated),
+            Ok(written) => Err(AdapterError::ManifestUpdateError(format!(
+                "writing {} to {} would read back as {}; check the write template",
+                version,
+                self.file.display(),
+                written
+            ))
+            .into()),
+            Err(_) => Err(AdapterError::ManifestUpdateError(format!(
+                "the version pattern no longer matches {} after writing; check the write template",
+                self.file.display()
+            ))
+            .into()),
+        }
+    }
+}
+
+impl PackageAdapter for GenericAdapter {
+    fn name(&self) -> &'static str {
+        "generic"
+    }
+
+    fn default_registry(&self) -> &'static str {
+        ""
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        let found = std::fs::read_to_string(self.manifest_path(path))
+            .is_ok_and(|content| self.pattern.is_match(&content));
+        debug!(adapter = "generic", path = %path.display(), found, "detecting package");
+        found
+    }
+
+    fn manifest_names(&self) -> &[&str] {
+        &[]
+    }
+
+    fn get_info(&self, path: &Path) -> Result<PackageInfo> {
+        let (manifest_path, content) = self.read(path)?;
+        let name = self.package_name.clone().unwrap_or_else(|| {
+            path.canonicalize()
+                .ok()
+                .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+                .unwrap_or_else(|| "package".to_string())
+        });
+        Ok(PackageInfo::new(
+            name,
+            self.extract_version(&content)?,
+            "generic",
+            manifest_path,
+        ))
+    }
+
+    fn get_version(&self, path: &Path) -> Result<String> {
+        let (_, content) = self.read(path)?;
+        let version = self.extract_version(&content)?;
+        debug!(adapter = "generic", version = %version, "read version");
+        Ok(version)
+    }
+
+    fn set_version(&self, path: &Path, version: &str) -> Result<()> {
+        info!(adapter = "generic", version, path = %path.display(), "setting version");
+        with_manifest_lock(&self.manifest_path(path), || {
+            let (manifest_path, content) = self.read(path)?;
+            let updated = self.replace_version(&content, version)?;
+            std::fs::write(&manifest_path, updated)?;
+            Ok(())
+        })
+    }
+
+    fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
+        info!(
+            adapter = "generic",
+            path = %path.display(),
+            dry_run = options.dry_run,
+            "generic packages have no registry, nothing to publish"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const HEADER: &str = r#"#pragma once
+
+#define APP_NAME "demo"
+#define APP_VERSION "1.2.3"
+#define APP_BUILD 42
+"#;
+
+    const CMAKE: &str = r#"cmake_minimum_required(VERSION 3.20)
+project(demo VERSION 1.2.3 LANGUAGES C CXX)
+"#;
+
+    #[test]
+    fn test_define_version_round_trip() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("include")).unwrap();
+        std::fs::write(temp.path().join("include/version.h"), HEADER).unwrap();
+
+        let adapter =
+            GenericAdapter::new("include/version.h", r#"#define APP_VERSION "([^"]+)""#).unwrap();
+        assert!(adapter.detect(temp.path()));
+        assert_eq!(adapter.get_version(temp.path()).unwrap(), "1.2.3");
+
+        adapter.set_version(temp.path(), "1.3.0").unwrap();
+        assert_eq!(adapter.get_version(temp.path()).unwrap(), "1.3.0");
+        let content = std::fs::read_to_string(temp.path().join("include/version.h")).unwrap();
+        assert_eq!(content, HEADER.replace("1.2.3", "1.3.0"));
+    }
+
+    #[test]
+    fn test_cmake_project_version_with_template() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("CMakeLists.txt"), CMAKE).unwrap();
+
+        let adapter =
+            GenericAdapter::new("CMakeLists.txt", r"project\(demo VERSION (\d+\.\d+\.\d+)")
+                .unwrap()
+                .with_template("project(demo VERSION {version}")
+                .with_package_name("demo");
+
+        // Only the project line matches, not cmake_minimum_required
+        let info = adapter.get_info(temp.path()).unwrap();
+        assert_eq!(info.name, "demo");
+        assert_eq!(info.version, "1.2.3");
+
+        adapter.set_version(temp.path(), "2.0.0").unwrap();
+        let content = std::fs::read_to_string(temp.path().join("CMakeLists.txt")).unwrap();
+        assert!(content.contains("project(demo VERSION 2.0.0 LANGUAGES C CXX)"));
+        assert!(content.contains("cmake_minimum_required(VERSION 3.20)"));
+    }
+
+    #[test]
+    fn test_pattern_needs_one_capture_group() {
+        assert!(GenericAdapter::new("version.h", r#"APP_VERSION "[^"]+""#).is_err());
+        assert!(GenericAdapter::new("version.h", r#"(APP)_VERSION "([^"]+)""#).is_err());
+        assert!(GenericAdapter::new("version.h", r#"APP_VERSION "(?:v)?([^"]+)""#).is_ok());
+        assert!(GenericAdapter::new("version.h", "(unclosed").is_err());
+    }
+
+    #[test]
+    fn test_template_must_round_trip() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("version.h"), HEADER).unwrap();
+
+        let adapter = GenericAdapter::new("version.h", r#"#define APP_VERSION "([^"]+)""#)
+            .unwrap()
+            .with_template("#define APP_VERSION {version}");
+        let err = adapter.set_version(temp.path(), "1.3.0").unwrap_err();
+        assert!(err.to_string().contains("write template"));
+
+        // Nothing was written
+        let content = std::fs::read_to_string(temp.path().join("version.h")).unwrap();
+        assert_eq!(content, HEADER);
+    }
+
+    #[test]
+    fn test_missing_file_or_match() {
+        let temp = TempDir::new().unwrap();
+        let adapter = GenericAdapter::new("version.h", r#"APP_VERSION "([^"]+)""#).unwrap();
+        assert!(!adapter.detect(temp.path()));
+        assert!(adapter.get_version(temp.path()).is_err());
+
+        std::fs::write(temp.path().join("version.h"), "int main() {}\n").unwrap();
+        assert!(!adapter.detect(temp.path()));
+        assert!(adapter.get_version(temp.path()).is_err());
+    }
+}
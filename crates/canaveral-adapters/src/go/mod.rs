@@ -15,7 +15,7 @@ use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::traits::{PackageAdapter, RequiredTool};
 
 pub use gomod::GoMod;
 
@@ -33,19 +33,32 @@ impl GoAdapter {
         path.join("go.mod")
     }
 
+    /// Tag prefix for the module at `path`: its directory relative to the
+    /// repository root (empty for a module at the root)
+    fn tag_prefix(&self, path: &Path) -> Result<String> {
+        let output = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(path)
+            .output()
+            .map_err(|e| AdapterError::CommandFailed {
+                command: "git rev-parse".to_string(),
+                reason: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Ok(String::new());
+        }
+
+        let root = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+        Ok(module_tag_prefix(&root, path))
+    }
+
     /// Get the latest git tag for this module
-    fn get_latest_tag(&self, path: &Path, module_path: &str) -> Result<Option<String>> {
-        // Try module-prefixed tags first (for monorepos)
-        let prefix = if module_path.contains('/') {
-            // Extract the last part as potential prefix
-            module_path.rsplit('/').next().unwrap_or("")
-        } else {
-            ""
-        };
+    fn get_latest_tag(&self, path: &Path) -> Result<Option<String>> {
+        let prefix = self.tag_prefix(path)?;
 
-        // List tags matching the module prefix
         let output = Command::new("git")
-            .args(["tag", "-l", "--sort=-v:refname"])
+            .args(["tag", "-l"])
             .current_dir(path)
             .output()
             .map_err(|e| AdapterError::CommandFailed {
@@ -58,37 +71,23 @@ impl GoAdapter {
         }
 
         let stdout = String::from_utf8_lossy(&output.stdout);
-        let tags: Vec<&str> = stdout.lines().collect();
-
-        // Look for tags matching this module
-        for tag in &tags {
-            // Check for prefixed tags (e.g., "module/v1.0.0")
-            if !prefix.is_empty() && tag.starts_with(&format!("{}/v", prefix)) {
-                return Ok(Some(tag.to_string()));
-            }
-            // Check for simple version tags
-            if tag.starts_with('v') && semver::Version::parse(&tag[1..]).is_ok() {
-                return Ok(Some(tag.to_string()));
-            }
-        }
+        let latest = stdout
+            .lines()
+            .filter_map(|tag| {
+                let version = version_from_tag(tag, &prefix)?;
+                let parsed = semver::Version::parse(&version).ok()?;
+                Some((parsed, tag.to_string()))
+            })
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, tag)| tag);
 
-        Ok(None)
+        debug!(adapter = "go", prefix = %prefix, latest = ?latest, "found latest module tag");
+        Ok(latest)
     }
 
     /// Create a git tag for this module version
-    fn create_tag(&self, path: &Path, module_path: &str, version: &str) -> Result<String> {
-        // Determine tag format
-        let tag = if module_path.contains('/') {
-            // Use prefixed tag for submodules
-            let prefix = module_path.rsplit('/').next().unwrap_or("");
-            if prefix.is_empty() || prefix == module_path {
-                format!("v{}", version)
-            } else {
-                format!("{}/v{}", prefix, version)
-            }
-        } else {
-            format!("v{}", version)
-        };
+    fn create_tag(&self, path: &Path, version: &str) -> Result<String> {
+        let tag = module_tag(&self.tag_prefix(path)?, version);
 
         // Create the tag
         let output = Command::new("git")
@@ -113,6 +112,50 @@ impl GoAdapter {
     }
 }
 
+/// Tag prefix for a module directory relative to the repository root.
+///
+/// Go expects nested modules to be tagged `<dir>/vX.Y.Z`, where `<dir>` is
+/// the module's directory relative to the repository root.
+fn module_tag_prefix(repo_root: &Path, module_dir: &Path) -> String {
+    let canonical = |p: &Path| p.canonicalize().unwrap_or_else(|_| p.to_path_buf());
+    let root = canonical(repo_root);
+    let dir = canonical(module_dir);
+
+    dir.strip_prefix(&root)
+        .map(|rel| {
+            rel.components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .unwrap_or_default()
+}
+
+/// Tag name for a module version
+fn module_tag(prefix: &str, version: &str) -> String {
+    if prefix.is_empty() {
+        format!("v{}", version)
+    } else {
+        format!("{}/v{}", prefix, version)
+    }
+}
+
+/// Extract the version from a tag if it belongs to the module with `prefix`
+fn version_from_tag(tag: &str, prefix: &str) -> Option<String> {
+    let rest = if prefix.is_empty() {
+        tag
+    } else {
+        tag.strip_prefix(prefix)?.strip_prefix('/')?
+    };
+
+    // Reject tags of other (nested) modules
+    if rest.contains('/') {
+        return None;
+    }
+
+    rest.strip_prefix('v').map(str::to_string)
+}
+
 impl Default for GoAdapter {
     fn default() -> Self {
         Self::new()
@@ -138,24 +181,27 @@ impl PackageAdapter for GoAdapter {
         &["go.mod"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[
+            RequiredTool::new("go", &["version"], "Install Go from https://go.dev/dl"),
+            RequiredTool::new(
+                "git",
+                &["--version"],
+                "Install git from https://git-scm.com",
+            ),
+        ];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let manifest_path = self.manifest_path(path);
         let gomod = GoMod::load_from_path(&manifest_path)?;
 
         // Get version from git tags
+        let prefix = self.tag_prefix(path)?;
         let version = self
-            .get_latest_tag(path, &gomod.module)?
-            .map(|t| {
-                // Strip prefix if present
-                if let Some(v) = t.strip_prefix(&format!(
-                    "{}/",
-                    gomod.module.rsplit('/').next().unwrap_or("")
-                )) {
-                    v.strip_prefix('v').unwrap_or(v).to_string()
-                } else {
-                    t.strip_prefix('v').unwrap_or(&t).to_string()
-                }
-            })
+            .get_latest_tag(path)?
+            .and_then(|t| version_from_tag(&t, &prefix))
             .unwrap_or_else(|| "0.0.0".to_string());
 
         Ok(PackageInfo {
@@ -176,10 +222,12 @@ impl PackageAdapter for GoAdapter {
     fn set_version(&self, path: &Path, version: &str) -> Result<()> {
         info!(adapter = "go", version, path = %path.display(), "setting version");
         let manifest_path = self.manifest_path(path);
-        let gomod = GoMod::load_from_path(&manifest_path)?;
+        if !manifest_path.exists() {
+            return Err(AdapterError::ManifestNotFound(manifest_path).into());
+        }
 
         // For Go, "setting version" means creating a git tag
-        self.create_tag(path, &gomod.module, version)?;
+        self.create_tag(path, version)?;
 
         Ok(())
     }
@@ -324,6 +372,14 @@ impl PackageAdapter for GoAdapter {
         Ok(output.map(|o| o.status.success()).unwrap_or(false))
     }
 
+    fn supports_fmt(&self) -> bool {
+        true
+    }
+
+    fn supports_lint(&self) -> bool {
+        true
+    }
+
     fn fmt(&self, path: &Path, check: bool) -> Result<()> {
         if check {
             // gofmt -l lists files that differ from gofmt's formatting
@@ -500,6 +556,101 @@ mod tests {
         assert_eq!(info.package_type, "go");
     }
 
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+    }
+
+    /// Repo with a root module and a nested module in `submodule/`
+    fn setup_nested_modules() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q"]);
+        git(root, &["config", "user.name", "Test"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+
+        std::fs::write(
+            root.join("go.mod"),
+            "module github.com/example/repo\n\ngo 1.21\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("submodule")).unwrap();
+        std::fs::write(
+            root.join("submodule/go.mod"),
+            "module github.com/example/repo/submodule\n\ngo 1.21\n",
+        )
+        .unwrap();
+
+        git(root, &["add", "."]);
+        git(root, &["commit", "-q", "-m", "initial"]);
+        temp
+    }
+
+    #[test]
+    fn test_module_tag_prefix() {
+        let root = Path::new("/repo");
+        assert_eq!(module_tag_prefix(root, root), "");
+        assert_eq!(
+            module_tag_prefix(root, &root.join("submodule")),
+            "submodule"
+        );
+        assert_eq!(
+            module_tag_prefix(root, &root.join("libs/nested")),
+            "libs/nested"
+        );
+    }
+
+    #[test]
+    fn test_version_from_tag() {
+        assert_eq!(version_from_tag("v1.2.3", ""), Some("1.2.3".to_string()));
+        assert_eq!(version_from_tag("submodule/v1.2.3", ""), None);
+        assert_eq!(
+            version_from_tag("submodule/v1.2.3", "submodule"),
+            Some("1.2.3".to_string())
+        );
+        assert_eq!(version_from_tag("v1.2.3", "submodule"), None);
+        assert_eq!(
+            version_from_tag("other/submodule/v1.0.0", "submodule"),
+            None
+        );
+        assert_eq!(
+            version_from_tag("submodule/nested/v1.0.0", "submodule"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_nested_module_tags() {
+        let adapter = GoAdapter::new();
+        let temp = setup_nested_modules();
+        let root = temp.path();
+        let submodule = root.join("submodule");
+
+        adapter.set_version(root, "1.0.0").unwrap();
+        adapter.set_version(&submodule, "1.2.3").unwrap();
+
+        let output = Command::new("git")
+            .args(["tag", "-l"])
+            .current_dir(root)
+            .output()
+            .unwrap();
+        let tags = String::from_utf8_lossy(&output.stdout);
+        assert!(tags.lines().any(|t| t == "v1.0.0"));
+        assert!(tags.lines().any(|t| t == "submodule/v1.2.3"));
+
+        assert_eq!(adapter.get_version(root).unwrap(), "1.0.0");
+        assert_eq!(adapter.get_version(&submodule).unwrap(), "1.2.3");
+
+        // A newer root tag doesn't leak into the nested module
+        adapter.set_version(root, "2.0.0").unwrap();
+        assert_eq!(adapter.get_version(root).unwrap(), "2.0.0");
+        assert_eq!(adapter.get_version(&submodule).unwrap(), "1.2.3");
+    }
+
     #[test]
     fn test_manifest_names() {
         let adapter = GoAdapter::new();
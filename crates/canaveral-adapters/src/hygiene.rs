@@ -0,0 +1,370 @@
+//! Formatting and lint checks across every package in a workspace
+//!
+//! Before a release, each package is checked with its own adapter's
+//! formatter (in check mode) and linter, e.g. `cargo fmt`/`clippy` for Rust
+//! and `ruff` for Python. Results are collected into one [`HygieneReport`]
+//! that passes only when no package failed a check.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use tracing::{debug, info, instrument};
+
+use canaveral_core::error::Result;
+use canaveral_core::monorepo::DiscoveredPackage;
+
+use crate::registry::AdapterRegistry;
+use crate::traits::PackageAdapter;
+
+/// Default number of packages checked at once
+pub const DEFAULT_CHECK_CONCURRENCY: usize = 4;
+
+/// Outcome of one check on one package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CheckStatus {
+    /// The check ran and passed
+    Passed,
+    /// The check ran and failed, with the tool's output
+    Failed(String),
+    /// The package's adapter has no such check, or no adapter was found
+    Skipped,
+}
+
+impl CheckStatus {
+    fn from_result(result: Result<()>) -> Self {
+        match result {
+            Ok(()) => Self::Passed,
+            Err(e) => Self::Failed(e.to_string()),
+        }
+    }
+
+    /// Whether the check ran and failed
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Failed(_))
+    }
+}
+
+/// Format and lint results for a single package
+#[derive(Debug, Clone)]
+pub struct PackageHygiene {
+    /// Package name
+    pub name: String,
+    /// Package directory
+    pub path: PathBuf,
+    /// Name of the adapter that checked the package, if one was found
+    pub adapter: Option<&'static str>,
+    /// Result of the formatting check
+    pub fmt: CheckStatus,
+    /// Result of the linter
+    pub lint: CheckStatus,
+}
+
+impl PackageHygiene {
+    /// Whether neither check failed
+    pub fn passed(&self) -> bool {
+        !self.fmt.is_failure() && !self.lint.is_failure()
+    }
+}
+
+/// Format and lint results across a workspace
+#[derive(Debug, Clone, Default)]
+pub struct HygieneReport {
+    /// Per-package results, in the order the packages were given
+    pub packages: Vec<PackageHygiene>,
+}
+
+impl HygieneReport {
+    /// Whether no package failed a check
+    pub fn passed(&self) -> bool {
+        self.packages.iter().all(PackageHygiene::passed)
+    }
+
+    /// Packages that failed at least one check
+    pub fn failures(&self) -> impl Iterator<Item = &PackageHygiene> {
+        self.packages.iter().filter(|p| !p.passed())
+    }
+
+    /// Failure diagnostics as `package (check): output` lines
+    pub fn diagnostics(&self) -> Vec<String> {
+        let mut diagnostics = Vec::new();
+        for package in &self.packages {
+            for (check, status) in [("fmt", &package.fmt), ("lint", &package.lint)] {
+                if let CheckStatus::Failed(output) = status {
+                    diagnostics.push(format!("{} ({}): {}", package.name, check, output.trim()));
+                }
+            }
+        }
+        diagnostics
+    }
+}
+
+impl AdapterRegistry {
+    /// Detect every package under `root` and run its format and lint checks
+    ///
+    /// At most `concurrency` packages are checked at once.
+    #[instrument(skip(self), fields(root = %root.display()))]
+    pub fn check_workspace(&self, root: &Path, concurrency: usize) -> Result<HygieneReport> {
+        let packages = self.discover_packages(root)?;
+        Ok(self.check_packages(&packages, concurrency))
+    }
+
+    /// Run the format and lint checks of already-discovered packages
+    ///
+    /// Formatting is checked without changing files. A failing package
+    /// doesn't stop the others.
+    pub fn check_packages(
+        &self,
+        packages: &[DiscoveredPackage],
+        concurrency: usize,
+    ) -> HygieneReport {
+        let concurrency = concurrency.max(1);
+        info!(
+            packages = packages.len(),
+            concurrency, "checking workspace formatting and lints"
+        );
+
+        let queue = Mutex::new(packages.iter());
+        let results: Mutex<HashMap<&str, PackageHygiene>> = Mutex::new(HashMap::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..concurrency.min(packages.len()) {
+                scope.spawn(|| loop {
+                    let Some(pkg) = queue.lock().unwrap_or_else(|e| e.into_inner()).next() else {
+                        break;
+                    };
+                    let result = check_one(self.adapter_for(pkg).as_deref(), pkg);
+                    results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(pkg.name.as_str(), result);
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        let report = HygieneReport {
+            packages: packages
+                .iter()
+                .filter_map(|p| results.remove(p.name.as_str()))
+                .collect(),
+        };
+        info!(
+            checked = report.packages.len(),
+            failed = report.failures().count(),
+            "workspace checks finished"
+        );
+        report
+    }
+}
+
+/// Run the checks the adapter supports on one package
+fn check_one(adapter: Option<&dyn PackageAdapter>, pkg: &DiscoveredPackage) -> PackageHygiene {
+    let mut result = PackageHygiene {
+        name: pkg.name.clone(),
+        path: pkg.path.clone(),
+        adapter: adapter.map(|a| a.name()),
+        fmt: CheckStatus::Skipped,
+        lint: CheckStatus::Skipped,
+    };
+    let Some(adapter) = adapter else {
+        debug!(package = %pkg.name, "no adapter, skipping checks");
+        return result;
+    };
+
+    if adapter.supports_fmt() {
+        result.fmt = CheckStatus::from_result(adapter.fmt(&pkg.path, true));
+    }
+    if adapter.supports_lint() {
+        result.lint = CheckStatus::from_result(adapter.lint(&pkg.path));
+    }
+    debug!(package = %pkg.name, fmt = ?result.fmt, lint = ?result.lint, "checked package");
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use canaveral_core::error::AdapterError;
+    use canaveral_core::types::PackageInfo;
+
+    use crate::publish::PublishOptions;
+
+    /// Adapter whose checks fail for configured packages
+    struct MockAdapter {
+        name: &'static str,
+        lints: bool,
+        unformatted: Vec<&'static str>,
+        lint_errors: Vec<&'static str>,
+        running: Arc<AtomicUsize>,
+        max_running: Arc<AtomicUsize>,
+    }
+
+    impl MockAdapter {
+        fn new(name: &'static str, lints: bool) -> Self {
+            Self {
+                name,
+                lints,
+                unformatted: Vec::new(),
+                lint_errors: Vec::new(),
+                running: Arc::new(AtomicUsize::new(0)),
+                max_running: Arc::new(AtomicUsize::new(0)),
+            }
+        }
+
+        fn run(&self, path: &Path, failing: &[&str], message: &str) -> Result<()> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            let package = path.file_name().unwrap().to_string_lossy();
+            if failing.contains(&package.as_ref()) {
+                return Err(AdapterError::CommandFailed {
+                    command: message.to_string(),
+                    reason: format!("{} in {}", message, package),
+                }
+                .into());
+            }
+            Ok(())
+        }
+    }
+
+    impl PackageAdapter for MockAdapter {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        fn default_registry(&self) -> &'static str {
+            "https://registry.invalid"
+        }
+
+        fn detect(&self, _path: &Path) -> bool {
+            false
+        }
+
+        fn get_info(&self, path: &Path) -> Result<PackageInfo> {
+            Ok(PackageInfo::new(
+                "mock",
+                "1.0.0",
+                self.name,
+                path.join("mock.toml"),
+            ))
+        }
+
+        fn get_version(&self, _path: &Path) -> Result<String> {
+            Ok("1.0.0".to_string())
+        }
+
+        fn set_version(&self, _path: &Path, _version: &str) -> Result<()> {
+            Ok(())
+        }
+
+        fn publish_with_options(&self, _path: &Path, _options: &PublishOptions) -> Result<()> {
+            Ok(())
+        }
+
+        fn manifest_names(&self) -> &[&str] {
+            &["mock.toml"]
+        }
+
+        fn supports_fmt(&self) -> bool {
+            true
+        }
+
+        fn supports_lint(&self) -> bool {
+            self.lints
+        }
+
+        fn fmt(&self, path: &Path, check: bool) -> Result<()> {
+            assert!(check, "hygiene checks must not rewrite files");
+            self.run(path, &self.unformatted, "unformatted")
+        }
+
+        fn lint(&self, path: &Path) -> Result<()> {
+            self.run(path, &self.lint_errors, "lint error")
+        }
+    }
+
+    fn package(name: &str, package_type: &str) -> DiscoveredPackage {
+        DiscoveredPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            path: PathBuf::from("/workspace").join(name),
+            manifest_path: PathBuf::from("/workspace").join(name).join("mock.toml"),
+            package_type: package_type.to_string(),
+            private: false,
+            workspace_dependencies: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_mixed_results() {
+        let mut rust = MockAdapter::new("rust", true);
+        rust.unformatted = vec!["core"];
+        let mut python = MockAdapter::new("python", true);
+        python.lint_errors = vec!["scripts"];
+        let mut registry = AdapterRegistry::empty();
+        registry.register(rust);
+        registry.register(python);
+        registry.register(MockAdapter::new("docs", false));
+
+        let packages = vec![
+            package("core", "rust"),
+            package("cli", "rust"),
+            package("scripts", "python"),
+            package("site", "docs"),
+            package("assets", "unknown"),
+        ];
+        let report = registry.check_packages(&packages, 2);
+
+        assert!(!report.passed());
+        let names: Vec<&str> = report.packages.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, ["core", "cli", "scripts", "site", "assets"]);
+        assert_eq!(
+            report
+                .failures()
+                .map(|p| p.name.as_str())
+                .collect::<Vec<_>>(),
+            ["core", "scripts"]
+        );
+
+        let core = &report.packages[0];
+        assert_eq!(core.adapter, Some("rust"));
+        assert!(core.fmt.is_failure());
+        assert_eq!(core.lint, CheckStatus::Passed);
+        // Adapters without a linter only get the formatting check
+        assert_eq!(report.packages[3].fmt, CheckStatus::Passed);
+        assert_eq!(report.packages[3].lint, CheckStatus::Skipped);
+        // Packages without an adapter are skipped, not failed
+        assert_eq!(report.packages[4].adapter, None);
+        assert!(report.packages[4].passed());
+
+        let diagnostics = report.diagnostics();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(diagnostics[0].starts_with("core (fmt): "));
+        assert!(diagnostics[0].contains("unformatted in core"));
+        assert!(diagnostics[1].starts_with("scripts (lint): "));
+    }
+
+    #[test]
+    fn test_bounded_concurrency() {
+        let adapter = MockAdapter::new("rust", true);
+        let max_running = adapter.max_running.clone();
+        let mut registry = AdapterRegistry::empty();
+        registry.register(adapter);
+
+        let packages: Vec<DiscoveredPackage> = (0..8)
+            .map(|i| package(&format!("crate-{}", i), "rust"))
+            .collect();
+        let report = registry.check_packages(&packages, 3);
+
+        assert!(report.passed());
+        assert_eq!(report.packages.len(), 8);
+        let max = max_running.load(Ordering::SeqCst);
+        assert!((1..=3).contains(&max), "ran {} checks at once", max);
+    }
+}
@@ -1,26 +1,48 @@
 //! Canaveral Adapters - Package adapters for release management
 //!
-//! This crate provides package manager adapters for npm, Cargo, Python, Go, Maven, Docker, etc.
+//! This crate provides package manager adapters for npm, Cargo, Python, Go, Maven, Docker, etc.,
+//! plus a generic regex-based adapter for versions in arbitrary files.
 
+pub mod batch;
 pub mod cargo;
+pub mod cocoapods;
 pub mod credentials;
 pub mod detector;
 pub mod docker;
+pub mod generic;
 pub mod go;
+pub mod hygiene;
+pub mod lock;
 pub mod manifest;
 pub mod maven;
+pub mod mirror;
 pub mod npm;
 pub mod publish;
+pub mod publish_files;
+pub mod published;
 pub mod python;
 pub mod registry;
+#[cfg(feature = "sbom")]
+pub mod sbom;
 mod traits;
 
+pub use batch::{PublishOutcome, PublishStatus, WorkspacePublishOptions};
+pub use cocoapods::CocoaPodsAdapter;
 pub use credentials::{Credential, CredentialProvider};
 pub use detector::{detect_packages, detect_packages_recursive};
 pub use docker::DockerAdapter;
+pub use generic::GenericAdapter;
 pub use go::GoAdapter;
+pub use hygiene::{CheckStatus, HygieneReport, PackageHygiene, DEFAULT_CHECK_CONCURRENCY};
+pub use lock::{with_manifest_lock, ManifestLock, DEFAULT_LOCK_TIMEOUT};
 pub use manifest::ManifestFile;
 pub use maven::MavenAdapter;
-pub use publish::{PublishAccess, PublishOptions, ValidationResult};
+pub use mirror::{MirroredAdapter, VersionMirror};
+pub use publish::{PublishAccess, PublishOptions, ValidationResult, ValidationSummary};
+pub use publish_files::{
+    check_export_ignored, check_publish_files, default_secret_patterns, find_secret_files,
+    ExportIgnore, DEFAULT_SECRET_PATTERNS, EXPORT_IGNORE_ATTRIBUTE,
+};
+pub use published::{latest_release, RegistryProtocol, RegistryVersions};
 pub use registry::AdapterRegistry;
-pub use traits::PackageAdapter;
+pub use traits::{PackageAdapter, RequiredTool};
@@ -0,0 +1,168 @@
+//! Advisory locking of manifests during version writes
+//!
+//! Adapters read, modify and write their manifest in `set_version`. Two
+//! processes (or parallel bump tasks) doing that at once can interleave and
+//! leave a mangled file, so the write runs under an exclusive `flock` on the
+//! manifest itself. The lock is advisory: it only excludes other writers
+//! that lock too, which includes every canaveral adapter.
+
+use std::fs::{File, OpenOptions};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use fs2::FileExt;
+use tracing::debug;
+
+use canaveral_core::error::{AdapterError, Result};
+
+/// How long to wait for another writer before giving up
+pub const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Delay between attempts to take a held lock
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// An exclusive lock on a manifest, released when dropped
+#[derive(Debug)]
+pub struct ManifestLock {
+    file: File,
+    path: PathBuf,
+}
+
+impl ManifestLock {
+    /// Lock the manifest at `path`, waiting up to `timeout` for other writers
+    ///
+    /// Fails with [`AdapterError::ManifestLocked`] if the lock is still held
+    /// when the timeout runs out, or [`AdapterError::ManifestNotFound`] if
+    /// the manifest doesn't exist.
+    pub fn acquire(path: &Path, timeout: Duration) -> Result<Self> {
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+
+        let started = Instant::now();
+        loop {
+            match file.try_lock_exclusive() {
+                Ok(()) => break,
+                Err(e) if e.kind() == fs2::lock_contended_error().kind() => {
+                    if started.elapsed() >= timeout {
+                        return Err(AdapterError::ManifestLocked {
+                            path: path.to_path_buf(),
+                            timeout,
+                        }
+                        .into());
+                    }
+                    std::thread::sleep(POLL_INTERVAL);
+                }
+                Err(e) => return Err(AdapterError::Io(e).into()),
+            }
+        }
+
+        debug!(path = %path.display(), waited_ms = started.elapsed().as_millis() as u64, "locked manifest");
+        Ok(Self {
+            file,
+            path: path.to_path_buf(),
+        })
+    }
+
+    /// The locked manifest
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ManifestLock {
+    fn drop(&mut self) {
+        // Closing the file releases the lock too; unlock explicitly so it is
+        // released before the drop returns
+        let _ = FileExt::unlock(&self.file);
+    }
+}
+
+/// Run `write` while holding the lock on the manifest at `path`
+///
+/// Waits up to [`DEFAULT_LOCK_TIMEOUT`]. A missing manifest isn't locked,
+/// leaving `write` to report it.
+pub fn with_manifest_lock<T>(path: &Path, write: impl FnOnce() -> Result<T>) -> Result<T> {
+    if !path.exists() {
+        return write();
+    }
+    let _lock = ManifestLock::acquire(path, DEFAULT_LOCK_TIMEOUT)?;
+    write()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::npm::{NpmAdapter, PackageJson};
+    use crate::traits::PackageAdapter;
+    use canaveral_core::error::CanaveralError;
+    use std::sync::{Arc, Barrier};
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_held_lock_times_out() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("package.json");
+        std::fs::write(&path, "{}").unwrap();
+
+        let held = ManifestLock::acquire(&path, DEFAULT_LOCK_TIMEOUT).unwrap();
+        let err = ManifestLock::acquire(&path, Duration::from_millis(50)).unwrap_err();
+        assert!(matches!(
+            err,
+            CanaveralError::Adapter(AdapterError::ManifestLocked { .. })
+        ));
+        assert!(err.to_string().contains("Manifest is locked"));
+
+        drop(held);
+        assert!(ManifestLock::acquire(&path, Duration::from_millis(50)).is_ok());
+    }
+
+    #[test]
+    fn test_missing_manifest() {
+        let temp = TempDir::new().unwrap();
+        let err = ManifestLock::acquire(&temp.path().join("Cargo.toml"), DEFAULT_LOCK_TIMEOUT)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            CanaveralError::Adapter(AdapterError::ManifestNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_racing_bumps_leave_consistent_manifest() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "demo", "version": "1.0.0", "description": "A package with enough content to make a torn write visible"}"#,
+        )
+        .unwrap();
+
+        let barrier = Arc::new(Barrier::new(2));
+        let handles: Vec<_> = ["1.1.0", "20.0.0-beta.1"]
+            .into_iter()
+            .map(|version| {
+                let dir = temp.path().to_path_buf();
+                let barrier = barrier.clone();
+                std::thread::spawn(move || {
+                    barrier.wait();
+                    for _ in 0..50 {
+                        NpmAdapter::new().set_version(&dir, version).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let manifest = PackageJson::load_from_path(&temp.path().join("package.json")).unwrap();
+        assert!(
+            ["1.1.0", "20.0.0-beta.1"].contains(&manifest.version.as_str()),
+            "{}",
+            manifest.version
+        );
+        assert_eq!(manifest.name, "demo");
+    }
+}
@@ -13,18 +13,32 @@ use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
+use crate::lock::{ManifestLock, DEFAULT_LOCK_TIMEOUT};
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::traits::{PackageAdapter, RequiredTool};
 
 pub use pom::PomXml;
 
 /// Maven package adapter
-pub struct MavenAdapter;
+pub struct MavenAdapter {
+    /// Use `versions:set` for multi-module reactors instead of editing pom.xml
+    use_versions_plugin: bool,
+}
 
 impl MavenAdapter {
     /// Create a new Maven adapter
     pub fn new() -> Self {
-        Self
+        Self {
+            use_versions_plugin: false,
+        }
+    }
+
+    /// Enable or disable `mvn versions:set` for multi-module projects
+    ///
+    /// When disabled (the default), only the root pom.xml is edited directly.
+    pub fn with_versions_plugin(mut self, enabled: bool) -> Self {
+        self.use_versions_plugin = enabled;
+        self
     }
 
     /// Get the pom.xml path
@@ -43,6 +57,55 @@ impl MavenAdapter {
             "mvn"
         }
     }
+
+    /// Maven invocations needed to bump every module of a reactor
+    ///
+    /// Returns `None` when the project should be updated by editing the
+    /// root pom.xml directly (single-module project or plugin disabled).
+    fn versions_plugin_commands(&self, pom: &PomXml, version: &str) -> Option<Vec<Vec<String>>> {
+        if !self.use_versions_plugin || !pom.is_multi_module() {
+            return None;
+        }
+
+        Some(vec![
+            vec![
+                "versions:set".to_string(),
+                format!("-DnewVersion={}", version),
+                "-DprocessAllModules=true".to_string(),
+                "-DgenerateBackupPoms=false".to_string(),
+                "-B".to_string(),
+            ],
+            vec!["versions:commit".to_string(), "-B".to_string()],
+        ])
+    }
+
+    /// Run a Maven command, failing on a non-zero exit status
+    fn run_maven(&self, path: &Path, args: &[String]) -> Result<()> {
+        let mvn = self.maven_cmd(path);
+        let command = format!("{} {}", mvn, args.join(" "));
+        debug!(adapter = "maven", command = %command, "running maven");
+
+        let output = Command::new(mvn)
+            .args(args)
+            .current_dir(path)
+            .output()
+            .map_err(|e| AdapterError::CommandFailed {
+                command: command.clone(),
+                reason: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let stdout = String::from_utf8_lossy(&output.stdout);
+            return Err(AdapterError::CommandFailed {
+                command,
+                reason: format!("{}\n{}", stdout, stderr),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
 }
 
 impl Default for MavenAdapter {
@@ -70,6 +133,15 @@ impl PackageAdapter for MavenAdapter {
         &["pom.xml"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[RequiredTool::new(
+            "mvn",
+            &["--version"],
+            "Install Maven from https://maven.apache.org",
+        )];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let manifest_path = self.manifest_path(path);
         let pom = PomXml::load_from_path(&manifest_path)?;
@@ -110,13 +182,32 @@ impl PackageAdapter for MavenAdapter {
     fn set_version(&self, path: &Path, version: &str) -> Result<()> {
         info!(adapter = "maven", version, path = %path.display(), "setting version");
         let manifest_path = self.manifest_path(path);
-        PomXml::update_version(&manifest_path, version)
+        let pom = PomXml::load_from_path(&manifest_path)?;
+
+        // versions:set rewrites the module poms too; the root pom lock
+        // still keeps two canaveral runs from updating the reactor at once
+        let _lock = ManifestLock::acquire(&manifest_path, DEFAULT_LOCK_TIMEOUT)?;
+        match self.versions_plugin_commands(&pom, version) {
+            Some(commands) => {
+                debug!(
+                    adapter = "maven",
+                    modules = pom.modules.len(),
+                    "updating reactor with versions:set"
+                );
+                for args in &commands {
+                    self.run_maven(path, args)?;
+                }
+                Ok(())
+            }
+            None => PomXml::update_version(&manifest_path, version),
+        }
     }
 
     fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
         info!(adapter = "maven", path = %path.display(), dry_run = options.dry_run, "publishing package");
         let mvn = self.maven_cmd(path);
         let mut cmd = Command::new(mvn);
+        cmd.envs(&options.env);
         cmd.current_dir(path);
 
         if options.dry_run {
@@ -135,7 +226,7 @@ impl PackageAdapter for MavenAdapter {
             }
 
             // Skip GPG signing if specified
-            if options.extra.get("skip_gpg").is_some_and(|v| v == "true") {
+            if options.extra_flag("skip_gpg") {
                 cmd.arg("-Dgpg.skip=true");
             }
         }
@@ -406,6 +497,57 @@ mod tests {
         assert_eq!(info.package_type, "maven");
     }
 
+    #[test]
+    fn test_versions_plugin_for_multi_module() {
+        let adapter = MavenAdapter::new().with_versions_plugin(true);
+        let pom = PomXml::parse(
+            r#"<project>
+    <groupId>com.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.0.0</version>
+    <packaging>pom</packaging>
+    <modules>
+        <module>core</module>
+        <module>app</module>
+    </modules>
+</project>"#,
+        )
+        .unwrap();
+
+        let commands = adapter.versions_plugin_commands(&pom, "1.1.0").unwrap();
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands[0][0], "versions:set");
+        assert!(commands[0].contains(&"-DnewVersion=1.1.0".to_string()));
+        assert!(commands[0].contains(&"-DprocessAllModules=true".to_string()));
+        assert_eq!(commands[1][0], "versions:commit");
+
+        let disabled = MavenAdapter::new();
+        assert!(disabled.versions_plugin_commands(&pom, "1.1.0").is_none());
+    }
+
+    #[test]
+    fn test_single_module_edits_pom_directly() {
+        let adapter = MavenAdapter::new();
+        let temp = TempDir::new().unwrap();
+        let pom_path = temp.path().join("pom.xml");
+
+        std::fs::write(
+            &pom_path,
+            r#"<project>
+    <groupId>com.example</groupId>
+    <artifactId>my-lib</artifactId>
+    <version>1.0.0</version>
+</project>"#,
+        )
+        .unwrap();
+
+        let pom = PomXml::load_from_path(&pom_path).unwrap();
+        assert!(adapter.versions_plugin_commands(&pom, "1.1.0").is_none());
+
+        adapter.set_version(temp.path(), "1.1.0").unwrap();
+        assert_eq!(adapter.get_version(temp.path()).unwrap(), "1.1.0");
+    }
+
     #[test]
     fn test_manifest_names() {
         let adapter = MavenAdapter::new();
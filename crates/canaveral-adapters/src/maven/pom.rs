@@ -31,6 +31,8 @@ pub struct PomXml {
     pub scm: Option<Scm>,
     /// Parent POM
     pub parent: Option<Parent>,
+    /// Reactor modules declared in `<modules>`
+    pub modules: Vec<String>,
 }
 
 /// License information
@@ -137,6 +139,11 @@ impl PomXml {
             tag: Self::extract_element(&scm_block, "tag"),
         });
 
+        // Parse reactor modules
+        let modules = Self::extract_block(content, "modules")
+            .map(|b| Self::parse_modules(&b))
+            .unwrap_or_default();
+
         Ok(PomXml {
             group_id,
             artifact_id,
@@ -149,9 +156,15 @@ impl PomXml {
             developers,
             scm,
             parent,
+            modules,
         })
     }
 
+    /// Whether this pom is the parent of a multi-module reactor
+    pub fn is_multi_module(&self) -> bool {
+        !self.modules.is_empty()
+    }
+
     /// Extract a simple element value
     fn extract_element(content: &str, element: &str) -> Option<String> {
         let start_tag = format!("<{}>", element);
@@ -233,6 +246,27 @@ impl PomXml {
         developers
     }
 
+    /// Parse module paths from a modules block
+    fn parse_modules(block: &str) -> Vec<String> {
+        let mut modules = Vec::new();
+
+        let mut pos = 0;
+        while let Some(start) = block[pos..].find("<module>") {
+            let value_start = pos + start + "<module>".len();
+            if let Some(end) = block[value_start..].find("</module>") {
+                let module = block[value_start..value_start + end].trim();
+                if !module.is_empty() {
+                    modules.push(module.to_string());
+                }
+                pos = value_start + end;
+            } else {
+                break;
+            }
+        }
+
+        modules
+    }
+
     /// Update the version in a pom.xml file
     pub fn update_version(path: &Path, new_version: &str) -> Result<()> {
         let content = std::fs::read_to_string(path).map_err(|e| {
@@ -433,6 +467,25 @@ mod tests {
         assert_eq!(pom.developers[0].name, Some("John Doe".to_string()));
     }
 
+    #[test]
+    fn test_parse_with_modules() {
+        let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<project>
+    <groupId>com.example</groupId>
+    <artifactId>parent</artifactId>
+    <version>1.0.0</version>
+    <packaging>pom</packaging>
+    <modules>
+        <module>core</module>
+        <module>cli</module>
+    </modules>
+</project>"#;
+
+        let pom = PomXml::parse(content).unwrap();
+        assert!(pom.is_multi_module());
+        assert_eq!(pom.modules, vec!["core", "cli"]);
+    }
+
     #[test]
     fn test_update_version() {
         let temp = TempDir::new().unwrap();
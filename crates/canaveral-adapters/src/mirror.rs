@@ -0,0 +1,456 @@
+//! Version mirror files
+//!
+//! Some packages repeat their version outside the manifest, in a
+//! `src/version.ts` constant or a second JSON file. [`MirroredAdapter`]
+//! wraps a package's adapter so `set_version` writes those mirrors in
+//! lockstep with the manifest, and refuses to bump when they have already
+//! drifted from it.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use regex::Regex;
+use tracing::{debug, info};
+
+use canaveral_core::config::VersionMirrorConfig;
+use canaveral_core::error::{AdapterError, Result};
+use canaveral_core::types::PackageInfo;
+
+use crate::credentials::CredentialProvider;
+use crate::generic::GenericAdapter;
+use crate::lock::with_manifest_lock;
+use crate::publish::{PublishOptions, ValidationResult};
+use crate::traits::{PackageAdapter, RequiredTool};
+
+/// A secondary file holding the package version
+#[derive(Debug, Clone)]
+pub enum VersionMirror {
+    /// Version captured by a regex in a text file
+    Pattern(GenericAdapter),
+    /// Version at a dotted path in a JSON file
+    Json {
+        /// JSON file, relative to the package directory
+        file: PathBuf,
+        /// Object keys leading to the version string
+        path: Vec<String>,
+    },
+}
+
+impl VersionMirror {
+    /// Mirror read and written with a regex capturing the version
+    pub fn pattern(file: impl Into<PathBuf>, pattern: &str) -> Result<Self> {
+        Ok(Self::Pattern(GenericAdapter::new(file, pattern)?))
+    }
+
+    /// Mirror at a dotted `path` such as `expo.version` in a JSON file
+    pub fn json(file: impl Into<PathBuf>, path: &str) -> Self {
+        Self::Json {
+            file: file.into(),
+            path: path.split('.').map(str::to_string).collect(),
+        }
+    }
+
+    /// Build a mirror from its configuration
+    pub fn from_config(config: &VersionMirrorConfig) -> Result<Self> {
+        match (&config.pattern, &config.json_path) {
+            (Some(pattern), None) => {
+                let mirror = GenericAdapter::new(&config.file, pattern)?;
+                Ok(Self::Pattern(match &config.template {
+                    Some(template) => mirror.with_template(template),
+                    None => mirror,
+                }))
+            }
+            (None, Some(path)) => Ok(Self::json(&config.file, path)),
+            _ => Err(AdapterError::ManifestParseError(format!(
+                "version mirror {} needs exactly one of pattern or json_path",
+                config.file.display()
+            ))
+            .into()),
+        }
+    }
+
+    /// The mirror file, relative to the package directory
+    pub fn file(&self) -> &Path {
+        match self {
+            Self::Pattern(adapter) => adapter.file(),
+            Self::Json { file, .. } => file,
+        }
+    }
+
+    /// Read the mirrored version
+    ///
+    /// Reads under the file's lock, so a mirror shared between packages is
+    /// never seen halfway through another package's write.
+    pub fn read(&self, package: &Path) -> Result<String> {
+        with_manifest_lock(&package.join(self.file()), || match self {
+            Self::Pattern(adapter) => adapter.get_version(package),
+            Self::Json { .. } => {
+                let content = self.read_file(package)?;
+                self.json_version(&content)
+            }
+        })
+    }
+
+    /// Write `version` to the mirror
+    pub fn write(&self, package: &Path, version: &str) -> Result<()> {
+        match self {
+            Self::Pattern(adapter) => adapter.set_version(package, version),
+            Self::Json { .. } => {
+                let file = package.join(self.file());
+                with_manifest_lock(&file, || {
+                    let content = self.read_file(package)?;
+                    let updated = self.replace_json_version(&content, version)?;
+                    std::fs::write(&file, updated)?;
+                    Ok(())
+                })
+            }
+        }
+    }
+
+    fn read_file(&self, package: &Path) -> Result<String> {
+        let file = package.join(self.file());
+        if !file.exists() {
+            return Err(AdapterError::ManifestNotFound(file).into());
+        }
+        Ok(std::fs::read_to_string(file)?)
+    }
+
+    fn json_path(&self) -> String {
+        match self {
+            Self::Json { path, .. } => path.join("."),
+            Self::Pattern(_) => String::new(),
+        }
+    }
+
+    fn json_version(&self, content: &str) -> Result<String> {
+        let Self::Json { path, .. } = self else {
+            unreachable!("only JSON mirrors have a JSON version");
+        };
+        let value: serde_json::Value = serde_json::from_str(content).map_err(|e| {
+            AdapterError::ManifestParseError(format!("{}: {}", self.file().display(), e))
+        })?;
+        path.iter()
+            .try_fold(&value, |value, key| value.get(key))
+            .and_then(|value| value.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                AdapterError::ManifestParseError(format!(
+                    "no string at '{}' in {}",
+                    self.json_path(),
+                    self.file().display()
+                ))
+                .into()
+            })
+    }
+
+    /// Replace the version in place, keeping the rest of the file untouched
+    ///
+    /// The value is found textually as `"<last key>": "<current version>"`,
+    /// so formatting and key order survive. Fails if that text occurs more
+    /// than once, rather than guess which one the path means.
+    fn replace_json_version(&self, content: &str, version: &str) -> Result<String> {
+        let Self::Json { path, .. } = self else {
+            unreachable!("only JSON mirrors have a JSON version");
+        };
+        let current = self.json_version(content)?;
+        let key = path.last().map(String::as_str).unwrap_or_default();
+        let pattern = Regex::new(&format!(
+            r#""{}"\s*:\s*"({})""#,
+            regex::escape(key),
+            regex::escape(&current)
+        ))
+        .expect("escaped pattern is valid");
+
+        let matches: Vec<_> = pattern.captures_iter(content).collect();
+        let range = match matches.as_slice() {
+            [only] => only.get(1).expect("pattern has one group").range(),
+            _ => {
+                return Err(AdapterError::ManifestUpdateError(format!(
+                    "'{}' in {} is ambiguous, {} entries read \"{}\": \"{}\"",
+                    self.json_path(),
+                    self.file().display(),
+                    matches.len(),
+                    key,
+                    current
+                ))
+                .into())
+            }
+        };
+
+        let mut updated = String::with_capacity(content.len());
+        updated.push_str(&content[..range.start]);
+        updated.push_str(version);
+        updated.push_str(&content[range.end..]);
+        Ok(updated)
+    }
+}
+
+/// Adapter that bumps version mirrors along with the manifest
+///
+/// Everything except `set_version` goes straight to the wrapped adapter.
+#[derive(Clone)]
+pub struct MirroredAdapter {
+    inner: Arc<dyn PackageAdapter>,
+    mirrors: Vec<VersionMirror>,
+}
+
+impl std::fmt::Debug for MirroredAdapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MirroredAdapter")
+            .field("inner", &self.inner.name())
+            .field("mirrors", &self.mirrors)
+            .finish()
+    }
+}
+
+impl MirroredAdapter {
+    /// Wrap `inner`, keeping `mirrors` in step with its manifest
+    pub fn new(inner: Arc<dyn PackageAdapter>, mirrors: Vec<VersionMirror>) -> Self {
+        Self { inner, mirrors }
+    }
+
+    /// Wrap `inner` with the mirrors from a package's configuration
+    ///
+    /// Returns `inner` unchanged when there are none.
+    pub fn from_config(
+        inner: Arc<dyn PackageAdapter>,
+        mirrors: &[VersionMirrorConfig],
+    ) -> Result<Arc<dyn PackageAdapter>> {
+        if mirrors.is_empty() {
+            return Ok(inner);
+        }
+        let mirrors = mirrors
+            .iter()
+            .map(VersionMirror::from_config)
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Arc::new(Self::new(inner, mirrors)))
+    }
+
+    /// The mirrors kept in step with the manifest
+    pub fn mirrors(&self) -> &[VersionMirror] {
+        &self.mirrors
+    }
+
+    /// Check every mirror agrees with the manifest version
+    ///
+    /// Fails with [`AdapterError::VersionDrift`] listing each mirror that
+    /// doesn't, and returns the shared version otherwise.
+    pub fn check_drift(&self, path: &Path) -> Result<String> {
+        let expected = self.inner.get_version(path)?;
+        let mut drifted = Vec::new();
+        for mirror in &self.mirrors {
+            let found = mirror.read(path)?;
+            debug!(file = %mirror.file().display(), version = %found, "read version mirror");
+            if found != expected {
+                drifted.push(format!("  {}: {}", mirror.file().display(), found));
+            }
+        }
+        if !drifted.is_empty() {
+            return Err(AdapterError::VersionDrift {
+                expected,
+                drifted: drifted.join("\n"),
+            }
+            .into());
+        }
+        Ok(expected)
+    }
+}
+
+impl PackageAdapter for MirroredAdapter {
+    fn name(&self) -> &'static str {
+        self.inner.name()
+    }
+
+    fn default_registry(&self) -> &'static str {
+        self.inner.default_registry()
+    }
+
+    fn detect(&self, path: &Path) -> bool {
+        self.inner.detect(path)
+    }
+
+    fn get_info(&self, path: &Path) -> Result<PackageInfo> {
+        self.inner.get_info(path)
+    }
+
+    fn get_version(&self, path: &Path) -> Result<String> {
+        self.inner.get_version(path)
+    }
+
+    fn published_versions(&self, path: &Path, name: &str) -> Result<Vec<semver::Version>> {
+        self.inner.published_versions(path, name)
+    }
+
+    fn set_version(&self, path: &Path, version: &str) -> Result<()> {
+        self.check_drift(path)?;
+        info!(
+            adapter = self.inner.name(),
+            version,
+            mirrors = self.mirrors.len(),
+            "setting version with mirrors"
+        );
+        self.inner.set_version(path, version)?;
+        for mirror in &self.mirrors {
+            mirror.write(path, version)?;
+        }
+        Ok(())
+    }
+
+    fn publish(&self, path: &Path, dry_run: bool) -> Result<()> {
+        self.inner.publish(path, dry_run)
+    }
+
+    fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
+        self.inner.publish_with_options(path, options)
+    }
+
+    fn validate_publishable(&self, path: &Path) -> Result<ValidationResult> {
+        self.inner.validate_publishable(path)
+    }
+
+    fn check_auth(&self, credentials: &mut CredentialProvider) -> Result<bool> {
+        self.inner.check_auth(credentials)
+    }
+
+    fn manifest_names(&self) -> &[&str] {
+        self.inner.manifest_names()
+    }
+
+    fn supports_fmt(&self) -> bool {
+        self.inner.supports_fmt()
+    }
+
+    fn supports_lint(&self) -> bool {
+        self.inner.supports_lint()
+    }
+
+    fn required_tools(&self) -> &[RequiredTool] {
+        self.inner.required_tools()
+    }
+
+    fn fmt(&self, path: &Path, check: bool) -> Result<()> {
+        self.inner.fmt(path, check)
+    }
+
+    fn lint(&self, path: &Path) -> Result<()> {
+        self.inner.lint(path)
+    }
+
+    fn build(&self, path: &Path) -> Result<()> {
+        self.inner.build(path)
+    }
+
+    fn test(&self, path: &Path) -> Result<()> {
+        self.inner.test(path)
+    }
+
+    fn clean(&self, path: &Path) -> Result<()> {
+        self.inner.clean(path)
+    }
+
+    fn pack(&self, path: &Path) -> Result<Option<PathBuf>> {
+        self.inner.pack(path)
+    }
+
+    fn list_publish_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        self.inner.list_publish_files(path)
+    }
+
+    fn workspace_dependencies(
+        &self,
+        path: &Path,
+        known_packages: &[String],
+    ) -> Result<Vec<String>> {
+        self.inner.workspace_dependencies(path, known_packages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::npm::NpmAdapter;
+    use canaveral_core::error::CanaveralError;
+    use tempfile::TempDir;
+
+    const VERSION_TS: &str = "// Generated at release time\nexport const VERSION = \"1.0.0\";\n";
+
+    fn package(version_ts: &str) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::create_dir(temp.path().join("src")).unwrap();
+        std::fs::write(temp.path().join("src/version.ts"), version_ts).unwrap();
+        std::fs::write(
+            temp.path().join("app.json"),
+            "{\n  \"expo\": {\n    \"name\": \"demo\",\n    \"version\": \"1.0.0\"\n  }\n}\n",
+        )
+        .unwrap();
+        temp
+    }
+
+    fn adapter() -> MirroredAdapter {
+        MirroredAdapter::new(
+            Arc::new(NpmAdapter::new()),
+            vec![
+                VersionMirror::pattern("src/version.ts", r#"VERSION = "([^"]+)""#).unwrap(),
+                VersionMirror::json("app.json", "expo.version"),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_bump_updates_mirrors() {
+        let temp = package(VERSION_TS);
+        adapter().set_version(temp.path(), "1.1.0").unwrap();
+
+        assert_eq!(NpmAdapter::new().get_version(temp.path()).unwrap(), "1.1.0");
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("src/version.ts")).unwrap(),
+            "// Generated at release time\nexport const VERSION = \"1.1.0\";\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("app.json")).unwrap(),
+            "{\n  \"expo\": {\n    \"name\": \"demo\",\n    \"version\": \"1.1.0\"\n  }\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_drift_refuses_bump() {
+        let temp = package("export const VERSION = \"0.9.0\";\n");
+        let err = adapter().set_version(temp.path(), "1.1.0").unwrap_err();
+        assert!(matches!(
+            err,
+            CanaveralError::Adapter(AdapterError::VersionDrift { .. })
+        ));
+        let message = err.to_string();
+        assert!(message.contains("manifest version 1.0.0"), "{}", message);
+        assert!(message.contains("src/version.ts: 0.9.0"), "{}", message);
+
+        // Nothing was written
+        assert_eq!(NpmAdapter::new().get_version(temp.path()).unwrap(), "1.0.0");
+        assert_eq!(
+            VersionMirror::json("app.json", "expo.version")
+                .read(temp.path())
+                .unwrap(),
+            "1.0.0"
+        );
+    }
+
+    #[test]
+    fn test_from_config() {
+        let inner: Arc<dyn PackageAdapter> = Arc::new(NpmAdapter::new());
+        let wrapped = MirroredAdapter::from_config(inner.clone(), &[]).unwrap();
+        assert!(Arc::ptr_eq(&wrapped, &inner));
+
+        let both = VersionMirrorConfig {
+            file: "app.json".into(),
+            pattern: Some("(.*)".to_string()),
+            json_path: Some("version".to_string()),
+            template: None,
+        };
+        assert!(MirroredAdapter::from_config(inner, &[both]).is_err());
+    }
+}
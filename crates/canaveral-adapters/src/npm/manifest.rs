@@ -97,6 +97,30 @@ impl PackageJson {
             .map_err(|e| AdapterError::ManifestParseError(e.to_string()).into())
     }
 
+    /// Workspace packages this package depends on
+    ///
+    /// A dependency counts when it names a known package or uses the
+    /// `workspace:` protocol, in the normal, dev and peer dependencies.
+    pub fn workspace_dependencies(&self, known_packages: &[String]) -> Vec<String> {
+        let mut deps: Vec<String> = [
+            &self.dependencies,
+            &self.dev_dependencies,
+            &self.peer_dependencies,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|deps| deps.iter())
+        .filter(|(name, spec)| {
+            spec.starts_with("workspace:") || known_packages.iter().any(|k| k == *name)
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+
+        deps.sort();
+        deps.dedup();
+        deps
+    }
+
     /// Save package.json to a file path
     pub fn save_to_path(&self, path: &Path) -> Result<()> {
         let content = serde_json::to_string_pretty(self)
@@ -239,4 +263,40 @@ mod tests {
         let content = std::fs::read_to_string(&path).unwrap();
         assert!(content.contains("customField"));
     }
+
+    #[test]
+    fn test_workspace_dependencies() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("package.json");
+
+        std::fs::write(
+            &path,
+            r#"{
+                "name": "@acme/web",
+                "version": "1.0.0",
+                "dependencies": {
+                    "@acme/ui": "workspace:^",
+                    "@acme/api-client": "^2.1.0",
+                    "react": "^18.2.0"
+                },
+                "devDependencies": {
+                    "@acme/eslint-config": "workspace:*"
+                },
+                "peerDependencies": {
+                    "@acme/ui": "workspace:^"
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let pkg = PackageJson::load_from_path(&path).unwrap();
+        let known = vec!["@acme/api-client".to_string(), "@acme/ui".to_string()];
+        assert_eq!(
+            pkg.workspace_dependencies(&known),
+            ["@acme/api-client", "@acme/eslint-config", "@acme/ui"]
+        );
+        assert!(pkg
+            .workspace_dependencies(&[])
+            .contains(&"@acme/ui".to_string()));
+    }
 }
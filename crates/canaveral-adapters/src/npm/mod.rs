@@ -1,22 +1,35 @@
 //! npm package adapter
 
 mod manifest;
+mod npmrc;
+mod pack;
+mod yarnrc;
 
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::{Arc, Mutex};
 
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
 use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
+use crate::lock::with_manifest_lock;
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::published::{RegistryProtocol, RegistryVersions, NPM_REGISTRY_URL};
+use crate::traits::{PackageAdapter, RequiredTool};
 pub use manifest::PackageJson;
+pub use npmrc::Npmrc;
 
 /// npm package adapter
-pub struct NpmAdapter;
+pub struct NpmAdapter {
+    /// Registry to read published versions from instead of the configured one
+    registry_url: Option<String>,
+    /// Published versions, per registry
+    published: Mutex<HashMap<String, Arc<RegistryVersions>>>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum JsPackageManager {
@@ -29,7 +42,43 @@ enum JsPackageManager {
 impl NpmAdapter {
     /// Create a new npm adapter
     pub fn new() -> Self {
-        Self
+        Self {
+            registry_url: None,
+            published: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Read published versions from the registry at `url` instead of the
+    /// one the package publishes to
+    pub fn with_registry_url(mut self, url: impl Into<String>) -> Self {
+        self.registry_url = Some(url.into());
+        self
+    }
+
+    /// Versions published to the registry the package at `path` publishes to
+    ///
+    /// The registry, credentials and TLS settings are resolved like
+    /// [`Self::publish_args`] does, from package.json and the npm and Yarn
+    /// config.
+    fn registry_versions(&self, path: &Path, name: &str) -> Arc<RegistryVersions> {
+        let npmrc = Npmrc::load(path, |key| std::env::var(key).ok());
+        let registry = match &self.registry_url {
+            Some(url) => url.clone(),
+            None => self.publish_registry(path, name, &npmrc, None),
+        };
+        let mut published = self.published.lock().unwrap_or_else(|e| e.into_inner());
+        published
+            .entry(registry.trim_end_matches('/').to_string())
+            .or_insert_with(|| {
+                debug!(adapter = "npm", package = %name, registry = %registry, "resolved registry");
+                Arc::new(
+                    RegistryVersions::new(RegistryProtocol::Npm, registry.as_str())
+                        .with_authorization(npmrc.authorization_for(&registry))
+                        .with_ca_file(npmrc.cafile.clone())
+                        .with_strict_ssl(npmrc.strict_ssl.unwrap_or(true)),
+                )
+            })
+            .clone()
     }
 
     /// Get the package.json path
@@ -42,6 +91,65 @@ impl NpmAdapter {
         name.starts_with('@')
     }
 
+    /// Fail early if no credentials are configured for `registry`
+    ///
+    /// Credentials come from `.npmrc` or `.yarnrc.yml`; for the public
+    /// registry `NPM_TOKEN`, `NODE_AUTH_TOKEN` and `YARN_NPM_AUTH_TOKEN` are
+    /// accepted too.
+    fn ensure_registry_token(
+        &self,
+        npmrc: &Npmrc,
+        registry: &str,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<()> {
+        if npmrc.has_credentials_for(registry) {
+            return Ok(());
+        }
+        let is_default = registry.trim_end_matches('/') == self.default_registry();
+        if is_default
+            && ["NPM_TOKEN", "NODE_AUTH_TOKEN", "YARN_NPM_AUTH_TOKEN"]
+                .iter()
+                .any(|key| env(key).is_some())
+        {
+            return Ok(());
+        }
+
+        let key = registry
+            .split_once("://")
+            .map_or(registry, |(_, rest)| rest)
+            .trim_end_matches('/');
+        Err(AdapterError::AuthenticationFailed {
+            registry: registry.to_string(),
+            reason: format!(
+                "no auth token found; add `//{}/:_authToken=${{NPM_TOKEN}}` to .npmrc",
+                key
+            ),
+        }
+        .into())
+    }
+
+    /// Fail early if provenance can't be attested in this environment
+    ///
+    /// npm signs provenance with an OIDC token from the CI provider: GitHub
+    /// Actions exposes it through `ACTIONS_ID_TOKEN_REQUEST_URL`/`_TOKEN`
+    /// (with `id-token: write` permission), GitLab through `SIGSTORE_ID_TOKEN`.
+    fn ensure_provenance_env(&self, env: impl Fn(&str) -> Option<String>) -> Result<()> {
+        let set = |key: &str| env(key).is_some_and(|v| !v.is_empty());
+        if set("SIGSTORE_ID_TOKEN")
+            || (set("ACTIONS_ID_TOKEN_REQUEST_URL") && set("ACTIONS_ID_TOKEN_REQUEST_TOKEN"))
+        {
+            return Ok(());
+        }
+
+        Err(AdapterError::PublishFailed(
+            "provenance requires a CI OIDC token: ACTIONS_ID_TOKEN_REQUEST_URL is not set; \
+             on GitHub Actions add `permissions: id-token: write` to the job, on GitLab \
+             configure an `id_tokens: SIGSTORE_ID_TOKEN` entry"
+                .to_string(),
+        )
+        .into())
+    }
+
     fn detect_package_manager(&self, path: &Path) -> JsPackageManager {
         let manifest = PackageJson::load_from_path(&self.manifest_path(path)).ok();
         if let Some(manager) = manifest
@@ -81,6 +189,96 @@ impl NpmAdapter {
         }
     }
 
+    /// Registry the package at `path` publishes to
+    ///
+    /// An explicit registry wins, then `publishConfig.registry` in
+    /// package.json, then the scope's or default registry from the npm and
+    /// Yarn config (see [`Npmrc::load`]).
+    fn publish_registry(
+        &self,
+        path: &Path,
+        name: &str,
+        npmrc: &Npmrc,
+        explicit: Option<&str>,
+    ) -> String {
+        let publish_config = PackageJson::load_from_path(&self.manifest_path(path))
+            .ok()
+            .and_then(|manifest| {
+                manifest
+                    .other
+                    .get("publishConfig")
+                    .and_then(|config| config.get("registry"))
+                    .and_then(|registry| registry.as_str())
+                    .map(str::to_string)
+            });
+        explicit
+            .map(str::to_string)
+            .or(publish_config)
+            .unwrap_or_else(|| npmrc.registry_for(name))
+    }
+
+    /// Command and arguments for publishing the package at `path`
+    ///
+    /// `env` looks up environment variables (for the registry config and
+    /// the provenance check). `--registry` is only passed when one is set
+    /// in `options`; otherwise the package manager resolves it the same way.
+    fn publish_args(
+        &self,
+        path: &Path,
+        options: &PublishOptions,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<(String, Vec<String>)> {
+        let manager = self.detect_package_manager(path);
+        let (command, mut args) = self.publish_command(manager);
+
+        if options.dry_run {
+            args.push("--dry-run".to_string());
+        }
+
+        let info = self.get_info(path)?;
+
+        let npmrc = Npmrc::load(path, &env);
+        let registry = self.publish_registry(path, &info.name, &npmrc, options.registry.as_deref());
+        if !options.dry_run {
+            self.ensure_registry_token(&npmrc, &registry, &env)?;
+        }
+        debug!(adapter = "npm", package = %info.name, registry = %registry, "resolved registry");
+        if let Some(registry) = &options.registry {
+            args.extend(["--registry".to_string(), registry.clone()]);
+        }
+
+        // Access level
+        if let Some(ref access) = options.access {
+            args.extend(["--access".to_string(), access.to_string()]);
+        } else if self.is_scoped_package(&info.name) {
+            // Default scoped packages to public unless specified
+            args.extend(["--access".to_string(), "public".to_string()]);
+        }
+
+        // Tag
+        if let Some(ref tag) = options.tag {
+            args.extend(["--tag".to_string(), tag.clone()]);
+        }
+
+        // OTP
+        if let Some(ref otp) = options.otp {
+            args.extend(["--otp".to_string(), otp.clone()]);
+        }
+
+        // Provenance attestation (dry runs don't sign, so the env isn't required)
+        if options.provenance {
+            match self.ensure_provenance_env(env) {
+                Ok(()) => args.push("--provenance".to_string()),
+                Err(e) if options.dry_run => {
+                    warn!(adapter = "npm", error = %e, "skipping provenance for dry run");
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok((command, args))
+    }
+
     fn run_script_command(
         &self,
         manager: JsPackageManager,
@@ -141,7 +339,11 @@ impl PackageAdapter for NpmAdapter {
     }
 
     fn default_registry(&self) -> &'static str {
-        "https://registry.npmjs.org"
+        NPM_REGISTRY_URL
+    }
+
+    fn published_versions(&self, path: &Path, name: &str) -> Result<Vec<semver::Version>> {
+        self.registry_versions(path, name).versions(name)
     }
 
     fn detect(&self, path: &Path) -> bool {
@@ -154,6 +356,15 @@ impl PackageAdapter for NpmAdapter {
         &["package.json"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[RequiredTool::new(
+            "npm",
+            &["--version"],
+            "Install Node.js from https://nodejs.org",
+        )];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let manifest_path = self.manifest_path(path);
         let manifest = PackageJson::load_from_path(&manifest_path)?;
@@ -176,52 +387,29 @@ impl PackageAdapter for NpmAdapter {
     fn set_version(&self, path: &Path, version: &str) -> Result<()> {
         info!(adapter = "npm", version, path = %path.display(), "setting version");
         let manifest_path = self.manifest_path(path);
-        let mut manifest = PackageJson::load_from_path(&manifest_path)?;
-        manifest.version = version.to_string();
-        manifest.save_to_path(&manifest_path)?;
-        Ok(())
+        with_manifest_lock(&manifest_path, || {
+            let mut manifest = PackageJson::load_from_path(&manifest_path)?;
+            manifest.version = version.to_string();
+            manifest.save_to_path(&manifest_path)
+        })
     }
 
     fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
         info!(adapter = "npm", path = %path.display(), dry_run = options.dry_run, "publishing package");
-        let manager = self.detect_package_manager(path);
-        let (command, base_args) = self.publish_command(manager);
+        let (command, args) = self.publish_args(path, options, |key| {
+            options
+                .env
+                .get(key)
+                .cloned()
+                .or_else(|| std::env::var(key).ok())
+        })?;
         let mut cmd = Command::new(&command);
-        cmd.args(&base_args);
+        cmd.args(&args);
+        cmd.envs(&options.env);
         cmd.current_dir(path);
 
-        if options.dry_run {
-            cmd.arg("--dry-run");
-        }
-
-        // Registry
-        if let Some(ref registry) = options.registry {
-            cmd.arg("--registry").arg(registry);
-        }
-
-        // Access level
-        if let Some(ref access) = options.access {
-            cmd.arg("--access").arg(access.to_string());
-        } else {
-            // Default scoped packages to public unless specified
-            let info = self.get_info(path)?;
-            if self.is_scoped_package(&info.name) {
-                cmd.arg("--access").arg("public");
-            }
-        }
-
-        // Tag
-        if let Some(ref tag) = options.tag {
-            cmd.arg("--tag").arg(tag);
-        }
-
-        // OTP
-        if let Some(ref otp) = options.otp {
-            cmd.arg("--otp").arg(otp);
-        }
-
         let output = cmd.output().map_err(|e| AdapterError::CommandFailed {
-            command: format!("{} {}", command, base_args.join(" ")),
+            command: format!("{} {}", command, args.join(" ")),
             reason: e.to_string(),
         })?;
 
@@ -310,6 +498,14 @@ impl PackageAdapter for NpmAdapter {
         Ok(output.status.success())
     }
 
+    fn supports_fmt(&self) -> bool {
+        true
+    }
+
+    fn supports_lint(&self) -> bool {
+        true
+    }
+
     fn fmt(&self, path: &Path, check: bool) -> Result<()> {
         let manifest = PackageJson::load_from_path(&self.manifest_path(path))?;
         let manager = self.detect_package_manager(path);
@@ -461,6 +657,36 @@ impl PackageAdapter for NpmAdapter {
         Ok(())
     }
 
+    fn workspace_dependencies(
+        &self,
+        path: &Path,
+        known_packages: &[String],
+    ) -> Result<Vec<String>> {
+        Ok(PackageJson::load_from_path(&self.manifest_path(path))?
+            .workspace_dependencies(known_packages))
+    }
+
+    fn list_publish_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let output = Command::new("npm")
+            .args(["pack", "--dry-run", "--json"])
+            .current_dir(path)
+            .output()
+            .map_err(|e| AdapterError::CommandFailed {
+                command: "npm pack --dry-run --json".to_string(),
+                reason: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(AdapterError::CommandFailed {
+                command: "npm pack --dry-run --json".to_string(),
+                reason: String::from_utf8_lossy(&output.stderr).to_string(),
+            }
+            .into());
+        }
+
+        pack::parse_pack_json(&String::from_utf8_lossy(&output.stdout))
+    }
+
     fn pack(&self, path: &Path) -> Result<Option<PathBuf>> {
         let manager = self.detect_package_manager(path);
         let (command, args) = self.pack_command(manager);
@@ -496,8 +722,80 @@ impl PackageAdapter for NpmAdapter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use canaveral_test_support::{MockResponse, MockServer};
     use tempfile::TempDir;
 
+    #[test]
+    fn test_published_versions_from_registry() {
+        let packument = r#"{
+            "name": "@acme/widget",
+            "dist-tags": { "latest": "1.4.0", "next": "2.0.0-rc.1" },
+            "versions": {
+                "1.0.0": {}, "1.4.0": {}, "1.10.0-dev": {}, "2.0.0-rc.1": {}, "not-semver": {}
+            }
+        }"#;
+        let server = MockServer::serve(vec![MockResponse::status("200 OK", packument.to_string())]);
+        let adapter = NpmAdapter::new().with_registry_url(server.url());
+
+        let versions = adapter
+            .published_versions(Path::new("."), "@acme/widget")
+            .unwrap();
+        let versions: Vec<String> = versions.iter().map(|v| v.to_string()).collect();
+        assert_eq!(versions, ["1.0.0", "1.4.0", "1.10.0-dev", "2.0.0-rc.1"]);
+        assert_eq!(
+            crate::latest_release(
+                &adapter
+                    .published_versions(Path::new("."), "@acme/widget")
+                    .unwrap()
+            )
+            .map(|v| v.to_string()),
+            Some("1.4.0".to_string())
+        );
+
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .map(|r| r.path.as_str())
+                .collect::<Vec<_>>(),
+            ["/@acme%2Fwidget"]
+        );
+    }
+
+    #[test]
+    fn test_published_versions_use_scope_registry_and_token() {
+        let server = MockServer::serve(vec![MockResponse::status(
+            "200 OK",
+            r#"{"name": "@acme/widget", "versions": {"1.2.0": {}}}"#.to_string(),
+        )]);
+        let registry = format!("{}/npm/", server.url());
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "@acme/widget", "version": "0.0.0-dev"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".npmrc"),
+            format!(
+                "@acme:registry={}\n{}:_authToken=secret\n",
+                registry,
+                registry.trim_start_matches("http:")
+            ),
+        )
+        .unwrap();
+
+        let adapter = NpmAdapter::new();
+        let versions = adapter
+            .published_versions(temp.path(), "@acme/widget")
+            .unwrap();
+        assert_eq!(versions, [semver::Version::new(1, 2, 0)]);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].path, "/npm/@acme%2Fwidget");
+        assert_eq!(requests[0].header("Authorization"), Some("Bearer secret"));
+    }
+
     #[test]
     fn test_detect() {
         let adapter = NpmAdapter::new();
@@ -528,6 +826,137 @@ mod tests {
         assert_eq!(version, "1.2.3");
     }
 
+    #[test]
+    fn test_publish_requires_token_for_scoped_registry() {
+        let adapter = NpmAdapter::new();
+        let temp = TempDir::new().unwrap();
+
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "@internal/ui", "version": "1.0.0"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".npmrc"),
+            "@internal:registry=https://npm.internal.example.com/\n",
+        )
+        .unwrap();
+
+        let err = adapter
+            .publish_args(temp.path(), &PublishOptions::new(), isolated_env(&temp))
+            .unwrap_err();
+        assert!(err.to_string().contains("npm.internal.example.com"));
+    }
+
+    /// Environment without the host's npm user config
+    fn isolated_env(temp: &TempDir) -> impl Fn(&str) -> Option<String> {
+        let userconfig = temp.path().join("user-npmrc").display().to_string();
+        move |key| (key == "NPM_CONFIG_USERCONFIG").then(|| userconfig.clone())
+    }
+
+    fn scoped_package_with_token() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "@acme/ui", "version": "1.0.0"}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".npmrc"),
+            "//registry.npmjs.org/:_authToken=publish-token\n",
+        )
+        .unwrap();
+        temp
+    }
+
+    #[test]
+    fn test_publish_args_with_provenance() {
+        let adapter = NpmAdapter::new();
+        let temp = scoped_package_with_token();
+        let isolated = isolated_env(&temp);
+        let github = |key: &str| match key {
+            "ACTIONS_ID_TOKEN_REQUEST_URL" => Some("https://token.actions.example".to_string()),
+            "ACTIONS_ID_TOKEN_REQUEST_TOKEN" => Some("request-token".to_string()),
+            _ => isolated(key),
+        };
+
+        let options = PublishOptions::new().tag("next").provenance(true);
+        let (command, args) = adapter.publish_args(temp.path(), &options, github).unwrap();
+        assert_eq!(command, "npm");
+        assert_eq!(
+            args,
+            [
+                "publish",
+                "--access",
+                "public",
+                "--tag",
+                "next",
+                "--provenance"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_provenance_requires_oidc_env() {
+        let adapter = NpmAdapter::new();
+        let temp = scoped_package_with_token();
+        let options = PublishOptions::new().provenance(true);
+
+        let err = adapter
+            .publish_args(temp.path(), &options, isolated_env(&temp))
+            .unwrap_err();
+        assert!(err.to_string().contains("ACTIONS_ID_TOKEN_REQUEST_URL"));
+        assert!(err.to_string().contains("id-token: write"));
+
+        // Dry runs go ahead without the attestation
+        let (_, args) = adapter
+            .publish_args(
+                temp.path(),
+                &options.clone().dry_run(true),
+                isolated_env(&temp),
+            )
+            .unwrap();
+        assert!(!args.iter().any(|a| a == "--provenance"));
+    }
+
+    #[test]
+    fn test_publish_args_pass_only_explicit_registry() {
+        let adapter = NpmAdapter::new();
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "ui", "version": "1.0.0", "publishConfig": {"registry": "https://npm.example.com/"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".yarnrc.yml"),
+            "npmRegistries:\n  \"//npm.example.com\":\n    npmAuthToken: yarn-token\n",
+        )
+        .unwrap();
+
+        // publishConfig.registry is left to the package manager, but its
+        // token is still checked
+        let (_, args) = adapter
+            .publish_args(temp.path(), &PublishOptions::new(), isolated_env(&temp))
+            .unwrap();
+        assert!(!args.iter().any(|a| a == "--registry"));
+
+        let options = PublishOptions::new().registry("https://npm.example.com/");
+        let (_, args) = adapter
+            .publish_args(temp.path(), &options, isolated_env(&temp))
+            .unwrap();
+        assert!(args.ends_with(&[
+            "--registry".to_string(),
+            "https://npm.example.com/".to_string()
+        ]));
+
+        let options = PublishOptions::new().registry("https://other.example.com/");
+        let err = adapter
+            .publish_args(temp.path(), &options, isolated_env(&temp))
+            .unwrap_err();
+        assert!(err.to_string().contains("other.example.com"));
+    }
+
     #[test]
     fn test_set_version() {
         let adapter = NpmAdapter::new();
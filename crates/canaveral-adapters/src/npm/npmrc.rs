@@ -0,0 +1,346 @@
+//! `.npmrc` registry and auth resolution
+//!
+//! Scoped packages can publish to different registries (`@internal/*` to a
+//! company registry, everything else to npmjs). The mapping lives in
+//! `.npmrc` as `@scope:registry=<url>` lines, and credentials are keyed by
+//! the registry URL without its protocol (`//npm.example.com/:_authToken=...`
+//! or `//npm.example.com/:_auth=...`). Yarn 2+ keeps the same settings in
+//! `.yarnrc.yml`, which is read alongside. `cafile` and `strict-ssl` apply
+//! to registry lookups too.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use tracing::debug;
+
+/// Registry used when neither the scope nor the config names one
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org/";
+
+/// Registry and auth settings read from one or more `.npmrc` files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Npmrc {
+    /// Default registry (`registry=`)
+    pub registry: Option<String>,
+    /// Scope (including `@`) to registry URL
+    pub scope_registries: HashMap<String, String>,
+    /// Protocol-less registry prefix (`//host/path/`) to auth token
+    pub auth_tokens: HashMap<String, String>,
+    /// Protocol-less registry prefix to basic auth (`_auth`, `npmAuthIdent`)
+    pub basic_auths: HashMap<String, String>,
+    /// Basic auth for the default registry (top-level `_auth`)
+    pub auth: Option<String>,
+    /// Extra CA certificates to trust (`cafile`)
+    pub cafile: Option<PathBuf>,
+    /// Whether TLS certificates are verified (`strict-ssl`)
+    pub strict_ssl: Option<bool>,
+}
+
+impl Npmrc {
+    /// Parse the contents of an `.npmrc` file
+    ///
+    /// `${VAR}` references are expanded from the environment; settings whose
+    /// variables are unset are dropped.
+    pub fn parse(content: &str) -> Self {
+        let mut npmrc = Self::default();
+
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            let Some(value) = expand_env(value.trim().trim_matches('"')) else {
+                debug!(key, "skipping .npmrc entry with unset variable");
+                continue;
+            };
+
+            if key == "registry" {
+                npmrc.registry = Some(value);
+            } else if key == "_auth" {
+                npmrc.auth = Some(value);
+            } else if key == "cafile" {
+                npmrc.cafile = Some(PathBuf::from(value));
+            } else if key == "strict-ssl" {
+                npmrc.strict_ssl = value.parse().ok();
+            } else if let Some(prefix) = key.strip_suffix(":_authToken") {
+                npmrc.auth_tokens.insert(with_trailing_slash(prefix), value);
+            } else if let Some(prefix) = key.strip_suffix(":_auth") {
+                npmrc.basic_auths.insert(with_trailing_slash(prefix), value);
+            } else if let Some(scope) = key.strip_suffix(":registry") {
+                if scope.starts_with('@') {
+                    npmrc.scope_registries.insert(scope.to_string(), value);
+                }
+            }
+        }
+
+        npmrc
+    }
+
+    /// Load the user config and every `.npmrc` and `.yarnrc.yml` from the
+    /// filesystem root down to `package_dir`, with closer files taking
+    /// precedence
+    ///
+    /// `env` looks up environment variables: the user config is
+    /// `NPM_CONFIG_USERCONFIG` (default `~/.npmrc`, plus `~/.yarnrc.yml`),
+    /// and `NPM_CONFIG_REGISTRY` overrides the default registry.
+    pub fn load(package_dir: &Path, env: impl Fn(&str) -> Option<String>) -> Self {
+        let home = env("HOME")
+            .or_else(|| env("USERPROFILE"))
+            .map(PathBuf::from);
+        let mut paths: Vec<PathBuf> = match env("NPM_CONFIG_USERCONFIG") {
+            Some(userconfig) => vec![PathBuf::from(userconfig)],
+            None => home.iter().map(|home| home.join(".npmrc")).collect(),
+        };
+        paths.extend(home.iter().map(|home| home.join(".yarnrc.yml")));
+
+        let mut ancestors: Vec<&Path> = package_dir.ancestors().collect();
+        ancestors.reverse();
+        for dir in ancestors {
+            paths.push(dir.join(".npmrc"));
+            paths.push(dir.join(".yarnrc.yml"));
+        }
+
+        let mut npmrc = Self::load_files(&paths);
+        if let Some(registry) = env("NPM_CONFIG_REGISTRY") {
+            npmrc.registry = Some(registry);
+        }
+        npmrc
+    }
+
+    /// Load and merge the given files, later files taking precedence
+    ///
+    /// Files named `.yarnrc.yml` are read as Yarn config.
+    pub fn load_files(paths: &[PathBuf]) -> Self {
+        let mut npmrc = Self::default();
+        for path in paths {
+            if let Ok(content) = std::fs::read_to_string(path) {
+                debug!(path = %path.display(), "reading registry config");
+                if path.file_name().is_some_and(|name| name == ".yarnrc.yml") {
+                    npmrc.merge(Self::parse_yarnrc(&content));
+                } else {
+                    npmrc.merge(Self::parse(&content));
+                }
+            }
+        }
+        npmrc
+    }
+
+    /// Overlay `other` on top of these settings
+    pub fn merge(&mut self, other: Self) {
+        if other.registry.is_some() {
+            self.registry = other.registry;
+        }
+        if other.auth.is_some() {
+            self.auth = other.auth;
+        }
+        if other.cafile.is_some() {
+            self.cafile = other.cafile;
+        }
+        if other.strict_ssl.is_some() {
+            self.strict_ssl = other.strict_ssl;
+        }
+        self.scope_registries.extend(other.scope_registries);
+        self.auth_tokens.extend(other.auth_tokens);
+        self.basic_auths.extend(other.basic_auths);
+    }
+
+    /// Registry a package publishes to, based on its scope
+    pub fn registry_for(&self, package_name: &str) -> String {
+        let scoped = package_name
+            .split_once('/')
+            .filter(|(scope, _)| scope.starts_with('@'))
+            .and_then(|(scope, _)| self.scope_registries.get(scope));
+
+        scoped
+            .or(self.registry.as_ref())
+            .map(|url| with_trailing_slash(url))
+            .unwrap_or_else(|| DEFAULT_REGISTRY.to_string())
+    }
+
+    /// Auth token for a registry URL
+    ///
+    /// Like npm, tries the full registry path first and then each parent path
+    /// up to the host.
+    pub fn auth_token_for(&self, registry: &str) -> Option<&str> {
+        lookup(&self.auth_tokens, registry)
+    }
+
+    /// Whether a token or basic auth is configured for a registry URL
+    pub fn has_credentials_for(&self, registry: &str) -> bool {
+        self.authorization_for(registry).is_some()
+    }
+
+    /// `Authorization` header value for requests to a registry URL
+    ///
+    /// A token is sent as `Bearer`, basic auth as `Basic`; Yarn's
+    /// `user:pass` idents are base64-encoded like Yarn does.
+    pub fn authorization_for(&self, registry: &str) -> Option<String> {
+        if let Some(token) = self.auth_token_for(registry) {
+            return Some(format!("Bearer {}", token));
+        }
+        let is_default = with_trailing_slash(registry)
+            == with_trailing_slash(self.registry.as_deref().unwrap_or(DEFAULT_REGISTRY));
+        let basic =
+            lookup(&self.basic_auths, registry).or(self.auth.as_deref().filter(|_| is_default))?;
+        Some(if basic.contains(':') {
+            format!("Basic {}", BASE64_STANDARD.encode(basic))
+        } else {
+            format!("Basic {}", basic)
+        })
+    }
+}
+
+/// The entry for `registry` in a map keyed by registry prefix, trying the
+/// full registry path first and then each parent path up to the host
+fn lookup<'a>(entries: &'a HashMap<String, String>, registry: &str) -> Option<&'a str> {
+    let mut key = registry_key(registry);
+    loop {
+        if let Some(value) = entries.get(&key) {
+            return Some(value);
+        }
+        // "//host/a/b/" -> "//host/a/"
+        let trimmed = key[..key.len() - 1].rfind('/')?;
+        if trimmed < 2 {
+            return None;
+        }
+        key.truncate(trimmed + 1);
+    }
+}
+
+/// `https://host/path` as the protocol-less `//host/path/` credentials key
+pub(super) fn registry_key(registry: &str) -> String {
+    let url = with_trailing_slash(registry);
+    let without_scheme = url
+        .split_once("://")
+        .map_or(url.as_str(), |(_, rest)| rest)
+        .trim_start_matches('/');
+    format!("//{}", without_scheme)
+}
+
+pub(super) fn with_trailing_slash(url: &str) -> String {
+    if url.ends_with('/') {
+        url.to_string()
+    } else {
+        format!("{}/", url)
+    }
+}
+
+/// Expand `${VAR}` references, returning `None` if any variable is unset
+pub(super) fn expand_env(value: &str) -> Option<String> {
+    let mut output = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        let end = rest[start..].find('}')? + start;
+        output.push_str(&rest[..start]);
+        output.push_str(&std::env::var(&rest[start + 2..end]).ok()?);
+        rest = &rest[end + 1..];
+    }
+    output.push_str(rest);
+
+    Some(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const NPMRC: &str = "\
+; company registry for internal packages
+@internal:registry=https://npm.example.com/repository/npm-private
+//npm.example.com/repository/:_authToken=internal-token
+//registry.npmjs.org/:_authToken=public-token
+//unused.example.com/:_authToken=${CANAVERAL_TEST_UNSET_NPM_TOKEN}
+";
+
+    #[test]
+    fn test_parse_and_select_registry_per_scope() {
+        let npmrc = Npmrc::parse(NPMRC);
+
+        assert_eq!(
+            npmrc.registry_for("@internal/ui"),
+            "https://npm.example.com/repository/npm-private/"
+        );
+        assert_eq!(npmrc.registry_for("@public/ui"), DEFAULT_REGISTRY);
+        assert_eq!(npmrc.registry_for("left-pad"), DEFAULT_REGISTRY);
+
+        // Token is keyed by a parent path of the scoped registry
+        assert_eq!(
+            npmrc.auth_token_for(&npmrc.registry_for("@internal/ui")),
+            Some("internal-token")
+        );
+        assert_eq!(npmrc.auth_token_for(DEFAULT_REGISTRY), Some("public-token"));
+        assert_eq!(npmrc.auth_token_for("https://unused.example.com"), None);
+        assert_eq!(npmrc.auth_token_for("https://other.example.com/"), None);
+    }
+
+    #[test]
+    fn test_project_npmrc_overrides_parent() {
+        let temp = TempDir::new().unwrap();
+        let package = temp.path().join("packages/ui");
+        std::fs::create_dir_all(&package).unwrap();
+
+        std::fs::write(
+            temp.path().join(".npmrc"),
+            "registry=https://mirror.example.com\n//mirror.example.com/:_authToken=root\n",
+        )
+        .unwrap();
+        std::fs::write(
+            package.join(".npmrc"),
+            "//mirror.example.com/:_authToken=package\n",
+        )
+        .unwrap();
+
+        let npmrc = Npmrc::load_files(&[temp.path().join(".npmrc"), package.join(".npmrc")]);
+        assert_eq!(npmrc.registry_for("ui"), "https://mirror.example.com/");
+        assert_eq!(
+            npmrc.auth_token_for("https://mirror.example.com"),
+            Some("package")
+        );
+    }
+
+    #[test]
+    fn test_basic_auth_counts_as_credentials() {
+        let npmrc = Npmrc::parse("//npm.example.com/:_auth=dXNlcjpwYXNz\n_auth=ZGVmYXVsdDpwYXNz\n");
+        assert!(npmrc.has_credentials_for("https://npm.example.com/team/"));
+        assert_eq!(npmrc.auth_token_for("https://npm.example.com/"), None);
+        // Top-level _auth applies to the default registry only
+        assert!(npmrc.has_credentials_for(DEFAULT_REGISTRY));
+        assert!(!npmrc.has_credentials_for("https://other.example.com/"));
+        assert_eq!(
+            npmrc
+                .authorization_for("https://npm.example.com/")
+                .as_deref(),
+            Some("Basic dXNlcjpwYXNz")
+        );
+    }
+
+    #[test]
+    fn test_parse_tls_settings() {
+        let npmrc = Npmrc::parse("cafile=/etc/ssl/corp.pem\nstrict-ssl=false\n");
+        assert_eq!(npmrc.cafile, Some(PathBuf::from("/etc/ssl/corp.pem")));
+        assert_eq!(npmrc.strict_ssl, Some(false));
+    }
+
+    #[test]
+    fn test_load_reads_userconfig_from_env() {
+        let temp = TempDir::new().unwrap();
+        let userconfig = temp.path().join("user-npmrc");
+        std::fs::write(&userconfig, "//registry.npmjs.org/:_authToken=user\n").unwrap();
+        let package = temp.path().join("pkg");
+        std::fs::create_dir_all(&package).unwrap();
+
+        let npmrc = Npmrc::load(&package, |key| match key {
+            "NPM_CONFIG_USERCONFIG" => Some(userconfig.display().to_string()),
+            "NPM_CONFIG_REGISTRY" => Some("https://mirror.example.com".to_string()),
+            _ => None,
+        });
+        assert_eq!(npmrc.auth_token_for(DEFAULT_REGISTRY), Some("user"));
+        assert_eq!(npmrc.registry_for("ui"), "https://mirror.example.com/");
+    }
+}
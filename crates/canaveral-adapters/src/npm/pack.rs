@@ -0,0 +1,90 @@
+//! Parsing of `npm pack --dry-run --json` output
+
+use std::path::PathBuf;
+
+use serde::Deserialize;
+
+use canaveral_core::error::{AdapterError, Result};
+
+#[derive(Debug, Deserialize)]
+struct PackResult {
+    #[serde(default)]
+    files: Vec<PackFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PackFile {
+    path: String,
+}
+
+/// File paths from `npm pack --dry-run --json`
+///
+/// Lifecycle scripts (`prepack`, `prepare`) may print to stdout before the
+/// JSON array, so everything before the first line starting with `[` is
+/// skipped.
+pub(super) fn parse_pack_json(output: &str) -> Result<Vec<PathBuf>> {
+    let start = output
+        .lines()
+        .scan(0, |offset, line| {
+            let at = *offset;
+            *offset += line.len() + 1;
+            Some((at, line))
+        })
+        .find(|(_, line)| line.trim_start().starts_with('['))
+        .map(|(at, _)| at)
+        .ok_or_else(|| AdapterError::CommandFailed {
+            command: "npm pack --dry-run --json".to_string(),
+            reason: "no JSON output".to_string(),
+        })?;
+
+    let results: Vec<PackResult> =
+        serde_json::from_str(&output[start..]).map_err(|e| AdapterError::CommandFailed {
+            command: "npm pack --dry-run --json".to_string(),
+            reason: format!("unexpected output: {}", e),
+        })?;
+
+    Ok(results
+        .into_iter()
+        .flat_map(|r| r.files)
+        .map(|f| PathBuf::from(f.path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_pack_json() {
+        let output = r#"
+> my-lib@1.2.0 prepack
+> tsc
+
+[
+  {
+    "id": "my-lib@1.2.0",
+    "name": "my-lib",
+    "version": "1.2.0",
+    "size": 1024,
+    "files": [
+      { "path": "LICENSE", "size": 1070, "mode": 420 },
+      { "path": "dist/index.js", "size": 512, "mode": 420 },
+      { "path": "package.json", "size": 300, "mode": 420 }
+    ],
+    "entryCount": 3
+  }
+]
+"#;
+        let files = parse_pack_json(output).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("LICENSE"),
+                PathBuf::from("dist/index.js"),
+                PathBuf::from("package.json")
+            ]
+        );
+
+        assert!(parse_pack_json("npm ERR! something").is_err());
+    }
+}
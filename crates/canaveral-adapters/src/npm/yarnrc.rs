@@ -0,0 +1,119 @@
+//! `.yarnrc.yml` registry and auth resolution
+//!
+//! Yarn 2+ doesn't read `.npmrc`. Its registry is `npmRegistryServer` and
+//! its credentials are `npmAuthToken` or `npmAuthIdent`, set at the top
+//! level, per registry under `npmRegistries` or per scope under `npmScopes`.
+//! TLS is configured with `httpsCaFilePath` and `enableStrictSsl`.
+
+use serde_yaml::Value;
+use tracing::debug;
+
+use super::npmrc::{expand_env, registry_key, with_trailing_slash, Npmrc, DEFAULT_REGISTRY};
+
+impl Npmrc {
+    /// Parse the contents of a `.yarnrc.yml` file
+    ///
+    /// `${VAR}` references are expanded from the environment; settings whose
+    /// variables are unset are dropped.
+    pub fn parse_yarnrc(content: &str) -> Self {
+        let mut npmrc = Self::default();
+        let root: Value = match serde_yaml::from_str(content) {
+            Ok(root) => root,
+            Err(e) => {
+                debug!(error = %e, "skipping unparseable .yarnrc.yml");
+                return npmrc;
+            }
+        };
+
+        npmrc.registry = setting(&root, "npmRegistryServer");
+        let default = npmrc
+            .registry
+            .clone()
+            .unwrap_or_else(|| DEFAULT_REGISTRY.to_string());
+        npmrc.add_yarn_auth(&root, &default);
+        npmrc.cafile = setting(&root, "httpsCaFilePath").map(Into::into);
+        npmrc.strict_ssl = root.get("enableStrictSsl").and_then(Value::as_bool);
+
+        if let Some(registries) = root.get("npmRegistries").and_then(Value::as_mapping) {
+            for (registry, settings) in registries {
+                if let Some(registry) = registry.as_str() {
+                    npmrc.add_yarn_auth(settings, registry);
+                }
+            }
+        }
+
+        if let Some(scopes) = root.get("npmScopes").and_then(Value::as_mapping) {
+            for (scope, settings) in scopes {
+                let Some(scope) = scope.as_str() else {
+                    continue;
+                };
+                let registry = setting(settings, "npmRegistryServer");
+                if let Some(registry) = &registry {
+                    npmrc.scope_registries.insert(
+                        format!("@{}", scope.trim_start_matches('@')),
+                        with_trailing_slash(registry),
+                    );
+                }
+                npmrc.add_yarn_auth(settings, registry.as_deref().unwrap_or(&default));
+            }
+        }
+
+        npmrc
+    }
+
+    /// Record the credentials in `settings` for `registry`
+    fn add_yarn_auth(&mut self, settings: &Value, registry: &str) {
+        if let Some(token) = setting(settings, "npmAuthToken") {
+            self.auth_tokens.insert(registry_key(registry), token);
+        }
+        if let Some(ident) = setting(settings, "npmAuthIdent") {
+            self.basic_auths.insert(registry_key(registry), ident);
+        }
+    }
+}
+
+/// The string setting `key`, with environment variables expanded
+fn setting(settings: &Value, key: &str) -> Option<String> {
+    settings.get(key)?.as_str().and_then(expand_env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YARNRC: &str = r#"
+npmAuthToken: public-token
+npmRegistries:
+  "//npm.pkg.github.com":
+    npmAuthIdent: "user:pass"
+npmScopes:
+  internal:
+    npmRegistryServer: "https://npm.example.com/private"
+    npmAuthToken: internal-token
+"#;
+
+    #[test]
+    fn test_parse_yarnrc() {
+        let npmrc = Npmrc::parse_yarnrc(YARNRC);
+
+        assert_eq!(npmrc.auth_token_for(DEFAULT_REGISTRY), Some("public-token"));
+        assert!(npmrc.has_credentials_for("https://npm.pkg.github.com/"));
+        assert_eq!(
+            npmrc.registry_for("@internal/ui"),
+            "https://npm.example.com/private/"
+        );
+        assert_eq!(
+            npmrc.auth_token_for("https://npm.example.com/private/"),
+            Some("internal-token")
+        );
+        assert!(!npmrc.has_credentials_for("https://other.example.com/"));
+    }
+
+    #[test]
+    fn test_invalid_yarnrc_is_ignored() {
+        assert_eq!(
+            Npmrc::parse_yarnrc("npmScopes: [unclosed"),
+            Npmrc::default()
+        );
+    }
+}
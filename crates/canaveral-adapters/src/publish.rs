@@ -2,6 +2,7 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tracing::warn;
 
 /// Options for publishing a package
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -21,8 +22,29 @@ pub struct PublishOptions {
     /// OTP/2FA code if required
     pub otp: Option<String>,
 
-    /// Additional adapter-specific options
+    /// Publish even with uncommitted changes in the package
+    #[serde(default)]
+    pub allow_dirty: bool,
+
+    /// Skip the adapter's pre-upload verification (e.g. `cargo publish --no-verify`)
+    #[serde(default)]
+    pub skip_verify: bool,
+
+    /// Treat a version that already exists in the registry as success
+    #[serde(default)]
+    pub skip_existing: bool,
+
+    /// Publish with a provenance attestation (npm; requires CI OIDC)
+    #[serde(default)]
+    pub provenance: bool,
+
+    /// Additional adapter-specific options (e.g. `token`, `skip_gpg`)
     pub extra: HashMap<String, String>,
+
+    /// Environment variables for the publish command (e.g. a private CA
+    /// bundle in `NODE_EXTRA_CA_CERTS`)
+    #[serde(default)]
+    pub env: HashMap<String, String>,
 }
 
 impl PublishOptions {
@@ -61,11 +83,69 @@ impl PublishOptions {
         self
     }
 
+    /// Allow publishing with uncommitted changes
+    pub fn allow_dirty(mut self, allow_dirty: bool) -> Self {
+        self.allow_dirty = allow_dirty;
+        self
+    }
+
+    /// Skip pre-upload verification
+    pub fn skip_verify(mut self, skip_verify: bool) -> Self {
+        self.skip_verify = skip_verify;
+        self
+    }
+
+    /// Skip versions that already exist in the registry
+    pub fn skip_existing(mut self, skip_existing: bool) -> Self {
+        self.skip_existing = skip_existing;
+        self
+    }
+
+    /// Publish with a provenance attestation
+    pub fn provenance(mut self, provenance: bool) -> Self {
+        self.provenance = provenance;
+        self
+    }
+
+    /// Whether an adapter-specific extra option is set to `"true"`
+    pub fn extra_flag(&self, key: &str) -> bool {
+        self.extra.get(key).is_some_and(|v| v == "true")
+    }
+
+    /// Map the legacy `allow_dirty`, `no_verify` and `skip_existing` extra
+    /// keys onto their typed options, warning that they are deprecated
+    pub fn with_legacy_extras(mut self) -> Self {
+        for (key, option, field) in [
+            ("allow_dirty", "allow_dirty", &mut self.allow_dirty),
+            ("no_verify", "skip_verify", &mut self.skip_verify),
+            ("skip_existing", "skip_existing", &mut self.skip_existing),
+        ] {
+            if let Some(value) = self.extra.remove(key) {
+                warn!(
+                    "publish extra key `{}` is deprecated; use `{}` instead",
+                    key, option
+                );
+                *field |= value == "true";
+            }
+        }
+        self
+    }
+
     /// Add extra option
     pub fn with_extra(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
         self.extra.insert(key.into(), value.into());
         self
     }
+
+    /// Set environment variables for the publish command
+    pub fn with_env<K: Into<String>, V: Into<String>>(
+        mut self,
+        env: impl IntoIterator<Item = (K, V)>,
+    ) -> Self {
+        self.env
+            .extend(env.into_iter().map(|(k, v)| (k.into(), v.into())));
+        self
+    }
 }
 
 /// Access level for published packages
@@ -135,12 +215,66 @@ impl ValidationResult {
     }
 
     /// Merge another validation result into this one
+    ///
+    /// Errors and warnings are concatenated; the merged result passes only
+    /// if both inputs passed and no errors were collected.
     pub fn merge(&mut self, other: ValidationResult) {
-        if !other.passed {
-            self.passed = false;
-        }
+        self.passed = self.passed && other.passed;
         self.errors.extend(other.errors);
         self.warnings.extend(other.warnings);
+        self.passed = self.passed && self.errors.is_empty();
+    }
+
+    /// Count issues by severity
+    pub fn summary(&self) -> ValidationSummary {
+        ValidationSummary {
+            errors: self.errors.len(),
+            warnings: self.warnings.len(),
+        }
+    }
+
+    /// Whether this result should stop a publish
+    pub fn is_blocking(&self) -> bool {
+        !self.passed || !self.errors.is_empty()
+    }
+}
+
+impl FromIterator<ValidationResult> for ValidationResult {
+    fn from_iter<I: IntoIterator<Item = ValidationResult>>(iter: I) -> Self {
+        iter.into_iter().fold(Self::pass(), |mut acc, result| {
+            acc.merge(result);
+            acc
+        })
+    }
+}
+
+/// Issue counts of a validation result, by severity
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ValidationSummary {
+    /// Number of errors
+    pub errors: usize,
+    /// Number of warnings
+    pub warnings: usize,
+}
+
+impl ValidationSummary {
+    /// Total number of issues
+    pub fn total(&self) -> usize {
+        self.errors + self.warnings
+    }
+}
+
+impl std::fmt::Display for ValidationSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let plural = |n: usize| if n == 1 { "" } else { "s" };
+        write!(
+            f,
+            "{} error{}, {} warning{}",
+            self.errors,
+            plural(self.errors),
+            self.warnings,
+            plural(self.warnings)
+        )
     }
 }
 
@@ -173,6 +307,45 @@ mod tests {
         assert_eq!(opts.otp, Some("123456".to_string()));
     }
 
+    #[test]
+    fn test_typed_flags_and_extra() {
+        let opts = PublishOptions::new()
+            .allow_dirty(true)
+            .skip_existing(true)
+            .with_extra("skip_gpg", "true")
+            .with_extra("token", "secret");
+
+        assert!(opts.allow_dirty);
+        assert!(!opts.skip_verify);
+        assert!(opts.skip_existing);
+        assert!(opts.extra_flag("skip_gpg"));
+        assert!(!opts.extra_flag("token"));
+        assert!(!opts.extra_flag("missing"));
+
+        // Options serialized before the typed fields existed still load
+        let legacy: PublishOptions = serde_json::from_str(
+            r#"{"dry_run":false,"registry":null,"access":null,"tag":null,"otp":null,"extra":{}}"#,
+        )
+        .unwrap();
+        assert!(!legacy.allow_dirty);
+    }
+
+    #[test]
+    fn test_legacy_extras() {
+        let opts = PublishOptions::new()
+            .with_extra("allow_dirty", "true")
+            .with_extra("no_verify", "true")
+            .with_extra("skip_existing", "false")
+            .with_extra("token", "secret")
+            .with_legacy_extras();
+
+        assert!(opts.allow_dirty);
+        assert!(opts.skip_verify);
+        assert!(!opts.skip_existing);
+        assert_eq!(opts.extra.len(), 1);
+        assert_eq!(opts.extra["token"], "secret");
+    }
+
     #[test]
     fn test_validation_result() {
         let mut result = ValidationResult::pass();
@@ -200,4 +373,36 @@ mod tests {
         assert_eq!(result1.errors.len(), 1);
         assert_eq!(result1.warnings.len(), 1);
     }
+
+    #[test]
+    fn test_merge_pass_with_failure_summary() {
+        let mut pass = ValidationResult::pass();
+        pass.add_warning("No repository field");
+        assert!(!pass.is_blocking());
+
+        let mut failure = ValidationResult::fail("Missing version");
+        failure.add_error("Missing license");
+        failure.add_warning("No README");
+
+        pass.merge(failure);
+        assert!(!pass.passed);
+        assert!(pass.is_blocking());
+
+        let summary = pass.summary();
+        assert_eq!(
+            summary,
+            ValidationSummary {
+                errors: 2,
+                warnings: 2
+            }
+        );
+        assert_eq!(summary.total(), 4);
+        assert_eq!(summary.to_string(), "2 errors, 2 warnings");
+
+        let combined: ValidationResult = vec![ValidationResult::pass(), ValidationResult::pass()]
+            .into_iter()
+            .collect();
+        assert!(combined.passed);
+        assert_eq!(combined.summary().to_string(), "0 errors, 0 warnings");
+    }
 }
@@ -0,0 +1,380 @@
+//! Checks on the files a package would publish
+//!
+//! Adapters list what a publish would upload via
+//! [`PackageAdapter::list_publish_files`](crate::PackageAdapter::list_publish_files).
+//! The helpers here flag entries that look like secrets (`.env`, private
+//! keys, registry credentials) or that `.gitattributes` marks
+//! `export-ignore` (test fixtures, CI configs) before they end up on a
+//! public registry.
+
+use std::path::{Component, Path, PathBuf};
+
+use glob::{MatchOptions, Pattern};
+use tracing::{debug, warn};
+
+use crate::publish::ValidationResult;
+
+/// File name patterns treated as likely secrets when none are configured
+pub const DEFAULT_SECRET_PATTERNS: &[&str] = &[
+    ".env",
+    ".env.*",
+    "*.pem",
+    "*.key",
+    "*.p12",
+    "*.pfx",
+    "id_rsa*",
+    "id_ed25519*",
+    ".npmrc",
+    ".pypirc",
+];
+
+/// Files whose name matches one of the secret patterns
+///
+/// Patterns are matched against the file name only, so `*.pem` flags
+/// `certs/server.pem` as well as `server.pem`. Invalid patterns are skipped.
+pub fn find_secret_files<'a>(files: &'a [PathBuf], patterns: &[String]) -> Vec<&'a Path> {
+    let compiled: Vec<Pattern> = patterns
+        .iter()
+        .filter_map(|p| match Pattern::new(p) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                warn!(pattern = %p, error = %e, "ignoring invalid secret file pattern");
+                None
+            }
+        })
+        .collect();
+
+    files
+        .iter()
+        .filter(|file| {
+            file.file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| compiled.iter().any(|p| p.matches(name)))
+        })
+        .map(PathBuf::as_path)
+        .collect()
+}
+
+/// Validation warnings for publish files that look like secrets
+pub fn check_publish_files(files: &[PathBuf], patterns: &[String]) -> ValidationResult {
+    let mut result = ValidationResult::pass();
+    for file in find_secret_files(files, patterns) {
+        result.add_warning(format!(
+            "{} looks like a secret and would be published",
+            file.display()
+        ));
+    }
+    debug!(
+        files = files.len(),
+        flagged = result.warnings.len(),
+        "checked publish files"
+    );
+    result
+}
+
+/// [`DEFAULT_SECRET_PATTERNS`] as owned strings
+pub fn default_secret_patterns() -> Vec<String> {
+    DEFAULT_SECRET_PATTERNS
+        .iter()
+        .map(|p| p.to_string())
+        .collect()
+}
+
+/// Attribute marking paths left out of `git archive` exports
+pub const EXPORT_IGNORE_ATTRIBUTE: &str = "export-ignore";
+
+/// A single gitignore-style pattern
+#[derive(Debug, Clone)]
+struct PathRule {
+    pattern: Pattern,
+    /// Matched against the whole relative path rather than the file name
+    anchored: bool,
+    /// Only matches directories
+    dir_only: bool,
+    /// Re-includes paths matched by an earlier rule
+    negated: bool,
+}
+
+impl PathRule {
+    fn matches(&self, path: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        if self.anchored {
+            self.pattern.matches_with(path, options)
+        } else {
+            let name = path.rsplit('/').next().unwrap_or(path);
+            self.pattern.matches_with(name, options)
+        }
+    }
+}
+
+/// Ordered `.gitattributes` path patterns
+///
+/// A leading `!` negates a pattern, a trailing `/` restricts it to
+/// directories, and a pattern containing `/` is matched against the whole
+/// path relative to the rules' base directory instead of the file name.
+/// The last matching pattern wins.
+#[derive(Debug, Clone, Default)]
+struct PathRules {
+    rules: Vec<PathRule>,
+}
+
+impl PathRules {
+    /// Add a pattern, ignoring it if it is not a valid glob
+    fn add(&mut self, pattern: &str) {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let (dir_only, pattern) = match pattern.strip_suffix('/') {
+            Some(rest) => (true, rest),
+            None => (false, pattern),
+        };
+        let anchored = pattern.contains('/');
+        let pattern = pattern.trim_start_matches('/');
+        if pattern.is_empty() {
+            return;
+        }
+        match Pattern::new(pattern) {
+            Ok(pattern) => self.rules.push(PathRule {
+                pattern,
+                anchored,
+                dir_only,
+                negated,
+            }),
+            Err(e) => warn!(pattern, error = %e, "ignoring invalid path pattern"),
+        }
+    }
+
+    /// Whether there are no rules
+    fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether the rules match the file at `path`, relative to their base
+    ///
+    /// `None` if no rule applies. A file inside a matched directory is
+    /// matched too, as with `.gitignore`.
+    fn decide(&self, path: &Path) -> Option<bool> {
+        let components: Vec<&str> = path
+            .components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect();
+
+        let mut prefix = String::new();
+        for (i, component) in components.iter().enumerate() {
+            if !prefix.is_empty() {
+                prefix.push('/');
+            }
+            prefix.push_str(component);
+            let is_dir = i + 1 < components.len();
+            let decision = self
+                .rules
+                .iter()
+                .rev()
+                .find(|rule| rule.matches(&prefix, is_dir))
+                .map(|rule| !rule.negated);
+            if !is_dir {
+                return decision;
+            }
+            if decision == Some(true) {
+                return Some(true);
+            }
+        }
+        None
+    }
+}
+
+/// Paths marked `export-ignore` by `.gitattributes`
+///
+/// Reads `.gitattributes` from the package directory and its parents up to
+/// the repository root, with deeper files taking precedence like in git.
+#[derive(Debug, Clone, Default)]
+pub struct ExportIgnore {
+    /// Rules per `.gitattributes` directory, outermost first
+    rules: Vec<(PathBuf, PathRules)>,
+}
+
+impl ExportIgnore {
+    /// Load the `.gitattributes` files applying to `dir`
+    pub fn load(dir: &Path) -> Self {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut rules = Vec::new();
+        for ancestor in dir.ancestors() {
+            if let Ok(content) = std::fs::read_to_string(ancestor.join(".gitattributes")) {
+                let attributes = Self::parse_attributes(&content);
+                if !attributes.is_empty() {
+                    rules.push((ancestor.to_path_buf(), attributes));
+                }
+            }
+            if ancestor.join(".git").exists() {
+                break;
+            }
+        }
+        rules.reverse();
+        Self { rules }
+    }
+
+    /// Rules from `.gitattributes` content, matching `export-ignore` paths
+    ///
+    /// `-export-ignore` and `!export-ignore` unset the attribute again.
+    fn parse_attributes(content: &str) -> PathRules {
+        let mut rules = PathRules::default();
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(pattern) = fields.next().filter(|p| !p.starts_with('#')) else {
+                continue;
+            };
+            for attribute in fields {
+                match attribute.trim_start_matches(['-', '!']) {
+                    EXPORT_IGNORE_ATTRIBUTE if attribute == EXPORT_IGNORE_ATTRIBUTE => {
+                        rules.add(pattern)
+                    }
+                    EXPORT_IGNORE_ATTRIBUTE => rules.add(&format!("!{}", pattern)),
+                    _ => {}
+                }
+            }
+        }
+        rules
+    }
+
+    /// Whether nothing is marked `export-ignore`
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether the file at `path` (absolute, or relative to the working
+    /// directory) is marked `export-ignore`
+    pub fn is_ignored(&self, path: &Path) -> bool {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        self.rules
+            .iter()
+            .rev()
+            .find_map(|(base, rules)| rules.decide(path.strip_prefix(base).ok()?))
+            .unwrap_or(false)
+    }
+}
+
+/// Validation warnings for publish files marked `export-ignore`
+///
+/// `files` are relative to `dir`, as listed by
+/// [`PackageAdapter::list_publish_files`](crate::PackageAdapter::list_publish_files).
+/// Files left out of source archives are usually test fixtures or CI
+/// configuration that shouldn't ship either.
+pub fn check_export_ignored(dir: &Path, files: &[PathBuf]) -> ValidationResult {
+    let mut result = ValidationResult::pass();
+    let export_ignore = ExportIgnore::load(dir);
+    if export_ignore.is_empty() {
+        return result;
+    }
+    for file in files {
+        if export_ignore.is_ignored(&dir.join(file)) {
+            result.add_warning(format!(
+                "{} is marked export-ignore in .gitattributes but would be published",
+                file.display()
+            ));
+        }
+    }
+    debug!(
+        files = files.len(),
+        flagged = result.warnings.len(),
+        "checked publish files against export-ignore"
+    );
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_flags_secret_files() {
+        let files: Vec<PathBuf> = [
+            "package.json",
+            "dist/index.js",
+            ".env",
+            ".env.production",
+            "certs/server.pem",
+            "src/environment.ts",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+
+        let flagged = find_secret_files(&files, &default_secret_patterns());
+        assert_eq!(
+            flagged,
+            vec![
+                Path::new(".env"),
+                Path::new(".env.production"),
+                Path::new("certs/server.pem")
+            ]
+        );
+
+        let result = check_publish_files(&files, &["*.ts".to_string()]);
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("src/environment.ts"));
+    }
+
+    #[test]
+    fn test_path_rules() {
+        let mut rules = PathRules::default();
+        for pattern in ["*.log", "/dist/", "docs/*.md", "!keep.log"] {
+            rules.add(pattern);
+        }
+        assert_eq!(rules.decide(Path::new("debug.log")), Some(true));
+        assert_eq!(rules.decide(Path::new("nested/debug.log")), Some(true));
+        assert_eq!(rules.decide(Path::new("keep.log")), Some(false));
+        assert_eq!(rules.decide(Path::new("dist/index.js")), Some(true));
+        assert_eq!(rules.decide(Path::new("src/dist")), None);
+        assert_eq!(rules.decide(Path::new("docs/guide.md")), Some(true));
+        assert_eq!(rules.decide(Path::new("docs/api/index.md")), None);
+        assert_eq!(rules.decide(Path::new("src/index.js")), None);
+    }
+
+    #[test]
+    fn test_export_ignore_attributes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join(".git")).unwrap();
+        std::fs::write(
+            temp.path().join(".gitattributes"),
+            "* text=auto\n/tests export-ignore\n.github/ export-ignore\n*.snap export-ignore\n",
+        )
+        .unwrap();
+        let package = temp.path().join("packages/lib");
+        std::fs::create_dir_all(&package).unwrap();
+        std::fs::write(package.join(".gitattributes"), "*.snap -export-ignore\n").unwrap();
+
+        let root = ExportIgnore::load(temp.path());
+        assert!(root.is_ignored(&temp.path().join("tests/fixtures/data.json")));
+        assert!(root.is_ignored(&temp.path().join(".github/workflows/ci.yml")));
+        assert!(root.is_ignored(&temp.path().join("output.snap")));
+        assert!(!root.is_ignored(&temp.path().join("src/lib.rs")));
+        // Anchored to the repository root
+        assert!(!root.is_ignored(&temp.path().join("src/tests/mod.rs")));
+
+        // The package's own .gitattributes takes precedence
+        let nested = ExportIgnore::load(&package);
+        assert!(!nested.is_ignored(&package.join("output.snap")));
+        assert!(nested.is_ignored(&temp.path().join("tests/data.json")));
+
+        // Checked against the files the packaging tool lists
+        let files = [
+            PathBuf::from("src/lib.rs"),
+            PathBuf::from("tests/data.json"),
+        ];
+        let result = check_export_ignored(temp.path(), &files);
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 1);
+        assert!(result.warnings[0].contains("tests/data.json"));
+    }
+}
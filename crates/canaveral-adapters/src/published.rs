@@ -0,0 +1,355 @@
+//! Versions already published to a package registry
+//!
+//! Some packages keep a placeholder version such as `0.0.0-dev` in their
+//! manifest and get the real version at publish time. For those, the
+//! previous version has to come from the registry. [`RegistryVersions`]
+//! fetches and caches it; adapters expose it through
+//! [`PackageAdapter::published_versions`](crate::PackageAdapter::published_versions).
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use reqwest::blocking::Client;
+use reqwest::StatusCode;
+use semver::Version;
+use serde::Deserialize;
+use tracing::debug;
+
+use canaveral_core::error::{AdapterError, Result};
+
+/// Default npm registry
+pub const NPM_REGISTRY_URL: &str = "https://registry.npmjs.org";
+
+/// Default crates.io sparse index
+pub const CRATES_INDEX_URL: &str = "https://index.crates.io";
+
+/// Default PyPI simple API
+pub const PYPI_SIMPLE_URL: &str = "https://pypi.org/simple";
+
+/// Registry protocols [`RegistryVersions`] can read
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryProtocol {
+    /// npm registry package documents
+    Npm,
+    /// Cargo sparse index
+    CratesIndex,
+    /// PyPI simple API, JSON flavour (PEP 691)
+    PypiSimple,
+}
+
+/// Published versions of packages in one registry, cached per name
+///
+/// Each package is fetched at most once per instance, so repeated lookups
+/// during a run don't hit the registry again.
+#[derive(Debug)]
+pub struct RegistryVersions {
+    protocol: RegistryProtocol,
+    base_url: String,
+    authorization: Option<String>,
+    ca_file: Option<PathBuf>,
+    strict_ssl: bool,
+    cache: Mutex<HashMap<String, Vec<Version>>>,
+}
+
+impl RegistryVersions {
+    /// Read versions from the registry at `base_url`
+    pub fn new(protocol: RegistryProtocol, base_url: impl Into<String>) -> Self {
+        Self {
+            protocol,
+            base_url: base_url.into().trim_end_matches('/').to_string(),
+            authorization: None,
+            ca_file: None,
+            strict_ssl: true,
+            cache: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Send `value` as the `Authorization` header
+    pub fn with_authorization(mut self, value: Option<String>) -> Self {
+        self.authorization = value;
+        self
+    }
+
+    /// Trust the PEM certificates in `path` besides the system roots
+    pub fn with_ca_file(mut self, path: Option<PathBuf>) -> Self {
+        self.ca_file = path;
+        self
+    }
+
+    /// Whether to verify the registry's TLS certificate (default true)
+    pub fn with_strict_ssl(mut self, strict: bool) -> Self {
+        self.strict_ssl = strict;
+        self
+    }
+
+    /// The registry base URL
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Published versions of `name`, oldest first
+    ///
+    /// A package the registry doesn't know has no versions. Yanked crates
+    /// and versions that aren't valid semver are left out.
+    pub fn versions(&self, name: &str) -> Result<Vec<Version>> {
+        if let Some(versions) = self.lock().get(name) {
+            return Ok(versions.clone());
+        }
+
+        let url = self.package_url(name);
+        debug!(package = name, url = %url, "fetching published versions");
+        let mut versions = match self.fetch(&url)? {
+            Some(body) => self.parse(&url, &body)?,
+            None => Vec::new(),
+        };
+        versions.sort();
+        versions.dedup();
+
+        self.lock().insert(name.to_string(), versions.clone());
+        Ok(versions)
+    }
+
+    /// GET `url`, returning `None` for 404
+    fn fetch(&self, url: &str) -> Result<Option<String>> {
+        let failed = |reason: String| AdapterError::RegistryRequestFailed {
+            url: url.to_string(),
+            reason,
+        };
+
+        let mut builder = Client::builder()
+            .user_agent(concat!("canaveral/", env!("CARGO_PKG_VERSION")))
+            .danger_accept_invalid_certs(!self.strict_ssl);
+        if let Some(path) = &self.ca_file {
+            let pem = std::fs::read(path)
+                .map_err(|e| failed(format!("cannot read CA file {}: {}", path.display(), e)))?;
+            for cert in reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| failed(format!("invalid CA file {}: {}", path.display(), e)))?
+            {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+        let client = builder.build().map_err(|e| failed(e.to_string()))?;
+        let mut request = client.get(url).header("Accept", self.accept());
+        if let Some(authorization) = &self.authorization {
+            request = request.header("Authorization", authorization);
+        }
+        let response = request.send().map_err(|e| failed(e.to_string()))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => response
+                .text()
+                .map(Some)
+                .map_err(|e| failed(e.to_string()).into()),
+            status => Err(failed(format!("HTTP {}", status)).into()),
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Vec<Version>>> {
+        self.cache.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn package_url(&self, name: &str) -> String {
+        match self.protocol {
+            RegistryProtocol::Npm => format!("{}/{}", self.base_url, name.replace('/', "%2F")),
+            RegistryProtocol::CratesIndex => {
+                format!("{}/{}", self.base_url, crates_index_path(name))
+            }
+            RegistryProtocol::PypiSimple => {
+                format!("{}/{}/", self.base_url, normalize_pypi_name(name))
+            }
+        }
+    }
+
+    fn accept(&self) -> &'static str {
+        match self.protocol {
+            // Abbreviated package document, enough for versions
+            RegistryProtocol::Npm => "application/vnd.npm.install-v1+json",
+            RegistryProtocol::CratesIndex => "text/plain",
+            RegistryProtocol::PypiSimple => "application/vnd.pypi.simple.v1+json",
+        }
+    }
+
+    fn parse(&self, url: &str, body: &str) -> Result<Vec<Version>> {
+        let invalid = |e: serde_json::Error| AdapterError::RegistryRequestFailed {
+            url: url.to_string(),
+            reason: format!("invalid response: {}", e),
+        };
+        Ok(match self.protocol {
+            RegistryProtocol::Npm => parse_npm_versions(body).map_err(invalid)?,
+            RegistryProtocol::CratesIndex => parse_crates_index(body).map_err(invalid)?,
+            RegistryProtocol::PypiSimple => parse_pypi_versions(body).map_err(invalid)?,
+        })
+    }
+}
+
+/// The highest published version that isn't a pre-release
+pub fn latest_release(versions: &[Version]) -> Option<&Version> {
+    versions.iter().filter(|v| v.pre.is_empty()).max()
+}
+
+/// Path of a crate's file in a Cargo index
+fn crates_index_path(name: &str) -> String {
+    let name = name.to_lowercase();
+    match name.len() {
+        1 => format!("1/{}", name),
+        2 => format!("2/{}", name),
+        3 => format!("3/{}/{}", &name[..1], name),
+        _ => format!("{}/{}/{}", &name[..2], &name[2..4], name),
+    }
+}
+
+/// PEP 503 name normalization
+pub(crate) fn normalize_pypi_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
+fn parse_npm_versions(body: &str) -> serde_json::Result<Vec<Version>> {
+    #[derive(Deserialize)]
+    struct Packument {
+        #[serde(default)]
+        versions: HashMap<String, serde_json::Value>,
+    }
+
+    let packument: Packument = serde_json::from_str(body)?;
+    Ok(packument
+        .versions
+        .keys()
+        .filter_map(|v| Version::parse(v).ok())
+        .collect())
+}
+
+fn parse_crates_index(body: &str) -> serde_json::Result<Vec<Version>> {
+    #[derive(Deserialize)]
+    struct IndexEntry {
+        vers: String,
+        #[serde(default)]
+        yanked: bool,
+    }
+
+    let mut versions = Vec::new();
+    for line in body.lines().filter(|l| !l.trim().is_empty()) {
+        let entry: IndexEntry = serde_json::from_str(line)?;
+        if entry.yanked {
+            continue;
+        }
+        if let Ok(version) = Version::parse(&entry.vers) {
+            versions.push(version);
+        }
+    }
+    Ok(versions)
+}
+
+fn parse_pypi_versions(body: &str) -> serde_json::Result<Vec<Version>> {
+    #[derive(Deserialize)]
+    struct Project {
+        #[serde(default)]
+        versions: Vec<String>,
+    }
+
+    let project: Project = serde_json::from_str(body)?;
+    Ok(project
+        .versions
+        .iter()
+        .filter_map(|v| pep440_release(v))
+        .collect())
+}
+
+/// A PEP 440 final release (`1.2`, `1.2.3`) as semver; other versions are skipped
+fn pep440_release(version: &str) -> Option<Version> {
+    let parts: Vec<u64> = version
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+    match parts[..] {
+        [major] => Some(Version::new(major, 0, 0)),
+        [major, minor] => Some(Version::new(major, minor, 0)),
+        [major, minor, patch] => Some(Version::new(major, minor, patch)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_test_support::{MockResponse, MockServer};
+
+    #[test]
+    fn test_crates_index_path() {
+        assert_eq!(crates_index_path("a"), "1/a");
+        assert_eq!(crates_index_path("io"), "2/io");
+        assert_eq!(crates_index_path("syn"), "3/s/syn");
+        assert_eq!(crates_index_path("Serde_json"), "se/rd/serde_json");
+    }
+
+    #[test]
+    fn test_pypi_versions() {
+        assert_eq!(normalize_pypi_name("My_Package.Name"), "my-package-name");
+        let body =
+            r#"{"name": "demo", "versions": ["0.9", "1.0.0", "1.1.0rc1", "2.0.1.post1", "1.2.3"]}"#;
+        let versions = parse_pypi_versions(body).unwrap();
+        assert_eq!(
+            versions,
+            [
+                Version::new(0, 9, 0),
+                Version::new(1, 0, 0),
+                Version::new(1, 2, 3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_versions_are_cached_and_missing_packages_are_empty() {
+        let index = [
+            r#"{"name":"demo","vers":"0.1.0","yanked":false}"#,
+            r#"{"name":"demo","vers":"0.3.0","yanked":true}"#,
+            r#"{"name":"demo","vers":"0.2.0"}"#,
+        ]
+        .join("\n");
+        let server = MockServer::serve(vec![
+            MockResponse::status("200 OK", index),
+            MockResponse::status("404 Not Found", String::new()),
+        ]);
+        let registry = RegistryVersions::new(RegistryProtocol::CratesIndex, server.url());
+
+        let expected = [Version::new(0, 1, 0), Version::new(0, 2, 0)];
+        assert_eq!(registry.versions("demo").unwrap(), expected);
+        // Served from the cache
+        assert_eq!(registry.versions("demo").unwrap(), expected);
+        assert!(registry.versions("nope").unwrap().is_empty());
+
+        assert_eq!(
+            server
+                .requests()
+                .iter()
+                .map(|r| r.path.as_str())
+                .collect::<Vec<_>>(),
+            ["/de/mo/demo", "/no/pe/nope"]
+        );
+    }
+
+    #[test]
+    fn test_latest_release_skips_prereleases() {
+        let versions = [
+            Version::parse("1.0.0").unwrap(),
+            Version::parse("1.2.0").unwrap(),
+            Version::parse("2.0.0-beta.1").unwrap(),
+        ];
+        assert_eq!(
+            latest_release(&versions),
+            Some(&Version::parse("1.2.0").unwrap())
+        );
+        assert_eq!(latest_release(&[]), None);
+    }
+}
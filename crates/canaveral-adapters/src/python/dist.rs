@@ -0,0 +1,164 @@
+//! Contents of built Python distributions (wheels and sdists)
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::path::{Component, Path, PathBuf};
+
+use canaveral_core::error::{AdapterError, Result};
+
+/// Wheels and sdists in `dist` built for `version`
+pub(super) fn find_artifacts(dist: &Path, version: &str) -> Result<Vec<PathBuf>> {
+    if !dist.exists() {
+        return Ok(Vec::new());
+    }
+
+    let marker = format!("-{}", version);
+    let mut artifacts = Vec::new();
+    for entry in std::fs::read_dir(dist)? {
+        let path = entry?.path();
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        if (name.ends_with(".whl") || name.ends_with(".tar.gz")) && name.contains(&marker) {
+            artifacts.push(path);
+        }
+    }
+    artifacts.sort();
+    Ok(artifacts)
+}
+
+/// Union of the file paths inside the given archives
+///
+/// Sdist entries are reported without their `name-version/` top-level
+/// directory so they line up with the project layout.
+pub(super) fn list_archives(archives: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut files = BTreeSet::new();
+    for archive in archives {
+        let name = archive.to_string_lossy();
+        if name.ends_with(".whl") {
+            files.extend(list_wheel(archive)?);
+        } else if name.ends_with(".tar.gz") {
+            files.extend(list_sdist(archive)?);
+        }
+    }
+    Ok(files.into_iter().collect())
+}
+
+fn list_wheel(path: &Path) -> Result<Vec<PathBuf>> {
+    let mut archive = zip::ZipArchive::new(File::open(path)?)
+        .map_err(|e| invalid_archive(path, e.to_string()))?;
+
+    let mut files = Vec::new();
+    for i in 0..archive.len() {
+        let entry = archive
+            .by_index(i)
+            .map_err(|e| invalid_archive(path, e.to_string()))?;
+        if !entry.is_dir() {
+            files.push(PathBuf::from(entry.name()));
+        }
+    }
+    Ok(files)
+}
+
+fn list_sdist(path: &Path) -> Result<Vec<PathBuf>> {
+    let decoder = flate2::read::GzDecoder::new(File::open(path)?);
+    let mut archive = tar::Archive::new(decoder);
+
+    let mut files = Vec::new();
+    for entry in archive
+        .entries()
+        .map_err(|e| invalid_archive(path, e.to_string()))?
+    {
+        let entry = entry.map_err(|e| invalid_archive(path, e.to_string()))?;
+        if entry.header().entry_type().is_dir() {
+            continue;
+        }
+        let entry_path = entry
+            .path()
+            .map_err(|e| invalid_archive(path, e.to_string()))?;
+        let relative: PathBuf = entry_path
+            .components()
+            .skip(1)
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .collect();
+        if relative.as_os_str().is_empty() {
+            continue;
+        }
+        files.push(relative);
+    }
+    Ok(files)
+}
+
+fn invalid_archive(path: &Path, reason: String) -> AdapterError {
+    AdapterError::CommandFailed {
+        command: format!("read {}", path.display()),
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_wheel(path: &Path, entries: &[&str]) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        for entry in entries {
+            zip.start_file(*entry, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            zip.write_all(b"x").unwrap();
+        }
+        zip.finish().unwrap();
+    }
+
+    fn write_sdist(path: &Path, entries: &[&str]) {
+        let encoder =
+            flate2::write::GzEncoder::new(File::create(path).unwrap(), Default::default());
+        let mut builder = tar::Builder::new(encoder);
+        for entry in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(1);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, entry, &b"x"[..]).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_list_wheel_and_sdist() {
+        let temp = TempDir::new().unwrap();
+        let dist = temp.path().join("dist");
+        std::fs::create_dir(&dist).unwrap();
+
+        write_wheel(
+            &dist.join("mypkg-1.0.0-py3-none-any.whl"),
+            &["mypkg/__init__.py", "mypkg-1.0.0.dist-info/METADATA"],
+        );
+        write_sdist(
+            &dist.join("mypkg-1.0.0.tar.gz"),
+            &[
+                "mypkg-1.0.0/pyproject.toml",
+                "mypkg-1.0.0/mypkg/__init__.py",
+                "mypkg-1.0.0/.env",
+            ],
+        );
+        write_wheel(&dist.join("mypkg-0.9.0-py3-none-any.whl"), &["old.py"]);
+
+        let artifacts = find_artifacts(&dist, "1.0.0").unwrap();
+        assert_eq!(artifacts.len(), 2);
+
+        let files = list_archives(&artifacts).unwrap();
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from(".env"),
+                PathBuf::from("mypkg/__init__.py"),
+                PathBuf::from("mypkg-1.0.0.dist-info/METADATA"),
+                PathBuf::from("pyproject.toml"),
+            ]
+        );
+    }
+}
@@ -67,12 +67,104 @@ impl PyProjectToml {
         self.doc.get("build-system").is_some()
     }
 
+    /// Workspace packages this project depends on through a local path
+    ///
+    /// Covers `name @ file:...` references in `[project].dependencies`, path
+    /// dependencies in Poetry's dependency tables and path or workspace
+    /// sources in `[tool.uv.sources]`. Names are compared normalized, so
+    /// `My_Pkg` matches `my-pkg`.
+    pub fn workspace_dependencies(&self, known_packages: &[String]) -> Vec<String> {
+        let mut local: Vec<String> = Vec::new();
+
+        let requirements = self
+            .doc
+            .get("project")
+            .and_then(|p| p.get("dependencies"))
+            .and_then(|d| d.as_array());
+        for requirement in requirements
+            .into_iter()
+            .flatten()
+            .filter_map(|r| r.as_str())
+        {
+            if let Some((name, url)) = requirement.split_once('@') {
+                if url.trim().starts_with("file:") {
+                    let name = name.split('[').next().unwrap_or(name);
+                    local.push(name.trim().to_string());
+                }
+            }
+        }
+
+        let tool = self.doc.get("tool");
+        let poetry = tool.and_then(|t| t.get("poetry"));
+        let groups = poetry
+            .and_then(|p| p.get("group"))
+            .and_then(|g| g.as_table_like())
+            .into_iter()
+            .flat_map(|groups| {
+                groups
+                    .iter()
+                    .filter_map(|(_, group)| group.get("dependencies"))
+            });
+        let poetry_tables = ["dependencies", "dev-dependencies"]
+            .into_iter()
+            .filter_map(|key| poetry.and_then(|p| p.get(key)))
+            .chain(groups);
+        let uv_sources = tool
+            .and_then(|t| t.get("uv"))
+            .and_then(|u| u.get("sources"));
+
+        for table in poetry_tables
+            .chain(uv_sources)
+            .filter_map(|t| t.as_table_like())
+        {
+            for (name, spec) in table.iter() {
+                let Some(spec) = spec.as_table_like() else {
+                    continue;
+                };
+                let workspace = spec
+                    .get("workspace")
+                    .and_then(|w| w.as_bool())
+                    .unwrap_or(false);
+                if spec.contains_key("path") || workspace {
+                    local.push(name.to_string());
+                }
+            }
+        }
+
+        let mut deps: Vec<String> = known_packages
+            .iter()
+            .filter(|known| {
+                let known = normalize_name(known);
+                local.iter().any(|name| normalize_name(name) == known)
+            })
+            .cloned()
+            .collect();
+        deps.sort();
+        deps.dedup();
+        deps
+    }
+
     /// Access the underlying document
     pub fn doc(&self) -> &DocumentMut {
         &self.doc
     }
 }
 
+/// Normalize a distribution name as in PEP 503
+fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.trim().chars() {
+        if matches!(c, '-' | '_' | '.') {
+            if !normalized.ends_with('-') {
+                normalized.push('-');
+            }
+        } else {
+            normalized.push(c.to_ascii_lowercase());
+        }
+    }
+    normalized
+}
+
 impl ManifestFile for PyProjectToml {
     fn filename() -> &'static str {
         "pyproject.toml"
@@ -1,5 +1,6 @@
 //! Python package adapter
 
+mod dist;
 mod manifest;
 
 use std::path::{Path, PathBuf};
@@ -11,18 +12,30 @@ use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
+use crate::lock::with_manifest_lock;
 use crate::manifest::ManifestFile;
 use crate::publish::{PublishOptions, ValidationResult};
-use crate::traits::PackageAdapter;
+use crate::published::{RegistryProtocol, RegistryVersions, PYPI_SIMPLE_URL};
+use crate::traits::{PackageAdapter, RequiredTool};
 pub use manifest::PyProjectToml;
 
 /// Python package adapter (using pyproject.toml)
-pub struct PythonAdapter;
+pub struct PythonAdapter {
+    published: RegistryVersions,
+}
 
 impl PythonAdapter {
     /// Create a new Python adapter
     pub fn new() -> Self {
-        Self
+        Self {
+            published: RegistryVersions::new(RegistryProtocol::PypiSimple, PYPI_SIMPLE_URL),
+        }
+    }
+
+    /// Read published versions from the simple API at `url` instead of PyPI
+    pub fn with_index_url(mut self, url: impl Into<String>) -> Self {
+        self.published = RegistryVersions::new(RegistryProtocol::PypiSimple, url);
+        self
     }
 
     /// Get the pyproject.toml path
@@ -56,6 +69,12 @@ impl PackageAdapter for PythonAdapter {
         "https://upload.pypi.org/legacy/"
     }
 
+    /// Only final releases are returned, as semver; PEP 440 pre-releases
+    /// and post-releases are skipped
+    fn published_versions(&self, _path: &Path, name: &str) -> Result<Vec<semver::Version>> {
+        self.published.versions(name)
+    }
+
     fn detect(&self, path: &Path) -> bool {
         let manifest_path = self.manifest_path(path);
         if !manifest_path.exists() {
@@ -77,6 +96,18 @@ impl PackageAdapter for PythonAdapter {
         &["pyproject.toml"]
     }
 
+    fn required_tools(&self) -> &[RequiredTool] {
+        const TOOLS: &[RequiredTool] = &[
+            RequiredTool::new(
+                "python",
+                &["--version"],
+                "Install Python from https://www.python.org",
+            ),
+            RequiredTool::new("twine", &["--version"], "pip install twine"),
+        ];
+        TOOLS
+    }
+
     fn get_info(&self, path: &Path) -> Result<PackageInfo> {
         let manifest_path = self.manifest_path(path);
         let manifest = self.load_manifest(path)?;
@@ -111,17 +142,20 @@ impl PackageAdapter for PythonAdapter {
 
     fn set_version(&self, path: &Path, version: &str) -> Result<()> {
         info!(adapter = "python", version, path = %path.display(), "setting version");
-        let mut manifest = self.load_manifest(path)?;
-        manifest
-            .set_version(version)
-            .map_err(|e| AdapterError::ManifestParseError(e.to_string()))?;
-        manifest
-            .save_to_path(&self.manifest_path(path))
-            .map_err(|e| AdapterError::ManifestUpdateError(e.to_string()).into())
+        with_manifest_lock(&self.manifest_path(path), || {
+            let mut manifest = self.load_manifest(path)?;
+            manifest
+                .set_version(version)
+                .map_err(|e| AdapterError::ManifestParseError(e.to_string()))?;
+            manifest
+                .save_to_path(&self.manifest_path(path))
+                .map_err(|e| AdapterError::ManifestUpdateError(e.to_string()).into())
+        })
     }
 
     fn publish_with_options(&self, path: &Path, options: &PublishOptions) -> Result<()> {
         info!(adapter = "python", path = %path.display(), dry_run = options.dry_run, "publishing package");
+        let options = &options.clone().with_legacy_extras();
         // Build first (unless already built)
         let dist = self.dist_path(path);
         if !dist.exists() || std::fs::read_dir(&dist).map(|d| d.count()).unwrap_or(0) == 0 {
@@ -132,6 +166,7 @@ impl PackageAdapter for PythonAdapter {
             // For dry run, just check the package with twine
             let check_output = Command::new("twine")
                 .args(["check", "dist/*"])
+                .envs(&options.env)
                 .current_dir(path)
                 .output()
                 .map_err(|e| AdapterError::CommandFailed {
@@ -150,6 +185,7 @@ impl PackageAdapter for PythonAdapter {
         // Publish with twine
         let mut cmd = Command::new("twine");
         cmd.arg("upload");
+        cmd.envs(&options.env);
         cmd.current_dir(path);
 
         // Repository URL
@@ -166,11 +202,7 @@ impl PackageAdapter for PythonAdapter {
         }
 
         // Skip existing (useful for retries)
-        if options
-            .extra
-            .get("skip_existing")
-            .is_some_and(|v| v == "true")
-        {
+        if options.skip_existing {
             cmd.arg("--skip-existing");
         }
 
@@ -300,6 +332,14 @@ impl PackageAdapter for PythonAdapter {
         Ok(false)
     }
 
+    fn supports_fmt(&self) -> bool {
+        true
+    }
+
+    fn supports_lint(&self) -> bool {
+        true
+    }
+
     fn fmt(&self, path: &Path, check: bool) -> Result<()> {
         let mut cmd = Command::new("ruff");
         cmd.arg("format").current_dir(path);
@@ -436,6 +476,30 @@ impl PackageAdapter for PythonAdapter {
         Ok(())
     }
 
+    fn workspace_dependencies(
+        &self,
+        path: &Path,
+        known_packages: &[String],
+    ) -> Result<Vec<String>> {
+        Ok(self
+            .load_manifest(path)?
+            .workspace_dependencies(known_packages))
+    }
+
+    fn list_publish_files(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let version = self.get_version(path)?;
+        let dist = self.dist_path(path);
+
+        let mut artifacts = dist::find_artifacts(&dist, &version)?;
+        if artifacts.is_empty() {
+            debug!(adapter = "python", version = %version, "no built distributions, building");
+            self.build(path)?;
+            artifacts = dist::find_artifacts(&dist, &version)?;
+        }
+
+        dist::list_archives(&artifacts)
+    }
+
     fn pack(&self, path: &Path) -> Result<Option<PathBuf>> {
         // Build the package
         self.build(path)?;
@@ -5,8 +5,13 @@ use std::sync::Arc;
 
 use tracing::debug;
 
+use canaveral_core::config::Config;
+use canaveral_core::error::Result;
+
 use crate::cargo::CargoAdapter;
+use crate::cocoapods::CocoaPodsAdapter;
 use crate::docker::DockerAdapter;
+use crate::generic::GenericAdapter;
 use crate::go::GoAdapter;
 use crate::maven::MavenAdapter;
 use crate::npm::NpmAdapter;
@@ -16,6 +21,8 @@ use crate::traits::PackageAdapter;
 /// Registry of available package adapters
 pub struct AdapterRegistry {
     adapters: Vec<Arc<dyn PackageAdapter>>,
+    /// Adapters configured for specific packages, by package name
+    configured: Vec<(String, Arc<dyn PackageAdapter>)>,
 }
 
 impl AdapterRegistry {
@@ -29,14 +36,44 @@ impl AdapterRegistry {
                 Arc::new(GoAdapter::new()),
                 Arc::new(MavenAdapter::new()),
                 Arc::new(DockerAdapter::new()),
+                Arc::new(CocoaPodsAdapter::new()),
             ],
+            configured: Vec::new(),
+        }
+    }
+
+    /// Create a registry with the built-in adapters set up from `config`,
+    /// a [`GenericAdapter`] for each configured package with a
+    /// `version_source` and a [`CargoAdapter`] for each `cargo` package
+    /// with `release_assets`
+    ///
+    /// Configured adapters are detected first, so they win over a manifest
+    /// in the same directory.
+    pub fn from_config(config: &Config) -> Result<Self> {
+        let mut registry = Self::new();
+        registry.replace(Arc::new(
+            MavenAdapter::new().with_versions_plugin(config.maven.use_versions_plugin),
+        ));
+        for package in &config.packages {
+            if let Some(adapter) = GenericAdapter::from_config(package)? {
+                registry
+                    .configured
+                    .push((package.name.clone(), Arc::new(adapter)));
+            }
+            if let Some(adapter) = CargoAdapter::from_config(package) {
+                registry
+                    .configured
+                    .push((package.name.clone(), Arc::new(adapter)));
+            }
         }
+        Ok(registry)
     }
 
     /// Create an empty registry
     pub fn empty() -> Self {
         Self {
             adapters: Vec::new(),
+            configured: Vec::new(),
         }
     }
 
@@ -45,6 +82,17 @@ impl AdapterRegistry {
         self.adapters.push(Arc::new(adapter));
     }
 
+    /// Replace the built-in adapter with the same name
+    fn replace(&mut self, adapter: Arc<dyn PackageAdapter>) {
+        if let Some(slot) = self
+            .adapters
+            .iter_mut()
+            .find(|a| a.name() == adapter.name())
+        {
+            *slot = adapter;
+        }
+    }
+
     /// Get adapter by name
     pub fn get(&self, name: &str) -> Option<Arc<dyn PackageAdapter>> {
         let result = self.adapters.iter().find(|a| a.name() == name).cloned();
@@ -56,9 +104,25 @@ impl AdapterRegistry {
         result
     }
 
+    /// Adapter for the package `name` of `package_type`: the one configured
+    /// for it, else the adapter for its type
+    pub fn for_package(&self, name: &str, package_type: &str) -> Option<Arc<dyn PackageAdapter>> {
+        self.configured
+            .iter()
+            .find(|(package, _)| package == name)
+            .map(|(_, adapter)| adapter.clone())
+            .or_else(|| self.get(package_type))
+    }
+
     /// Detect which adapter applies to a path
     pub fn detect(&self, path: &Path) -> Option<Arc<dyn PackageAdapter>> {
-        let result = self.adapters.iter().find(|a| a.detect(path)).cloned();
+        let result = self
+            .configured
+            .iter()
+            .map(|(_, adapter)| adapter)
+            .chain(&self.adapters)
+            .find(|a| a.detect(path))
+            .cloned();
         debug!(
             path = %path.display(),
             adapter = result.as_ref().map(|a| a.name()),
@@ -104,9 +168,115 @@ mod tests {
         assert!(registry.get("go").is_some());
         assert!(registry.get("maven").is_some());
         assert!(registry.get("docker").is_some());
+        assert!(registry.get("cocoapods").is_some());
         assert!(registry.get("unknown").is_none());
     }
 
+    #[test]
+    fn test_generic_adapters_from_config() {
+        use canaveral_core::config::{PackageConfig, VersionSourceConfig};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        for (dir, version) in [("lib", "1.2.3"), ("tool", "0.4.0")] {
+            std::fs::create_dir_all(temp.path().join(dir)).unwrap();
+            std::fs::write(
+                temp.path().join(dir).join("version.h"),
+                format!("#define VERSION \"{}\"\n", version),
+            )
+            .unwrap();
+        }
+        let package = |name: &str| PackageConfig {
+            name: name.to_string(),
+            path: name.into(),
+            package_type: "generic".to_string(),
+            publish: false,
+            registry: None,
+            tag_format: None,
+            version_files: Vec::new(),
+            version_mirrors: Vec::new(),
+            version_source: Some(VersionSourceConfig {
+                file: "version.h".into(),
+                pattern: r#"#define VERSION "([^"]+)""#.to_string(),
+                template: None,
+            }),
+            release_assets: None,
+        };
+        let config = Config {
+            packages: vec![package("lib"), package("tool")],
+            ..Config::default()
+        };
+
+        let registry = AdapterRegistry::from_config(&config).unwrap();
+        let tool = temp.path().join("tool");
+        let adapter = registry.detect(&tool).unwrap();
+        assert_eq!(adapter.name(), "generic");
+        assert_eq!(adapter.get_version(&tool).unwrap(), "0.4.0");
+        let adapter = registry.for_package("tool", "generic").unwrap();
+        assert_eq!(adapter.get_info(&tool).unwrap().name, "tool");
+        assert_eq!(registry.for_package("web", "npm").unwrap().name(), "npm");
+
+        // Broken patterns are config errors
+        let mut broken = package("lib");
+        broken.version_source.as_mut().unwrap().pattern = "no group".to_string();
+        let config = Config {
+            packages: vec![broken],
+            ..Config::default()
+        };
+        assert!(AdapterRegistry::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_cargo_adapters_from_config() {
+        use canaveral_core::config::{PackageConfig, ReleaseAssetsConfig};
+
+        let temp = tempfile::TempDir::new().unwrap();
+        for name in ["cli", "core"] {
+            std::fs::create_dir_all(temp.path().join(name)).unwrap();
+            std::fs::write(
+                temp.path().join(name).join("Cargo.toml"),
+                format!("[package]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+            )
+            .unwrap();
+        }
+        let package = |name: &str, release_assets| PackageConfig {
+            name: name.to_string(),
+            path: name.into(),
+            package_type: "cargo".to_string(),
+            publish: true,
+            registry: None,
+            tag_format: None,
+            version_files: Vec::new(),
+            version_mirrors: Vec::new(),
+            version_source: None,
+            release_assets,
+        };
+        let assets = ReleaseAssetsConfig {
+            targets: vec!["x86_64-unknown-linux-gnu".to_string()],
+            assets: vec!["cli-x86_64-unknown-linux-gnu.tgz".to_string()],
+        };
+        let config = Config {
+            packages: vec![package("cli", Some(assets)), package("core", None)],
+            ..Config::default()
+        };
+
+        let registry = AdapterRegistry::from_config(&config).unwrap();
+        assert_eq!(registry.configured.len(), 1);
+        let configured = registry.configured[0].1.clone();
+        assert!(Arc::ptr_eq(
+            &registry.detect(&temp.path().join("cli")).unwrap(),
+            &configured
+        ));
+        assert!(Arc::ptr_eq(
+            &registry.for_package("cli", "cargo").unwrap(),
+            &configured
+        ));
+
+        // Other crates fall through to the built-in adapter
+        let core = registry.detect(&temp.path().join("core")).unwrap();
+        assert_eq!(core.name(), "cargo");
+        assert!(!Arc::ptr_eq(&core, &configured));
+    }
+
     #[test]
     fn test_adapter_names() {
         let registry = AdapterRegistry::new();
@@ -0,0 +1,300 @@
+//! Cargo dependencies from `cargo metadata`
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+use serde::Deserialize;
+
+use canaveral_core::error::{AdapterError, Result};
+
+use super::{Component, Sbom};
+
+#[derive(Debug, Deserialize)]
+struct Metadata {
+    packages: Vec<Package>,
+    resolve: Option<Resolve>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Package {
+    id: String,
+    name: String,
+    version: String,
+    license: Option<String>,
+    manifest_path: std::path::PathBuf,
+}
+
+#[derive(Debug, Deserialize)]
+struct Resolve {
+    root: Option<String>,
+    nodes: Vec<Node>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Node {
+    id: String,
+    #[serde(default)]
+    deps: Vec<NodeDep>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NodeDep {
+    pkg: String,
+    #[serde(default)]
+    dep_kinds: Vec<DepKind>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DepKind {
+    kind: Option<String>,
+}
+
+/// Generate an SBOM for the crate at `path` by running `cargo metadata`
+pub fn cargo_sbom(path: &Path) -> Result<Sbom> {
+    let output = Command::new("cargo")
+        .args(["metadata", "--format-version", "1"])
+        .current_dir(path)
+        .output()
+        .map_err(|e| AdapterError::CommandFailed {
+            command: "cargo metadata".to_string(),
+            reason: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(AdapterError::CommandFailed {
+            command: "cargo metadata".to_string(),
+            reason: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+        .into());
+    }
+
+    sbom_from_cargo_metadata(&String::from_utf8_lossy(&output.stdout), path)
+}
+
+/// Build an SBOM from `cargo metadata --format-version 1` output
+///
+/// The root is the resolved root package, or the package whose manifest is
+/// in `path` for workspaces. Components are the packages it depends on,
+/// directly or transitively, excluding dev-dependencies, each listing its
+/// own direct dependencies.
+pub fn sbom_from_cargo_metadata(json: &str, path: &Path) -> Result<Sbom> {
+    let metadata: Metadata = serde_json::from_str(json)
+        .map_err(|e| AdapterError::ManifestParseError(format!("cargo metadata: {}", e)))?;
+    let resolve = metadata.resolve.ok_or_else(|| {
+        AdapterError::ManifestParseError("cargo metadata has no dependency resolution".to_string())
+    })?;
+
+    let packages: HashMap<&str, &Package> = metadata
+        .packages
+        .iter()
+        .map(|p| (p.id.as_str(), p))
+        .collect();
+    let manifest = path.join("Cargo.toml");
+    let root = resolve
+        .root
+        .as_deref()
+        .and_then(|id| packages.get(id).copied())
+        .or_else(|| {
+            metadata
+                .packages
+                .iter()
+                .find(|p| p.manifest_path == manifest)
+        })
+        .ok_or_else(|| {
+            AdapterError::ManifestParseError(format!(
+                "no package at {} (virtual workspace?)",
+                path.display()
+            ))
+        })?;
+
+    let nodes: HashMap<&str, &Node> = resolve.nodes.iter().map(|n| (n.id.as_str(), n)).collect();
+    // Non-dev dependencies of a package, by package ID
+    let deps = |id: &str| -> Vec<&str> {
+        nodes.get(id).map_or_else(Vec::new, |node| {
+            node.deps
+                .iter()
+                .filter(|dep| {
+                    dep.dep_kinds.is_empty()
+                        || dep
+                            .dep_kinds
+                            .iter()
+                            .any(|k| k.kind.as_deref() != Some("dev"))
+                })
+                .map(|dep| dep.pkg.as_str())
+                .collect()
+        })
+    };
+    let with_deps = |package: &Package| {
+        component(package).with_dependencies(
+            deps(&package.id)
+                .into_iter()
+                .filter_map(|id| packages.get(id))
+                .map(|p| purl(p))
+                .collect(),
+        )
+    };
+
+    let mut seen: HashSet<&str> = HashSet::from([root.id.as_str()]);
+    let mut queue = vec![root.id.as_str()];
+    let mut components = Vec::new();
+    while let Some(id) = queue.pop() {
+        for dep in deps(id) {
+            if !seen.insert(dep) {
+                continue;
+            }
+            queue.push(dep);
+            if let Some(package) = packages.get(dep) {
+                components.push(with_deps(package));
+            }
+        }
+    }
+
+    Ok(Sbom::new(with_deps(root), components))
+}
+
+fn component(package: &Package) -> Component {
+    Component::new(
+        &package.name,
+        &package.version,
+        package.license.clone(),
+        purl(package),
+    )
+}
+
+fn purl(package: &Package) -> String {
+    format!("pkg:cargo/{}@{}", package.name, package.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+        "packages": [
+            {
+                "id": "app 0.3.0 (path+file:///work/app)",
+                "name": "app",
+                "version": "0.3.0",
+                "license": "MIT",
+                "manifest_path": "/work/app/Cargo.toml"
+            },
+            {
+                "id": "serde 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                "name": "serde",
+                "version": "1.0.200",
+                "license": "MIT OR Apache-2.0",
+                "manifest_path": "/registry/serde-1.0.200/Cargo.toml"
+            },
+            {
+                "id": "serde_derive 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                "name": "serde_derive",
+                "version": "1.0.200",
+                "license": "MIT OR Apache-2.0",
+                "manifest_path": "/registry/serde_derive-1.0.200/Cargo.toml"
+            },
+            {
+                "id": "tempfile 3.10.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                "name": "tempfile",
+                "version": "3.10.0",
+                "license": "MIT OR Apache-2.0",
+                "manifest_path": "/registry/tempfile-3.10.0/Cargo.toml"
+            }
+        ],
+        "workspace_members": ["app 0.3.0 (path+file:///work/app)"],
+        "resolve": {
+            "root": "app 0.3.0 (path+file:///work/app)",
+            "nodes": [
+                {
+                    "id": "app 0.3.0 (path+file:///work/app)",
+                    "deps": [
+                        {
+                            "pkg": "serde 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": null, "target": null}]
+                        },
+                        {
+                            "pkg": "tempfile 3.10.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": "dev", "target": null}]
+                        }
+                    ]
+                },
+                {
+                    "id": "serde 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "deps": [
+                        {
+                            "pkg": "serde_derive 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                            "dep_kinds": [{"kind": null, "target": null}]
+                        }
+                    ]
+                },
+                {
+                    "id": "serde_derive 1.0.200 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "deps": []
+                },
+                {
+                    "id": "tempfile 3.10.0 (registry+https://github.com/rust-lang/crates.io-index)",
+                    "deps": []
+                }
+            ]
+        }
+    }"#;
+
+    #[test]
+    fn test_cyclonedx_from_cargo_metadata() {
+        let sbom = sbom_from_cargo_metadata(METADATA, Path::new("/work/app")).unwrap();
+        let doc = sbom.to_cyclonedx();
+
+        assert_eq!(doc["bomFormat"], "CycloneDX");
+        assert_eq!(doc["specVersion"], "1.5");
+        assert!(doc["serialNumber"]
+            .as_str()
+            .unwrap()
+            .starts_with("urn:uuid:"));
+        assert!(doc["metadata"]["timestamp"].is_string());
+
+        let root = &doc["metadata"]["component"];
+        assert_eq!(root["name"], "app");
+        assert_eq!(root["purl"], "pkg:cargo/app@0.3.0");
+
+        // Transitive dependencies are included, dev-dependencies are not
+        let components = doc["components"].as_array().unwrap();
+        let names: Vec<_> = components
+            .iter()
+            .map(|c| c["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(names, ["serde", "serde_derive"]);
+        for component in components {
+            assert_eq!(component["type"], "library");
+            assert!(component["version"].is_string());
+            assert!(component["purl"]
+                .as_str()
+                .unwrap()
+                .starts_with("pkg:cargo/"));
+            assert_eq!(component["licenses"][0]["expression"], "MIT OR Apache-2.0");
+        }
+        // The graph nests: app -> serde -> serde_derive
+        let dependencies = doc["dependencies"].as_array().unwrap();
+        assert_eq!(dependencies.len(), 3);
+        assert_eq!(dependencies[0]["ref"], "pkg:cargo/app@0.3.0");
+        assert_eq!(
+            dependencies[0]["dependsOn"],
+            serde_json::json!(["pkg:cargo/serde@1.0.200"])
+        );
+        assert_eq!(dependencies[1]["ref"], "pkg:cargo/serde@1.0.200");
+        assert_eq!(
+            dependencies[1]["dependsOn"],
+            serde_json::json!(["pkg:cargo/serde_derive@1.0.200"])
+        );
+        assert_eq!(dependencies[2]["dependsOn"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_virtual_workspace_needs_a_member() {
+        let metadata = METADATA.replace(
+            r#""root": "app 0.3.0 (path+file:///work/app)","#,
+            r#""root": null,"#,
+        );
+        assert!(sbom_from_cargo_metadata(&metadata, Path::new("/work")).is_err());
+        let sbom = sbom_from_cargo_metadata(&metadata, Path::new("/work/app")).unwrap();
+        assert_eq!(sbom.root.name, "app");
+    }
+}
@@ -0,0 +1,382 @@
+//! Software bill of materials (SBOM) generation
+//!
+//! Builds an [`Sbom`] listing a package's dependencies from the package
+//! manager's own data (`cargo metadata`, the npm lockfile, installed Python
+//! distributions) and renders it as CycloneDX or SPDX JSON.
+
+mod cargo;
+mod npm;
+mod python;
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde_json::{json, Value};
+use tracing::{debug, info};
+
+use canaveral_core::error::{AdapterError, Result};
+
+pub use cargo::{cargo_sbom, sbom_from_cargo_metadata};
+pub use npm::{npm_sbom, sbom_from_npm_lockfile};
+pub use python::{python_sbom, sbom_from_site_packages};
+
+/// CycloneDX specification version written by [`Sbom::to_cyclonedx`]
+pub const CYCLONEDX_SPEC_VERSION: &str = "1.5";
+
+/// SPDX specification version written by [`Sbom::to_spdx`]
+pub const SPDX_VERSION: &str = "SPDX-2.3";
+
+/// SBOM document formats
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SbomFormat {
+    /// CycloneDX JSON
+    #[default]
+    CycloneDx,
+    /// SPDX JSON
+    Spdx,
+}
+
+impl SbomFormat {
+    /// Conventional file extension for the format
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::CycloneDx => "cdx.json",
+            Self::Spdx => "spdx.json",
+        }
+    }
+}
+
+/// A package in an SBOM
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Component {
+    /// Package name
+    pub name: String,
+    /// Package version
+    pub version: String,
+    /// Declared license, ideally an SPDX expression
+    pub license: Option<String>,
+    /// Package URL (`pkg:cargo/serde@1.0.0`)
+    pub purl: String,
+    /// Purls of the components this one depends on directly
+    pub dependencies: Vec<String>,
+}
+
+impl Component {
+    /// Create a component
+    pub fn new(
+        name: impl Into<String>,
+        version: impl Into<String>,
+        license: Option<String>,
+        purl: impl Into<String>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            license: license.filter(|l| !l.trim().is_empty()),
+            purl: purl.into(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Set the purls of the components this one depends on directly
+    pub fn with_dependencies(mut self, mut dependencies: Vec<String>) -> Self {
+        dependencies.sort();
+        dependencies.dedup();
+        self.dependencies = dependencies;
+        self
+    }
+}
+
+/// Bill of materials for one package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sbom {
+    /// The package the SBOM describes
+    pub root: Component,
+    /// Its dependencies, sorted by name and version
+    pub components: Vec<Component>,
+}
+
+impl Sbom {
+    /// Create an SBOM, sorting and de-duplicating `components`
+    ///
+    /// Dependencies on purls that aren't in the SBOM are dropped, so every
+    /// edge of the dependency graph points at a listed component.
+    pub fn new(mut root: Component, mut components: Vec<Component>) -> Self {
+        components.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+        components.dedup_by(|a, b| a.purl == b.purl);
+
+        let purls: HashSet<String> = components.iter().map(|c| c.purl.clone()).collect();
+        for component in std::iter::once(&mut root).chain(&mut components) {
+            component.dependencies.retain(|purl| purls.contains(purl));
+        }
+        Self { root, components }
+    }
+
+    /// The root followed by its dependencies
+    fn all_components(&self) -> impl Iterator<Item = &Component> {
+        std::iter::once(&self.root).chain(&self.components)
+    }
+
+    /// Render as a CycloneDX JSON document
+    pub fn to_cyclonedx(&self) -> Value {
+        let component = |c: &Component| {
+            let mut value = json!({
+                "type": "library",
+                "bom-ref": c.purl,
+                "name": c.name,
+                "version": c.version,
+                "purl": c.purl,
+            });
+            if let Some(license) = &c.license {
+                value["licenses"] = json!([cyclonedx_license(license)]);
+            }
+            value
+        };
+
+        json!({
+            "bomFormat": "CycloneDX",
+            "specVersion": CYCLONEDX_SPEC_VERSION,
+            "serialNumber": format!("urn:uuid:{}", uuid::Uuid::new_v4()),
+            "version": 1,
+            "metadata": {
+                "timestamp": timestamp(),
+                "tools": {
+                    "components": [{
+                        "type": "application",
+                        "name": "canaveral",
+                        "version": env!("CARGO_PKG_VERSION"),
+                    }]
+                },
+                "component": component(&self.root),
+            },
+            "components": self
+                .components
+                .iter()
+                .map(component)
+                .collect::<Vec<_>>(),
+            "dependencies": self
+                .all_components()
+                .map(|c| json!({ "ref": c.purl, "dependsOn": c.dependencies }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    /// Render as an SPDX JSON document
+    pub fn to_spdx(&self) -> Value {
+        let package = |c: &Component, id: &str| {
+            json!({
+                "name": c.name,
+                "SPDXID": id,
+                "versionInfo": c.version,
+                "downloadLocation": "NOASSERTION",
+                "licenseConcluded": "NOASSERTION",
+                "licenseDeclared": c
+                    .license
+                    .as_deref()
+                    .filter(|l| is_spdx_expression(l))
+                    .unwrap_or("NOASSERTION"),
+                "externalRefs": [{
+                    "referenceCategory": "PACKAGE-MANAGER",
+                    "referenceType": "purl",
+                    "referenceLocator": c.purl,
+                }],
+            })
+        };
+
+        let ids: HashMap<&str, String> = self
+            .all_components()
+            .enumerate()
+            .map(|(i, c)| (c.purl.as_str(), format!("SPDXRef-Package-{}", i)))
+            .collect();
+        let packages: Vec<Value> = self
+            .all_components()
+            .map(|c| package(c, &ids[c.purl.as_str()]))
+            .collect();
+        let mut relationships = vec![json!({
+            "spdxElementId": "SPDXRef-DOCUMENT",
+            "relationshipType": "DESCRIBES",
+            "relatedSpdxElement": ids[self.root.purl.as_str()],
+        })];
+        for c in self.all_components() {
+            for dependency in &c.dependencies {
+                relationships.push(json!({
+                    "spdxElementId": ids[c.purl.as_str()],
+                    "relationshipType": "DEPENDS_ON",
+                    "relatedSpdxElement": ids[dependency.as_str()],
+                }));
+            }
+        }
+
+        let name = format!("{}-{}", self.root.name, self.root.version);
+        json!({
+            "spdxVersion": SPDX_VERSION,
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": name,
+            "documentNamespace": format!(
+                "https://spdx.org/spdxdocs/{}-{}",
+                name.replace('/', "-"),
+                uuid::Uuid::new_v4()
+            ),
+            "creationInfo": {
+                "created": timestamp(),
+                "creators": [concat!("Tool: canaveral-", env!("CARGO_PKG_VERSION"))],
+            },
+            "packages": packages,
+            "relationships": relationships,
+        })
+    }
+
+    /// Render in `format` as pretty-printed JSON
+    pub fn render(&self, format: SbomFormat) -> Result<String> {
+        let document = match format {
+            SbomFormat::CycloneDx => self.to_cyclonedx(),
+            SbomFormat::Spdx => self.to_spdx(),
+        };
+        Ok(serde_json::to_string_pretty(&document)?)
+    }
+
+    /// Write the SBOM into `dir` as `<name>-<version>.<ext>`
+    ///
+    /// Returns the path written, ready to attach as a release artifact.
+    pub fn write(&self, dir: &Path, format: SbomFormat) -> Result<PathBuf> {
+        std::fs::create_dir_all(dir)?;
+        let file_name = format!(
+            "{}-{}.{}",
+            self.root.name.trim_start_matches('@').replace('/', "-"),
+            self.root.version,
+            format.extension()
+        );
+        let path = dir.join(file_name);
+        std::fs::write(&path, self.render(format)?)?;
+        info!(
+            path = %path.display(),
+            components = self.components.len(),
+            "wrote SBOM"
+        );
+        Ok(path)
+    }
+}
+
+/// Generate an SBOM for the package at `path`
+///
+/// `package_type` is the adapter name (`cargo`, `npm` or `python`).
+pub fn generate_sbom(path: &Path, package_type: &str) -> Result<Sbom> {
+    debug!(path = %path.display(), package_type, "generating SBOM");
+    match package_type {
+        "cargo" => cargo_sbom(path),
+        "npm" => npm_sbom(path),
+        "python" => python_sbom(path),
+        other => Err(AdapterError::UnsupportedType(format!("SBOM for {} packages", other)).into()),
+    }
+}
+
+fn timestamp() -> String {
+    chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+/// Whether `license` looks like an SPDX expression rather than free text
+///
+/// License identifiers must alternate with `AND`/`OR`/`WITH`, so
+/// "MIT OR Apache-2.0" qualifies but "BSD License" does not.
+fn is_spdx_expression(license: &str) -> bool {
+    let mut expect_id = true;
+    for token in license.split_whitespace() {
+        let is_operator = matches!(token, "AND" | "OR" | "WITH");
+        let is_id = token
+            .trim_matches(|c| c == '(' || c == ')')
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+' | ':'));
+        if (expect_id && (is_operator || !is_id)) || (!expect_id && !is_operator) {
+            return false;
+        }
+        expect_id = !expect_id;
+    }
+    // Ends on an identifier, and isn't empty
+    !expect_id
+}
+
+fn cyclonedx_license(license: &str) -> Value {
+    if is_spdx_expression(license) {
+        json!({ "expression": license })
+    } else {
+        json!({ "license": { "name": license } })
+    }
+}
+
+/// Read a JSON file produced by a package manager
+fn read_json(path: &Path) -> Result<Value> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|_| AdapterError::ManifestNotFound(path.to_path_buf()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| AdapterError::ManifestParseError(format!("{}: {}", path.display(), e)).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Sbom {
+        Sbom::new(
+            Component::new("app", "1.0.0", Some("MIT".into()), "pkg:cargo/app@1.0.0")
+                .with_dependencies(vec![
+                    "pkg:cargo/serde@1.0.200".into(),
+                    "pkg:cargo/missing@1.0.0".into(),
+                ]),
+            vec![
+                Component::new(
+                    "serde",
+                    "1.0.200",
+                    Some("MIT OR Apache-2.0".into()),
+                    "pkg:cargo/serde@1.0.200",
+                )
+                .with_dependencies(vec!["pkg:cargo/odd@0.1.0".into()]),
+                Component::new(
+                    "odd",
+                    "0.1.0",
+                    Some("BSD License".into()),
+                    "pkg:cargo/odd@0.1.0",
+                ),
+            ],
+        )
+    }
+
+    #[test]
+    fn test_spdx_document() {
+        let doc = sample().to_spdx();
+        assert_eq!(doc["spdxVersion"], SPDX_VERSION);
+        assert_eq!(doc["name"], "app-1.0.0");
+        let packages = doc["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 3);
+        // Components are sorted by name
+        assert_eq!(packages[1]["name"], "odd");
+        assert_eq!(packages[1]["licenseDeclared"], "NOASSERTION");
+        assert_eq!(packages[2]["licenseDeclared"], "MIT OR Apache-2.0");
+        assert_eq!(
+            packages[2]["externalRefs"][0]["referenceLocator"],
+            "pkg:cargo/serde@1.0.200"
+        );
+        // Dependencies nest: app -> serde -> odd
+        let relationships = doc["relationships"].as_array().unwrap();
+        assert_eq!(relationships.len(), 3);
+        assert_eq!(relationships[1]["spdxElementId"], "SPDXRef-Package-0");
+        assert_eq!(relationships[1]["relatedSpdxElement"], "SPDXRef-Package-2");
+        assert_eq!(relationships[2]["spdxElementId"], "SPDXRef-Package-2");
+        assert_eq!(relationships[2]["relatedSpdxElement"], "SPDXRef-Package-1");
+    }
+
+    #[test]
+    fn test_write_uses_format_extension() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = sample().write(temp.path(), SbomFormat::CycloneDx).unwrap();
+        assert_eq!(path.file_name().unwrap(), "app-1.0.0.cdx.json");
+        let doc: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(doc["bomFormat"], "CycloneDX");
+        assert_eq!(
+            doc["components"][0]["licenses"][0]["license"]["name"],
+            "BSD License"
+        );
+
+        assert!(generate_sbom(temp.path(), "maven").is_err());
+    }
+}
@@ -0,0 +1,258 @@
+//! npm dependencies from `package-lock.json`
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use serde_json::{Map, Value};
+
+use canaveral_core::error::{AdapterError, Result};
+
+use super::{read_json, Component, Sbom};
+
+/// Generate an SBOM for the npm package at `path` from its lockfile
+pub fn npm_sbom(path: &Path) -> Result<Sbom> {
+    sbom_from_npm_lockfile(&read_json(&path.join("package-lock.json"))?)
+}
+
+/// Build an SBOM from a parsed `package-lock.json`
+///
+/// Lockfile v2/v3 `packages` entries are used when present, falling back to
+/// the v1 `dependencies` tree. Dev dependencies and workspace links are
+/// left out. Each package's dependencies are resolved the way node does,
+/// from its own `node_modules` up to the top level. v1 lockfiles don't
+/// record the root's own dependencies, so the root depends on the top-level
+/// packages nothing else requires.
+pub fn sbom_from_npm_lockfile(lockfile: &Value) -> Result<Sbom> {
+    let str_field =
+        |value: &Value, key: &str| value.get(key).and_then(Value::as_str).map(String::from);
+    let root_entry = lockfile.pointer("/packages/").unwrap_or(lockfile);
+    let name = str_field(root_entry, "name")
+        .or_else(|| str_field(lockfile, "name"))
+        .ok_or_else(|| {
+            AdapterError::ManifestParseError("package-lock.json has no name".to_string())
+        })?;
+    let version = str_field(root_entry, "version")
+        .or_else(|| str_field(lockfile, "version"))
+        .unwrap_or_else(|| "0.0.0".to_string());
+    let mut root = component(&name, &version, str_field(root_entry, "license"));
+
+    let mut components = Vec::new();
+    if let Some(packages) = lockfile.get("packages").and_then(Value::as_object) {
+        let mut purls: HashMap<&str, String> = HashMap::new();
+        let mut installed = Vec::new();
+        for (key, entry) in packages {
+            let Some(install_path) = key.rsplit_once("node_modules/").map(|(_, n)| n) else {
+                continue;
+            };
+            if is_true(entry, "dev") || is_true(entry, "link") {
+                continue;
+            }
+            let Some(version) = str_field(entry, "version") else {
+                continue;
+            };
+            let name = str_field(entry, "name").unwrap_or_else(|| install_path.to_string());
+            let component = component(&name, &version, str_field(entry, "license"));
+            purls.insert(key.as_str(), component.purl.clone());
+            installed.push((key.as_str(), entry, component));
+        }
+
+        let depends_on = |from: &str, entry: &Value| -> Vec<String> {
+            dependency_names(entry)
+                .filter_map(|dep| resolve(packages, from, dep))
+                .filter_map(|key| purls.get(key).cloned())
+                .collect()
+        };
+        root = root.with_dependencies(depends_on("", root_entry));
+        for (key, entry, component) in installed {
+            components.push(component.with_dependencies(depends_on(key, entry)));
+        }
+    } else if let Some(dependencies) = lockfile.get("dependencies").and_then(Value::as_object) {
+        collect_v1(dependencies, &mut Vec::new(), &mut components);
+
+        let required: HashSet<&str> = components
+            .iter()
+            .flat_map(|c| c.dependencies.iter().map(String::as_str))
+            .collect();
+        let top_level = dependencies
+            .iter()
+            .filter(|(_, entry)| !is_true(entry, "dev"))
+            .filter_map(|(name, entry)| Some(purl(name, entry.get("version")?.as_str()?)))
+            .filter(|purl| !required.contains(purl.as_str()))
+            .collect();
+        root = root.with_dependencies(top_level);
+    }
+
+    Ok(Sbom::new(root, components))
+}
+
+/// Names of the packages a lockfile entry depends on (not dev)
+fn dependency_names(entry: &Value) -> impl Iterator<Item = &str> + '_ {
+    ["dependencies", "optionalDependencies", "peerDependencies"]
+        .into_iter()
+        .filter_map(|key| entry.get(key).and_then(Value::as_object))
+        .flat_map(|deps| deps.keys().map(String::as_str))
+}
+
+/// Lockfile key of the package `name` resolves to from the package at
+/// `from`, looking in each enclosing `node_modules` up to the top level
+fn resolve<'a>(packages: &'a Map<String, Value>, from: &str, name: &str) -> Option<&'a str> {
+    let mut dir = from;
+    loop {
+        let key = if dir.is_empty() {
+            format!("node_modules/{}", name)
+        } else {
+            format!("{}/node_modules/{}", dir, name)
+        };
+        if let Some((key, _)) = packages.get_key_value(&key) {
+            return Some(key.as_str());
+        }
+        if dir.is_empty() {
+            return None;
+        }
+        dir = dir
+            .rsplit_once("/node_modules/")
+            .map_or("", |(parent, _)| parent);
+    }
+}
+
+/// Walk the nested lockfile v1 `dependencies` tree
+///
+/// `scopes` holds the enclosing `dependencies` maps, innermost last, which
+/// an entry's `requires` resolve against.
+fn collect_v1<'a>(
+    dependencies: &'a Map<String, Value>,
+    scopes: &mut Vec<&'a Map<String, Value>>,
+    components: &mut Vec<Component>,
+) {
+    scopes.push(dependencies);
+    for (name, entry) in dependencies {
+        if is_true(entry, "dev") {
+            continue;
+        }
+        let nested = entry.get("dependencies").and_then(Value::as_object);
+        if let Some(version) = entry.get("version").and_then(Value::as_str) {
+            let requires = entry
+                .get("requires")
+                .and_then(Value::as_object)
+                .into_iter()
+                .flat_map(|requires| requires.keys())
+                .filter_map(|dep| {
+                    let resolved = nested
+                        .into_iter()
+                        .chain(scopes.iter().rev().copied())
+                        .find_map(|scope| scope.get(dep))?;
+                    Some(purl(dep, resolved.get("version")?.as_str()?))
+                })
+                .collect();
+            components.push(component(name, version, None).with_dependencies(requires));
+        }
+        if let Some(nested) = nested {
+            collect_v1(nested, scopes, components);
+        }
+    }
+    scopes.pop();
+}
+
+fn is_true(entry: &Value, key: &str) -> bool {
+    entry.get(key).and_then(Value::as_bool).unwrap_or(false)
+}
+
+fn component(name: &str, version: &str, license: Option<String>) -> Component {
+    Component::new(name, version, license, purl(name, version))
+}
+
+fn purl(name: &str, version: &str) -> String {
+    // Scoped names keep their slash; the `@` is percent-encoded
+    let purl_name = name
+        .strip_prefix('@')
+        .map_or_else(|| name.to_string(), |n| format!("%40{}", n));
+    format!("pkg:npm/{}@{}", purl_name, version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_lockfile_v3() {
+        let lockfile = json!({
+            "name": "web",
+            "version": "2.1.0",
+            "lockfileVersion": 3,
+            "packages": {
+                "": {
+                    "name": "web",
+                    "version": "2.1.0",
+                    "license": "ISC",
+                    "dependencies": { "@scope/ui": "^1.0.0", "left-pad": "^1.3.0", "shared": "*" },
+                    "devDependencies": { "vitest": "^1.6.0" }
+                },
+                "node_modules/@scope/ui": {
+                    "version": "1.0.0",
+                    "license": "MIT",
+                    "dependencies": { "chalk": "^5.0.0" }
+                },
+                "node_modules/left-pad": {
+                    "version": "1.3.0",
+                    "license": "WTFPL",
+                    "dependencies": { "chalk": "^4.1.0" }
+                },
+                "node_modules/left-pad/node_modules/chalk": { "version": "4.1.2" },
+                "node_modules/chalk": { "version": "5.3.0" },
+                "node_modules/vitest": { "version": "1.6.0", "dev": true },
+                "node_modules/shared": { "resolved": "packages/shared", "link": true },
+                "packages/shared": { "name": "shared", "version": "0.1.0" }
+            }
+        });
+
+        let sbom = sbom_from_npm_lockfile(&lockfile).unwrap();
+        assert_eq!(sbom.root.purl, "pkg:npm/web@2.1.0");
+        assert_eq!(sbom.root.license.as_deref(), Some("ISC"));
+        let purls: Vec<_> = sbom.components.iter().map(|c| c.purl.as_str()).collect();
+        assert_eq!(
+            purls,
+            [
+                "pkg:npm/%40scope/ui@1.0.0",
+                "pkg:npm/chalk@4.1.2",
+                "pkg:npm/chalk@5.3.0",
+                "pkg:npm/left-pad@1.3.0"
+            ]
+        );
+
+        // Dependencies resolve through the nearest node_modules
+        assert_eq!(
+            sbom.root.dependencies,
+            ["pkg:npm/%40scope/ui@1.0.0", "pkg:npm/left-pad@1.3.0"]
+        );
+        assert_eq!(sbom.components[0].dependencies, ["pkg:npm/chalk@5.3.0"]);
+        assert_eq!(sbom.components[3].dependencies, ["pkg:npm/chalk@4.1.2"]);
+    }
+
+    #[test]
+    fn test_lockfile_v1() {
+        let lockfile = json!({
+            "name": "legacy",
+            "version": "1.0.0",
+            "lockfileVersion": 1,
+            "dependencies": {
+                "express": {
+                    "version": "4.18.2",
+                    "requires": { "debug": "2.6.9", "ms": "2.1.3" },
+                    "dependencies": { "debug": { "version": "2.6.9" } }
+                },
+                "ms": { "version": "2.1.3" },
+                "mocha": { "version": "10.0.0", "dev": true }
+            }
+        });
+
+        let sbom = sbom_from_npm_lockfile(&lockfile).unwrap();
+        let names: Vec<_> = sbom.components.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["debug", "express", "ms"]);
+        assert_eq!(
+            sbom.components[1].dependencies,
+            ["pkg:npm/debug@2.6.9", "pkg:npm/ms@2.1.3"]
+        );
+        assert_eq!(sbom.root.dependencies, ["pkg:npm/express@4.18.2"]);
+    }
+}
@@ -0,0 +1,245 @@
+//! Python dependencies from installed distribution metadata
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tracing::debug;
+
+use canaveral_core::error::{AdapterError, Result};
+
+use super::{Component, Sbom};
+use crate::published::normalize_pypi_name;
+use crate::python::PythonAdapter;
+use crate::PackageAdapter;
+
+/// Generate an SBOM for the Python project at `path`
+///
+/// Components are the distributions installed in the project's virtualenv
+/// (`.venv` or `venv`), or in the active interpreter's site-packages when
+/// the project has none.
+pub fn python_sbom(path: &Path) -> Result<Sbom> {
+    let info = PythonAdapter::new().get_info(path)?;
+    let site_packages = match project_site_packages(path) {
+        Some(dir) => dir,
+        None => interpreter_site_packages()?,
+    };
+    debug!(site_packages = %site_packages.display(), "reading installed distributions");
+    sbom_from_site_packages(&info.name, &info.version, &site_packages)
+}
+
+/// Build an SBOM for `name` from the `*.dist-info` directories in `site_packages`
+///
+/// The dependency graph follows each distribution's `Requires-Dist`
+/// (ignoring extras). When the project itself isn't installed, the root
+/// depends on the distributions nothing else requires.
+pub fn sbom_from_site_packages(name: &str, version: &str, site_packages: &Path) -> Result<Sbom> {
+    let root_name = normalize_pypi_name(name);
+    let mut root = component(name, version, None);
+    let mut root_requires = None;
+    let mut installed = Vec::new();
+
+    for entry in std::fs::read_dir(site_packages)?.flatten() {
+        let dir = entry.path();
+        if dir.extension().and_then(|e| e.to_str()) != Some("dist-info") {
+            continue;
+        }
+        let Ok(metadata) = std::fs::read_to_string(dir.join("METADATA")) else {
+            continue;
+        };
+        let Some((distribution, requires)) = parse_metadata(&metadata) else {
+            continue;
+        };
+        if normalize_pypi_name(&distribution.name) == root_name {
+            // The project itself, e.g. an editable install
+            root.license = distribution.license;
+            root_requires = Some(requires);
+        } else {
+            installed.push((distribution, requires));
+        }
+    }
+
+    let purls: HashMap<String, String> = installed
+        .iter()
+        .map(|(c, _)| (normalize_pypi_name(&c.name), c.purl.clone()))
+        .collect();
+    let resolve = |requires: &[String]| -> Vec<String> {
+        requires
+            .iter()
+            .filter_map(|name| purls.get(&normalize_pypi_name(name)).cloned())
+            .collect()
+    };
+
+    let components: Vec<Component> = installed
+        .iter()
+        .map(|(c, requires)| c.clone().with_dependencies(resolve(requires)))
+        .collect();
+    let root_dependencies = match root_requires {
+        Some(requires) => resolve(&requires),
+        None => {
+            let required: HashSet<&str> = components
+                .iter()
+                .flat_map(|c| c.dependencies.iter().map(String::as_str))
+                .collect();
+            components
+                .iter()
+                .map(|c| c.purl.clone())
+                .filter(|purl| !required.contains(purl.as_str()))
+                .collect()
+        }
+    };
+
+    Ok(Sbom::new(
+        root.with_dependencies(root_dependencies),
+        components,
+    ))
+}
+
+/// Parse the headers of a core metadata (`METADATA`) file into the
+/// distribution and the names it requires
+fn parse_metadata(content: &str) -> Option<(Component, Vec<String>)> {
+    let (mut name, mut version) = (None, None);
+    let (mut expression, mut license, mut classifier) = (None, None, None);
+    let mut requires = Vec::new();
+    for line in content.lines() {
+        // Headers end at the first blank line; the description follows
+        if line.is_empty() {
+            break;
+        }
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key {
+            "Name" => name = Some(value),
+            "Version" => version = Some(value),
+            "License-Expression" => expression = Some(value),
+            // Long license texts are folded over several lines; only a
+            // short single-line value is a usable identifier
+            "License" if value.len() <= 64 => license = Some(value),
+            "Classifier" if value.starts_with("License ::") => {
+                classifier = value.rsplit(" :: ").next().map(String::from)
+            }
+            // `name[extra] (>=1.0) ; marker`; optional extras aren't installed
+            "Requires-Dist" if !value.contains("extra ==") => {
+                let end = value
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-')))
+                    .unwrap_or(value.len());
+                requires.push(value[..end].to_string());
+            }
+            _ => {}
+        }
+    }
+    let component = component(&name?, &version?, expression.or(license).or(classifier));
+    Some((component, requires))
+}
+
+fn component(name: &str, version: &str, license: Option<String>) -> Component {
+    Component::new(
+        name,
+        version,
+        license,
+        format!("pkg:pypi/{}@{}", normalize_pypi_name(name), version),
+    )
+}
+
+fn project_site_packages(path: &Path) -> Option<PathBuf> {
+    for venv in [".venv", "venv"] {
+        let venv = path.join(venv);
+        // Windows layout
+        let lib = venv.join("Lib").join("site-packages");
+        if lib.is_dir() {
+            return Some(lib);
+        }
+        let Ok(entries) = std::fs::read_dir(venv.join("lib")) else {
+            continue;
+        };
+        let found = entries
+            .flatten()
+            .map(|e| e.path().join("site-packages"))
+            .find(|p| p.is_dir());
+        if found.is_some() {
+            return found;
+        }
+    }
+    None
+}
+
+fn interpreter_site_packages() -> Result<PathBuf> {
+    let output = Command::new("python")
+        .args([
+            "-c",
+            "import sysconfig; print(sysconfig.get_paths()['purelib'])",
+        ])
+        .output()
+        .map_err(|e| AdapterError::CommandFailed {
+            command: "python".to_string(),
+            reason: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(AdapterError::CommandFailed {
+            command: "python".to_string(),
+            reason: String::from_utf8_lossy(&output.stderr).to_string(),
+        }
+        .into());
+    }
+    Ok(PathBuf::from(
+        String::from_utf8_lossy(&output.stdout).trim().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn install(site_packages: &Path, dist: &str, metadata: &str) {
+        let dir = site_packages.join(format!("{}.dist-info", dist));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("METADATA"), metadata).unwrap();
+    }
+
+    #[test]
+    fn test_sbom_from_project_venv() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let site_packages = temp.path().join(".venv/lib/python3.12/site-packages");
+        install(
+            &site_packages,
+            "requests-2.32.0",
+            "Metadata-Version: 2.1\nName: requests\nVersion: 2.32.0\nLicense: Apache 2.0\nRequires-Dist: typing_extensions>=4.0\nRequires-Dist: PySocks!=1.5.7,>=1.5.6; extra == \"socks\"\n\nLicense: not a header\n",
+        );
+        install(
+            &site_packages,
+            "Typing_Extensions-4.12.0",
+            "Metadata-Version: 2.4\nName: Typing_Extensions\nVersion: 4.12.0\nLicense-Expression: PSF-2.0\nClassifier: License :: OSI Approved :: Python Software Foundation License\n",
+        );
+        install(
+            &site_packages,
+            "my_app-0.1.0",
+            "Metadata-Version: 2.1\nName: my_app\nVersion: 0.1.0\nClassifier: License :: OSI Approved :: MIT License\nRequires-Dist: requests (>=2.0)\n",
+        );
+        std::fs::create_dir_all(site_packages.join("requests")).unwrap();
+
+        let dir = project_site_packages(temp.path()).unwrap();
+        assert_eq!(dir, site_packages);
+
+        let sbom = sbom_from_site_packages("my-app", "0.1.0", &dir).unwrap();
+        assert_eq!(sbom.root.purl, "pkg:pypi/my-app@0.1.0");
+        assert_eq!(sbom.root.license.as_deref(), Some("MIT License"));
+        assert_eq!(sbom.components.len(), 2);
+        assert_eq!(sbom.components[0].name, "Typing_Extensions");
+        assert_eq!(sbom.components[0].purl, "pkg:pypi/typing-extensions@4.12.0");
+        assert_eq!(sbom.components[0].license.as_deref(), Some("PSF-2.0"));
+        assert_eq!(sbom.components[1].license.as_deref(), Some("Apache 2.0"));
+
+        assert_eq!(
+            sbom.components[1].dependencies,
+            ["pkg:pypi/typing-extensions@4.12.0"]
+        );
+        assert_eq!(sbom.root.dependencies, ["pkg:pypi/requests@2.32.0"]);
+
+        // Without the project installed, the root depends on what nothing
+        // else requires
+        let sbom = sbom_from_site_packages("other", "1.0.0", &dir).unwrap();
+        assert_eq!(sbom.root.dependencies, ["pkg:pypi/my-app@0.1.0"]);
+    }
+}
@@ -2,12 +2,38 @@
 
 use std::path::Path;
 
-use canaveral_core::error::Result;
+use canaveral_core::error::{AdapterError, Result};
 use canaveral_core::types::PackageInfo;
 
 use crate::credentials::CredentialProvider;
 use crate::publish::{PublishOptions, ValidationResult};
 
+/// An external tool a package adapter runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequiredTool {
+    /// Executable name
+    pub command: &'static str,
+    /// Arguments that print the tool's version
+    pub version_args: &'static [&'static str],
+    /// How to install the tool
+    pub install_hint: &'static str,
+}
+
+impl RequiredTool {
+    /// A tool whose version is printed by `version_args`
+    pub const fn new(
+        command: &'static str,
+        version_args: &'static [&'static str],
+        install_hint: &'static str,
+    ) -> Self {
+        Self {
+            command,
+            version_args,
+            install_hint,
+        }
+    }
+}
+
 /// Trait for package adapters
 pub trait PackageAdapter: Send + Sync {
     /// Get the adapter name (e.g., "npm", "cargo")
@@ -25,6 +51,19 @@ pub trait PackageAdapter: Send + Sync {
     /// Get current version
     fn get_version(&self, path: &Path) -> Result<String>;
 
+    /// Versions of package `name` at `path` published to its registry, oldest first
+    ///
+    /// Used to bump from the latest published version when the manifest
+    /// only holds a placeholder such as `0.0.0-dev`. Results are cached for
+    /// the adapter's lifetime; unpublished packages have no versions.
+    fn published_versions(&self, _path: &Path, _name: &str) -> Result<Vec<semver::Version>> {
+        Err(AdapterError::UnsupportedType(format!(
+            "{} does not support registry version lookup",
+            self.name()
+        ))
+        .into())
+    }
+
     /// Set version in manifest
     fn set_version(&self, path: &Path, version: &str) -> Result<()>;
 
@@ -80,6 +119,21 @@ pub trait PackageAdapter: Send + Sync {
         Ok(())
     }
 
+    /// Whether [`fmt`](Self::fmt) runs a formatter for this adapter
+    fn supports_fmt(&self) -> bool {
+        false
+    }
+
+    /// Whether [`lint`](Self::lint) runs a linter for this adapter
+    fn supports_lint(&self) -> bool {
+        false
+    }
+
+    /// External tools needed to version and publish packages
+    fn required_tools(&self) -> &[RequiredTool] {
+        &[]
+    }
+
     /// Build the package (if applicable)
     fn build(&self, _path: &Path) -> Result<()> {
         Ok(())
@@ -99,4 +153,25 @@ pub trait PackageAdapter: Send + Sync {
     fn pack(&self, _path: &Path) -> Result<Option<std::path::PathBuf>> {
         Ok(None)
     }
+
+    /// List the files a publish would upload, relative to the package root
+    ///
+    /// Adapters that can't determine this return an empty list.
+    fn list_publish_files(&self, _path: &Path) -> Result<Vec<std::path::PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// Packages of the workspace this package depends on
+    ///
+    /// `known_packages` are the names of the workspace's packages. The result
+    /// is sorted and deduplicated, and feeds the dependency graph used for
+    /// publish ordering and affected dependents. Adapters that can't tell
+    /// return an empty list.
+    fn workspace_dependencies(
+        &self,
+        _path: &Path,
+        _known_packages: &[String],
+    ) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
 }
@@ -0,0 +1,333 @@
+//! "What changed" between two released versions
+//!
+//! Combines the commit range, the changelog sections generated from it, and
+//! the packages whose manifest version differs between the two refs.
+
+use std::collections::BTreeMap;
+
+use canaveral_core::config::ChangelogConfig;
+use canaveral_git::{CommitInfo, GitRepo, Result};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, instrument};
+
+use crate::generator::ChangelogGenerator;
+use crate::types::ChangelogEntry;
+
+/// Manifest files inspected for package versions
+const MANIFESTS: &[&str] = &["Cargo.toml", "package.json", "pyproject.toml"];
+
+/// Differences between two versions of a repository
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionDiff {
+    /// Ref the diff starts from (exclusive)
+    pub from: String,
+    /// Ref the diff ends at (inclusive)
+    pub to: String,
+    /// Commits reachable from `to` but not `from`, newest first
+    pub commits: Vec<CommitInfo>,
+    /// Changelog entry for the commit range, grouped into sections
+    pub changelog: ChangelogEntry,
+    /// Packages whose version changed, sorted by name
+    pub packages: Vec<PackageVersionChange>,
+}
+
+impl VersionDiff {
+    /// Whether nothing changed between the two refs
+    pub fn is_empty(&self) -> bool {
+        self.commits.is_empty() && self.packages.is_empty()
+    }
+}
+
+/// A package whose version differs between two refs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackageVersionChange {
+    /// Package name
+    pub name: String,
+    /// Manifest path relative to the repository root
+    pub manifest: String,
+    /// Version at the `from` ref (`None` if the package was added)
+    pub from_version: Option<String>,
+    /// Version at the `to` ref (`None` if the package was removed)
+    pub to_version: Option<String>,
+}
+
+/// Diff two versions using the default changelog configuration
+pub fn diff_versions(repo: &GitRepo, from: &str, to: &str) -> Result<VersionDiff> {
+    diff_versions_with_config(repo, from, to, &ChangelogConfig::default())
+}
+
+/// Diff two versions, grouping commits according to `config`
+#[instrument(skip(repo, config))]
+pub fn diff_versions_with_config(
+    repo: &GitRepo,
+    from: &str,
+    to: &str,
+    config: &ChangelogConfig,
+) -> Result<VersionDiff> {
+    let commits = repo.commits_between(from, to)?;
+
+    let mut generator = ChangelogGenerator::new(config.clone());
+    if let Some(tag) = repo.find_tag(to)? {
+        generator = generator.with_tag(&tag);
+    }
+    let changelog = generator.generate(to, &commits);
+
+    let before = package_versions(repo, from)?;
+    let after = package_versions(repo, to)?;
+
+    let mut packages = Vec::new();
+    for (key, (manifest, to_version)) in &after {
+        let from_version = before.get(key).map(|(_, v)| v.clone());
+        if from_version.as_ref() != Some(to_version) {
+            packages.push(PackageVersionChange {
+                name: key.clone(),
+                manifest: manifest.clone(),
+                from_version,
+                to_version: Some(to_version.clone()),
+            });
+        }
+    }
+    for (key, (manifest, from_version)) in &before {
+        if !after.contains_key(key) {
+            packages.push(PackageVersionChange {
+                name: key.clone(),
+                manifest: manifest.clone(),
+                from_version: Some(from_version.clone()),
+                to_version: None,
+            });
+        }
+    }
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+    info!(
+        from,
+        to,
+        commits = commits.len(),
+        packages = packages.len(),
+        "computed version diff"
+    );
+
+    Ok(VersionDiff {
+        from: from.to_string(),
+        to: to.to_string(),
+        commits,
+        changelog,
+        packages,
+    })
+}
+
+/// Package name -> (manifest path, version) for every manifest at `rev`
+fn package_versions(repo: &GitRepo, rev: &str) -> Result<BTreeMap<String, (String, String)>> {
+    let workspace_version = repo
+        .file_at(rev, "Cargo.toml")?
+        .and_then(|content| content.parse::<toml::Table>().ok())
+        .and_then(|root| {
+            root.get("workspace")?
+                .get("package")?
+                .get("version")?
+                .as_str()
+                .map(String::from)
+        });
+
+    let mut versions = BTreeMap::new();
+    for path in repo.files_at(rev)? {
+        let file_name = path.rsplit('/').next().unwrap_or(&path);
+        if !MANIFESTS.contains(&file_name) {
+            continue;
+        }
+        let Some(content) = repo.file_at(rev, &path)? else {
+            continue;
+        };
+
+        let parsed = match file_name {
+            "Cargo.toml" => parse_cargo(&content, workspace_version.as_deref()),
+            "package.json" => parse_package_json(&content),
+            _ => parse_pyproject(&content),
+        };
+
+        if let Some((name, version)) = parsed {
+            debug!(rev, manifest = %path, name = %name, version = %version, "found package");
+            versions.insert(name, (path, version));
+        }
+    }
+
+    Ok(versions)
+}
+
+fn parse_cargo(content: &str, workspace_version: Option<&str>) -> Option<(String, String)> {
+    let table = content.parse::<toml::Table>().ok()?;
+    let package = table.get("package")?;
+    let name = package.get("name")?.as_str()?.to_string();
+
+    let version = match package.get("version") {
+        Some(toml::Value::String(v)) => v.clone(),
+        // `version.workspace = true`
+        Some(toml::Value::Table(_)) => workspace_version?.to_string(),
+        _ => return None,
+    };
+
+    Some((name, version))
+}
+
+fn parse_package_json(content: &str) -> Option<(String, String)> {
+    let json: serde_json::Value = serde_json::from_str(content).ok()?;
+    Some((
+        json.get("name")?.as_str()?.to_string(),
+        json.get("version")?.as_str()?.to_string(),
+    ))
+}
+
+fn parse_pyproject(content: &str) -> Option<(String, String)> {
+    let table = content.parse::<toml::Table>().ok()?;
+    let project = table
+        .get("project")
+        .or_else(|| table.get("tool")?.get("poetry"))?;
+    Some((
+        project.get("name")?.as_str()?.to_string(),
+        project.get("version")?.as_str()?.to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    fn commit(dir: &Path, files: &[(&str, &str)], message: &str) {
+        for (path, content) in files {
+            let path = dir.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        git(dir, &["add", "-A"]);
+        git(dir, &["commit", "-q", "-m", message]);
+    }
+
+    fn cargo(name: &str) -> String {
+        format!("[package]\nname = \"{}\"\nversion.workspace = true\n", name)
+    }
+
+    fn setup() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "-q"]);
+        git(dir, &["config", "user.name", "Test"]);
+        git(dir, &["config", "user.email", "test@example.com"]);
+
+        commit(
+            dir,
+            &[
+                (
+                    "Cargo.toml",
+                    "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.0.0\"\n",
+                ),
+                ("crates/core/Cargo.toml", &cargo("core")),
+                ("web/package.json", r#"{"name": "web", "version": "0.3.0"}"#),
+                ("docs/package.json", r#"{"name": "docs", "version": "1.0.0"}"#),
+            ],
+            "chore: initial commit",
+        );
+        git(dir, &["tag", "v1.0.0"]);
+
+        commit(
+            dir,
+            &[("crates/cli/Cargo.toml", &cargo("cli"))],
+            "feat(cli): add command line interface",
+        );
+        commit(
+            dir,
+            &[("web/package.json", r#"{"name": "web", "version": "0.3.1"}"#)],
+            "fix(web): handle empty responses",
+        );
+        commit(
+            dir,
+            &[(
+                "Cargo.toml",
+                "[workspace]\nmembers = [\"crates/*\"]\n\n[workspace.package]\nversion = \"1.1.0\"\n",
+            )],
+            "chore(release): 1.1.0",
+        );
+        git(dir, &["tag", "-a", "v1.1.0", "-m", "Release 1.1.0"]);
+
+        temp
+    }
+
+    #[test]
+    fn test_diff_versions() {
+        let temp = setup();
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        let diff = diff_versions(&repo, "v1.0.0", "v1.1.0").unwrap();
+        assert_eq!(diff.commits.len(), 3);
+        assert_eq!(diff.commits[0].message, "chore(release): 1.1.0");
+
+        let titles: Vec<_> = diff
+            .changelog
+            .sections
+            .iter()
+            .map(|s| s.title.as_str())
+            .collect();
+        assert_eq!(titles, vec!["Features", "Bug Fixes", "Chores"]);
+        assert_eq!(diff.changelog.sections[0].commits.len(), 1);
+        assert_eq!(diff.changelog.sections[1].commits.len(), 1);
+
+        let changes: Vec<_> = diff
+            .packages
+            .iter()
+            .map(|p| {
+                (
+                    p.name.as_str(),
+                    p.from_version.as_deref(),
+                    p.to_version.as_deref(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("cli", None, Some("1.1.0")),
+                ("core", Some("1.0.0"), Some("1.1.0")),
+                ("web", Some("0.3.0"), Some("0.3.1")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_diff_same_ref_is_empty() {
+        let temp = setup();
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        let diff = diff_versions(&repo, "v1.1.0", "v1.1.0").unwrap();
+        assert!(diff.is_empty());
+        assert!(diff.changelog.sections.is_empty());
+    }
+
+    #[test]
+    fn test_parse_manifests() {
+        assert_eq!(
+            parse_cargo("[package]\nname = \"a\"\nversion = \"2.0.0\"\n", None),
+            Some(("a".to_string(), "2.0.0".to_string()))
+        );
+        assert_eq!(parse_cargo(&cargo("b"), None), None);
+        assert_eq!(
+            parse_pyproject("[tool.poetry]\nname = \"py\"\nversion = \"0.1.0\"\n"),
+            Some(("py".to_string(), "0.1.0".to_string()))
+        );
+    }
+}
@@ -3,15 +3,18 @@
 use canaveral_core::config::ChangelogConfig;
 use tracing::{debug, instrument};
 
-use super::ChangelogFormatter;
-use crate::types::ChangelogEntry;
+use super::{format_date, ChangelogFormatter};
+use crate::types::{ChangelogEntry, NO_UNRELEASED_CHANGES, SECURITY_TRAILER};
 
 /// Markdown changelog formatter
+#[derive(Debug, Clone)]
 pub struct MarkdownFormatter {
     /// Include compare link between versions
     pub include_compare_link: bool,
     /// Repository URL for links
     pub repo_url: Option<String>,
+    /// Tag name template, with `{version}` as the placeholder
+    pub tag_format: String,
 }
 
 impl MarkdownFormatter {
@@ -20,6 +23,7 @@ impl MarkdownFormatter {
         Self {
             include_compare_link: true,
             repo_url: None,
+            tag_format: "v{version}".to_string(),
         }
     }
 
@@ -28,6 +32,72 @@ impl MarkdownFormatter {
         self.repo_url = Some(url.into());
         self
     }
+
+    /// Set the tag name template used in compare links (e.g. `app-v{version}`)
+    pub fn with_tag_format(mut self, format: impl Into<String>) -> Self {
+        self.tag_format = format.into();
+        self
+    }
+
+    /// Format several entries (newest first) as one document, followed by
+    /// the reference-style version links
+    pub fn format_all(&self, entries: &[ChangelogEntry], config: &ChangelogConfig) -> String {
+        let mut output: String = entries.iter().map(|e| self.format(e, config)).collect();
+
+        let versions: Vec<&str> = entries.iter().map(|e| e.version.as_str()).collect();
+        let links = self.format_links(&versions, config);
+        if !links.is_empty() {
+            output.push_str(&links);
+        }
+        output
+    }
+
+    /// Reference-style link definitions for `## [x.y.z]` headings
+    ///
+    /// `versions` are ordered newest first. Each version links to the compare
+    /// view against the next-older version; the oldest links to its tag page
+    /// since there is nothing to compare it with. An `[Unreleased]` link
+    /// compares the newest tag with `HEAD`. Returns an empty string when no
+    /// repository URL is configured or compare links are disabled.
+    pub fn format_links(&self, versions: &[&str], config: &ChangelogConfig) -> String {
+        let Some(repo_url) = self.repository_url(config) else {
+            return String::new();
+        };
+        if !self.include_compare_link || versions.is_empty() {
+            return String::new();
+        }
+
+        let mut output = format!(
+            "[Unreleased]: {}/compare/{}...HEAD\n",
+            repo_url,
+            self.tag_name(versions[0])
+        );
+        for (i, version) in versions.iter().enumerate() {
+            let url = match versions.get(i + 1) {
+                Some(previous) => format!(
+                    "{}/compare/{}...{}",
+                    repo_url,
+                    self.tag_name(previous),
+                    self.tag_name(version)
+                ),
+                None => format!("{}/releases/tag/{}", repo_url, self.tag_name(version)),
+            };
+            output.push_str(&format!("[{}]: {}\n", version, url));
+        }
+        output
+    }
+
+    /// Repository URL from the formatter, falling back to the config
+    fn repository_url<'a>(&'a self, config: &'a ChangelogConfig) -> Option<&'a str> {
+        self.repo_url
+            .as_deref()
+            .or(config.repository_url.as_deref())
+            .map(|url| url.trim_end_matches('/').trim_end_matches(".git"))
+    }
+
+    fn tag_name(&self, version: &str) -> String {
+        self.tag_format.replace("{version}", version)
+    }
 }
 
 impl Default for MarkdownFormatter {
@@ -41,15 +111,22 @@ impl ChangelogFormatter for MarkdownFormatter {
     fn format(&self, entry: &ChangelogEntry, config: &ChangelogConfig) -> String {
         let mut output = String::new();
 
-        // Version header
-        let date_str = entry.date.format("%Y-%m-%d").to_string();
-        output.push_str(&format!("## [{}] - {}\n\n", entry.version, date_str));
+        // Version header; unreleased changes have no date yet
+        if entry.is_unreleased() {
+            output.push_str(&format!("## [{}]\n\n", entry.version));
+            if entry.is_empty() {
+                output.push_str(&format!("{}\n\n", NO_UNRELEASED_CHANGES));
+            }
+        } else {
+            let date_str = format_date(&entry.date, config.timezone.as_deref());
+            output.push_str(&format!("## [{}] - {}\n\n", entry.version, date_str));
+        }
 
         // Breaking changes
         if !entry.breaking_changes.is_empty() {
             output.push_str("### ⚠ BREAKING CHANGES\n\n");
             for commit in &entry.breaking_changes {
-                output.push_str(&format!("- {}", commit.description));
+                output.push_str(&format!("- {}", commit.display_description()));
                 if let Some(scope) = &commit.scope {
                     output.push_str(&format!(" ({})", scope));
                 }
@@ -62,7 +139,7 @@ impl ChangelogFormatter for MarkdownFormatter {
         }
 
         // Sections
-        for section in &entry.sections {
+        for section in entry.visible_sections() {
             if section.is_empty() {
                 continue;
             }
@@ -70,7 +147,13 @@ impl ChangelogFormatter for MarkdownFormatter {
             output.push_str(&format!("### {}\n\n", section.title));
 
             for commit in &section.commits {
-                output.push_str(&format!("- {}", commit.description));
+                output.push_str(&format!("- {}", commit.display_description()));
+
+                // Advisory IDs, e.g. `Security: CVE-2024-1234`
+                let advisories: Vec<&str> = commit.trailers(SECURITY_TRAILER).collect();
+                if !advisories.is_empty() {
+                    output.push_str(&format!(" [{}]", advisories.join(", ")));
+                }
 
                 if let Some(scope) = &commit.scope {
                     output.push_str(&format!(" ({})", scope));
@@ -78,7 +161,7 @@ impl ChangelogFormatter for MarkdownFormatter {
 
                 if config.include_hashes {
                     let short_hash = &commit.hash[..7.min(commit.hash.len())];
-                    if let Some(repo_url) = &self.repo_url {
+                    if let Some(repo_url) = self.repository_url(config) {
                         output.push_str(&format!(
                             " ([{}]({}/commit/{}))",
                             short_hash, repo_url, commit.hash
@@ -196,6 +279,25 @@ mod tests {
         assert!(output.contains("remove deprecated API"));
     }
 
+    #[test]
+    fn test_format_date_in_timezone() {
+        use chrono::TimeZone;
+
+        let formatter = MarkdownFormatter::new();
+        let mut config = ChangelogConfig::default();
+        let entry = ChangelogEntry::new("1.2.0")
+            .with_date(Utc.with_ymd_and_hms(2024, 4, 30, 22, 0, 0).unwrap());
+
+        assert!(formatter
+            .format(&entry, &config)
+            .starts_with("## [1.2.0] - 2024-04-30"));
+
+        config.timezone = Some("+02:00".to_string());
+        assert!(formatter
+            .format(&entry, &config)
+            .starts_with("## [1.2.0] - 2024-05-01"));
+    }
+
     #[test]
     fn test_format_with_repo_url() {
         let formatter = MarkdownFormatter::new().with_repo_url("https://github.com/test/repo");
@@ -221,4 +323,45 @@ mod tests {
 
         assert!(output.contains("https://github.com/test/repo/commit/"));
     }
+
+    #[test]
+    fn test_compare_link_between_releases() {
+        let formatter = MarkdownFormatter::new().with_repo_url("https://github.com/test/repo/");
+        let config = ChangelogConfig::default();
+
+        let links = formatter.format_links(&["1.1.0", "1.0.0"], &config);
+        assert!(links.contains("[1.1.0]: https://github.com/test/repo/compare/v1.0.0...v1.1.0\n"));
+    }
+
+    #[test]
+    fn test_first_release_links_to_tag() {
+        let formatter = MarkdownFormatter::new().with_tag_format("app-{version}");
+        let config = ChangelogConfig {
+            repository_url: Some("https://github.com/test/repo.git".to_string()),
+            ..Default::default()
+        };
+
+        let output = formatter.format_all(&[ChangelogEntry::new("0.1.0")], &config);
+        assert!(output.ends_with("[0.1.0]: https://github.com/test/repo/releases/tag/app-0.1.0\n"));
+    }
+
+    #[test]
+    fn test_unreleased_link() {
+        let formatter = MarkdownFormatter::new().with_repo_url("https://github.com/test/repo");
+        let config = ChangelogConfig::default();
+
+        let links = formatter.format_links(&["2.0.0", "1.0.0"], &config);
+        assert!(
+            links.starts_with("[Unreleased]: https://github.com/test/repo/compare/v2.0.0...HEAD\n")
+        );
+
+        let disabled = MarkdownFormatter {
+            include_compare_link: false,
+            ..formatter
+        };
+        assert!(disabled.format_links(&["2.0.0"], &config).is_empty());
+        assert!(MarkdownFormatter::new()
+            .format_links(&["2.0.0"], &config)
+            .is_empty());
+    }
 }
@@ -7,6 +7,8 @@ pub use markdown::MarkdownFormatter;
 pub use registry::FormatterRegistry;
 
 use canaveral_core::config::ChangelogConfig;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use tracing::warn;
 
 use crate::types::ChangelogEntry;
 
@@ -18,3 +20,134 @@ pub trait ChangelogFormatter: Send + Sync {
     /// Get the file extension for this format
     fn extension(&self) -> &'static str;
 }
+
+/// Format a release date as `YYYY-MM-DD` in the given time zone.
+///
+/// `timezone` accepts "UTC" (the default), "local", or a fixed offset such as
+/// "+02:00", "-0500" or "+05:30". Unrecognized values fall back to UTC.
+pub fn format_date(date: &DateTime<Utc>, timezone: Option<&str>) -> String {
+    const FORMAT: &str = "%Y-%m-%d";
+
+    match timezone.map(str::trim) {
+        None | Some("") => date.format(FORMAT).to_string(),
+        Some(tz) if tz.eq_ignore_ascii_case("utc") || tz == "Z" => date.format(FORMAT).to_string(),
+        Some(tz) if tz.eq_ignore_ascii_case("local") => {
+            date.with_timezone(&Local).format(FORMAT).to_string()
+        }
+        Some(tz) => match parse_offset(tz) {
+            Some(offset) => date.with_timezone(&offset).format(FORMAT).to_string(),
+            None => {
+                warn!(timezone = tz, "unrecognized changelog timezone, using UTC");
+                date.format(FORMAT).to_string()
+            }
+        },
+    }
+}
+
+/// Parse a `YYYY-MM-DD` release date written by [`format_date`]
+///
+/// Gives midnight in `timezone`, so formatting the result with the same
+/// time zone reproduces the date.
+pub fn parse_date(date: &str, timezone: Option<&str>) -> Option<DateTime<Utc>> {
+    let date = NaiveDate::parse_from_str(date.trim(), "%Y-%m-%d").ok()?;
+    Some(start_of_date(date, timezone))
+}
+
+/// Midnight at the start of `date` in `timezone`, as accepted by [`format_date`]
+///
+/// Formatting the result with the same time zone gives `date` back.
+pub fn start_of_date(date: NaiveDate, timezone: Option<&str>) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+
+    let local = match timezone.map(str::trim) {
+        Some(tz) if tz.eq_ignore_ascii_case("local") => Local
+            .from_local_datetime(&midnight)
+            .earliest()
+            .map(|d| d.with_timezone(&Utc)),
+        Some(tz) => parse_offset(tz).and_then(|offset| {
+            offset
+                .from_local_datetime(&midnight)
+                .single()
+                .map(|d| d.with_timezone(&Utc))
+        }),
+        None => None,
+    };
+    local.unwrap_or_else(|| midnight.and_utc())
+}
+
+/// Parse a fixed UTC offset (`+02:00`, `-0500`, `+05`, optionally prefixed
+/// with `UTC`)
+fn parse_offset(tz: &str) -> Option<FixedOffset> {
+    let tz = tz
+        .strip_prefix("UTC")
+        .or_else(|| tz.strip_prefix("utc"))
+        .unwrap_or(tz);
+    let (sign, rest) = match tz.as_bytes().first()? {
+        b'+' => (1, &tz[1..]),
+        b'-' => (-1, &tz[1..]),
+        _ => return None,
+    };
+
+    let digits: String = rest.chars().filter(|c| *c != ':').collect();
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let (hours, minutes) = match digits.len() {
+        1 | 2 => (digits.parse::<i32>().ok()?, 0),
+        4 => (
+            digits[..2].parse::<i32>().ok()?,
+            digits[2..].parse::<i32>().ok()?,
+        ),
+        _ => return None,
+    };
+    if hours > 14 || minutes > 59 {
+        return None;
+    }
+
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_format_date_utc() {
+        let date = Utc.with_ymd_and_hms(2024, 5, 1, 23, 30, 0).unwrap();
+        assert_eq!(format_date(&date, None), "2024-05-01");
+        assert_eq!(format_date(&date, Some("UTC")), "2024-05-01");
+    }
+
+    #[test]
+    fn test_format_date_offsets() {
+        let date = Utc.with_ymd_and_hms(2024, 5, 1, 23, 30, 0).unwrap();
+        assert_eq!(format_date(&date, Some("+02:00")), "2024-05-02");
+        assert_eq!(format_date(&date, Some("+0530")), "2024-05-02");
+        assert_eq!(format_date(&date, Some("UTC+1")), "2024-05-02");
+
+        let early = Utc.with_ymd_and_hms(2024, 5, 1, 2, 0, 0).unwrap();
+        assert_eq!(format_date(&early, Some("-05:00")), "2024-04-30");
+    }
+
+    #[test]
+    fn test_format_date_invalid_timezone_falls_back_to_utc() {
+        let date = Utc.with_ymd_and_hms(2024, 5, 1, 23, 30, 0).unwrap();
+        assert_eq!(format_date(&date, Some("Mars/Olympus")), "2024-05-01");
+        assert_eq!(format_date(&date, Some("+25:00")), "2024-05-01");
+    }
+
+    #[test]
+    fn test_start_of_date_round_trips() {
+        let date = NaiveDate::from_ymd_opt(2024, 5, 1).unwrap();
+        for tz in [
+            None,
+            Some("UTC"),
+            Some("-05:00"),
+            Some("+09:30"),
+            Some("local"),
+        ] {
+            assert_eq!(format_date(&start_of_date(date, tz), tz), "2024-05-01");
+        }
+    }
+}
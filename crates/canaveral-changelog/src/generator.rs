@@ -1,29 +1,42 @@
 //! Changelog generation
 
-use std::collections::HashMap;
-
 use canaveral_core::config::ChangelogConfig;
-use canaveral_git::CommitInfo;
+use canaveral_git::{CommitInfo, DateRange, GitRepo, TagInfo};
+use chrono::{DateTime, Utc};
 use tracing::{debug, info, instrument};
 
 use crate::formatter::{ChangelogFormatter, MarkdownFormatter};
-use crate::parser::{CommitParser, ConventionalParser};
-use crate::types::{ChangelogEntry, ParsedCommit, Section};
+use crate::history::ChangelogHistory;
+use crate::parser::{CommitParser, ConventionalParser, ParserConfig};
+use crate::types::{
+    ChangelogEntry, ParsedCommit, Section, DEPRECATED_TRAILER, DEPRECATIONS_SECTION,
+    SECURITY_SECTION, SECURITY_TRAILER, UNRELEASED,
+};
 
 /// Changelog generator
 pub struct ChangelogGenerator {
     parser: Box<dyn CommitParser>,
     formatter: Box<dyn ChangelogFormatter>,
     config: ChangelogConfig,
+    release_date: Option<DateTime<Utc>>,
+    tag_date: Option<DateTime<Utc>>,
+    date_range: DateRange,
 }
 
 impl ChangelogGenerator {
     /// Create a new generator with default parser and formatter
+    ///
+    /// The parser picks up the type aliases and skip markers from `config`.
     pub fn new(config: ChangelogConfig) -> Self {
+        let parser_config = ParserConfig::from_changelog(&config);
+
         Self {
-            parser: Box::new(ConventionalParser::new()),
+            parser: Box::new(ConventionalParser::with_config(parser_config)),
             formatter: Box::new(MarkdownFormatter::new()),
             config,
+            release_date: None,
+            tag_date: None,
+            date_range: DateRange::default(),
         }
     }
 
@@ -39,6 +52,33 @@ impl ChangelogGenerator {
         self
     }
 
+    /// Use an explicit release date for generated entries
+    pub fn with_release_date(mut self, date: DateTime<Utc>) -> Self {
+        self.release_date = Some(date);
+        self
+    }
+
+    /// Date entries after a release tag (used when no explicit date is set)
+    pub fn with_tag(mut self, tag: &TagInfo) -> Self {
+        self.tag_date = tag.timestamp;
+        self
+    }
+
+    /// Only include commits whose commit date falls in `range`
+    ///
+    /// Applied on top of whatever commits are passed in, so combining it with
+    /// a tag range selects the intersection of both.
+    pub fn with_date_range(mut self, range: DateRange) -> Self {
+        self.date_range = range;
+        self
+    }
+
+    /// Release date for generated entries: the explicit date, then the tag
+    /// date, then now
+    pub fn release_date(&self) -> DateTime<Utc> {
+        self.release_date.or(self.tag_date).unwrap_or_else(Utc::now)
+    }
+
     /// Generate a changelog entry from commits
     #[instrument(skip(self, commits), fields(commit_count = commits.len()))]
     pub fn generate(&self, version: &str, commits: &[CommitInfo]) -> ChangelogEntry {
@@ -47,58 +87,58 @@ impl ChangelogGenerator {
             commit_count = commits.len(),
             "generating changelog entry"
         );
-        let mut entry = ChangelogEntry::new(version);
+        let mut entry = ChangelogEntry::new(version).with_date(self.release_date());
 
         // Parse commits
         let parsed: Vec<ParsedCommit> = commits
             .iter()
+            .filter(|c| self.date_range.includes(c))
             .filter_map(|c| self.parser.parse(c))
             .filter(|c| self.parser.should_include(c))
             .collect();
 
-        // Group commits by type
-        let mut grouped: HashMap<String, Vec<ParsedCommit>> = HashMap::new();
+        // Group commits by section, in order of first appearance
+        let mut grouped: Vec<Section> = Vec::new();
         let mut breaking = Vec::new();
+        let mut security = Section::new(SECURITY_SECTION);
+        let mut deprecations = Section::new(DEPRECATIONS_SECTION);
 
         for commit in parsed {
             if commit.breaking {
                 breaking.push(commit.clone());
             }
 
-            grouped
-                .entry(commit.commit_type.clone())
-                .or_default()
-                .push(commit);
-        }
+            // Trailers route commits into their own sections, whatever the type
+            if commit.trailer(SECURITY_TRAILER).is_some() {
+                security.add_commit(commit);
+                continue;
+            }
+            if commit.trailer(DEPRECATED_TRAILER).is_some() {
+                deprecations.add_commit(commit);
+                continue;
+            }
 
-        // Create sections based on config
-        for (commit_type, commits) in grouped {
-            if let Some(type_config) = self.config.types.get(&commit_type) {
-                if !type_config.hidden {
-                    let mut section = Section::new(&type_config.section);
-                    for commit in commits {
-                        section.add_commit(commit);
-                    }
-                    entry.add_section(section);
-                }
-            } else {
-                // Unknown type - add with default section name
-                let section_name = match commit_type.as_str() {
-                    "feat" => "Features",
-                    "fix" => "Bug Fixes",
-                    "docs" => "Documentation",
-                    "perf" => "Performance",
-                    _ => continue, // Skip unknown types
-                };
-
-                let mut section = Section::new(section_name);
-                for commit in commits {
+            // Hidden sections are kept so they stay available
+            // programmatically
+            let Some((title, hidden)) = self.section_for(&commit) else {
+                continue;
+            };
+            match grouped.iter_mut().find(|s| s.title == title) {
+                Some(section) => section.add_commit(commit),
+                None => {
+                    let mut section = Section::new(title).with_hidden(hidden);
                     section.add_commit(commit);
+                    grouped.push(section);
                 }
-                entry.add_section(section);
             }
         }
 
+        for section in grouped {
+            entry.add_section(section);
+        }
+        entry.add_section(deprecations);
+        entry.add_section(security);
+
         // Add breaking changes
         for commit in breaking {
             entry.add_breaking_change(commit);
@@ -117,6 +157,8 @@ impl ChangelogGenerator {
                 "Bug Fixes" => 1,
                 "Performance" => 2,
                 "Documentation" => 3,
+                DEPRECATIONS_SECTION => 4,
+                SECURITY_SECTION => 5,
                 _ => 99,
             };
             order(&a.title).cmp(&order(&b.title))
@@ -125,6 +167,91 @@ impl ChangelogGenerator {
         entry
     }
 
+    /// Section title and visibility for a commit
+    ///
+    /// Scope routes are checked first, in order, then the type's section.
+    /// Unknown types without a route are left out.
+    fn section_for(&self, commit: &ParsedCommit) -> Option<(&str, bool)> {
+        let scope = commit.scope.as_deref();
+        if let Some(route) = self
+            .config
+            .routes
+            .iter()
+            .find(|r| r.matches(&commit.commit_type, scope))
+        {
+            return Some((&route.section, route.hidden));
+        }
+        if let Some(type_config) = self.config.types.get(&commit.commit_type) {
+            return Some((&type_config.section, type_config.hidden));
+        }
+        let title = match commit.commit_type.as_str() {
+            "feat" => "Features",
+            "fix" => "Bug Fixes",
+            "docs" => "Documentation",
+            "perf" => "Performance",
+            _ => return None,
+        };
+        Some((title, false))
+    }
+
+    /// Generate the Unreleased entry from commits after `since_tag`
+    ///
+    /// Without `since_tag` the latest version tag is used, or the whole
+    /// history when nothing has been released. No version is computed and
+    /// nothing is written; the entry uses the configured skip patterns and
+    /// sections like a release would.
+    pub fn unreleased(
+        &self,
+        repo: &GitRepo,
+        since_tag: Option<&str>,
+    ) -> canaveral_git::Result<ChangelogEntry> {
+        let since_tag = match since_tag {
+            Some(tag) => Some(tag.to_string()),
+            None => repo.find_latest_tag(None)?.map(|t| t.name),
+        };
+        let commits = match &since_tag {
+            Some(tag) => repo.commits_since_tag(tag)?,
+            None => repo.all_commits()?,
+        };
+        debug!(since_tag = ?since_tag, commit_count = commits.len(), "previewing unreleased changes");
+        Ok(self.generate(UNRELEASED, &commits))
+    }
+
+    /// Format the Unreleased entry from commits after `since_tag`
+    ///
+    /// See [`Self::unreleased`]. An empty range renders an explicit
+    /// "No unreleased changes." rather than an empty section.
+    pub fn preview_unreleased(
+        &self,
+        repo: &GitRepo,
+        since_tag: Option<&str>,
+    ) -> canaveral_git::Result<String> {
+        let entry = self.unreleased(repo, since_tag)?;
+        Ok(self.format(&entry))
+    }
+
+    /// Generate the entry for `version` on top of an existing changelog
+    ///
+    /// Commits already listed under a released version in `history` are
+    /// left out, so rewritten history doesn't list them twice.
+    pub fn generate_after(
+        &self,
+        history: &ChangelogHistory,
+        version: &str,
+        commits: &[CommitInfo],
+    ) -> ChangelogEntry {
+        let new: Vec<CommitInfo> = commits
+            .iter()
+            .filter(|c| !history.lists_commit(&c.hash))
+            .cloned()
+            .collect();
+        debug!(
+            skipped = commits.len() - new.len(),
+            "left out commits already in the changelog"
+        );
+        self.generate(version, &new)
+    }
+
     /// Format a changelog entry to string
     pub fn format(&self, entry: &ChangelogEntry) -> String {
         self.formatter.format(entry, &self.config)
@@ -143,7 +270,7 @@ impl ChangelogGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::Utc;
+    use chrono::TimeZone;
 
     fn make_commit(message: &str) -> CommitInfo {
         CommitInfo::new(
@@ -199,4 +326,305 @@ mod tests {
         assert!(formatted.contains("1.0.0"));
         assert!(formatted.contains("Features") || formatted.contains("feat"));
     }
+
+    #[test]
+    fn test_config_type_aliases() {
+        let mut config = ChangelogConfig::default();
+        config
+            .aliases
+            .insert("bugfix".to_string(), "fix".to_string());
+        let generator = ChangelogGenerator::new(config);
+
+        let entry = generator.generate("1.0.1", &[make_commit("bugfix: handle empty input")]);
+
+        assert_eq!(entry.sections.len(), 1);
+        assert_eq!(entry.sections[0].title, "Bug Fixes");
+        assert_eq!(entry.sections[0].commits[0].commit_type, "fix");
+    }
+
+    #[test]
+    fn test_hidden_type_excluded_from_output() {
+        let mut config = ChangelogConfig::default();
+        config.types.insert(
+            "build".to_string(),
+            canaveral_core::config::CommitTypeConfig {
+                section: "Build System".to_string(),
+                hidden: false,
+            },
+        );
+        config.types.insert(
+            "ci".to_string(),
+            canaveral_core::config::CommitTypeConfig {
+                section: "CI".to_string(),
+                hidden: true,
+            },
+        );
+        let generator = ChangelogGenerator::new(config);
+
+        let entry = generator.generate(
+            "1.1.0",
+            &[
+                make_commit("build: bump toolchain"),
+                make_commit("ci: cache dependencies"),
+            ],
+        );
+
+        // The hidden section is still present in the parsed data
+        let ci = entry.sections.iter().find(|s| s.title == "CI").unwrap();
+        assert!(ci.hidden);
+        assert_eq!(ci.commits[0].description, "cache dependencies");
+
+        let output = generator.format(&entry);
+        assert!(output.contains("### Build System"));
+        assert!(output.contains("bump toolchain"));
+        assert!(!output.contains("### CI"));
+        assert!(!output.contains("cache dependencies"));
+    }
+
+    #[test]
+    fn test_trailers_route_sections() {
+        let generator = ChangelogGenerator::new(ChangelogConfig::default());
+
+        let entry = generator.generate(
+            "1.4.0",
+            &[
+                make_commit("feat: add export"),
+                make_commit("chore(deps): bump openssl").with_body("Security: CVE-2024-1234"),
+                make_commit("feat(api): add v2 endpoints")
+                    .with_body("Deprecated: the v1 endpoints\nRelease-Note: New v2 API"),
+            ],
+        );
+
+        let titles: Vec<&str> = entry.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Features", "Deprecations", "Security"]);
+        assert_eq!(entry.sections[0].commits.len(), 1);
+
+        let output = generator.format(&entry);
+        assert!(output.contains("### Security\n\n- bump openssl [CVE-2024-1234] (deps)"));
+        assert!(output.contains("### Deprecations\n\n- New v2 API (api)"));
+        assert!(!output.contains("add v2 endpoints"));
+    }
+
+    fn api_routes() -> ChangelogConfig {
+        use canaveral_core::config::SectionRoute;
+
+        ChangelogConfig {
+            routes: vec![
+                SectionRoute {
+                    types: vec!["feat".to_string(), "fix".to_string()],
+                    scopes: vec!["api".to_string()],
+                    section: "API".to_string(),
+                    hidden: false,
+                },
+                // Never reached for api fixes: the rule above comes first
+                SectionRoute {
+                    types: vec!["fix".to_string()],
+                    section: "Fixes".to_string(),
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_scope_route_overrides_type_section() {
+        let generator = ChangelogGenerator::new(api_routes());
+
+        let entry = generator.generate(
+            "2.0.0",
+            &[
+                make_commit("feat(api): add v2 endpoints"),
+                make_commit("fix(api): reject empty bodies"),
+                make_commit("fix(cli): handle missing config"),
+            ],
+        );
+
+        let titles: Vec<&str> = entry.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["API", "Fixes"]);
+
+        let api = &entry.sections[0];
+        let descriptions: Vec<&str> = api.commits.iter().map(|c| c.description.as_str()).collect();
+        assert_eq!(
+            descriptions,
+            vec!["add v2 endpoints", "reject empty bodies"]
+        );
+        // Each commit lands in exactly one section
+        let total: usize = entry.sections.iter().map(|s| s.commits.len()).sum();
+        assert_eq!(total, 3);
+    }
+
+    #[test]
+    fn test_unrouted_commits_fall_through_to_type_sections() {
+        let generator = ChangelogGenerator::new(api_routes());
+
+        let entry = generator.generate(
+            "2.0.0",
+            &[
+                make_commit("feat(ui): dark mode"),
+                make_commit("feat: export to csv"),
+                make_commit("docs(api): document pagination"),
+            ],
+        );
+
+        let titles: Vec<&str> = entry.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Features", "Documentation"]);
+        assert_eq!(entry.sections[0].commits.len(), 2);
+        // The api scope only routes the types its rule lists
+        assert_eq!(
+            entry.sections[1].commits[0].description,
+            "document pagination"
+        );
+    }
+
+    #[test]
+    fn test_skipped_commits_excluded() {
+        let generator = ChangelogGenerator::new(ChangelogConfig::default());
+
+        let entry = generator.generate(
+            "1.1.0",
+            &[
+                make_commit("feat: add feature"),
+                make_commit("fix: internal tweak [skip changelog]"),
+                make_commit("chore(release): 1.0.0")
+                    .with_body(canaveral_core::workflow::RELEASE_COMMIT_TRAILER),
+            ],
+        );
+
+        let descriptions: Vec<&str> = entry
+            .sections
+            .iter()
+            .flat_map(|s| s.commits.iter().map(|c| c.description.as_str()))
+            .collect();
+        assert_eq!(descriptions, vec!["add feature"]);
+    }
+
+    #[test]
+    fn test_date_range_filters_commits() {
+        let at = |day: u32, message: &str| {
+            CommitInfo::new(
+                "abc1234567890",
+                message,
+                "Test Author",
+                "test@example.com",
+                Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap(),
+            )
+        };
+        let range = DateRange::since(Utc.with_ymd_and_hms(2024, 1, 10, 0, 0, 0).unwrap())
+            .with_until(Utc.with_ymd_and_hms(2024, 1, 20, 0, 0, 0).unwrap());
+        let generator = ChangelogGenerator::new(ChangelogConfig::default()).with_date_range(range);
+
+        let entry = generator.generate(
+            "1.1.0",
+            &[
+                at(25, "feat: too late"),
+                at(15, "feat: in range"),
+                at(5, "fix: too early"),
+            ],
+        );
+
+        assert_eq!(entry.sections.len(), 1);
+        assert_eq!(entry.sections[0].commits.len(), 1);
+        assert_eq!(entry.sections[0].commits[0].description, "in range");
+    }
+
+    #[test]
+    fn test_explicit_release_date() {
+        let date = Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap();
+        let tag = TagInfo::new("v1.2.0", "abc123")
+            .with_timestamp(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap());
+        let generator = ChangelogGenerator::new(ChangelogConfig::default())
+            .with_tag(&tag)
+            .with_release_date(date);
+
+        let formatted = generator.generate_formatted("1.2.0", &[make_commit("feat: add feature")]);
+
+        assert!(formatted.starts_with("## [1.2.0] - 2024-05-01"));
+    }
+
+    #[test]
+    fn test_tag_derived_release_date() {
+        let tag = TagInfo::new("v1.1.0", "abc123")
+            .with_timestamp(Utc.with_ymd_and_hms(2023, 11, 20, 9, 0, 0).unwrap());
+        let generator = ChangelogGenerator::new(ChangelogConfig::default()).with_tag(&tag);
+
+        let entry = generator.generate("1.1.0", &[make_commit("fix: fix bug")]);
+
+        assert_eq!(entry.date, tag.timestamp.unwrap());
+        assert!(generator
+            .format(&entry)
+            .starts_with("## [1.1.0] - 2023-11-20"));
+    }
+
+    /// Repository with `released` commits tagged `v1.0.0`, then `unreleased`
+    fn repo_with_release(released: &[&str], unreleased: &[&str]) -> tempfile::TempDir {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let commit = |message: &str| {
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap()
+        };
+
+        for message in released {
+            commit(message);
+        }
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.0.0", head.as_object(), false)
+            .unwrap();
+        for message in unreleased {
+            commit(message);
+        }
+        temp
+    }
+
+    #[test]
+    fn test_preview_unreleased() {
+        let temp = repo_with_release(
+            &["feat: initial feature"],
+            &[
+                "feat(api): add search",
+                "fix: handle empty query",
+                "fix: internal tweak [skip changelog]",
+            ],
+        );
+        let repo = GitRepo::open(temp.path()).unwrap();
+        let generator = ChangelogGenerator::new(ChangelogConfig::default());
+
+        let entry = generator.unreleased(&repo, None).unwrap();
+        assert!(entry.is_unreleased());
+        let titles: Vec<&str> = entry.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Features", "Bug Fixes"]);
+
+        let preview = generator.preview_unreleased(&repo, None).unwrap();
+        assert!(preview.starts_with("## [Unreleased]\n\n### Features\n\n- add search (api)"));
+        assert!(preview.contains("### Bug Fixes\n\n- handle empty query"));
+        assert!(!preview.contains("initial feature"));
+        assert!(!preview.contains("internal tweak"));
+    }
+
+    #[test]
+    fn test_preview_without_unreleased_changes() {
+        let temp = repo_with_release(&["feat: initial feature"], &[]);
+        let repo = GitRepo::open(temp.path()).unwrap();
+        let generator = ChangelogGenerator::new(ChangelogConfig::default());
+
+        let preview = generator.preview_unreleased(&repo, Some("v1.0.0")).unwrap();
+        assert_eq!(preview, "## [Unreleased]\n\nNo unreleased changes.\n\n");
+    }
+
+    #[test]
+    fn test_release_date_defaults_to_now() {
+        let generator = ChangelogGenerator::new(ChangelogConfig::default())
+            .with_tag(&TagInfo::new("v1.0.0", "abc123"));
+
+        let before = Utc::now();
+        let entry = generator.generate("1.0.0", &[make_commit("feat: add feature")]);
+
+        assert!(entry.date >= before);
+    }
 }
@@ -0,0 +1,612 @@
+//! Reading existing changelogs
+//!
+//! Regenerating past versions from git breaks once history has been
+//! rewritten: squashed or rebased commits drop out of tag ranges or show up
+//! twice. [`ChangelogHistory`] parses a changelog written by
+//! [`MarkdownFormatter`](crate::MarkdownFormatter) back into entries so only
+//! the new version has to be generated; past versions are kept exactly as
+//! written.
+
+use std::path::Path;
+use std::sync::OnceLock;
+
+use canaveral_core::config::ChangelogConfig;
+use regex::Regex;
+use tracing::debug;
+
+use crate::formatter::parse_date;
+use crate::types::{
+    ChangelogEntry, Footer, ParsedCommit, Section, SECURITY_SECTION, SECURITY_TRAILER,
+};
+
+/// Heading the formatter writes above breaking changes
+const BREAKING_HEADING: &str = "⚠ BREAKING CHANGES";
+/// Heading the formatter writes above entry notes
+const NOTES_HEADING: &str = "Notes";
+
+/// `## [1.2.0] - 2024-05-01` or `## [Unreleased]`
+/// Shortest abbreviated hash [`ChangelogHistory::lists_commit`] matches on
+const MIN_HASH_PREFIX: usize = 7;
+
+/// Whether two possibly abbreviated commit hashes name the same commit
+fn hashes_match(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    short.len() >= MIN_HASH_PREFIX
+        && short.chars().all(|c| c.is_ascii_hexdigit())
+        && long
+            .get(..short.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(short))
+}
+
+fn version_heading_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"^## \[([^\]]+)\](?: - (\d{4}-\d{2}-\d{2}))?\s*$").expect("Invalid regex")
+    })
+}
+
+/// Trailing ` (abc1234)` or ` ([abc1234](https://…/commit/<full hash>))`
+fn hash_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r" \((?:\[([0-9a-f]+)\]\([^)\s]*/commit/([0-9a-f]+)\)|([0-9a-f]+))\)$")
+            .expect("Invalid regex")
+    })
+}
+
+/// Trailing ` (scope)`
+fn scope_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r" \(([^()\s]+)\)$").expect("Invalid regex"))
+}
+
+/// Trailing ` [CVE-2024-1234, GHSA-…]` on security entries
+fn advisory_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| Regex::new(r" \[([^\]]+)\]$").expect("Invalid regex"))
+}
+
+/// A parsed markdown changelog
+#[derive(Debug, Clone)]
+pub struct ChangelogHistory {
+    content: String,
+    /// Byte offset of each entry's heading in `content`
+    offsets: Vec<usize>,
+    entries: Vec<ChangelogEntry>,
+}
+
+impl ChangelogHistory {
+    /// Parse a changelog written by the markdown formatter with `config`
+    ///
+    /// `config` tells which of hashes and authors the entries carry, and the
+    /// time zone their dates are in. Anything before the first version
+    /// heading is kept as the preamble.
+    pub fn parse(content: &str, config: &ChangelogConfig) -> Self {
+        let mut offsets = Vec::new();
+        let mut entries: Vec<ChangelogEntry> = Vec::new();
+        let mut section: Option<Section> = None;
+        let mut in_breaking = false;
+        let mut notes: Option<Vec<&str>> = None;
+        let mut offset = 0;
+
+        for line in content.split_inclusive('\n') {
+            let start = offset;
+            offset += line.len();
+            let line = line.trim_end();
+
+            if let Some(captures) = version_heading_regex().captures(line) {
+                if let Some(entry) = entries.last_mut() {
+                    finish_entry(entry, section.take(), notes.take());
+                }
+                let mut entry = ChangelogEntry::new(&captures[1]);
+                if let Some(date) = captures
+                    .get(2)
+                    .and_then(|d| parse_date(d.as_str(), config.timezone.as_deref()))
+                {
+                    entry = entry.with_date(date);
+                }
+                offsets.push(start);
+                entries.push(entry);
+                in_breaking = false;
+                continue;
+            }
+            let Some(entry) = entries.last_mut() else {
+                continue;
+            };
+
+            if let Some(lines) = notes.as_mut() {
+                lines.push(line);
+            } else if let Some(title) = line.strip_prefix("### ") {
+                if let Some(finished) = section.take() {
+                    entry.add_section(finished);
+                }
+                in_breaking = title == BREAKING_HEADING;
+                if title == NOTES_HEADING {
+                    notes = Some(Vec::new());
+                } else if !in_breaking {
+                    section = Some(Section::new(title));
+                }
+            } else if let Some(item) = line.strip_prefix("- ") {
+                let date = entry.date;
+                if in_breaking {
+                    let mut commit = parse_item(item, None, config, false);
+                    commit.breaking = true;
+                    commit.timestamp = date;
+                    entry.add_breaking_change(commit);
+                } else if let Some(section) = section.as_mut() {
+                    let mut commit = parse_item(item, Some(&section.title), config, true);
+                    commit.timestamp = date;
+                    section.add_commit(commit);
+                }
+            }
+        }
+        if let Some(entry) = entries.last_mut() {
+            finish_entry(entry, section.take(), notes.take());
+        }
+
+        debug!(entries = entries.len(), "parsed existing changelog");
+        Self {
+            content: content.to_string(),
+            offsets,
+            entries,
+        }
+    }
+
+    /// Read and parse the changelog at `path`; a missing file is empty
+    pub fn read(path: &Path, config: &ChangelogConfig) -> std::io::Result<Self> {
+        match std::fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content, config)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::parse("", config)),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Entries in the changelog, newest first
+    pub fn entries(&self) -> &[ChangelogEntry] {
+        &self.entries
+    }
+
+    /// Text before the first version heading
+    pub fn preamble(&self) -> &str {
+        &self.content[..self.offsets.first().copied().unwrap_or(self.content.len())]
+    }
+
+    /// Released versions, newest first
+    pub fn released_versions(&self) -> Vec<&str> {
+        self.entries
+            .iter()
+            .filter(|e| !e.is_unreleased())
+            .map(|e| e.version.as_str())
+            .collect()
+    }
+
+    /// Whether `version` has already been released in the changelog
+    pub fn contains(&self, version: &str) -> bool {
+        self.entries
+            .iter()
+            .any(|e| e.version == version && !e.is_unreleased())
+    }
+
+    /// Whether a commit is listed under a released version
+    ///
+    /// Matches on the abbreviated hash, since that is what the changelog
+    /// shows when it doesn't link commits. Hashes shorter than seven hex
+    /// digits never match, so a changelog written without hashes lists no
+    /// commits.
+    pub fn lists_commit(&self, hash: &str) -> bool {
+        self.entries
+            .iter()
+            .filter(|e| !e.is_unreleased())
+            .flat_map(|e| e.sections.iter().flat_map(|s| &s.commits))
+            .any(|c| hashes_match(&c.hash, hash))
+    }
+
+    /// The changelog with `formatted`, the entry for `version`, added
+    ///
+    /// The entry goes after the preamble and replaces an Unreleased entry;
+    /// released versions are kept byte for byte. When `version` has already
+    /// been released the content is returned unchanged.
+    pub fn insert(&self, version: &str, formatted: &str) -> String {
+        if self.contains(version) {
+            debug!(
+                version,
+                "version already in changelog, leaving it unchanged"
+            );
+            return self.content.clone();
+        }
+
+        let rest = self
+            .entries
+            .iter()
+            .zip(&self.offsets)
+            .find(|(entry, _)| !entry.is_unreleased())
+            .map_or("", |(_, &offset)| &self.content[offset..]);
+
+        let mut output = self.preamble().to_string();
+        if !output.is_empty() && !output.ends_with("\n\n") {
+            output.push_str(if output.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        output.push_str(formatted);
+        if !rest.is_empty() && !formatted.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// The changelog with the released entry for `version` replaced by
+    /// `formatted`
+    ///
+    /// Every other entry, and reference links after the last one, are kept
+    /// byte for byte. A version that isn't in the changelog yet is added as
+    /// by [`insert`](Self::insert).
+    pub fn replace(&self, version: &str, formatted: &str) -> String {
+        let Some(index) = self
+            .entries
+            .iter()
+            .position(|e| e.version == version && !e.is_unreleased())
+        else {
+            return self.insert(version, formatted);
+        };
+
+        let start = self.offsets[index];
+        let end = match self.offsets.get(index + 1) {
+            Some(&next) => next,
+            None => self.links_start(start),
+        };
+        let rest = &self.content[end..];
+
+        let mut output = self.content[..start].to_string();
+        output.push_str(formatted);
+        if !rest.is_empty() && !formatted.ends_with("\n\n") {
+            output.push('\n');
+        }
+        output.push_str(rest);
+        output
+    }
+
+    /// The changelog with the reference links after the last entry replaced
+    /// by `links`, as [`MarkdownFormatter::format_links`] writes them
+    ///
+    /// [`MarkdownFormatter::format_links`]: crate::MarkdownFormatter::format_links
+    pub fn with_links(&self, links: &str) -> String {
+        let from = self.offsets.last().copied().unwrap_or(self.content.len());
+        let mut output = self.content[..self.links_start(from)].to_string();
+        if !links.is_empty() && !output.is_empty() && !output.ends_with("\n\n") {
+            output.push_str(if output.ends_with('\n') { "\n" } else { "\n\n" });
+        }
+        output.push_str(links);
+        output
+    }
+
+    /// Offset of the reference links at the end of the changelog, searching
+    /// from `from`; the end of the content when there are none
+    fn links_start(&self, from: usize) -> usize {
+        let mut start = self.content.len();
+        let mut offset = self.content.len();
+        for line in self.content[from..].split_inclusive('\n').rev() {
+            offset -= line.len();
+            let line = line.trim_end();
+            if is_link_definition(line) {
+                start = offset;
+            } else if !line.is_empty() {
+                break;
+            }
+        }
+        start
+    }
+}
+
+/// Close the open section and attach notes to `entry`
+fn finish_entry(entry: &mut ChangelogEntry, section: Option<Section>, notes: Option<Vec<&str>>) {
+    if let Some(section) = section {
+        entry.add_section(section);
+    }
+    if let Some(lines) = notes {
+        // Only the last entry's notes can be followed by version links
+        let text = lines
+            .iter()
+            .take_while(|l| !is_link_definition(l))
+            .copied()
+            .collect::<Vec<_>>()
+            .join("\n");
+        let text = text.trim();
+        if !text.is_empty() {
+            entry.notes = Some(text.to_string());
+        }
+    }
+}
+
+/// `[1.2.0]: https://…` reference link lines after the last entry
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
+/// Parse a `- description (scope) (hash) - author` item back into a commit
+///
+/// Only section items carry links and authors; breaking change items show
+/// the plain short hash.
+fn parse_item(
+    item: &str,
+    section: Option<&str>,
+    config: &ChangelogConfig,
+    full_item: bool,
+) -> ParsedCommit {
+    let mut rest = item.trim_end();
+    let mut author = String::new();
+    let mut hash = String::new();
+    let mut scope = None;
+    let mut footers = Vec::new();
+
+    if full_item && config.include_authors {
+        if let Some((before, name)) = rest.rsplit_once(" - ") {
+            author = name.to_string();
+            rest = before;
+        }
+    }
+    if config.include_hashes {
+        if let Some(captures) = hash_regex().captures(rest) {
+            hash = captures
+                .get(2)
+                .or_else(|| captures.get(3))
+                .map_or("", |m| m.as_str())
+                .to_string();
+            rest = &rest[..captures.get(0).expect("whole match").start()];
+        }
+    }
+    if let Some(captures) = scope_regex().captures(rest) {
+        scope = Some(captures[1].to_string());
+        rest = &rest[..captures.get(0).expect("whole match").start()];
+    }
+    if section == Some(SECURITY_SECTION) {
+        if let Some(captures) = advisory_regex().captures(rest) {
+            footers.extend(captures[1].split(", ").map(|advisory| Footer {
+                token: SECURITY_TRAILER.to_string(),
+                value: advisory.to_string(),
+            }));
+            rest = &rest[..captures.get(0).expect("whole match").start()];
+        }
+    }
+
+    ParsedCommit {
+        hash,
+        commit_type: section
+            .and_then(|title| commit_type_for(title, config))
+            .unwrap_or_default(),
+        scope,
+        breaking: false,
+        description: rest.to_string(),
+        body: None,
+        footers,
+        author,
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+/// Commit type whose section is `title`
+fn commit_type_for(title: &str, config: &ChangelogConfig) -> Option<String> {
+    config
+        .types
+        .iter()
+        .filter(|(_, t)| t.section == title)
+        .map(|(name, _)| name.clone())
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::formatter::{ChangelogFormatter, MarkdownFormatter};
+    use crate::generator::ChangelogGenerator;
+    use canaveral_git::CommitInfo;
+    use chrono::{TimeZone, Utc};
+
+    fn commit(hash: &str, message: &str) -> CommitInfo {
+        CommitInfo::new(hash, message, "Ada", "ada@example.com", Utc::now())
+    }
+
+    fn released(config: &ChangelogConfig) -> (ChangelogEntry, ChangelogEntry) {
+        let date = |day| Utc.with_ymd_and_hms(2024, 5, day, 9, 30, 0).unwrap();
+        let v1 = ChangelogGenerator::new(config.clone())
+            .with_release_date(date(1))
+            .generate(
+                "1.0.0",
+                &[
+                    commit("aaaaaaa111", "feat: initial release"),
+                    commit("bbbbbbb222", "fix(parser): handle (nested) parens"),
+                ],
+            );
+        let v2 = ChangelogGenerator::new(config.clone())
+            .with_release_date(date(20))
+            .generate(
+                "2.0.0",
+                &[
+                    commit("ccccccc333", "feat(api)!: drop v1 endpoints"),
+                    commit("ddddddd444", "chore(deps): bump openssl")
+                        .with_body("Security: CVE-2024-1234"),
+                ],
+            );
+        (v1, v2)
+    }
+
+    #[test]
+    fn test_parse_round_trip() {
+        let config = ChangelogConfig {
+            include_authors: true,
+            ..Default::default()
+        };
+        let formatter = MarkdownFormatter::new().with_repo_url("https://github.com/acme/app");
+        let (v1, v2) = released(&config);
+        let content = format!(
+            "# Changelog\n\n{}",
+            formatter.format_all(&[v2.clone(), v1.clone()], &config)
+        );
+
+        let history = ChangelogHistory::parse(&content, &config);
+        assert_eq!(history.preamble(), "# Changelog\n\n");
+        let versions: Vec<&str> = history
+            .entries()
+            .iter()
+            .map(|e| e.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["2.0.0", "1.0.0"]);
+
+        let parsed = &history.entries()[1];
+        let titles: Vec<&str> = parsed.sections.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, vec!["Features", "Bug Fixes"]);
+        let fix = &parsed.sections[1].commits[0];
+        assert_eq!(fix.commit_type, "fix");
+        assert_eq!(fix.description, "handle (nested) parens");
+        assert_eq!(fix.scope.as_deref(), Some("parser"));
+        assert_eq!(fix.hash, "bbbbbbb222");
+        assert_eq!(fix.author, "Ada");
+
+        let parsed_v2 = &history.entries()[0];
+        assert_eq!(
+            parsed_v2.breaking_changes[0].description,
+            "drop v1 endpoints"
+        );
+        let security = parsed_v2.sections.last().unwrap();
+        assert_eq!(security.title, SECURITY_SECTION);
+        assert_eq!(
+            security.commits[0].trailer("Security"),
+            Some("CVE-2024-1234")
+        );
+
+        // Formatting the parsed entries reproduces the original text
+        for (original, parsed) in [(&v2, parsed_v2), (&v1, parsed)] {
+            assert_eq!(
+                formatter.format(parsed, &config),
+                formatter.format(original, &config)
+            );
+        }
+        assert!(history.lists_commit("bbbbbbb222"));
+        assert!(history.lists_commit("bbbbbbb222cafe"));
+        assert!(!history.lists_commit("eeeeeee555"));
+        assert!(!history.lists_commit("bbbbbb"));
+        assert!(!history.lists_commit(""));
+    }
+
+    #[test]
+    fn test_lists_no_commits_without_hashes() {
+        let config = ChangelogConfig {
+            include_hashes: false,
+            ..Default::default()
+        };
+        let formatter = MarkdownFormatter::new();
+        let (v1, v2) = released(&config);
+        let content = format!(
+            "# Changelog\n\n{}{}",
+            formatter.format(&v2, &config),
+            formatter.format(&v1, &config)
+        );
+        let history = ChangelogHistory::parse(&content, &config);
+
+        // Parsed commits have no hash, which must not match every commit
+        assert!(!history.lists_commit("aaaaaaa111"));
+        let entry = ChangelogGenerator::new(config.clone()).generate_after(
+            &history,
+            "2.1.0",
+            &[commit("eeeeeee555", "feat: add export")],
+        );
+        assert_eq!(entry.sections[0].commits.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_only_adds_new_version() {
+        let config = ChangelogConfig::default();
+        let formatter = MarkdownFormatter::new();
+        let (v1, v2) = released(&config);
+        let past = format!(
+            "{}{}",
+            formatter.format(&v2, &config),
+            formatter.format(&v1, &config)
+        );
+        let content = format!(
+            "# Changelog\n\n## [Unreleased]\n\n- stale preview\n\n{}",
+            past
+        );
+        let history = ChangelogHistory::parse(&content, &config);
+
+        let generator = ChangelogGenerator::new(config.clone());
+        // The rewritten history still carries an already released commit
+        let entry = generator.generate_after(
+            &history,
+            "2.1.0",
+            &[
+                commit("eeeeeee555", "feat: add export"),
+                commit("aaaaaaa111", "feat: initial release"),
+            ],
+        );
+        let updated = history.insert("2.1.0", &generator.format(&entry));
+
+        assert_eq!(
+            updated,
+            format!("# Changelog\n\n{}{}", generator.format(&entry), past)
+        );
+        assert_eq!(updated.matches("initial release").count(), 1);
+        assert_eq!(updated.matches("## [").count(), 3);
+
+        // Adding a version that is already there changes nothing
+        let again = ChangelogHistory::parse(&updated, &config);
+        assert_eq!(again.insert("2.1.0", "## [2.1.0]\n\n"), updated);
+    }
+
+    #[test]
+    fn test_replace_released_version() {
+        let config = ChangelogConfig::default();
+        let formatter = MarkdownFormatter::new();
+        let (v1, v2) = released(&config);
+        let links = "[2.0.0]: https://example.com/v2\n[1.0.0]: https://example.com/v1\n";
+        let content = format!(
+            "# Changelog\n\n{}{}{}",
+            formatter.format(&v2, &config),
+            formatter.format(&v1, &config),
+            links
+        );
+        let history = ChangelogHistory::parse(&content, &config);
+
+        let fixed = "## [1.0.0] - 2024-05-01\n\n### Features\n\n- initial release, typo fixed\n\n";
+        let updated = history.replace("1.0.0", fixed);
+        assert_eq!(
+            updated,
+            format!(
+                "# Changelog\n\n{}{}{}",
+                formatter.format(&v2, &config),
+                fixed,
+                links
+            )
+        );
+
+        let fixed_v2 = "## [2.0.0] - 2024-05-20\n\n- rewritten\n\n";
+        let updated = ChangelogHistory::parse(&updated, &config).replace("2.0.0", fixed_v2);
+        assert!(updated.starts_with(&format!("# Changelog\n\n{}## [1.0.0]", fixed_v2)));
+        assert!(updated.ends_with(links));
+
+        // A version the changelog doesn't list yet is added on top
+        let added = history.replace("2.1.0", "## [2.1.0]\n\n");
+        assert!(added.starts_with("# Changelog\n\n## [2.1.0]\n\n## [2.0.0]"));
+    }
+
+    #[test]
+    fn test_with_links_replaces_reference_links() {
+        let config = ChangelogConfig::default();
+        let formatter = MarkdownFormatter::new().with_repo_url("https://github.com/acme/app");
+        let (v1, v2) = released(&config);
+        let entries = format!(
+            "# Changelog\n\n{}{}",
+            formatter.format(&v2, &config),
+            formatter.format(&v1, &config)
+        );
+        let stale = format!("{}[1.0.0]: https://example.com/v1\n", entries);
+
+        let history = ChangelogHistory::parse(&stale, &config);
+        assert_eq!(history.released_versions(), ["2.0.0", "1.0.0"]);
+        let links = formatter.format_links(&history.released_versions(), &config);
+        assert_eq!(history.with_links(&links), format!("{}{}", entries, links));
+        assert_eq!(
+            ChangelogHistory::parse(&entries, &config).with_links(&links),
+            format!("{}{}", entries, links)
+        );
+    }
+}
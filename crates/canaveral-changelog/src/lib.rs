@@ -2,15 +2,21 @@
 //!
 //! This crate provides commit parsing and changelog generation capabilities.
 
+pub mod diff;
 pub mod formatter;
 pub mod generator;
+pub mod history;
 pub mod parser;
 pub mod release_notes;
 pub mod types;
 
+pub use diff::{diff_versions, diff_versions_with_config, PackageVersionChange, VersionDiff};
 pub use formatter::{ChangelogFormatter, FormatterRegistry, MarkdownFormatter};
 pub use generator::ChangelogGenerator;
-pub use parser::{CommitParser, ConventionalParser, ParserRegistry};
-pub use release_notes::{ReleaseNotes, ReleaseNotesGenerator};
-pub use types::ParsedCommit;
+pub use history::ChangelogHistory;
+pub use parser::{CommitParser, ConventionalParser, ParserConfig, ParserRegistry};
+pub use release_notes::{
+    Collapse, GithubNotesOptions, HtmlMode, ReleaseNotes, ReleaseNotesGenerator,
+};
 pub use types::{ChangelogEntry, Section};
+pub use types::{ParsedCommit, ReleaseAs, NO_UNRELEASED_CHANGES, UNRELEASED};
@@ -24,7 +24,7 @@ fn conventional_regex() -> &'static Regex {
 fn footer_regex() -> &'static Regex {
     static RE: OnceLock<Regex> = OnceLock::new();
     RE.get_or_init(|| {
-        Regex::new(r"^(?P<token>[A-Za-z-]+|BREAKING CHANGE): (?P<value>.+)$")
+        Regex::new(r"^(?P<token>[A-Za-z-]+|BREAKING CHANGE):(?: (?P<value>.*))?$")
             .expect("Invalid regex")
     })
 }
@@ -51,7 +51,9 @@ impl ConventionalParser {
     fn parse_message(&self, message: &str, body: Option<&str>) -> Option<ParsedMessage> {
         let caps = conventional_regex().captures(message)?;
 
-        let commit_type = caps.name("type")?.as_str().to_lowercase();
+        let commit_type = self
+            .config
+            .resolve_type(&caps.name("type")?.as_str().to_lowercase());
         let scope = caps.name("scope").map(|m| m.as_str().to_string());
         let breaking_marker = caps.name("breaking").is_some();
         let description = caps.name("description")?.as_str().to_string();
@@ -80,37 +82,61 @@ impl ConventionalParser {
     }
 
     /// Parse the body and extract footers
+    ///
+    /// Only the final paragraph is a trailer block, and only when every line
+    /// of it is a `Token: value` trailer or an indented continuation, so
+    /// lines such as `Note: ...` in the body text stay in the body.
     fn parse_body(&self, body: &str) -> (Option<String>, Vec<Footer>) {
-        let mut footers = Vec::new();
-        let mut body_lines = Vec::new();
-        let mut in_footer = false;
-
-        for line in body.lines() {
-            if let Some(caps) = footer_regex().captures(line) {
-                in_footer = true;
-                footers.push(Footer {
-                    token: caps.name("token").unwrap().as_str().to_string(),
-                    value: caps.name("value").unwrap().as_str().to_string(),
-                });
-            } else if in_footer && line.starts_with(' ') {
-                // Continuation of previous footer
-                if let Some(last) = footers.last_mut() {
-                    last.value.push('\n');
-                    last.value.push_str(line.trim());
-                }
-            } else if !in_footer {
-                body_lines.push(line);
-            }
+        let lines: Vec<&str> = body.trim_end().lines().collect();
+        let block_start = lines
+            .iter()
+            .rposition(|line| line.trim().is_empty())
+            .map_or(0, |i| i + 1);
+
+        match parse_trailer_block(&lines[block_start..]) {
+            Some(footers) => (join_body(&lines[..block_start]), footers),
+            None => (join_body(&lines), Vec::new()),
         }
+    }
+}
 
-        let body_text = if body_lines.is_empty() {
-            None
-        } else {
-            Some(body_lines.join("\n").trim().to_string())
-        };
+/// Parse a paragraph as trailers, or `None` if any line isn't one
+///
+/// Values continue on following indented lines, so a trailer may also start
+/// with an empty value (`Release-Note:`) followed by indented text.
+fn parse_trailer_block(lines: &[&str]) -> Option<Vec<Footer>> {
+    let mut footers: Vec<Footer> = Vec::new();
+
+    for (i, line) in lines.iter().enumerate() {
+        if line.starts_with(' ') {
+            // Continuation of previous footer
+            let last = footers.last_mut()?;
+            if !last.value.is_empty() {
+                last.value.push('\n');
+            }
+            last.value.push_str(line.trim());
+            continue;
+        }
 
-        (body_text, footers)
+        let caps = footer_regex().captures(line)?;
+        let value = caps.name("value").map_or("", |v| v.as_str().trim());
+        let continued = lines.get(i + 1).is_some_and(|next| next.starts_with(' '));
+        if value.is_empty() && !continued {
+            return None;
+        }
+        footers.push(Footer {
+            token: caps.name("token").unwrap().as_str().to_string(),
+            value: value.to_string(),
+        });
     }
+
+    (!footers.is_empty()).then_some(footers)
+}
+
+/// Body text from its lines, or `None` if blank
+fn join_body(lines: &[&str]) -> Option<String> {
+    let text = lines.join("\n").trim().to_string();
+    (!text.is_empty()).then_some(text)
 }
 
 impl Default for ConventionalParser {
@@ -136,6 +162,11 @@ impl CommitParser for ConventionalParser {
             return None;
         }
 
+        if self.config.is_skipped(commit) {
+            debug!(hash = %&commit.hash[..7.min(commit.hash.len())], "skipping marked commit");
+            return None;
+        }
+
         let parsed = self.parse_message(&commit.message, commit.body.as_deref())?;
 
         debug!(
@@ -177,6 +208,8 @@ impl CommitParser for ConventionalParser {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::ReleaseAs;
+    use canaveral_core::types::{ReleaseCommit, ReleaseType};
     use chrono::Utc;
 
     fn make_commit(message: &str) -> CommitInfo {
@@ -263,6 +296,92 @@ mod tests {
         assert_eq!(parsed.footers[0].value, "#123");
     }
 
+    #[test]
+    fn test_parse_trailers() {
+        let parser = ConventionalParser::new();
+        let mut commit = make_commit("fix(auth): validate token audience");
+        commit.body = Some(
+            "Tokens for other services were accepted.\n\n\
+             Release-Note:\n  Sign-in now rejects tokens issued\n  for other apps.\n\
+             security: CVE-2024-1234\n\
+             Security: GHSA-abcd-efgh-ijkl\n\
+             Refs: #42"
+                .to_string(),
+        );
+
+        let parsed = parser.parse(&commit).unwrap();
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Tokens for other services were accepted.")
+        );
+        assert_eq!(parsed.footers.len(), 4);
+        assert_eq!(
+            parsed.trailer("release-note"),
+            Some("Sign-in now rejects tokens issued\nfor other apps.")
+        );
+        assert_eq!(
+            parsed.trailers("SECURITY").collect::<Vec<_>>(),
+            vec!["CVE-2024-1234", "GHSA-abcd-efgh-ijkl"]
+        );
+        assert_eq!(parsed.trailer("Refs"), Some("#42"));
+        assert_eq!(
+            parsed.display_description(),
+            "Sign-in now rejects tokens issued\nfor other apps."
+        );
+
+        // A bare `Token:` line without indented continuation stays in the body
+        let mut commit = make_commit("feat: add export");
+        commit.body = Some("Formats:\n- csv\n- json".to_string());
+        let parsed = parser.parse(&commit).unwrap();
+        assert!(parsed.footers.is_empty());
+        assert_eq!(parsed.body.as_deref(), Some("Formats:\n- csv\n- json"));
+
+        // Only the final paragraph is a trailer block
+        let mut commit = make_commit("fix: rebuild cache");
+        commit.body = Some(
+            "Note: the cache is rebuilt on start.\nIt was stale.\n\nSecurity: CVE-2024-1\nRefs: #42"
+                .to_string(),
+        );
+        let parsed = parser.parse(&commit).unwrap();
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Note: the cache is rebuilt on start.\nIt was stale.")
+        );
+        assert_eq!(parsed.trailer("note"), None);
+        assert_eq!(parsed.trailer("Security"), Some("CVE-2024-1"));
+
+        // A trailer block mixed with prose is body text
+        commit.body = Some("Body text\n\nNote: keep this\nin the body".to_string());
+        let parsed = parser.parse(&commit).unwrap();
+        assert!(parsed.footers.is_empty());
+        assert_eq!(
+            parsed.body.as_deref(),
+            Some("Body text\n\nNote: keep this\nin the body")
+        );
+    }
+
+    #[test]
+    fn test_parse_release_as() {
+        let parser = ConventionalParser::new();
+        let mut commit = make_commit("chore: prepare 1.5");
+        commit.body = Some("Release-As: v1.5.0".to_string());
+        let parsed = parser.parse(&commit).unwrap();
+        assert_eq!(
+            parsed.release_as(),
+            Some(ReleaseAs::Version("1.5.0".to_string()))
+        );
+
+        let mut commit = make_commit("fix: typo in help text");
+        commit.body = Some("Refs: #12\nRelease-As: skip".to_string());
+        let parsed = parser.parse(&commit).unwrap();
+        assert_eq!(parsed.release_as(), Some(ReleaseAs::Skip));
+        assert_eq!(parsed.release_type(), None);
+
+        let parsed = parser.parse(&make_commit("fix: typo")).unwrap();
+        assert_eq!(parsed.release_as(), None);
+        assert_eq!(parsed.release_type(), Some(ReleaseType::Patch));
+    }
+
     #[test]
     fn test_breaking_change_footer() {
         let parser = ConventionalParser::new();
@@ -273,6 +392,54 @@ mod tests {
         assert!(parsed.breaking);
     }
 
+    #[test]
+    fn test_parse_type_alias() {
+        let parser = ConventionalParser::with_config(
+            ParserConfig::default()
+                .alias("bugfix", "fix")
+                .alias("Feature", "feat"),
+        );
+
+        let parsed = parser.parse(&make_commit("bugfix(ui): fix crash")).unwrap();
+        assert_eq!(parsed.commit_type, "fix");
+        assert_eq!(parsed.scope, Some("ui".to_string()));
+
+        let parsed = parser.parse(&make_commit("FEATURE: add thing")).unwrap();
+        assert_eq!(parsed.commit_type, "feat");
+
+        let parsed = parser.parse(&make_commit("chore: unaliased")).unwrap();
+        assert_eq!(parsed.commit_type, "chore");
+    }
+
+    #[test]
+    fn test_skip_markers() {
+        let parser = ConventionalParser::with_config(
+            ParserConfig::default().skip_marker("[Skip Changelog]"),
+        );
+
+        let commit = make_commit("fix: typo in docs [skip changelog]");
+        assert!(parser.parse(&commit).is_none());
+
+        let mut commit = make_commit("feat: squashed work");
+        commit.body = Some("* wip\n* [SKIP CHANGELOG]".to_string());
+        assert!(parser.parse(&commit).is_none());
+
+        assert!(parser.parse(&make_commit("fix: real fix")).is_some());
+    }
+
+    #[test]
+    fn test_skips_canaveral_release_commits() {
+        // Recognized without any configured markers
+        let parser = ConventionalParser::new();
+
+        let mut release = make_commit("feat(release): 2.0.0");
+        release.body = Some("Release-Tool: canaveral".to_string());
+        assert!(parser.parse(&release).is_none());
+
+        // The same subject without the trailer is a normal commit
+        assert!(parser.parse(&make_commit("feat(release): 2.0.0")).is_some());
+    }
+
     #[test]
     fn test_should_include_with_excludes() {
         let parser = ConventionalParser::with_config(ParserConfig::default().exclude_type("chore"));
@@ -1,6 +1,10 @@
 //! Parser configuration types
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+
+use canaveral_core::config::ChangelogConfig;
+use canaveral_core::workflow::RELEASE_COMMIT_TRAILER;
+use canaveral_git::CommitInfo;
 
 /// Configuration for the commit parser
 #[derive(Debug, Clone, Default)]
@@ -13,6 +17,10 @@ pub struct ParserConfig {
     pub include_untyped: bool,
     /// Whether to include merge commits
     pub include_merges: bool,
+    /// Type aliases, mapping a commit type to its canonical type
+    pub aliases: HashMap<String, String>,
+    /// Lowercased markers that exclude a commit when found in its message
+    pub skip_markers: Vec<String>,
 }
 
 impl ParserConfig {
@@ -23,9 +31,19 @@ impl ParserConfig {
             exclude_types: HashSet::new(),
             include_untyped: true,
             include_merges: true,
+            aliases: HashMap::new(),
+            skip_markers: Vec::new(),
         }
     }
 
+    /// Parser settings from the changelog configuration (type aliases and
+    /// skip markers)
+    pub fn from_changelog(config: &ChangelogConfig) -> Self {
+        Self::default()
+            .with_aliases(config.aliases.clone())
+            .with_skip_markers(config.skip_markers.iter().cloned())
+    }
+
     /// Add a type to include
     pub fn include_type(mut self, type_name: impl Into<String>) -> Self {
         self.include_types.insert(type_name.into());
@@ -38,6 +56,72 @@ impl ParserConfig {
         self
     }
 
+    /// Treat commits of type `alias` as type `target`
+    pub fn alias(mut self, alias: impl Into<String>, target: impl Into<String>) -> Self {
+        self.aliases
+            .insert(alias.into().to_lowercase(), target.into().to_lowercase());
+        self
+    }
+
+    /// Add several type aliases
+    pub fn with_aliases<I, K, V>(mut self, aliases: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        for (alias, target) in aliases {
+            self = self.alias(alias, target);
+        }
+        self
+    }
+
+    /// Exclude commits whose message contains `marker` (case-insensitive)
+    pub fn skip_marker(mut self, marker: impl Into<String>) -> Self {
+        self.skip_markers.push(marker.into().to_lowercase());
+        self
+    }
+
+    /// Add several skip markers
+    pub fn with_skip_markers<I, S>(mut self, markers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for marker in markers {
+            self = self.skip_marker(marker);
+        }
+        self
+    }
+
+    /// Whether a commit is excluded by a skip marker or is a release commit
+    /// created by canaveral
+    pub fn is_skipped(&self, commit: &CommitInfo) -> bool {
+        let body = commit.body.as_deref().unwrap_or_default();
+        if body
+            .lines()
+            .any(|line| line.trim().eq_ignore_ascii_case(RELEASE_COMMIT_TRAILER))
+        {
+            return true;
+        }
+
+        if self.skip_markers.is_empty() {
+            return false;
+        }
+        let message = commit.full_message().to_lowercase();
+        self.skip_markers
+            .iter()
+            .any(|m| message.contains(m.as_str()))
+    }
+
+    /// Resolve a commit type through the alias table
+    pub fn resolve_type(&self, commit_type: &str) -> String {
+        self.aliases
+            .get(commit_type)
+            .cloned()
+            .unwrap_or_else(|| commit_type.to_string())
+    }
+
     /// Set whether to include untyped commits
     pub fn with_untyped(mut self, include: bool) -> Self {
         self.include_untyped = include;
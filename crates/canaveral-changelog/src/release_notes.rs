@@ -61,7 +61,7 @@ impl ReleaseNotesGenerator {
             contributors_set.insert(commit.author.clone());
 
             let entry = NoteEntry {
-                description: commit.description.clone(),
+                description: commit.display_description().to_string(),
                 scope: commit.scope.clone(),
                 hash: commit.hash.chars().take(7).collect(),
                 author: commit.author.clone(),
@@ -80,7 +80,7 @@ impl ReleaseNotesGenerator {
                     })
                     .unwrap_or_else(|| commit.description.clone());
                 breaking_details.push(BreakingChange {
-                    description: commit.description.clone(),
+                    description: commit.display_description().to_string(),
                     migration_guidance: migration,
                     scope: commit.scope.clone(),
                 });
@@ -189,6 +189,183 @@ impl ReleaseNotesGenerator {
         output
     }
 
+    /// Format release notes as HTML
+    ///
+    /// [`HtmlMode::Plain`] gives an unstyled fragment for embedding in a
+    /// page. [`HtmlMode::Email`] gives a complete document laid out with
+    /// tables and inline styles only, which is what Outlook and most webmail
+    /// clients render reliably. All commit text is escaped.
+    #[instrument(skip(self, notes), fields(version = %notes.version))]
+    pub fn format_html(&self, notes: &ReleaseNotes, mode: HtmlMode) -> String {
+        let style = |tag: HtmlTag| match mode {
+            HtmlMode::Plain => String::new(),
+            HtmlMode::Email => format!(" style=\"{}\"", tag.email_style()),
+        };
+        let mut body = String::new();
+
+        body.push_str(&format!(
+            "<h1{}>Release {}</h1>\n",
+            style(HtmlTag::H1),
+            escape_html(&notes.version)
+        ));
+        if !notes.headline.is_empty() {
+            body.push_str(&format!(
+                "<p{}>{}</p>\n",
+                style(HtmlTag::P),
+                escape_html(&notes.headline)
+            ));
+        }
+
+        let mut sections = vec![
+            ("Breaking Changes", &notes.breaking_changes),
+            ("New Features", &notes.features),
+            ("Bug Fixes", &notes.fixes),
+        ];
+        if self.config.categorize {
+            sections.push(("Other Changes", &notes.other_changes));
+        }
+        for (title, entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            body.push_str(&format!("<h2{}>{}</h2>\n", style(HtmlTag::H2), title));
+            body.push_str(&format!("<ul{}>\n", style(HtmlTag::Ul)));
+            for entry in entries {
+                body.push_str(&format!("<li{}>", style(HtmlTag::Li)));
+                if let Some(scope) = &entry.scope {
+                    body.push_str(&format!("<strong>{}</strong>: ", escape_html(scope)));
+                }
+                body.push_str(&format!(
+                    "{} (<code{}>{}</code>)</li>\n",
+                    escape_html(&entry.description),
+                    style(HtmlTag::Code),
+                    escape_html(&entry.hash)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        if let Some(guide) = &notes.migration_guide {
+            body.push_str(&format!("<h2{}>Migration Guide</h2>\n", style(HtmlTag::H2)));
+            for block in guide.split("\n\n").map(str::trim).filter(|b| !b.is_empty()) {
+                match block.strip_prefix("### ") {
+                    Some(heading) => body.push_str(&format!(
+                        "<h3{}>{}</h3>\n",
+                        style(HtmlTag::H3),
+                        escape_html(heading)
+                    )),
+                    None => body.push_str(&format!(
+                        "<p{}>{}</p>\n",
+                        style(HtmlTag::P),
+                        escape_html(block).replace('\n', "<br>")
+                    )),
+                }
+            }
+        }
+
+        if !notes.contributors.is_empty() && self.config.include_contributors {
+            body.push_str(&format!("<h2{}>Contributors</h2>\n", style(HtmlTag::H2)));
+            body.push_str(&format!("<ul{}>\n", style(HtmlTag::Ul)));
+            for contributor in &notes.contributors {
+                body.push_str(&format!(
+                    "<li{}>{}</li>\n",
+                    style(HtmlTag::Li),
+                    escape_html(contributor)
+                ));
+            }
+            body.push_str("</ul>\n");
+        }
+
+        match mode {
+            HtmlMode::Plain => body,
+            HtmlMode::Email => format!(
+                concat!(
+                    "<!DOCTYPE html>\n",
+                    "<html>\n<head>\n<meta charset=\"utf-8\">\n",
+                    "<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n",
+                    "<title>Release {version}</title>\n</head>\n",
+                    "<body style=\"margin:0;padding:0;background-color:#f4f4f5;\">\n",
+                    "<table role=\"presentation\" width=\"100%\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" style=\"background-color:#f4f4f5;\">\n",
+                    "<tr><td align=\"center\" style=\"padding:24px 12px;\">\n",
+                    "<table role=\"presentation\" width=\"600\" cellpadding=\"0\" cellspacing=\"0\" border=\"0\" style=\"width:600px;max-width:600px;background-color:#ffffff;border:1px solid #e4e4e7;\">\n",
+                    "<tr><td style=\"{cell}\">\n{body}</td></tr>\n",
+                    "</table>\n</td></tr>\n</table>\n</body>\n</html>\n"
+                ),
+                version = escape_html(&notes.version),
+                cell = EMAIL_CELL_STYLE,
+                body = body
+            ),
+        }
+    }
+
+    /// Format release notes as a GitHub Release body
+    ///
+    /// Sections are wrapped in `<details>` blocks according to
+    /// `options.collapse`; breaking changes always stay expanded. The body
+    /// ends with a "Full Changelog" compare link when one is configured.
+    #[instrument(skip(self, notes, options), fields(version = %notes.version))]
+    pub fn format_github(&self, notes: &ReleaseNotes, options: &GithubNotesOptions) -> String {
+        let mut output = String::new();
+
+        if !notes.headline.is_empty() {
+            output.push_str(&format!("{}\n\n", notes.headline));
+        }
+
+        if !notes.breaking_changes.is_empty() {
+            output.push_str("## Breaking Changes\n\n");
+            for entry in &notes.breaking_changes {
+                self.format_entry(&mut output, entry);
+            }
+            output.push('\n');
+        }
+
+        let mut sections = vec![
+            ("New Features", &notes.features),
+            ("Bug Fixes", &notes.fixes),
+        ];
+        if self.config.categorize {
+            sections.push(("Other Changes", &notes.other_changes));
+        }
+        for (title, entries) in sections {
+            if entries.is_empty() {
+                continue;
+            }
+            let mut body = String::new();
+            for entry in entries {
+                self.format_entry(&mut body, entry);
+            }
+            push_github_section(&mut output, title, entries.len(), &body, options.collapse);
+        }
+
+        if let Some(guide) = &notes.migration_guide {
+            let body = format!("{}\n", guide.trim_end());
+            let count = notes.breaking_changes.len();
+            push_github_section(
+                &mut output,
+                "Migration Guide",
+                count,
+                &body,
+                options.collapse,
+            );
+        }
+
+        if !notes.contributors.is_empty() && self.config.include_contributors {
+            let body: String = notes
+                .contributors
+                .iter()
+                .map(|c| format!("- {}\n", c))
+                .collect();
+            let count = notes.contributors.len();
+            push_github_section(&mut output, "Contributors", count, &body, options.collapse);
+        }
+
+        if let Some(url) = &options.compare_url {
+            output.push_str(&format!("**Full Changelog**: {}\n", url));
+        }
+
+        output
+    }
+
     /// Generate and format in one step
     #[instrument(skip(self, commits), fields(commit_count = commits.len()))]
     pub fn generate_formatted(&self, version: &str, commits: &[CommitInfo]) -> String {
@@ -266,6 +443,81 @@ impl ReleaseNotesGenerator {
     }
 }
 
+/// When [`ReleaseNotesGenerator::format_github`] collapses sections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Collapse {
+    /// Flat markdown headings
+    #[default]
+    Never,
+    /// Every section in a `<details>` block
+    Always,
+    /// Sections with more than this many entries
+    Over(usize),
+}
+
+impl Collapse {
+    fn applies(self, entries: usize) -> bool {
+        match self {
+            Self::Never => false,
+            Self::Always => true,
+            Self::Over(threshold) => entries > threshold,
+        }
+    }
+}
+
+/// Options for [`ReleaseNotesGenerator::format_github`]
+#[derive(Debug, Clone, Default)]
+pub struct GithubNotesOptions {
+    /// Which sections to collapse
+    pub collapse: Collapse,
+    /// Target of the "Full Changelog" link
+    pub compare_url: Option<String>,
+}
+
+impl GithubNotesOptions {
+    /// Flat markdown without a compare link
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Collapse sections according to `collapse`
+    pub fn with_collapse(mut self, collapse: Collapse) -> Self {
+        self.collapse = collapse;
+        self
+    }
+
+    /// Link to the compare view between `previous_tag` and `tag`
+    pub fn with_compare(mut self, repository_url: &str, previous_tag: &str, tag: &str) -> Self {
+        self.compare_url = Some(format!(
+            "{}/compare/{}...{}",
+            repository_url.trim_end_matches('/'),
+            previous_tag,
+            tag
+        ));
+        self
+    }
+}
+
+/// Append a section as a heading, or as a `<details>` block when collapsed
+///
+/// GitHub only renders markdown inside `<details>` after a blank line.
+fn push_github_section(
+    output: &mut String,
+    title: &str,
+    entries: usize,
+    body: &str,
+    collapse: Collapse,
+) {
+    if collapse.applies(entries) {
+        output.push_str(&format!(
+            "<details>\n<summary><strong>{}</strong> ({})</summary>\n\n{}\n</details>\n\n",
+            title, entries, body
+        ));
+    } else {
+        output.push_str(&format!("## {}\n\n{}\n", title, body));
+    }
+}
+
 /// Structured release notes
 #[derive(Debug, Clone)]
 pub struct ReleaseNotes {
@@ -300,6 +552,63 @@ pub struct NoteEntry {
     pub author: String,
 }
 
+/// HTML flavour produced by [`ReleaseNotesGenerator::format_html`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HtmlMode {
+    /// Unstyled fragment
+    #[default]
+    Plain,
+    /// Complete document with table layout and inline styles
+    Email,
+}
+
+/// Base font and padding of the email content cell
+const EMAIL_CELL_STYLE: &str =
+    "padding:32px;font-family:Arial,Helvetica,sans-serif;font-size:15px;line-height:22px;color:#18181b;";
+
+/// Elements given inline styles in email mode
+#[derive(Debug, Clone, Copy)]
+enum HtmlTag {
+    H1,
+    H2,
+    H3,
+    P,
+    Ul,
+    Li,
+    Code,
+}
+
+impl HtmlTag {
+    /// Inline style; email clients drop `<style>` blocks and external CSS
+    fn email_style(self) -> &'static str {
+        match self {
+            Self::H1 => "margin:0 0 16px 0;font-family:Arial,Helvetica,sans-serif;font-size:24px;line-height:30px;font-weight:bold;color:#18181b;",
+            Self::H2 => "margin:24px 0 8px 0;font-family:Arial,Helvetica,sans-serif;font-size:18px;line-height:24px;font-weight:bold;color:#18181b;",
+            Self::H3 => "margin:16px 0 8px 0;font-family:Arial,Helvetica,sans-serif;font-size:16px;line-height:22px;font-weight:bold;color:#18181b;",
+            Self::P => "margin:0 0 12px 0;font-family:Arial,Helvetica,sans-serif;font-size:15px;line-height:22px;color:#3f3f46;",
+            Self::Ul => "margin:0 0 12px 0;padding:0 0 0 24px;",
+            Self::Li => "margin:0 0 6px 0;font-family:Arial,Helvetica,sans-serif;font-size:15px;line-height:22px;color:#3f3f46;",
+            Self::Code => "font-family:Consolas,Menlo,monospace;font-size:13px;color:#71717a;",
+        }
+    }
+}
+
+/// Escape text for HTML element content and attribute values
+fn escape_html(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 /// A breaking change with migration guidance
 #[derive(Debug, Clone)]
 struct BreakingChange {
@@ -416,4 +725,104 @@ mod tests {
         assert!(notes.fixes.is_empty());
         assert_eq!(notes.headline, "Maintenance release.");
     }
+
+    #[test]
+    fn test_format_html_email_inline_styles() {
+        let config = ReleaseNotesConfig {
+            include_contributors: true,
+            ..Default::default()
+        };
+        let generator = ReleaseNotesGenerator::new(config);
+        let notes = generator.generate(
+            "1.2.0",
+            &[
+                make_commit("feat(ui): render <Select> & <Option> in forms"),
+                make_commit("fix: don't drop \"quoted\" args"),
+            ],
+        );
+
+        let html = generator.format_html(&notes, HtmlMode::Email);
+
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<table role=\"presentation\""));
+        assert!(!html.contains("<style"));
+        assert!(!html.contains("<link"));
+        assert!(html.contains("<h1 style=\"margin:0 0 16px 0;"));
+        assert!(html.contains("<h2 style=\""));
+        assert!(html.contains("<li style=\""));
+        assert!(html
+            .contains("<strong>ui</strong>: render &lt;Select&gt; &amp; &lt;Option&gt; in forms"));
+        assert!(html.contains("don&#39;t drop &quot;quoted&quot; args"));
+        assert!(!html.contains("<Select>"));
+        assert!(html.contains("Test Author"));
+    }
+
+    #[test]
+    fn test_format_html_plain() {
+        let generator = ReleaseNotesGenerator::new(ReleaseNotesConfig::default());
+        let notes = generator.generate("2.0.0", &[make_commit("feat!: drop <v1> API & shims")]);
+
+        let html = generator.format_html(&notes, HtmlMode::Plain);
+
+        assert!(html.starts_with("<h1>Release 2.0.0</h1>"));
+        assert!(!html.contains("style="));
+        assert!(
+            html.contains("<h2>Breaking Changes</h2>\n<ul>\n<li>drop &lt;v1&gt; API &amp; shims")
+        );
+        assert!(html.contains("<h2>Migration Guide</h2>"));
+    }
+
+    fn github_notes() -> ReleaseNotes {
+        let generator = ReleaseNotesGenerator::new(ReleaseNotesConfig::default());
+        let mut commits = vec![
+            make_commit("fix: resolve crash on startup"),
+            make_commit("feat!: drop the v1 API"),
+        ];
+        for i in 0..4 {
+            commits.push(make_commit(&format!("feat(ui): widget {}", i)));
+        }
+        generator.generate("2.0.0", &commits)
+    }
+
+    #[test]
+    fn test_format_github_collapses_past_threshold() {
+        let generator = ReleaseNotesGenerator::new(ReleaseNotesConfig::default());
+        let notes = github_notes();
+        let options = GithubNotesOptions::new()
+            .with_collapse(Collapse::Over(3))
+            .with_compare("https://github.com/acme/app/", "v1.4.0", "v2.0.0");
+
+        let body = generator.format_github(&notes, &options);
+
+        // Four features are over the threshold, one fix is not
+        assert!(body.contains(
+            "<details>\n<summary><strong>New Features</strong> (4)</summary>\n\n- **ui**: widget 0"
+        ));
+        assert!(body.contains("## Bug Fixes\n\n- resolve crash on startup"));
+        assert!(!body.contains("<summary><strong>Bug Fixes"));
+        // Breaking changes are never collapsed
+        assert!(body.contains("## Breaking Changes\n\n- drop the v1 API"));
+        assert_eq!(body.matches("<details>").count(), 1);
+        assert!(body.ends_with(
+            "**Full Changelog**: https://github.com/acme/app/compare/v1.4.0...v2.0.0\n"
+        ));
+    }
+
+    #[test]
+    fn test_format_github_flat() {
+        let generator = ReleaseNotesGenerator::new(ReleaseNotesConfig::default());
+        let notes = github_notes();
+
+        let body = generator.format_github(&notes, &GithubNotesOptions::new());
+        assert!(!body.contains("<details>"));
+        assert!(body.contains("## New Features\n\n"));
+        assert!(!body.contains("Full Changelog"));
+
+        let body = generator.format_github(
+            &notes,
+            &GithubNotesOptions::new().with_collapse(Collapse::Always),
+        );
+        // Features, fixes, migration guide and contributors
+        assert_eq!(body.matches("<details>").count(), 4);
+    }
 }
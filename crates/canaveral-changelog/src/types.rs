@@ -1,8 +1,30 @@
 //! Changelog types
 
+use canaveral_core::summary::{ChangelogItem, ChangelogSectionSummary, ChangelogSummary};
+use canaveral_core::types::{ReleaseCommit, ReleaseType};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Trailer whose value replaces the commit description in changelogs
+pub const RELEASE_NOTE_TRAILER: &str = "Release-Note";
+/// Trailer that routes a commit into the [`SECURITY_SECTION`]
+pub const SECURITY_TRAILER: &str = "Security";
+/// Trailer that routes a commit into the [`DEPRECATIONS_SECTION`]
+pub const DEPRECATED_TRAILER: &str = "Deprecated";
+/// Trailer that forces the next version (`Release-As: 1.5.0`) or keeps a
+/// commit out of releases (`Release-As: skip`)
+pub const RELEASE_AS_TRAILER: &str = "Release-As";
+
+/// Section for commits with a `Security` trailer
+pub const SECURITY_SECTION: &str = "Security";
+/// Section for commits with a `Deprecated` trailer
+pub const DEPRECATIONS_SECTION: &str = "Deprecations";
+
+/// Version of the entry collecting changes since the latest release
+pub const UNRELEASED: &str = "Unreleased";
+/// Placeholder body of an [`UNRELEASED`] entry without changes
+pub const NO_UNRELEASED_CHANGES: &str = "No unreleased changes.";
+
 /// A parsed commit from conventional commit format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParsedCommit {
@@ -41,6 +63,84 @@ impl ParsedCommit {
     pub fn is_patch(&self) -> bool {
         matches!(self.commit_type.as_str(), "fix" | "perf")
     }
+
+    /// Values of a repeatable trailer (case-insensitive), in commit order
+    pub fn trailers<'a>(&'a self, token: &'a str) -> impl Iterator<Item = &'a str> {
+        self.footers
+            .iter()
+            .filter(move |f| f.token.eq_ignore_ascii_case(token))
+            .map(|f| f.value.as_str())
+    }
+
+    /// Value of the first trailer with `token` (case-insensitive)
+    pub fn trailer(&self, token: &str) -> Option<&str> {
+        self.footers
+            .iter()
+            .find(|f| f.token.eq_ignore_ascii_case(token))
+            .map(|f| f.value.as_str())
+    }
+
+    /// Description shown in changelogs: the `Release-Note` trailer if
+    /// present, else the commit description
+    pub fn display_description(&self) -> &str {
+        self.trailer(RELEASE_NOTE_TRAILER)
+            .unwrap_or(&self.description)
+    }
+
+    /// Release intent from the `Release-As` trailer
+    ///
+    /// When the trailer is repeated the last value wins.
+    pub fn release_as(&self) -> Option<ReleaseAs> {
+        let value = self.trailers(RELEASE_AS_TRAILER).last()?.trim();
+        if value.is_empty() {
+            None
+        } else if value.eq_ignore_ascii_case("skip") {
+            Some(ReleaseAs::Skip)
+        } else {
+            Some(ReleaseAs::Version(
+                value.strip_prefix('v').unwrap_or(value).to_string(),
+            ))
+        }
+    }
+}
+
+/// Release intent carried by a `Release-As` trailer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseAs {
+    /// Release this exact version instead of the computed one
+    Version(String),
+    /// The commit never triggers a release
+    Skip,
+}
+
+impl ReleaseCommit for ParsedCommit {
+    fn release_type(&self) -> Option<ReleaseType> {
+        if self.release_as() == Some(ReleaseAs::Skip) {
+            None
+        } else if self.is_major() {
+            Some(ReleaseType::Major)
+        } else if self.is_minor() {
+            Some(ReleaseType::Minor)
+        } else if self.is_patch() {
+            Some(ReleaseType::Patch)
+        } else {
+            None
+        }
+    }
+
+    fn summary(&self) -> String {
+        let hash = self.hash.get(..7).unwrap_or(&self.hash);
+        let scope = self
+            .scope
+            .as_ref()
+            .map(|s| format!("({})", s))
+            .unwrap_or_default();
+        let breaking = if self.breaking { "!" } else { "" };
+        format!(
+            "{} {}{}{}: {}",
+            hash, self.commit_type, scope, breaking, self.description
+        )
+    }
 }
 
 /// A footer field from a conventional commit
@@ -59,6 +159,9 @@ pub struct Section {
     pub title: String,
     /// Commits in this section
     pub commits: Vec<ParsedCommit>,
+    /// Whether this section is hidden from rendered output
+    #[serde(default)]
+    pub hidden: bool,
 }
 
 impl Section {
@@ -67,9 +170,16 @@ impl Section {
         Self {
             title: title.into(),
             commits: Vec::new(),
+            hidden: false,
         }
     }
 
+    /// Mark the section as hidden (kept in the entry, but not rendered)
+    pub fn with_hidden(mut self, hidden: bool) -> Self {
+        self.hidden = hidden;
+        self
+    }
+
     /// Add a commit to the section
     pub fn add_commit(&mut self, commit: ParsedCommit) {
         self.commits.push(commit);
@@ -132,9 +242,45 @@ impl ChangelogEntry {
         self
     }
 
-    /// Check if entry has any content
+    /// Sections that should be rendered
+    pub fn visible_sections(&self) -> impl Iterator<Item = &Section> {
+        self.sections.iter().filter(|s| !s.hidden)
+    }
+
+    /// Check if entry has any visible content
     pub fn is_empty(&self) -> bool {
-        self.sections.is_empty() && self.breaking_changes.is_empty()
+        self.visible_sections().next().is_none() && self.breaking_changes.is_empty()
+    }
+
+    /// Whether this entry collects unreleased changes rather than a version
+    pub fn is_unreleased(&self) -> bool {
+        self.version == UNRELEASED
+    }
+}
+
+impl From<&ParsedCommit> for ChangelogItem {
+    fn from(commit: &ParsedCommit) -> Self {
+        Self {
+            description: commit.display_description().to_string(),
+            scope: commit.scope.clone(),
+            hash: commit.hash.clone(),
+        }
+    }
+}
+
+/// Visible sections only, matching what the formatters render
+impl From<&ChangelogEntry> for ChangelogSummary {
+    fn from(entry: &ChangelogEntry) -> Self {
+        Self {
+            sections: entry
+                .visible_sections()
+                .map(|section| ChangelogSectionSummary {
+                    title: section.title.clone(),
+                    items: section.commits.iter().map(Into::into).collect(),
+                })
+                .collect(),
+            breaking_changes: entry.breaking_changes.iter().map(Into::into).collect(),
+        }
     }
 }
 
@@ -247,4 +393,72 @@ mod tests {
 
         assert!(!section.is_empty());
     }
+
+    #[test]
+    fn test_release_commit() {
+        let commit = |commit_type: &str, breaking: bool| ParsedCommit {
+            hash: "a1b2c3d4e5f6".to_string(),
+            commit_type: commit_type.to_string(),
+            scope: Some("api".to_string()),
+            breaking,
+            description: "add search".to_string(),
+            body: None,
+            footers: vec![],
+            author: "Test".to_string(),
+            timestamp: Utc::now(),
+        };
+
+        assert_eq!(
+            commit("feat", true).release_type(),
+            Some(ReleaseType::Major)
+        );
+        assert_eq!(
+            commit("feat", false).release_type(),
+            Some(ReleaseType::Minor)
+        );
+        assert_eq!(
+            commit("perf", false).release_type(),
+            Some(ReleaseType::Patch)
+        );
+        assert_eq!(commit("docs", false).release_type(), None);
+        assert_eq!(
+            commit("feat", true).summary(),
+            "a1b2c3d feat(api)!: add search"
+        );
+
+        let commits = [commit("fix", false), commit("feat", true)];
+        assert_eq!(
+            ReleaseType::from_parsed_commits(&commits, true),
+            Some(ReleaseType::Minor)
+        );
+    }
+
+    #[test]
+    fn test_entry_to_summary_skips_hidden_sections() {
+        let commit = |description: &str| ParsedCommit {
+            hash: "abc123".to_string(),
+            commit_type: "feat".to_string(),
+            scope: Some("cli".to_string()),
+            breaking: false,
+            description: description.to_string(),
+            body: None,
+            footers: vec![],
+            author: "Test".to_string(),
+            timestamp: Utc::now(),
+        };
+
+        let mut entry = ChangelogEntry::new("1.0.0");
+        let mut features = Section::new("Features");
+        features.add_commit(commit("add export"));
+        entry.add_section(features);
+        let mut chores = Section::new("Chores").with_hidden(true);
+        chores.add_commit(commit("bump deps"));
+        entry.add_section(chores);
+
+        let summary = ChangelogSummary::from(&entry);
+        assert_eq!(summary.sections.len(), 1);
+        assert_eq!(summary.sections[0].title, "Features");
+        assert_eq!(summary.sections[0].items[0].description, "add export");
+        assert_eq!(summary.sections[0].items[0].scope.as_deref(), Some("cli"));
+    }
 }
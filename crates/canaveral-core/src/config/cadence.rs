@@ -0,0 +1,80 @@
+//! Release cadence configuration
+
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConfigError, Result};
+
+/// Minimum amount of change between releases
+///
+/// Both thresholds are off by default. A release below either one is
+/// refused unless forced.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CadenceConfig {
+    /// Releasable commits (features, fixes, breaking changes) required
+    /// since the last release
+    pub min_commits: Option<usize>,
+
+    /// Time required since the last release tag, such as `12h`, `7d` or `2w`
+    pub min_interval: Option<String>,
+}
+
+impl CadenceConfig {
+    /// `min_interval` as a duration
+    pub fn min_interval(&self) -> Result<Option<Duration>> {
+        self.min_interval
+            .as_deref()
+            .map(|value| {
+                parse_interval(value).ok_or_else(|| {
+                    ConfigError::InvalidValue {
+                        field: "cadence.min_interval".to_string(),
+                        message: format!("'{}' is not a duration like 30m, 12h, 7d or 2w", value),
+                    }
+                    .into()
+                })
+            })
+            .transpose()
+    }
+}
+
+/// Parse `<number><unit>` with unit `s`, `m`, `h`, `d` or `w`
+fn parse_interval(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value.find(|c: char| !c.is_ascii_digit())?;
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount.parse().ok()?;
+    let seconds = match unit.trim() {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return None,
+    };
+    Some(Duration::from_secs(amount.checked_mul(seconds)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("90s"), Some(Duration::from_secs(90)));
+        assert_eq!(parse_interval("12h"), Some(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_interval("7d"), Some(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_interval("2w"), Some(Duration::from_secs(14 * 86400)));
+        assert_eq!(parse_interval("7"), None);
+        assert_eq!(parse_interval("d"), None);
+        assert_eq!(parse_interval("3 days"), None);
+
+        let config = CadenceConfig {
+            min_interval: Some("soon".to_string()),
+            ..Default::default()
+        };
+        assert!(config.min_interval().is_err());
+        assert_eq!(CadenceConfig::default().min_interval().unwrap(), None);
+    }
+}
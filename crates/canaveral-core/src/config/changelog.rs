@@ -21,6 +21,21 @@ pub struct ChangelogConfig {
     #[serde(default)]
     pub types: HashMap<String, CommitTypeConfig>,
 
+    /// Section routing rules keyed on commit type and scope, checked in
+    /// order before `types`; the first matching rule picks the section
+    #[serde(default)]
+    pub routes: Vec<SectionRoute>,
+
+    /// Commit type aliases, mapping a non-standard type to the type it should
+    /// be treated as (e.g. `bugfix` -> `fix`)
+    #[serde(default)]
+    pub aliases: HashMap<String, String>,
+
+    /// Markers that exclude a commit from the changelog and version
+    /// inference when they appear in its message (case-insensitive)
+    #[serde(default)]
+    pub skip_markers: Vec<String>,
+
     /// Header template
     pub header: Option<String>,
 
@@ -32,6 +47,18 @@ pub struct ChangelogConfig {
 
     /// Whether to include dates
     pub include_dates: bool,
+
+    /// Time zone used to format release dates: "UTC", "local", or a fixed
+    /// offset such as "+02:00" (defaults to UTC)
+    pub timezone: Option<String>,
+
+    /// Repository URL used for commit and version compare links
+    /// (e.g. `https://github.com/owner/repo`)
+    pub repository_url: Option<String>,
+
+    /// Parse the existing changelog file and only add the new version,
+    /// keeping past versions as written
+    pub preserve_existing: bool,
 }
 
 impl Default for ChangelogConfig {
@@ -92,10 +119,16 @@ impl Default for ChangelogConfig {
             file: PathBuf::from("CHANGELOG.md"),
             format: "markdown".to_string(),
             types,
+            routes: Vec::new(),
+            aliases: HashMap::new(),
+            skip_markers: vec!["[skip changelog]".to_string()],
             header: None,
             include_hashes: true,
             include_authors: false,
             include_dates: true,
+            timezone: None,
+            repository_url: None,
+            preserve_existing: false,
         }
     }
 }
@@ -106,5 +139,32 @@ pub struct CommitTypeConfig {
     /// Section header in changelog
     pub section: String,
     /// Whether to hide this type from changelog
+    #[serde(default)]
     pub hidden: bool,
 }
+
+/// Rule routing commits to a changelog section by type and scope
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SectionRoute {
+    /// Commit types the rule applies to (any type when empty)
+    #[serde(default)]
+    pub types: Vec<String>,
+    /// Commit scopes the rule applies to (any scope, or none, when empty)
+    #[serde(default)]
+    pub scopes: Vec<String>,
+    /// Section header in changelog
+    pub section: String,
+    /// Whether to hide this section from changelog
+    #[serde(default)]
+    pub hidden: bool,
+}
+
+impl SectionRoute {
+    /// Whether a commit with `commit_type` and `scope` matches this rule
+    pub fn matches(&self, commit_type: &str, scope: Option<&str>) -> bool {
+        let type_matches = self.types.is_empty() || self.types.iter().any(|t| t == commit_type);
+        let scope_matches = self.scopes.is_empty()
+            || scope.is_some_and(|scope| self.scopes.iter().any(|s| s == scope));
+        type_matches && scope_matches
+    }
+}
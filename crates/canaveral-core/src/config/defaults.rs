@@ -107,6 +107,7 @@ post_publish = []
 # [stores.npm]
 # registry_url = "https://registry.npmjs.org"
 # # token: env var NPM_TOKEN or from ~/.npmrc
+# provenance = false  # attest builds in CI (needs `id-token: write` on GitHub Actions)
 #
 # [stores.crates_io]
 # registry_url = "https://crates.io"
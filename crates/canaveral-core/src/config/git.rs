@@ -12,6 +12,11 @@ pub struct GitConfig {
     /// Branch to release from
     pub branch: String,
 
+    /// Glob patterns for branches releases may run from (e.g. `main`,
+    /// `release/*`); when empty, only `branch` is allowed
+    #[serde(default)]
+    pub release_branches: Vec<String>,
+
     /// Whether to require clean working directory
     pub require_clean: bool,
 
@@ -29,6 +34,30 @@ pub struct GitConfig {
 
     /// Whether to sign tags
     pub sign_tags: bool,
+
+    /// Fetch the full history from `remote` when running in a shallow clone
+    pub auto_unshallow: bool,
+
+    /// Write, commit and tag releases in a temporary worktree of HEAD and
+    /// push from there, leaving the working tree untouched
+    pub release_in_worktree: bool,
+
+    /// Whether to delete the local release tag and reset the release commit
+    /// when pushing them fails
+    pub rollback_on_push_failure: RollbackMode,
+}
+
+/// When to undo a local release whose push failed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RollbackMode {
+    /// Ask, rolling back when there is no one to ask
+    #[default]
+    Prompt,
+    /// Always roll back
+    Always,
+    /// Keep the local tag and commit
+    Never,
 }
 
 impl Default for GitConfig {
@@ -36,12 +65,16 @@ impl Default for GitConfig {
         Self {
             remote: "origin".to_string(),
             branch: "main".to_string(),
+            release_branches: Vec::new(),
             require_clean: true,
             push_tags: true,
             push_commits: true,
             commit_message: "chore(release): {version}".to_string(),
             sign_commits: false,
             sign_tags: false,
+            auto_unshallow: false,
+            release_in_worktree: false,
+            rollback_on_push_failure: RollbackMode::default(),
         }
     }
 }
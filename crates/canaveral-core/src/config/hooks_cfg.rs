@@ -2,10 +2,20 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::hooks::HookStage;
+
 /// Hooks configuration
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
 pub struct HooksConfig {
+    /// Commands to run before any release step
+    #[serde(default)]
+    pub pre_release: Vec<String>,
+
+    /// Commands to run once the release has finished
+    #[serde(default)]
+    pub post_release: Vec<String>,
+
     /// Commands to run before version bump
     #[serde(default)]
     pub pre_version: Vec<String>,
@@ -39,6 +49,28 @@ pub struct HooksConfig {
     pub post_git: Vec<String>,
 }
 
+impl HooksConfig {
+    /// Commands configured for `stage`
+    ///
+    /// `pre_git` runs before the release commit and `post_git` after the
+    /// tag; stages without a setting have no commands.
+    pub fn commands(&self, stage: HookStage) -> &[String] {
+        match stage {
+            HookStage::PreRelease => &self.pre_release,
+            HookStage::PostRelease => &self.post_release,
+            HookStage::PreVersion => &self.pre_version,
+            HookStage::PostVersion => &self.post_version,
+            HookStage::PreChangelog => &self.pre_changelog,
+            HookStage::PostChangelog => &self.post_changelog,
+            HookStage::PrePublish => &self.pre_publish,
+            HookStage::PostPublish => &self.post_publish,
+            HookStage::PreCommit => &self.pre_git,
+            HookStage::PostTag => &self.post_git,
+            HookStage::PostCommit | HookStage::PreTag => &[],
+        }
+    }
+}
+
 /// Git hooks configuration (commit-msg, pre-commit, pre-push validation)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
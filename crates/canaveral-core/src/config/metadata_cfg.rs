@@ -22,6 +22,10 @@ pub struct MetadataConfig {
     /// Validation settings
     #[serde(default)]
     pub validation: MetadataValidationConfig,
+
+    /// Screenshot file selection
+    #[serde(default)]
+    pub screenshots: MetadataScreenshotsConfig,
 }
 
 /// Metadata storage configuration
@@ -74,6 +78,26 @@ pub struct MetadataValidationConfig {
     /// Locales that must be present
     #[serde(default)]
     pub required_locales: Vec<String>,
+
+    /// Hash distance (0-64) at or below which screenshots are reported as
+    /// duplicates; defaults to 5
+    #[serde(default)]
+    pub similarity_threshold: Option<u32>,
+}
+
+/// Which files in screenshot directories are uploaded
+///
+/// Globs match file names, ignoring case. Hidden files are always skipped.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default)]
+pub struct MetadataScreenshotsConfig {
+    /// File name globs to include; defaults to PNG and JPEG images
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// File name globs to skip, e.g. `*_original.png`
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 fn default_storage_format() -> String {
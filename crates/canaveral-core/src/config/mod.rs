@@ -1,5 +1,6 @@
 //! Configuration system for Canaveral
 
+pub mod cadence;
 pub mod changelog;
 pub mod ci;
 pub mod defaults;
@@ -8,6 +9,7 @@ pub mod hooks_cfg;
 pub mod ios;
 mod loader;
 pub mod metadata_cfg;
+pub mod notifications;
 pub mod pr;
 pub mod publishing;
 pub mod release_notes;
@@ -15,6 +17,7 @@ mod root;
 pub mod signing;
 pub mod stores;
 pub mod tasks;
+pub mod tls;
 pub mod tools;
 pub mod validation;
 pub mod versioning;
@@ -22,6 +25,7 @@ pub mod versioning;
 #[cfg(test)]
 mod types;
 
+pub use cadence::*;
 pub use changelog::*;
 pub use ci::*;
 pub use defaults::*;
@@ -30,6 +34,7 @@ pub use hooks_cfg::*;
 pub use ios::*;
 pub use loader::*;
 pub use metadata_cfg::*;
+pub use notifications::*;
 pub use pr::*;
 pub use publishing::*;
 pub use release_notes::*;
@@ -37,6 +42,7 @@ pub use root::*;
 pub use signing::*;
 pub use stores::*;
 pub use tasks::*;
+pub use tls::*;
 pub use tools::*;
 pub use validation::*;
 pub use versioning::*;
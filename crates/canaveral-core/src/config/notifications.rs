@@ -0,0 +1,40 @@
+//! Release notification configuration
+
+use serde::{Deserialize, Serialize};
+
+/// Release notification configuration
+///
+/// Webhook URLs are secrets; keep them in `canaveral.local.toml` or
+/// reference an environment variable (`${SLACK_WEBHOOK_URL}`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationsConfig {
+    /// Slack incoming webhook URLs
+    #[serde(default)]
+    pub slack: Vec<String>,
+
+    /// Generic webhook URLs receiving the JSON release payload
+    #[serde(default)]
+    pub webhooks: Vec<String>,
+
+    /// Whether to notify after a successful release
+    pub on_success: bool,
+
+    /// Whether to notify after a failed release
+    pub on_failure: bool,
+
+    /// Request timeout in seconds
+    pub timeout_secs: u64,
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            slack: Vec::new(),
+            webhooks: Vec::new(),
+            on_success: true,
+            on_failure: true,
+            timeout_secs: 10,
+        }
+    }
+}
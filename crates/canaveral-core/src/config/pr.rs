@@ -18,6 +18,9 @@ pub struct PrConfig {
 
     /// Whether to require conventional commits
     pub require_conventional_commits: bool,
+
+    /// Branch the pending release PR is prepared on
+    pub release_branch: String,
 }
 
 impl Default for PrConfig {
@@ -32,6 +35,7 @@ impl Default for PrConfig {
             ],
             require_changelog: false,
             require_conventional_commits: true,
+            release_branch: "canaveral/release".to_string(),
         }
     }
 }
@@ -16,6 +16,10 @@ pub struct PublishConfig {
 
     /// Dry run mode
     pub dry_run: bool,
+
+    /// File name patterns (e.g. `.env`, `*.pem`) flagged as likely secrets
+    /// when they would be published; built-in defaults apply if unset
+    pub secret_file_patterns: Option<Vec<String>>,
 }
 
 impl Default for PublishConfig {
@@ -24,10 +28,20 @@ impl Default for PublishConfig {
             enabled: true,
             registries: HashMap::new(),
             dry_run: false,
+            secret_file_patterns: None,
         }
     }
 }
 
+/// Maven adapter configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MavenConfig {
+    /// Bump multi-module reactors with `mvn versions:set` instead of
+    /// editing the root pom.xml
+    pub use_versions_plugin: bool,
+}
+
 /// Registry configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RegistryConfig {
@@ -1,5 +1,7 @@
 //! Release notes generation configuration
 
+use std::path::PathBuf;
+
 use serde::{Deserialize, Serialize};
 
 /// Release notes generation configuration
@@ -21,6 +23,16 @@ pub struct ReleaseNotesConfig {
     /// Locales for release notes
     #[serde(default)]
     pub locales: Vec<String>,
+
+    /// File `canaveral release` writes the release notes to, relative to
+    /// the project root
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+
+    /// Collapse GitHub Release body sections with more entries than this
+    /// into `<details>` blocks (flat markdown when unset)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub github_collapse_over: Option<usize>,
 }
 
 impl Default for ReleaseNotesConfig {
@@ -31,6 +43,8 @@ impl Default for ReleaseNotesConfig {
             include_migration_guide: true,
             auto_update_store_metadata: false,
             locales: vec!["en-US".to_string()],
+            file: None,
+            github_collapse_over: None,
         }
     }
 }
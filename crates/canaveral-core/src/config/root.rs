@@ -2,18 +2,21 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::cadence::CadenceConfig;
 use super::changelog::ChangelogConfig;
 use super::ci::CIConfig;
 use super::git::GitConfig;
 use super::hooks_cfg::{GitHooksConfig, HooksConfig};
 use super::ios::IosConfig;
 use super::metadata_cfg::MetadataConfig;
+use super::notifications::NotificationsConfig;
 use super::pr::PrConfig;
-use super::publishing::PublishConfig;
+use super::publishing::{MavenConfig, PublishConfig};
 use super::release_notes::ReleaseNotesConfig;
 use super::signing::SigningConfig;
 use super::stores::StoresConfig;
 use super::tasks::TasksConfig;
+use super::tls::TlsConfig;
 use super::tools::ToolsConfig;
 use super::versioning::VersioningConfig;
 
@@ -26,7 +29,7 @@ pub struct PackageConfig {
     /// Path to package (relative to repo root)
     pub path: std::path::PathBuf,
 
-    /// Package type (npm, cargo, python, etc.)
+    /// Package type (npm, cargo, python, generic, etc.)
     #[serde(rename = "type")]
     pub package_type: String,
 
@@ -43,6 +46,72 @@ pub struct PackageConfig {
     /// Files to update with version
     #[serde(default)]
     pub version_files: Vec<std::path::PathBuf>,
+
+    /// Secondary files holding the same version, bumped with the manifest
+    #[serde(default)]
+    pub version_mirrors: Vec<VersionMirrorConfig>,
+
+    /// File holding the version of a `generic` package
+    #[serde(default)]
+    pub version_source: Option<VersionSourceConfig>,
+
+    /// Prebuilt binaries a `cargo` package releases for `cargo binstall`
+    #[serde(default)]
+    pub release_assets: Option<ReleaseAssetsConfig>,
+}
+
+/// Prebuilt binaries checked against a crate's `cargo binstall` metadata
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAssetsConfig {
+    /// Target triples binaries are released for
+    #[serde(default)]
+    pub targets: Vec<String>,
+
+    /// Release asset file names
+    #[serde(default)]
+    pub assets: Vec<String>,
+}
+
+/// The file a `generic` package's version is read from and written to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionSourceConfig {
+    /// File path, relative to the package directory
+    pub file: std::path::PathBuf,
+
+    /// Regex whose single capture group is the version
+    pub pattern: String,
+
+    /// Replacement for the whole `pattern` match, with `{version}` placeholders
+    pub template: Option<String>,
+}
+
+/// A secondary file that repeats the package version
+///
+/// Exactly one of `pattern` and `json_path` locates the version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VersionMirrorConfig {
+    /// File path, relative to the package directory
+    pub file: std::path::PathBuf,
+
+    /// Regex whose single capture group is the version
+    pub pattern: Option<String>,
+
+    /// Dotted path to a string in a JSON file, e.g. `expo.version`
+    pub json_path: Option<String>,
+
+    /// Replacement for the whole `pattern` match, with `{version}` placeholders
+    pub template: Option<String>,
+}
+
+impl Config {
+    /// Configuration for the package named `name`, or for the package at
+    /// the repository root when no name is given
+    pub fn package_config(&self, name: Option<&str>) -> Option<&PackageConfig> {
+        self.packages.iter().find(|package| match name {
+            Some(name) => package.name == name,
+            None => matches!(package.path.to_str(), Some("" | ".")),
+        })
+    }
 }
 
 fn default_true() -> bool {
@@ -81,6 +150,10 @@ pub struct Config {
     /// Publishing configuration
     pub publish: PublishConfig,
 
+    /// Maven adapter configuration
+    #[serde(default)]
+    pub maven: MavenConfig,
+
     /// iOS platform configuration
     #[serde(default)]
     pub ios: IosConfig,
@@ -113,6 +186,10 @@ pub struct Config {
     #[serde(default)]
     pub release_notes: ReleaseNotesConfig,
 
+    /// Notifications sent after a release
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
     /// Git hooks configuration (commit-msg, pre-commit, pre-push)
     #[serde(default)]
     pub git_hooks: GitHooksConfig,
@@ -120,4 +197,12 @@ pub struct Config {
     /// Tool version pinning (mise/asdf-style)
     #[serde(default)]
     pub tools: ToolsConfig,
+
+    /// Private CA and client certificates for registries
+    #[serde(default)]
+    pub tls: TlsConfig,
+
+    /// Minimum commits and time between releases
+    #[serde(default)]
+    pub cadence: CadenceConfig,
 }
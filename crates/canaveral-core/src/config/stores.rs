@@ -27,6 +27,16 @@ pub struct StoresConfig {
     /// Crates.io registry configuration
     #[serde(default)]
     pub crates_io: Option<CratesIoRegistryConfig>,
+
+    /// Seconds before expiry at which cached store API tokens are refreshed
+    /// (default: 300)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_refresh_buffer_secs: Option<u64>,
+
+    /// Seconds of clock skew to tolerate when signing store API tokens
+    /// (default: 60)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clock_skew_secs: Option<u64>,
 }
 
 /// NPM registry configuration
@@ -36,6 +46,9 @@ pub struct NpmRegistryConfig {
     pub registry_url: String,
     /// NPM authentication token
     pub token: Option<String>,
+    /// Publish with provenance attestations (`npm publish --provenance`)
+    #[serde(default)]
+    pub provenance: bool,
 }
 
 impl Default for NpmRegistryConfig {
@@ -43,6 +56,7 @@ impl Default for NpmRegistryConfig {
         Self {
             registry_url: "https://registry.npmjs.org".to_string(),
             token: None,
+            provenance: false,
         }
     }
 }
@@ -72,6 +72,9 @@ pub struct CacheConfig {
 
     /// Cache directory
     pub dir: PathBuf,
+
+    /// Hash algorithm for cache keys
+    pub hash: HashAlgorithm,
 }
 
 impl Default for CacheConfig {
@@ -79,6 +82,28 @@ impl Default for CacheConfig {
         Self {
             enabled: true,
             dir: PathBuf::from(".canaveral/cache"),
+            hash: HashAlgorithm::default(),
+        }
+    }
+}
+
+/// Hash algorithm used to content-address task results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    /// SHA-256
+    #[default]
+    Sha256,
+    /// BLAKE3, several times faster on large inputs
+    Blake3,
+}
+
+impl HashAlgorithm {
+    /// Stable identifier, as used in configuration
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sha256 => "sha256",
+            Self::Blake3 => "blake3",
         }
     }
 }
@@ -0,0 +1,163 @@
+//! TLS configuration for private registries
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ConfigError, Result};
+
+/// Custom trust and client certificates for registries behind a private CA
+///
+/// The built-in HTTP registry and store clients trust `ca_bundle` in
+/// addition to the public roots and present `client_cert`/`client_key` for
+/// mutual TLS. CLI-based publishers (npm, cargo, twine) get the CA bundle
+/// through their environment variables; they don't support client
+/// certificates that way.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TlsConfig {
+    /// PEM file with one or more CA certificates to trust
+    pub ca_bundle: Option<PathBuf>,
+
+    /// PEM client certificate for mutual TLS
+    pub client_cert: Option<PathBuf>,
+
+    /// PEM private key of `client_cert`
+    pub client_key: Option<PathBuf>,
+}
+
+impl TlsConfig {
+    /// Whether no custom TLS settings are configured
+    pub fn is_empty(&self) -> bool {
+        self.ca_bundle.is_none() && self.client_cert.is_none() && self.client_key.is_none()
+    }
+
+    /// Check that the configured files exist and the client certificate
+    /// and key are set together
+    pub fn validate(&self) -> Result<()> {
+        let files = [
+            ("tls.ca_bundle", &self.ca_bundle),
+            ("tls.client_cert", &self.client_cert),
+            ("tls.client_key", &self.client_key),
+        ];
+        for (field, path) in files {
+            if let Some(path) = path {
+                require_file(field, path)?;
+            }
+        }
+
+        if self.client_cert.is_some() != self.client_key.is_some() {
+            return Err(ConfigError::InvalidValue {
+                field: "tls".to_string(),
+                message: "client_cert and client_key must be set together".to_string(),
+            }
+            .into());
+        }
+        Ok(())
+    }
+
+    /// Environment variables pointing CLI publishers at the CA bundle
+    ///
+    /// `NODE_EXTRA_CA_CERTS` adds to Node's roots, but `CARGO_HTTP_CAINFO`
+    /// and `TWINE_CERT` replace the system roots, so those two get a bundle
+    /// of the system roots followed by `ca_bundle`, written to `dir`.
+    pub fn command_env(&self, dir: &Path) -> Result<Vec<(String, String)>> {
+        let Some(ca_bundle) = &self.ca_bundle else {
+            return Ok(Vec::new());
+        };
+        let combined = dir.join("canaveral-ca-bundle.pem");
+        let mut pem = system_roots().unwrap_or_default();
+        if !pem.is_empty() && !pem.ends_with('\n') {
+            pem.push('\n');
+        }
+        pem.push_str(&std::fs::read_to_string(ca_bundle)?);
+        std::fs::write(&combined, pem)?;
+
+        let extra = ca_bundle.display().to_string();
+        let combined = combined.display().to_string();
+        Ok(vec![
+            ("NODE_EXTRA_CA_CERTS".to_string(), extra),
+            ("CARGO_HTTP_CAINFO".to_string(), combined.clone()),
+            ("TWINE_CERT".to_string(), combined),
+        ])
+    }
+}
+
+/// Well-known locations of the system CA bundle
+const SYSTEM_ROOTS: &[&str] = &[
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/ssl/cert.pem",
+    "/usr/local/etc/openssl/cert.pem",
+];
+
+/// PEM contents of the system CA bundle, honouring `SSL_CERT_FILE`
+fn system_roots() -> Option<String> {
+    std::env::var_os("SSL_CERT_FILE")
+        .map(PathBuf::from)
+        .into_iter()
+        .chain(SYSTEM_ROOTS.iter().map(PathBuf::from))
+        .find_map(|path| std::fs::read_to_string(path).ok())
+}
+
+fn require_file(field: &str, path: &Path) -> Result<()> {
+    if !path.is_file() {
+        return Err(ConfigError::InvalidValue {
+            field: field.to_string(),
+            message: format!("file not found at {}", path.display()),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_and_env() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let ca = temp.path().join("ca.pem");
+        std::fs::write(&ca, "-----BEGIN CERTIFICATE-----\n").unwrap();
+
+        let config = TlsConfig {
+            ca_bundle: Some(ca.clone()),
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+        let env = config.command_env(temp.path()).unwrap();
+        assert!(env.contains(&("NODE_EXTRA_CA_CERTS".to_string(), ca.display().to_string())));
+        // Cargo and twine get the system roots with the custom CA appended
+        let combined = temp.path().join("canaveral-ca-bundle.pem");
+        assert!(env.contains(&(
+            "CARGO_HTTP_CAINFO".to_string(),
+            combined.display().to_string()
+        )));
+        let bundle = std::fs::read_to_string(&combined).unwrap();
+        assert!(bundle.ends_with("-----BEGIN CERTIFICATE-----\n"));
+        assert!(bundle.starts_with(&system_roots().unwrap_or_default()));
+        assert!(TlsConfig::default()
+            .command_env(temp.path())
+            .unwrap()
+            .is_empty());
+
+        let missing = TlsConfig {
+            ca_bundle: Some(temp.path().join("missing.pem")),
+            ..Default::default()
+        };
+        let err = missing.validate().unwrap_err().to_string();
+        assert!(
+            err.contains("tls.ca_bundle") && err.contains("missing.pem"),
+            "{}",
+            err
+        );
+
+        let half = TlsConfig {
+            client_cert: Some(ca),
+            ..Default::default()
+        };
+        assert!(half.validate().is_err());
+    }
+}
@@ -13,6 +13,7 @@ pub fn validate_config(config: &Config) -> Result<()> {
     validate_git(config)?;
     validate_changelog(config)?;
     validate_packages(config)?;
+    config.cadence.min_interval()?;
     debug!("configuration validation passed");
     Ok(())
 }
@@ -35,6 +36,31 @@ fn validate_versioning(config: &Config) -> Result<()> {
         .into());
     }
 
+    let mut names = std::collections::HashSet::new();
+    for group in &config.versioning.fixed_groups {
+        let invalid = |message: String| ConfigError::InvalidValue {
+            field: "versioning.fixed_groups".to_string(),
+            message,
+        };
+        if group.name.is_empty() {
+            return Err(invalid("group name must not be empty".to_string()).into());
+        }
+        if !names.insert(group.name.as_str()) {
+            return Err(invalid(format!("duplicate group '{}'", group.name)).into());
+        }
+        if group.packages.is_empty() {
+            return Err(invalid(format!("group '{}' has no packages", group.name)).into());
+        }
+        for pattern in &group.packages {
+            glob::Pattern::new(pattern).map_err(|e| {
+                invalid(format!(
+                    "group '{}' has invalid pattern '{}': {}",
+                    group.name, pattern, e
+                ))
+            })?;
+        }
+    }
+
     Ok(())
 }
 
@@ -55,10 +81,14 @@ fn validate_git(config: &Config) -> Result<()> {
         .into());
     }
 
-    if !config.git.commit_message.contains("{version}") {
+    let message = &config.git.commit_message;
+    if !["{version}", "{packages}", "{#packages}"]
+        .iter()
+        .any(|placeholder| message.contains(placeholder))
+    {
         return Err(ConfigError::InvalidValue {
             field: "git.commit_message".to_string(),
-            message: "must contain {version} placeholder".to_string(),
+            message: "must contain a {version} or {packages} placeholder".to_string(),
         }
         .into());
     }
@@ -76,6 +106,16 @@ fn validate_changelog(config: &Config) -> Result<()> {
             }
             .into());
         }
+
+        for (i, route) in config.changelog.routes.iter().enumerate() {
+            if route.section.trim().is_empty() {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("changelog.routes[{}].section", i),
+                    message: "section cannot be empty".to_string(),
+                }
+                .into());
+            }
+        }
     }
 
     Ok(())
@@ -94,7 +134,16 @@ fn validate_packages(config: &Config) -> Result<()> {
             .into());
         }
 
-        let valid_types = ["npm", "cargo", "python", "go", "maven", "docker"];
+        let valid_types = [
+            "npm",
+            "cargo",
+            "python",
+            "go",
+            "maven",
+            "docker",
+            "cocoapods",
+            "generic",
+        ];
         if !valid_types.contains(&package.package_type.as_str()) {
             return Err(ConfigError::InvalidValue {
                 field: format!("packages[{}].type", i),
@@ -102,6 +151,24 @@ fn validate_packages(config: &Config) -> Result<()> {
             }
             .into());
         }
+
+        if (package.package_type == "generic") != package.version_source.is_some() {
+            return Err(ConfigError::InvalidValue {
+                field: format!("packages[{}].version_source", i),
+                message: "required for generic packages, and only allowed for them".to_string(),
+            }
+            .into());
+        }
+
+        for (j, mirror) in package.version_mirrors.iter().enumerate() {
+            if mirror.pattern.is_some() == mirror.json_path.is_some() {
+                return Err(ConfigError::InvalidValue {
+                    field: format!("packages[{}].version_mirrors[{}]", i, j),
+                    message: "set exactly one of pattern or json_path".to_string(),
+                }
+                .into());
+            }
+        }
     }
 
     Ok(())
@@ -110,6 +177,7 @@ fn validate_packages(config: &Config) -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{FixedGroupConfig, PackageConfig, VersionSourceConfig};
 
     #[test]
     fn test_validate_default_config() {
@@ -130,4 +198,51 @@ mod tests {
         config.versioning.tag_format = "no-placeholder".to_string();
         assert!(validate_config(&config).is_err());
     }
+
+    #[test]
+    fn test_validate_generic_version_source() {
+        let package = PackageConfig {
+            name: "firmware".to_string(),
+            path: ".".into(),
+            package_type: "generic".to_string(),
+            publish: false,
+            registry: None,
+            tag_format: None,
+            version_files: Vec::new(),
+            version_mirrors: Vec::new(),
+            version_source: None,
+            release_assets: None,
+        };
+        let mut config = Config {
+            packages: vec![package],
+            ..Config::default()
+        };
+        assert!(validate_config(&config).is_err());
+
+        config.packages[0].version_source = Some(VersionSourceConfig {
+            file: "version.h".into(),
+            pattern: r#"VERSION "([^"]+)""#.to_string(),
+            template: None,
+        });
+        assert!(validate_config(&config).is_ok());
+
+        config.packages[0].package_type = "npm".to_string();
+        assert!(validate_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_validate_fixed_groups() {
+        let mut config = Config::default();
+        config.versioning.fixed_groups = vec![
+            FixedGroupConfig::new("core", vec!["@acme/core-*".to_string()]),
+            FixedGroupConfig::new("core", vec!["@acme/ui".to_string()]),
+        ];
+        assert!(validate_config(&config).is_err());
+
+        config.versioning.fixed_groups[1].name = "ui".to_string();
+        assert!(validate_config(&config).is_ok());
+
+        config.versioning.fixed_groups[1].packages = vec!["@acme/[ui".to_string()];
+        assert!(validate_config(&config).is_err());
+    }
 }
@@ -1,5 +1,6 @@
 //! Versioning configuration
 
+use glob::{MatchOptions, Pattern};
 use serde::{Deserialize, Serialize};
 
 /// Versioning configuration
@@ -20,6 +21,17 @@ pub struct VersioningConfig {
 
     /// Build metadata
     pub build_metadata: Option<String>,
+
+    /// Whether pre-release tags count when finding the latest version
+    ///
+    /// When off, `v1.3.0-rc.1` is passed over in favour of `v1.2.0`.
+    pub include_prereleases: bool,
+
+    /// Groups of monorepo packages that always share one version
+    ///
+    /// Packages outside every group are versioned on their own.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fixed_groups: Vec<FixedGroupConfig>,
 }
 
 impl Default for VersioningConfig {
@@ -30,6 +42,56 @@ impl Default for VersioningConfig {
             independent: false,
             prerelease_identifier: None,
             build_metadata: None,
+            include_prereleases: true,
+            fixed_groups: Vec::new(),
         }
     }
 }
+
+impl VersioningConfig {
+    /// The first fixed group containing `package`, if any
+    pub fn fixed_group(&self, package: &str) -> Option<&FixedGroupConfig> {
+        self.fixed_groups
+            .iter()
+            .find(|group| group.matches(package))
+    }
+}
+
+/// Packages released together under a single version and tag
+///
+/// Every member gets the version of the highest bump any member needs, and
+/// the group is tagged once as `{name}@{version}`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FixedGroupConfig {
+    /// Group name, used in the group's tag
+    pub name: String,
+
+    /// Package name globs selecting the members, e.g. `@acme/core-*`
+    pub packages: Vec<String>,
+}
+
+impl FixedGroupConfig {
+    /// Create a group from member name globs
+    pub fn new(name: impl Into<String>, packages: Vec<String>) -> Self {
+        Self {
+            name: name.into(),
+            packages,
+        }
+    }
+
+    /// Whether the package `name` is a member of the group
+    pub fn matches(&self, name: &str) -> bool {
+        let options = MatchOptions {
+            require_literal_separator: true,
+            ..MatchOptions::new()
+        };
+        self.packages.iter().any(|pattern| {
+            Pattern::new(pattern).is_ok_and(|pattern| pattern.matches_with(name, options))
+        })
+    }
+
+    /// Tag for a release of the group at `version`
+    pub fn tag(&self, version: &str) -> String {
+        format!("{}@{}", self.name, version)
+    }
+}
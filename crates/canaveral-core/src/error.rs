@@ -1,11 +1,105 @@
 //! Error types for Canaveral
 
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Result type alias using CanaveralError
 pub type Result<T> = std::result::Result<T, CanaveralError>;
 
+/// Stable error category used to pick a CLI exit code
+///
+/// The string form (see [`ErrorCode::as_str`]) is part of the public
+/// interface: CI scripts may branch on it, so variants must not be renamed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// Unclassified failure (`general`, exit 1)
+    General,
+    /// Invalid or missing configuration (`config`, exit 2)
+    Config,
+    /// Git operation failed (`git`, exit 3)
+    Git,
+    /// Version parsing or bumping failed (`version`, exit 4)
+    Version,
+    /// Validation or pre-condition failed (`validation`, exit 5)
+    Validation,
+    /// Missing or rejected credentials (`auth`, exit 6)
+    Auth,
+    /// Registry, store, or network failure (`network`, exit 7)
+    Network,
+    /// Required external tool is not installed (`tool-missing`, exit 8)
+    ToolMissing,
+    /// Build or packaging failed (`build`, exit 10)
+    Build,
+    /// Tests failed (`test`, exit 11)
+    Test,
+    /// Operation cancelled by the user (`cancelled`, exit 130)
+    Cancelled,
+}
+
+impl ErrorCode {
+    /// Stable string identifier for this category
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::General => "general",
+            Self::Config => "config",
+            Self::Git => "git",
+            Self::Version => "version",
+            Self::Validation => "validation",
+            Self::Auth => "auth",
+            Self::Network => "network",
+            Self::ToolMissing => "tool-missing",
+            Self::Build => "build",
+            Self::Test => "test",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// Process exit code for this category
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::General => 1,
+            Self::Config => 2,
+            Self::Git => 3,
+            Self::Version => 4,
+            Self::Validation => 5,
+            Self::Auth => 6,
+            Self::Network => 7,
+            Self::ToolMissing => 8,
+            Self::Build => 10,
+            Self::Test => 11,
+            Self::Cancelled => 130,
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl std::str::FromStr for ErrorCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "general" => Ok(Self::General),
+            "config" => Ok(Self::Config),
+            "git" => Ok(Self::Git),
+            "version" => Ok(Self::Version),
+            "validation" => Ok(Self::Validation),
+            "auth" => Ok(Self::Auth),
+            "network" => Ok(Self::Network),
+            "tool-missing" => Ok(Self::ToolMissing),
+            "build" => Ok(Self::Build),
+            "test" => Ok(Self::Test),
+            "cancelled" => Ok(Self::Cancelled),
+            _ => Err(format!("Unknown error code: {}", s)),
+        }
+    }
+}
+
 /// Main error type for Canaveral operations
 #[derive(Debug, Error)]
 pub enum CanaveralError {
@@ -133,10 +227,37 @@ pub enum GitError {
     #[error("Failed to push to remote: {0}")]
     PushFailed(String),
 
+    /// Branch push rejected because the remote has commits we don't
+    #[error("Push to {remote}/{branch} rejected: remote has new commits (non-fast-forward)")]
+    NonFastForward { remote: String, branch: String },
+
+    /// Tag exists on the remote but points at a different commit
+    #[error("Tag {tag} already exists on remote at {remote_sha} (local {local_sha})")]
+    TagConflict {
+        tag: String,
+        local_sha: String,
+        remote_sha: String,
+    },
+
     /// Remote not found
     #[error("Remote not found: {0}")]
     RemoteNotFound(String),
 
+    /// Failed to fetch from a remote
+    #[error("Failed to fetch from remote: {0}")]
+    FetchFailed(String),
+
+    /// Failed to create or remove a worktree
+    #[error("Worktree operation failed: {0}")]
+    WorktreeFailed(String),
+
+    /// Operation needs history that a shallow clone doesn't have
+    #[error(
+        "{0} needs the full git history, but this is a shallow clone. \
+         Run `git fetch --unshallow --tags` or set `fetch-depth: 0` in your CI checkout"
+    )]
+    ShallowClone(String),
+
     /// Git2 library error
     #[error("Git error: {0}")]
     Git2(#[from] git2::Error),
@@ -161,6 +282,10 @@ pub enum VersionError {
     #[error("Invalid bump type: {0}")]
     InvalidBumpType(String),
 
+    /// Requested version does not come after the current version
+    #[error("Version {1} is not greater than the current version {0}")]
+    NotIncreasing(String, String),
+
     /// Semver error
     #[error("Semver error: {0}")]
     Semver(#[from] semver::Error),
@@ -205,6 +330,18 @@ pub enum AdapterError {
     #[error("Failed to update manifest: {0}")]
     ManifestUpdateError(String),
 
+    /// Another writer held the manifest lock for the whole timeout
+    #[error(
+        "Manifest is locked: {} is being written by another process (waited {}s)",
+        .path.display(),
+        .timeout.as_secs()
+    )]
+    ManifestLocked { path: PathBuf, timeout: Duration },
+
+    /// Version mirror files disagree with the manifest
+    #[error("Version drift, files disagree with the manifest version {expected}:\n{drifted}")]
+    VersionDrift { expected: String, drifted: String },
+
     /// Publish failed
     #[error("Failed to publish package: {0}")]
     PublishFailed(String),
@@ -221,6 +358,10 @@ pub enum AdapterError {
     #[error("Command failed: {command} - {reason}")]
     CommandFailed { command: String, reason: String },
 
+    /// Registry request failed
+    #[error("Registry request to {url} failed: {reason}")]
+    RegistryRequestFailed { url: String, reason: String },
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
@@ -237,6 +378,20 @@ pub enum WorkflowError {
     #[error("Pre-condition not met: {0}")]
     PreConditionFailed(String),
 
+    /// Current branch is not an allowed release branch
+    #[error(
+        "Branch '{branch}' is not a release branch (allowed: {allowed}). Use --allow-branch to override"
+    )]
+    BranchNotAllowed { branch: String, allowed: String },
+
+    /// Working tree has uncommitted changes
+    #[error("Working directory has uncommitted changes. Commit or stash them first:\n{0}")]
+    DirtyWorkingTree(String),
+
+    /// Release would come sooner than the configured cadence allows
+    #[error("Release refused: {0}. Use --force to override")]
+    CadenceNotMet(String),
+
     /// Step failed
     #[error("Workflow step '{step}' failed: {reason}")]
     StepFailed { step: String, reason: String },
@@ -268,6 +423,16 @@ pub enum HookError {
     /// Invalid hook configuration
     #[error("Invalid hook configuration: {0}")]
     InvalidConfig(String),
+
+    /// Unknown hook stage name
+    #[error(
+        "Unknown hook stage '{stage}'{}",
+        .suggestion.map(|s| format!(", did you mean '{}'?", s)).unwrap_or_default()
+    )]
+    UnknownStage {
+        stage: String,
+        suggestion: Option<&'static str>,
+    },
 }
 
 /// Task orchestration errors
@@ -327,4 +492,108 @@ impl CanaveralError {
     pub fn other<S: Into<String>>(msg: S) -> Self {
         Self::Other(msg.into())
     }
+
+    /// Error category for exit code mapping
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::Config(_) | Self::Toml(_) => ErrorCode::Config,
+            Self::Git(_) => ErrorCode::Git,
+            Self::Version(_) => ErrorCode::Version,
+            Self::Adapter(e) => e.code(),
+            Self::Workflow(e) => e.code(),
+            Self::Hook(HookError::InvalidConfig(_) | HookError::UnknownStage { .. }) => {
+                ErrorCode::Config
+            }
+            Self::GitHook(GitHookError::CommitMsgValidation(_)) => ErrorCode::Validation,
+            Self::Task(TaskError::ExecutionFailed { .. }) => ErrorCode::Build,
+            Self::Changelog(_)
+            | Self::Hook(_)
+            | Self::GitHook(_)
+            | Self::Task(_)
+            | Self::Io(_)
+            | Self::Json(_)
+            | Self::Other(_) => ErrorCode::General,
+        }
+    }
+}
+
+impl AdapterError {
+    /// Error category for exit code mapping
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::ManifestNotFound(_) | Self::ManifestParseError(_) => ErrorCode::Config,
+            Self::AuthenticationFailed { .. } => ErrorCode::Auth,
+            Self::PublishFailed(_) | Self::RegistryRequestFailed { .. } => ErrorCode::Network,
+            Self::UnsupportedType(_) | Self::VersionDrift { .. } => ErrorCode::Validation,
+            Self::ManifestUpdateError(_)
+            | Self::ManifestLocked { .. }
+            | Self::CommandFailed { .. }
+            | Self::Io(_) => ErrorCode::General,
+        }
+    }
+}
+
+impl WorkflowError {
+    /// Error category for exit code mapping
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            Self::ValidationFailed(_)
+            | Self::PreConditionFailed(_)
+            | Self::BranchNotAllowed { .. }
+            | Self::DirtyWorkingTree(_)
+            | Self::CadenceNotMet(_) => ErrorCode::Validation,
+            Self::Cancelled => ErrorCode::Cancelled,
+            Self::StepFailed { .. } | Self::DryRun => ErrorCode::General,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code_round_trip() {
+        for code in [
+            ErrorCode::General,
+            ErrorCode::Config,
+            ErrorCode::Git,
+            ErrorCode::Version,
+            ErrorCode::Validation,
+            ErrorCode::Auth,
+            ErrorCode::Network,
+            ErrorCode::ToolMissing,
+            ErrorCode::Build,
+            ErrorCode::Test,
+            ErrorCode::Cancelled,
+        ] {
+            assert_eq!(code.as_str().parse::<ErrorCode>().unwrap(), code);
+        }
+        assert!("bogus".parse::<ErrorCode>().is_err());
+    }
+
+    #[test]
+    fn test_canaveral_error_codes() {
+        let err: CanaveralError = ConfigError::MissingField("name".to_string()).into();
+        assert_eq!(err.code(), ErrorCode::Config);
+        assert_eq!(err.code().exit_code(), 2);
+
+        let err: CanaveralError = AdapterError::AuthenticationFailed {
+            registry: "npm".to_string(),
+            reason: "bad token".to_string(),
+        }
+        .into();
+        assert_eq!(err.code(), ErrorCode::Auth);
+
+        let err: CanaveralError = WorkflowError::ValidationFailed("dirty".to_string()).into();
+        assert_eq!(err.code(), ErrorCode::Validation);
+
+        let err: CanaveralError = WorkflowError::Cancelled.into();
+        assert_eq!(err.code().exit_code(), 130);
+
+        let err: CanaveralError = GitError::NoCommits.into();
+        assert_eq!(err.code(), ErrorCode::Git);
+
+        assert_eq!(CanaveralError::other("boom").code(), ErrorCode::General);
+    }
 }
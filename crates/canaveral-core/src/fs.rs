@@ -0,0 +1,137 @@
+//! Atomic file writes
+//!
+//! Changelogs and release notes are rewritten on every release. Writing
+//! them in place leaves a truncated file behind if the process is killed
+//! half-way, so the new contents go to a temporary file next to the target
+//! first and are renamed over it once complete. A rename within one
+//! directory is atomic: readers see either the old file or the new one.
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tracing::debug;
+
+/// Distinguishes temporary files written by the same process
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Write `contents` to `path` atomically, creating parent directories
+///
+/// An existing file keeps its permissions and is left untouched if
+/// anything fails before the final rename.
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    write_atomic_with(path, contents.as_ref(), |_| Ok(()))
+}
+
+/// Prepend `entry` to the file at `path` atomically
+///
+/// The entry and the existing contents are separated by a newline. A
+/// missing file is created with just the entry.
+pub fn prepend_atomic(path: &Path, entry: &str) -> io::Result<()> {
+    let contents = match fs::read_to_string(path) {
+        Ok(existing) => format!("{}\n{}", entry, existing),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => entry.to_string(),
+        Err(e) => return Err(e),
+    };
+    write_atomic(path, contents)
+}
+
+/// [`write_atomic`], running `before_rename` on the finished temporary file
+fn write_atomic_with(
+    path: &Path,
+    contents: &[u8],
+    before_rename: impl FnOnce(&Path) -> io::Result<()>,
+) -> io::Result<()> {
+    let dir = match path.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+    fs::create_dir_all(dir)?;
+
+    let temp = temp_path(dir, path);
+    let result = (|| {
+        let mut file = File::create(&temp)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&temp, metadata.permissions())?;
+        }
+        before_rename(&temp)?;
+        fs::rename(&temp, path)
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp);
+    }
+    debug!(path = %path.display(), bytes = contents.len(), ok = result.is_ok(), "atomic write");
+    result
+}
+
+/// Hidden temporary file in `dir` for writing `path`
+fn temp_path(dir: &Path, path: &Path) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    dir.join(format!(
+        ".{}.{}-{}.tmp",
+        name,
+        std::process::id(),
+        TEMP_COUNTER.fetch_add(1, Ordering::Relaxed)
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_write_creates_parent_directories() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("dist/notes/RELEASE.md");
+
+        write_atomic(&path, "## 1.0.0\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "## 1.0.0\n");
+
+        prepend_atomic(&path, "## 1.1.0\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "## 1.1.0\n\n## 1.0.0\n");
+        // Only the target is left in the directory
+        assert_eq!(fs::read_dir(path.parent().unwrap()).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_failed_write_keeps_original() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("CHANGELOG.md");
+        fs::write(&path, "## 1.0.0\n").unwrap();
+
+        let err = write_atomic_with(&path, b"## 1.1.0\n", |temp| {
+            // The new contents are complete, but the process dies before
+            // the rename
+            assert_eq!(fs::read_to_string(temp).unwrap(), "## 1.1.0\n");
+            Err(io::Error::new(io::ErrorKind::Interrupted, "killed"))
+        })
+        .unwrap_err();
+
+        assert_eq!(err.kind(), io::ErrorKind::Interrupted);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "## 1.0.0\n");
+        assert_eq!(fs::read_dir(temp.path()).unwrap().count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_write_preserves_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("notes.md");
+        fs::write(&path, "old").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o640)).unwrap();
+
+        write_atomic(&path, "new").unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o640);
+    }
+}
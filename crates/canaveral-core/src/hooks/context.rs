@@ -0,0 +1,241 @@
+//! Variables passed to hooks
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Hook execution context
+#[derive(Debug, Clone, Default)]
+pub struct HookContext {
+    /// Current version
+    pub version: Option<String>,
+    /// Previous version
+    pub previous_version: Option<String>,
+    /// Package name
+    pub package_name: Option<String>,
+    /// Release type (major, minor, patch)
+    pub release_type: Option<String>,
+    /// Git tag
+    pub tag: Option<String>,
+    /// Whether this is a dry run
+    pub dry_run: bool,
+    /// Generated changelog file
+    pub changelog_path: Option<PathBuf>,
+    /// Generated release notes file
+    pub release_notes_path: Option<PathBuf>,
+    /// Built artifacts
+    pub artifacts: Vec<PathBuf>,
+    /// Additional custom variables
+    pub custom: HashMap<String, String>,
+}
+
+impl HookContext {
+    /// Create a new hook context
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the version
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    /// Set the previous version
+    pub fn with_previous_version(mut self, version: impl Into<String>) -> Self {
+        self.previous_version = Some(version.into());
+        self
+    }
+
+    /// Set the package name
+    pub fn with_package_name(mut self, name: impl Into<String>) -> Self {
+        self.package_name = Some(name.into());
+        self
+    }
+
+    /// Set the release type
+    pub fn with_release_type(mut self, release_type: impl Into<String>) -> Self {
+        self.release_type = Some(release_type.into());
+        self
+    }
+
+    /// Set the tag
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Set dry run mode
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the changelog file path
+    pub fn with_changelog_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.changelog_path = Some(path.into());
+        self
+    }
+
+    /// Set the release notes file path
+    pub fn with_release_notes_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.release_notes_path = Some(path.into());
+        self
+    }
+
+    /// Add a built artifact
+    pub fn with_artifact(mut self, path: impl Into<PathBuf>) -> Self {
+        self.artifacts.push(path.into());
+        self
+    }
+
+    /// Add several built artifacts
+    pub fn with_artifacts<I, P>(mut self, paths: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<PathBuf>,
+    {
+        self.artifacts.extend(paths.into_iter().map(Into::into));
+        self
+    }
+
+    /// Add a custom variable
+    pub fn with_custom(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom.insert(key.into(), value.into());
+        self
+    }
+
+    /// Convert context to environment variables
+    ///
+    /// Artifacts are joined with the platform path separator (`:` on Unix,
+    /// `;` on Windows), like `PATH`.
+    pub fn to_env(&self) -> HashMap<String, String> {
+        let mut env = HashMap::new();
+
+        if let Some(ref v) = self.version {
+            env.insert("CANAVERAL_VERSION".to_string(), v.clone());
+        }
+        if let Some(ref v) = self.previous_version {
+            env.insert("CANAVERAL_PREVIOUS_VERSION".to_string(), v.clone());
+        }
+        if let Some(ref v) = self.package_name {
+            env.insert("CANAVERAL_PACKAGE".to_string(), v.clone());
+        }
+        if let Some(ref v) = self.release_type {
+            env.insert("CANAVERAL_RELEASE_TYPE".to_string(), v.clone());
+        }
+        if let Some(ref v) = self.tag {
+            env.insert("CANAVERAL_TAG".to_string(), v.clone());
+        }
+        env.insert("CANAVERAL_DRY_RUN".to_string(), self.dry_run.to_string());
+        if let Some(ref p) = self.changelog_path {
+            env.insert(
+                "CANAVERAL_CHANGELOG_PATH".to_string(),
+                p.to_string_lossy().into_owned(),
+            );
+        }
+        if let Some(ref p) = self.release_notes_path {
+            env.insert(
+                "CANAVERAL_RELEASE_NOTES_PATH".to_string(),
+                p.to_string_lossy().into_owned(),
+            );
+        }
+        if !self.artifacts.is_empty() {
+            env.insert(
+                "CANAVERAL_ARTIFACTS".to_string(),
+                join_artifacts(&self.artifacts),
+            );
+        }
+
+        for (k, v) in &self.custom {
+            env.insert(format!("CANAVERAL_{}", k.to_uppercase()), v.clone());
+        }
+
+        env
+    }
+}
+
+/// Join artifact paths with the platform path separator
+fn join_artifacts(paths: &[PathBuf]) -> String {
+    match std::env::join_paths(paths) {
+        Ok(joined) => joined.to_string_lossy().into_owned(),
+        // A path containing the separator can't be joined losslessly;
+        // fall back to a plain join rather than dropping the variable
+        Err(_) => {
+            let separator = if cfg!(windows) { ";" } else { ":" };
+            paths
+                .iter()
+                .map(|p| p.to_string_lossy())
+                .collect::<Vec<_>>()
+                .join(separator)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hook_context_to_env() {
+        let ctx = HookContext::new()
+            .with_version("1.0.0")
+            .with_previous_version("0.9.0")
+            .with_package_name("my-package")
+            .with_release_type("minor")
+            .with_tag("v1.0.0")
+            .with_dry_run(true)
+            .with_custom("build_id", "123");
+
+        let env = ctx.to_env();
+
+        assert_eq!(env.get("CANAVERAL_VERSION"), Some(&"1.0.0".to_string()));
+        assert_eq!(
+            env.get("CANAVERAL_PREVIOUS_VERSION"),
+            Some(&"0.9.0".to_string())
+        );
+        assert_eq!(
+            env.get("CANAVERAL_PACKAGE"),
+            Some(&"my-package".to_string())
+        );
+        assert_eq!(
+            env.get("CANAVERAL_RELEASE_TYPE"),
+            Some(&"minor".to_string())
+        );
+        assert_eq!(env.get("CANAVERAL_TAG"), Some(&"v1.0.0".to_string()));
+        assert_eq!(env.get("CANAVERAL_DRY_RUN"), Some(&"true".to_string()));
+        assert_eq!(env.get("CANAVERAL_BUILD_ID"), Some(&"123".to_string()));
+    }
+
+    #[test]
+    fn test_hook_context_paths_to_env() {
+        let env = HookContext::new().to_env();
+        assert!(!env.contains_key("CANAVERAL_CHANGELOG_PATH"));
+        assert!(!env.contains_key("CANAVERAL_RELEASE_NOTES_PATH"));
+        assert!(!env.contains_key("CANAVERAL_ARTIFACTS"));
+
+        let ctx = HookContext::new()
+            .with_changelog_path("CHANGELOG.md")
+            .with_release_notes_path("target/release-notes.md")
+            .with_artifact("dist/app-1.0.0.tar.gz")
+            .with_artifacts(["dist/app-1.0.0.whl", "dist/app-1.0.0.zip"]);
+        let env = ctx.to_env();
+
+        assert_eq!(
+            env.get("CANAVERAL_CHANGELOG_PATH"),
+            Some(&"CHANGELOG.md".to_string())
+        );
+        assert_eq!(
+            env.get("CANAVERAL_RELEASE_NOTES_PATH"),
+            Some(&"target/release-notes.md".to_string())
+        );
+
+        let artifacts = env.get("CANAVERAL_ARTIFACTS").unwrap();
+        let split: Vec<PathBuf> = std::env::split_paths(artifacts).collect();
+        assert_eq!(split, ctx.artifacts);
+        #[cfg(unix)]
+        assert_eq!(
+            artifacts,
+            "dist/app-1.0.0.tar.gz:dist/app-1.0.0.whl:dist/app-1.0.0.zip"
+        );
+    }
+}
@@ -17,11 +17,16 @@
 use std::collections::HashMap;
 use std::path::Path;
 use std::process::{Command, Stdio};
+use std::str::FromStr;
 
 use tracing::{debug, info, warn};
 
 use crate::error::{HookError, Result};
 
+mod context;
+
+pub use context::HookContext;
+
 /// Hook lifecycle stages
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum HookStage {
@@ -89,7 +94,52 @@ impl HookStage {
         }
     }
 
-    /// Get all stages in order
+    /// Position of the stage in the release lifecycle, starting at 0
+    ///
+    /// Stages run in ascending order: `PreRelease` first, `PostRelease` last.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::PreRelease => 0,
+            Self::PreVersion => 1,
+            Self::PostVersion => 2,
+            Self::PreChangelog => 3,
+            Self::PostChangelog => 4,
+            Self::PreCommit => 5,
+            Self::PostCommit => 6,
+            Self::PreTag => 7,
+            Self::PostTag => 8,
+            Self::PrePublish => 9,
+            Self::PostPublish => 10,
+            Self::PostRelease => 11,
+        }
+    }
+
+    /// The stage that runs after this one, if any
+    pub fn next(&self) -> Option<Self> {
+        Self::all().get(self.index() + 1).copied()
+    }
+
+    /// The stage that runs before this one, if any
+    pub fn prev(&self) -> Option<Self> {
+        self.index()
+            .checked_sub(1)
+            .and_then(|i| Self::all().get(i).copied())
+    }
+
+    /// The stage name closest to `s`, for "did you mean" hints
+    ///
+    /// Only names within two edits of `s` are suggested.
+    pub fn suggest(s: &str) -> Option<Self> {
+        let s = s.to_lowercase();
+        Self::all()
+            .iter()
+            .map(|stage| (edit_distance(&s, stage.as_str()), *stage))
+            .filter(|(distance, _)| *distance <= 2)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, stage)| stage)
+    }
+
+    /// Get all stages in lifecycle order
     pub fn all() -> &'static [HookStage] {
         &[
             Self::PreRelease,
@@ -108,6 +158,33 @@ impl HookStage {
     }
 }
 
+impl FromStr for HookStage {
+    type Err = HookError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Self::parse(s).ok_or_else(|| HookError::UnknownStage {
+            stage: s.to_string(),
+            suggestion: Self::suggest(s).map(|stage| stage.as_str()),
+        })
+    }
+}
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 /// A hook command to execute
 #[derive(Debug, Clone)]
 pub struct Hook {
@@ -188,102 +265,6 @@ pub struct HookResult {
     pub duration_ms: u64,
 }
 
-/// Hook execution context
-#[derive(Debug, Clone, Default)]
-pub struct HookContext {
-    /// Current version
-    pub version: Option<String>,
-    /// Previous version
-    pub previous_version: Option<String>,
-    /// Package name
-    pub package_name: Option<String>,
-    /// Release type (major, minor, patch)
-    pub release_type: Option<String>,
-    /// Git tag
-    pub tag: Option<String>,
-    /// Whether this is a dry run
-    pub dry_run: bool,
-    /// Additional custom variables
-    pub custom: HashMap<String, String>,
-}
-
-impl HookContext {
-    /// Create a new hook context
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    /// Set the version
-    pub fn with_version(mut self, version: impl Into<String>) -> Self {
-        self.version = Some(version.into());
-        self
-    }
-
-    /// Set the previous version
-    pub fn with_previous_version(mut self, version: impl Into<String>) -> Self {
-        self.previous_version = Some(version.into());
-        self
-    }
-
-    /// Set the package name
-    pub fn with_package_name(mut self, name: impl Into<String>) -> Self {
-        self.package_name = Some(name.into());
-        self
-    }
-
-    /// Set the release type
-    pub fn with_release_type(mut self, release_type: impl Into<String>) -> Self {
-        self.release_type = Some(release_type.into());
-        self
-    }
-
-    /// Set the tag
-    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
-        self.tag = Some(tag.into());
-        self
-    }
-
-    /// Set dry run mode
-    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
-        self.dry_run = dry_run;
-        self
-    }
-
-    /// Add a custom variable
-    pub fn with_custom(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
-        self.custom.insert(key.into(), value.into());
-        self
-    }
-
-    /// Convert context to environment variables
-    pub fn to_env(&self) -> HashMap<String, String> {
-        let mut env = HashMap::new();
-
-        if let Some(ref v) = self.version {
-            env.insert("CANAVERAL_VERSION".to_string(), v.clone());
-        }
-        if let Some(ref v) = self.previous_version {
-            env.insert("CANAVERAL_PREVIOUS_VERSION".to_string(), v.clone());
-        }
-        if let Some(ref v) = self.package_name {
-            env.insert("CANAVERAL_PACKAGE".to_string(), v.clone());
-        }
-        if let Some(ref v) = self.release_type {
-            env.insert("CANAVERAL_RELEASE_TYPE".to_string(), v.clone());
-        }
-        if let Some(ref v) = self.tag {
-            env.insert("CANAVERAL_TAG".to_string(), v.clone());
-        }
-        env.insert("CANAVERAL_DRY_RUN".to_string(), self.dry_run.to_string());
-
-        for (k, v) in &self.custom {
-            env.insert(format!("CANAVERAL_{}", k.to_uppercase()), v.clone());
-        }
-
-        env
-    }
-}
-
 /// Hook runner for executing hooks at lifecycle stages
 #[derive(Debug, Clone, Default)]
 pub struct HookRunner {
@@ -495,13 +476,14 @@ pub fn build_hook_runner(config: &HooksConfig, base_dir: Option<&Path>) -> HookR
 
     let mut total_hooks = 0;
     for (stage_name, hook_configs) in &config.hooks {
-        if let Some(stage) = HookStage::parse(stage_name) {
-            for hook_config in hook_configs {
-                runner.register(stage, hook_config.clone().into());
-                total_hooks += 1;
+        match stage_name.parse::<HookStage>() {
+            Ok(stage) => {
+                for hook_config in hook_configs {
+                    runner.register(stage, hook_config.clone().into());
+                    total_hooks += 1;
+                }
             }
-        } else {
-            warn!(stage = %stage_name, "unknown hook stage, skipping");
+            Err(e) => warn!(stage = %stage_name, "{}, skipping", e),
         }
     }
 
@@ -522,6 +504,44 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_hook_stage_ordering() {
+        let all = HookStage::all();
+        assert_eq!(all.first(), Some(&HookStage::PreRelease));
+        assert_eq!(all.last(), Some(&HookStage::PostRelease));
+        for (i, stage) in all.iter().enumerate() {
+            assert_eq!(stage.index(), i);
+        }
+        for pair in all.windows(2) {
+            assert!(pair[0].index() < pair[1].index());
+            assert_eq!(pair[0].next(), Some(pair[1]));
+            assert_eq!(pair[1].prev(), Some(pair[0]));
+        }
+        assert_eq!(HookStage::PreRelease.prev(), None);
+        assert_eq!(HookStage::PostRelease.next(), None);
+    }
+
+    #[test]
+    fn test_hook_stage_from_str_suggestion() {
+        assert_eq!("pre-tag".parse::<HookStage>().unwrap(), HookStage::PreTag);
+
+        let err = "pre-comit".parse::<HookStage>().unwrap_err();
+        assert!(matches!(
+            err,
+            HookError::UnknownStage {
+                suggestion: Some("pre-commit"),
+                ..
+            }
+        ));
+        assert_eq!(
+            err.to_string(),
+            "Unknown hook stage 'pre-comit', did you mean 'pre-commit'?"
+        );
+
+        let err = "deploy".parse::<HookStage>().unwrap_err();
+        assert_eq!(err.to_string(), "Unknown hook stage 'deploy'");
+    }
+
     #[test]
     fn test_hook_creation() {
         let hook = Hook::new("echo hello")
@@ -539,37 +559,6 @@ mod tests {
         assert_eq!(hook.description, Some("Test hook".to_string()));
     }
 
-    #[test]
-    fn test_hook_context_to_env() {
-        let ctx = HookContext::new()
-            .with_version("1.0.0")
-            .with_previous_version("0.9.0")
-            .with_package_name("my-package")
-            .with_release_type("minor")
-            .with_tag("v1.0.0")
-            .with_dry_run(true)
-            .with_custom("build_id", "123");
-
-        let env = ctx.to_env();
-
-        assert_eq!(env.get("CANAVERAL_VERSION"), Some(&"1.0.0".to_string()));
-        assert_eq!(
-            env.get("CANAVERAL_PREVIOUS_VERSION"),
-            Some(&"0.9.0".to_string())
-        );
-        assert_eq!(
-            env.get("CANAVERAL_PACKAGE"),
-            Some(&"my-package".to_string())
-        );
-        assert_eq!(
-            env.get("CANAVERAL_RELEASE_TYPE"),
-            Some(&"minor".to_string())
-        );
-        assert_eq!(env.get("CANAVERAL_TAG"), Some(&"v1.0.0".to_string()));
-        assert_eq!(env.get("CANAVERAL_DRY_RUN"), Some(&"true".to_string()));
-        assert_eq!(env.get("CANAVERAL_BUILD_ID"), Some(&"123".to_string()));
-    }
-
     #[test]
     fn test_hook_runner_register() {
         let mut runner = HookRunner::new();
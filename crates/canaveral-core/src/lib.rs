@@ -5,10 +5,14 @@
 
 pub mod config;
 pub mod error;
+pub mod fs;
 pub mod hooks;
 pub mod migration;
 pub mod monorepo;
+pub mod notify;
 pub mod plugins;
+pub mod retry;
+pub mod summary;
 pub mod templates;
 pub mod types;
 pub mod workflow;
@@ -16,13 +20,16 @@ pub mod workflow;
 pub use error::{CanaveralError, HookError, Result};
 pub use hooks::{Hook, HookContext, HookRunner, HookStage, HooksConfig};
 pub use migration::{
-    auto_migrate, detect_tool, MigrationResult, MigrationSource, Migrator, MigratorRegistry,
-    ReleasePleaseMigrator, SemanticReleaseMigrator,
+    auto_migrate, detect_tool, preview_migration, MigrationPreview, MigrationResult,
+    MigrationSource, Migrator, MigratorRegistry, ReleasePleaseMigrator, SemanticReleaseMigrator,
 };
 pub use monorepo::detector::{WorkspaceDetector, WorkspaceDetectorRegistry};
 pub use monorepo::publishing::PublishCallbackRegistry;
+pub use notify::{Notifier, ReleaseOutcome};
 pub use plugins::{ExternalPlugin, PluginConfig, PluginInfo, PluginRegistry, PluginType};
+pub use retry::{retry, retry_blocking, RetryPolicy};
+pub use summary::ReleaseSummary;
 pub use templates::{
     CITemplate, CITemplateRegistry, GitHubActionsTemplate, GitLabCITemplate, TemplateOptions,
 };
-pub use types::{ReleaseResult, ReleaseType};
+pub use types::{ReleaseCommit, ReleaseResult, ReleaseType};
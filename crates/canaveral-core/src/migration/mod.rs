@@ -6,12 +6,13 @@
 //! - standard-version
 //! - lerna
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use serde::Serialize;
 use tracing::{debug, info};
 
 use crate::config::Config;
-use crate::error::Result;
+use crate::error::{CanaveralError, Result};
 
 mod registry;
 mod release_please;
@@ -21,8 +22,12 @@ pub use registry::MigratorRegistry;
 pub use release_please::ReleasePleaseMigrator;
 pub use semantic_release::SemanticReleaseMigrator;
 
+/// File the generated configuration is written to
+pub const MIGRATED_CONFIG_FILE: &str = "canaveral.toml";
+
 /// Migration source type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
 pub enum MigrationSource {
     /// semantic-release configuration
     SemanticRelease,
@@ -73,6 +78,8 @@ pub struct MigrationResult {
     pub source: MigrationSource,
     /// Generated Canaveral configuration
     pub config: Config,
+    /// Source tool files that were read
+    pub sources: Vec<PathBuf>,
     /// Migration warnings
     pub warnings: Vec<String>,
     /// Unsupported features that couldn't be migrated
@@ -87,12 +94,18 @@ impl MigrationResult {
         Self {
             source,
             config,
+            sources: Vec::new(),
             warnings: Vec::new(),
             unsupported: Vec::new(),
             manual_steps: Vec::new(),
         }
     }
 
+    /// Record a source tool file that was read
+    pub fn add_source(&mut self, path: impl Into<PathBuf>) {
+        self.sources.push(path.into());
+    }
+
     /// Add a warning
     pub fn warn(&mut self, msg: impl Into<String>) {
         self.warnings.push(msg.into());
@@ -112,6 +125,59 @@ impl MigrationResult {
     pub fn has_issues(&self) -> bool {
         !self.warnings.is_empty() || !self.unsupported.is_empty()
     }
+
+    /// Serialize the generated configuration as `canaveral.toml` content
+    pub fn config_toml(&self) -> Result<String> {
+        toml::to_string_pretty(&self.config).map_err(|e| {
+            CanaveralError::other(format!("Failed to serialize migrated config: {}", e))
+        })
+    }
+
+    /// Structured view of the migration, for review before writing
+    pub fn preview(&self) -> Result<MigrationPreview> {
+        Ok(MigrationPreview {
+            source: self.source,
+            config: self.config_toml()?,
+            sources: self.sources.clone(),
+            warnings: self.warnings.clone(),
+            unsupported: self.unsupported.clone(),
+            manual_steps: self.manual_steps.clone(),
+        })
+    }
+
+    /// Write the generated configuration to `canaveral.toml` in `dir`
+    ///
+    /// Refuses to overwrite an existing configuration. Returns the path
+    /// written.
+    pub fn write(&self, dir: &Path) -> Result<PathBuf> {
+        let path = dir.join(MIGRATED_CONFIG_FILE);
+        if path.exists() {
+            return Err(CanaveralError::other(format!(
+                "{} already exists, not overwriting",
+                path.display()
+            )));
+        }
+        std::fs::write(&path, self.config_toml()?)?;
+        info!(path = %path.display(), source = self.source.as_str(), "wrote migrated config");
+        Ok(path)
+    }
+}
+
+/// What a migration would produce, without writing anything
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationPreview {
+    /// Source tool that would be migrated from
+    pub source: MigrationSource,
+    /// Generated `canaveral.toml` content
+    pub config: String,
+    /// Source tool files that were read
+    pub sources: Vec<PathBuf>,
+    /// Settings that need attention after migrating
+    pub warnings: Vec<String>,
+    /// Settings that don't translate to Canaveral
+    pub unsupported: Vec<String>,
+    /// Suggested manual steps
+    pub manual_steps: Vec<String>,
 }
 
 /// Trait for migrating from a specific tool
@@ -126,7 +192,15 @@ pub trait Migrator {
     fn detect_config(&self, path: &Path) -> Option<std::path::PathBuf>;
 
     /// Perform the migration
+    ///
+    /// Only reads the source tool's files; write the result with
+    /// [`MigrationResult::write`].
     fn migrate(&self, path: &Path) -> Result<MigrationResult>;
+
+    /// Preview the migration without writing anything
+    fn preview(&self, path: &Path) -> Result<MigrationPreview> {
+        self.migrate(path)?.preview()
+    }
 }
 
 /// Auto-detect and migrate from any supported tool
@@ -134,6 +208,11 @@ pub fn auto_migrate(path: &Path) -> Result<MigrationResult> {
     MigratorRegistry::new().migrate(path)
 }
 
+/// Auto-detect a supported tool and preview its migration
+pub fn preview_migration(path: &Path) -> Result<MigrationPreview> {
+    MigratorRegistry::new().preview(path)
+}
+
 /// Detect which release tool is configured
 pub fn detect_tool(path: &Path) -> Option<MigrationSource> {
     debug!(path = %path.display(), "detecting release tool");
@@ -221,4 +300,15 @@ mod tests {
         assert_eq!(result.unsupported.len(), 1);
         assert_eq!(result.manual_steps.len(), 1);
     }
+
+    #[test]
+    fn test_write_refuses_to_overwrite() {
+        let temp = TempDir::new().unwrap();
+        let result = MigrationResult::new(MigrationSource::SemanticRelease, Config::default());
+
+        let path = result.write(temp.path()).unwrap();
+        let written = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(written, result.config_toml().unwrap());
+        assert!(result.write(temp.path()).is_err());
+    }
 }
@@ -7,7 +7,8 @@ use tracing::{debug, info, warn};
 use crate::error::{CanaveralError, Result};
 
 use super::{
-    MigrationResult, MigrationSource, Migrator, ReleasePleaseMigrator, SemanticReleaseMigrator,
+    MigrationPreview, MigrationResult, MigrationSource, Migrator, ReleasePleaseMigrator,
+    SemanticReleaseMigrator,
 };
 
 /// Central registry of migration providers.
@@ -70,6 +71,17 @@ impl MigratorRegistry {
         }
     }
 
+    /// Auto-detect the tool for `path` and preview its migration without
+    /// writing anything.
+    pub fn preview(&self, path: &Path) -> Result<MigrationPreview> {
+        match self.detect(path) {
+            Some(migrator) => migrator.preview(path),
+            None => Err(CanaveralError::other(
+                "No supported release tool configuration found",
+            )),
+        }
+    }
+
     /// Return all registered migrators.
     pub fn all(&self) -> &[Box<dyn Migrator>] {
         &self.migrators
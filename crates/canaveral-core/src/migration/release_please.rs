@@ -26,10 +26,8 @@ struct ReleasePleaseConfig {
     changelog_sections: Option<Vec<ChangelogSection>>,
     include_component_in_tag: Option<bool>,
     include_v_in_tag: Option<bool>,
-    #[allow(dead_code)]
     tag_separator: Option<String>,
     separate_pull_requests: Option<bool>,
-    #[allow(dead_code)]
     bootstrap_sha: Option<String>,
     versioning_strategy: Option<String>,
 }
@@ -159,6 +157,12 @@ impl Migrator for ReleasePleaseMigrator {
         let manifest = self.parse_manifest(path)?;
         let mut config = Config::default();
         let mut result = MigrationResult::new(MigrationSource::ReleasePlease, config.clone());
+        for filename in MigrationSource::ReleasePlease.config_files() {
+            let source = path.join(filename);
+            if source.exists() {
+                result.add_source(source);
+            }
+        }
 
         // Determine if this is a monorepo
         let is_monorepo = !rp_config.packages.is_empty() || manifest.packages.len() > 1;
@@ -196,6 +200,20 @@ impl Migrator for ReleasePleaseMigrator {
             "{version}".to_string()
         };
 
+        if let Some(ref separator) = rp_config.tag_separator {
+            result.unsupported(format!(
+                "Tag separator '{}' not supported - component tags use '@'",
+                separator
+            ));
+        }
+
+        if let Some(ref sha) = rp_config.bootstrap_sha {
+            result.warn(format!(
+                "bootstrap-sha '{}' ignored - canaveral starts from the latest release tag",
+                sha
+            ));
+        }
+
         if rp_config.include_component_in_tag == Some(true) {
             result.warn("Component in tag is enabled - configure tag format in canaveral");
             result.manual_step("Set versioning.tag_format to include package name for monorepo");
@@ -352,6 +370,57 @@ mod tests {
         assert!(result.warnings.iter().any(|w| w.contains("Monorepo")));
     }
 
+    #[test]
+    fn test_preview_does_not_write() {
+        let temp = TempDir::new().unwrap();
+        let migrator = ReleasePleaseMigrator::new();
+
+        std::fs::write(
+            temp.path().join("release-please-config.json"),
+            r#"{
+                "release-type": "node",
+                "include-v-in-tag": false,
+                "changelog-path": "docs/CHANGES.md",
+                "tag-separator": "-",
+                "versioning-strategy": "prerelease"
+            }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join(".release-please-manifest.json"),
+            r#"{".": "1.4.0"}"#,
+        )
+        .unwrap();
+        let before: Vec<_> = std::fs::read_dir(temp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+
+        let preview = migrator.preview(temp.path()).unwrap();
+
+        assert_eq!(preview.source, MigrationSource::ReleasePlease);
+        let config: Config = toml::from_str(&preview.config).unwrap();
+        assert_eq!(config.versioning.tag_format, "{version}");
+        assert_eq!(config.changelog.file, PathBuf::from("docs/CHANGES.md"));
+        assert_eq!(
+            preview.sources,
+            [
+                temp.path().join("release-please-config.json"),
+                temp.path().join(".release-please-manifest.json")
+            ]
+        );
+        assert!(preview.unsupported.iter().any(|u| u.contains("'-'")));
+        assert!(preview.unsupported.iter().any(|u| u.contains("prerelease")));
+
+        // Nothing was written
+        let after: Vec<_> = std::fs::read_dir(temp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect();
+        assert_eq!(before, after);
+        assert!(!temp.path().join("canaveral.toml").exists());
+    }
+
     #[test]
     fn test_map_release_type() {
         let migrator = ReleasePleaseMigrator::new();
@@ -1,6 +1,6 @@
 //! semantic-release migration
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use serde::Deserialize;
 use tracing::info;
@@ -47,8 +47,8 @@ impl SemanticReleaseMigrator {
         Self
     }
 
-    /// Parse semantic-release configuration
-    fn parse_config(&self, path: &Path) -> Result<SemanticReleaseConfig> {
+    /// Parse semantic-release configuration, returning the file it came from
+    fn parse_config(&self, path: &Path) -> Result<(SemanticReleaseConfig, Option<PathBuf>)> {
         // Try various config file locations
         let config_files = [
             ".releaserc",
@@ -64,12 +64,12 @@ impl SemanticReleaseMigrator {
 
                 // Try JSON first
                 if let Ok(config) = serde_json::from_str(&content) {
-                    return Ok(config);
+                    return Ok((config, Some(config_path)));
                 }
 
                 // Try YAML
                 if let Ok(config) = serde_yaml::from_str(&content) {
-                    return Ok(config);
+                    return Ok((config, Some(config_path)));
                 }
             }
         }
@@ -81,12 +81,13 @@ impl SemanticReleaseMigrator {
             let package: serde_json::Value = serde_json::from_str(&content)?;
 
             if let Some(release) = package.get("release") {
-                return serde_json::from_value(release.clone())
-                    .map_err(|e| CanaveralError::other(e.to_string()));
+                let config = serde_json::from_value(release.clone())
+                    .map_err(|e| CanaveralError::other(e.to_string()))?;
+                return Ok((config, Some(package_json)));
             }
         }
 
-        Ok(SemanticReleaseConfig::default())
+        Ok((SemanticReleaseConfig::default(), None))
     }
 
     /// Convert branches to release branches
@@ -180,9 +181,12 @@ impl Migrator for SemanticReleaseMigrator {
 
     fn migrate(&self, path: &Path) -> Result<MigrationResult> {
         info!(path = %path.display(), "migrating from semantic-release");
-        let sr_config = self.parse_config(path)?;
+        let (sr_config, source) = self.parse_config(path)?;
         let mut config = Config::default();
         let mut result = MigrationResult::new(MigrationSource::SemanticRelease, config.clone());
+        if let Some(source) = source {
+            result.add_source(source);
+        }
 
         // Convert branches
         if let Some(ref branches) = sr_config.branches {
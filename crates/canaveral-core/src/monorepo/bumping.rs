@@ -0,0 +1,312 @@
+//! Applying version bumps to package manifests
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+
+use crate::error::Result;
+
+use super::discovery::DiscoveredPackage;
+use super::versioning::VersionBump;
+
+/// Default number of packages written at once
+pub const DEFAULT_BUMP_CONCURRENCY: usize = 8;
+
+/// Result of writing a single package's version
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PackageBumpResult {
+    /// Package name
+    pub package: String,
+    /// Version written
+    pub version: String,
+    /// Manifest the version was written to
+    pub manifest_path: PathBuf,
+    /// Error message if the write failed
+    pub error: Option<String>,
+    /// Time taken to write
+    pub duration: Duration,
+}
+
+impl PackageBumpResult {
+    /// Whether the write succeeded
+    pub fn success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Overall result of applying version bumps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BumpResult {
+    /// Results for each package, in bump order
+    pub packages: Vec<PackageBumpResult>,
+    /// Total duration
+    pub total_duration: Duration,
+}
+
+impl BumpResult {
+    /// Whether every package was written
+    pub fn success(&self) -> bool {
+        self.packages.iter().all(|p| p.success())
+    }
+
+    /// Get packages that were written
+    pub fn successful(&self) -> Vec<&PackageBumpResult> {
+        self.packages.iter().filter(|p| p.success()).collect()
+    }
+
+    /// Get packages that failed
+    pub fn failed(&self) -> Vec<&PackageBumpResult> {
+        self.packages.iter().filter(|p| !p.success()).collect()
+    }
+
+    /// Manifests that were written, without duplicates
+    pub fn changed_files(&self) -> Vec<&Path> {
+        let mut files: Vec<&Path> = Vec::new();
+        for package in self.successful() {
+            if !files.contains(&package.manifest_path.as_path()) {
+                files.push(&package.manifest_path);
+            }
+        }
+        files
+    }
+}
+
+/// Version setter function type
+///
+/// Writes `version` to the package's manifest, and to any other file that
+/// repeats it.
+pub type SetVersionFn = Box<dyn Fn(&DiscoveredPackage, &str) -> Result<()> + Send + Sync>;
+
+/// Writes version bumps to package manifests in parallel
+///
+/// Each package's manifest write is independent file I/O, so up to
+/// `concurrency` packages are written at once. The setter must lock every
+/// file it reads and rewrites, as the adapters' `set_version` does with its
+/// manifest lock, so writes to files shared between packages (such as a
+/// workspace root manifest) don't interleave.
+pub struct VersionApplier {
+    /// Maximum number of packages written at once
+    concurrency: usize,
+    /// Version setter function
+    setter: SetVersionFn,
+}
+
+impl VersionApplier {
+    /// Create an applier writing versions with `setter`
+    pub fn new(setter: SetVersionFn) -> Self {
+        Self {
+            concurrency: DEFAULT_BUMP_CONCURRENCY,
+            setter,
+        }
+    }
+
+    /// Set the maximum number of packages written at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Write every bump, collecting a result per package
+    ///
+    /// A failed write doesn't stop the others; check
+    /// [`BumpResult::success`] before committing.
+    pub fn apply(&self, packages: &[DiscoveredPackage], bumps: &[VersionBump]) -> BumpResult {
+        let start = Instant::now();
+        info!(
+            bumps = bumps.len(),
+            concurrency = self.concurrency,
+            "applying version bumps"
+        );
+
+        let queue: Mutex<std::slice::Iter<'_, VersionBump>> = Mutex::new(bumps.iter());
+        let results: Mutex<HashMap<String, PackageBumpResult>> = Mutex::new(HashMap::new());
+
+        std::thread::scope(|scope| {
+            for _ in 0..self.concurrency.min(bumps.len()) {
+                scope.spawn(|| loop {
+                    let Some(bump) = queue.lock().unwrap_or_else(|e| e.into_inner()).next() else {
+                        break;
+                    };
+                    let result = self.apply_one(packages, bump);
+                    results
+                        .lock()
+                        .unwrap_or_else(|e| e.into_inner())
+                        .insert(bump.package.clone(), result);
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        let packages: Vec<PackageBumpResult> = bumps
+            .iter()
+            .filter_map(|b| results.remove(&b.package))
+            .collect();
+        let result = BumpResult {
+            packages,
+            total_duration: start.elapsed(),
+        };
+        info!(
+            written = result.successful().len(),
+            failed = result.failed().len(),
+            duration_ms = result.total_duration.as_millis() as u64,
+            "version bumps applied"
+        );
+        result
+    }
+
+    fn apply_one(&self, packages: &[DiscoveredPackage], bump: &VersionBump) -> PackageBumpResult {
+        let start = Instant::now();
+        let Some(package) = packages.iter().find(|p| p.name == bump.package) else {
+            warn!(package = %bump.package, "bumped package not found");
+            return PackageBumpResult {
+                package: bump.package.clone(),
+                version: bump.new_version.clone(),
+                manifest_path: PathBuf::new(),
+                error: Some("package not found in workspace".to_string()),
+                duration: start.elapsed(),
+            };
+        };
+
+        let error = (self.setter)(package, &bump.new_version)
+            .err()
+            .map(|e| e.to_string());
+        match &error {
+            Some(error) => warn!(package = %package.name, error = %error, "version write failed"),
+            None => debug!(package = %package.name, version = %bump.new_version, "version written"),
+        }
+        PackageBumpResult {
+            package: package.name.clone(),
+            version: bump.new_version.clone(),
+            manifest_path: package.manifest_path.clone(),
+            error,
+            duration: start.elapsed(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CanaveralError;
+    use crate::types::ReleaseType;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    fn package(root: &Path, name: &str, manifest: &str) -> DiscoveredPackage {
+        DiscoveredPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            path: root.join(name),
+            manifest_path: root.join(manifest),
+            package_type: "npm".to_string(),
+            private: false,
+            workspace_dependencies: vec![],
+        }
+    }
+
+    fn bump(name: &str, version: &str) -> VersionBump {
+        VersionBump {
+            package: name.to_string(),
+            current_version: "1.0.0".to_string(),
+            new_version: version.to_string(),
+            release_type: ReleaseType::Minor,
+            reason: "direct changes".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_writes_every_package() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let packages: Vec<_> = (0..20)
+            .map(|i| {
+                package(
+                    temp.path(),
+                    &format!("pkg-{}", i),
+                    &format!("pkg-{}.txt", i),
+                )
+            })
+            .collect();
+        let bumps: Vec<_> = packages.iter().map(|p| bump(&p.name, "1.1.0")).collect();
+
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let max_in_flight = Arc::new(AtomicUsize::new(0));
+        let (current, max) = (in_flight.clone(), max_in_flight.clone());
+        let applier = VersionApplier::new(Box::new(move |package, version| {
+            let now = current.fetch_add(1, Ordering::SeqCst) + 1;
+            max.fetch_max(now, Ordering::SeqCst);
+            std::thread::sleep(Duration::from_millis(5));
+            std::fs::write(&package.manifest_path, version)?;
+            current.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }))
+        .with_concurrency(4);
+
+        let result = applier.apply(&packages, &bumps);
+        assert!(result.success());
+        assert_eq!(result.packages.len(), 20);
+        assert_eq!(result.packages[3].package, "pkg-3");
+        assert_eq!(result.changed_files().len(), 20);
+        for package in &packages {
+            assert_eq!(
+                std::fs::read_to_string(&package.manifest_path).unwrap(),
+                "1.1.0"
+            );
+        }
+        assert!(max_in_flight.load(Ordering::SeqCst) <= 4);
+    }
+
+    #[test]
+    fn test_shared_manifest_is_listed_once() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let packages: Vec<_> = (0..8)
+            .map(|i| package(temp.path(), &format!("pkg-{}", i), "Shared.toml"))
+            .collect();
+        let bumps: Vec<_> = packages.iter().map(|p| bump(&p.name, "2.0.0")).collect();
+
+        let result = VersionApplier::new(Box::new(|package, version| {
+            std::fs::write(&package.manifest_path, version)?;
+            Ok(())
+        }))
+        .with_concurrency(8)
+        .apply(&packages, &bumps);
+        assert!(result.success());
+        assert_eq!(result.changed_files(), [temp.path().join("Shared.toml")]);
+    }
+
+    #[test]
+    fn test_failures_are_collected() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let packages = vec![
+            package(temp.path(), "good", "good.txt"),
+            package(temp.path(), "bad", "bad.txt"),
+        ];
+        let bumps = vec![
+            bump("good", "1.1.0"),
+            bump("bad", "1.1.0"),
+            bump("missing", "1.1.0"),
+        ];
+
+        let applier = VersionApplier::new(Box::new(|package, version| {
+            if package.name == "bad" {
+                return Err(CanaveralError::other("manifest is read-only"));
+            }
+            std::fs::write(&package.manifest_path, version)?;
+            Ok(())
+        }));
+        let result = applier.apply(&packages, &bumps);
+
+        assert!(!result.success());
+        assert_eq!(result.successful().len(), 1);
+        let failed: Vec<_> = result.failed().iter().map(|p| p.package.as_str()).collect();
+        assert_eq!(failed, ["bad", "missing"]);
+        assert!(result.packages[1]
+            .error
+            .as_deref()
+            .unwrap()
+            .contains("read-only"));
+    }
+}
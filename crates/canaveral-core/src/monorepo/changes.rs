@@ -9,6 +9,7 @@ use tracing::{debug, info};
 use crate::error::Result;
 
 use super::discovery::DiscoveredPackage;
+use super::filter::PackageFilter;
 use super::graph::DependencyGraph;
 
 /// A package that has changes
@@ -56,6 +57,8 @@ pub struct ChangeDetector {
     root: PathBuf,
     /// Include changes from dependencies (transitive)
     include_transitive: bool,
+    /// Packages to consider
+    filter: PackageFilter,
 }
 
 impl ChangeDetector {
@@ -64,6 +67,7 @@ impl ChangeDetector {
         Self {
             root,
             include_transitive: true,
+            filter: PackageFilter::default(),
         }
     }
 
@@ -73,6 +77,15 @@ impl ChangeDetector {
         self
     }
 
+    /// Narrow detected changes to the packages selected by `filter`
+    ///
+    /// See [`PackageFilter::select_changed`] for how included packages
+    /// without changes are handled.
+    pub fn with_filter(mut self, filter: PackageFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
     /// Detect changed packages since a reference (tag, commit, branch)
     pub fn detect_changes(
         &self,
@@ -142,7 +155,10 @@ impl ChangeDetector {
             }
         }
 
-        let result: Vec<ChangedPackage> = changed.into_values().collect();
+        let mut result: Vec<ChangedPackage> = changed.into_values().collect();
+        if !self.filter.is_empty() {
+            result = self.filter.select_changed(packages, result, &self.root);
+        }
         info!(changed_packages = result.len(), "change detection complete");
         Ok(result)
     }
@@ -390,6 +406,24 @@ mod tests {
         assert_eq!(changes[0].change_reason, ChangeReason::DirectChanges);
     }
 
+    #[test]
+    fn test_detect_changes_with_filter() {
+        let temp = TempDir::new().unwrap();
+        let packages = create_test_packages(&temp);
+        let changed_files = vec![
+            PathBuf::from("packages/pkg-a/src/index.js"),
+            PathBuf::from("packages/pkg-b/src/utils.js"),
+        ];
+
+        let detector = ChangeDetector::new(temp.path().to_path_buf())
+            .with_filter(PackageFilter::new().exclude("pkg-b"));
+        let changes = detector
+            .detect_changes(&packages, &changed_files, None)
+            .unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].name, "pkg-a");
+    }
+
     #[test]
     fn test_change_filter() {
         let filter = ChangeFilter::default();
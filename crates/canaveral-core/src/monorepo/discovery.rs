@@ -62,7 +62,14 @@ impl PackageDiscovery {
     }
 
     /// Discover all packages in the workspace
-    pub fn discover(&self) -> Result<Vec<DiscoveredPackage>> {
+    ///
+    /// `dependencies` reports which of the workspace's package names (the
+    /// second argument) a package depends on; package adapters implement it
+    /// by reading the package's manifest.
+    pub fn discover(
+        &self,
+        dependencies: impl Fn(&DiscoveredPackage, &[String]) -> Result<Vec<String>>,
+    ) -> Result<Vec<DiscoveredPackage>> {
         debug!(
             workspace_type = %self.workspace.workspace_type,
             patterns = self.workspace.package_patterns.len(),
@@ -121,7 +128,7 @@ impl PackageDiscovery {
         // Second pass: resolve workspace dependencies
         let all_names: Vec<String> = package_names.values().cloned().collect();
         for pkg in &mut packages {
-            pkg.workspace_dependencies = self.find_workspace_deps(pkg, &all_names)?;
+            pkg.workspace_dependencies = dependencies(pkg, &all_names)?;
         }
 
         info!(count = packages.len(), "discovered packages");
@@ -294,151 +301,6 @@ impl PackageDiscovery {
 
         Ok(None)
     }
-
-    /// Find workspace dependencies for a package
-    fn find_workspace_deps(
-        &self,
-        pkg: &DiscoveredPackage,
-        all_names: &[String],
-    ) -> Result<Vec<String>> {
-        match self.workspace.workspace_type {
-            WorkspaceType::Cargo => self.find_cargo_workspace_deps(&pkg.manifest_path, all_names),
-            WorkspaceType::Npm
-            | WorkspaceType::Yarn
-            | WorkspaceType::Pnpm
-            | WorkspaceType::Lerna
-            | WorkspaceType::Turbo
-            | WorkspaceType::Nx => self.find_npm_workspace_deps(&pkg.manifest_path, all_names),
-            WorkspaceType::Python => self.find_python_workspace_deps(&pkg.manifest_path, all_names),
-            WorkspaceType::Custom => Ok(Vec::new()),
-        }
-    }
-
-    /// Find Cargo workspace dependencies
-    fn find_cargo_workspace_deps(
-        &self,
-        manifest_path: &Path,
-        all_names: &[String],
-    ) -> Result<Vec<String>> {
-        let content = std::fs::read_to_string(manifest_path)?;
-
-        #[derive(Deserialize)]
-        struct CargoToml {
-            dependencies: Option<HashMap<String, toml::Value>>,
-            #[serde(rename = "dev-dependencies")]
-            dev_dependencies: Option<HashMap<String, toml::Value>>,
-            #[serde(rename = "build-dependencies")]
-            build_dependencies: Option<HashMap<String, toml::Value>>,
-        }
-
-        let cargo: CargoToml = toml::from_str(&content)?;
-
-        let mut deps = Vec::new();
-
-        for section in [
-            cargo.dependencies,
-            cargo.dev_dependencies,
-            cargo.build_dependencies,
-        ]
-        .into_iter()
-        .flatten()
-        {
-            for name in section.keys() {
-                if all_names.contains(name) {
-                    deps.push(name.clone());
-                }
-            }
-        }
-
-        deps.sort();
-        deps.dedup();
-        Ok(deps)
-    }
-
-    /// Find npm workspace dependencies
-    fn find_npm_workspace_deps(
-        &self,
-        manifest_path: &Path,
-        all_names: &[String],
-    ) -> Result<Vec<String>> {
-        let content = std::fs::read_to_string(manifest_path)?;
-
-        #[derive(Deserialize)]
-        struct PackageJson {
-            dependencies: Option<HashMap<String, String>>,
-            #[serde(rename = "devDependencies")]
-            dev_dependencies: Option<HashMap<String, String>>,
-            #[serde(rename = "peerDependencies")]
-            peer_dependencies: Option<HashMap<String, String>>,
-        }
-
-        let pkg: PackageJson = serde_json::from_str(&content)?;
-
-        let mut deps = Vec::new();
-
-        for section in [
-            pkg.dependencies,
-            pkg.dev_dependencies,
-            pkg.peer_dependencies,
-        ]
-        .into_iter()
-        .flatten()
-        {
-            for name in section.keys() {
-                if all_names.contains(name) {
-                    deps.push(name.clone());
-                }
-            }
-        }
-
-        deps.sort();
-        deps.dedup();
-        Ok(deps)
-    }
-
-    /// Find Python workspace dependencies
-    fn find_python_workspace_deps(
-        &self,
-        manifest_path: &Path,
-        all_names: &[String],
-    ) -> Result<Vec<String>> {
-        let content = std::fs::read_to_string(manifest_path)?;
-
-        #[derive(Deserialize)]
-        struct PyProject {
-            project: Option<ProjectSection>,
-        }
-
-        #[derive(Deserialize)]
-        struct ProjectSection {
-            dependencies: Option<Vec<String>>,
-        }
-
-        let pyproj: PyProject = toml::from_str(&content)?;
-
-        let mut deps = Vec::new();
-
-        if let Some(project) = pyproj.project {
-            if let Some(dependencies) = project.dependencies {
-                for dep in dependencies {
-                    // Extract package name from dependency specification
-                    // e.g., "my-package>=1.0.0" -> "my-package"
-                    let name = dep
-                        .split(|c: char| !c.is_alphanumeric() && c != '-' && c != '_')
-                        .next()
-                        .unwrap_or(&dep);
-
-                    if all_names.contains(&name.to_string()) {
-                        deps.push(name.to_string());
-                    }
-                }
-            }
-        }
-
-        deps.sort();
-        deps.dedup();
-        Ok(deps)
-    }
 }
 
 #[cfg(test)]
@@ -488,7 +350,18 @@ pkg-a = { path = "../pkg-a" }
 
         let ws = Workspace::detect(temp.path()).unwrap().unwrap();
         let discovery = PackageDiscovery::new(ws);
-        let packages = discovery.discover().unwrap();
+        let packages = discovery
+            .discover(|pkg, names| {
+                let mut names = names.to_vec();
+                names.sort();
+                assert_eq!(names, ["pkg-a", "pkg-b"]);
+                Ok(if pkg.name == "pkg-b" {
+                    vec!["pkg-a".to_string()]
+                } else {
+                    Vec::new()
+                })
+            })
+            .unwrap();
 
         assert_eq!(packages.len(), 2);
 
@@ -497,7 +370,8 @@ pkg-a = { path = "../pkg-a" }
 
         assert_eq!(pkg_a.version, "1.0.0");
         assert_eq!(pkg_b.version, "2.0.0");
-        assert!(pkg_b.workspace_dependencies.contains(&"pkg-a".to_string()));
+        assert_eq!(pkg_b.workspace_dependencies, ["pkg-a"]);
+        assert!(pkg_a.workspace_dependencies.is_empty());
     }
 
     #[test]
@@ -537,13 +411,12 @@ pkg-a = { path = "../pkg-a" }
 
         let ws = Workspace::detect(temp.path()).unwrap().unwrap();
         let discovery = PackageDiscovery::new(ws);
-        let packages = discovery.discover().unwrap();
+        let packages = discovery.discover(|_, _| Ok(Vec::new())).unwrap();
 
         assert_eq!(packages.len(), 2);
 
         let utils = packages.iter().find(|p| p.name == "@my/utils").unwrap();
-        assert!(utils
-            .workspace_dependencies
-            .contains(&"@my/core".to_string()));
+        assert_eq!(utils.version, "1.0.0");
+        assert_eq!(utils.package_type, "npm");
     }
 }
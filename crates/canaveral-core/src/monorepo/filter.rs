@@ -0,0 +1,259 @@
+//! Package selection by name or path globs
+
+use std::path::Path;
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::{ConfigError, Result};
+
+use super::changes::{ChangeReason, ChangedPackage};
+use super::discovery::DiscoveredPackage;
+
+/// Selects packages by glob patterns matched against package names and
+/// paths relative to the workspace root
+///
+/// A package is selected when it matches any include pattern (or there are
+/// none) and no exclude pattern. Packages named by an include pattern are
+/// released even when change detection didn't pick them up, provided they
+/// changed since the last release or [`force`](Self::force) is set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageFilter {
+    /// Patterns selecting packages, e.g. `packages/api` or `@acme/*`
+    pub include: Vec<String>,
+    /// Patterns dropping packages
+    pub exclude: Vec<String>,
+    /// Select included packages even without changes since the last release
+    pub force: bool,
+}
+
+impl PackageFilter {
+    /// Create an empty filter that selects every package
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add include pattern
+    pub fn include(mut self, pattern: impl Into<String>) -> Self {
+        self.include.push(pattern.into());
+        self
+    }
+
+    /// Add exclude pattern
+    pub fn exclude(mut self, pattern: impl Into<String>) -> Self {
+        self.exclude.push(pattern.into());
+        self
+    }
+
+    /// Select included packages even without changes
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+
+    /// Whether the filter has no patterns
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    /// Check that every pattern is a valid glob
+    pub fn validate(&self) -> Result<()> {
+        for pattern in self.include.iter().chain(&self.exclude) {
+            Pattern::new(pattern).map_err(|e| ConfigError::InvalidValue {
+                field: "filter".to_string(),
+                message: format!("invalid pattern '{}': {}", pattern, e),
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Whether the package `name` at `path` (relative to the workspace root)
+    /// is selected
+    pub fn matches(&self, name: &str, path: &Path) -> bool {
+        let path = path.to_string_lossy().replace('\\', "/");
+        let path = path.trim_end_matches('/');
+        let hit = |patterns: &[String]| {
+            patterns.iter().any(|p| {
+                Pattern::new(p.trim_end_matches('/')).is_ok_and(|p| {
+                    let options = MatchOptions {
+                        require_literal_separator: true,
+                        ..MatchOptions::new()
+                    };
+                    p.matches_with(name, options) || p.matches_with(path, options)
+                })
+            })
+        };
+        (self.include.is_empty() || hit(&self.include)) && !hit(&self.exclude)
+    }
+
+    /// Whether `package` is selected, with its path taken relative to `root`
+    pub fn matches_package(&self, package: &DiscoveredPackage, root: &Path) -> bool {
+        let path = package.path.strip_prefix(root).unwrap_or(&package.path);
+        self.matches(&package.name, path)
+    }
+
+    /// The selected packages out of `packages`
+    pub fn apply<'p>(
+        &self,
+        packages: &'p [DiscoveredPackage],
+        root: &Path,
+    ) -> Vec<&'p DiscoveredPackage> {
+        packages
+            .iter()
+            .filter(|p| self.matches_package(p, root))
+            .collect()
+    }
+
+    /// Narrow change detection results to the selected packages
+    ///
+    /// Excluded packages are dropped from `changed`. Packages matching an
+    /// include pattern but not in `changed` have nothing to release: they
+    /// are skipped with a warning, or added as [`ChangeReason::Forced`] when
+    /// [`force`](Self::force) is set.
+    pub fn select_changed(
+        &self,
+        packages: &[DiscoveredPackage],
+        changed: Vec<ChangedPackage>,
+        root: &Path,
+    ) -> Vec<ChangedPackage> {
+        let mut selected: Vec<ChangedPackage> = changed
+            .into_iter()
+            .filter(|c| {
+                let path = c.path.strip_prefix(root).unwrap_or(&c.path);
+                let keep = self.matches(&c.name, path);
+                if !keep {
+                    debug!(package = %c.name, "package filtered out");
+                }
+                keep
+            })
+            .collect();
+
+        if self.include.is_empty() {
+            return selected;
+        }
+        for package in self.apply(packages, root) {
+            if selected.iter().any(|c| c.name == package.name) {
+                continue;
+            }
+            if self.force {
+                debug!(package = %package.name, "forcing release of unchanged package");
+                selected.push(ChangedPackage {
+                    name: package.name.clone(),
+                    path: package.path.clone(),
+                    changed_files: Vec::new(),
+                    change_reason: ChangeReason::Forced,
+                    commits: Vec::new(),
+                });
+            } else {
+                warn!(
+                    package = %package.name,
+                    "package selected by filter has no changes since the last release, skipping (use force to release anyway)"
+                );
+            }
+        }
+        selected
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn package(root: &Path, name: &str, dir: &str) -> DiscoveredPackage {
+        DiscoveredPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            path: root.join(dir),
+            manifest_path: root.join(dir).join("package.json"),
+            package_type: "npm".to_string(),
+            private: false,
+            workspace_dependencies: vec![],
+        }
+    }
+
+    fn changed(package: &DiscoveredPackage) -> ChangedPackage {
+        ChangedPackage {
+            name: package.name.clone(),
+            path: package.path.clone(),
+            changed_files: vec![PathBuf::from("src/index.ts")],
+            change_reason: ChangeReason::DirectChanges,
+            commits: Vec::new(),
+        }
+    }
+
+    fn workspace(root: &Path) -> Vec<DiscoveredPackage> {
+        vec![
+            package(root, "@acme/api", "packages/api"),
+            package(root, "@acme/web", "packages/web"),
+            package(root, "docs", "apps/docs"),
+        ]
+    }
+
+    #[test]
+    fn test_include_narrows_to_one_package() {
+        let root = Path::new("/repo");
+        let packages = workspace(root);
+        let all_changed: Vec<_> = packages.iter().map(changed).collect();
+
+        let filter = PackageFilter::new().include("packages/api");
+        let names: Vec<_> = filter
+            .apply(&packages, root)
+            .iter()
+            .map(|p| p.name.as_str())
+            .collect();
+        assert_eq!(names, ["@acme/api"]);
+
+        let selected = filter.select_changed(&packages, all_changed, root);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name, "@acme/api");
+
+        // Names match too
+        let filter = PackageFilter::new().include("@acme/*");
+        assert_eq!(filter.apply(&packages, root).len(), 2);
+    }
+
+    #[test]
+    fn test_exclude_drops_package() {
+        let root = Path::new("/repo");
+        let packages = workspace(root);
+        let all_changed: Vec<_> = packages.iter().map(changed).collect();
+
+        let filter = PackageFilter::new().exclude("apps/*");
+        let selected = filter.select_changed(&packages, all_changed, root);
+        let names: Vec<_> = selected.iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, ["@acme/api", "@acme/web"]);
+    }
+
+    #[test]
+    fn test_included_package_without_changes() {
+        let root = Path::new("/repo");
+        let packages = workspace(root);
+        let web_changed = vec![changed(&packages[1])];
+
+        let filter = PackageFilter::new().include("packages/api");
+        assert!(filter
+            .select_changed(&packages, web_changed.clone(), root)
+            .is_empty());
+
+        let selected = filter
+            .with_force(true)
+            .select_changed(&packages, web_changed, root);
+        assert_eq!(selected.len(), 1);
+        assert_eq!(selected[0].name, "@acme/api");
+        assert_eq!(selected[0].change_reason, ChangeReason::Forced);
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(PackageFilter::new()
+            .include("packages/[")
+            .validate()
+            .is_err());
+        assert!(PackageFilter::new()
+            .include("packages/*")
+            .validate()
+            .is_ok());
+    }
+}
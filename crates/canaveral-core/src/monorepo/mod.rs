@@ -4,21 +4,29 @@
 //! - Workspace detection (Cargo, npm, pnpm, yarn, lerna, nx, turbo)
 //! - Package discovery with glob patterns
 //! - Change detection based on git diffs
+//! - Package selection by name or path globs
 //! - Dependency graph analysis with topological sorting
 //! - Versioning modes (independent, fixed, grouped)
+//! - Parallel version writes to package manifests
 //! - Coordinated publishing with failure handling
 
+pub mod bumping;
 pub mod changes;
 pub mod detector;
 pub mod discovery;
+pub mod filter;
 pub mod graph;
 pub mod publishing;
 pub mod versioning;
 pub mod workspace;
 
+pub use bumping::{
+    BumpResult, PackageBumpResult, SetVersionFn, VersionApplier, DEFAULT_BUMP_CONCURRENCY,
+};
 pub use changes::{ChangeDetector, ChangeFilter, ChangeReason, ChangedPackage};
 pub use detector::{WorkspaceDetector, WorkspaceDetectorRegistry};
 pub use discovery::{DiscoveredPackage, PackageDiscovery};
+pub use filter::PackageFilter;
 pub use graph::{DependencyGraph, PackageNode};
 pub use publishing::{
     FailureStrategy, PackagePublishResult, PlannedPublish, PublishCallback, PublishCoordinator,
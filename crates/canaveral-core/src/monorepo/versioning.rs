@@ -5,6 +5,7 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info};
 
+use crate::config::{FixedGroupConfig, VersioningConfig};
 use crate::error::Result;
 use crate::types::ReleaseType;
 
@@ -20,7 +21,7 @@ pub enum VersioningMode {
     Independent,
     /// All packages share the same version
     Fixed,
-    /// Hybrid: groups of packages share versions
+    /// Hybrid: groups of packages share versions, the rest are independent
     Grouped,
 }
 
@@ -60,7 +61,7 @@ pub struct VersioningStrategy {
     /// Versioning mode
     mode: VersioningMode,
     /// Version groups (for grouped mode)
-    groups: HashMap<String, Vec<String>>,
+    groups: Vec<FixedGroupConfig>,
     /// Whether to sync peer dependencies
     sync_peer_deps: bool,
     /// Whether to bump dependents when a dependency changes
@@ -72,18 +73,52 @@ impl VersioningStrategy {
     pub fn new(mode: VersioningMode) -> Self {
         Self {
             mode,
-            groups: HashMap::new(),
+            groups: Vec::new(),
             sync_peer_deps: true,
             bump_dependents: false,
         }
     }
 
+    /// Create the strategy configured by `config`
+    ///
+    /// Fixed groups select grouped mode; otherwise `independent` picks
+    /// between independent and fixed versioning.
+    pub fn from_config(config: &VersioningConfig) -> Self {
+        if !config.fixed_groups.is_empty() {
+            Self::new(VersioningMode::Grouped).with_fixed_groups(config.fixed_groups.clone())
+        } else if config.independent {
+            Self::new(VersioningMode::Independent)
+        } else {
+            Self::new(VersioningMode::Fixed)
+        }
+    }
+
+    /// Set version groups for grouped mode, as member name globs by group name
+    pub fn with_groups(self, groups: HashMap<String, Vec<String>>) -> Self {
+        let mut groups: Vec<_> = groups
+            .into_iter()
+            .map(|(name, packages)| FixedGroupConfig::new(name, packages))
+            .collect();
+        groups.sort_by(|a, b| a.name.cmp(&b.name));
+        self.with_fixed_groups(groups)
+    }
+
     /// Set version groups for grouped mode
-    pub fn with_groups(mut self, groups: HashMap<String, Vec<String>>) -> Self {
+    ///
+    /// A package matching several groups belongs to the first.
+    pub fn with_fixed_groups(mut self, groups: Vec<FixedGroupConfig>) -> Self {
         self.groups = groups;
         self
     }
 
+    /// The group `package` belongs to in grouped mode
+    pub fn group_of(&self, package: &str) -> Option<&FixedGroupConfig> {
+        if self.mode != VersioningMode::Grouped {
+            return None;
+        }
+        self.groups.iter().find(|group| group.matches(package))
+    }
+
     /// Set whether to sync peer dependencies
     pub fn sync_peer_deps(mut self, sync: bool) -> Self {
         self.sync_peer_deps = sync;
@@ -103,20 +138,50 @@ impl VersioningStrategy {
         changes: &[ChangedPackage],
         release_type: ReleaseType,
         graph: Option<&DependencyGraph>,
+    ) -> Result<Vec<VersionBump>> {
+        self.calculate(packages, changes, &|_| release_type, graph)
+    }
+
+    /// Calculate version bumps where each changed package needs its own
+    /// release type, e.g. from its conventional commits
+    ///
+    /// Changed packages missing from `release_types` get a patch bump.
+    /// Packages sharing a version get the highest bump any of them needs.
+    pub fn calculate_bumps_by_package(
+        &self,
+        packages: &[DiscoveredPackage],
+        changes: &[ChangedPackage],
+        release_types: &HashMap<String, ReleaseType>,
+        graph: Option<&DependencyGraph>,
+    ) -> Result<Vec<VersionBump>> {
+        let release_type_of = |name: &str| {
+            release_types
+                .get(name)
+                .copied()
+                .unwrap_or(ReleaseType::Patch)
+        };
+        self.calculate(packages, changes, &release_type_of, graph)
+    }
+
+    fn calculate(
+        &self,
+        packages: &[DiscoveredPackage],
+        changes: &[ChangedPackage],
+        release_type_of: &dyn Fn(&str) -> ReleaseType,
+        graph: Option<&DependencyGraph>,
     ) -> Result<Vec<VersionBump>> {
         info!(
             mode = %self.mode,
             changes = changes.len(),
-            release_type = ?release_type,
             "calculating version bumps"
         );
         let result = match self.mode {
             VersioningMode::Independent => {
-                self.calculate_independent_bumps(packages, changes, release_type, graph)
+                self.calculate_independent_bumps(packages, changes, release_type_of, graph)
             }
-            VersioningMode::Fixed => self.calculate_fixed_bumps(packages, changes, release_type),
+            VersioningMode::Fixed => self.calculate_fixed_bumps(packages, changes, release_type_of),
             VersioningMode::Grouped => {
-                self.calculate_grouped_bumps(packages, changes, release_type, graph)
+                self.calculate_grouped_bumps(packages, changes, release_type_of, graph)
             }
         };
         if let Ok(ref bumps) = result {
@@ -140,7 +205,7 @@ impl VersioningStrategy {
         &self,
         packages: &[DiscoveredPackage],
         changes: &[ChangedPackage],
-        release_type: ReleaseType,
+        release_type_of: &dyn Fn(&str) -> ReleaseType,
         graph: Option<&DependencyGraph>,
     ) -> Result<Vec<VersionBump>> {
         let mut bumps = Vec::new();
@@ -148,6 +213,7 @@ impl VersioningStrategy {
 
         for change in changes {
             if let Some(pkg) = packages.iter().find(|p| p.name == change.name) {
+                let release_type = release_type_of(&pkg.name);
                 let new_version = self.bump_version(&pkg.version, release_type)?;
                 bumps.push(VersionBump {
                     package: pkg.name.clone(),
@@ -193,21 +259,15 @@ impl VersioningStrategy {
         &self,
         packages: &[DiscoveredPackage],
         changes: &[ChangedPackage],
-        release_type: ReleaseType,
+        release_type_of: &dyn Fn(&str) -> ReleaseType,
     ) -> Result<Vec<VersionBump>> {
-        if changes.is_empty() {
+        let Some(release_type) =
+            highest_release_type(changes.iter().map(|c| release_type_of(&c.name)))
+        else {
             return Ok(Vec::new());
-        }
-
-        // Find the highest current version
-        let max_version = packages
-            .iter()
-            .filter_map(|p| semver::Version::parse(&p.version).ok())
-            .max()
-            .map(|v| v.to_string())
-            .unwrap_or_else(|| "0.0.0".to_string());
+        };
 
-        let new_version = self.bump_version(&max_version, release_type)?;
+        let new_version = self.bump_version(&max_version(packages.iter()), release_type)?;
 
         // Bump all packages to the same version
         let bumps = packages
@@ -233,65 +293,53 @@ impl VersioningStrategy {
         &self,
         packages: &[DiscoveredPackage],
         changes: &[ChangedPackage],
-        release_type: ReleaseType,
+        release_type_of: &dyn Fn(&str) -> ReleaseType,
         graph: Option<&DependencyGraph>,
     ) -> Result<Vec<VersionBump>> {
         let mut bumps = Vec::new();
         let changed_names: Vec<_> = changes.iter().map(|c| c.name.clone()).collect();
 
-        // Track which groups have changes
-        let mut group_has_changes: HashMap<String, bool> = HashMap::new();
-
-        for (group_name, members) in &self.groups {
-            let has_changes = members.iter().any(|m| changed_names.contains(m));
-            group_has_changes.insert(group_name.clone(), has_changes);
-        }
-
-        // Bump packages by group
-        for (group_name, members) in &self.groups {
-            if !group_has_changes.get(group_name).copied().unwrap_or(false) {
+        // Bump packages by group, to the highest bump any changed member needs
+        for group in &self.groups {
+            let members: Vec<_> = packages
+                .iter()
+                .filter(|p| self.group_of(&p.name).is_some_and(|g| g.name == group.name))
+                .collect();
+            let Some(release_type) = highest_release_type(
+                members
+                    .iter()
+                    .filter(|p| changed_names.contains(&p.name))
+                    .map(|p| release_type_of(&p.name)),
+            ) else {
                 continue;
-            }
+            };
 
-            // Find max version in group
-            let max_version = packages
-                .iter()
-                .filter(|p| members.contains(&p.name))
-                .filter_map(|p| semver::Version::parse(&p.version).ok())
-                .max()
-                .map(|v| v.to_string())
-                .unwrap_or_else(|| "0.0.0".to_string());
-
-            let new_version = self.bump_version(&max_version, release_type)?;
-
-            for member in members {
-                if let Some(pkg) = packages.iter().find(|p| &p.name == member) {
-                    bumps.push(VersionBump {
-                        package: pkg.name.clone(),
-                        current_version: pkg.version.clone(),
-                        new_version: new_version.clone(),
-                        release_type,
-                        reason: if changed_names.contains(&pkg.name) {
-                            "direct changes".to_string()
-                        } else {
-                            format!("group '{}' updated", group_name)
-                        },
-                    });
-                }
+            let new_version =
+                self.bump_version(&max_version(members.iter().copied()), release_type)?;
+
+            for pkg in members {
+                bumps.push(VersionBump {
+                    package: pkg.name.clone(),
+                    current_version: pkg.version.clone(),
+                    new_version: new_version.clone(),
+                    release_type,
+                    reason: if changed_names.contains(&pkg.name) {
+                        "direct changes".to_string()
+                    } else {
+                        format!("group '{}' updated", group.name)
+                    },
+                });
             }
         }
 
         // Handle packages not in any group (independent versioning)
         for change in changes {
-            let in_group = self
-                .groups
-                .values()
-                .any(|members| members.contains(&change.name));
-            if in_group {
+            if self.group_of(&change.name).is_some() {
                 continue;
             }
 
             if let Some(pkg) = packages.iter().find(|p| p.name == change.name) {
+                let release_type = release_type_of(&pkg.name);
                 let new_version = self.bump_version(&pkg.version, release_type)?;
                 bumps.push(VersionBump {
                     package: pkg.name.clone(),
@@ -309,15 +357,7 @@ impl VersioningStrategy {
                 let bumped_names: Vec<_> = bumps.iter().map(|b| b.package.clone()).collect();
 
                 for pkg in packages {
-                    if bumped_names.contains(&pkg.name) {
-                        continue;
-                    }
-
-                    let in_group = self
-                        .groups
-                        .values()
-                        .any(|members| members.contains(&pkg.name));
-                    if in_group {
+                    if bumped_names.contains(&pkg.name) || self.group_of(&pkg.name).is_some() {
                         continue;
                     }
 
@@ -387,7 +427,12 @@ impl VersioningStrategy {
     }
 
     /// Generate tag name for a package version
+    ///
+    /// Members of a group share the group's tag.
     pub fn tag_name(&self, package: &str, version: &str) -> String {
+        if let Some(group) = self.group_of(package) {
+            return group.tag(version);
+        }
         match self.mode {
             VersioningMode::Fixed => format!("v{}", version),
             VersioningMode::Independent | VersioningMode::Grouped => {
@@ -397,6 +442,20 @@ impl VersioningStrategy {
     }
 }
 
+/// The highest of `release_types`, or `None` if there are none
+fn highest_release_type(release_types: impl Iterator<Item = ReleaseType>) -> Option<ReleaseType> {
+    release_types.max_by_key(|t| t.precedence())
+}
+
+/// The highest valid version among `packages`, or `0.0.0`
+fn max_version<'p>(packages: impl Iterator<Item = &'p DiscoveredPackage>) -> String {
+    packages
+        .filter_map(|p| semver::Version::parse(&p.version).ok())
+        .max()
+        .map(|v| v.to_string())
+        .unwrap_or_else(|| "0.0.0".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -505,6 +564,52 @@ mod tests {
         assert!(!bumps.iter().any(|b| b.package == "cli"));
     }
 
+    #[test]
+    fn test_fixed_group_shares_highest_bump() {
+        let mut packages = create_packages();
+        packages[0].name = "@acme/core-api".to_string();
+        packages[1].name = "@acme/core-db".to_string();
+        let changes: Vec<_> = ["@acme/core-api", "@acme/core-db", "cli"]
+            .into_iter()
+            .map(|name| ChangedPackage {
+                name: name.to_string(),
+                path: "packages".into(),
+                changed_files: vec![],
+                change_reason: ChangeReason::ConventionalCommit,
+                commits: vec![],
+            })
+            .collect();
+        let release_types = HashMap::from([
+            ("@acme/core-api".to_string(), ReleaseType::Patch),
+            ("@acme/core-db".to_string(), ReleaseType::Minor),
+            ("cli".to_string(), ReleaseType::Patch),
+        ]);
+
+        let config = VersioningConfig {
+            fixed_groups: vec![FixedGroupConfig::new(
+                "core",
+                vec!["@acme/core-*".to_string()],
+            )],
+            ..Default::default()
+        };
+        let strategy = VersioningStrategy::from_config(&config);
+        let bumps = strategy
+            .calculate_bumps_by_package(&packages, &changes, &release_types, None)
+            .unwrap();
+
+        let version_of = |name: &str| {
+            bumps
+                .iter()
+                .find(|b| b.package == name)
+                .map(|b| b.new_version.as_str())
+        };
+        // Grouped members move together to the minor bump of the highest version
+        assert_eq!(version_of("@acme/core-api"), Some("1.3.0"));
+        assert_eq!(version_of("@acme/core-db"), Some("1.3.0"));
+        // The ungrouped package keeps its own version and bump
+        assert_eq!(version_of("cli"), Some("2.0.1"));
+    }
+
     #[test]
     fn test_tag_names() {
         let independent = VersioningStrategy::new(VersioningMode::Independent);
@@ -0,0 +1,40 @@
+//! Release notifications
+//!
+//! A [`Notifier`] receives the [`ReleaseSummary`] once a release finishes,
+//! successfully or not, and posts it somewhere people will see it. The
+//! Slack and webhook notifiers for the notifications configuration live in
+//! the CLI, which owns the HTTP client; core only defines the interface.
+//!
+//! Notifications are best effort: [`ReleaseWorkflow::notify`](crate::workflow::ReleaseWorkflow::notify)
+//! logs failures and carries on.
+
+use serde::Serialize;
+
+use crate::error::Result;
+use crate::summary::ReleaseSummary;
+
+/// How a release ended
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "status", content = "error", rename_all = "lowercase")]
+pub enum ReleaseOutcome {
+    /// The release completed
+    Success,
+    /// The release failed with this error
+    Failure(String),
+}
+
+impl ReleaseOutcome {
+    /// Whether the release completed
+    pub fn is_success(&self) -> bool {
+        matches!(self, Self::Success)
+    }
+}
+
+/// Sends a message about a finished release
+pub trait Notifier: Send + Sync {
+    /// Name used in logs (`slack`, `webhook`, ...)
+    fn name(&self) -> &str;
+
+    /// Send the notification for `summary`
+    fn notify(&self, summary: &ReleaseSummary, outcome: &ReleaseOutcome) -> Result<()>;
+}
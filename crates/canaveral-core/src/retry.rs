@@ -0,0 +1,272 @@
+//! Retries with exponential backoff
+//!
+//! [`retry`] (async) and [`retry_blocking`] re-run a fallible operation
+//! while its error is retryable, waiting `base_delay * 2^n` (capped at
+//! `max_delay`, with optional jitter) between attempts.
+
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tracing::warn;
+
+/// How often and how long to wait between attempts
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetryPolicy {
+    /// Total attempts, including the first (at least 1)
+    pub max_attempts: u32,
+    /// Delay before the first retry, doubled for each further retry
+    pub base_delay: Duration,
+    /// Upper bound on any single delay
+    pub max_delay: Duration,
+    /// Fraction of each delay randomized, from 0.0 (none) to 1.0
+    pub jitter: f64,
+}
+
+impl RetryPolicy {
+    /// Policy making up to `max_attempts` attempts, 1s apart initially
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+            jitter: 0.0,
+        }
+    }
+
+    /// Policy making a single attempt
+    pub fn none() -> Self {
+        Self::new(1)
+    }
+
+    /// Set the delay before the first retry
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Set the upper bound on any single delay
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Randomize each delay by up to `jitter` of its length (clamped to 0..=1)
+    ///
+    /// Spreads out retries from concurrent callers hitting the same limit.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Delay before retry number `retry` (0 for the first retry), without jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let factor = 2u32.saturating_pow(retry);
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+
+    /// Delay before retry number `retry`, with jitter applied
+    pub fn delay(&self, retry: u32) -> Duration {
+        let delay = self.backoff(retry);
+        if self.jitter == 0.0 {
+            return delay;
+        }
+        // Scale into [1 - jitter, 1]; retries only need to be spread out,
+        // not unpredictable, so the clock is random enough
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.subsec_nanos())
+            .unwrap_or(0);
+        let random = f64::from(nanos % 1_000_000) / 1_000_000.0;
+        delay.mul_f64(1.0 - self.jitter * random)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(3).with_jitter(0.2)
+    }
+}
+
+/// Run `op` until it succeeds, fails with a non-retryable error, or the
+/// policy's attempts are used up
+///
+/// `op` receives the attempt number, starting at 1. The last error is
+/// returned when giving up.
+pub async fn retry<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    op: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    retry_notify(policy, is_retryable, |_, _, _| {}, op).await
+}
+
+/// Like [`retry`], calling `on_retry(attempt, &error, delay)` before each wait
+pub async fn retry_notify<T, E, F, Fut>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut on_retry: impl FnMut(u32, &E, Duration),
+    mut op: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    F: FnMut(u32) -> Fut,
+    Fut: Future<Output = Result<T, E>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op(attempt).await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                let delay = policy.delay(attempt - 1);
+                warn!(attempt, max_attempts = policy.max_attempts, delay_ms = delay.as_millis() as u64, error = %e, "attempt failed, retrying");
+                on_retry(attempt, &e, delay);
+                tokio::time::sleep(delay).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Blocking version of [`retry`], sleeping the current thread between attempts
+pub fn retry_blocking<T, E>(
+    policy: &RetryPolicy,
+    is_retryable: impl Fn(&E) -> bool,
+    mut op: impl FnMut(u32) -> Result<T, E>,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+{
+    let mut attempt = 1;
+    loop {
+        match op(attempt) {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_attempts && is_retryable(&e) => {
+                let delay = policy.delay(attempt - 1);
+                warn!(attempt, max_attempts = policy.max_attempts, delay_ms = delay.as_millis() as u64, error = %e, "attempt failed, retrying");
+                std::thread::sleep(delay);
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[derive(Debug, PartialEq)]
+    enum TestError {
+        Transient,
+        Fatal,
+    }
+
+    impl std::fmt::Display for TestError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self)
+        }
+    }
+
+    fn retryable(e: &TestError) -> bool {
+        *e == TestError::Transient
+    }
+
+    fn fast(max_attempts: u32) -> RetryPolicy {
+        RetryPolicy::new(max_attempts).with_base_delay(Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_max() {
+        let policy = RetryPolicy::new(10)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(500));
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(1), Duration::from_millis(200));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(3), Duration::from_millis(500));
+        assert_eq!(policy.backoff(40), Duration::from_millis(500));
+
+        let jittered = policy.with_jitter(0.5);
+        let delay = jittered.delay(1);
+        assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn test_succeeds_on_third_attempt() {
+        let calls = Cell::new(0);
+        let mut retries = Vec::new();
+        let result = retry_notify(
+            &fast(5),
+            retryable,
+            |attempt, _, _| retries.push(attempt),
+            |attempt| {
+                calls.set(calls.get() + 1);
+                async move {
+                    if attempt < 3 {
+                        Err(TestError::Transient)
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            },
+        )
+        .await;
+
+        assert_eq!(result, Ok(3));
+        assert_eq!(calls.get(), 3);
+        assert_eq!(retries, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_attempts() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry(&fast(3), retryable, |_| {
+            calls.set(calls.get() + 1);
+            async { Err(TestError::Transient) }
+        })
+        .await;
+
+        assert_eq!(result, Err(TestError::Transient));
+        assert_eq!(calls.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_fails_immediately() {
+        let calls = Cell::new(0);
+        let result: Result<(), _> = retry(&fast(5), retryable, |_| {
+            calls.set(calls.get() + 1);
+            async { Err(TestError::Fatal) }
+        })
+        .await;
+
+        assert_eq!(result, Err(TestError::Fatal));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn test_retry_blocking() {
+        let result = retry_blocking(&fast(3), retryable, |attempt| {
+            if attempt < 3 {
+                Err(TestError::Transient)
+            } else {
+                Ok("done")
+            }
+        });
+        assert_eq!(result, Ok("done"));
+
+        let mut calls = 0;
+        let result: Result<(), _> = retry_blocking(&fast(3), retryable, |_| {
+            calls += 1;
+            Err(TestError::Fatal)
+        });
+        assert_eq!(result, Err(TestError::Fatal));
+        assert_eq!(calls, 1);
+    }
+}
@@ -0,0 +1,341 @@
+//! Release summary artifact
+//!
+//! A [`ReleaseSummary`] collects everything a release produced (versions,
+//! changelog, published packages, artifacts and store uploads) into one JSON
+//! document for downstream automation. Crates that own the richer types
+//! (changelog entries, store upload results) convert into the plain records
+//! defined here.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+use crate::error::Result;
+use crate::types::{ReleaseResult, ReleaseType};
+
+/// Summary of a completed (or dry-run) release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReleaseSummary {
+    /// Package name
+    pub package: String,
+    /// New version
+    pub version: String,
+    /// Previous version
+    pub previous_version: Option<String>,
+    /// Release type that was applied
+    pub release_type: ReleaseType,
+    /// Tag for this release
+    pub tag: String,
+    /// Whether this was a dry run
+    pub dry_run: bool,
+    /// When the summary was assembled
+    pub released_at: DateTime<Utc>,
+    /// Structured changelog for this version
+    pub changelog: Option<ChangelogSummary>,
+    /// Packages published to registries
+    pub packages: Vec<PublishedPackage>,
+    /// Built artifacts with checksums
+    pub artifacts: Vec<ArtifactSummary>,
+    /// Software bills of materials generated for the release (also listed
+    /// in `artifacts`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sboms: Vec<ArtifactSummary>,
+    /// App store upload results
+    pub store_uploads: Vec<StoreUploadSummary>,
+    /// Notes and warnings collected during the release
+    pub notes: Vec<String>,
+}
+
+/// Changelog sections for one version
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChangelogSummary {
+    /// Visible sections in display order
+    pub sections: Vec<ChangelogSectionSummary>,
+    /// Breaking changes
+    pub breaking_changes: Vec<ChangelogItem>,
+}
+
+/// One changelog section (e.g. "Features")
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogSectionSummary {
+    /// Section title
+    pub title: String,
+    /// Entries in this section
+    pub items: Vec<ChangelogItem>,
+}
+
+/// One changelog entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChangelogItem {
+    /// Change description
+    pub description: String,
+    /// Commit scope
+    pub scope: Option<String>,
+    /// Commit hash
+    pub hash: String,
+}
+
+/// A package published to a registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublishedPackage {
+    /// Package name
+    pub name: String,
+    /// Published version
+    pub version: String,
+    /// Registry name or URL
+    pub registry: String,
+    /// URL of the published package, if known
+    pub url: Option<String>,
+}
+
+/// A built artifact and its checksum
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ArtifactSummary {
+    /// Artifact path
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Hex-encoded SHA-256 of the contents
+    pub sha256: String,
+}
+
+impl ArtifactSummary {
+    /// Read an artifact and compute its size and checksum
+    ///
+    /// The file is streamed through the hasher, so large artifacts aren't
+    /// held in memory.
+    pub fn from_path(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let mut file = std::fs::File::open(&path)?;
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut file, &mut hasher)?;
+        Ok(Self {
+            size,
+            sha256: format!("{:x}", hasher.finalize()),
+            path,
+        })
+    }
+}
+
+/// Result of uploading a build to an app store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoreUploadSummary {
+    /// Store name (e.g. "app-store-connect", "google-play")
+    pub store: String,
+    /// Whether the upload succeeded
+    pub success: bool,
+    /// Build or submission ID assigned by the store
+    pub build_id: Option<String>,
+    /// Store console URL for the build
+    pub console_url: Option<String>,
+    /// Store processing status
+    pub status: String,
+    /// Warnings reported by the store
+    pub warnings: Vec<String>,
+}
+
+impl ReleaseSummary {
+    /// Start a summary from a release result
+    pub fn from_result(result: &ReleaseResult) -> Self {
+        Self {
+            package: result.package.clone(),
+            version: result.new_version.clone(),
+            previous_version: result.previous_version.clone(),
+            release_type: result.release_type,
+            tag: result.tag.clone(),
+            dry_run: false,
+            released_at: Utc::now(),
+            changelog: None,
+            packages: Vec::new(),
+            artifacts: Vec::new(),
+            sboms: Vec::new(),
+            store_uploads: Vec::new(),
+            notes: result.notes.clone(),
+        }
+    }
+
+    /// Set dry run mode
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set the release timestamp
+    pub fn with_released_at(mut self, released_at: DateTime<Utc>) -> Self {
+        self.released_at = released_at;
+        self
+    }
+
+    /// Set the structured changelog
+    pub fn with_changelog(mut self, changelog: ChangelogSummary) -> Self {
+        self.changelog = Some(changelog);
+        self
+    }
+
+    /// Add a published package
+    pub fn with_package(mut self, package: PublishedPackage) -> Self {
+        self.packages.push(package);
+        self
+    }
+
+    /// Add a built artifact
+    pub fn with_artifact(mut self, artifact: ArtifactSummary) -> Self {
+        self.artifacts.push(artifact);
+        self
+    }
+
+    /// Add a generated SBOM
+    pub fn with_sbom(mut self, sbom: ArtifactSummary) -> Self {
+        self.sboms.push(sbom);
+        self
+    }
+
+    /// Add a store upload result
+    pub fn with_store_upload(mut self, upload: StoreUploadSummary) -> Self {
+        self.store_uploads.push(upload);
+        self
+    }
+
+    /// Serialize to pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Read a summary previously written with [`write_to`](Self::write_to)
+    pub fn read_from(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Write the summary as JSON, creating parent directories as needed
+    pub fn write_to(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_json()?)?;
+        info!(path = %path.display(), version = %self.version, "wrote release summary");
+        debug!(
+            packages = self.packages.len(),
+            artifacts = self.artifacts.len(),
+            store_uploads = self.store_uploads.len(),
+            "release summary contents"
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_summary_serialized_shape() {
+        let temp = TempDir::new().unwrap();
+        let artifact_path = temp.path().join("app-1.2.0.tar.gz");
+        std::fs::write(&artifact_path, b"hello").unwrap();
+
+        let result = ReleaseResult::new("my-app", "1.2.0")
+            .with_previous_version("1.1.0")
+            .with_release_type(ReleaseType::Minor);
+
+        let summary = ReleaseSummary::from_result(&result)
+            .with_released_at(Utc.with_ymd_and_hms(2024, 5, 1, 12, 0, 0).unwrap())
+            .with_changelog(ChangelogSummary {
+                sections: vec![ChangelogSectionSummary {
+                    title: "Features".to_string(),
+                    items: vec![ChangelogItem {
+                        description: "add export".to_string(),
+                        scope: Some("cli".to_string()),
+                        hash: "abc1234".to_string(),
+                    }],
+                }],
+                breaking_changes: Vec::new(),
+            })
+            .with_package(PublishedPackage {
+                name: "my-app".to_string(),
+                version: "1.2.0".to_string(),
+                registry: "npm".to_string(),
+                url: Some("https://www.npmjs.com/package/my-app/v/1.2.0".to_string()),
+            })
+            .with_artifact(ArtifactSummary::from_path(&artifact_path).unwrap())
+            .with_store_upload(StoreUploadSummary {
+                store: "google-play".to_string(),
+                success: true,
+                build_id: Some("42".to_string()),
+                console_url: None,
+                status: "processing".to_string(),
+                warnings: Vec::new(),
+            });
+
+        let path = temp.path().join("out/summary.json");
+        summary.write_to(&path).unwrap();
+        let value: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "package": "my-app",
+                "version": "1.2.0",
+                "previous_version": "1.1.0",
+                "release_type": "minor",
+                "tag": "v1.2.0",
+                "dry_run": false,
+                "released_at": "2024-05-01T12:00:00Z",
+                "changelog": {
+                    "sections": [{
+                        "title": "Features",
+                        "items": [{"description": "add export", "scope": "cli", "hash": "abc1234"}]
+                    }],
+                    "breaking_changes": []
+                },
+                "packages": [{
+                    "name": "my-app",
+                    "version": "1.2.0",
+                    "registry": "npm",
+                    "url": "https://www.npmjs.com/package/my-app/v/1.2.0"
+                }],
+                "artifacts": [{
+                    "path": artifact_path.to_string_lossy(),
+                    "size": 5,
+                    "sha256": "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+                }],
+                "store_uploads": [{
+                    "store": "google-play",
+                    "success": true,
+                    "build_id": "42",
+                    "console_url": null,
+                    "status": "processing",
+                    "warnings": []
+                }],
+                "notes": []
+            })
+        );
+
+        let read = ReleaseSummary::read_from(&path).unwrap();
+        assert_eq!(read.artifacts, summary.artifacts);
+        assert_eq!(read.store_uploads.len(), 1);
+    }
+
+    #[test]
+    fn test_summary_includes_sboms() {
+        let temp = TempDir::new().unwrap();
+        let sbom_path = temp.path().join("my-app-1.2.0.cdx.json");
+        std::fs::write(&sbom_path, b"{}").unwrap();
+
+        let summary = ReleaseSummary::from_result(&ReleaseResult::new("my-app", "1.2.0"))
+            .with_sbom(ArtifactSummary::from_path(&sbom_path).unwrap());
+        let value: serde_json::Value = serde_json::from_str(&summary.to_json().unwrap()).unwrap();
+        assert_eq!(
+            value["sboms"][0]["path"],
+            json!(sbom_path.to_string_lossy())
+        );
+        assert_eq!(value["sboms"][0]["size"], 2);
+    }
+}
@@ -29,6 +29,58 @@ impl ReleaseType {
             Self::Custom => "custom",
         }
     }
+
+    /// Release type required by `commits`, or `None` if no commit requires a release
+    ///
+    /// Major beats minor beats patch. With `pre_1_0`, breaking changes only
+    /// require a minor bump, as 0.x versions make no compatibility promises.
+    pub fn from_parsed_commits<C: ReleaseCommit>(commits: &[C], pre_1_0: bool) -> Option<Self> {
+        commits
+            .iter()
+            .filter_map(|c| c.release_type())
+            .map(|t| t.downgraded(pre_1_0))
+            .max_by_key(|t| t.precedence())
+    }
+
+    /// Commits in `commits` that require this release type
+    pub fn triggered_by<C: ReleaseCommit>(
+        self,
+        commits: &[C],
+        pre_1_0: bool,
+    ) -> impl Iterator<Item = &C> {
+        commits.iter().filter(move |c| {
+            c.release_type()
+                .is_some_and(|t| t.downgraded(pre_1_0) == self)
+        })
+    }
+
+    fn downgraded(self, pre_1_0: bool) -> Self {
+        match self {
+            Self::Major if pre_1_0 => Self::Minor,
+            other => other,
+        }
+    }
+
+    pub(crate) fn precedence(self) -> u8 {
+        match self {
+            Self::Major => 3,
+            Self::Minor => 2,
+            Self::Patch => 1,
+            Self::Prerelease | Self::Custom => 0,
+        }
+    }
+}
+
+/// A commit that may require a release
+///
+/// Implemented by parsed commits (e.g. `canaveral_changelog::ParsedCommit`)
+/// so [`ReleaseType::from_parsed_commits`] can pick the release type.
+pub trait ReleaseCommit {
+    /// Release type this commit requires on its own
+    fn release_type(&self) -> Option<ReleaseType>;
+
+    /// One line identifying the commit, e.g. `a1b2c3d feat(api): add search`
+    fn summary(&self) -> String;
 }
 
 impl std::fmt::Display for ReleaseType {
@@ -63,6 +115,9 @@ pub struct ReleaseResult {
     pub new_version: String,
     /// Release type that was applied
     pub release_type: ReleaseType,
+    /// Commits that required `release_type`, as [`ReleaseCommit::summary`] lines
+    #[serde(default)]
+    pub triggering_commits: Vec<String>,
     /// Tag that was created
     pub tag: String,
     /// Whether the release was published
@@ -85,6 +140,7 @@ impl ReleaseResult {
             previous_version: None,
             new_version,
             release_type: ReleaseType::Patch,
+            triggering_commits: Vec::new(),
             tag,
             published: false,
             changelog: None,
@@ -104,6 +160,20 @@ impl ReleaseResult {
         self
     }
 
+    /// Set the release type and the commits requiring it from `commits`
+    ///
+    /// Leaves the result unchanged when no commit requires a release.
+    pub fn with_commits<C: ReleaseCommit>(mut self, commits: &[C], pre_1_0: bool) -> Self {
+        if let Some(release_type) = ReleaseType::from_parsed_commits(commits, pre_1_0) {
+            self.release_type = release_type;
+            self.triggering_commits = release_type
+                .triggered_by(commits, pre_1_0)
+                .map(ReleaseCommit::summary)
+                .collect();
+        }
+        self
+    }
+
     /// Set the tag
     pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
         self.tag = tag.into();
@@ -196,4 +266,64 @@ mod tests {
         assert!(result.published);
         assert_eq!(result.notes.len(), 1);
     }
+
+    struct Commit(&'static str, Option<ReleaseType>);
+
+    impl ReleaseCommit for Commit {
+        fn release_type(&self) -> Option<ReleaseType> {
+            self.1
+        }
+
+        fn summary(&self) -> String {
+            self.0.to_string()
+        }
+    }
+
+    #[test]
+    fn test_release_type_from_parsed_commits() {
+        let docs = || Commit("docs: typo", None);
+        let fix = || Commit("fix: crash", Some(ReleaseType::Patch));
+        let feat = || Commit("feat: search", Some(ReleaseType::Minor));
+        let breaking = || Commit("feat!: new api", Some(ReleaseType::Major));
+        let from = |commits: &[Commit], pre_1_0| ReleaseType::from_parsed_commits(commits, pre_1_0);
+
+        assert_eq!(from(&[], false), None);
+        assert_eq!(from(&[docs()], false), None);
+        assert_eq!(from(&[docs(), fix()], false), Some(ReleaseType::Patch));
+        assert_eq!(from(&[fix(), feat()], false), Some(ReleaseType::Minor));
+        assert_eq!(
+            from(&[feat(), breaking(), fix()], false),
+            Some(ReleaseType::Major)
+        );
+
+        // Before 1.0, breaking changes are a minor bump
+        assert_eq!(from(&[fix(), breaking()], true), Some(ReleaseType::Minor));
+        assert_eq!(from(&[fix()], true), Some(ReleaseType::Patch));
+    }
+
+    #[test]
+    fn test_release_result_with_commits() {
+        let commits = [
+            Commit("a1b2c3d feat: search", Some(ReleaseType::Minor)),
+            Commit("b2c3d4e fix: crash", Some(ReleaseType::Patch)),
+            Commit("c3d4e5f feat!: new api", Some(ReleaseType::Major)),
+        ];
+
+        let result = ReleaseResult::new("app", "2.0.0").with_commits(&commits, false);
+        assert_eq!(result.release_type, ReleaseType::Major);
+        assert_eq!(result.triggering_commits, ["c3d4e5f feat!: new api"]);
+
+        let result = ReleaseResult::new("app", "0.3.0").with_commits(&commits, true);
+        assert_eq!(result.release_type, ReleaseType::Minor);
+        assert_eq!(
+            result.triggering_commits,
+            ["a1b2c3d feat: search", "c3d4e5f feat!: new api"]
+        );
+
+        let result = ReleaseResult::new("app", "1.0.1")
+            .with_release_type(ReleaseType::Patch)
+            .with_commits(&[Commit("docs: typo", None)], false);
+        assert_eq!(result.release_type, ReleaseType::Patch);
+        assert!(result.triggering_commits.is_empty());
+    }
 }
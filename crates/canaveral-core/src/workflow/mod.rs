@@ -1,12 +1,14 @@
 //! Workflow orchestration for Canaveral
 
 mod changelog;
+mod outcome;
 pub mod pr;
 mod release;
 mod validation;
 mod version;
 
 pub use changelog::*;
+pub use outcome::*;
 pub use pr::*;
 pub use release::*;
 pub use validation::*;
@@ -0,0 +1,239 @@
+//! Whether a release run released anything, and why not
+
+use std::time::Duration;
+
+use serde::{Serialize, Serializer};
+
+use super::release::ReleaseResumption;
+use super::validation::format_elapsed;
+use crate::summary::ReleaseSummary;
+
+/// How a release run ended when it didn't fail
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum ReleaseOutcome {
+    /// A release was made (or would have been, in a dry run)
+    Released(Box<ReleaseSummary>),
+    /// Nothing was released
+    Skipped(SkipReason),
+}
+
+impl ReleaseOutcome {
+    /// Whether nothing was released
+    pub fn is_skipped(&self) -> bool {
+        matches!(self, Self::Skipped(_))
+    }
+}
+
+/// Why a release run made no release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "reason", rename_all = "snake_case")]
+pub enum SkipReason {
+    /// No commits since the last release
+    NoCommits {
+        /// Tag of the last release (`None` before the first release)
+        since_tag: Option<String>,
+    },
+    /// Commits since the last release, but none that bump the version,
+    /// either by type or because they were filtered out
+    NoReleasableCommits {
+        /// Tag of the last release (`None` before the first release)
+        since_tag: Option<String>,
+        /// Commits looked at
+        commits: usize,
+    },
+    /// Fewer releasable commits than `cadence.min_commits`
+    TooFewCommits {
+        /// Releasable commits required
+        min_commits: usize,
+        /// Releasable commits since the last release
+        releasable_commits: usize,
+    },
+    /// Less time since the last release than `cadence.min_interval`
+    CadenceGuard {
+        /// Time required between releases, in seconds
+        #[serde(serialize_with = "as_secs")]
+        min_interval: Duration,
+        /// Time since the last release, in seconds
+        #[serde(serialize_with = "as_secs")]
+        elapsed: Duration,
+    },
+    /// The version is already tagged and published
+    AlreadyReleased {
+        /// Version that was released before
+        version: String,
+        /// Its tag
+        tag: String,
+    },
+    /// The release was declined at the confirmation prompt
+    Declined,
+}
+
+impl SkipReason {
+    /// Reason for a run that found no version bump in `commits` commits
+    /// since `since_tag`
+    pub fn no_bump(since_tag: Option<&str>, commits: usize) -> Self {
+        let since_tag = since_tag.map(str::to_string);
+        if commits == 0 {
+            Self::NoCommits { since_tag }
+        } else {
+            Self::NoReleasableCommits { since_tag, commits }
+        }
+    }
+
+    /// Reason to skip `version` given what earlier runs already did
+    pub fn from_resumption(
+        resumption: &ReleaseResumption,
+        version: &str,
+        tag: &str,
+    ) -> Option<Self> {
+        matches!(resumption, ReleaseResumption::AlreadyReleased).then(|| Self::AlreadyReleased {
+            version: version.to_string(),
+            tag: tag.to_string(),
+        })
+    }
+
+    /// What to do about it, if anything
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            Self::NoCommits { .. } => None,
+            Self::NoReleasableCommits { .. } => {
+                Some("Only feat, fix, perf and breaking commits bump the version; use --release-type to release anyway")
+            }
+            Self::TooFewCommits { .. } | Self::CadenceGuard { .. } => {
+                Some("Use --force to release anyway")
+            }
+            Self::AlreadyReleased { .. } | Self::Declined => None,
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoCommits {
+                since_tag: Some(tag),
+            } => write!(f, "no commits since {}", tag),
+            Self::NoCommits { since_tag: None } => write!(f, "no commits to release"),
+            Self::NoReleasableCommits { since_tag, commits } => {
+                write!(f, "none of the {} commit(s) ", commits)?;
+                if let Some(tag) = since_tag {
+                    write!(f, "since {} ", tag)?;
+                }
+                write!(f, "require a version bump")
+            }
+            Self::TooFewCommits {
+                min_commits,
+                releasable_commits,
+            } => write!(
+                f,
+                "only {} releasable commit(s) since the last release, {} required",
+                releasable_commits, min_commits
+            ),
+            Self::CadenceGuard {
+                min_interval,
+                elapsed,
+            } => write!(
+                f,
+                "last release was {} ago, {} required between releases",
+                format_elapsed(*elapsed),
+                format_elapsed(*min_interval)
+            ),
+            Self::AlreadyReleased { version, tag } => {
+                write!(f, "{} is already tagged as {} and published", version, tag)
+            }
+            Self::Declined => write!(f, "the release was not confirmed"),
+        }
+    }
+}
+
+fn as_secs<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u64(duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::workflow::{cadence_skip_reason, resume_release, CadenceState, ExistingRelease};
+    use chrono::Utc;
+
+    #[test]
+    fn test_skip_scenarios() {
+        assert_eq!(
+            SkipReason::no_bump(Some("v1.2.0"), 0),
+            SkipReason::NoCommits {
+                since_tag: Some("v1.2.0".to_string())
+            }
+        );
+        // Commits that were all filtered out or don't bump the version
+        let filtered = SkipReason::no_bump(Some("v1.2.0"), 3);
+        assert_eq!(
+            filtered.to_string(),
+            "none of the 3 commit(s) since v1.2.0 require a version bump"
+        );
+
+        let now = Utc::now();
+        let mut config = Config::default();
+        config.cadence.min_interval = Some("7d".to_string());
+        let state = CadenceState {
+            last_release: Some(now - chrono::Duration::days(2)),
+            releasable_commits: 4,
+        };
+        assert_eq!(
+            cadence_skip_reason(&config, &state, now).unwrap(),
+            Some(SkipReason::CadenceGuard {
+                min_interval: Duration::from_secs(7 * 86_400),
+                elapsed: Duration::from_secs(2 * 86_400),
+            })
+        );
+        config.cadence.min_commits = Some(5);
+        assert_eq!(
+            cadence_skip_reason(&config, &state, now).unwrap(),
+            Some(SkipReason::TooFewCommits {
+                min_commits: 5,
+                releasable_commits: 4,
+            })
+        );
+        assert_eq!(
+            cadence_skip_reason(&Config::default(), &state, now).unwrap(),
+            None
+        );
+
+        let released = resume_release(&ExistingRelease::new(true).with_registry("npm", true));
+        assert_eq!(
+            SkipReason::from_resumption(&released, "1.3.0", "v1.3.0"),
+            Some(SkipReason::AlreadyReleased {
+                version: "1.3.0".to_string(),
+                tag: "v1.3.0".to_string(),
+            })
+        );
+        let partial = resume_release(&ExistingRelease::new(true).with_registry("npm", false));
+        assert_eq!(
+            SkipReason::from_resumption(&partial, "1.3.0", "v1.3.0"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_skipped_json() {
+        let outcome = ReleaseOutcome::Skipped(SkipReason::CadenceGuard {
+            min_interval: Duration::from_secs(3600),
+            elapsed: Duration::from_secs(600),
+        });
+        assert!(outcome.is_skipped());
+        assert_eq!(
+            serde_json::to_value(&outcome).unwrap(),
+            serde_json::json!({
+                "status": "skipped",
+                "reason": "cadence_guard",
+                "min_interval": 3600,
+                "elapsed": 600,
+            })
+        );
+        assert_eq!(
+            SkipReason::NoCommits { since_tag: None }.to_string(),
+            "no commits to release"
+        );
+    }
+}
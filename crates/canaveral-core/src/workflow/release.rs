@@ -1,11 +1,228 @@
 //! Release workflow orchestration
 
-use tracing::{debug, info};
+use std::path::{Path, PathBuf};
 
+use tracing::{debug, info, warn};
+
+use super::validation::{
+    validate_config_for_release, PreflightCheck, ValidationIssue, ValidationSummary,
+};
+use crate::config::validation::validate_config;
 use crate::config::Config;
 use crate::error::Result;
+use crate::hooks::{Hook, HookContext, HookRunner, HookStage};
+use crate::notify::{Notifier, ReleaseOutcome};
+use crate::summary::{ArtifactSummary, ReleaseSummary};
 use crate::types::{ReleaseResult, ReleaseType};
 
+/// Trailer canaveral adds to the release commits it creates
+///
+/// Commit parsers recognize it and leave release commits out of the next
+/// changelog and version bump.
+pub const RELEASE_COMMIT_TRAILER: &str = "Release-Tool: canaveral";
+
+/// Render the release commit message for `package` at `version`, ending
+/// with [`RELEASE_COMMIT_TRAILER`]
+pub fn release_commit_message(template: &str, package: &str, version: &str) -> String {
+    ReleaseCommitMessage::new(version)
+        .with_package(package, version)
+        .render(template)
+}
+
+/// Values for the release commit message template
+///
+/// Templates can use `{version}`, `{date}` (`YYYY-MM-DD`) and `{packages}`,
+/// a comma-separated list of `name@version`. A `{#packages}...{/packages}`
+/// block is repeated for every package, with `{name}` and `{version}`
+/// standing for that package inside it.
+#[derive(Debug, Clone)]
+pub struct ReleaseCommitMessage {
+    version: String,
+    packages: Vec<(String, String)>,
+    date: chrono::NaiveDate,
+}
+
+impl ReleaseCommitMessage {
+    /// Message for releasing `version`, dated today
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            packages: Vec::new(),
+            date: chrono::Utc::now().date_naive(),
+        }
+    }
+
+    /// Message for releasing several packages at once
+    ///
+    /// `{version}` is the highest of their versions.
+    pub fn for_packages<N, V>(packages: impl IntoIterator<Item = (N, V)>) -> Self
+    where
+        N: Into<String>,
+        V: Into<String>,
+    {
+        let packages: Vec<(String, String)> = packages
+            .into_iter()
+            .map(|(name, version)| (name.into(), version.into()))
+            .collect();
+        let version = packages
+            .iter()
+            .map(|(_, version)| version)
+            .max_by(
+                |a, b| match (semver::Version::parse(a), semver::Version::parse(b)) {
+                    (Ok(a), Ok(b)) => a.cmp(&b),
+                    _ => a.cmp(b),
+                },
+            )
+            .cloned()
+            .unwrap_or_default();
+        Self {
+            packages,
+            ..Self::new(version)
+        }
+    }
+
+    /// Add a released package
+    pub fn with_package(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.packages.push((name.into(), version.into()));
+        self
+    }
+
+    /// Set the release date
+    pub fn with_date(mut self, date: chrono::NaiveDate) -> Self {
+        self.date = date;
+        self
+    }
+
+    /// Render `template`, ending the message with [`RELEASE_COMMIT_TRAILER`]
+    /// so commit parsers leave the release commit out of the next release
+    pub fn render(&self, template: &str) -> String {
+        const OPEN: &str = "{#packages}";
+        const CLOSE: &str = "{/packages}";
+
+        let mut message = String::new();
+        let mut rest = template;
+        while let Some(start) = rest.find(OPEN) {
+            message.push_str(&self.fill(&rest[..start]));
+            let block = &rest[start + OPEN.len()..];
+            let (body, after) = block.split_once(CLOSE).unwrap_or((block, ""));
+            for (name, version) in &self.packages {
+                message.push_str(&body.replace("{name}", name).replace("{version}", version));
+            }
+            rest = after;
+        }
+        message.push_str(&self.fill(rest));
+
+        format!("{}\n\n{}", message.trim_end(), RELEASE_COMMIT_TRAILER)
+    }
+
+    fn fill(&self, text: &str) -> String {
+        let packages: Vec<String> = self
+            .packages
+            .iter()
+            .map(|(name, version)| format!("{}@{}", name, version))
+            .collect();
+        text.replace("{version}", &self.version)
+            .replace("{date}", &self.date.format("%Y-%m-%d").to_string())
+            .replace("{packages}", &packages.join(", "))
+    }
+}
+
+/// What earlier runs already did for a release version
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExistingRelease {
+    /// Whether the release tag exists
+    pub tagged: bool,
+    /// Whether the version is on each registry checked, by registry name
+    ///
+    /// Registries that couldn't be checked are left out.
+    pub registries: Vec<(String, bool)>,
+}
+
+impl ExistingRelease {
+    /// State of a release whose tag does or doesn't exist yet
+    pub fn new(tagged: bool) -> Self {
+        Self {
+            tagged,
+            registries: Vec::new(),
+        }
+    }
+
+    /// Record whether the version is published to `registry`
+    pub fn with_registry(mut self, registry: impl Into<String>, published: bool) -> Self {
+        self.registries.push((registry.into(), published));
+        self
+    }
+}
+
+/// A release step that may be left over from an earlier run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseStep {
+    /// Commit the release and create its tag
+    Tag,
+    /// Publish to the named registry
+    Publish(String),
+}
+
+impl std::fmt::Display for ReleaseStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tag => write!(f, "tag"),
+            Self::Publish(registry) => write!(f, "publish to {}", registry),
+        }
+    }
+}
+
+/// How to run a release given what already exists for its version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReleaseResumption {
+    /// Nothing exists yet, run every step
+    Fresh,
+    /// Tagged and published everywhere, nothing to do
+    AlreadyReleased,
+    /// Partly released, run only the missing steps
+    Resume(Vec<ReleaseStep>),
+}
+
+impl ReleaseResumption {
+    /// Whether `step` still has to run
+    pub fn runs(&self, step: &ReleaseStep) -> bool {
+        match self {
+            Self::Fresh => true,
+            Self::AlreadyReleased => false,
+            Self::Resume(steps) => steps.contains(step),
+        }
+    }
+}
+
+/// Decide which steps of a release are still missing
+///
+/// Re-running a release is safe: a version that is tagged and on every
+/// checked registry is already released, and one with only some of that
+/// done resumes with the rest.
+pub fn resume_release(existing: &ExistingRelease) -> ReleaseResumption {
+    let missing: Vec<ReleaseStep> = (!existing.tagged)
+        .then_some(ReleaseStep::Tag)
+        .into_iter()
+        .chain(
+            existing
+                .registries
+                .iter()
+                .filter(|(_, published)| !published)
+                .map(|(registry, _)| ReleaseStep::Publish(registry.clone())),
+        )
+        .collect();
+
+    let resumption = if missing.is_empty() {
+        ReleaseResumption::AlreadyReleased
+    } else if !existing.tagged && existing.registries.iter().all(|(_, published)| !published) {
+        ReleaseResumption::Fresh
+    } else {
+        ReleaseResumption::Resume(missing)
+    };
+    debug!(?existing, ?resumption, "checked for an earlier release");
+    resumption
+}
+
 /// Options for a release
 #[derive(Debug, Clone, Default)]
 pub struct ReleaseOptions {
@@ -55,12 +272,129 @@ impl ReleaseOptions {
 pub struct ReleaseWorkflow<'a> {
     config: &'a Config,
     options: ReleaseOptions,
+    release_notes_path: Option<PathBuf>,
+    artifacts: Vec<PathBuf>,
+    checks: Vec<Box<dyn PreflightCheck + 'a>>,
+    notifiers: Vec<Box<dyn Notifier + 'a>>,
 }
 
 impl<'a> ReleaseWorkflow<'a> {
     /// Create a new release workflow
     pub fn new(config: &'a Config, options: ReleaseOptions) -> Self {
-        Self { config, options }
+        Self {
+            config,
+            options,
+            release_notes_path: None,
+            artifacts: Vec::new(),
+            checks: Vec::new(),
+            notifiers: Vec::new(),
+        }
+    }
+
+    /// Set the generated release notes file
+    pub fn with_release_notes_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.release_notes_path = Some(path.into());
+        self
+    }
+
+    /// Set the artifacts built for this release
+    pub fn with_artifacts(mut self, artifacts: Vec<PathBuf>) -> Self {
+        self.artifacts = artifacts;
+        self
+    }
+
+    /// Add a pre-flight check run by [`validate`](Self::validate)
+    pub fn with_check(mut self, check: impl PreflightCheck + 'a) -> Self {
+        self.checks.push(Box::new(check));
+        self
+    }
+
+    /// Add a notifier called by [`notify`](Self::notify)
+    pub fn with_notifier(mut self, notifier: impl Notifier + 'a) -> Self {
+        self.notifiers.push(Box::new(notifier));
+        self
+    }
+
+    /// Add several notifiers called by [`notify`](Self::notify)
+    pub fn with_notifiers(
+        mut self,
+        notifiers: impl IntoIterator<Item = Box<dyn Notifier + 'a>>,
+    ) -> Self {
+        self.notifiers.extend(notifiers);
+        self
+    }
+
+    /// Tell the notifiers how the release ended
+    ///
+    /// Dry runs send nothing, and `on_success`/`on_failure` in the
+    /// notifications configuration pick which outcomes are sent. A notifier
+    /// that fails only logs a warning, so notifications never fail a
+    /// release. Returns the number of notifications sent.
+    pub fn notify(&self, summary: &ReleaseSummary, outcome: &ReleaseOutcome) -> usize {
+        let config = &self.config.notifications;
+        let wanted = if outcome.is_success() {
+            config.on_success
+        } else {
+            config.on_failure
+        };
+        if self.is_dry_run() || !wanted {
+            debug!(
+                success = outcome.is_success(),
+                "skipping release notifications"
+            );
+            return 0;
+        }
+
+        let mut sent = 0;
+        for notifier in &self.notifiers {
+            match notifier.notify(summary, outcome) {
+                Ok(()) => sent += 1,
+                Err(e) => {
+                    warn!(notifier = notifier.name(), error = %e, "release notification failed")
+                }
+            }
+        }
+        info!(
+            sent,
+            success = outcome.is_success(),
+            "release notifications sent"
+        );
+        sent
+    }
+
+    /// Run every pre-flight check without changing anything
+    ///
+    /// The configuration is always checked first, followed by the checks
+    /// added with [`with_check`](Self::with_check). Every check runs even
+    /// after one fails, so the summary lists all issues at once.
+    pub fn validate(&self) -> ValidationSummary {
+        info!(
+            checks = self.checks.len() + 1,
+            "running pre-flight validation"
+        );
+        let mut summary = ValidationSummary::default();
+        let config_check = ConfigCheck;
+        let checks = std::iter::once(&config_check as &dyn PreflightCheck).chain(
+            self.checks
+                .iter()
+                .map(|c| c.as_ref() as &dyn PreflightCheck),
+        );
+        for check in checks {
+            let issues = check.run(self.config).unwrap_or_else(|e| {
+                warn!(check = check.name(), error = %e, "pre-flight check failed to run");
+                vec![ValidationIssue::error(format!(
+                    "Check failed to run: {}",
+                    e
+                ))]
+            });
+            debug!(
+                check = check.name(),
+                issues = issues.len(),
+                "pre-flight check done"
+            );
+            summary.add(check.name(), issues);
+        }
+        summary
     }
 
     /// Execute the release
@@ -98,6 +432,109 @@ impl<'a> ReleaseWorkflow<'a> {
     pub fn is_dry_run(&self) -> bool {
         self.options.dry_run || self.config.publish.dry_run
     }
+
+    /// Context for hooks run at `stage` of this release
+    ///
+    /// The changelog and release notes paths are set from `post-changelog`
+    /// onwards, and artifacts from `pre-publish` onwards, so hooks only see
+    /// files that exist by the time they run.
+    pub fn hook_context(&self, stage: HookStage, result: &ReleaseResult) -> HookContext {
+        let mut context = HookContext::new()
+            .with_version(&result.new_version)
+            .with_package_name(&result.package)
+            .with_release_type(result.release_type.to_string())
+            .with_tag(&result.tag)
+            .with_dry_run(self.is_dry_run());
+        if let Some(previous) = &result.previous_version {
+            context = context.with_previous_version(previous);
+        }
+
+        if stage_reached(stage, HookStage::PostChangelog) {
+            if self.config.changelog.enabled && !self.options.skip_changelog {
+                context = context.with_changelog_path(&self.config.changelog.file);
+            }
+            if let Some(path) = &self.release_notes_path {
+                context = context.with_release_notes_path(path);
+            }
+        }
+        if stage_reached(stage, HookStage::PrePublish) {
+            context = context.with_artifacts(self.artifacts.iter().cloned());
+        }
+
+        context
+    }
+
+    /// Run the hooks configured for `stage` in `dir`
+    ///
+    /// Hooks get the stage's [`hook_context`](Self::hook_context) as
+    /// `CANAVERAL_*` environment variables. Dry runs run no hooks. Returns
+    /// the number of hooks run.
+    pub fn run_hooks(&self, stage: HookStage, result: &ReleaseResult, dir: &Path) -> Result<usize> {
+        self.run_hooks_with_artifacts(stage, result, dir, &[])
+    }
+
+    /// Run the hooks configured for `stage` like [`run_hooks`](Self::run_hooks),
+    /// with `artifacts` listed after the workflow's own
+    pub fn run_hooks_with_artifacts(
+        &self,
+        stage: HookStage,
+        result: &ReleaseResult,
+        dir: &Path,
+        artifacts: &[PathBuf],
+    ) -> Result<usize> {
+        let commands = self.config.hooks.commands(stage);
+        if commands.is_empty() {
+            return Ok(0);
+        }
+        if self.is_dry_run() {
+            debug!(stage = stage.as_str(), "dry run, skipping hooks");
+            return Ok(0);
+        }
+        let mut runner = HookRunner::new().with_base_dir(dir.to_string_lossy());
+        runner.register_all(stage, commands.iter().map(Hook::new).collect());
+        let context = self
+            .hook_context(stage, result)
+            .with_artifacts(artifacts.iter().cloned());
+        Ok(runner.run(stage, &context)?.len())
+    }
+
+    /// Start a release summary for `result`, with checksums for the
+    /// workflow's artifacts
+    ///
+    /// Callers add the changelog, published packages and store uploads as
+    /// those steps complete.
+    pub fn summary(&self, result: &ReleaseResult) -> Result<ReleaseSummary> {
+        let mut summary = ReleaseSummary::from_result(result).with_dry_run(self.is_dry_run());
+        for artifact in &self.artifacts {
+            summary = summary.with_artifact(ArtifactSummary::from_path(artifact)?);
+        }
+        Ok(summary)
+    }
+}
+
+/// Configuration errors block, disabled release steps only warn
+struct ConfigCheck;
+
+impl PreflightCheck for ConfigCheck {
+    fn name(&self) -> &str {
+        "config"
+    }
+
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        let mut issues = Vec::new();
+        if let Err(e) = validate_config(config) {
+            issues.push(ValidationIssue::error(e.to_string()));
+        }
+        issues.extend(ValidationIssue::from_result(&validate_config_for_release(
+            config,
+        )?));
+        Ok(issues)
+    }
+}
+
+/// Whether `stage` runs at or after `milestone` in the release lifecycle
+fn stage_reached(stage: HookStage, milestone: HookStage) -> bool {
+    stage.index() >= milestone.index()
 }
 
 #[cfg(test)]
@@ -121,4 +558,321 @@ mod tests {
         let result = workflow.execute().unwrap();
         assert_eq!(result.new_version, "1.0.0");
     }
+
+    #[test]
+    fn test_release_commit_message() {
+        assert_eq!(
+            release_commit_message("chore(release): {version}", "demo", "1.2.0"),
+            "chore(release): 1.2.0\n\nRelease-Tool: canaveral"
+        );
+        assert_eq!(
+            release_commit_message("chore(release): {packages}", "demo", "1.2.0"),
+            "chore(release): demo@1.2.0\n\nRelease-Tool: canaveral"
+        );
+
+        let date = chrono::NaiveDate::from_ymd_opt(2026, 3, 14).unwrap();
+        assert_eq!(
+            ReleaseCommitMessage::new("1.2.0")
+                .with_date(date)
+                .render("chore(release): v{version} [skip ci]\n\nReleased {date}"),
+            "chore(release): v1.2.0 [skip ci]\n\nReleased 2026-03-14\n\nRelease-Tool: canaveral"
+        );
+    }
+
+    #[test]
+    fn test_resume_release() {
+        assert_eq!(
+            resume_release(&ExistingRelease::new(false).with_registry("npm", false)),
+            ReleaseResumption::Fresh
+        );
+
+        // Tagged and published: re-running is a no-op
+        let released = resume_release(&ExistingRelease::new(true).with_registry("npm", true));
+        assert_eq!(released, ReleaseResumption::AlreadyReleased);
+        assert!(!released.runs(&ReleaseStep::Tag));
+        assert_eq!(
+            resume_release(&ExistingRelease::new(true)),
+            ReleaseResumption::AlreadyReleased
+        );
+    }
+
+    #[test]
+    fn test_resume_partial_release() {
+        // Tagged, but publishing failed: only publish again
+        let resumption = resume_release(
+            &ExistingRelease::new(true)
+                .with_registry("crates.io", true)
+                .with_registry("npm", false),
+        );
+        assert_eq!(
+            resumption,
+            ReleaseResumption::Resume(vec![ReleaseStep::Publish("npm".to_string())])
+        );
+        assert!(resumption.runs(&ReleaseStep::Publish("npm".to_string())));
+        assert!(!resumption.runs(&ReleaseStep::Publish("crates.io".to_string())));
+        assert!(!resumption.runs(&ReleaseStep::Tag));
+
+        // Published, but the tag was never created
+        assert_eq!(
+            resume_release(&ExistingRelease::new(false).with_registry("npm", true)),
+            ReleaseResumption::Resume(vec![ReleaseStep::Tag])
+        );
+    }
+
+    #[test]
+    fn test_release_commit_message_for_packages() {
+        let message =
+            ReleaseCommitMessage::for_packages([("@acme/core", "2.10.0"), ("@acme/cli", "2.9.1")])
+                .with_date(chrono::NaiveDate::from_ymd_opt(2026, 3, 14).unwrap());
+
+        assert_eq!(
+            message.render("chore(release): {packages}"),
+            "chore(release): @acme/core@2.10.0, @acme/cli@2.9.1\n\nRelease-Tool: canaveral"
+        );
+        assert_eq!(
+            message.render(
+                "chore(release): v{version} on {date} [skip ci]\n\n{#packages}- {name} {version}\n{/packages}"
+            ),
+            "chore(release): v2.10.0 on 2026-03-14 [skip ci]\n\n- @acme/core 2.10.0\n- @acme/cli 2.9.1\n\nRelease-Tool: canaveral"
+        );
+    }
+
+    #[test]
+    fn test_summary_includes_artifact_checksums() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let artifact = temp.path().join("app.tar.gz");
+        std::fs::write(&artifact, b"artifact").unwrap();
+
+        let config = Config::default();
+        let workflow =
+            ReleaseWorkflow::new(&config, ReleaseOptions::dry_run().with_version("2.0.0"))
+                .with_artifacts(vec![artifact.clone()]);
+        let result = workflow.execute().unwrap();
+        let summary = workflow.summary(&result).unwrap();
+
+        assert_eq!(summary.version, "2.0.0");
+        assert!(summary.dry_run);
+        assert_eq!(summary.artifacts.len(), 1);
+        assert_eq!(summary.artifacts[0].path, artifact);
+        assert_eq!(summary.artifacts[0].size, 8);
+
+        let missing = ReleaseWorkflow::new(&config, ReleaseOptions::default())
+            .with_artifacts(vec![temp.path().join("missing.zip")]);
+        assert!(missing.summary(&result).is_err());
+    }
+
+    struct StubCheck {
+        name: &'static str,
+        issues: Vec<ValidationIssue>,
+    }
+
+    impl PreflightCheck for StubCheck {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn run(&self, _config: &Config) -> Result<Vec<ValidationIssue>> {
+            Ok(self.issues.clone())
+        }
+    }
+
+    #[test]
+    fn test_validate_aggregates_checks() {
+        let config = Config::default();
+        let passing_adapter = StubCheck {
+            name: "packages",
+            issues: Vec::new(),
+        };
+        let failing_metadata = StubCheck {
+            name: "metadata",
+            issues: vec![
+                ValidationIssue::error("Name exceeds 30 characters")
+                    .with_target("apple/com.example.app"),
+                ValidationIssue::warning("No screenshots").with_target("apple/com.example.app"),
+            ],
+        };
+
+        let workflow = ReleaseWorkflow::new(&config, ReleaseOptions::default())
+            .with_check(passing_adapter)
+            .with_check(failing_metadata);
+        let summary = workflow.validate();
+
+        assert_eq!(summary.checks, ["config", "packages", "metadata"]);
+        assert!(!summary.passed());
+        assert_eq!(summary.exit_code(), 5);
+        let errors: Vec<_> = summary.errors().collect();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].check, "metadata");
+        assert_eq!(errors[0].target.as_deref(), Some("apple/com.example.app"));
+        assert_eq!(summary.warnings().count(), 1);
+
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["issues"][0]["severity"], "error");
+
+        // Only the adapter: passes, but strict mode blocks on warnings
+        let workflow =
+            ReleaseWorkflow::new(&config, ReleaseOptions::default()).with_check(StubCheck {
+                name: "packages",
+                issues: vec![ValidationIssue::warning("README missing").with_target("web")],
+            });
+        let summary = workflow.validate();
+        assert!(summary.passed());
+        assert_eq!(summary.exit_code(), 0);
+        assert!(!summary.into_strict().passed());
+    }
+
+    struct StubNotifier {
+        fail: bool,
+        sent: std::sync::Mutex<Vec<ReleaseOutcome>>,
+    }
+
+    impl Notifier for &StubNotifier {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        fn notify(&self, _summary: &ReleaseSummary, outcome: &ReleaseOutcome) -> Result<()> {
+            if self.fail {
+                return Err(crate::error::CanaveralError::other("unreachable"));
+            }
+            self.sent.lock().unwrap().push(outcome.clone());
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_notify_is_best_effort() {
+        let config = Config::default();
+        let failing = StubNotifier {
+            fail: true,
+            sent: Default::default(),
+        };
+        let recording = StubNotifier {
+            fail: false,
+            sent: Default::default(),
+        };
+        let result = ReleaseResult::new("my-app", "1.0.0");
+
+        let workflow = ReleaseWorkflow::new(&config, ReleaseOptions::default())
+            .with_notifier(&failing)
+            .with_notifier(&recording);
+        let summary = workflow.summary(&result).unwrap();
+        let failure = ReleaseOutcome::Failure("publish failed".to_string());
+        assert_eq!(workflow.notify(&summary, &ReleaseOutcome::Success), 1);
+        assert_eq!(workflow.notify(&summary, &failure), 1);
+        assert_eq!(
+            *recording.sent.lock().unwrap(),
+            [ReleaseOutcome::Success, failure.clone()]
+        );
+
+        let workflow =
+            ReleaseWorkflow::new(&config, ReleaseOptions::dry_run()).with_notifier(&recording);
+        assert_eq!(workflow.notify(&summary, &ReleaseOutcome::Success), 0);
+
+        let mut success_only = Config::default();
+        success_only.notifications.on_failure = false;
+        let workflow = ReleaseWorkflow::new(&success_only, ReleaseOptions::default())
+            .with_notifier(&recording);
+        assert_eq!(workflow.notify(&summary, &failure), 0);
+        assert_eq!(recording.sent.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_hook_context_paths_by_stage() {
+        let config = Config::default();
+        let workflow =
+            ReleaseWorkflow::new(&config, ReleaseOptions::default().with_version("1.0.0"))
+                .with_release_notes_path("target/notes.md")
+                .with_artifacts(vec![PathBuf::from("dist/app.tar.gz")]);
+        let result = workflow.execute().unwrap();
+
+        let pre = workflow.hook_context(HookStage::PreChangelog, &result);
+        assert_eq!(pre.version.as_deref(), Some("1.0.0"));
+        assert!(pre.changelog_path.is_none());
+        assert!(pre.artifacts.is_empty());
+
+        let post = workflow.hook_context(HookStage::PostChangelog, &result);
+        assert_eq!(post.changelog_path, Some(config.changelog.file.clone()));
+        assert_eq!(
+            post.release_notes_path,
+            Some(PathBuf::from("target/notes.md"))
+        );
+        assert!(post.artifacts.is_empty());
+
+        let publish = workflow.hook_context(HookStage::PostPublish, &result);
+        assert_eq!(publish.artifacts, vec![PathBuf::from("dist/app.tar.gz")]);
+
+        let skipped = ReleaseWorkflow::new(
+            &config,
+            ReleaseOptions {
+                skip_changelog: true,
+                ..Default::default()
+            },
+        );
+        let context = skipped.hook_context(HookStage::PostRelease, &result);
+        assert!(context.changelog_path.is_none());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_hooks_with_context() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let mut config = Config::default();
+        config.hooks.post_release =
+            vec![r#"echo "$CANAVERAL_VERSION $CANAVERAL_ARTIFACTS" > hook.txt"#.to_string()];
+        let workflow =
+            ReleaseWorkflow::new(&config, ReleaseOptions::default().with_version("1.0.0"))
+                .with_artifacts(vec![PathBuf::from("dist/app.tar.gz")]);
+        let result = workflow.execute().unwrap();
+
+        assert_eq!(
+            workflow
+                .run_hooks(HookStage::PreRelease, &result, temp.path())
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            workflow
+                .run_hooks(HookStage::PostRelease, &result, temp.path())
+                .unwrap(),
+            1
+        );
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("hook.txt")).unwrap(),
+            "1.0.0 dist/app.tar.gz\n"
+        );
+
+        // Published files are listed after the workflow's artifacts
+        let mut config = Config::default();
+        config.hooks.post_publish = vec![r#"echo "$CANAVERAL_ARTIFACTS" > hook.txt"#.to_string()];
+        let workflow = ReleaseWorkflow::new(&config, ReleaseOptions::default())
+            .with_artifacts(vec![PathBuf::from("dist/app.tar.gz")]);
+        let published = [PathBuf::from("pkg/index.js")];
+        workflow
+            .run_hooks_with_artifacts(HookStage::PostPublish, &result, temp.path(), &published)
+            .unwrap();
+        let listed = std::fs::read_to_string(temp.path().join("hook.txt")).unwrap();
+        assert_eq!(
+            std::env::split_paths(listed.trim_end()).collect::<Vec<_>>(),
+            [
+                PathBuf::from("dist/app.tar.gz"),
+                PathBuf::from("pkg/index.js")
+            ]
+        );
+
+        let mut failing = Config::default();
+        failing.hooks.post_release = vec!["exit 3".to_string()];
+        let workflow =
+            ReleaseWorkflow::new(&failing, ReleaseOptions::default().with_version("1.0.0"));
+        assert!(workflow
+            .run_hooks(HookStage::PostRelease, &result, temp.path())
+            .is_err());
+        let dry_run = ReleaseWorkflow::new(&failing, ReleaseOptions::dry_run());
+        assert_eq!(
+            dry_run
+                .run_hooks(HookStage::PostRelease, &result, temp.path())
+                .unwrap(),
+            0
+        );
+    }
 }
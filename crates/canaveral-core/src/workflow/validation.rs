@@ -1,9 +1,16 @@
 //! Pre-release validation
 
+use chrono::{DateTime, Utc};
+use glob::{MatchOptions, Pattern};
+use serde::Serialize;
 use tracing::{debug, info, warn};
 
 use crate::config::Config;
-use crate::error::{Result, WorkflowError};
+use crate::error::{ErrorCode, Result, WorkflowError};
+use crate::workflow::SkipReason;
+
+/// Most uncommitted changes listed in a dirty working tree error
+const MAX_LISTED_CHANGES: usize = 20;
 
 /// Validation result
 #[derive(Debug, Clone)]
@@ -47,6 +54,150 @@ impl ValidationResult {
     }
 }
 
+/// Severity of a pre-flight issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum IssueSeverity {
+    /// Worth knowing, never blocks
+    Info,
+    /// Should be fixed, but the release can proceed
+    Warning,
+    /// Blocks the release
+    Error,
+}
+
+impl std::fmt::Display for IssueSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Info => write!(f, "info"),
+            Self::Warning => write!(f, "warning"),
+            Self::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// An issue found by a [`PreflightCheck`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ValidationIssue {
+    /// Name of the check that reported it; filled in by [`ReleaseWorkflow::validate`](super::ReleaseWorkflow::validate)
+    pub check: String,
+    /// Package or platform the issue is about
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    /// How serious the issue is
+    pub severity: IssueSeverity,
+    /// What is wrong
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Create an issue
+    pub fn new(severity: IssueSeverity, message: impl Into<String>) -> Self {
+        Self {
+            check: String::new(),
+            target: None,
+            severity,
+            message: message.into(),
+        }
+    }
+
+    /// Create a blocking issue
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Error, message)
+    }
+
+    /// Create a warning
+    pub fn warning(message: impl Into<String>) -> Self {
+        Self::new(IssueSeverity::Warning, message)
+    }
+
+    /// Set the package or platform the issue is about
+    pub fn with_target(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+
+    /// Issues for the errors and warnings of a [`ValidationResult`]
+    pub fn from_result(result: &ValidationResult) -> Vec<Self> {
+        let errors = result.errors.iter().map(Self::error);
+        errors
+            .chain(result.warnings.iter().map(Self::warning))
+            .collect()
+    }
+}
+
+/// A read-only check run by [`ReleaseWorkflow::validate`](super::ReleaseWorkflow::validate)
+///
+/// Checks must not change the repository, manifests or remote state.
+pub trait PreflightCheck {
+    /// Short name shown in the report (`packages`, `metadata`, ...)
+    fn name(&self) -> &str;
+
+    /// Run the check and report what it found
+    ///
+    /// An `Err` means the check itself could not run and is reported as a
+    /// blocking issue.
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>>;
+}
+
+/// Aggregated outcome of all pre-flight checks
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ValidationSummary {
+    /// Names of the checks that ran, in order
+    pub checks: Vec<String>,
+    /// Issues from all checks, in the order they were found
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationSummary {
+    /// Record the issues reported by `check`
+    pub fn add(&mut self, check: &str, issues: impl IntoIterator<Item = ValidationIssue>) {
+        self.checks.push(check.to_string());
+        self.issues.extend(issues.into_iter().map(|mut issue| {
+            issue.check = check.to_string();
+            issue
+        }));
+    }
+
+    /// Whether no issue blocks the release
+    pub fn passed(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Blocking issues
+    pub fn errors(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(IssueSeverity::Error)
+    }
+
+    /// Non-blocking warnings
+    pub fn warnings(&self) -> impl Iterator<Item = &ValidationIssue> {
+        self.with_severity(IssueSeverity::Warning)
+    }
+
+    fn with_severity(&self, severity: IssueSeverity) -> impl Iterator<Item = &ValidationIssue> {
+        self.issues.iter().filter(move |i| i.severity == severity)
+    }
+
+    /// Treat warnings as blocking
+    pub fn into_strict(mut self) -> Self {
+        for issue in &mut self.issues {
+            if issue.severity == IssueSeverity::Warning {
+                issue.severity = IssueSeverity::Error;
+            }
+        }
+        self
+    }
+
+    /// Process exit code: 0 when passed, the validation exit code otherwise
+    pub fn exit_code(&self) -> i32 {
+        if self.passed() {
+            0
+        } else {
+            ErrorCode::Validation.exit_code()
+        }
+    }
+}
+
 /// Validate that the release can proceed
 pub fn validate_release(
     config: &Config,
@@ -79,6 +230,196 @@ pub fn validate_release(
     Ok(result)
 }
 
+/// Repository state checked before a release
+#[derive(Debug, Clone, Default)]
+pub struct RepoState {
+    /// Current branch (`None` for a detached HEAD)
+    pub branch: Option<String>,
+    /// Uncommitted changes as short status lines
+    pub changes: Vec<String>,
+}
+
+/// Branch patterns releases may run from
+///
+/// `git.release_branches` when set, otherwise just `git.branch`.
+pub fn release_branch_patterns(config: &Config) -> Vec<String> {
+    if config.git.release_branches.is_empty() {
+        vec![config.git.branch.clone()]
+    } else {
+        config.git.release_branches.clone()
+    }
+}
+
+/// Whether `branch` matches one of the allowed release branch patterns
+///
+/// `*` stops at `/`, so `release/*` allows `release/1.x` but not
+/// `release/1.x/hotfix`; use `release/**` for nested branches.
+pub fn is_release_branch(config: &Config, branch: &str) -> bool {
+    let options = MatchOptions {
+        require_literal_separator: true,
+        ..MatchOptions::new()
+    };
+    release_branch_patterns(config)
+        .iter()
+        .any(|pattern| match Pattern::new(pattern) {
+            Ok(p) => p.matches_with(branch, options),
+            Err(e) => {
+                warn!(pattern = %pattern, error = %e, "ignoring invalid release branch pattern");
+                false
+            }
+        })
+}
+
+/// Pre-flight checks before a release: the branch must be an allowed release
+/// branch (unless `allow_branch`), and the working tree must be clean when
+/// `git.require_clean` is set
+///
+/// A detached HEAD (common in CI tag builds) only produces a warning.
+pub fn preflight_release(
+    config: &Config,
+    state: &RepoState,
+    allow_branch: bool,
+) -> Result<ValidationResult> {
+    info!(branch = ?state.branch, changes = state.changes.len(), allow_branch, "running release pre-flight checks");
+    let mut result = ValidationResult::pass();
+
+    match &state.branch {
+        Some(branch) if !is_release_branch(config, branch) => {
+            if allow_branch {
+                warn!(branch = %branch, "releasing from a non-release branch (allowed)");
+                result.add_warning(format!("Releasing from non-release branch '{}'", branch));
+            } else {
+                return Err(WorkflowError::BranchNotAllowed {
+                    branch: branch.clone(),
+                    allowed: release_branch_patterns(config).join(", "),
+                }
+                .into());
+            }
+        }
+        Some(_) => {}
+        None => result.add_warning("HEAD is detached; skipping release branch check"),
+    }
+
+    if config.git.require_clean && !state.changes.is_empty() {
+        let mut listed: Vec<String> = state
+            .changes
+            .iter()
+            .take(MAX_LISTED_CHANGES)
+            .map(|line| format!("  {}", line))
+            .collect();
+        if state.changes.len() > MAX_LISTED_CHANGES {
+            listed.push(format!(
+                "  ... and {} more",
+                state.changes.len() - MAX_LISTED_CHANGES
+            ));
+        }
+        return Err(WorkflowError::DirtyWorkingTree(listed.join("\n")).into());
+    }
+
+    debug!(warnings = result.warnings.len(), "pre-flight checks passed");
+    Ok(result)
+}
+
+/// Release history checked against `cadence`
+#[derive(Debug, Clone, Default)]
+pub struct CadenceState {
+    /// When the previous release was tagged (`None` for a first release)
+    pub last_release: Option<DateTime<Utc>>,
+    /// Releasable commits since the previous release
+    pub releasable_commits: usize,
+}
+
+/// Check that enough has changed since the last release
+///
+/// Refuses with [`WorkflowError::CadenceNotMet`] when there are fewer
+/// releasable commits than `cadence.min_commits`, or less time has passed
+/// than `cadence.min_interval`. With `force`, the shortfalls become
+/// warnings. A first release has no interval to wait out.
+pub fn check_release_cadence(
+    config: &Config,
+    state: &CadenceState,
+    now: DateTime<Utc>,
+    force: bool,
+) -> Result<ValidationResult> {
+    let mut result = ValidationResult::pass();
+    let mut shortfalls = Vec::new();
+
+    if let Some(min_commits) = config.cadence.min_commits {
+        if state.releasable_commits < min_commits {
+            shortfalls.push(format!(
+                "only {} releasable commit(s) since the last release, {} required",
+                state.releasable_commits, min_commits
+            ));
+        }
+    }
+
+    if let (Some(min_interval), Some(last_release)) =
+        (config.cadence.min_interval()?, state.last_release)
+    {
+        let elapsed = (now - last_release).to_std().unwrap_or_default();
+        if elapsed < min_interval {
+            shortfalls.push(format!(
+                "last release was {} ago, {} required",
+                format_elapsed(elapsed),
+                config.cadence.min_interval.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    if shortfalls.is_empty() {
+        return Ok(result);
+    }
+    if !force {
+        return Err(WorkflowError::CadenceNotMet(shortfalls.join("; ")).into());
+    }
+    for shortfall in shortfalls {
+        warn!(reason = %shortfall, "release cadence not met (forced)");
+        result.add_warning(format!("Cadence not met: {}", shortfall));
+    }
+    Ok(result)
+}
+
+/// Why `cadence` rules out a release now, if it does
+///
+/// The commit shortfall is reported when both thresholds are missed.
+pub fn cadence_skip_reason(
+    config: &Config,
+    state: &CadenceState,
+    now: DateTime<Utc>,
+) -> Result<Option<SkipReason>> {
+    if let Some(min_commits) = config.cadence.min_commits {
+        if state.releasable_commits < min_commits {
+            return Ok(Some(SkipReason::TooFewCommits {
+                min_commits,
+                releasable_commits: state.releasable_commits,
+            }));
+        }
+    }
+
+    if let (Some(min_interval), Some(last_release)) =
+        (config.cadence.min_interval()?, state.last_release)
+    {
+        let elapsed = (now - last_release).to_std().unwrap_or_default();
+        if elapsed < min_interval {
+            return Ok(Some(SkipReason::CadenceGuard {
+                min_interval,
+                elapsed,
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Coarse human rendering of an elapsed duration
+pub(crate) fn format_elapsed(elapsed: std::time::Duration) -> String {
+    let secs = elapsed.as_secs();
+    match secs {
+        s if s >= 86_400 => format!("{}d {}h", s / 86_400, s % 86_400 / 3600),
+        s if s >= 3600 => format!("{}h {}m", s / 3600, s % 3600 / 60),
+        s => format!("{}m", s / 60),
+    }
+}
+
 /// Validate configuration
 pub fn validate_config_for_release(config: &Config) -> Result<ValidationResult> {
     debug!("validating config for release");
@@ -97,3 +438,147 @@ pub fn validate_config_for_release(config: &Config) -> Result<ValidationResult>
 
     Ok(result)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::CanaveralError;
+
+    fn config_with_branches(patterns: &[&str]) -> Config {
+        let mut config = Config::default();
+        config.git.release_branches = patterns.iter().map(|p| p.to_string()).collect();
+        config
+    }
+
+    fn on_branch(branch: &str) -> RepoState {
+        RepoState {
+            branch: Some(branch.to_string()),
+            changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_preflight_allowed_branch() {
+        let config = config_with_branches(&["main", "release/*"]);
+
+        assert!(preflight_release(&config, &on_branch("main"), false).is_ok());
+        let result = preflight_release(&config, &on_branch("release/2.x"), false).unwrap();
+        assert!(result.warnings.is_empty());
+
+        // Defaults to `git.branch` when no patterns are configured
+        assert!(preflight_release(&Config::default(), &on_branch("main"), false).is_ok());
+
+        // Detached HEAD only warns
+        let result = preflight_release(&config, &RepoState::default(), false).unwrap();
+        assert_eq!(result.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_preflight_disallowed_branch() {
+        let config = config_with_branches(&["main", "release/*"]);
+
+        for branch in ["feature/login", "release/2.x/hotfix"] {
+            let err = preflight_release(&config, &on_branch(branch), false).unwrap_err();
+            assert!(err.to_string().contains("--allow-branch"));
+            assert!(
+                matches!(
+                    &err,
+                    CanaveralError::Workflow(WorkflowError::BranchNotAllowed { branch: b, allowed })
+                        if b == branch && allowed == "main, release/*"
+                ),
+                "unexpected error: {}",
+                err
+            );
+        }
+
+        let allowed = preflight_release(&config, &on_branch("feature/login"), true).unwrap();
+        assert!(allowed.passed);
+        assert_eq!(allowed.warnings.len(), 1);
+    }
+
+    #[test]
+    fn test_preflight_dirty_working_tree() {
+        let state = RepoState {
+            branch: Some("main".to_string()),
+            changes: vec![" M src/lib.rs".to_string(), "?? notes.txt".to_string()],
+        };
+
+        let err = preflight_release(&Config::default(), &state, true).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("uncommitted changes"));
+        assert!(message.contains("  M src/lib.rs"));
+        assert!(message.contains("  ?? notes.txt"));
+
+        let mut config = Config::default();
+        config.git.require_clean = false;
+        assert!(preflight_release(&config, &state, false).is_ok());
+    }
+
+    fn cadence_config(min_commits: Option<usize>, min_interval: Option<&str>) -> Config {
+        let mut config = Config::default();
+        config.cadence.min_commits = min_commits;
+        config.cadence.min_interval = min_interval.map(str::to_string);
+        config
+    }
+
+    #[test]
+    fn test_cadence_below_threshold_refused() {
+        let now = Utc::now();
+        let config = cadence_config(Some(3), Some("7d"));
+        let state = CadenceState {
+            last_release: Some(now - chrono::Duration::days(2)),
+            releasable_commits: 2,
+        };
+        let err = check_release_cadence(&config, &state, now, false).unwrap_err();
+        assert!(matches!(
+            err,
+            CanaveralError::Workflow(WorkflowError::CadenceNotMet(_))
+        ));
+        let message = err.to_string();
+        assert!(
+            message.contains("only 2 releasable commit(s)"),
+            "{}",
+            message
+        );
+        assert!(message.contains("last release was 2d 0h ago, 7d required"));
+        assert!(message.contains("--force"));
+    }
+
+    #[test]
+    fn test_cadence_at_threshold_allowed() {
+        let now = Utc::now();
+        let config = cadence_config(Some(3), Some("7d"));
+        let state = CadenceState {
+            last_release: Some(now - chrono::Duration::days(7)),
+            releasable_commits: 3,
+        };
+        let result = check_release_cadence(&config, &state, now, false).unwrap();
+        assert!(result.passed);
+        assert!(result.warnings.is_empty());
+
+        // No previous release means no interval to wait out
+        let first = CadenceState {
+            last_release: None,
+            releasable_commits: 3,
+        };
+        assert!(check_release_cadence(&config, &first, now, false).is_ok());
+
+        // Unset thresholds never refuse
+        let empty = CadenceState::default();
+        assert!(check_release_cadence(&Config::default(), &empty, now, false).is_ok());
+    }
+
+    #[test]
+    fn test_cadence_forced_override() {
+        let now = Utc::now();
+        let config = cadence_config(Some(5), Some("1w"));
+        let state = CadenceState {
+            last_release: Some(now - chrono::Duration::hours(3)),
+            releasable_commits: 1,
+        };
+        let result = check_release_cadence(&config, &state, now, true).unwrap();
+        assert!(result.passed);
+        assert_eq!(result.warnings.len(), 2);
+        assert!(result.warnings[1].contains("3h 0m ago"));
+    }
+}
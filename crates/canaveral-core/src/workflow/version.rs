@@ -56,8 +56,39 @@ pub fn calculate_next_version(
     Ok(next_str)
 }
 
+/// Check that a requested version moves forward from `current_version`
+///
+/// Used for versions that bypass the bump calculation, such as a
+/// `Release-As` footer. Returns the requested version on success.
+pub fn check_version_increase(current_version: &str, requested: &str) -> Result<String> {
+    use crate::error::VersionError;
+    let parse = |v: &str| {
+        semver::Version::parse(v)
+            .map_err(|e| VersionError::ParseFailed(v.to_string(), e.to_string()))
+    };
+    if parse(requested)? <= parse(current_version)? {
+        return Err(VersionError::NotIncreasing(
+            current_version.to_string(),
+            requested.to_string(),
+        )
+        .into());
+    }
+    debug!(
+        current = current_version,
+        requested, "requested version accepted"
+    );
+    Ok(requested.to_string())
+}
+
 /// Format a version tag based on the configuration
+///
+/// Members of a fixed group share the group's tag; with fixed groups
+/// configured, packages outside them are tagged on their own.
 pub fn format_tag(config: &Config, version: &str, package: Option<&str>) -> String {
+    if let Some(group) = package.and_then(|pkg| config.versioning.fixed_group(pkg)) {
+        return group.tag(version);
+    }
+
     let tag_format = if let Some(pkg) = package {
         // Check for package-specific tag format
         config
@@ -66,7 +97,7 @@ pub fn format_tag(config: &Config, version: &str, package: Option<&str>) -> Stri
             .find(|p| p.name == pkg)
             .and_then(|p| p.tag_format.clone())
             .unwrap_or_else(|| {
-                if config.versioning.independent {
+                if config.versioning.independent || !config.versioning.fixed_groups.is_empty() {
                     format!("{}@{{version}}", pkg)
                 } else {
                     config.versioning.tag_format.clone()
@@ -79,6 +110,20 @@ pub fn format_tag(config: &Config, version: &str, package: Option<&str>) -> Stri
     tag_format.replace("{version}", version)
 }
 
+/// Regex matching the tags [`format_tag`] creates for `package`
+///
+/// Lets tag lookups skip other packages' tags in a monorepo.
+pub fn tag_pattern(config: &Config, package: Option<&str>) -> String {
+    const PLACEHOLDER: &str = "{version}";
+    let format = format_tag(config, PLACEHOLDER, package);
+    let (prefix, suffix) = format.split_once(PLACEHOLDER).unwrap_or((&format, ""));
+    format!(
+        r"^{}\d+\.\d+\.\d+(?:-[0-9A-Za-z.-]+)?(?:\+[0-9A-Za-z.-]+)?{}$",
+        regex::escape(prefix),
+        regex::escape(suffix)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,10 +149,52 @@ mod tests {
         assert_eq!(next, "1.2.4");
     }
 
+    #[test]
+    fn test_check_version_increase() {
+        assert_eq!(check_version_increase("1.2.3", "1.5.0").unwrap(), "1.5.0");
+        assert!(check_version_increase("1.2.3", "1.2.3").is_err());
+        assert!(check_version_increase("1.2.3", "1.0.0").is_err());
+        assert!(check_version_increase("1.2.3", "next").is_err());
+    }
+
     #[test]
     fn test_format_tag() {
         let config = Config::default();
         let tag = format_tag(&config, "1.0.0", None);
         assert_eq!(tag, "v1.0.0");
     }
+
+    #[test]
+    fn test_tag_pattern() {
+        let mut config = Config::default();
+        let pattern = regex::Regex::new(&tag_pattern(&config, None)).unwrap();
+        assert!(pattern.is_match("v1.0.0"));
+        assert!(pattern.is_match("v2.0.0-rc.1"));
+        assert!(!pattern.is_match("core@1.0.0"));
+        assert!(!pattern.is_match("vnext"));
+
+        config.versioning.independent = true;
+        let pattern = regex::Regex::new(&tag_pattern(&config, Some("@acme/core"))).unwrap();
+        assert!(pattern.is_match("@acme/core@1.2.0"));
+        assert!(!pattern.is_match("@acme/core-ui@1.2.0"));
+        assert!(!pattern.is_match("v1.2.0"));
+    }
+
+    #[test]
+    fn test_format_tag_fixed_group() {
+        let mut config = Config::default();
+        config.versioning.fixed_groups = vec![crate::config::FixedGroupConfig::new(
+            "core",
+            vec!["@acme/core-*".to_string()],
+        )];
+        assert_eq!(
+            format_tag(&config, "2.0.0", Some("@acme/core-api")),
+            "core@2.0.0"
+        );
+        assert_eq!(
+            format_tag(&config, "2.0.0", Some("@acme/core-db")),
+            "core@2.0.0"
+        );
+        assert_eq!(format_tag(&config, "1.4.0", Some("cli")), "cli@1.4.0");
+    }
 }
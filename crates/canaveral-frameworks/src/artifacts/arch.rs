@@ -0,0 +1,367 @@
+//! CPU architecture of build artifacts
+//!
+//! Adapters tag artifacts with the architecture they built for when they
+//! know it (from the build target or the output file name). Otherwise
+//! [`detect_binary`] reads it from the Mach-O, ELF or PE header of the
+//! executable.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::traits::Platform;
+
+/// Bytes read from the start of a file for header detection
+const HEADER_LEN: usize = 4096;
+
+/// CPU architecture an artifact runs on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Arch {
+    /// 64-bit x86 (`amd64`, `x64`)
+    #[serde(rename = "x86_64")]
+    X86_64,
+    /// 64-bit ARM (`aarch64`, `arm64-v8a`)
+    #[serde(rename = "arm64")]
+    Arm64,
+    /// 32-bit x86 (`i686`, `ia32`)
+    #[serde(rename = "x86")]
+    X86,
+    /// 32-bit ARM (`armeabi-v7a`)
+    #[serde(rename = "armv7")]
+    Armv7,
+    /// Several architectures in one artifact (macOS universal binaries,
+    /// universal APKs)
+    #[serde(rename = "universal")]
+    Universal,
+}
+
+impl Arch {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::X86_64 => "x86_64",
+            Self::Arm64 => "arm64",
+            Self::X86 => "x86",
+            Self::Armv7 => "armv7",
+            Self::Universal => "universal",
+        }
+    }
+
+    /// Parse an architecture name, accepting the aliases used by Rust
+    /// targets, Node, Debian and Android
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "x86_64" | "x64" | "amd64" => Some(Self::X86_64),
+            "arm64" | "aarch64" | "arm64-v8a" => Some(Self::Arm64),
+            "x86" | "i386" | "i686" | "ia32" => Some(Self::X86),
+            "arm" | "armv7" | "armv7l" | "armhf" | "armeabi-v7a" => Some(Self::Armv7),
+            "universal" => Some(Self::Universal),
+            _ => None,
+        }
+    }
+
+    /// Architecture of the machine running this build
+    pub fn host() -> Option<Self> {
+        Self::parse(std::env::consts::ARCH)
+    }
+
+    /// Architecture of a Rust target triple, such as `aarch64-apple-darwin`
+    /// or `universal-apple-darwin`
+    pub fn from_target_triple(triple: &str) -> Option<Self> {
+        triple.split('-').next().and_then(Self::parse)
+    }
+
+    /// Architecture named in a file name, such as `App-1.0.0-arm64.dmg`,
+    /// `app_1.0.0_amd64.deb` or `app-armeabi-v7a-release.apk`
+    pub fn from_file_name(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        // Android ABIs contain separators themselves
+        for abi in ["arm64-v8a", "armeabi-v7a"] {
+            if name.contains(abi) {
+                return Self::parse(abi);
+            }
+        }
+        name.split(['-', '_', '.', ' '])
+            .find_map(|token| match token {
+                // Too ambiguous on its own ("arm" in a product name)
+                "arm" => None,
+                token => Self::parse(token),
+            })
+    }
+}
+
+impl std::fmt::Display for Arch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Platform and architecture read from an executable header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BinaryInfo {
+    /// Platform the binary format belongs to
+    pub platform: Platform,
+    /// Architecture the binary is built for
+    pub arch: Arch,
+}
+
+/// Detect the platform and architecture of an executable or app bundle
+///
+/// Reads the Mach-O (including universal), ELF or PE header of `path`. For
+/// an `.app` bundle the main executable is read. Mach-O binaries report
+/// macOS and ELF binaries Linux, as the header doesn't tell them apart from
+/// iOS and Android.
+/// Returns `None` for anything else, such as archives and disk images.
+pub fn detect_binary(path: &Path) -> Option<BinaryInfo> {
+    let executable = if path.is_dir() {
+        bundle_executable(path)?
+    } else {
+        path.to_path_buf()
+    };
+
+    let mut header = Vec::with_capacity(HEADER_LEN);
+    std::fs::File::open(executable)
+        .ok()?
+        .take(HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    parse_binary_header(&header)
+}
+
+/// The executable of a macOS or iOS app bundle
+fn bundle_executable(bundle: &Path) -> Option<PathBuf> {
+    let macos = bundle.join("Contents/MacOS");
+    let name = bundle.file_stem()?;
+    // iOS bundles are flat, with the executable at the top level
+    for named in [macos.join(name), bundle.join(name)] {
+        if named.is_file() {
+            return Some(named);
+        }
+    }
+    let mut files: Vec<PathBuf> = std::fs::read_dir(macos)
+        .ok()?
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file())
+        .collect();
+    files.sort();
+    files.into_iter().next()
+}
+
+/// Parse the platform and architecture from the first bytes of a binary
+pub fn parse_binary_header(header: &[u8]) -> Option<BinaryInfo> {
+    let u16_le = |at: usize| Some(u16::from_le_bytes(header.get(at..at + 2)?.try_into().ok()?));
+    let u32_le = |at: usize| Some(u32::from_le_bytes(header.get(at..at + 4)?.try_into().ok()?));
+    let u32_be = |at: usize| Some(u32::from_be_bytes(header.get(at..at + 4)?.try_into().ok()?));
+
+    match header.get(..4)? {
+        // Mach-O, 32 and 64 bit (little endian)
+        [0xce, 0xfa, 0xed, 0xfe] | [0xcf, 0xfa, 0xed, 0xfe] => Some(BinaryInfo {
+            platform: Platform::MacOs,
+            arch: mach_cpu_type(u32_le(4)?)?,
+        }),
+        // Universal binary; Java class files share the magic but have a
+        // version >= 45 where the architecture count would be
+        [0xca, 0xfe, 0xba, 0xbe] => {
+            let count = u32_be(4)?;
+            if count == 0 || count >= 45 {
+                return None;
+            }
+            let arch = if count == 1 {
+                mach_cpu_type(u32_be(8)?)?
+            } else {
+                Arch::Universal
+            };
+            Some(BinaryInfo {
+                platform: Platform::MacOs,
+                arch,
+            })
+        }
+        [0x7f, b'E', b'L', b'F'] => {
+            let machine = match header.get(5)? {
+                1 => u16_le(18)?,
+                2 => u16::from_be_bytes(header.get(18..20)?.try_into().ok()?),
+                _ => return None,
+            };
+            let arch = match machine {
+                0x3e => Arch::X86_64,
+                0xb7 => Arch::Arm64,
+                0x03 => Arch::X86,
+                0x28 => Arch::Armv7,
+                _ => return None,
+            };
+            Some(BinaryInfo {
+                platform: Platform::Linux,
+                arch,
+            })
+        }
+        [b'M', b'Z', ..] => {
+            let pe = u32_le(0x3c)? as usize;
+            if header.get(pe..pe + 4)? != b"PE\0\0" {
+                return None;
+            }
+            let arch = match u16_le(pe + 4)? {
+                0x8664 => Arch::X86_64,
+                0xaa64 => Arch::Arm64,
+                0x014c => Arch::X86,
+                0x01c4 => Arch::Armv7,
+                _ => return None,
+            };
+            Some(BinaryInfo {
+                platform: Platform::Windows,
+                arch,
+            })
+        }
+        _ => None,
+    }
+}
+
+/// Architecture of a Mach-O CPU type
+fn mach_cpu_type(cpu_type: u32) -> Option<Arch> {
+    const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+    match cpu_type {
+        0x07 => Some(Arch::X86),
+        0x0c => Some(Arch::Armv7),
+        t if t == 0x07 | CPU_ARCH_ABI64 => Some(Arch::X86_64),
+        t if t == 0x0c | CPU_ARCH_ABI64 => Some(Arch::Arm64),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    //! Minimal executable headers, enough for detection
+
+    pub fn macho(cpu_type: u32) -> Vec<u8> {
+        let mut bytes = vec![0xcf, 0xfa, 0xed, 0xfe];
+        bytes.extend(cpu_type.to_le_bytes());
+        bytes.extend([0; 24]);
+        bytes
+    }
+
+    pub fn macho_arm64() -> Vec<u8> {
+        macho(0x0100_000c)
+    }
+
+    pub fn macho_x86_64() -> Vec<u8> {
+        macho(0x0100_0007)
+    }
+
+    pub fn fat(cpu_types: &[u32]) -> Vec<u8> {
+        let mut bytes = vec![0xca, 0xfe, 0xba, 0xbe];
+        bytes.extend((cpu_types.len() as u32).to_be_bytes());
+        for cpu_type in cpu_types {
+            bytes.extend(cpu_type.to_be_bytes());
+            bytes.extend([0; 16]);
+        }
+        bytes
+    }
+
+    pub fn elf(machine: u16) -> Vec<u8> {
+        let mut bytes = vec![0x7f, b'E', b'L', b'F', 2, 1, 1, 0];
+        bytes.extend([0; 10]);
+        bytes.extend(machine.to_le_bytes());
+        bytes.extend([0; 44]);
+        bytes
+    }
+
+    pub fn pe(machine: u16) -> Vec<u8> {
+        let mut bytes = vec![0; 0x80];
+        bytes[..2].copy_from_slice(b"MZ");
+        bytes[0x3c..0x40].copy_from_slice(&0x80u32.to_le_bytes());
+        bytes.extend(b"PE\0\0");
+        bytes.extend(machine.to_le_bytes());
+        bytes.extend([0; 18]);
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::*;
+    use super::*;
+    use tempfile::TempDir;
+
+    fn detect(bytes: &[u8]) -> Option<(Platform, Arch)> {
+        parse_binary_header(bytes).map(|info| (info.platform, info.arch))
+    }
+
+    #[test]
+    fn test_detect_macho_arm64_vs_x86_64() {
+        let temp = TempDir::new().unwrap();
+        let arm = temp.path().join("tool-arm64");
+        let intel = temp.path().join("tool-x86_64");
+        std::fs::write(&arm, macho_arm64()).unwrap();
+        std::fs::write(&intel, macho_x86_64()).unwrap();
+
+        let info = detect_binary(&arm).unwrap();
+        assert_eq!((info.platform, info.arch), (Platform::MacOs, Arch::Arm64));
+        let info = detect_binary(&intel).unwrap();
+        assert_eq!((info.platform, info.arch), (Platform::MacOs, Arch::X86_64));
+    }
+
+    #[test]
+    fn test_detect_universal_and_app_bundle() {
+        assert_eq!(
+            detect(&fat(&[0x0100_0007, 0x0100_000c])),
+            Some((Platform::MacOs, Arch::Universal))
+        );
+        assert_eq!(
+            detect(&fat(&[0x0100_000c])),
+            Some((Platform::MacOs, Arch::Arm64))
+        );
+        // Java class file (major version 61)
+        assert_eq!(detect(&[0xca, 0xfe, 0xba, 0xbe, 0, 0, 0, 61]), None);
+
+        let temp = TempDir::new().unwrap();
+        let bundle = temp.path().join("My App.app");
+        std::fs::create_dir_all(bundle.join("Contents/MacOS")).unwrap();
+        std::fs::write(bundle.join("Contents/MacOS/My App"), macho_arm64()).unwrap();
+        assert_eq!(detect_binary(&bundle).unwrap().arch, Arch::Arm64);
+    }
+
+    #[test]
+    fn test_detect_elf_and_pe() {
+        assert_eq!(detect(&elf(0xb7)), Some((Platform::Linux, Arch::Arm64)));
+        assert_eq!(detect(&elf(0x3e)), Some((Platform::Linux, Arch::X86_64)));
+        assert_eq!(detect(&pe(0x8664)), Some((Platform::Windows, Arch::X86_64)));
+        assert_eq!(detect(&pe(0xaa64)), Some((Platform::Windows, Arch::Arm64)));
+        assert_eq!(detect(b"PK\x03\x04 not a binary"), None);
+        assert_eq!(detect(b"MZ"), None);
+    }
+
+    #[test]
+    fn test_arch_from_file_name() {
+        assert_eq!(
+            Arch::from_file_name("My App-1.0.0-arm64.dmg"),
+            Some(Arch::Arm64)
+        );
+        assert_eq!(
+            Arch::from_file_name("my-app_1.0.0_amd64.deb"),
+            Some(Arch::X86_64)
+        );
+        assert_eq!(
+            Arch::from_file_name("MyApp_1.0.0_x64-setup.exe"),
+            Some(Arch::X86_64)
+        );
+        assert_eq!(
+            Arch::from_file_name("app-armeabi-v7a-release.apk"),
+            Some(Arch::Armv7)
+        );
+        assert_eq!(
+            Arch::from_file_name("App-1.0.0-universal.dmg"),
+            Some(Arch::Universal)
+        );
+        assert_eq!(Arch::from_file_name("app-release.apk"), None);
+        assert_eq!(
+            Arch::from_target_triple("aarch64-unknown-linux-gnu"),
+            Some(Arch::Arm64)
+        );
+        assert_eq!(
+            Arch::from_target_triple("universal-apple-darwin"),
+            Some(Arch::Universal)
+        );
+        assert_eq!(Arch::from_target_triple("wasm32-unknown-unknown"), None);
+        assert_eq!(Arch::from_file_name("arm-wrestling.zip"), None);
+    }
+}
@@ -0,0 +1,287 @@
+//! Binary delta patches between artifact versions
+//!
+//! A patch is the new artifact compressed with zstd using the previous
+//! artifact as a reference prefix (the same technique as
+//! `zstd --patch-from`). Content shared with the base compresses to almost
+//! nothing, so clients that already have the base download only what
+//! changed. Applying the patch needs the exact base bytes; the base and
+//! target hashes are recorded in the patch artifact's metadata so updaters
+//! can check both.
+
+use std::io::{Read, Write};
+use std::path::Path;
+
+use sha2::{Digest, Sha256};
+use tracing::{debug, info};
+
+use super::{Artifact, ArtifactKind};
+use crate::error::{FrameworkError, Result};
+
+/// Default zstd compression level for patches
+pub const DEFAULT_PATCH_LEVEL: i32 = 19;
+
+/// Metadata key: version of the artifact the patch applies to
+pub const PATCH_BASE_VERSION: &str = "patch_base_version";
+/// Metadata key: SHA256 of the artifact the patch applies to
+pub const PATCH_BASE_SHA256: &str = "patch_base_sha256";
+/// Metadata key: SHA256 of the artifact the patch produces
+pub const PATCH_TARGET_SHA256: &str = "patch_target_sha256";
+/// Metadata key: size in bytes of the artifact the patch produces
+pub const PATCH_TARGET_SIZE: &str = "patch_target_size";
+
+/// Largest zstd window the decoder accepts
+const MAX_WINDOW_LOG: u32 = if cfg!(target_pointer_width = "64") {
+    31
+} else {
+    30
+};
+
+/// Create a patch at `output` that turns `base` into `target`
+///
+/// The returned artifact has kind [`ArtifactKind::Patch`], the target's
+/// platform and metadata, and the base version and both hashes in its
+/// custom metadata.
+pub fn create_patch(base: &Artifact, target: &Artifact, output: &Path) -> Result<Artifact> {
+    create_patch_with_level(base, target, output, DEFAULT_PATCH_LEVEL)
+}
+
+/// [`create_patch`] with an explicit zstd compression level
+pub fn create_patch_with_level(
+    base: &Artifact,
+    target: &Artifact,
+    output: &Path,
+    level: i32,
+) -> Result<Artifact> {
+    let base_bytes = read(&base.path)?;
+    let target_bytes = read(&target.path)?;
+    let patch = encode(&base_bytes, &target_bytes, level)?;
+
+    if let Some(parent) = output.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(output, &patch)?;
+    info!(
+        base = %base.path.display(),
+        target = %target.path.display(),
+        target_size = target_bytes.len(),
+        patch_size = patch.len(),
+        "created delta patch"
+    );
+
+    let mut metadata = target.metadata.clone();
+    let custom = &mut metadata.custom;
+    if let Some(version) = &base.metadata.version {
+        custom.insert(PATCH_BASE_VERSION.to_string(), version.clone().into());
+    }
+    custom.insert(PATCH_BASE_SHA256.to_string(), sha256(&base_bytes).into());
+    custom.insert(
+        PATCH_TARGET_SHA256.to_string(),
+        sha256(&target_bytes).into(),
+    );
+    custom.insert(
+        PATCH_TARGET_SIZE.to_string(),
+        (target_bytes.len() as u64).into(),
+    );
+
+    Ok(Artifact::new(output, ArtifactKind::Patch, target.platform)
+        .with_sha256()
+        .with_metadata(metadata))
+}
+
+/// Check that applying `patch` to `base` reproduces `target` exactly
+///
+/// Also checks the hashes recorded when the patch was created, so a patch
+/// made against a different base is rejected.
+pub fn verify_patch(base: &Path, patch: &Artifact, target: &Path) -> Result<()> {
+    let base_bytes = read(base)?;
+    let expected = read(target)?;
+
+    let custom = &patch.metadata.custom;
+    for (key, bytes, name) in [
+        (PATCH_BASE_SHA256, &base_bytes, "base"),
+        (PATCH_TARGET_SHA256, &expected, "target"),
+    ] {
+        if let Some(recorded) = custom.get(key).and_then(|v| v.as_str()) {
+            if recorded != sha256(bytes) {
+                return Err(mismatch(format!(
+                    "{} does not match the {} the patch was created for",
+                    name, name
+                )));
+            }
+        }
+    }
+
+    let actual = decode(&base_bytes, &read(&patch.path)?)?;
+    if actual != expected {
+        return Err(mismatch(format!(
+            "patched output ({} bytes) differs from the target ({} bytes)",
+            actual.len(),
+            expected.len()
+        )));
+    }
+    debug!(patch = %patch.path.display(), "verified delta patch");
+    Ok(())
+}
+
+/// The artifact to ship as an update from `base` to `target`
+///
+/// A verified patch written to `output` when there is a base and the patch
+/// is smaller than the target; the full target otherwise.
+pub fn update_artifact(
+    base: Option<&Artifact>,
+    target: &Artifact,
+    output: &Path,
+) -> Result<Artifact> {
+    let Some(base) = base else {
+        debug!(target = %target.path.display(), "no base artifact, shipping the full artifact");
+        return Ok(target.clone());
+    };
+
+    let patch = create_patch(base, target, output)?;
+    verify_patch(&base.path, &patch, &target.path)?;
+    if patch.size >= target.size {
+        info!(
+            patch_size = patch.size,
+            target_size = target.size,
+            "patch is not smaller than the artifact, shipping the full artifact"
+        );
+        std::fs::remove_file(output)?;
+        return Ok(target.clone());
+    }
+    Ok(patch)
+}
+
+fn encode(base: &[u8], target: &[u8], level: i32) -> Result<Vec<u8>> {
+    let mut encoder = zstd::stream::write::Encoder::with_ref_prefix(Vec::new(), level, base)
+        .map_err(patch_error)?;
+    // The window has to span the base for matches against it to be found
+    encoder
+        .window_log(window_log(base.len().max(target.len())))
+        .map_err(patch_error)?;
+    encoder.long_distance_matching(true).map_err(patch_error)?;
+    encoder
+        .set_pledged_src_size(Some(target.len() as u64))
+        .map_err(patch_error)?;
+    encoder.write_all(target).map_err(patch_error)?;
+    encoder.finish().map_err(patch_error)
+}
+
+fn decode(base: &[u8], patch: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder =
+        zstd::stream::read::Decoder::with_ref_prefix(patch, base).map_err(patch_error)?;
+    decoder
+        .window_log_max(MAX_WINDOW_LOG)
+        .map_err(patch_error)?;
+    let mut target = Vec::new();
+    decoder.read_to_end(&mut target).map_err(patch_error)?;
+    Ok(target)
+}
+
+/// Smallest zstd window log covering `len` bytes
+fn window_log(len: usize) -> u32 {
+    let bits = usize::BITS - len.saturating_sub(1).leading_zeros();
+    bits.clamp(10, MAX_WINDOW_LOG)
+}
+
+fn read(path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => FrameworkError::ArtifactNotFound {
+            expected_path: path.to_path_buf(),
+        },
+        _ => e.into(),
+    })
+}
+
+fn sha256(bytes: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(bytes))
+}
+
+fn patch_error(err: std::io::Error) -> FrameworkError {
+    FrameworkError::context("delta patch", err.to_string())
+}
+
+fn mismatch(message: String) -> FrameworkError {
+    FrameworkError::context("patch verification", message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::ArtifactMetadata;
+    use crate::traits::Platform;
+    use tempfile::TempDir;
+
+    /// Deterministic pseudo-random bytes, so the data doesn't compress on
+    /// its own and only the shared content makes the patch small
+    fn noise(len: usize, seed: u64) -> Vec<u8> {
+        let mut state = seed;
+        (0..len)
+            .map(|_| {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (state >> 33) as u8
+            })
+            .collect()
+    }
+
+    fn artifact(dir: &Path, name: &str, bytes: &[u8], version: &str) -> Artifact {
+        let path = dir.join(name);
+        std::fs::write(&path, bytes).unwrap();
+        Artifact::new(path, ArtifactKind::AppImage, Platform::Linux)
+            .with_metadata(ArtifactMetadata::new().with_version(version))
+    }
+
+    #[test]
+    fn test_patch_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let old = noise(64 * 1024, 1);
+        let mut new = old.clone();
+        new[1000..1016].copy_from_slice(b"patched section!");
+        new.extend(noise(512, 2));
+
+        let base = artifact(temp.path(), "app-1.0.0.AppImage", &old, "1.0.0");
+        let target = artifact(temp.path(), "app-1.1.0.AppImage", &new, "1.1.0");
+        let patch_path = temp.path().join("patches/app-1.0.0-1.1.0.patch");
+
+        let patch = create_patch(&base, &target, &patch_path).unwrap();
+        assert_eq!(patch.kind, ArtifactKind::Patch);
+        assert_eq!(patch.platform, Platform::Linux);
+        assert_eq!(patch.metadata.version.as_deref(), Some("1.1.0"));
+        assert_eq!(patch.metadata.custom[PATCH_BASE_VERSION], "1.0.0");
+        assert_eq!(patch.metadata.custom[PATCH_TARGET_SIZE], new.len() as u64);
+        assert!(patch.sha256.is_some());
+        assert!(
+            patch.size < 4 * 1024,
+            "patch is {} bytes for a {} byte artifact",
+            patch.size,
+            new.len()
+        );
+
+        let restored = decode(&old, &std::fs::read(&patch.path).unwrap()).unwrap();
+        assert_eq!(restored, new);
+        verify_patch(&base.path, &patch, &target.path).unwrap();
+
+        // Applying to the wrong base is caught
+        let other = artifact(temp.path(), "other.AppImage", &noise(1024, 3), "0.9.0");
+        assert!(verify_patch(&other.path, &patch, &target.path).is_err());
+    }
+
+    #[test]
+    fn test_full_artifact_without_base() {
+        let temp = TempDir::new().unwrap();
+        let target = artifact(temp.path(), "app.AppImage", &noise(2048, 4), "1.0.0");
+        let output = temp.path().join("app.patch");
+
+        let shipped = update_artifact(None, &target, &output).unwrap();
+        assert_eq!(shipped.path, target.path);
+        assert_eq!(shipped.kind, ArtifactKind::AppImage);
+        assert!(!output.exists());
+
+        // An unrelated base gives no savings, so the full artifact ships too
+        let base = artifact(temp.path(), "old.AppImage", &noise(2048, 5), "0.9.0");
+        let shipped = update_artifact(Some(&base), &target, &output).unwrap();
+        assert_eq!(shipped.path, target.path);
+        assert!(!output.exists());
+    }
+}
@@ -0,0 +1,609 @@
+//! Build artifacts - normalized representation of build outputs
+//!
+//! Regardless of which framework produces the output, artifacts are represented
+//! uniformly so downstream operations (signing, uploading, distribution) work
+//! the same way.
+
+mod arch;
+mod delta;
+mod package;
+
+pub use arch::{detect_binary, parse_binary_header, Arch, BinaryInfo};
+pub use delta::{
+    create_patch, create_patch_with_level, update_artifact, verify_patch, DEFAULT_PATCH_LEVEL,
+    PATCH_BASE_SHA256, PATCH_BASE_VERSION, PATCH_TARGET_SHA256, PATCH_TARGET_SIZE,
+};
+pub use package::{ArchiveFormat, ArtifactPackager};
+
+use std::path::PathBuf;
+
+use ignore::overrides::OverrideBuilder;
+use ignore::WalkBuilder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{FrameworkError, Result};
+use crate::traits::Platform;
+
+/// A build artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Artifact {
+    /// Path to the artifact file
+    pub path: PathBuf,
+
+    /// Kind of artifact
+    pub kind: ArtifactKind,
+
+    /// Target platform
+    pub platform: Platform,
+
+    /// Size in bytes
+    pub size: u64,
+
+    /// SHA256 hash (hex encoded)
+    pub sha256: Option<String>,
+
+    /// Metadata about the artifact
+    pub metadata: ArtifactMetadata,
+}
+
+impl Artifact {
+    /// Create a new artifact
+    pub fn new(path: impl Into<PathBuf>, kind: ArtifactKind, platform: Platform) -> Self {
+        let path = path.into();
+        let size = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+
+        Self {
+            path,
+            kind,
+            platform,
+            size,
+            sha256: None,
+            metadata: ArtifactMetadata::default(),
+        }
+    }
+
+    /// Compute and set SHA256 hash
+    pub fn with_sha256(mut self) -> Self {
+        if let Ok(content) = std::fs::read(&self.path) {
+            use sha2::Digest;
+            let hash = sha2::Sha256::digest(&content);
+            self.sha256 = Some(format!("{:x}", hash));
+        }
+        self
+    }
+
+    /// Set metadata
+    pub fn with_metadata(mut self, metadata: ArtifactMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Fill in the target platform and architecture the adapter didn't set
+    ///
+    /// The executable header is read first (see [`detect_binary`]), then
+    /// the architecture named in the file name. The platform falls back to
+    /// the artifact's own.
+    pub fn with_detected_arch(mut self) -> Self {
+        let detected = detect_binary(&self.path);
+        if self.metadata.arch.is_none() {
+            self.metadata.arch = detected
+                .map(|info| info.arch)
+                .or_else(|| self.filename().and_then(Arch::from_file_name));
+        }
+        if self.metadata.platform.is_none() {
+            // Headers can't tell iOS from macOS or Android from Linux
+            let platform = match detected.map(|info| info.platform) {
+                Some(Platform::MacOs) if self.platform == Platform::Ios => Platform::Ios,
+                Some(Platform::Linux) if self.platform == Platform::Android => Platform::Android,
+                Some(platform) => platform,
+                None => self.platform,
+            };
+            self.metadata.platform = Some(platform);
+        }
+        self
+    }
+
+    /// Get file extension
+    pub fn extension(&self) -> Option<&str> {
+        self.path.extension().and_then(|e| e.to_str())
+    }
+
+    /// Get filename
+    pub fn filename(&self) -> Option<&str> {
+        self.path.file_name().and_then(|n| n.to_str())
+    }
+
+    /// Check if artifact is ready for App Store
+    pub fn is_app_store_ready(&self) -> bool {
+        matches!(
+            self.kind,
+            ArtifactKind::Ipa | ArtifactKind::Aab | ArtifactKind::Pkg | ArtifactKind::Msix
+        )
+    }
+
+    /// Check if artifact needs signing
+    pub fn needs_signing(&self) -> bool {
+        match self.kind {
+            ArtifactKind::App | ArtifactKind::Apk | ArtifactKind::Exe => true,
+            ArtifactKind::Ipa | ArtifactKind::Aab => {
+                // Already signed during build for these formats
+                !self.metadata.signed
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Kind of build artifact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArtifactKind {
+    // iOS
+    /// iOS App Bundle (.app)
+    App,
+    /// iOS Archive (.xcarchive)
+    XcArchive,
+    /// iOS IPA for App Store/Ad Hoc
+    Ipa,
+
+    // Android
+    /// Android APK
+    Apk,
+    /// Android App Bundle (AAB) for Play Store
+    Aab,
+
+    // macOS
+    /// macOS Application Bundle (.app)
+    MacApp,
+    /// macOS Installer Package (.pkg)
+    Pkg,
+    /// macOS Disk Image (.dmg)
+    Dmg,
+
+    // Windows
+    /// Windows Executable (.exe)
+    Exe,
+    /// Windows Installer (.msi)
+    Msi,
+    /// Windows App Package (.msix/.appx)
+    Msix,
+
+    // Linux
+    /// Debian package (.deb)
+    Deb,
+    /// Red Hat package (.rpm)
+    Rpm,
+    /// AppImage
+    AppImage,
+    /// Flatpak
+    Flatpak,
+    /// Snap package
+    Snap,
+    /// Tarball (.tar.gz)
+    Tarball,
+
+    // Web
+    /// Static web build directory
+    WebBuild,
+
+    // Cross-platform
+    /// Tauri bundle (platform-specific)
+    TauriBundle,
+    /// Electron package
+    ElectronPackage,
+    /// Auto-update manifest (e.g. Tauri `latest.json`)
+    UpdateManifest,
+    /// Differential update block map (.blockmap)
+    Blockmap,
+    /// Binary delta against a previous artifact (.patch)
+    Patch,
+
+    // Other
+    /// Generic archive
+    Archive,
+    /// Unknown/custom
+    Other,
+}
+
+impl ArtifactKind {
+    /// Get typical file extension for this kind
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::App => "app",
+            Self::XcArchive => "xcarchive",
+            Self::Ipa => "ipa",
+            Self::Apk => "apk",
+            Self::Aab => "aab",
+            Self::MacApp => "app",
+            Self::Pkg => "pkg",
+            Self::Dmg => "dmg",
+            Self::Exe => "exe",
+            Self::Msi => "msi",
+            Self::Msix => "msix",
+            Self::Deb => "deb",
+            Self::Rpm => "rpm",
+            Self::AppImage => "AppImage",
+            Self::Flatpak => "flatpak",
+            Self::Snap => "snap",
+            Self::Tarball => "tar.gz",
+            Self::WebBuild => "",
+            Self::TauriBundle => "",
+            Self::ElectronPackage => "",
+            Self::UpdateManifest => "json",
+            Self::Blockmap => "blockmap",
+            Self::Patch => "patch",
+            Self::Archive => "zip",
+            Self::Other => "",
+        }
+    }
+
+    /// Infer artifact kind from file path
+    pub fn from_path(path: &std::path::Path) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("")
+            .to_lowercase();
+
+        match ext.as_str() {
+            "app" => {
+                // Could be iOS or macOS - check parent path
+                if path.to_string_lossy().contains("Build/Products") {
+                    Self::App
+                } else {
+                    Self::MacApp
+                }
+            }
+            "xcarchive" => Self::XcArchive,
+            "ipa" => Self::Ipa,
+            "apk" => Self::Apk,
+            "aab" => Self::Aab,
+            "pkg" => Self::Pkg,
+            "dmg" => Self::Dmg,
+            "exe" => Self::Exe,
+            "msi" => Self::Msi,
+            "msix" | "appx" => Self::Msix,
+            "deb" => Self::Deb,
+            "rpm" => Self::Rpm,
+            "appimage" => Self::AppImage,
+            "flatpak" => Self::Flatpak,
+            "snap" => Self::Snap,
+            "gz" if path.to_string_lossy().ends_with(".tar.gz") => Self::Tarball,
+            "zip" => Self::Archive,
+            "blockmap" => Self::Blockmap,
+            "patch" => Self::Patch,
+            _ => Self::Other,
+        }
+    }
+}
+
+/// Metadata about an artifact
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ArtifactMetadata {
+    /// Version string embedded in artifact
+    pub version: Option<String>,
+
+    /// Build number
+    pub build_number: Option<u64>,
+
+    /// Universal identifier (bundle ID for mobile, package name for libraries, etc.)
+    pub identifier: Option<String>,
+
+    /// Minimum OS version
+    pub min_os_version: Option<String>,
+
+    /// Target architectures
+    pub architectures: Vec<String>,
+
+    /// Target CPU architecture, for routing per-arch uploads
+    pub arch: Option<Arch>,
+
+    /// Target platform of the binary (e.g. `macos` for a Tauri `.app`)
+    pub platform: Option<Platform>,
+
+    /// Whether artifact is signed
+    pub signed: bool,
+
+    /// Signing identity used
+    pub signing_identity: Option<String>,
+
+    /// Build timestamp
+    pub built_at: Option<chrono::DateTime<chrono::Utc>>,
+
+    /// Framework that produced this artifact
+    pub framework: Option<String>,
+
+    /// Framework version
+    pub framework_version: Option<String>,
+
+    /// Custom metadata
+    pub custom: std::collections::HashMap<String, serde_json::Value>,
+}
+
+impl ArtifactMetadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_version(mut self, version: impl Into<String>) -> Self {
+        self.version = Some(version.into());
+        self
+    }
+
+    pub fn with_build_number(mut self, build_number: u64) -> Self {
+        self.build_number = Some(build_number);
+        self
+    }
+
+    pub fn with_identifier(mut self, identifier: impl Into<String>) -> Self {
+        self.identifier = Some(identifier.into());
+        self
+    }
+
+    pub fn with_framework(mut self, framework: impl Into<String>) -> Self {
+        self.framework = Some(framework.into());
+        self
+    }
+
+    pub fn with_signed(mut self, signed: bool) -> Self {
+        self.signed = signed;
+        self
+    }
+
+    pub fn with_arch(mut self, arch: Arch) -> Self {
+        self.arch = Some(arch);
+        self
+    }
+
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+}
+
+/// Ignore-aware enumeration of candidate artifact files
+///
+/// Honours `.gitignore` and `.dockerignore` files under the root (whether or
+/// not it is inside a git repository). Include globs restrict the walk to
+/// matching files and take precedence over ignore files; exclude globs drop
+/// matching files.
+#[derive(Debug, Clone)]
+pub struct ArtifactWalker {
+    root: PathBuf,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    respect_ignore_files: bool,
+    max_depth: Option<usize>,
+}
+
+impl ArtifactWalker {
+    /// Create a walker rooted at a build output directory
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+            respect_ignore_files: true,
+            max_depth: None,
+        }
+    }
+
+    /// Only collect files matching this glob, even if ignored
+    pub fn with_include(mut self, glob: impl Into<String>) -> Self {
+        self.include.push(glob.into());
+        self
+    }
+
+    /// Skip files matching this glob (e.g. `*.map`)
+    pub fn with_exclude(mut self, glob: impl Into<String>) -> Self {
+        self.exclude.push(glob.into());
+        self
+    }
+
+    /// Whether to honour `.gitignore`/`.dockerignore` files (default: true)
+    pub fn with_respect_ignore_files(mut self, respect: bool) -> Self {
+        self.respect_ignore_files = respect;
+        self
+    }
+
+    /// Limit how deep the walk descends below the root
+    pub fn with_max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+
+    /// Collect matching files, sorted by path
+    pub fn walk(&self) -> Result<Vec<PathBuf>> {
+        let mut overrides = OverrideBuilder::new(&self.root);
+        for glob in &self.include {
+            overrides.add(glob).map_err(|e| invalid_glob(glob, e))?;
+        }
+        for glob in &self.exclude {
+            overrides
+                .add(&format!("!{}", glob))
+                .map_err(|e| invalid_glob(glob, e))?;
+        }
+        let overrides = overrides
+            .build()
+            .map_err(|e| FrameworkError::context("artifact walk", e.to_string()))?;
+
+        let mut builder = WalkBuilder::new(&self.root);
+        builder
+            .standard_filters(self.respect_ignore_files)
+            .require_git(false)
+            .max_depth(self.max_depth)
+            .overrides(overrides);
+        if self.respect_ignore_files {
+            builder.add_custom_ignore_filename(".dockerignore");
+        }
+
+        let mut files = Vec::new();
+        for entry in builder.build() {
+            let entry =
+                entry.map_err(|e| FrameworkError::context("artifact walk", e.to_string()))?;
+            if entry.file_type().is_some_and(|t| t.is_file()) {
+                files.push(entry.into_path());
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Total size in bytes of the files [`walk`](Self::walk) collects
+    pub fn total_size(&self) -> Result<u64> {
+        Ok(self
+            .walk()?
+            .iter()
+            .filter_map(|path| path.metadata().ok())
+            .map(|metadata| metadata.len())
+            .sum())
+    }
+}
+
+fn invalid_glob(glob: &str, err: ignore::Error) -> FrameworkError {
+    FrameworkError::InvalidConfig {
+        message: format!("invalid artifact glob '{}': {}", glob, err),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_artifact_kind_from_path() {
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("app.ipa")),
+            ArtifactKind::Ipa
+        );
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("app-release.aab")),
+            ArtifactKind::Aab
+        );
+        assert_eq!(
+            ArtifactKind::from_path(Path::new("myapp.dmg")),
+            ArtifactKind::Dmg
+        );
+    }
+
+    #[test]
+    fn test_artifact_metadata_builder() {
+        let meta = ArtifactMetadata::new()
+            .with_version("1.2.3")
+            .with_build_number(42)
+            .with_identifier("com.example.app")
+            .with_framework("flutter")
+            .with_signed(true);
+
+        assert_eq!(meta.version, Some("1.2.3".to_string()));
+        assert_eq!(meta.build_number, Some(42));
+        assert_eq!(meta.identifier, Some("com.example.app".to_string()));
+        assert!(meta.signed);
+    }
+
+    #[test]
+    fn test_artifact_app_store_ready() {
+        let ipa = Artifact::new("/tmp/test.ipa", ArtifactKind::Ipa, Platform::Ios);
+        assert!(ipa.is_app_store_ready());
+
+        let apk = Artifact::new("/tmp/test.apk", ArtifactKind::Apk, Platform::Android);
+        assert!(!apk.is_app_store_ready());
+
+        let aab = Artifact::new("/tmp/test.aab", ArtifactKind::Aab, Platform::Android);
+        assert!(aab.is_app_store_ready());
+    }
+
+    #[test]
+    fn test_artifact_arch_detection() {
+        let temp = TempDir::new().unwrap();
+        let binary = temp.path().join("tool");
+        std::fs::write(&binary, arch::fixtures::macho_arm64()).unwrap();
+
+        let artifact =
+            Artifact::new(&binary, ArtifactKind::Other, Platform::Linux).with_detected_arch();
+        assert_eq!(artifact.metadata.arch, Some(Arch::Arm64));
+        assert_eq!(artifact.metadata.platform, Some(Platform::MacOs));
+
+        // Adapter-supplied values win; file names are the fallback
+        let artifact = Artifact::new(&binary, ArtifactKind::Other, Platform::MacOs)
+            .with_metadata(ArtifactMetadata::new().with_arch(Arch::Universal))
+            .with_detected_arch();
+        assert_eq!(artifact.metadata.arch, Some(Arch::Universal));
+
+        let dmg = temp.path().join("My App-1.0.0-x64.dmg");
+        std::fs::write(&dmg, "not a binary").unwrap();
+        let artifact = Artifact::new(&dmg, ArtifactKind::Dmg, Platform::MacOs).with_detected_arch();
+        assert_eq!(artifact.metadata.arch, Some(Arch::X86_64));
+        assert_eq!(artifact.metadata.platform, Some(Platform::MacOs));
+
+        let json = serde_json::to_value(&artifact.metadata).unwrap();
+        assert_eq!(json["arch"], "x86_64");
+        assert_eq!(json["platform"], "macos");
+    }
+
+    fn write(root: &Path, rel: &str) {
+        let path = root.join(rel);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, rel).unwrap();
+    }
+
+    fn relative(root: &Path, files: Vec<PathBuf>) -> Vec<String> {
+        files
+            .into_iter()
+            .map(|f| {
+                f.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_walker_respects_ignore_files() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        write(root, ".gitignore");
+        std::fs::write(root.join(".gitignore"), "*.map\n.cache/\n").unwrap();
+        std::fs::write(root.join(".dockerignore"), "tmp/\n").unwrap();
+        write(root, "dist/app.js");
+        write(root, "dist/app.js.map");
+        write(root, ".cache/chunk.bin");
+        write(root, "tmp/scratch.txt");
+
+        let files = relative(root, ArtifactWalker::new(root).walk().unwrap());
+        assert_eq!(files, vec!["dist/app.js"]);
+    }
+
+    #[test]
+    fn test_walker_include_overrides_ignore() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(root.join(".gitignore"), "*.map\n").unwrap();
+        write(root, "dist/app.js");
+        write(root, "dist/app.js.map");
+        write(root, "dist/vendor.js.map");
+
+        let files = ArtifactWalker::new(root)
+            .with_include("dist/app.js*")
+            .walk()
+            .unwrap();
+        assert_eq!(
+            relative(root, files),
+            vec!["dist/app.js", "dist/app.js.map"]
+        );
+
+        let files = ArtifactWalker::new(root)
+            .with_respect_ignore_files(false)
+            .with_exclude("vendor.*")
+            .walk()
+            .unwrap();
+        // Without ignore files, hidden files are collected too
+        assert_eq!(
+            relative(root, files),
+            vec![".gitignore", "dist/app.js", "dist/app.js.map"]
+        );
+    }
+}
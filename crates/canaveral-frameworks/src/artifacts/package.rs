@@ -0,0 +1,447 @@
+//! Packaging directory artifacts into a single archive
+//!
+//! Uploads and release assets usually need one file, so directory outputs
+//! (a web `dist/`, a `.app` bundle) are packed as zip or tar.gz first. Unix
+//! permissions and symlinks are kept, which `.app` bundles rely on, and
+//! archives are deterministic by default: entries are sorted and carry a
+//! fixed timestamp, so the same tree always produces the same bytes.
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Datelike, Local, Timelike};
+use tracing::{debug, info};
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+
+use super::{Artifact, ArtifactKind};
+use crate::error::{FrameworkError, Result};
+
+/// Archive formats for packaged artifacts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ArchiveFormat {
+    /// Deflate-compressed zip
+    #[default]
+    Zip,
+    /// Gzip-compressed tarball
+    TarGz,
+}
+
+impl ArchiveFormat {
+    /// File extension, without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::TarGz => "tar.gz",
+        }
+    }
+
+    /// Artifact kind of the packaged archive
+    pub fn kind(&self) -> ArtifactKind {
+        match self {
+            Self::Zip => ArtifactKind::Archive,
+            Self::TarGz => ArtifactKind::Tarball,
+        }
+    }
+}
+
+/// Packs a directory artifact into a single archive file
+#[derive(Debug, Clone)]
+pub struct ArtifactPackager {
+    format: ArchiveFormat,
+    output_dir: Option<PathBuf>,
+    keep_parent: bool,
+    deterministic: bool,
+}
+
+impl ArtifactPackager {
+    /// Create a packager for the given format
+    pub fn new(format: ArchiveFormat) -> Self {
+        Self {
+            format,
+            output_dir: None,
+            keep_parent: true,
+            deterministic: true,
+        }
+    }
+
+    /// Write the archive here instead of next to the directory
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// Whether entries are prefixed with the directory's own name (default: true)
+    ///
+    /// Keep it for bundles, which must unpack as `MyApp.app/...`; drop it
+    /// for web builds that should unpack in place.
+    pub fn with_keep_parent(mut self, keep: bool) -> Self {
+        self.keep_parent = keep;
+        self
+    }
+
+    /// Whether to use sorted entries and fixed timestamps (default: true)
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    /// Path the archive for `dir` is written to
+    pub fn archive_path(&self, dir: &Path) -> PathBuf {
+        let name = dir
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "artifact".to_string());
+        let parent = dir.parent().unwrap_or_else(|| Path::new("."));
+        self.output_dir.as_deref().unwrap_or(parent).join(format!(
+            "{}.{}",
+            name,
+            self.format.extension()
+        ))
+    }
+
+    /// Pack `artifact`, which must be a directory, returning the archive artifact
+    ///
+    /// The new artifact keeps the platform and metadata of the original.
+    pub fn package(&self, artifact: &Artifact) -> Result<Artifact> {
+        let dir = &artifact.path;
+        if !dir.is_dir() {
+            return Err(FrameworkError::context(
+                "artifact packaging",
+                format!("{} is not a directory", dir.display()),
+            ));
+        }
+
+        let output = self.archive_path(dir);
+        if let Some(parent) = output.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let entries = self.entries(dir)?;
+        debug!(
+            dir = %dir.display(),
+            output = %output.display(),
+            entries = entries.len(),
+            "packaging artifact"
+        );
+
+        let file = BufWriter::new(File::create(&output)?);
+        match self.format {
+            ArchiveFormat::Zip => self.write_zip(file, &entries)?,
+            ArchiveFormat::TarGz => self.write_tar_gz(file, &entries)?,
+        }
+
+        let packaged = Artifact::new(&output, self.format.kind(), artifact.platform)
+            .with_metadata(artifact.metadata.clone());
+        info!(path = %output.display(), size = packaged.size, "packaged artifact");
+        Ok(packaged)
+    }
+
+    /// Everything under `dir`, sorted, with archive names using `/`
+    fn entries(&self, dir: &Path) -> Result<Vec<Entry>> {
+        let base = match (self.keep_parent, dir.parent()) {
+            (true, Some(parent)) => parent,
+            _ => dir,
+        };
+        let mut entries = Vec::new();
+        for entry in WalkDir::new(dir)
+            .follow_links(false)
+            .sort_by_file_name()
+            .min_depth(if self.keep_parent { 0 } else { 1 })
+        {
+            let entry =
+                entry.map_err(|e| FrameworkError::context("artifact packaging", e.to_string()))?;
+            let name = entry
+                .path()
+                .strip_prefix(base)
+                .unwrap_or(entry.path())
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/");
+            let metadata = entry.path().symlink_metadata()?;
+            let kind = if metadata.file_type().is_symlink() {
+                EntryKind::Symlink(std::fs::read_link(entry.path())?)
+            } else if metadata.is_dir() {
+                EntryKind::Dir
+            } else {
+                EntryKind::File
+            };
+            entries.push(Entry {
+                path: entry.path().to_path_buf(),
+                mode: mode(&metadata, &kind),
+                modified: metadata.modified().ok(),
+                name,
+                kind,
+            });
+        }
+        Ok(entries)
+    }
+
+    fn modified(&self, entry: &Entry) -> Option<SystemTime> {
+        if self.deterministic {
+            None
+        } else {
+            entry.modified
+        }
+    }
+
+    fn write_zip(&self, file: impl Write + std::io::Seek, entries: &[Entry]) -> Result<()> {
+        let zip_err = |e: zip::result::ZipError| FrameworkError::context("zip", e.to_string());
+        let mut zip = zip::ZipWriter::new(file);
+        for entry in entries {
+            let options = SimpleFileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated)
+                .unix_permissions(entry.mode)
+                .last_modified_time(zip_time(self.modified(entry)));
+            match &entry.kind {
+                EntryKind::Dir => zip.add_directory(&entry.name, options).map_err(zip_err)?,
+                EntryKind::Symlink(target) => zip
+                    .add_symlink(&entry.name, target.to_string_lossy(), options)
+                    .map_err(zip_err)?,
+                EntryKind::File => {
+                    zip.start_file(&entry.name, options).map_err(zip_err)?;
+                    std::io::copy(&mut File::open(&entry.path)?, &mut zip)?;
+                }
+            }
+        }
+        zip.finish().map_err(zip_err)?.flush()?;
+        Ok(())
+    }
+
+    fn write_tar_gz(&self, file: impl Write, entries: &[Entry]) -> Result<()> {
+        // The gzip header's mtime defaults to 0, so it stays deterministic
+        let gz = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+        let mut tar = tar::Builder::new(gz);
+        for entry in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_mode(entry.mode);
+            header.set_mtime(
+                self.modified(entry)
+                    .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+                    .map_or(0, |d| d.as_secs()),
+            );
+            let name = match entry.kind {
+                EntryKind::Dir => format!("{}/", entry.name),
+                _ => entry.name.clone(),
+            };
+            match &entry.kind {
+                EntryKind::Dir => {
+                    header.set_entry_type(tar::EntryType::Directory);
+                    header.set_size(0);
+                    tar.append_data(&mut header, name, std::io::empty())?;
+                }
+                EntryKind::Symlink(target) => {
+                    header.set_entry_type(tar::EntryType::Symlink);
+                    header.set_size(0);
+                    tar.append_link(&mut header, name, target)?;
+                }
+                EntryKind::File => {
+                    let file = File::open(&entry.path)?;
+                    header.set_entry_type(tar::EntryType::Regular);
+                    header.set_size(file.metadata()?.len());
+                    tar.append_data(&mut header, name, file)?;
+                }
+            }
+        }
+        tar.into_inner()?.finish()?.flush()?;
+        Ok(())
+    }
+}
+
+struct Entry {
+    path: PathBuf,
+    name: String,
+    kind: EntryKind,
+    mode: u32,
+    modified: Option<SystemTime>,
+}
+
+enum EntryKind {
+    File,
+    Dir,
+    Symlink(PathBuf),
+}
+
+#[cfg(unix)]
+fn mode(metadata: &std::fs::Metadata, _kind: &EntryKind) -> u32 {
+    use std::os::unix::fs::PermissionsExt;
+    metadata.permissions().mode() & 0o7777
+}
+
+#[cfg(not(unix))]
+fn mode(_metadata: &std::fs::Metadata, kind: &EntryKind) -> u32 {
+    match kind {
+        EntryKind::File => 0o644,
+        EntryKind::Dir | EntryKind::Symlink(_) => 0o755,
+    }
+}
+
+/// Zip timestamp for `time`; the zip epoch (1980-01-01) when unset
+fn zip_time(time: Option<SystemTime>) -> zip::DateTime {
+    time.and_then(|t| {
+        let t: DateTime<Local> = t.into();
+        zip::DateTime::from_date_and_time(
+            u16::try_from(t.year()).ok()?,
+            t.month() as u8,
+            t.day() as u8,
+            t.hour() as u8,
+            t.minute() as u8,
+            t.second() as u8,
+        )
+        .ok()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::traits::Platform;
+    use std::collections::BTreeMap;
+    use std::io::Read;
+    use tempfile::TempDir;
+
+    fn site(root: &Path) -> Artifact {
+        let dist = root.join("dist");
+        std::fs::create_dir_all(dist.join("assets")).unwrap();
+        std::fs::write(dist.join("index.html"), "<html></html>").unwrap();
+        std::fs::write(dist.join("assets/app.js"), "console.log(1)").unwrap();
+        Artifact::new(&dist, ArtifactKind::WebBuild, Platform::Web)
+    }
+
+    #[test]
+    fn test_zip_round_trip() {
+        let temp = TempDir::new().unwrap();
+        let artifact = site(temp.path());
+        let out = temp.path().join("out");
+
+        let packaged = ArtifactPackager::new(ArchiveFormat::Zip)
+            .with_output_dir(&out)
+            .package(&artifact)
+            .unwrap();
+        assert_eq!(packaged.path, out.join("dist.zip"));
+        assert_eq!(packaged.kind, ArtifactKind::Archive);
+        assert_eq!(packaged.platform, Platform::Web);
+        assert_eq!(
+            packaged.size,
+            std::fs::metadata(&packaged.path).unwrap().len()
+        );
+
+        let mut zip = zip::ZipArchive::new(File::open(&packaged.path).unwrap()).unwrap();
+        let mut files = BTreeMap::new();
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i).unwrap();
+            if file.is_file() {
+                let mut content = String::new();
+                file.read_to_string(&mut content).unwrap();
+                files.insert(file.name().to_string(), content);
+            }
+        }
+        assert_eq!(
+            files,
+            BTreeMap::from([
+                (
+                    "dist/assets/app.js".to_string(),
+                    "console.log(1)".to_string()
+                ),
+                ("dist/index.html".to_string(), "<html></html>".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_tar_gz_round_trip_without_parent() {
+        let temp = TempDir::new().unwrap();
+        let artifact = site(temp.path());
+
+        let packaged = ArtifactPackager::new(ArchiveFormat::TarGz)
+            .with_keep_parent(false)
+            .package(&artifact)
+            .unwrap();
+        assert_eq!(packaged.path, temp.path().join("dist.tar.gz"));
+        assert_eq!(packaged.kind, ArtifactKind::Tarball);
+
+        let unpacked = temp.path().join("unpacked");
+        let gz = flate2::read::GzDecoder::new(File::open(&packaged.path).unwrap());
+        tar::Archive::new(gz).unpack(&unpacked).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(unpacked.join("assets/app.js")).unwrap(),
+            "console.log(1)"
+        );
+        assert_eq!(
+            std::fs::read_to_string(unpacked.join("index.html")).unwrap(),
+            "<html></html>"
+        );
+    }
+
+    #[test]
+    fn test_deterministic_archives_are_identical() {
+        let temp = TempDir::new().unwrap();
+        let artifact = site(temp.path());
+        let packager = ArtifactPackager::new(ArchiveFormat::Zip);
+
+        let first = std::fs::read(packager.package(&artifact).unwrap().path).unwrap();
+        // Touch a file; content and layout are unchanged
+        let index = artifact.path.join("index.html");
+        File::options()
+            .write(true)
+            .open(&index)
+            .unwrap()
+            .set_modified(SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1 << 30))
+            .unwrap();
+        let second = std::fs::read(packager.package(&artifact).unwrap().path).unwrap();
+        assert_eq!(first, second);
+
+        let file = Artifact::new(&index, ArtifactKind::Other, Platform::Web);
+        assert!(packager.package(&file).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_bundle_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let bundle = temp.path().join("MyApp.app");
+        let macos = bundle.join("Contents/MacOS");
+        std::fs::create_dir_all(&macos).unwrap();
+        std::fs::write(macos.join("MyApp"), "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(macos.join("MyApp"), std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+        std::os::unix::fs::symlink("Contents/MacOS/MyApp", bundle.join("Current")).unwrap();
+        let artifact = Artifact::new(&bundle, ArtifactKind::MacApp, Platform::MacOs);
+
+        let zip_path = ArtifactPackager::new(ArchiveFormat::Zip)
+            .package(&artifact)
+            .unwrap()
+            .path;
+        let mut zip = zip::ZipArchive::new(File::open(&zip_path).unwrap()).unwrap();
+        let binary = zip.by_name("MyApp.app/Contents/MacOS/MyApp").unwrap();
+        assert_eq!(binary.unix_mode().unwrap() & 0o777, 0o755);
+        drop(binary);
+        let mut link = zip.by_name("MyApp.app/Current").unwrap();
+        assert!(link.is_symlink());
+        let mut target = String::new();
+        link.read_to_string(&mut target).unwrap();
+        assert_eq!(target, "Contents/MacOS/MyApp");
+
+        let tar_path = ArtifactPackager::new(ArchiveFormat::TarGz)
+            .package(&artifact)
+            .unwrap()
+            .path;
+        let unpacked = temp.path().join("unpacked");
+        let gz = flate2::read::GzDecoder::new(File::open(&tar_path).unwrap());
+        tar::Archive::new(gz).unpack(&unpacked).unwrap();
+        let binary = unpacked.join("MyApp.app/Contents/MacOS/MyApp");
+        assert_eq!(
+            std::fs::metadata(&binary).unwrap().permissions().mode() & 0o777,
+            0o755
+        );
+        assert_eq!(
+            std::fs::read_link(unpacked.join("MyApp.app/Current")).unwrap(),
+            Path::new("Contents/MacOS/MyApp")
+        );
+    }
+}
@@ -8,6 +8,8 @@ use std::collections::HashSet;
 
 use serde::{Deserialize, Serialize};
 
+use crate::traits::PrerequisiteStatus;
+
 /// A capability that an adapter might support
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
@@ -229,6 +231,28 @@ impl FromIterator<Capability> for Capabilities {
     }
 }
 
+/// A capability of an adapter detected for a project, and whether the
+/// adapter's prerequisites are met
+#[derive(Debug, Clone)]
+pub struct CapabilityStatus {
+    pub capability: Capability,
+    /// ID of the adapter providing the capability
+    pub adapter_id: String,
+    /// Prerequisites of that adapter
+    pub prerequisites: PrerequisiteStatus,
+}
+
+/// Prerequisites of one adapter detected for a project
+#[derive(Debug, Clone)]
+pub struct AdapterPrerequisites {
+    /// ID of the adapter
+    pub adapter_id: String,
+    /// Kind of adapter: `build`, `test`, `screenshot` or `ota`
+    pub kind: &'static str,
+    /// Whether the adapter's tools are installed
+    pub prerequisites: PrerequisiteStatus,
+}
+
 /// Common capability sets for frameworks
 impl Capabilities {
     /// Capabilities typical for Flutter
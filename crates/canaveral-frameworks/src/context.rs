@@ -49,6 +49,9 @@ pub struct BuildContext {
 
     /// Build number to embed
     pub build_number: Option<u64>,
+
+    /// Previous release's artifact to create an update patch from
+    pub delta_base: Option<PathBuf>,
 }
 
 impl BuildContext {
@@ -67,6 +70,7 @@ impl BuildContext {
             signing: None,
             version: None,
             build_number: None,
+            delta_base: None,
         }
     }
 
@@ -120,6 +124,11 @@ impl BuildContext {
         self
     }
 
+    pub fn with_delta_base(mut self, base: impl Into<PathBuf>) -> Self {
+        self.delta_base = Some(base.into());
+        self
+    }
+
     /// Load context from environment variables (CI/CD mode)
     pub fn from_env(path: impl Into<PathBuf>, platform: Platform) -> Self {
         let mut ctx = Self::new(path, platform);
@@ -71,6 +71,20 @@ impl Ord for Detection {
     }
 }
 
+/// Detection hook that votes for a registered build adapter
+///
+/// Lets a project layout the adapter's own detection misses (e.g. a Flutter
+/// app wrapped in internal build tooling) resolve to that adapter without
+/// writing a full [`BuildAdapter`]. Register with
+/// [`FrameworkRegistry::register_detector`](crate::FrameworkRegistry::register_detector).
+pub trait Detector: Send + Sync {
+    /// ID of the build adapter this detector votes for
+    fn adapter_id(&self) -> &str;
+
+    /// Detect the project at the given path
+    fn detect(&self, path: &Path) -> Detection;
+}
+
 /// Framework detector that manages multiple adapters
 pub struct FrameworkDetector {
     adapters: Vec<Box<dyn BuildAdapter>>,
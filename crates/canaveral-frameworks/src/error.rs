@@ -3,6 +3,8 @@
 use std::path::PathBuf;
 use thiserror::Error;
 
+use canaveral_core::error::ErrorCode;
+
 /// Result type for framework operations
 pub type Result<T> = std::result::Result<T, FrameworkError>;
 
@@ -86,6 +88,10 @@ pub enum FrameworkError {
     #[error("Operation timed out after {seconds}s")]
     Timeout { seconds: u64 },
 
+    /// Cancelled before completion (e.g. Ctrl-C)
+    #[error("Cancelled during {step}")]
+    Cancelled { step: String },
+
     /// Generic error with context
     #[error("{context}: {message}")]
     Context { context: String, message: String },
@@ -117,30 +123,79 @@ impl FrameworkError {
         }
     }
 
+    /// Create a cancellation error for the given step
+    pub fn cancelled(step: impl Into<String>) -> Self {
+        Self::Cancelled { step: step.into() }
+    }
+
+    /// Check if this is a cancellation
+    pub fn is_cancelled(&self) -> bool {
+        matches!(self, Self::Cancelled { .. })
+    }
+
     /// Check if this is a retryable error
     pub fn is_retryable(&self) -> bool {
         matches!(self, Self::Timeout { .. } | Self::Io(_))
     }
 
-    /// Get exit code for CLI
-    pub fn exit_code(&self) -> i32 {
+    /// Error category for exit code mapping
+    pub fn code(&self) -> ErrorCode {
         match self {
-            Self::NoFrameworkDetected { .. } => 2,
-            Self::AmbiguousFramework { .. } => 2,
-            Self::ToolNotFound { .. } => 3,
-            Self::BuildFailed { .. } => 10,
-            Self::TestFailed { .. } => 11,
-            Self::ScreenshotFailed { .. } => 12,
-            Self::ArtifactNotFound { .. } => 13,
-            Self::InvalidConfig { .. } => 4,
-            Self::CommandFailed { exit_code, .. } => exit_code.unwrap_or(1),
-            Self::UnsupportedCapability { .. } => 5,
-            Self::UnsupportedPlatform { .. } => 5,
-            Self::VersionParseError { .. } => 6,
-            Self::Io(_) => 7,
-            Self::Serialization(_) => 8,
-            Self::Timeout { .. } => 9,
-            Self::Context { .. } => 1,
+            Self::NoFrameworkDetected { .. }
+            | Self::AmbiguousFramework { .. }
+            | Self::InvalidConfig { .. } => ErrorCode::Config,
+            Self::ToolNotFound { .. } => ErrorCode::ToolMissing,
+            Self::BuildFailed { .. }
+            | Self::ScreenshotFailed { .. }
+            | Self::ArtifactNotFound { .. } => ErrorCode::Build,
+            Self::TestFailed { .. } => ErrorCode::Test,
+            Self::UnsupportedCapability { .. } | Self::UnsupportedPlatform { .. } => {
+                ErrorCode::Validation
+            }
+            Self::VersionParseError { .. } => ErrorCode::Version,
+            Self::Cancelled { .. } => ErrorCode::Cancelled,
+            Self::CommandFailed { .. }
+            | Self::Io(_)
+            | Self::Serialization(_)
+            | Self::Timeout { .. }
+            | Self::Context { .. } => ErrorCode::General,
         }
     }
+
+    /// Get exit code for CLI
+    pub fn exit_code(&self) -> i32 {
+        self.code().exit_code()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_follow_error_codes() {
+        let err = FrameworkError::tool_not_found("flutter", "Install Flutter");
+        assert_eq!(err.code(), ErrorCode::ToolMissing);
+        assert_eq!(err.exit_code(), 8);
+
+        let err = FrameworkError::InvalidConfig {
+            message: "bad".to_string(),
+        };
+        assert_eq!(err.exit_code(), ErrorCode::Config.exit_code());
+
+        let err = FrameworkError::build_failed("ios", "xcodebuild failed");
+        assert_eq!(err.code(), ErrorCode::Build);
+
+        let err = FrameworkError::TestFailed {
+            summary: "2 failed".to_string(),
+            failed_count: 2,
+            total_count: 10,
+        };
+        assert_eq!(err.code(), ErrorCode::Test);
+
+        let err = FrameworkError::cancelled("build");
+        assert!(err.is_cancelled());
+        assert!(!err.is_retryable());
+        assert_eq!(err.exit_code(), 130);
+    }
 }
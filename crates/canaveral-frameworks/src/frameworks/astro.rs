@@ -3,13 +3,13 @@
 //! Supports building Astro-based static sites and web applications.
 
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use tracing::{debug, info, instrument};
 
-use crate::artifacts::{Artifact, ArtifactKind, ArtifactMetadata};
+use crate::artifacts::{Artifact, ArtifactKind, ArtifactMetadata, ArtifactWalker};
 use crate::capabilities::Capabilities;
 use crate::context::{BuildContext, BuildProfile};
 use crate::detection::{file_exists, has_npm_dependency, Detection};
@@ -76,15 +76,21 @@ impl AstroAdapter {
         }
     }
 
-    fn run_package_manager(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_package_manager(
+        &self,
+        args: &[&str],
+        path: &Path,
+    ) -> Result<std::process::Output> {
         let pm = self.detect_package_manager(path);
         let mut full_args = pm.run_args();
         full_args.extend_from_slice(args);
 
         let output = Command::new(pm.command())
+            .kill_on_drop(true)
             .args(&full_args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("{} {}", pm.command(), full_args.join(" ")),
                 exit_code: None,
@@ -189,7 +195,12 @@ impl BuildAdapter for AstroAdapter {
         let mut status = PrerequisiteStatus::ok();
 
         // Check Node.js
-        match Command::new("node").arg("--version").output() {
+        match Command::new("node")
+            .kill_on_drop(true)
+            .arg("--version")
+            .output()
+            .await
+        {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 status = status.with_tool(ToolStatus::found("node", Some(version)));
@@ -203,7 +214,12 @@ impl BuildAdapter for AstroAdapter {
         }
 
         // Check npm (or package manager)
-        match Command::new("npm").arg("--version").output() {
+        match Command::new("npm")
+            .kill_on_drop(true)
+            .arg("--version")
+            .output()
+            .await
+        {
             Ok(output) if output.status.success() => {
                 let version = String::from_utf8_lossy(&output.stdout).trim().to_string();
                 status = status.with_tool(ToolStatus::found("npm", Some(version)));
@@ -235,7 +251,7 @@ impl BuildAdapter for AstroAdapter {
         };
 
         // Run build
-        let output = self.run_package_manager(&[build_cmd], &ctx.path)?;
+        let output = self.run_package_manager(&[build_cmd], &ctx.path).await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -257,8 +273,8 @@ impl BuildAdapter for AstroAdapter {
             });
         }
 
-        // Calculate directory size
-        let size = calculate_dir_size(&dist_dir)?;
+        // Size of the files that ship, honouring ignore files
+        let size = ArtifactWalker::new(&dist_dir).total_size()?;
 
         // Create artifact metadata
         let metadata = ArtifactMetadata::new()
@@ -325,32 +341,6 @@ struct PackageJson {
     scripts: std::collections::HashMap<String, String>,
 }
 
-// Helper function to calculate directory size
-fn calculate_dir_size(path: &Path) -> Result<u64> {
-    let mut total_size = 0u64;
-
-    if path.is_dir() {
-        for entry in std::fs::read_dir(path).map_err(|e| FrameworkError::Context {
-            context: format!("reading directory: {}", path.display()),
-            message: e.to_string(),
-        })? {
-            let entry = entry.map_err(|e| FrameworkError::Context {
-                context: "reading directory entry".to_string(),
-                message: e.to_string(),
-            })?;
-            let path = entry.path();
-
-            if path.is_file() {
-                total_size += path.metadata().map(|m| m.len()).unwrap_or(0);
-            } else if path.is_dir() {
-                total_size += calculate_dir_size(&path)?;
-            }
-        }
-    }
-
-    Ok(total_size)
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,586 @@
+//! Expo EAS Update OTA adapter
+//!
+//! Publishes JavaScript bundle updates with `eas update` and rolls back by
+//! republishing an earlier update group with `eas update:republish`. Both
+//! run non-interactively with `--json`, so the update group ID and links
+//! come from the CLI's JSON output.
+
+use std::path::Path;
+use tokio::process::Command;
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use tracing::{debug, info, instrument, warn};
+
+use crate::detection::{file_exists, has_npm_dependency, Detection};
+use crate::error::{FrameworkError, Result};
+use crate::traits::{OtaAdapter, OtaContext, OtaResult, Platform, PrerequisiteStatus, ToolStatus};
+
+/// Expo EAS Update adapter
+pub struct EasUpdateAdapter {
+    /// Path or name of the EAS CLI binary, or of the program wrapping it
+    eas_binary: String,
+    /// Arguments passed to `eas_binary` before the EAS CLI's own
+    eas_prefix_args: Vec<String>,
+}
+
+impl EasUpdateAdapter {
+    pub fn new() -> Self {
+        Self {
+            eas_binary: "eas".to_string(),
+            eas_prefix_args: Vec::new(),
+        }
+    }
+
+    /// Use a different EAS CLI binary (a single path or name, not a command line)
+    pub fn with_binary(mut self, binary: impl Into<String>) -> Self {
+        self.eas_binary = binary.into();
+        self.eas_prefix_args.clear();
+        self
+    }
+
+    /// Run the EAS CLI through another program, e.g. `npx` with `["eas-cli"]`
+    pub fn with_command<S: Into<String>>(
+        mut self,
+        program: impl Into<String>,
+        args: impl IntoIterator<Item = S>,
+    ) -> Self {
+        self.eas_binary = program.into();
+        self.eas_prefix_args = args.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The EAS CLI invocation for `args`, for logs and errors
+    fn command_line(&self, args: &[String]) -> String {
+        std::iter::once(&self.eas_binary)
+            .chain(&self.eas_prefix_args)
+            .chain(args)
+            .map(String::as_str)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// Arguments for `eas update` publishing `ctx`
+    ///
+    /// Publishes to `ctx.branch` when set, otherwise to the branch linked to
+    /// `ctx.channel`.
+    pub fn update_args(ctx: &OtaContext) -> Result<Vec<String>> {
+        let mut args = vec!["update".to_string()];
+        match &ctx.branch {
+            Some(branch) => args.extend(["--branch".to_string(), branch.clone()]),
+            None => args.extend(["--channel".to_string(), ctx.channel.clone()]),
+        }
+        args.extend([
+            "--message".to_string(),
+            ctx.message
+                .clone()
+                .unwrap_or_else(|| format!("Update for {}", ctx.channel)),
+        ]);
+        if let Some(platform) = ctx.platform {
+            args.extend([
+                "--platform".to_string(),
+                eas_platform(platform)?.to_string(),
+            ]);
+        }
+        args.extend(["--non-interactive".to_string(), "--json".to_string()]);
+        Ok(args)
+    }
+
+    /// Arguments for `eas update:republish` of update group `target` to
+    /// `ctx.channel` (or `ctx.branch`)
+    pub fn republish_args(ctx: &OtaContext, target: &str) -> Result<Vec<String>> {
+        let mut args = vec![
+            "update:republish".to_string(),
+            "--group".to_string(),
+            target.to_string(),
+        ];
+        match &ctx.branch {
+            Some(branch) => args.extend(["--destination-branch".to_string(), branch.clone()]),
+            None => args.extend(["--destination-channel".to_string(), ctx.channel.clone()]),
+        }
+        args.extend([
+            "--message".to_string(),
+            ctx.message
+                .clone()
+                .unwrap_or_else(|| format!("Roll back to {}", target)),
+        ]);
+        if let Some(platform) = ctx.platform {
+            args.extend([
+                "--platform".to_string(),
+                eas_platform(platform)?.to_string(),
+            ]);
+        }
+        args.extend(["--non-interactive".to_string(), "--json".to_string()]);
+        Ok(args)
+    }
+
+    /// Check the project can publish `ctx` without publishing anything
+    ///
+    /// Requires `eas.json`, an Expo app config with an `updates.url`, and
+    /// when `ctx.runtime_version` is set, a matching runtime version in the
+    /// app config (EAS reads the runtime version from there).
+    pub fn validate(&self, ctx: &OtaContext) -> Result<()> {
+        if ctx.channel.trim().is_empty() && ctx.branch.is_none() {
+            return Err(invalid("an EAS Update channel or branch is required"));
+        }
+        if let Some(platform) = ctx.platform {
+            eas_platform(platform)?;
+        }
+
+        let eas_json = ctx.path.join("eas.json");
+        let content = std::fs::read_to_string(&eas_json)
+            .map_err(|e| invalid(format!("cannot read {}: {}", eas_json.display(), e)))?;
+        serde_json::from_str::<serde_json::Value>(&content)
+            .map_err(|e| invalid(format!("invalid eas.json: {}", e)))?;
+
+        let Some(app) = read_app_config(&ctx.path)? else {
+            // app.config.js/ts can't be read statically; EAS checks it
+            debug!("dynamic Expo config, skipping app config checks");
+            return Ok(());
+        };
+        if app.updates.and_then(|u| u.url).is_none() {
+            return Err(invalid(
+                "app.json has no expo.updates.url; run `eas update:configure`",
+            ));
+        }
+        if let Some(wanted) = &ctx.runtime_version {
+            match resolve_runtime_version(app.runtime_version.as_ref(), app.version.as_deref()) {
+                Some(actual) if &actual != wanted => {
+                    return Err(invalid(format!(
+                        "app config targets runtime version {}, not {}",
+                        actual, wanted
+                    )));
+                }
+                Some(_) => {}
+                None => warn!(
+                    runtime_version = %wanted,
+                    "runtime version is computed by a policy, cannot check it before publishing"
+                ),
+            }
+        }
+        Ok(())
+    }
+
+    async fn run_eas(&self, args: &[String], path: &Path) -> Result<String> {
+        let command = self.command_line(args);
+        debug!(command = %command, "running eas");
+        let output = Command::new(&self.eas_binary)
+            .kill_on_drop(true)
+            .args(&self.eas_prefix_args)
+            .args(args)
+            .current_dir(path)
+            .output()
+            .await
+            .map_err(|e| FrameworkError::CommandFailed {
+                command: command.clone(),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: e.to_string(),
+            })?;
+
+        if !output.status.success() {
+            return Err(FrameworkError::CommandFailed {
+                command,
+                exit_code: output.status.code(),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    }
+}
+
+impl Default for EasUpdateAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl OtaAdapter for EasUpdateAdapter {
+    fn id(&self) -> &'static str {
+        "eas-update"
+    }
+
+    fn name(&self) -> &'static str {
+        "EAS Update"
+    }
+
+    fn detect(&self, path: &Path) -> Detection {
+        if !file_exists(path, "eas.json") {
+            return Detection::No;
+        }
+        if has_npm_dependency(path, "expo-updates") {
+            Detection::Yes(95)
+        } else if has_npm_dependency(path, "expo") {
+            Detection::Yes(80)
+        } else {
+            Detection::Maybe(40)
+        }
+    }
+
+    async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+        let status = match which::which(&self.eas_binary) {
+            Ok(_) => {
+                let version = Command::new(&self.eas_binary)
+                    .kill_on_drop(true)
+                    .args(&self.eas_prefix_args)
+                    .arg("--version")
+                    .output()
+                    .await
+                    .ok()
+                    .and_then(|o| String::from_utf8(o.stdout).ok())
+                    .map(|s| s.trim().to_string());
+                PrerequisiteStatus::ok().with_tool(ToolStatus::found("eas", version))
+            }
+            Err(_) => PrerequisiteStatus::ok().with_tool(ToolStatus::missing(
+                "eas",
+                "Install with: npm install -g eas-cli",
+            )),
+        };
+        Ok(status)
+    }
+
+    #[instrument(skip(self, ctx), fields(channel = %ctx.channel, dry_run = ctx.dry_run))]
+    async fn publish(&self, ctx: &OtaContext) -> Result<OtaResult> {
+        self.validate(ctx)?;
+        let args = Self::update_args(ctx)?;
+        if ctx.dry_run {
+            info!(command = %self.command_line(&args), "dry run, not publishing update");
+            return Ok(OtaResult {
+                id: "dry-run".to_string(),
+                channel: ctx.channel.clone(),
+                url: None,
+            });
+        }
+
+        let group = parse_update_output(&self.run_eas(&args, &ctx.path).await?)?;
+        info!(group = %group.id, platforms = ?group.platforms, "published EAS update");
+        Ok(group.into_result(&ctx.channel))
+    }
+
+    #[instrument(skip(self, ctx), fields(channel = %ctx.channel, dry_run = ctx.dry_run))]
+    async fn rollback(&self, ctx: &OtaContext, target: &str) -> Result<OtaResult> {
+        let args = Self::republish_args(ctx, target)?;
+        if ctx.dry_run {
+            info!(command = %self.command_line(&args), "dry run, not republishing update");
+            return Ok(OtaResult {
+                id: target.to_string(),
+                channel: ctx.channel.clone(),
+                url: None,
+            });
+        }
+
+        let group = parse_update_output(&self.run_eas(&args, &ctx.path).await?)?;
+        info!(group = %group.id, target, "republished EAS update");
+        Ok(group.into_result(&ctx.channel))
+    }
+}
+
+/// An update group published by `eas update`, one update per platform
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EasUpdateGroup {
+    /// Update group ID
+    pub id: String,
+    /// Branch the group was published to
+    pub branch: Option<String>,
+    /// Runtime version the group targets
+    pub runtime_version: Option<String>,
+    /// Platforms in the group
+    pub platforms: Vec<String>,
+    /// Link to one of the group's manifests
+    pub url: Option<String>,
+}
+
+impl EasUpdateGroup {
+    fn into_result(self, channel: &str) -> OtaResult {
+        OtaResult {
+            id: self.id,
+            channel: channel.to_string(),
+            url: self.url,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct EasUpdate {
+    group: String,
+    branch: Option<String>,
+    runtime_version: Option<String>,
+    platform: Option<String>,
+    manifest_permalink: Option<String>,
+}
+
+/// Parse the JSON printed by `eas update --json` (or `update:republish`)
+///
+/// The CLI prints an array with one update per platform, all sharing one
+/// group ID. Log lines around the JSON are skipped, and the last JSON
+/// document printed wins.
+pub fn parse_update_output(output: &str) -> Result<EasUpdateGroup> {
+    let json = last_json_document(output).ok_or_else(|| parse_error("no JSON in eas output"))?;
+    let updates: Vec<EasUpdate> = serde_json::from_value::<Vec<EasUpdate>>(json.clone())
+        .or_else(|_| serde_json::from_value::<EasUpdate>(json).map(|u| vec![u]))
+        .map_err(|e| parse_error(e.to_string()))?;
+
+    let first = updates
+        .first()
+        .ok_or_else(|| parse_error("eas published no updates"))?;
+    Ok(EasUpdateGroup {
+        id: first.group.clone(),
+        branch: first.branch.clone(),
+        runtime_version: first.runtime_version.clone(),
+        platforms: updates.iter().filter_map(|u| u.platform.clone()).collect(),
+        url: first.manifest_permalink.clone(),
+    })
+}
+
+/// The last JSON value in `output` that fills its lines
+///
+/// Log lines may contain brackets (`[expo] ...`), so a document must start
+/// a line and end one; anything else is skipped.
+fn last_json_document(output: &str) -> Option<serde_json::Value> {
+    let mut last = None;
+    let mut offset = 0;
+    while offset < output.len() {
+        let rest = &output[offset..];
+        if rest.trim_start_matches([' ', '\t']).starts_with(['[', '{']) {
+            let mut values = serde_json::Deserializer::from_str(rest).into_iter();
+            if let Some(Ok(value)) = values.next() {
+                let end = values.byte_offset();
+                let tail = &rest[end..];
+                if tail[..tail.find('\n').unwrap_or(tail.len())]
+                    .trim()
+                    .is_empty()
+                {
+                    last = Some(value);
+                    offset += end;
+                    continue;
+                }
+            }
+        }
+        offset += rest.find('\n').map_or(rest.len(), |i| i + 1);
+    }
+    last
+}
+
+fn eas_platform(platform: Platform) -> Result<&'static str> {
+    match platform {
+        Platform::Ios => Ok("ios"),
+        Platform::Android => Ok("android"),
+        other => Err(FrameworkError::UnsupportedPlatform {
+            platform: other.as_str().to_string(),
+            framework: "eas-update".to_string(),
+        }),
+    }
+}
+
+fn invalid(message: impl Into<String>) -> FrameworkError {
+    FrameworkError::InvalidConfig {
+        message: message.into(),
+    }
+}
+
+fn parse_error(message: impl Into<String>) -> FrameworkError {
+    FrameworkError::Context {
+        context: "parsing eas update output".to_string(),
+        message: message.into(),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AppJson {
+    expo: ExpoAppConfig,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExpoAppConfig {
+    version: Option<String>,
+    runtime_version: Option<serde_json::Value>,
+    updates: Option<UpdatesConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UpdatesConfig {
+    url: Option<String>,
+}
+
+/// The `expo` section of `app.json`, or `None` for dynamic configs
+fn read_app_config(path: &Path) -> Result<Option<ExpoAppConfig>> {
+    let app_json = path.join("app.json");
+    if !app_json.exists() {
+        if file_exists(path, "app.config.js") || file_exists(path, "app.config.ts") {
+            return Ok(None);
+        }
+        return Err(invalid("no Expo app config (app.json or app.config.js)"));
+    }
+    let content = std::fs::read_to_string(&app_json)?;
+    let app: AppJson =
+        serde_json::from_str(&content).map_err(|e| invalid(format!("invalid app.json: {}", e)))?;
+    Ok(Some(app.expo))
+}
+
+/// The runtime version an app config targets, if it can be known statically
+///
+/// `runtimeVersion` is either a literal or a policy; only the `appVersion`
+/// policy resolves without building.
+fn resolve_runtime_version(
+    runtime_version: Option<&serde_json::Value>,
+    app_version: Option<&str>,
+) -> Option<String> {
+    match runtime_version? {
+        serde_json::Value::String(version) => Some(version.clone()),
+        serde_json::Value::Object(policy)
+            if policy.get("policy").and_then(|p| p.as_str()) == Some("appVersion") =>
+        {
+            app_version.map(String::from)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_project(temp: &TempDir, runtime_version: &str) {
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{"name": "app", "dependencies": {"expo": "^51.0.0", "expo-updates": "~0.25.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("eas.json"),
+            r#"{"build": {"production": {"channel": "production"}}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("app.json"),
+            format!(
+                r#"{{"expo": {{"version": "1.4.0", "runtimeVersion": {}, "updates": {{"url": "https://u.expo.dev/abc"}}}}}}"#,
+                runtime_version
+            ),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_update_args() {
+        let ctx = OtaContext::new("/app", "production").with_message("Fix login crash");
+        assert_eq!(
+            EasUpdateAdapter::update_args(&ctx).unwrap(),
+            [
+                "update",
+                "--channel",
+                "production",
+                "--message",
+                "Fix login crash",
+                "--non-interactive",
+                "--json"
+            ]
+        );
+
+        let ctx = OtaContext::new("/app", "production")
+            .with_branch("release-1.4")
+            .with_platform(Platform::Ios);
+        let args = EasUpdateAdapter::update_args(&ctx).unwrap();
+        assert_eq!(args[1..3], ["--branch", "release-1.4"]);
+        assert_eq!(args[3..5], ["--message", "Update for production"]);
+        assert_eq!(args[5..7], ["--platform", "ios"]);
+
+        let ctx = OtaContext::new("/app", "production").with_platform(Platform::Web);
+        assert!(EasUpdateAdapter::update_args(&ctx).is_err());
+    }
+
+    #[test]
+    fn test_republish_args() {
+        let ctx = OtaContext::new("/app", "production");
+        let args = EasUpdateAdapter::republish_args(&ctx, "group-1").unwrap();
+        assert_eq!(
+            args,
+            [
+                "update:republish",
+                "--group",
+                "group-1",
+                "--destination-channel",
+                "production",
+                "--message",
+                "Roll back to group-1",
+                "--non-interactive",
+                "--json"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_update_output() {
+        let output = r#"Uploading assets...
+[
+  {"id": "u-ios", "group": "1f2e3d4c", "branch": "production", "runtimeVersion": "1.4.0", "platform": "ios", "manifestPermalink": "https://u.expo.dev/update/u-ios", "message": "Fix"},
+  {"id": "u-android", "group": "1f2e3d4c", "branch": "production", "runtimeVersion": "1.4.0", "platform": "android", "manifestPermalink": "https://u.expo.dev/update/u-android", "message": "Fix"}
+]"#;
+        let group = parse_update_output(output).unwrap();
+        assert_eq!(group.id, "1f2e3d4c");
+        assert_eq!(group.branch.as_deref(), Some("production"));
+        assert_eq!(group.runtime_version.as_deref(), Some("1.4.0"));
+        assert_eq!(group.platforms, ["ios", "android"]);
+        assert_eq!(
+            group.url.as_deref(),
+            Some("https://u.expo.dev/update/u-ios")
+        );
+
+        // Bracketed log lines and earlier JSON are skipped
+        let output = format!(
+            "[expo] Exporting bundle {{ios}}\n{{\"warning\": \"stale\"}}\n{}\n[expo] Done [2s]\n",
+            r#"[{"group": "9a8b", "platform": "ios"}]"#
+        );
+        let group = parse_update_output(&output).unwrap();
+        assert_eq!(group.id, "9a8b");
+        assert_eq!(group.platforms, ["ios"]);
+
+        assert!(parse_update_output("[]").is_err());
+        assert!(parse_update_output("Something went wrong").is_err());
+    }
+
+    #[test]
+    fn test_command_line() {
+        let args = vec!["update".to_string(), "--json".to_string()];
+        let adapter = EasUpdateAdapter::new().with_command("npx", ["eas-cli"]);
+        assert_eq!(adapter.command_line(&args), "npx eas-cli update --json");
+        let adapter = adapter.with_binary("/opt/eas");
+        assert_eq!(adapter.command_line(&args), "/opt/eas update --json");
+    }
+
+    #[test]
+    fn test_detection() {
+        let adapter = EasUpdateAdapter::new();
+        let temp = TempDir::new().unwrap();
+        assert!(!adapter.detect(temp.path()).detected());
+        create_project(&temp, r#""1.4.0""#);
+        assert!(adapter.detect(temp.path()).is_confident());
+    }
+
+    #[tokio::test]
+    async fn test_dry_run_validates_without_publishing() {
+        let temp = TempDir::new().unwrap();
+        create_project(&temp, r#"{"policy": "appVersion"}"#);
+        let adapter = EasUpdateAdapter::new().with_binary("eas-binary-that-does-not-exist");
+
+        let ctx = OtaContext::new(temp.path(), "production")
+            .with_runtime_version("1.4.0")
+            .with_dry_run(true);
+        let result = adapter.publish(&ctx).await.unwrap();
+        assert_eq!(result.id, "dry-run");
+        assert_eq!(result.channel, "production");
+
+        let ctx = ctx.with_runtime_version("2.0.0");
+        let err = adapter.publish(&ctx).await.unwrap_err();
+        assert!(err.to_string().contains("runtime version 1.4.0"));
+
+        std::fs::remove_file(temp.path().join("eas.json")).unwrap();
+        let ctx = OtaContext::new(temp.path(), "production").with_dry_run(true);
+        assert!(adapter.publish(&ctx).await.is_err());
+    }
+}
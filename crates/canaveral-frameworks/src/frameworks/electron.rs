@@ -0,0 +1,477 @@
+//! Electron distribution adapter
+//!
+//! Passes publishing through to electron-builder, which owns the publish
+//! providers (GitHub, S3, generic). Canaveral invokes it and maps the files it
+//! produces back into [`Artifact`]s.
+
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+use async_trait::async_trait;
+use regex::Regex;
+use tracing::{debug, info, instrument, warn};
+
+use crate::artifacts::{Arch, Artifact, ArtifactKind, ArtifactMetadata, ArtifactWalker};
+use crate::context::BuildContext;
+use crate::detection::{any_file_exists, has_npm_dependency, Detection};
+use crate::error::{FrameworkError, Result};
+use crate::traits::{
+    DistributeAdapter, DistributeContext, DistributeResult, DistributeStatus, Platform,
+    PrerequisiteStatus, ToolStatus,
+};
+
+/// Standalone electron-builder config files
+const CONFIG_FILES: &[&str] = &[
+    "electron-builder.yml",
+    "electron-builder.yaml",
+    "electron-builder.json",
+    "electron-builder.json5",
+    "electron-builder.toml",
+    "electron-builder.config.js",
+    "electron-builder.config.cjs",
+    "electron-builder.config.ts",
+];
+
+/// Default electron-builder output directory
+const DEFAULT_OUTPUT_DIR: &str = "dist";
+
+/// electron-builder publish passthrough
+pub struct ElectronAdapter {
+    /// Path to npx executable (auto-detected if None)
+    npx_path: Option<String>,
+}
+
+impl ElectronAdapter {
+    pub fn new() -> Self {
+        Self { npx_path: None }
+    }
+
+    pub fn with_npx_path(path: impl Into<String>) -> Self {
+        Self {
+            npx_path: Some(path.into()),
+        }
+    }
+
+    fn npx_cmd(&self) -> String {
+        self.npx_path.clone().unwrap_or_else(|| "npx".to_string())
+    }
+
+    /// Detect an electron-builder project
+    pub fn detect(&self, path: &Path) -> Detection {
+        let has_config_file = any_file_exists(path, CONFIG_FILES);
+        let has_dep = has_npm_dependency(path, "electron-builder");
+        let has_package_config = read_package_json(path)
+            .map(|json| json.get("build").is_some())
+            .unwrap_or(false);
+
+        if has_dep && (has_config_file || has_package_config) {
+            Detection::Yes(95)
+        } else if has_config_file {
+            Detection::Yes(85)
+        } else if has_dep {
+            Detection::Maybe(70)
+        } else {
+            Detection::No
+        }
+    }
+
+    /// Resolve the electron-builder output directory
+    /// (`directories.output`, defaulting to `dist/`)
+    pub fn output_dir(&self, path: &Path) -> PathBuf {
+        let from_package = read_package_json(path).and_then(|json| {
+            json.pointer("/build/directories/output")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        });
+
+        let from_yaml = || {
+            let re = Regex::new(r"(?m)^\s+output:\s*['\x22]?([^'\x22\s]+)").ok()?;
+            ["electron-builder.yml", "electron-builder.yaml"]
+                .iter()
+                .filter_map(|f| std::fs::read_to_string(path.join(f)).ok())
+                .find_map(|content| re.captures(&content).map(|c| c[1].to_string()))
+        };
+
+        path.join(
+            from_package
+                .or_else(from_yaml)
+                .unwrap_or_else(|| DEFAULT_OUTPUT_DIR.to_string()),
+        )
+    }
+
+    /// Run electron-builder and collect the produced artifacts.
+    ///
+    /// Publishes with `--publish always` unless the context is a dry run, in
+    /// which case `--publish never` is used and artifacts are only collected.
+    #[instrument(skip(self, ctx), fields(framework = "electron", platform = %ctx.platform.as_str()))]
+    pub async fn publish(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
+        let publish_mode = if ctx.dry_run { "never" } else { "always" };
+
+        let mut args = vec!["electron-builder", "--publish", publish_mode];
+        match ctx.platform {
+            Platform::MacOs => args.push("--mac"),
+            Platform::Windows => args.push("--win"),
+            Platform::Linux => args.push("--linux"),
+            other => {
+                return Err(FrameworkError::UnsupportedPlatform {
+                    platform: other.as_str().to_string(),
+                    framework: "electron-builder".to_string(),
+                })
+            }
+        }
+
+        info!("Running: npx {}", args.join(" "));
+
+        let npx = self.npx_cmd();
+        let output = Command::new(&npx)
+            .kill_on_drop(true)
+            .args(&args)
+            .envs(&ctx.env)
+            .current_dir(&ctx.path)
+            .output()
+            .await
+            .map_err(|e| FrameworkError::CommandFailed {
+                command: format!("{} {}", npx, args.join(" ")),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: e.to_string(),
+            })?;
+
+        let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+        if !output.status.success() {
+            return Err(FrameworkError::CommandFailed {
+                command: format!("{} {}", npx, args.join(" ")),
+                exit_code: output.status.code(),
+                stdout,
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
+        }
+
+        let mut files = parse_artifact_listing(&stdout);
+        let output_dir = self.output_dir(&ctx.path);
+        files.extend(scan_output_dir(&output_dir));
+
+        let version = read_package_json(&ctx.path).and_then(|json| {
+            json.get("version")
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        });
+
+        let mut artifacts: Vec<Artifact> = Vec::new();
+        for file in files {
+            let file = if file.is_absolute() {
+                file
+            } else {
+                ctx.path.join(file)
+            };
+            if artifacts.iter().any(|a| a.path == file) {
+                continue;
+            }
+            if let Some(artifact) = map_artifact(&file, ctx.platform, version.as_deref()) {
+                artifacts.push(artifact);
+            }
+        }
+
+        if artifacts.is_empty() {
+            warn!("No artifacts found in {:?}", output_dir);
+        } else {
+            info!("Found {} artifact(s)", artifacts.len());
+        }
+
+        Ok(artifacts)
+    }
+}
+
+impl Default for ElectronAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DistributeAdapter for ElectronAdapter {
+    fn id(&self) -> &'static str {
+        "electron-builder"
+    }
+
+    fn name(&self) -> &'static str {
+        "electron-builder"
+    }
+
+    async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+        let status = match which::which("npx") {
+            Ok(_) => PrerequisiteStatus::ok().with_tool(ToolStatus::found("npx", None)),
+            Err(_) => PrerequisiteStatus::ok().with_tool(ToolStatus::missing(
+                "npx",
+                "Install Node.js from https://nodejs.org",
+            )),
+        };
+
+        Ok(status)
+    }
+
+    async fn distribute(&self, ctx: &DistributeContext) -> Result<DistributeResult> {
+        let build_ctx =
+            BuildContext::new(&ctx.path, ctx.artifact.platform).with_dry_run(ctx.dry_run);
+        let artifacts = self.publish(&build_ctx).await?;
+
+        let id = artifacts
+            .iter()
+            .find_map(|a| a.metadata.version.clone())
+            .unwrap_or_else(|| "electron-builder".to_string());
+
+        Ok(DistributeResult {
+            id,
+            url: None,
+            groups: ctx.groups.clone(),
+        })
+    }
+
+    async fn status(&self, ctx: &DistributeContext) -> Result<DistributeStatus> {
+        // electron-builder publishes synchronously; there is nothing to poll
+        Ok(DistributeStatus {
+            id: ctx
+                .artifact
+                .metadata
+                .version
+                .clone()
+                .unwrap_or_else(|| "electron-builder".to_string()),
+            status: if ctx.dry_run { "skipped" } else { "published" }.to_string(),
+            install_count: None,
+        })
+    }
+}
+
+fn read_package_json(path: &Path) -> Option<serde_json::Value> {
+    let content = std::fs::read_to_string(path.join("package.json")).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Extract artifact paths from electron-builder log output.
+///
+/// electron-builder reports each produced file on the `file=` field of its
+/// `building target=...` lines, e.g.
+/// `  • building        target=DMG arch=arm64 file=dist/App-1.0.0-arm64.dmg`.
+/// File names may contain spaces, so the value runs until the next `key=`.
+pub fn parse_artifact_listing(output: &str) -> Vec<PathBuf> {
+    let re = Regex::new(r"\bfile=(.+?)(?:\s+[A-Za-z]+=|\s*$)").unwrap();
+
+    let mut files: Vec<PathBuf> = Vec::new();
+    for line in output.lines().filter(|l| l.contains("target=")) {
+        if let Some(caps) = re.captures(line) {
+            let file = PathBuf::from(caps[1].trim_matches('"'));
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    files
+}
+
+/// List the top-level files in the electron-builder output directory,
+/// skipping ignored ones
+fn scan_output_dir(dir: &Path) -> Vec<PathBuf> {
+    if !dir.is_dir() {
+        return Vec::new();
+    }
+    let files = match ArtifactWalker::new(dir).with_max_depth(1).walk() {
+        Ok(files) => files,
+        Err(e) => {
+            warn!("Cannot scan {:?}: {}", dir, e);
+            Vec::new()
+        }
+    };
+    debug!("Found {} file(s) in {:?}", files.len(), dir);
+    files
+}
+
+/// Map an electron-builder output file to an artifact.
+///
+/// Returns `None` for files that are not distributable (e.g.
+/// `builder-debug.yml`).
+pub fn map_artifact(
+    path: &Path,
+    default_platform: Platform,
+    version: Option<&str>,
+) -> Option<Artifact> {
+    let name = path.file_name()?.to_str()?;
+
+    let (kind, platform) = if name.ends_with(".blockmap") {
+        let target = Path::new(name.trim_end_matches(".blockmap"));
+        (
+            ArtifactKind::Blockmap,
+            infer_platform(target, default_platform),
+        )
+    } else if name.starts_with("latest") && (name.ends_with(".yml") || name.ends_with(".yaml")) {
+        let platform = if name.contains("-mac") {
+            Platform::MacOs
+        } else if name.contains("-linux") {
+            Platform::Linux
+        } else {
+            Platform::Windows
+        };
+        (ArtifactKind::UpdateManifest, platform)
+    } else {
+        let kind = ArtifactKind::from_path(path);
+        if matches!(kind, ArtifactKind::Other) {
+            return None;
+        }
+        (kind, infer_platform(path, default_platform))
+    };
+
+    let mut metadata = ArtifactMetadata::new()
+        .with_framework("electron")
+        .with_platform(platform);
+    if let Some(v) = version {
+        metadata = metadata.with_version(v);
+    }
+    // electron-builder names per-arch outputs `-arm64`, `-x64` or
+    // `-universal`; installers are x86 stubs, so headers can't tell
+    if let Some(arch) = Arch::from_file_name(name) {
+        metadata = metadata.with_arch(arch);
+    }
+
+    Some(
+        Artifact::new(path, kind, platform)
+            .with_metadata(metadata)
+            .with_sha256(),
+    )
+}
+
+fn infer_platform(path: &Path, default_platform: Platform) -> Platform {
+    match ArtifactKind::from_path(path) {
+        ArtifactKind::Dmg | ArtifactKind::Pkg | ArtifactKind::MacApp => Platform::MacOs,
+        ArtifactKind::Exe | ArtifactKind::Msi | ArtifactKind::Msix => Platform::Windows,
+        ArtifactKind::AppImage | ArtifactKind::Deb | ArtifactKind::Rpm | ArtifactKind::Snap => {
+            Platform::Linux
+        }
+        _ => {
+            let name = path.to_string_lossy().to_lowercase();
+            if name.contains("-mac") || name.contains("darwin") {
+                Platform::MacOs
+            } else if name.contains("-win") {
+                Platform::Windows
+            } else if name.contains("-linux") {
+                Platform::Linux
+            } else {
+                default_platform
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_electron_detection() {
+        let adapter = ElectronAdapter::new();
+        let temp = TempDir::new().unwrap();
+
+        assert!(!adapter.detect(temp.path()).detected());
+
+        std::fs::write(
+            temp.path().join("package.json"),
+            r#"{
+                "name": "my-app",
+                "version": "1.0.0",
+                "devDependencies": { "electron": "^30.0.0", "electron-builder": "^24.0.0" },
+                "build": { "appId": "com.example.app" }
+            }"#,
+        )
+        .unwrap();
+
+        let detection = adapter.detect(temp.path());
+        assert!(detection.detected());
+        assert!(detection.confidence() >= 90);
+    }
+
+    #[test]
+    fn test_electron_detection_config_file() {
+        let adapter = ElectronAdapter::new();
+        let temp = TempDir::new().unwrap();
+
+        std::fs::write(
+            temp.path().join("electron-builder.yml"),
+            "appId: com.example.app\ndirectories:\n  output: release\n",
+        )
+        .unwrap();
+
+        assert!(adapter.detect(temp.path()).detected());
+        assert_eq!(adapter.output_dir(temp.path()), temp.path().join("release"));
+    }
+
+    #[test]
+    fn test_parse_artifact_listing() {
+        let output = r#"
+  • electron-builder  version=24.13.3 os=23.4.0
+  • loaded configuration  file=package.json ("build" field)
+  • packaging       platform=darwin arch=arm64 electron=30.0.1 appOutDir=dist/mac-arm64
+  • building        target=DMG arch=arm64 file=dist/My App-1.0.0-arm64.dmg
+  • building        target=macOS zip arch=arm64 file=dist/My-App-1.0.0-arm64-mac.zip
+  • building block map  blockMapFile=dist/My-App-1.0.0-arm64.dmg.blockmap
+  • building        target=nsis file=dist/My-App-Setup-1.0.0.exe archs=x64
+  • building        target=AppImage arch=x64 file=dist/My-App-1.0.0.AppImage
+"#;
+
+        let files = parse_artifact_listing(output);
+        assert_eq!(
+            files,
+            vec![
+                PathBuf::from("dist/My App-1.0.0-arm64.dmg"),
+                PathBuf::from("dist/My-App-1.0.0-arm64-mac.zip"),
+                PathBuf::from("dist/My-App-Setup-1.0.0.exe"),
+                PathBuf::from("dist/My-App-1.0.0.AppImage"),
+            ]
+        );
+
+        let artifacts: Vec<Artifact> = files
+            .iter()
+            .filter_map(|f| map_artifact(f, Platform::MacOs, Some("1.0.0")))
+            .collect();
+        assert_eq!(artifacts.len(), 4);
+        assert_eq!(artifacts[0].kind, ArtifactKind::Dmg);
+        assert_eq!(artifacts[1].kind, ArtifactKind::Archive);
+        assert_eq!(artifacts[1].platform, Platform::MacOs);
+        assert_eq!(artifacts[2].kind, ArtifactKind::Exe);
+        assert_eq!(artifacts[2].platform, Platform::Windows);
+        assert_eq!(artifacts[3].kind, ArtifactKind::AppImage);
+        assert_eq!(artifacts[3].platform, Platform::Linux);
+        assert_eq!(artifacts[3].metadata.version.as_deref(), Some("1.0.0"));
+    }
+
+    #[test]
+    fn test_scan_output_dir_skips_ignored_files() {
+        let temp = TempDir::new().unwrap();
+        let dist = temp.path().join("dist");
+        std::fs::create_dir_all(dist.join("mac-arm64")).unwrap();
+        std::fs::write(dist.join(".gitignore"), "builder-debug.yml\n").unwrap();
+        std::fs::write(dist.join("App-1.0.0.dmg"), "dmg").unwrap();
+        std::fs::write(dist.join("builder-debug.yml"), "debug").unwrap();
+        std::fs::write(dist.join("mac-arm64").join("App"), "app").unwrap();
+
+        assert_eq!(scan_output_dir(&dist), vec![dist.join("App-1.0.0.dmg")]);
+        assert!(scan_output_dir(&temp.path().join("missing")).is_empty());
+    }
+
+    #[test]
+    fn test_map_update_files() {
+        let blockmap = map_artifact(
+            Path::new("dist/My-App-Setup-1.0.0.exe.blockmap"),
+            Platform::MacOs,
+            None,
+        )
+        .unwrap();
+        assert_eq!(blockmap.kind, ArtifactKind::Blockmap);
+        assert_eq!(blockmap.platform, Platform::Windows);
+
+        let latest_mac =
+            map_artifact(Path::new("dist/latest-mac.yml"), Platform::Linux, None).unwrap();
+        assert_eq!(latest_mac.kind, ArtifactKind::UpdateManifest);
+        assert_eq!(latest_mac.platform, Platform::MacOs);
+
+        assert!(map_artifact(Path::new("dist/builder-debug.yml"), Platform::MacOs, None).is_none());
+    }
+}
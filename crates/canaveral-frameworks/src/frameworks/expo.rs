@@ -3,7 +3,7 @@
 //! Supports building Expo and bare React Native apps using EAS Build or local builds.
 
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -39,11 +39,13 @@ impl ExpoAdapter {
         self
     }
 
-    fn run_npx(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_npx(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
         let output = Command::new("npx")
+            .kill_on_drop(true)
             .args(args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("npx {}", args.join(" ")),
                 exit_code: None,
@@ -54,11 +56,13 @@ impl ExpoAdapter {
         Ok(output)
     }
 
-    fn run_eas(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_eas(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
         let output = Command::new("eas")
+            .kill_on_drop(true)
             .args(args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("eas {}", args.join(" ")),
                 exit_code: None,
@@ -208,7 +212,7 @@ impl ExpoAdapter {
         // Local build option
         args.push("--local");
 
-        let output = self.run_eas(&args, &ctx.path)?;
+        let output = self.run_eas(&args, &ctx.path).await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -224,7 +228,9 @@ impl ExpoAdapter {
 
     async fn build_local(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
         // First, run expo prebuild to generate native projects
-        let prebuild_output = self.run_npx(&["expo", "prebuild", "--clean"], &ctx.path)?;
+        let prebuild_output = self
+            .run_npx(&["expo", "prebuild", "--clean"], &ctx.path)
+            .await?;
 
         if !prebuild_output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -277,9 +283,11 @@ impl ExpoAdapter {
         }
 
         let output = Command::new("xcodebuild")
+            .kill_on_drop(true)
             .args(&args)
             .current_dir(&ctx.path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("xcodebuild {}", args.join(" ")),
                 exit_code: None,
@@ -324,6 +332,7 @@ impl ExpoAdapter {
         })?;
 
         let output = Command::new("xcodebuild")
+            .kill_on_drop(true)
             .args([
                 "-exportArchive",
                 "-archivePath",
@@ -335,6 +344,7 @@ impl ExpoAdapter {
             ])
             .current_dir(&ctx.path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: "xcodebuild -exportArchive".to_string(),
                 exit_code: None,
@@ -367,9 +377,11 @@ impl ExpoAdapter {
         };
 
         let output = Command::new(gradle_wrapper)
+            .kill_on_drop(true)
             .arg(task)
             .current_dir(ctx.path.join("android"))
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("{} {}", gradle_wrapper, task),
                 exit_code: None,
@@ -389,7 +401,9 @@ impl ExpoAdapter {
     }
 
     async fn build_web(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
-        let output = self.run_npx(&["expo", "export", "--platform", "web"], &ctx.path)?;
+        let output = self
+            .run_npx(&["expo", "export", "--platform", "web"], &ctx.path)
+            .await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -635,8 +649,10 @@ impl BuildAdapter for ExpoAdapter {
         match which::which("node") {
             Ok(_) => {
                 let version = Command::new("node")
+                    .kill_on_drop(true)
                     .arg("--version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| String::from_utf8(o.stdout).ok())
                     .map(|s| s.trim().to_string());
@@ -665,8 +681,10 @@ impl BuildAdapter for ExpoAdapter {
         match which::which("eas") {
             Ok(_) => {
                 let version = Command::new("eas")
+                    .kill_on_drop(true)
                     .arg("--version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| String::from_utf8(o.stdout).ok())
                     .map(|s| s.trim().to_string());
@@ -701,7 +719,7 @@ impl BuildAdapter for ExpoAdapter {
 
     async fn clean(&self, path: &Path) -> Result<()> {
         // Clean Expo cache
-        let _ = self.run_npx(&["expo", "prebuild", "--clean"], path);
+        let _ = self.run_npx(&["expo", "prebuild", "--clean"], path).await;
 
         // Clean native builds
         let ios_build = path.join("ios/build");
@@ -3,7 +3,7 @@
 //! Supports building Flutter apps for iOS, Android, macOS, Windows, Linux, and Web.
 
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use tracing::{debug, info, instrument};
@@ -40,11 +40,13 @@ impl FlutterAdapter {
             .unwrap_or_else(|| "flutter".to_string())
     }
 
-    fn run_flutter(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_flutter(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
         let output = Command::new(self.flutter_cmd())
+            .kill_on_drop(true)
             .args(args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("flutter {}", args.join(" ")),
                 exit_code: None,
@@ -162,8 +164,10 @@ impl BuildAdapter for FlutterAdapter {
             Ok(_) => {
                 // Get flutter version
                 let version = Command::new("flutter")
+                    .kill_on_drop(true)
                     .args(["--version", "--machine"])
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         if o.status.success() {
@@ -243,7 +247,7 @@ impl BuildAdapter for FlutterAdapter {
 
         // Execute build
         let args_ref: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-        let output = self.run_flutter(&args_ref, &ctx.path)?;
+        let output = self.run_flutter(&args_ref, &ctx.path).await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -260,7 +264,7 @@ impl BuildAdapter for FlutterAdapter {
     }
 
     async fn clean(&self, path: &Path) -> Result<()> {
-        let output = self.run_flutter(&["clean"], path)?;
+        let output = self.run_flutter(&["clean"], path).await?;
 
         if !output.status.success() {
             return Err(FrameworkError::CommandFailed {
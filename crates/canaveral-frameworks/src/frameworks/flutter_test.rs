@@ -3,8 +3,8 @@
 //! Runs Flutter unit tests, widget tests, and integration tests.
 
 use std::path::Path;
-use std::process::Command;
 use std::time::Instant;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use tracing::{info, instrument};
@@ -67,6 +67,7 @@ impl FlutterTestAdapter {
                     TestStatus::Passed => passed += 1,
                     TestStatus::Failed => failed += 1,
                     TestStatus::Skipped => skipped += 1,
+                    TestStatus::Flaky => {}
                 }
 
                 let suite_name = caps.suite.unwrap_or_else(|| "default".to_string());
@@ -121,6 +122,7 @@ impl FlutterTestAdapter {
             passed,
             failed,
             skipped,
+            flaky: 0,
             duration_ms,
             suites: test_suites,
             coverage: None,
@@ -239,8 +241,10 @@ impl TestAdapter for FlutterTestAdapter {
         match which::which("flutter") {
             Ok(_) => {
                 let version = Command::new("flutter")
+                    .kill_on_drop(true)
                     .args(["--version", "--machine"])
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         if o.status.success() {
@@ -313,6 +317,7 @@ impl TestAdapter for FlutterTestAdapter {
                 passed: 0,
                 failed: 0,
                 skipped: 0,
+                flaky: 0,
                 duration_ms: 0,
                 suites: vec![],
                 coverage: None,
@@ -320,10 +325,12 @@ impl TestAdapter for FlutterTestAdapter {
         }
 
         let output = Command::new(self.flutter_cmd())
+            .kill_on_drop(true)
             .args(&args)
             .current_dir(&ctx.path)
             .envs(&ctx.env)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("flutter {}", args.join(" ")),
                 exit_code: None,
@@ -4,6 +4,8 @@
 //! for a specific framework (Flutter, Expo, React Native, native, etc.).
 
 pub mod astro;
+pub mod eas_update;
+pub mod electron;
 pub mod expo;
 pub mod flutter;
 pub mod flutter_test;
@@ -12,10 +14,13 @@ pub mod native_ios;
 pub mod nextjs;
 pub mod react_native;
 pub mod tauri;
+pub mod tauri_updater;
 pub mod vite;
 
 // Re-export adapters
 pub use astro::AstroAdapter;
+pub use eas_update::EasUpdateAdapter;
+pub use electron::ElectronAdapter;
 pub use expo::ExpoAdapter;
 pub use flutter::FlutterAdapter;
 pub use flutter_test::FlutterTestAdapter;
@@ -44,4 +49,10 @@ pub fn register_all(registry: &mut FrameworkRegistry) {
     // Test adapters
     registry.register_test(FlutterTestAdapter::new());
     registry.register_test(NativeIosAdapter::new());
+
+    // Distribution adapters
+    registry.register_distribute(ElectronAdapter::new());
+
+    // OTA adapters
+    registry.register_ota(EasUpdateAdapter::new());
 }
@@ -4,13 +4,13 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use regex::Regex;
 use tracing::{debug, info, instrument, warn};
 
-use crate::artifacts::{Artifact, ArtifactKind, ArtifactMetadata};
+use crate::artifacts::{Arch, Artifact, ArtifactKind, ArtifactMetadata};
 use crate::capabilities::{Capabilities, Capability};
 use crate::context::{BuildContext, BuildProfile};
 use crate::detection::{file_exists, Detection};
@@ -41,7 +41,7 @@ impl NativeAndroidAdapter {
     }
 
     /// Run gradle command
-    fn run_gradle(
+    async fn run_gradle(
         &self,
         args: &[&str],
         path: &Path,
@@ -50,6 +50,7 @@ impl NativeAndroidAdapter {
         let gradle = self.gradle_cmd(path);
 
         let mut cmd = Command::new(&gradle);
+        cmd.kill_on_drop(true);
         cmd.args(args).current_dir(path).envs(env);
 
         // Ensure ANDROID_HOME is set
@@ -61,12 +62,15 @@ impl NativeAndroidAdapter {
             }
         }
 
-        let output = cmd.output().map_err(|e| FrameworkError::CommandFailed {
-            command: format!("{} {}", gradle, args.join(" ")),
-            exit_code: None,
-            stdout: String::new(),
-            stderr: e.to_string(),
-        })?;
+        let output = cmd
+            .output()
+            .await
+            .map_err(|e| FrameworkError::CommandFailed {
+                command: format!("{} {}", gradle, args.join(" ")),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: e.to_string(),
+            })?;
 
         Ok(output)
     }
@@ -236,7 +240,13 @@ impl NativeAndroidAdapter {
                     if filename.contains("androidTest") || filename.contains("-test-") {
                         continue;
                     }
-                    let metadata = ArtifactMetadata::new().with_framework("native-android");
+                    // ABI splits are named after their ABI (`app-arm64-v8a-release.apk`)
+                    let mut metadata = ArtifactMetadata::new()
+                        .with_framework("native-android")
+                        .with_platform(Platform::Android);
+                    if let Some(arch) = Arch::from_file_name(&filename) {
+                        metadata = metadata.with_arch(arch);
+                    }
                     artifacts.push(
                         Artifact::new(&apk, ArtifactKind::Apk, Platform::Android)
                             .with_metadata(metadata),
@@ -250,9 +260,12 @@ impl NativeAndroidAdapter {
             for dir in &output_dirs {
                 if dir.exists() {
                     for aab in self.find_files_with_extension(dir, "aab") {
+                        // Bundles carry every ABI; Play serves the matching split
                         let metadata = ArtifactMetadata::new()
                             .with_framework("native-android")
-                            .with_signed(true);
+                            .with_signed(true)
+                            .with_platform(Platform::Android)
+                            .with_arch(Arch::Universal);
                         artifacts.push(
                             Artifact::new(&aab, ArtifactKind::Aab, Platform::Android)
                                 .with_metadata(metadata),
@@ -397,8 +410,10 @@ impl BuildAdapter for NativeAndroidAdapter {
         match which::which("java") {
             Ok(_) => {
                 let version = Command::new("java")
+                    .kill_on_drop(true)
                     .arg("-version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         // Java outputs version to stderr
@@ -421,8 +436,10 @@ impl BuildAdapter for NativeAndroidAdapter {
         match which::which("gradle") {
             Ok(_) => {
                 let version = Command::new("gradle")
+                    .kill_on_drop(true)
                     .arg("--version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         if o.status.success() {
@@ -546,7 +563,7 @@ impl BuildAdapter for NativeAndroidAdapter {
         }
 
         // Run build
-        let output = self.run_gradle(&args, path, &env)?;
+        let output = self.run_gradle(&args, path, &env).await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -562,7 +579,7 @@ impl BuildAdapter for NativeAndroidAdapter {
         // Build AAB if release
         if let Some(bundle) = bundle_task {
             let bundle_args = vec![&bundle[..], "--stacktrace"];
-            let output = self.run_gradle(&bundle_args, path, &env)?;
+            let output = self.run_gradle(&bundle_args, path, &env).await?;
 
             if !output.status.success() {
                 // Log warning but don't fail - APK was already built
@@ -586,7 +603,7 @@ impl BuildAdapter for NativeAndroidAdapter {
     }
 
     async fn clean(&self, path: &Path) -> Result<()> {
-        let output = self.run_gradle(&["clean"], path, &HashMap::new())?;
+        let output = self.run_gradle(&["clean"], path, &HashMap::new()).await?;
 
         if !output.status.success() {
             // Still try to clean manually
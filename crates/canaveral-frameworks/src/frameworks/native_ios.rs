@@ -5,13 +5,13 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use plist::Value as PlistValue;
 use tracing::{debug, info, instrument};
 
-use crate::artifacts::{Artifact, ArtifactKind, ArtifactMetadata};
+use crate::artifacts::{Arch, Artifact, ArtifactKind, ArtifactMetadata};
 use crate::capabilities::Capabilities;
 use crate::context::{BuildContext, BuildProfile, TestContext};
 use crate::detection::{file_exists, Detection};
@@ -86,7 +86,7 @@ impl NativeIosAdapter {
             }
         }
 
-        let output = Command::new("xcodebuild")
+        let output = std::process::Command::new("xcodebuild")
             .args(&args)
             .current_dir(path)
             .output()
@@ -360,9 +360,12 @@ impl NativeIosAdapter {
                 .into_iter()
                 .next()
         }) {
+            // Device builds are arm64-only
             let metadata = ArtifactMetadata::new()
                 .with_framework("native-ios")
-                .with_signed(true);
+                .with_signed(true)
+                .with_platform(Platform::Ios)
+                .with_arch(Arch::Arm64);
             artifacts
                 .push(Artifact::new(ipa, ArtifactKind::Ipa, Platform::Ios).with_metadata(metadata));
         }
@@ -374,7 +377,10 @@ impl NativeIosAdapter {
                 .into_iter()
                 .next()
             {
-                let metadata = ArtifactMetadata::new().with_framework("native-ios");
+                let metadata = ArtifactMetadata::new()
+                    .with_framework("native-ios")
+                    .with_platform(Platform::Ios)
+                    .with_arch(Arch::Arm64);
                 artifacts.push(
                     Artifact::new(archive, ArtifactKind::XcArchive, Platform::Ios)
                         .with_metadata(metadata),
@@ -390,9 +396,13 @@ impl NativeIosAdapter {
             {
                 continue;
             }
+            // Could be a simulator build, so read the executable
             let metadata = ArtifactMetadata::new().with_framework("native-ios");
-            artifacts
-                .push(Artifact::new(app, ArtifactKind::App, Platform::Ios).with_metadata(metadata));
+            artifacts.push(
+                Artifact::new(app, ArtifactKind::App, Platform::Ios)
+                    .with_metadata(metadata)
+                    .with_detected_arch(),
+            );
         }
 
         artifacts
@@ -506,6 +516,7 @@ impl NativeIosAdapter {
         if file_exists(path, "Podfile") && !file_exists(path, "Pods") {
             info!("installing CocoaPods dependencies");
             let output = tokio::process::Command::new("pod")
+                .kill_on_drop(true)
                 .args(["install"])
                 .current_dir(path)
                 .output()
@@ -595,8 +606,10 @@ impl BuildAdapter for NativeIosAdapter {
         match which::which("xcodebuild") {
             Ok(_) => {
                 let version = Command::new("xcodebuild")
+                    .kill_on_drop(true)
                     .arg("-version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         if o.status.success() {
@@ -637,8 +650,10 @@ impl BuildAdapter for NativeIosAdapter {
         match which::which("pod") {
             Ok(_) => {
                 let version = Command::new("pod")
+                    .kill_on_drop(true)
                     .arg("--version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| {
                         if o.status.success() {
@@ -896,6 +911,7 @@ impl TestAdapter for NativeIosAdapter {
                 passed: 0,
                 failed: 0,
                 skipped: 0,
+                flaky: 0,
                 duration_ms: 0,
                 suites: vec![],
                 coverage: None,
@@ -932,6 +948,7 @@ impl TestAdapter for NativeIosAdapter {
             passed: result.tests_passed,
             failed: result.tests_failed,
             skipped: result.tests_skipped,
+            flaky: 0,
             duration_ms: result.duration.as_millis() as u64,
             suites,
             coverage: None,
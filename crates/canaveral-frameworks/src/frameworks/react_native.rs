@@ -3,7 +3,7 @@
 //! Supports building bare React Native apps (without Expo).
 
 use std::path::Path;
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use serde::Deserialize;
@@ -44,11 +44,13 @@ impl ReactNativeAdapter {
         self
     }
 
-    fn run_npx(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_npx(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
         let output = Command::new("npx")
+            .kill_on_drop(true)
             .args(args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("npx {}", args.join(" ")),
                 exit_code: None,
@@ -116,7 +118,7 @@ impl ReactNativeAdapter {
 
         // Bundle JavaScript first for release builds
         if matches!(ctx.profile, BuildProfile::Release) {
-            self.bundle_js(&ctx.path, Platform::Ios)?;
+            self.bundle_js(&ctx.path, Platform::Ios).await?;
         }
 
         let workspace_path = workspace.to_string_lossy().to_string();
@@ -140,9 +142,11 @@ impl ReactNativeAdapter {
         }
 
         let output = Command::new("xcodebuild")
+            .kill_on_drop(true)
             .args(&args)
             .current_dir(&ctx.path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("xcodebuild {}", args.join(" ")),
                 exit_code: None,
@@ -199,7 +203,7 @@ impl ReactNativeAdapter {
         })
     }
 
-    fn bundle_js(&self, path: &Path, platform: Platform) -> Result<()> {
+    async fn bundle_js(&self, path: &Path, platform: Platform) -> Result<()> {
         let platform_name = match platform {
             Platform::Ios => "ios",
             Platform::Android => "android",
@@ -223,27 +227,29 @@ impl ReactNativeAdapter {
             std::fs::create_dir_all(path.join("android/app/src/main/assets")).ok();
         }
 
-        let output = self.run_npx(
-            &[
-                "react-native",
-                "bundle",
-                "--platform",
-                platform_name,
-                "--dev",
-                "false",
-                "--entry-file",
-                entry_file,
-                "--bundle-output",
-                bundle_path,
-                "--assets-dest",
-                match platform {
-                    Platform::Ios => "ios",
-                    Platform::Android => "android/app/src/main/res",
-                    _ => ".",
-                },
-            ],
-            path,
-        )?;
+        let output = self
+            .run_npx(
+                &[
+                    "react-native",
+                    "bundle",
+                    "--platform",
+                    platform_name,
+                    "--dev",
+                    "false",
+                    "--entry-file",
+                    entry_file,
+                    "--bundle-output",
+                    bundle_path,
+                    "--assets-dest",
+                    match platform {
+                        Platform::Ios => "ios",
+                        Platform::Android => "android/app/src/main/res",
+                        _ => ".",
+                    },
+                ],
+                path,
+            )
+            .await?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -277,6 +283,7 @@ impl ReactNativeAdapter {
         })?;
 
         let output = Command::new("xcodebuild")
+            .kill_on_drop(true)
             .args([
                 "-exportArchive",
                 "-archivePath",
@@ -288,6 +295,7 @@ impl ReactNativeAdapter {
             ])
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: "xcodebuild -exportArchive".to_string(),
                 exit_code: None,
@@ -309,7 +317,7 @@ impl ReactNativeAdapter {
     async fn build_android(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
         // Bundle JavaScript first for release builds
         if matches!(ctx.profile, BuildProfile::Release) {
-            self.bundle_js(&ctx.path, Platform::Android)?;
+            self.bundle_js(&ctx.path, Platform::Android).await?;
         }
 
         let task = match ctx.profile {
@@ -325,6 +333,7 @@ impl ReactNativeAdapter {
         };
 
         let mut cmd = Command::new(gradle_wrapper);
+        cmd.kill_on_drop(true);
         cmd.arg(task).current_dir(ctx.path.join("android"));
 
         // Pass Hermes setting
@@ -337,12 +346,15 @@ impl ReactNativeAdapter {
             cmd.env("RCT_NEW_ARCH_ENABLED", "1");
         }
 
-        let output = cmd.output().map_err(|e| FrameworkError::CommandFailed {
-            command: format!("{} {}", gradle_wrapper, task),
-            exit_code: None,
-            stdout: String::new(),
-            stderr: e.to_string(),
-        })?;
+        let output = cmd
+            .output()
+            .await
+            .map_err(|e| FrameworkError::CommandFailed {
+                command: format!("{} {}", gradle_wrapper, task),
+                exit_code: None,
+                stdout: String::new(),
+                stderr: e.to_string(),
+            })?;
 
         if !output.status.success() {
             return Err(FrameworkError::BuildFailed {
@@ -476,7 +488,7 @@ impl ReactNativeAdapter {
         let ios_dir = path.join("ios");
         let info_plist = self.find_info_plist(&ios_dir)?;
 
-        let output = Command::new("/usr/libexec/PlistBuddy")
+        let output = std::process::Command::new("/usr/libexec/PlistBuddy")
             .args([
                 "-c",
                 &format!("Set :CFBundleShortVersionString {}", version.version),
@@ -498,7 +510,7 @@ impl ReactNativeAdapter {
         }
 
         if let Some(bn) = version.build_number {
-            let output = Command::new("/usr/libexec/PlistBuddy")
+            let output = std::process::Command::new("/usr/libexec/PlistBuddy")
                 .args([
                     "-c",
                     &format!("Set :CFBundleVersion {}", bn),
@@ -687,8 +699,10 @@ impl BuildAdapter for ReactNativeAdapter {
         match which::which("node") {
             Ok(_) => {
                 let version = Command::new("node")
+                    .kill_on_drop(true)
                     .arg("--version")
                     .output()
+                    .await
                     .ok()
                     .and_then(|o| String::from_utf8(o.stdout).ok())
                     .map(|s| s.trim().to_string());
@@ -734,13 +748,15 @@ impl BuildAdapter for ReactNativeAdapter {
     async fn build(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
         info!(platform = %ctx.platform.as_str(), profile = ?ctx.profile, "building React Native project");
         // Install dependencies first
-        let install_output = self.run_npx(&["npm", "install"], &ctx.path)?;
+        let install_output = self.run_npx(&["npm", "install"], &ctx.path).await?;
         if !install_output.status.success() {
             // Try yarn
             let _ = Command::new("yarn")
+                .kill_on_drop(true)
                 .arg("install")
                 .current_dir(&ctx.path)
-                .output();
+                .output()
+                .await;
         }
 
         match ctx.platform {
@@ -773,7 +789,9 @@ impl BuildAdapter for ReactNativeAdapter {
         }
 
         // Clean Metro cache
-        let _ = self.run_npx(&["react-native", "start", "--reset-cache"], path);
+        let _ = self
+            .run_npx(&["react-native", "start", "--reset-cache"], path)
+            .await;
 
         Ok(())
     }
@@ -818,7 +836,7 @@ impl ReactNativeAdapter {
         let ios_dir = path.join("ios");
         let info_plist = self.find_info_plist(&ios_dir).ok()?;
 
-        let output = Command::new("/usr/libexec/PlistBuddy")
+        let output = std::process::Command::new("/usr/libexec/PlistBuddy")
             .args([
                 "-c",
                 "Print :CFBundleVersion",
@@ -3,20 +3,21 @@
 //! Supports building Tauri desktop apps for macOS, Windows, and Linux.
 
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use tokio::process::Command;
 
 use async_trait::async_trait;
 use regex::Regex;
 use tracing::{debug, info, instrument, warn};
 use walkdir::WalkDir;
 
-use crate::artifacts::{Artifact, ArtifactKind, ArtifactMetadata};
+use crate::artifacts::{Arch, Artifact, ArtifactKind, ArtifactMetadata};
 use crate::capabilities::Capabilities;
 #[cfg(test)]
 use crate::capabilities::Capability;
 use crate::context::{BuildContext, BuildProfile};
 use crate::detection::{file_exists, has_npm_dependency, Detection};
 use crate::error::{FrameworkError, Result};
+use crate::frameworks::tauri_updater::{self, UpdaterBundle, UpdaterConfig, UpdaterManifest};
 use crate::traits::{BuildAdapter, Platform, PrerequisiteStatus, ToolStatus, VersionInfo};
 
 /// Tauri build adapter
@@ -53,11 +54,13 @@ impl TauriAdapter {
             .unwrap_or_else(|| "cargo".to_string())
     }
 
-    fn run_cargo(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_cargo(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
         let output = Command::new(self.cargo_cmd())
+            .kill_on_drop(true)
             .args(args)
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("cargo {}", args.join(" ")),
                 exit_code: None,
@@ -68,7 +71,20 @@ impl TauriAdapter {
         Ok(output)
     }
 
-    fn run_tauri_cli(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+    async fn run_tauri_cli(&self, args: &[&str], path: &Path) -> Result<std::process::Output> {
+        self.run_tauri_cli_with_env(args, path, &[]).await
+    }
+
+    /// Run tauri CLI command with extra environment variables
+    ///
+    /// Secrets go through `envs` so they never show up in the process list
+    /// or the debug log.
+    async fn run_tauri_cli_with_env(
+        &self,
+        args: &[&str],
+        path: &Path,
+        envs: &[(&str, &str)],
+    ) -> Result<std::process::Output> {
         let (cmd, all_args) = if self.use_npm_cli {
             // Use npm/npx to run tauri
             let mut npx_args = vec!["tauri"];
@@ -84,9 +100,12 @@ impl TauriAdapter {
         debug!("Running: {} {}", cmd, all_args.join(" "));
 
         let output = Command::new(&cmd)
+            .kill_on_drop(true)
             .args(&all_args)
+            .envs(envs.iter().copied())
             .current_dir(path)
             .output()
+            .await
             .map_err(|e| FrameworkError::CommandFailed {
                 command: format!("{} {}", cmd, all_args.join(" ")),
                 exit_code: None,
@@ -271,114 +290,227 @@ impl TauriAdapter {
 
         let mut artifacts = Vec::new();
 
-        // Tauri outputs to target/release/bundle/<platform>/
-        let bundle_dir = target_dir.join("release/bundle");
-        if !bundle_dir.exists() {
-            debug!("Bundle directory does not exist: {:?}", bundle_dir);
+        // Tauri outputs host builds to target/release/bundle/<platform>/ and
+        // cross builds to target/<triple>/release/bundle/<platform>/
+        let bundle_dirs = bundle_dirs(&target_dir);
+        if bundle_dirs.is_empty() {
+            debug!("No bundle directories found in {:?}", target_dir);
             return Ok(artifacts);
         }
 
         // Get version for metadata
         let version = self.parse_cargo_version(path).ok();
 
-        // Walk the bundle directory and find artifacts
-        for entry in WalkDir::new(&bundle_dir).max_depth(3) {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
+        for (bundle_dir, triple) in &bundle_dirs {
+            let triple = triple.as_deref();
 
-            let entry_path = entry.path();
-            if !entry_path.is_file() && !entry_path.is_dir() {
-                continue;
-            }
+            // Walk the bundle directory and find artifacts
+            for entry in WalkDir::new(bundle_dir).max_depth(3) {
+                let entry = match entry {
+                    Ok(e) => e,
+                    Err(_) => continue,
+                };
 
-            let kind = match entry_path.extension().and_then(|e| e.to_str()) {
-                Some("dmg") if platform == Platform::MacOs => ArtifactKind::Dmg,
-                Some("app") if platform == Platform::MacOs && entry_path.is_dir() => {
-                    ArtifactKind::MacApp
+                let entry_path = entry.path();
+                if !entry_path.is_file() && !entry_path.is_dir() {
+                    continue;
                 }
-                Some("pkg") if platform == Platform::MacOs => ArtifactKind::Pkg,
-                Some("exe") if platform == Platform::Windows => ArtifactKind::Exe,
-                Some("msi") if platform == Platform::Windows => ArtifactKind::Msi,
-                Some("deb") if platform == Platform::Linux => ArtifactKind::Deb,
-                Some("rpm") if platform == Platform::Linux => ArtifactKind::Rpm,
-                Some("AppImage") if platform == Platform::Linux => ArtifactKind::AppImage,
-                _ => {
-                    // Check for AppImage without extension
-                    if platform == Platform::Linux
-                        && entry_path
-                            .file_name()
-                            .map(|n| n.to_string_lossy().contains(".AppImage"))
-                            .unwrap_or(false)
+
+                let kind = match entry_path.extension().and_then(|e| e.to_str()) {
+                    Some("dmg") if platform == Platform::MacOs => ArtifactKind::Dmg,
+                    Some("app") if platform == Platform::MacOs && entry_path.is_dir() => {
+                        ArtifactKind::MacApp
+                    }
+                    Some("pkg") if platform == Platform::MacOs => ArtifactKind::Pkg,
+                    Some("exe") if platform == Platform::Windows => ArtifactKind::Exe,
+                    Some("msi") if platform == Platform::Windows => ArtifactKind::Msi,
+                    Some("deb") if platform == Platform::Linux => ArtifactKind::Deb,
+                    Some("rpm") if platform == Platform::Linux => ArtifactKind::Rpm,
+                    Some("AppImage") if platform == Platform::Linux => ArtifactKind::AppImage,
+                    _ => {
+                        // Check for AppImage without extension
+                        if platform == Platform::Linux
+                            && entry_path
+                                .file_name()
+                                .map(|n| n.to_string_lossy().contains(".AppImage"))
+                                .unwrap_or(false)
+                        {
+                            ArtifactKind::AppImage
+                        } else {
+                            continue;
+                        }
+                    }
+                };
+
+                let metadata = ArtifactMetadata::new()
+                    .with_framework("tauri")
+                    .with_signed(false);
+
+                let metadata = if let Some(ref v) = version {
+                    metadata.with_version(v)
+                } else {
+                    metadata
+                };
+
+                let artifact = Artifact::new(entry_path.to_path_buf(), kind, platform)
+                    .with_metadata(with_build_target(metadata, entry_path, platform, triple))
+                    .with_sha256();
+
+                artifacts.push(artifact);
+            }
+
+            // Also check for platform-specific directories
+            let platform_dirs = match platform {
+                Platform::MacOs => vec!["macos", "dmg", "app"],
+                Platform::Windows => vec!["msi", "nsis"],
+                Platform::Linux => vec!["deb", "rpm", "appimage"],
+                _ => vec![],
+            };
+
+            for dir_name in platform_dirs {
+                let platform_dir = bundle_dir.join(dir_name);
+                if platform_dir.exists() {
+                    for entry in std::fs::read_dir(&platform_dir)
+                        .into_iter()
+                        .flatten()
+                        .flatten()
                     {
-                        ArtifactKind::AppImage
-                    } else {
-                        continue;
+                        let entry_path = entry.path();
+                        let kind = ArtifactKind::from_path(&entry_path);
+                        if !matches!(kind, ArtifactKind::Other) {
+                            let metadata = ArtifactMetadata::new()
+                                .with_framework("tauri")
+                                .with_signed(false);
+
+                            let metadata = if let Some(ref v) = version {
+                                metadata.with_version(v)
+                            } else {
+                                metadata
+                            };
+
+                            // Avoid duplicates
+                            if !artifacts.iter().any(|a| a.path == entry_path) {
+                                let metadata =
+                                    with_build_target(metadata, &entry_path, platform, triple);
+                                let artifact = Artifact::new(entry_path, kind, platform)
+                                    .with_metadata(metadata)
+                                    .with_sha256();
+
+                                artifacts.push(artifact);
+                            }
+                        }
                     }
                 }
-            };
+            }
+        }
 
-            let metadata = ArtifactMetadata::new()
-                .with_framework("tauri")
-                .with_signed(false);
+        Ok(artifacts)
+    }
 
-            let metadata = if let Some(ref v) = version {
-                metadata.with_version(v)
-            } else {
-                metadata
-            };
+    /// Sign an updater bundle with `tauri signer sign`, returning the signature
+    async fn sign_updater_bundle(
+        &self,
+        path: &Path,
+        bundle: &UpdaterBundle,
+        config: &UpdaterConfig,
+    ) -> Result<String> {
+        let key = config
+            .private_key
+            .as_deref()
+            .ok_or_else(|| FrameworkError::InvalidConfig {
+                message: format!(
+                    "No updater signing key configured (set updater_private_key or {})",
+                    tauri_updater::PRIVATE_KEY_ENV
+                ),
+            })?;
 
-            let artifact = Artifact::new(entry_path.to_path_buf(), kind, platform)
-                .with_metadata(metadata)
-                .with_sha256();
+        let bundle_path = bundle.path.to_string_lossy().to_string();
+        let mut args = vec!["signer", "sign"];
+        let mut envs = Vec::new();
+        if Path::new(key).is_file() {
+            args.extend(["--private-key-path", key]);
+        } else {
+            envs.push((tauri_updater::PRIVATE_KEY_ENV, key));
+        }
+        if let Some(ref password) = config.key_password {
+            envs.push((tauri_updater::KEY_PASSWORD_ENV, password.as_str()));
+        }
+        args.push(&bundle_path);
 
-            artifacts.push(artifact);
+        let output = self.run_tauri_cli_with_env(&args, path, &envs).await?;
+        if !output.status.success() {
+            return Err(FrameworkError::CommandFailed {
+                command: format!("tauri signer sign {}", bundle_path),
+                exit_code: output.status.code(),
+                stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+            });
         }
 
-        // Also check for platform-specific directories
-        let platform_dirs = match platform {
-            Platform::MacOs => vec!["macos", "dmg", "app"],
-            Platform::Windows => vec!["msi", "nsis"],
-            Platform::Linux => vec!["deb", "rpm", "appimage"],
-            _ => vec![],
-        };
+        std::fs::read_to_string(bundle.signature_path()).map_err(|e| FrameworkError::Context {
+            context: "reading updater signature".to_string(),
+            message: e.to_string(),
+        })
+    }
 
-        for dir_name in platform_dirs {
-            let platform_dir = bundle_dir.join(dir_name);
-            if platform_dir.exists() {
-                for entry in std::fs::read_dir(&platform_dir)
-                    .into_iter()
-                    .flatten()
-                    .flatten()
-                {
-                    let entry_path = entry.path();
-                    let kind = ArtifactKind::from_path(&entry_path);
-                    if !matches!(kind, ArtifactKind::Other) {
-                        let metadata = ArtifactMetadata::new()
-                            .with_framework("tauri")
-                            .with_signed(false);
-
-                        let metadata = if let Some(ref v) = version {
-                            metadata.with_version(v)
-                        } else {
-                            metadata
-                        };
+    /// Sign updater bundles and write the `latest.json` updater manifest.
+    ///
+    /// Returns `None` when the build produced no updater bundles.
+    async fn generate_updater_manifest(&self, ctx: &BuildContext) -> Result<Option<Artifact>> {
+        let tauri_dir = self
+            .find_tauri_dir(&ctx.path)
+            .unwrap_or_else(|| ctx.path.clone());
+        let bundle_dir = tauri_dir.join("target/release/bundle");
+        let Some(target) = tauri_updater::updater_target(ctx.platform, std::env::consts::ARCH)
+        else {
+            return Ok(None);
+        };
 
-                        // Avoid duplicates
-                        if !artifacts.iter().any(|a| a.path == entry_path) {
-                            let artifact = Artifact::new(entry_path, kind, platform)
-                                .with_metadata(metadata)
-                                .with_sha256();
+        let mut bundles = tauri_updater::find_updater_bundles(&bundle_dir, &target);
+        if bundles.is_empty() {
+            debug!("No updater bundles found in {:?}", bundle_dir);
+            return Ok(None);
+        }
 
-                            artifacts.push(artifact);
-                        }
-                    }
-                }
+        let config = UpdaterConfig::from_context(ctx);
+        for bundle in bundles.iter_mut().filter(|b| b.signature.is_none()) {
+            if !config.can_sign() {
+                warn!(
+                    "Updater bundle {:?} is unsigned and no signing key is configured; skipping latest.json",
+                    bundle.path
+                );
+                return Ok(None);
             }
+            info!("Signing updater bundle {:?}", bundle.path);
+            bundle.signature = Some(self.sign_updater_bundle(&ctx.path, bundle, &config).await?);
         }
 
-        Ok(artifacts)
+        let version = match ctx.version {
+            Some(ref v) => v.clone(),
+            None => self.get_version(&ctx.path)?.version,
+        };
+        let mut manifest = UpdaterManifest::new(&version).with_pub_date(chrono::Utc::now());
+        if let Some(ref notes) = config.notes {
+            manifest = manifest.with_notes(notes);
+        }
+        if config.base_url.is_none() {
+            warn!("No updater_base_url configured; latest.json will use relative URLs");
+        }
+        manifest.add_bundles(&bundles, config.base_url.as_deref())?;
+
+        let out_dir = ctx.output_dir.clone().unwrap_or(bundle_dir);
+        std::fs::create_dir_all(&out_dir)?;
+        let manifest_path = out_dir.join(tauri_updater::MANIFEST_FILE_NAME);
+        manifest.write(&manifest_path)?;
+
+        let metadata = ArtifactMetadata::new()
+            .with_framework("tauri")
+            .with_version(version);
+        Ok(Some(
+            Artifact::new(manifest_path, ArtifactKind::UpdateManifest, ctx.platform)
+                .with_metadata(metadata)
+                .with_sha256(),
+        ))
     }
 
     /// Determine which package manager is being used
@@ -527,9 +659,11 @@ impl BuildAdapter for TauriAdapter {
                 };
                 let parts: Vec<&str> = install_cmd.split_whitespace().collect();
                 let output = Command::new(parts[0])
+                    .kill_on_drop(true)
                     .args(&parts[1..])
                     .current_dir(project_path)
                     .output()
+                    .await
                     .map_err(|e| FrameworkError::CommandFailed {
                         command: install_cmd.to_string(),
                         exit_code: None,
@@ -585,7 +719,7 @@ impl BuildAdapter for TauriAdapter {
 
         info!("Running: cargo tauri {}", args.join(" "));
 
-        let output = self.run_tauri_cli(&args, project_path)?;
+        let output = self.run_tauri_cli(&args, project_path).await?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -600,7 +734,13 @@ impl BuildAdapter for TauriAdapter {
         }
 
         // Find and return artifacts
-        let artifacts = self.find_artifacts(project_path, platform)?;
+        let mut artifacts = self.find_artifacts(project_path, platform)?;
+
+        if !is_debug {
+            if let Some(manifest) = self.generate_updater_manifest(ctx).await? {
+                artifacts.push(manifest);
+            }
+        }
 
         if artifacts.is_empty() {
             warn!("No artifacts found after build");
@@ -624,7 +764,7 @@ impl BuildAdapter for TauriAdapter {
         let target_dir = tauri_dir.join("target");
 
         if target_dir.exists() {
-            let output = self.run_cargo(&["clean"], &tauri_dir)?;
+            let output = self.run_cargo(&["clean"], &tauri_dir).await?;
 
             if !output.status.success() {
                 warn!(
@@ -708,6 +848,59 @@ impl BuildAdapter for TauriAdapter {
     }
 }
 
+/// Release bundle directories under `target`, with the triple of cross builds
+///
+/// The host bundle directory comes first, followed by cross builds sorted
+/// by triple.
+fn bundle_dirs(target_dir: &Path) -> Vec<(PathBuf, Option<String>)> {
+    let mut dirs = Vec::new();
+    let host = target_dir.join("release/bundle");
+    if host.is_dir() {
+        dirs.push((host, None));
+    }
+
+    let mut cross: Vec<_> = std::fs::read_dir(target_dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|entry| {
+            let triple = entry.file_name().to_str()?.to_string();
+            let bundle = entry.path().join("release/bundle");
+            bundle.is_dir().then_some((bundle, Some(triple)))
+        })
+        .collect();
+    cross.sort();
+    dirs.extend(cross);
+    dirs
+}
+
+/// Tag a bundle with the target it was built for
+///
+/// The bundle name wins when it names an architecture (e.g. `_universal.dmg`).
+/// Otherwise cross builds take it from their target triple and host builds
+/// from the host.
+fn with_build_target(
+    metadata: ArtifactMetadata,
+    path: &Path,
+    platform: Platform,
+    triple: Option<&str>,
+) -> ArtifactMetadata {
+    let from_target = match triple {
+        Some(triple) => Arch::from_target_triple(triple),
+        None => Arch::host(),
+    };
+    let arch = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .and_then(Arch::from_file_name)
+        .or(from_target);
+    let metadata = metadata.with_platform(platform);
+    match arch {
+        Some(arch) => metadata.with_arch(arch),
+        None => metadata,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -817,6 +1010,42 @@ edition = "2021"
         assert!(conf_content.contains(r#""version": "2.0.0""#));
     }
 
+    #[test]
+    fn test_cross_build_arch_from_target_triple() {
+        let adapter = TauriAdapter::new();
+        let temp = TempDir::new().unwrap();
+        let target = temp.path().join("src-tauri/target");
+        for (dir, name) in [
+            ("release/bundle/deb", "host.deb"),
+            ("aarch64-unknown-linux-gnu/release/bundle/deb", "cross.deb"),
+            (
+                "i686-unknown-linux-gnu/release/bundle/deb",
+                "app_1.0.0_amd64.deb",
+            ),
+        ] {
+            std::fs::create_dir_all(target.join(dir)).unwrap();
+            std::fs::write(target.join(dir).join(name), b"deb").unwrap();
+        }
+
+        let artifacts = adapter
+            .find_artifacts(temp.path(), Platform::Linux)
+            .unwrap();
+        let arch_of = |name: &str| {
+            artifacts
+                .iter()
+                .find(|a| a.path.file_name().unwrap() == name)
+                .unwrap()
+                .metadata
+                .arch
+        };
+
+        assert_eq!(artifacts.len(), 3);
+        assert_eq!(arch_of("host.deb"), Arch::host());
+        assert_eq!(arch_of("cross.deb"), Some(Arch::Arm64));
+        // The bundle name still wins over the triple
+        assert_eq!(arch_of("app_1.0.0_amd64.deb"), Some(Arch::X86_64));
+    }
+
     #[test]
     fn test_package_manager_detection() {
         let adapter = TauriAdapter::new();
@@ -846,4 +1075,49 @@ edition = "2021"
         assert!(!platforms.contains(&Platform::Ios));
         assert!(!platforms.contains(&Platform::Android));
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_sign_updater_bundle_keeps_secrets_off_argv() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = TempDir::new().unwrap();
+        let cargo = temp.path().join("cargo");
+        std::fs::write(
+            &cargo,
+            r#"#!/bin/sh
+echo "$@" > args.txt
+echo "$TAURI_SIGNING_PRIVATE_KEY:$TAURI_SIGNING_PRIVATE_KEY_PASSWORD" > env.txt
+for last; do :; done
+echo signature > "$last.sig"
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&cargo, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let bundle = UpdaterBundle {
+            path: temp.path().join("app.tar.gz"),
+            target: "linux-x86_64".to_string(),
+            installer: "appimage".to_string(),
+            signature: None,
+        };
+        let config = UpdaterConfig {
+            private_key: Some("secret-key".to_string()),
+            key_password: Some("hunter2".to_string()),
+            ..Default::default()
+        };
+        let adapter = TauriAdapter::with_cargo_path(cargo.to_string_lossy());
+        let signature = adapter
+            .sign_updater_bundle(temp.path(), &bundle, &config)
+            .await
+            .unwrap();
+        assert_eq!(signature.trim(), "signature");
+
+        let args = std::fs::read_to_string(temp.path().join("args.txt")).unwrap();
+        assert!(args.starts_with("tauri signer sign "));
+        assert!(!args.contains("secret-key"));
+        assert!(!args.contains("hunter2"));
+        let env = std::fs::read_to_string(temp.path().join("env.txt")).unwrap();
+        assert_eq!(env.trim(), "secret-key:hunter2");
+    }
 }
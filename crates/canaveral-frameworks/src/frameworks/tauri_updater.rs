@@ -0,0 +1,442 @@
+//! Tauri updater support
+//!
+//! Locates the updater bundles produced by `tauri build`, collects their
+//! minisign signatures and assembles the `latest.json` manifest consumed by
+//! the Tauri updater plugin.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use walkdir::WalkDir;
+
+use crate::context::BuildContext;
+use crate::error::{FrameworkError, Result};
+use crate::traits::Platform;
+
+/// Env var holding the updater private key (Tauri v2)
+pub const PRIVATE_KEY_ENV: &str = "TAURI_SIGNING_PRIVATE_KEY";
+/// Env var holding the updater private key password (Tauri v2)
+pub const KEY_PASSWORD_ENV: &str = "TAURI_SIGNING_PRIVATE_KEY_PASSWORD";
+/// Env var holding the updater private key (Tauri v1)
+pub const LEGACY_PRIVATE_KEY_ENV: &str = "TAURI_PRIVATE_KEY";
+/// Env var holding the updater private key password (Tauri v1)
+pub const LEGACY_KEY_PASSWORD_ENV: &str = "TAURI_KEY_PASSWORD";
+
+/// File name of the generated updater manifest
+pub const MANIFEST_FILE_NAME: &str = "latest.json";
+
+/// Updater settings resolved from build config and environment
+#[derive(Debug, Clone, Default)]
+pub struct UpdaterConfig {
+    /// Minisign private key (contents or path) used to sign bundles
+    pub private_key: Option<String>,
+    /// Password for the private key
+    pub key_password: Option<String>,
+    /// Base URL the bundles will be served from
+    pub base_url: Option<String>,
+    /// Release notes embedded in the manifest
+    pub notes: Option<String>,
+}
+
+impl UpdaterConfig {
+    /// Resolve updater settings for a build.
+    ///
+    /// Reads `updater_private_key`, `updater_key_password`, `updater_base_url`
+    /// and `updater_notes` from the framework config, falling back to the
+    /// standard Tauri signing env vars for the key and password.
+    pub fn from_context(ctx: &BuildContext) -> Self {
+        let config_str = |key: &str| {
+            ctx.config
+                .get(key)
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+        };
+        let env_var = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                ctx.env
+                    .get(*name)
+                    .cloned()
+                    .or_else(|| std::env::var(name).ok())
+                    .filter(|v| !v.is_empty())
+            })
+        };
+
+        Self {
+            private_key: config_str("updater_private_key")
+                .or_else(|| env_var(&[PRIVATE_KEY_ENV, LEGACY_PRIVATE_KEY_ENV])),
+            key_password: config_str("updater_key_password")
+                .or_else(|| env_var(&[KEY_PASSWORD_ENV, LEGACY_KEY_PASSWORD_ENV])),
+            base_url: config_str("updater_base_url"),
+            notes: config_str("updater_notes"),
+        }
+    }
+
+    /// Whether a signing key is available
+    pub fn can_sign(&self) -> bool {
+        self.private_key.is_some()
+    }
+}
+
+/// An updater bundle found in the Tauri bundle directory
+#[derive(Debug, Clone)]
+pub struct UpdaterBundle {
+    /// Path to the bundle
+    pub path: PathBuf,
+    /// Updater target (e.g. "darwin-aarch64")
+    pub target: String,
+    /// Installer type (e.g. "app", "appimage", "msi", "nsis")
+    pub installer: String,
+    /// Minisign signature contents, if signed
+    pub signature: Option<String>,
+}
+
+impl UpdaterBundle {
+    /// Path of the detached signature file next to the bundle
+    pub fn signature_path(&self) -> PathBuf {
+        signature_path(&self.path)
+    }
+
+    /// Bundle file name
+    pub fn file_name(&self) -> Option<&str> {
+        self.path.file_name().and_then(|n| n.to_str())
+    }
+}
+
+/// Download entry for one platform in the updater manifest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpdaterPlatform {
+    /// Minisign signature of the bundle
+    pub signature: String,
+    /// Download URL of the bundle
+    pub url: String,
+}
+
+/// Tauri updater manifest (`latest.json`)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UpdaterManifest {
+    /// Version being released
+    pub version: String,
+    /// Release notes
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Publication date (RFC 3339)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pub_date: Option<chrono::DateTime<chrono::Utc>>,
+    /// Downloads keyed by updater target
+    pub platforms: BTreeMap<String, UpdaterPlatform>,
+}
+
+impl UpdaterManifest {
+    /// Create an empty manifest for a version
+    pub fn new(version: impl Into<String>) -> Self {
+        Self {
+            version: version.into(),
+            notes: None,
+            pub_date: None,
+            platforms: BTreeMap::new(),
+        }
+    }
+
+    /// Set release notes
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    /// Set publication date
+    pub fn with_pub_date(mut self, date: chrono::DateTime<chrono::Utc>) -> Self {
+        self.pub_date = Some(date);
+        self
+    }
+
+    /// Add the downloads for a set of signed bundles.
+    ///
+    /// Each bundle is registered under `<target>-<installer>` and, if no other
+    /// bundle claimed it yet, under the plain `<target>` key. URLs are built by
+    /// joining `base_url` with the bundle file name; without a base URL the
+    /// bare file name is used.
+    pub fn add_bundles(&mut self, bundles: &[UpdaterBundle], base_url: Option<&str>) -> Result<()> {
+        for bundle in bundles {
+            let signature = bundle.signature.clone().ok_or_else(|| {
+                FrameworkError::context(
+                    "assembling updater manifest",
+                    format!("bundle {} has no signature", bundle.path.display()),
+                )
+            })?;
+            let file_name = bundle.file_name().ok_or_else(|| {
+                FrameworkError::context(
+                    "assembling updater manifest",
+                    format!("invalid bundle path {}", bundle.path.display()),
+                )
+            })?;
+
+            let url = match base_url {
+                Some(base) => format!("{}/{}", base.trim_end_matches('/'), file_name),
+                None => file_name.to_string(),
+            };
+            let entry = UpdaterPlatform {
+                signature: signature.trim().to_string(),
+                url,
+            };
+
+            self.platforms.insert(
+                format!("{}-{}", bundle.target, bundle.installer),
+                entry.clone(),
+            );
+            self.platforms.entry(bundle.target.clone()).or_insert(entry);
+        }
+
+        Ok(())
+    }
+
+    /// Serialize to pretty JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| FrameworkError::Serialization(e.to_string()))
+    }
+
+    /// Write the manifest to a file
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.to_json()?)?;
+        Ok(())
+    }
+}
+
+/// Updater target name for a platform and CPU architecture
+/// (e.g. `darwin-aarch64`, `windows-x86_64`)
+pub fn updater_target(platform: Platform, arch: &str) -> Option<String> {
+    let os = match platform {
+        Platform::MacOs => "darwin",
+        Platform::Windows => "windows",
+        Platform::Linux => "linux",
+        _ => return None,
+    };
+    let arch = match arch {
+        "x86" => "i686",
+        "arm" => "armv7",
+        other => other,
+    };
+    Some(format!("{}-{}", os, arch))
+}
+
+/// Detached signature path for a bundle (`<bundle>.sig`)
+pub fn signature_path(bundle: &Path) -> PathBuf {
+    let mut name = bundle.as_os_str().to_owned();
+    name.push(".sig");
+    PathBuf::from(name)
+}
+
+/// Installer type of an updater bundle, or `None` if the file is not one.
+///
+/// Tauri v1 always emits compressed archives (`.app.tar.gz`, `.AppImage.tar.gz`,
+/// `.msi.zip`, `.nsis.zip`). Tauri v2 keeps the archive for macOS but signs the
+/// raw installers on other platforms, so those only count when a signature
+/// file exists next to them.
+pub fn updater_installer(path: &Path) -> Option<&'static str> {
+    let name = path.file_name()?.to_str()?;
+
+    if name.ends_with(".app.tar.gz") {
+        return Some("app");
+    }
+    if name.ends_with(".AppImage.tar.gz") {
+        return Some("appimage");
+    }
+    if name.ends_with(".msi.zip") {
+        return Some("msi");
+    }
+    if name.ends_with(".nsis.zip") {
+        return Some("nsis");
+    }
+
+    if !signature_path(path).exists() {
+        return None;
+    }
+    if name.ends_with(".AppImage") {
+        Some("appimage")
+    } else if name.ends_with(".msi") {
+        Some("msi")
+    } else if name.ends_with("-setup.exe") {
+        Some("nsis")
+    } else {
+        None
+    }
+}
+
+/// Find updater bundles under a Tauri bundle directory, reading any existing
+/// signature files.
+pub fn find_updater_bundles(bundle_dir: &Path, target: &str) -> Vec<UpdaterBundle> {
+    let mut bundles: Vec<UpdaterBundle> = WalkDir::new(bundle_dir)
+        .max_depth(3)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let installer = updater_installer(e.path())?;
+            let signature = std::fs::read_to_string(signature_path(e.path())).ok();
+            Some(UpdaterBundle {
+                path: e.path().to_path_buf(),
+                target: target.to_string(),
+                installer: installer.to_string(),
+                signature,
+            })
+        })
+        .collect();
+
+    bundles.sort_by(|a, b| a.path.cmp(&b.path));
+    bundles
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn bundle(name: &str, target: &str, installer: &str, sig: Option<&str>) -> UpdaterBundle {
+        UpdaterBundle {
+            path: PathBuf::from("/tmp/bundle").join(name),
+            target: target.to_string(),
+            installer: installer.to_string(),
+            signature: sig.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_updater_target() {
+        assert_eq!(
+            updater_target(Platform::MacOs, "aarch64").as_deref(),
+            Some("darwin-aarch64")
+        );
+        assert_eq!(
+            updater_target(Platform::Windows, "x86").as_deref(),
+            Some("windows-i686")
+        );
+        assert_eq!(
+            updater_target(Platform::Linux, "x86_64").as_deref(),
+            Some("linux-x86_64")
+        );
+        assert_eq!(updater_target(Platform::Ios, "aarch64"), None);
+    }
+
+    #[test]
+    fn test_manifest_assembly() {
+        let bundles = vec![
+            bundle(
+                "App.app.tar.gz",
+                "darwin-aarch64",
+                "app",
+                Some("c2lnLW1hYw==\n"),
+            ),
+            bundle(
+                "App_1.0.0_x64_en-US.msi",
+                "windows-x86_64",
+                "msi",
+                Some("c2lnLW1zaQ=="),
+            ),
+            bundle(
+                "App_1.0.0_x64-setup.exe",
+                "windows-x86_64",
+                "nsis",
+                Some("c2lnLW5zaXM="),
+            ),
+            bundle(
+                "app_1.0.0_amd64.AppImage",
+                "linux-x86_64",
+                "appimage",
+                Some("c2lnLWxpbnV4"),
+            ),
+        ];
+
+        let mut manifest = UpdaterManifest::new("1.0.0").with_notes("Bug fixes");
+        manifest
+            .add_bundles(&bundles, Some("https://releases.example.com/v1.0.0/"))
+            .unwrap();
+
+        let mac = &manifest.platforms["darwin-aarch64"];
+        assert_eq!(mac.signature, "c2lnLW1hYw==");
+        assert_eq!(
+            mac.url,
+            "https://releases.example.com/v1.0.0/App.app.tar.gz"
+        );
+        assert_eq!(manifest.platforms["darwin-aarch64-app"], *mac);
+
+        // The first Windows installer claims the plain target key
+        assert_eq!(
+            manifest.platforms["windows-x86_64"].signature,
+            "c2lnLW1zaQ=="
+        );
+        assert_eq!(
+            manifest.platforms["windows-x86_64-nsis"].signature,
+            "c2lnLW5zaXM="
+        );
+        assert!(manifest.platforms.contains_key("linux-x86_64-appimage"));
+        assert_eq!(manifest.platforms.len(), 7);
+
+        let json: serde_json::Value = serde_json::from_str(&manifest.to_json().unwrap()).unwrap();
+        assert_eq!(json["version"], "1.0.0");
+        assert_eq!(json["notes"], "Bug fixes");
+        assert!(json.get("pub_date").is_none());
+        assert_eq!(
+            json["platforms"]["linux-x86_64"]["url"],
+            "https://releases.example.com/v1.0.0/app_1.0.0_amd64.AppImage"
+        );
+    }
+
+    #[test]
+    fn test_manifest_without_base_url() {
+        let bundles = vec![bundle(
+            "App.app.tar.gz",
+            "darwin-x86_64",
+            "app",
+            Some("sig"),
+        )];
+        let mut manifest = UpdaterManifest::new("2.0.0");
+        manifest.add_bundles(&bundles, None).unwrap();
+
+        assert_eq!(manifest.platforms["darwin-x86_64"].url, "App.app.tar.gz");
+    }
+
+    #[test]
+    fn test_manifest_requires_signatures() {
+        let bundles = vec![bundle("App.app.tar.gz", "darwin-x86_64", "app", None)];
+        let mut manifest = UpdaterManifest::new("1.0.0");
+        assert!(manifest.add_bundles(&bundles, None).is_err());
+    }
+
+    #[test]
+    fn test_find_updater_bundles() {
+        let temp = TempDir::new().unwrap();
+        let macos = temp.path().join("macos");
+        let msi = temp.path().join("msi");
+        std::fs::create_dir_all(&macos).unwrap();
+        std::fs::create_dir_all(&msi).unwrap();
+
+        std::fs::write(macos.join("App.app.tar.gz"), "bundle").unwrap();
+        std::fs::write(macos.join("App.app.tar.gz.sig"), "fake-sig").unwrap();
+        // Raw installer without a signature is not an updater bundle
+        std::fs::write(msi.join("App_1.0.0_x64_en-US.msi"), "installer").unwrap();
+
+        let bundles = find_updater_bundles(temp.path(), "darwin-aarch64");
+        assert_eq!(bundles.len(), 1);
+        assert_eq!(bundles[0].installer, "app");
+        assert_eq!(bundles[0].signature.as_deref(), Some("fake-sig"));
+
+        std::fs::write(msi.join("App_1.0.0_x64_en-US.msi.sig"), "msi-sig").unwrap();
+        let bundles = find_updater_bundles(temp.path(), "windows-x86_64");
+        assert_eq!(bundles.len(), 2);
+    }
+
+    #[test]
+    fn test_updater_config_from_context() {
+        let ctx = BuildContext::new("/tmp/app", Platform::MacOs)
+            .with_config(
+                "updater_base_url",
+                serde_json::json!("https://cdn.example.com"),
+            )
+            .with_env(PRIVATE_KEY_ENV, "secret-key");
+
+        let config = UpdaterConfig::from_context(&ctx);
+        assert!(config.can_sign());
+        assert_eq!(config.private_key.as_deref(), Some("secret-key"));
+        assert_eq!(config.base_url.as_deref(), Some("https://cdn.example.com"));
+    }
+}
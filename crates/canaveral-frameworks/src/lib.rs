@@ -43,20 +43,26 @@ pub mod error;
 pub mod frameworks;
 pub mod orchestration;
 pub mod output;
+pub mod plugin;
 pub mod registry;
 pub mod screenshots;
 pub mod simulator;
 pub mod testing;
 pub mod traits;
+pub mod updater;
 pub mod xcodebuild;
 
-pub use artifacts::{Artifact, ArtifactKind, ArtifactMetadata};
-pub use capabilities::{Capabilities, Capability};
+pub use artifacts::{
+    detect_binary, Arch, ArchiveFormat, Artifact, ArtifactKind, ArtifactMetadata, ArtifactPackager,
+    ArtifactWalker, BinaryInfo,
+};
+pub use capabilities::{AdapterPrerequisites, Capabilities, Capability, CapabilityStatus};
 pub use context::{BuildContext, ScreenshotContext, TestContext};
-pub use detection::{Detection, FrameworkDetector};
+pub use detection::{Detection, Detector, FrameworkDetector};
 pub use error::{FrameworkError, Result};
 pub use orchestration::{BuildOrchestrator, Orchestrator, OrchestratorConfig};
 pub use output::{Output, OutputFormat};
+pub use plugin::PluginBuildAdapter;
 pub use registry::FrameworkRegistry;
 pub use screenshots::{
     AppStoreScreenSize, CapturedScreenshot, DeviceConfig, DeviceManager, FrameConfig,
@@ -73,6 +79,7 @@ pub use traits::{
     BuildAdapter, DistributeAdapter, OtaAdapter, ScreenshotAdapter, TestAdapter, TestCase,
     TestReport, TestStatus, TestSuite, VersionAdapter,
 };
+pub use updater::{EdDsaSigner, UpdateEntry, UpdateFeed};
 pub use xcodebuild::{
     ArchiveResult, BuildConfiguration, BuildResult, Destination, ExportMethod, ExportOptions,
     ExportResult, SigningStyle, TestFailure, TestResult, XcodeBuildOptions, XcodeBuildRunner,
@@ -7,6 +7,7 @@ use std::path::Path;
 use std::sync::Arc;
 use std::time::Instant;
 
+use tokio_util::sync::CancellationToken;
 use tracing::{info, instrument};
 
 use crate::artifacts::Artifact;
@@ -17,12 +18,14 @@ use crate::registry::FrameworkRegistry;
 use crate::traits::{BuildAdapter, Platform};
 
 use super::config::OrchestratorConfig;
+use super::run_step;
 
 /// Build-specific orchestrator
 pub struct BuildOrchestrator {
     registry: Arc<FrameworkRegistry>,
     config: OrchestratorConfig,
     hooks: BuildHooks,
+    cancellation: CancellationToken,
 }
 
 impl BuildOrchestrator {
@@ -32,6 +35,7 @@ impl BuildOrchestrator {
             registry,
             config: OrchestratorConfig::from_env(),
             hooks: BuildHooks::default(),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -47,6 +51,12 @@ impl BuildOrchestrator {
         self
     }
 
+    /// Set the token that cancels running builds
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
     /// Build for a single platform
     #[instrument(skip(self, ctx), fields(path = %ctx.path.display(), platform = %ctx.platform.as_str()))]
     pub async fn build(&self, ctx: &BuildContext) -> Result<BuildOutput> {
@@ -60,14 +70,19 @@ impl BuildOrchestrator {
 
         // Check prerequisites
         if self.config.check_prerequisites {
-            self.check_prerequisites(&adapter).await?;
+            run_step(
+                &self.cancellation,
+                "prerequisites check",
+                self.check_prerequisites(&adapter),
+            )
+            .await?;
         }
 
         // Execute build
         let artifacts = if ctx.dry_run {
             vec![]
         } else {
-            adapter.build(ctx).await?
+            run_step(&self.cancellation, "build", adapter.build(ctx)).await?
         };
 
         // Post-build hook
@@ -100,6 +115,7 @@ impl BuildOrchestrator {
         let mut outputs = Vec::new();
         let mut all_artifacts = Vec::new();
         let mut errors = Vec::new();
+        let mut cancelled = false;
 
         for platform in platforms {
             let ctx = BuildContext::from_env(path, *platform);
@@ -109,6 +125,12 @@ impl BuildOrchestrator {
                     all_artifacts.extend(output.artifacts.clone());
                     outputs.push(output);
                 }
+                Err(e) if e.is_cancelled() => {
+                    // Remaining platforms are not started
+                    errors.push((*platform, e.to_string()));
+                    cancelled = true;
+                    break;
+                }
                 Err(e) => {
                     errors.push((*platform, e.to_string()));
                     if !self.config.ci {
@@ -123,6 +145,7 @@ impl BuildOrchestrator {
 
         Ok(MultiBuildOutput {
             success: errors.is_empty(),
+            cancelled,
             outputs,
             all_artifacts,
             errors,
@@ -170,6 +193,11 @@ impl BuildOrchestrator {
                 output.print(format);
                 (output, 0)
             }
+            Err(e) if e.is_cancelled() => {
+                let output = Output::cancelled("build", e.to_string());
+                output.print(format);
+                (output, e.exit_code())
+            }
             Err(e) => {
                 let output = Output::failure("build", e.to_string());
                 output.print(format);
@@ -229,6 +257,8 @@ pub struct BuildOutput {
 #[derive(Debug, Clone)]
 pub struct MultiBuildOutput {
     pub success: bool,
+    /// Whether the builds were cancelled before all platforms ran
+    pub cancelled: bool,
     pub outputs: Vec<BuildOutput>,
     pub all_artifacts: Vec<Artifact>,
     pub errors: Vec<(Platform, String)>,
@@ -302,4 +332,35 @@ mod tests {
         assert!(hooks.pre_build.is_some());
         assert!(hooks.post_build.is_some());
     }
+
+    #[tokio::test]
+    async fn test_build_all_stops_when_cancelled() {
+        use crate::orchestration::tests::{cancel_soon, StalledAdapter};
+
+        let mut registry = FrameworkRegistry::new();
+        registry.register_build(StalledAdapter);
+        let config = OrchestratorConfig {
+            ci: true,
+            check_prerequisites: false,
+            ..OrchestratorConfig::default()
+        };
+        let token = CancellationToken::new();
+        let orchestrator = BuildOrchestrator::new(Arc::new(registry))
+            .with_config(config)
+            .with_cancellation(token.clone());
+
+        let temp = tempfile::TempDir::new().unwrap();
+        cancel_soon(&token);
+        let result = orchestrator
+            .build_all(temp.path(), &[Platform::Android, Platform::Ios])
+            .await
+            .unwrap();
+
+        assert!(result.cancelled);
+        assert!(!result.success);
+        assert_eq!(
+            result.errors,
+            vec![(Platform::Android, "Cancelled during build".to_string())]
+        );
+    }
 }
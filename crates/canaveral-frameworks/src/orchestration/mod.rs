@@ -3,6 +3,11 @@
 //! The orchestrator coordinates multi-step workflows (build, sign, upload)
 //! with proper error handling, hooks, retries, and structured output.
 //! Designed for CI/CD first with full CLI support.
+//!
+//! Each step can be interrupted through a [`CancellationToken`] (wired to
+//! Ctrl-C by the CLI). A cancelled step is dropped, which kills child
+//! processes spawned with `kill_on_drop`, and the operation reports
+//! [`FrameworkError::Cancelled`].
 
 mod build;
 mod config;
@@ -10,12 +15,15 @@ mod config;
 pub use build::BuildOrchestrator;
 pub use config::OrchestratorConfig;
 
+use std::future::Future;
 use std::path::Path;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
+use canaveral_core::retry::{retry_notify, RetryPolicy};
+use tokio_util::sync::CancellationToken;
 use tracing::{info, instrument};
 
-use crate::artifacts::Artifact;
+use crate::artifacts::{update_artifact, Artifact, ArtifactKind};
 use crate::context::BuildContext;
 use crate::error::{FrameworkError, Result};
 use crate::output::{Output, OutputFormat};
@@ -29,6 +37,9 @@ pub struct Orchestrator {
 
     /// Configuration
     config: OrchestratorConfig,
+
+    /// Cancels the running operation
+    cancellation: CancellationToken,
 }
 
 impl Orchestrator {
@@ -37,6 +48,7 @@ impl Orchestrator {
         Self {
             registry: FrameworkRegistry::with_builtins(),
             config: OrchestratorConfig::default(),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -45,6 +57,7 @@ impl Orchestrator {
         Self {
             registry,
             config: OrchestratorConfig::default(),
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -53,6 +66,7 @@ impl Orchestrator {
         Self {
             registry: FrameworkRegistry::with_builtins(),
             config,
+            cancellation: CancellationToken::new(),
         }
     }
 
@@ -62,6 +76,17 @@ impl Orchestrator {
         self
     }
 
+    /// Set the token that cancels running operations
+    pub fn with_cancellation(mut self, token: CancellationToken) -> Self {
+        self.cancellation = token;
+        self
+    }
+
+    /// Get the cancellation token
+    pub fn cancellation(&self) -> &CancellationToken {
+        &self.cancellation
+    }
+
     /// Get reference to registry
     pub fn registry(&self) -> &FrameworkRegistry {
         &self.registry
@@ -93,7 +118,12 @@ impl Orchestrator {
 
         // Check prerequisites
         if self.config.check_prerequisites {
-            let status = adapter.check_prerequisites().await?;
+            let status = run_step(
+                &self.cancellation,
+                "prerequisites check",
+                adapter.check_prerequisites(),
+            )
+            .await?;
             if !status.satisfied {
                 return Err(self.prerequisites_error(&status));
             }
@@ -113,12 +143,15 @@ impl Orchestrator {
         }
 
         // Execute build with retry logic
-        let artifacts = self
-            .execute_with_retry(
+        let mut artifacts = run_step(
+            &self.cancellation,
+            "build",
+            self.execute_with_retry(
                 || async { adapter.build(ctx).await },
                 self.config.max_retries,
-            )
-            .await?;
+            ),
+        )
+        .await?;
 
         // Verify artifacts exist
         for artifact in &artifacts {
@@ -129,6 +162,13 @@ impl Orchestrator {
             }
         }
 
+        // Ship an update patch alongside the primary artifact
+        if let (Some(base), Some(primary)) = (&ctx.delta_base, artifacts.first()) {
+            if let Some(patch) = self.delta_patch(base, primary)? {
+                artifacts.push(patch);
+            }
+        }
+
         let duration_ms = start.elapsed().as_millis() as u64;
 
         self.log_success(&format!(
@@ -178,6 +218,11 @@ impl Orchestrator {
                 output.print(format);
                 (output, 0)
             }
+            Err(e) if e.is_cancelled() => {
+                let output = Output::cancelled("build", e.to_string());
+                output.print(format);
+                (output, e.exit_code())
+            }
             Err(e) => {
                 let output = Output::failure("build", e.to_string());
                 output.print(format);
@@ -249,30 +294,49 @@ impl Orchestrator {
         F: Fn() -> Fut,
         Fut: std::future::Future<Output = Result<T>>,
     {
-        let mut last_error = None;
-
-        for attempt in 0..=max_retries {
-            match f().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    if e.is_retryable() && attempt < max_retries {
-                        let delay = self.config.retry_delay_ms * (attempt + 1) as u64;
-                        self.log_warn(&format!(
-                            "Attempt {} failed ({}), retrying in {}ms...",
-                            attempt + 1,
-                            e,
-                            delay
-                        ));
-                        tokio::time::sleep(std::time::Duration::from_millis(delay)).await;
-                        last_error = Some(e);
-                    } else {
-                        return Err(e);
-                    }
-                }
-            }
+        let policy = RetryPolicy::new(max_retries + 1)
+            .with_base_delay(Duration::from_millis(self.config.retry_delay_ms))
+            .with_jitter(0.1);
+        retry_notify(
+            &policy,
+            FrameworkError::is_retryable,
+            |attempt, e, delay| {
+                self.log_warn(&format!(
+                    "Attempt {} failed ({}), retrying in {}ms...",
+                    attempt,
+                    e,
+                    delay.as_millis()
+                ))
+            },
+            |_| f(),
+        )
+        .await
+    }
+
+    /// Patch from the artifact at `base` to `target`, or `None` when the
+    /// full artifact should ship instead
+    fn delta_patch(&self, base: &Path, target: &Artifact) -> Result<Option<Artifact>> {
+        if !target.path.is_file() {
+            self.log_warn(&format!(
+                "Can't patch {}, it is not a file",
+                target.path.display()
+            ));
+            return Ok(None);
         }
+        let base = if base.is_file() {
+            Some(Artifact::new(base, target.kind, target.platform))
+        } else {
+            self.log_warn(&format!(
+                "Delta base {} not found, shipping the full artifact",
+                base.display()
+            ));
+            None
+        };
 
-        Err(last_error.unwrap())
+        let mut output = target.path.clone().into_os_string();
+        output.push(".patch");
+        let update = update_artifact(base.as_ref(), target, Path::new(&output))?;
+        Ok((update.kind == ArtifactKind::Patch).then_some(update))
     }
 
     fn prerequisites_error(&self, status: &PrerequisiteStatus) -> FrameworkError {
@@ -315,6 +379,29 @@ impl Orchestrator {
     }
 }
 
+/// Run one step of an operation, stopping when `token` is cancelled
+///
+/// Dropping the step kills child processes spawned with `kill_on_drop`.
+/// Commands that share the terminal's process group also receive the
+/// Ctrl-C themselves; their failure is reported as the cancellation.
+async fn run_step<T>(
+    token: &CancellationToken,
+    step: &str,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    if token.is_cancelled() {
+        return Err(FrameworkError::cancelled(step));
+    }
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(FrameworkError::cancelled(step)),
+        result = fut => match result {
+            Err(_) if token.is_cancelled() => Err(FrameworkError::cancelled(step)),
+            result => result,
+        },
+    }
+}
+
 impl Default for Orchestrator {
     fn default() -> Self {
         Self::new()
@@ -355,6 +442,177 @@ impl DetectResult {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::capabilities::Capabilities;
+    use crate::detection::Detection;
+    use crate::traits::{BuildAdapter, Platform, VersionInfo};
+    use std::time::Duration;
+
+    /// Adapter whose build never finishes
+    pub(super) struct StalledAdapter;
+
+    #[async_trait::async_trait]
+    impl BuildAdapter for StalledAdapter {
+        fn id(&self) -> &'static str {
+            "stalled"
+        }
+
+        fn name(&self) -> &'static str {
+            "Stalled"
+        }
+
+        fn detect(&self, _path: &Path) -> Detection {
+            Detection::confident(95)
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities::new()
+        }
+
+        fn supported_platforms(&self) -> &[Platform] {
+            &[Platform::Android]
+        }
+
+        async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+            Ok(PrerequisiteStatus::ok())
+        }
+
+        async fn build(&self, _ctx: &BuildContext) -> Result<Vec<Artifact>> {
+            std::future::pending().await
+        }
+
+        async fn clean(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_version(&self, _path: &Path) -> Result<VersionInfo> {
+            Ok(VersionInfo::new("1.0.0"))
+        }
+
+        fn set_version(&self, _path: &Path, _version: &VersionInfo) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Adapter that writes `app.AppImage` with fixed contents to the output
+    /// directory
+    struct FileAdapter(Vec<u8>);
+
+    #[async_trait::async_trait]
+    impl BuildAdapter for FileAdapter {
+        fn id(&self) -> &'static str {
+            "file"
+        }
+
+        fn name(&self) -> &'static str {
+            "File"
+        }
+
+        fn detect(&self, _path: &Path) -> Detection {
+            Detection::confident(95)
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities::new()
+        }
+
+        fn supported_platforms(&self) -> &[Platform] {
+            &[Platform::Linux]
+        }
+
+        async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+            Ok(PrerequisiteStatus::ok())
+        }
+
+        async fn build(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
+            let path = ctx.output_dir.as_ref().unwrap().join("app.AppImage");
+            std::fs::write(&path, &self.0)?;
+            Ok(vec![Artifact::new(
+                path,
+                ArtifactKind::AppImage,
+                Platform::Linux,
+            )])
+        }
+
+        async fn clean(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_version(&self, _path: &Path) -> Result<VersionInfo> {
+            Ok(VersionInfo::new("1.0.0"))
+        }
+
+        fn set_version(&self, _path: &Path, _version: &VersionInfo) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Cancel `token` shortly after the caller starts waiting
+    pub(super) fn cancel_soon(token: &CancellationToken) {
+        let token = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel();
+        });
+    }
+
+    #[tokio::test]
+    async fn test_build_cancelled_mid_step() {
+        let mut registry = FrameworkRegistry::new();
+        registry.register_build(StalledAdapter);
+        let config = OrchestratorConfig {
+            quiet: true,
+            ..OrchestratorConfig::default()
+        };
+        let orchestrator = Orchestrator::with_registry(registry).config(config);
+
+        let ctx = BuildContext::new(".", Platform::Android);
+        cancel_soon(orchestrator.cancellation());
+        let (output, code) = orchestrator
+            .build_with_output(&ctx, OutputFormat::Json)
+            .await;
+
+        assert!(output.cancelled);
+        assert!(!output.success);
+        assert_eq!(output.message, "Cancelled during build");
+        assert_eq!(code, 130);
+    }
+
+    #[tokio::test]
+    async fn test_build_ships_delta_patch() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let old: Vec<u8> = (0..8192u32).flat_map(|i| i.to_le_bytes()).collect();
+        let mut new = old.clone();
+        new[100..108].copy_from_slice(b"patched!");
+        let base = temp.path().join("app-1.0.0.AppImage");
+        std::fs::write(&base, &old).unwrap();
+
+        let mut registry = FrameworkRegistry::new();
+        registry.register_build(FileAdapter(new));
+        let config = OrchestratorConfig {
+            quiet: true,
+            check_prerequisites: false,
+            ..OrchestratorConfig::default()
+        };
+        let orchestrator = Orchestrator::with_registry(registry).config(config);
+        let ctx = BuildContext::new(temp.path(), Platform::Linux).with_output_dir(temp.path());
+
+        let result = orchestrator
+            .build(&ctx.clone().with_delta_base(&base))
+            .await
+            .unwrap();
+        assert_eq!(result.artifacts.len(), 2);
+        let patch = &result.artifacts[1];
+        assert_eq!(patch.kind, ArtifactKind::Patch);
+        assert_eq!(patch.path, temp.path().join("app.AppImage.patch"));
+
+        // Without the base only the full artifact ships
+        let result = orchestrator
+            .build(&ctx.with_delta_base(temp.path().join("missing.AppImage")))
+            .await
+            .unwrap();
+        assert_eq!(result.artifacts.len(), 1);
+        assert_eq!(result.artifacts[0].kind, ArtifactKind::AppImage);
+    }
 
     #[test]
     fn test_orchestrator_creation() {
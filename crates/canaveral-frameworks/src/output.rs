@@ -10,6 +10,7 @@ use std::io::Write;
 use serde::{Deserialize, Serialize};
 
 use crate::artifacts::Artifact;
+use crate::capabilities::{Capability, CapabilityStatus};
 use crate::traits::{TestReport, VersionInfo};
 
 /// Output format
@@ -58,6 +59,10 @@ pub struct Output {
     /// Whether the operation succeeded
     pub success: bool,
 
+    /// Whether the operation was cancelled (e.g. Ctrl-C) rather than failed
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cancelled: bool,
+
     /// Primary message
     pub message: String,
 
@@ -79,6 +84,10 @@ pub struct Output {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tests: Option<TestOutput>,
 
+    /// Capabilities of the detected adapters
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub capabilities: Vec<CapabilityOutput>,
+
     /// Warnings
     #[serde(skip_serializing_if = "Vec::is_empty")]
     pub warnings: Vec<String>,
@@ -101,12 +110,14 @@ impl Output {
     pub fn success(operation: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             success: true,
+            cancelled: false,
             message: message.into(),
             operation: operation.into(),
             duration_ms: None,
             artifacts: Vec::new(),
             version: None,
             tests: None,
+            capabilities: Vec::new(),
             warnings: Vec::new(),
             errors: Vec::new(),
             outputs: HashMap::new(),
@@ -118,12 +129,14 @@ impl Output {
     pub fn failure(operation: impl Into<String>, message: impl Into<String>) -> Self {
         Self {
             success: false,
+            cancelled: false,
             message: message.into(),
             operation: operation.into(),
             duration_ms: None,
             artifacts: Vec::new(),
             version: None,
             tests: None,
+            capabilities: Vec::new(),
             warnings: Vec::new(),
             errors: Vec::new(),
             outputs: HashMap::new(),
@@ -131,6 +144,14 @@ impl Output {
         }
     }
 
+    /// Create an output for an operation cancelled before completion
+    pub fn cancelled(operation: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            cancelled: true,
+            ..Self::failure(operation, message)
+        }
+    }
+
     pub fn with_duration(mut self, ms: u64) -> Self {
         self.duration_ms = Some(ms);
         self
@@ -157,6 +178,19 @@ impl Output {
         self
     }
 
+    /// Add capabilities, also exposed as `can_<capability>` CI outputs
+    /// that are `true` when the capability's prerequisites are met
+    pub fn with_capabilities(mut self, statuses: Vec<CapabilityStatus>) -> Self {
+        for status in statuses {
+            self.outputs.insert(
+                format!("can_{}", status.capability.as_str()),
+                status.prerequisites.satisfied.to_string(),
+            );
+            self.capabilities.push(CapabilityOutput::from(status));
+        }
+        self
+    }
+
     pub fn with_warning(mut self, warning: impl Into<String>) -> Self {
         self.warnings.push(warning.into());
         self
@@ -197,11 +231,21 @@ impl Output {
         write!(writer, "{}", self.render(format))
     }
 
+    fn status_symbol(&self) -> &'static str {
+        if self.success {
+            "✓"
+        } else if self.cancelled {
+            "⊘"
+        } else {
+            "✗"
+        }
+    }
+
     fn render_text(&self) -> String {
         let mut out = String::new();
 
         // Status line
-        let status = if self.success { "✓" } else { "✗" };
+        let status = self.status_symbol();
         out.push_str(&format!("{} {}\n", status, self.message));
 
         // Duration
@@ -242,6 +286,24 @@ impl Output {
             }
         }
 
+        // Capabilities
+        if !self.capabilities.is_empty() {
+            out.push_str("\nCapabilities:\n");
+            for cap in &self.capabilities {
+                let symbol = if cap.ready { "✓" } else { "✗" };
+                out.push_str(&format!(
+                    "  {} {} ({})",
+                    symbol,
+                    cap.capability.as_str(),
+                    cap.adapter
+                ));
+                if !cap.missing_tools.is_empty() {
+                    out.push_str(&format!(" - missing {}", cap.missing_tools.join(", ")));
+                }
+                out.push('\n');
+            }
+        }
+
         // Warnings
         for warning in &self.warnings {
             out.push_str(&format!("⚠ {}\n", warning));
@@ -313,11 +375,7 @@ impl Output {
         }
 
         // Summary
-        out.push_str(&format!(
-            "{} {}\n",
-            if self.success { "✓" } else { "✗" },
-            self.message
-        ));
+        out.push_str(&format!("{} {}\n", self.status_symbol(), self.message));
 
         out
     }
@@ -354,11 +412,7 @@ impl Output {
         }
 
         // Status
-        out.push_str(&format!(
-            "\n{} {}\n",
-            if self.success { "✓" } else { "✗" },
-            self.message
-        ));
+        out.push_str(&format!("\n{} {}\n", self.status_symbol(), self.message));
 
         out
     }
@@ -429,6 +483,37 @@ impl From<TestReport> for TestOutput {
     }
 }
 
+/// Capability output for serialization
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapabilityOutput {
+    pub capability: Capability,
+    pub adapter: String,
+    /// Whether the adapter's prerequisites are met
+    pub ready: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub missing_tools: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+impl From<CapabilityStatus> for CapabilityOutput {
+    fn from(s: CapabilityStatus) -> Self {
+        Self {
+            capability: s.capability,
+            adapter: s.adapter_id,
+            ready: s.prerequisites.satisfied,
+            missing_tools: s
+                .prerequisites
+                .tools
+                .into_iter()
+                .filter(|t| !t.available)
+                .map(|t| t.name)
+                .collect(),
+            warnings: s.prerequisites.warnings,
+        }
+    }
+}
+
 /// Format file size for display
 fn format_size(bytes: u64) -> String {
     const KB: u64 = 1024;
@@ -475,6 +560,24 @@ mod tests {
         assert_eq!(output.errors.len(), 1);
     }
 
+    #[test]
+    fn test_output_cancelled() {
+        let output = Output::cancelled("build", "Build cancelled");
+
+        assert!(!output.success);
+        assert!(output.cancelled);
+        assert!(output
+            .render(OutputFormat::Text)
+            .starts_with("⊘ Build cancelled"));
+        assert!(output
+            .render(OutputFormat::Json)
+            .contains("\"cancelled\": true"));
+
+        // Only cancelled outputs carry the flag
+        let json = Output::failure("build", "Build failed").render(OutputFormat::Json);
+        assert!(!json.contains("cancelled"));
+    }
+
     #[test]
     fn test_render_json() {
         let output = Output::success("test", "Tests passed").with_output("coverage", "85.5");
@@ -0,0 +1,269 @@
+//! Build adapters backed by external plugins
+//!
+//! Framework plugins use the subprocess contract from
+//! [`canaveral_core::plugins`]: each call writes a `PluginRequest` to the
+//! plugin's stdin and reads a `PluginResponse` from its stdout. The actions
+//! a framework plugin answers are:
+//!
+//! | Action | Input | Output |
+//! |--------|-------|--------|
+//! | `detect` | `{ "path" }` | confidence `0-100` (or `true`/`false`) |
+//! | `check_prerequisites` | `null` | `{ "satisfied", "warnings" }` |
+//! | `build` | `{ "context": BuildContext }` | `[Artifact]` |
+//! | `clean` | `{ "path" }` | ignored |
+//! | `get_version` | `{ "path" }` | `VersionInfo` or a version string |
+//! | `set_version` | `{ "path", "version": VersionInfo }` | ignored |
+//!
+//! The plugin's advertised capabilities list its platforms (`"ios"`,
+//! `"android"`, ...) and any [`Capability`] names (`"release_build"`, ...).
+
+use std::path::Path;
+
+use async_trait::async_trait;
+use canaveral_core::plugins::ExternalPlugin;
+use serde_json::{json, Value};
+use tracing::debug;
+
+use crate::artifacts::Artifact;
+use crate::capabilities::{Capabilities, Capability};
+use crate::context::BuildContext;
+use crate::detection::Detection;
+use crate::error::{FrameworkError, Result};
+use crate::traits::{BuildAdapter, Platform, PrerequisiteStatus, VersionInfo};
+
+/// A framework plugin surfaced as a [`BuildAdapter`]
+pub struct PluginBuildAdapter {
+    plugin: ExternalPlugin,
+    id: &'static str,
+    platforms: Vec<Platform>,
+    capabilities: Capabilities,
+}
+
+impl PluginBuildAdapter {
+    /// Wrap a framework plugin
+    pub fn new(plugin: ExternalPlugin) -> Self {
+        let info = plugin.info();
+
+        // Adapter IDs are `&'static str`; plugins are loaded once per
+        // process, so leaking the name is bounded.
+        let id: &'static str = Box::leak(info.name.clone().into_boxed_str());
+
+        let mut platforms = Vec::new();
+        let mut capabilities = Capabilities::new();
+        for capability in &info.capabilities {
+            if let Some(platform) = Platform::parse(capability) {
+                platforms.push(platform);
+                capabilities.add(build_capability(platform));
+            } else if let Ok(cap) =
+                serde_json::from_value::<Capability>(Value::String(capability.clone()))
+            {
+                capabilities.add(cap);
+            } else {
+                debug!(plugin = id, capability = %capability, "ignoring unknown plugin capability");
+            }
+        }
+
+        Self {
+            plugin,
+            id,
+            platforms,
+            capabilities,
+        }
+    }
+
+    /// Run a plugin action off the async runtime
+    async fn call(&self, action: &'static str, input: Value) -> Result<Value> {
+        let plugin = self.plugin.clone();
+        let id = self.id;
+        tokio::task::spawn_blocking(move || plugin.execute(action, &input))
+            .await
+            .map_err(|e| FrameworkError::context(format!("plugin {}", id), e.to_string()))?
+            .map_err(|e| FrameworkError::context(format!("plugin {}", id), e.to_string()))
+    }
+
+    fn call_blocking(&self, action: &str, input: Value) -> Result<Value> {
+        self.plugin
+            .execute(action, &input)
+            .map_err(|e| FrameworkError::context(format!("plugin {}", self.id), e.to_string()))
+    }
+}
+
+fn build_capability(platform: Platform) -> Capability {
+    match platform {
+        Platform::Ios => Capability::BuildIos,
+        Platform::Android => Capability::BuildAndroid,
+        Platform::MacOs => Capability::BuildMacos,
+        Platform::Windows => Capability::BuildWindows,
+        Platform::Linux => Capability::BuildLinux,
+        Platform::Web => Capability::BuildWeb,
+    }
+}
+
+/// Map a plugin's `detect` output to a [`Detection`]
+fn parse_detection(output: &Value) -> Detection {
+    let confidence = match output {
+        Value::Bool(true) => 80,
+        Value::Number(n) => n.as_u64().unwrap_or(0).min(100) as u8,
+        _ => 0,
+    };
+    match confidence {
+        0 => Detection::No,
+        80.. => Detection::confident(confidence),
+        _ => Detection::possible(confidence),
+    }
+}
+
+fn invalid_output(id: &str, action: &str, err: impl std::fmt::Display) -> FrameworkError {
+    FrameworkError::Serialization(format!(
+        "plugin {} returned invalid {} output: {}",
+        id, action, err
+    ))
+}
+
+#[async_trait]
+impl BuildAdapter for PluginBuildAdapter {
+    fn id(&self) -> &'static str {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.id
+    }
+
+    fn detect(&self, path: &Path) -> Detection {
+        match self.call_blocking("detect", json!({ "path": path })) {
+            Ok(output) => parse_detection(&output),
+            Err(e) => {
+                debug!(plugin = self.id, error = %e, "plugin detection failed");
+                Detection::No
+            }
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        self.capabilities.clone()
+    }
+
+    fn supported_platforms(&self) -> &[Platform] {
+        &self.platforms
+    }
+
+    async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+        let output = self.call("check_prerequisites", Value::Null).await?;
+        let mut status = PrerequisiteStatus::ok();
+        status.satisfied = output
+            .get("satisfied")
+            .and_then(Value::as_bool)
+            .unwrap_or(true);
+        for warning in output
+            .get("warnings")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+        {
+            status = status.with_warning(warning);
+        }
+        Ok(status)
+    }
+
+    async fn build(&self, ctx: &BuildContext) -> Result<Vec<Artifact>> {
+        let output = self.call("build", json!({ "context": ctx })).await?;
+        serde_json::from_value(output).map_err(|e| invalid_output(self.id, "build", e))
+    }
+
+    async fn clean(&self, path: &Path) -> Result<()> {
+        self.call("clean", json!({ "path": path })).await?;
+        Ok(())
+    }
+
+    fn get_version(&self, path: &Path) -> Result<VersionInfo> {
+        match self.call_blocking("get_version", json!({ "path": path }))? {
+            Value::String(version) => Ok(VersionInfo::new(version)),
+            output => serde_json::from_value(output)
+                .map_err(|e| invalid_output(self.id, "get_version", e)),
+        }
+    }
+
+    fn set_version(&self, path: &Path, version: &VersionInfo) -> Result<()> {
+        self.call_blocking("set_version", json!({ "path": path, "version": version }))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_core::plugins::{PluginInfo, PluginType};
+
+    fn plugin(command: &str) -> ExternalPlugin {
+        ExternalPlugin::new(
+            PluginInfo {
+                name: "acme-build".to_string(),
+                version: "1.0.0".to_string(),
+                plugin_type: PluginType::Framework,
+                description: None,
+                author: None,
+                capabilities: vec![
+                    "android".to_string(),
+                    "release_build".to_string(),
+                    "teleport".to_string(),
+                ],
+            },
+            command,
+        )
+    }
+
+    #[test]
+    fn test_capabilities_from_plugin_info() {
+        let adapter = PluginBuildAdapter::new(plugin("acme-build"));
+        assert_eq!(adapter.id(), "acme-build");
+        assert_eq!(adapter.supported_platforms(), &[Platform::Android]);
+        assert!(adapter.capabilities().has(Capability::BuildAndroid));
+        assert!(adapter.capabilities().has(Capability::ReleaseBuild));
+    }
+
+    #[test]
+    fn test_parse_detection() {
+        assert!(parse_detection(&json!(95)).is_confident());
+        assert_eq!(parse_detection(&json!(50)).confidence(), 50);
+        assert!(!parse_detection(&json!(50)).is_confident());
+        assert!(parse_detection(&json!(true)).is_confident());
+        assert!(!parse_detection(&json!(0)).detected());
+        assert!(!parse_detection(&Value::Null).detected());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_subprocess_contract() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let script = temp.path().join("acme-build");
+        std::fs::write(
+            &script,
+            r#"#!/bin/sh
+input=$(cat)
+case "$input" in
+  *'"action":"detect"'*) echo '{"output": 90}' ;;
+  *'"action":"get_version"'*) echo '{"output": {"version": "2.0.0", "build_number": 7}}' ;;
+  *) echo '{"error": "unsupported action"}' ;;
+esac
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let adapter = PluginBuildAdapter::new(plugin(&script.to_string_lossy()));
+        assert_eq!(adapter.detect(temp.path()).confidence(), 90);
+
+        let version = adapter.get_version(temp.path()).unwrap();
+        assert_eq!(version.version, "2.0.0");
+        assert_eq!(version.build_number, Some(7));
+
+        let err = adapter
+            .set_version(temp.path(), &VersionInfo::new("2.0.1"))
+            .unwrap_err();
+        assert!(err.to_string().contains("unsupported action"));
+    }
+}
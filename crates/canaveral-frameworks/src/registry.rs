@@ -9,9 +9,15 @@ use std::sync::Arc;
 
 use tracing::{debug, info, instrument, warn};
 
-use crate::detection::DetectionResult;
+use canaveral_core::plugins::{PluginRegistry, PluginType};
+
+use crate::capabilities::{AdapterPrerequisites, Capabilities, Capability, CapabilityStatus};
+use crate::detection::{DetectionResult, Detector};
 use crate::error::{FrameworkError, Result};
-use crate::traits::{BuildAdapter, DistributeAdapter, OtaAdapter, ScreenshotAdapter, TestAdapter};
+use crate::plugin::PluginBuildAdapter;
+use crate::traits::{
+    BuildAdapter, DistributeAdapter, OtaAdapter, PrerequisiteStatus, ScreenshotAdapter, TestAdapter,
+};
 
 /// Registry of framework adapters
 pub struct FrameworkRegistry {
@@ -33,6 +39,9 @@ pub struct FrameworkRegistry {
     /// Ordered list of build adapter IDs for detection priority
     build_detection_order: Vec<String>,
 
+    /// Extra detection hooks, ranked alongside the build adapters
+    detectors: Vec<Box<dyn Detector>>,
+
     /// Ordered list of test adapter IDs for detection priority
     test_detection_order: Vec<String>,
 }
@@ -47,6 +56,7 @@ impl FrameworkRegistry {
             distribute_adapters: HashMap::new(),
             ota_adapters: HashMap::new(),
             build_detection_order: Vec::new(),
+            detectors: Vec::new(),
             test_detection_order: Vec::new(),
         }
     }
@@ -66,13 +76,36 @@ impl FrameworkRegistry {
     // -------------------------------------------------------------------------
 
     /// Register a build adapter
+    ///
+    /// Registering an ID that already exists replaces that adapter but keeps
+    /// its detection priority, so custom adapters can override built-ins.
     pub fn register_build<A: BuildAdapter + 'static>(&mut self, adapter: A) {
         let id = adapter.id().to_string();
         debug!(adapter_id = %id, "registering build adapter");
-        self.build_detection_order.push(id.clone());
+        if !self.build_adapters.contains_key(&id) {
+            self.build_detection_order.push(id.clone());
+        }
         self.build_adapters.insert(id, Arc::new(adapter));
     }
 
+    /// Register a detection hook for an existing build adapter
+    pub fn register_detector(&mut self, detector: Box<dyn Detector>) {
+        debug!(adapter_id = %detector.adapter_id(), "registering detector");
+        self.detectors.push(detector);
+    }
+
+    /// Register every framework plugin as a build adapter
+    ///
+    /// Returns the number of adapters registered.
+    pub fn register_plugins(&mut self, plugins: &PluginRegistry) -> usize {
+        let framework_plugins = plugins.list(PluginType::Framework);
+        for plugin in &framework_plugins {
+            info!(plugin = %plugin.info().name, "registering framework plugin");
+            self.register_build(PluginBuildAdapter::new((*plugin).clone()));
+        }
+        framework_plugins.len()
+    }
+
     /// Get a build adapter by ID
     pub fn get_build(&self, id: &str) -> Option<Arc<dyn BuildAdapter>> {
         self.build_adapters.get(id).cloned()
@@ -104,6 +137,27 @@ impl FrameworkRegistry {
             })
             .collect();
 
+        // Detectors raise an adapter's confidence or add it to the ranking
+        for detector in &self.detectors {
+            let Some(adapter) = self.build_adapters.get(detector.adapter_id()) else {
+                warn!(adapter_id = %detector.adapter_id(), "detector targets unknown build adapter");
+                continue;
+            };
+            let detection = detector.detect(path);
+            if !detection.detected() {
+                continue;
+            }
+            match results.iter_mut().find(|r| r.adapter_id == adapter.id()) {
+                Some(existing) if existing.detection < detection => existing.detection = detection,
+                Some(_) => {}
+                None => results.push(DetectionResult {
+                    adapter_id: adapter.id().to_string(),
+                    adapter_name: adapter.name().to_string(),
+                    detection,
+                }),
+            }
+        }
+
         results.sort_by(|a, b| b.detection.cmp(&a.detection));
         if !results.is_empty() {
             info!(
@@ -319,6 +373,155 @@ impl FrameworkRegistry {
             })
             .collect()
     }
+
+    // -------------------------------------------------------------------------
+    // Capabilities
+    // -------------------------------------------------------------------------
+
+    /// Capabilities of the adapters detected for a project
+    ///
+    /// Combines the best build, test, screenshot and OTA adapters for the
+    /// project. Empty when nothing is detected.
+    pub fn capabilities_for(&self, path: &Path) -> Capabilities {
+        self.detected_adapters(path)
+            .iter()
+            .flat_map(|adapter| adapter.capabilities().all().copied().collect::<Vec<_>>())
+            .collect()
+    }
+
+    /// Capabilities of the detected adapters, with prerequisite status
+    ///
+    /// A capability offered by several adapters is attributed to the first
+    /// of build, test, screenshot and OTA. Sorted by capability name.
+    pub async fn capability_statuses(&self, path: &Path) -> Vec<CapabilityStatus> {
+        let mut statuses: Vec<CapabilityStatus> = Vec::new();
+        for adapter in self.detected_adapters(path) {
+            let prerequisites = adapter.prerequisites().await;
+            for capability in adapter.capabilities().all() {
+                if statuses.iter().any(|s| s.capability == *capability) {
+                    continue;
+                }
+                statuses.push(CapabilityStatus {
+                    capability: *capability,
+                    adapter_id: adapter.id().to_string(),
+                    prerequisites: prerequisites.clone(),
+                });
+            }
+        }
+        statuses.sort_by_key(|s| s.capability.as_str());
+        statuses
+    }
+
+    /// Prerequisites of the best adapter of each kind detected for a project
+    ///
+    /// In the order build, test, screenshot, OTA. A failing check is
+    /// reported as unsatisfied, with the error as a warning.
+    pub async fn adapter_prerequisites(&self, path: &Path) -> Vec<AdapterPrerequisites> {
+        let mut results = Vec::new();
+        for adapter in self.detected_adapters(path) {
+            results.push(AdapterPrerequisites {
+                adapter_id: adapter.id().to_string(),
+                kind: adapter.kind(),
+                prerequisites: adapter.prerequisites().await,
+            });
+        }
+        results
+    }
+
+    /// The best adapter of each kind detected for a project
+    fn detected_adapters(&self, path: &Path) -> Vec<DetectedAdapter> {
+        let mut adapters = Vec::new();
+        if let Some(adapter) = self.detect_build_best(path) {
+            adapters.push(DetectedAdapter::Build(adapter));
+        }
+        if let Some(adapter) = self
+            .detect_test(path)
+            .first()
+            .and_then(|r| self.get_test(&r.adapter_id))
+        {
+            adapters.push(DetectedAdapter::Test(adapter));
+        }
+        if let Some((_, adapter)) = self
+            .screenshot_adapters
+            .values()
+            .map(|a| (a.detect(path), a))
+            .filter(|(detection, _)| detection.detected())
+            .max_by(|a, b| a.0.cmp(&b.0))
+        {
+            adapters.push(DetectedAdapter::Screenshot(adapter.clone()));
+        }
+        if let Some(adapter) = self
+            .detect_ota(path)
+            .into_iter()
+            .max_by(|a, b| a.detection.cmp(&b.detection))
+            .and_then(|r| self.get_ota(&r.adapter_id))
+        {
+            adapters.push(DetectedAdapter::Ota(adapter));
+        }
+        adapters
+    }
+}
+
+/// An adapter detected for a project, of any kind
+enum DetectedAdapter {
+    Build(Arc<dyn BuildAdapter>),
+    Test(Arc<dyn TestAdapter>),
+    Screenshot(Arc<dyn ScreenshotAdapter>),
+    Ota(Arc<dyn OtaAdapter>),
+}
+
+impl DetectedAdapter {
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Build(a) => a.id(),
+            Self::Test(a) => a.id(),
+            Self::Screenshot(a) => a.id(),
+            Self::Ota(a) => a.id(),
+        }
+    }
+
+    fn capabilities(&self) -> Capabilities {
+        match self {
+            Self::Build(a) => a.capabilities(),
+            Self::Test(a) => a.capabilities(),
+            Self::Screenshot(a) => a.capabilities(),
+            // OTA adapters don't declare capabilities beyond OTA itself
+            Self::Ota(_) => Capabilities::new().with(Capability::OtaUpdates),
+        }
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Self::Build(_) => "build",
+            Self::Test(_) => "test",
+            Self::Screenshot(_) => "screenshot",
+            Self::Ota(_) => "ota",
+        }
+    }
+
+    async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+        match self {
+            Self::Build(a) => a.check_prerequisites().await,
+            Self::Test(a) => a.check_prerequisites().await,
+            Self::Screenshot(a) => a.check_prerequisites().await,
+            Self::Ota(a) => a.check_prerequisites().await,
+        }
+    }
+
+    /// Prerequisite status, treating a failed check as unsatisfied
+    async fn prerequisites(&self) -> PrerequisiteStatus {
+        match self.check_prerequisites().await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!(adapter_id = %self.id(), error = %e, "prerequisite check failed");
+                PrerequisiteStatus {
+                    satisfied: false,
+                    ..PrerequisiteStatus::ok()
+                }
+                .with_warning(e.to_string())
+            }
+        }
+    }
 }
 
 impl Default for FrameworkRegistry {
@@ -330,6 +533,193 @@ impl Default for FrameworkRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::artifacts::Artifact;
+    use crate::context::BuildContext;
+    use crate::detection::Detection;
+    use crate::output::{Output, OutputFormat};
+    use crate::traits::{Platform, ToolStatus, VersionInfo};
+
+    /// In-house adapter that recognises projects by an `acme.toml` marker
+    struct AcmeAdapter;
+
+    #[async_trait::async_trait]
+    impl BuildAdapter for AcmeAdapter {
+        fn id(&self) -> &'static str {
+            "acme"
+        }
+
+        fn name(&self) -> &'static str {
+            "Acme Build"
+        }
+
+        fn detect(&self, path: &Path) -> Detection {
+            if path.join("acme.toml").exists() {
+                Detection::confident(95)
+            } else {
+                Detection::No
+            }
+        }
+
+        fn capabilities(&self) -> Capabilities {
+            Capabilities::new()
+                .with(Capability::BuildAndroid)
+                .with(Capability::ReleaseBuild)
+        }
+
+        fn supported_platforms(&self) -> &[Platform] {
+            &[Platform::Android]
+        }
+
+        async fn check_prerequisites(&self) -> Result<PrerequisiteStatus> {
+            Ok(PrerequisiteStatus::ok()
+                .with_tool(ToolStatus::missing("acme", "Install the Acme toolchain")))
+        }
+
+        async fn build(&self, _ctx: &BuildContext) -> Result<Vec<Artifact>> {
+            Ok(Vec::new())
+        }
+
+        async fn clean(&self, _path: &Path) -> Result<()> {
+            Ok(())
+        }
+
+        fn get_version(&self, _path: &Path) -> Result<VersionInfo> {
+            Ok(VersionInfo::new("1.0.0"))
+        }
+
+        fn set_version(&self, _path: &Path, _version: &VersionInfo) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    /// Detector voting for Flutter when a wrapper file is present
+    struct WrappedFlutterDetector;
+
+    impl Detector for WrappedFlutterDetector {
+        fn adapter_id(&self) -> &str {
+            "flutter"
+        }
+
+        fn detect(&self, path: &Path) -> Detection {
+            if path.join("build-wrapper.json").exists() {
+                Detection::confident(90)
+            } else {
+                Detection::No
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_adapter_participates_in_detection() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("acme.toml"), "").unwrap();
+
+        let mut registry = FrameworkRegistry::with_builtins();
+        registry.register_build(AcmeAdapter);
+
+        let detections = registry.detect_build(temp.path());
+        assert_eq!(detections[0].adapter_id, "acme");
+        assert_eq!(
+            registry.resolve_build(temp.path(), None).unwrap().id(),
+            "acme"
+        );
+        assert_eq!(
+            registry
+                .resolve_build(temp.path(), Some("acme"))
+                .unwrap()
+                .name(),
+            "Acme Build"
+        );
+
+        // Re-registering an ID replaces the adapter without duplicating it
+        registry.register_build(AcmeAdapter);
+        assert_eq!(registry.detect_build(temp.path()).len(), 1);
+    }
+
+    #[test]
+    fn test_detector_merges_into_ranking() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("build-wrapper.json"), "{}").unwrap();
+
+        let mut registry = FrameworkRegistry::with_builtins();
+        assert!(registry.resolve_build(temp.path(), None).is_err());
+
+        registry.register_detector(Box::new(WrappedFlutterDetector));
+        let detections = registry.detect_build(temp.path());
+        assert_eq!(detections.len(), 1);
+        assert_eq!(detections[0].adapter_id, "flutter");
+        assert_eq!(detections[0].detection.confidence(), 90);
+        assert_eq!(
+            registry.resolve_build(temp.path(), None).unwrap().id(),
+            "flutter"
+        );
+    }
+
+    #[test]
+    fn test_capabilities_for_detected_framework() {
+        let registry = FrameworkRegistry::with_builtins();
+
+        let flutter = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            flutter.path().join("pubspec.yaml"),
+            "name: app\ndependencies:\n  flutter:\n    sdk: flutter\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(flutter.path().join("lib")).unwrap();
+        std::fs::write(flutter.path().join("lib/main.dart"), "void main() {}").unwrap();
+        std::fs::create_dir_all(flutter.path().join("test")).unwrap();
+
+        let caps = registry.capabilities_for(flutter.path());
+        assert!(caps.can_build_mobile());
+        assert!(caps.can_test());
+        assert!(caps.has(Capability::Screenshots));
+
+        let vite = tempfile::TempDir::new().unwrap();
+        std::fs::write(vite.path().join("vite.config.ts"), "export default {}").unwrap();
+        std::fs::write(
+            vite.path().join("package.json"),
+            r#"{"devDependencies": {"vite": "^5.0.0"}}"#,
+        )
+        .unwrap();
+
+        let caps = registry.capabilities_for(vite.path());
+        assert!(caps.has(Capability::BuildWeb));
+        assert!(!caps.can_build_mobile());
+        assert!(!caps.can_test());
+        assert!(!caps.has(Capability::Screenshots));
+        assert!(!caps.has(Capability::OtaUpdates));
+
+        let empty = tempfile::TempDir::new().unwrap();
+        assert_eq!(registry.capabilities_for(empty.path()).all().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_capability_statuses_output() {
+        let temp = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp.path().join("acme.toml"), "").unwrap();
+        let mut registry = FrameworkRegistry::new();
+        registry.register_build(AcmeAdapter);
+
+        let statuses = registry.capability_statuses(temp.path()).await;
+        let caps: Vec<_> = statuses.iter().map(|s| s.capability).collect();
+        assert_eq!(caps, [Capability::BuildAndroid, Capability::ReleaseBuild]);
+        assert!(statuses.iter().all(|s| s.adapter_id == "acme"));
+
+        let adapters = registry.adapter_prerequisites(temp.path()).await;
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].adapter_id, "acme");
+        assert_eq!(adapters[0].kind, "build");
+        assert!(!adapters[0].prerequisites.satisfied);
+
+        let output = Output::success("capabilities", "Acme Build").with_capabilities(statuses);
+        assert_eq!(output.outputs["can_build_android"], "false");
+        let json: serde_json::Value =
+            serde_json::from_str(&output.render(OutputFormat::Json)).unwrap();
+        let first = &json["capabilities"][0];
+        assert_eq!(first["capability"], "build_android");
+        assert_eq!(first["ready"], false);
+        assert_eq!(first["missing_tools"][0], "acme");
+    }
 
     #[test]
     fn test_empty_registry() {
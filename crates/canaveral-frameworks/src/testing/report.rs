@@ -22,6 +22,8 @@ pub struct TestReportOutput {
     pub failed: usize,
     /// Tests skipped
     pub skipped: usize,
+    /// Tests that passed only on a retry
+    pub flaky: usize,
     /// Duration in milliseconds
     pub duration_ms: u64,
     /// Test suites
@@ -38,6 +40,7 @@ pub struct TestSuiteOutput {
     pub tests: usize,
     pub failures: usize,
     pub skipped: usize,
+    pub flaky: usize,
     pub duration_ms: u64,
     pub cases: Vec<TestCaseOutput>,
 }
@@ -68,6 +71,7 @@ impl From<&TestReport> for TestReportOutput {
             passed: report.passed,
             failed: report.failed,
             skipped: report.skipped,
+            flaky: report.flaky,
             duration_ms: report.duration_ms,
             suites: report.suites.iter().map(|s| s.into()).collect(),
             coverage: report.coverage.as_ref().map(|c| c.into()),
@@ -89,11 +93,18 @@ impl From<&TestSuite> for TestSuiteOutput {
             .filter(|t| t.status == TestStatus::Skipped)
             .count();
 
+        let flaky = suite
+            .tests
+            .iter()
+            .filter(|t| t.status == TestStatus::Flaky)
+            .count();
+
         Self {
             name: suite.name.clone(),
             tests: suite.tests.len(),
             failures,
             skipped,
+            flaky,
             duration_ms: suite.duration_ms,
             cases: suite.tests.iter().map(|t| t.into()).collect(),
         }
@@ -108,6 +119,7 @@ impl From<&TestCase> for TestCaseOutput {
                 TestStatus::Passed => "passed".to_string(),
                 TestStatus::Failed => "failed".to_string(),
                 TestStatus::Skipped => "skipped".to_string(),
+                TestStatus::Flaky => "flaky".to_string(),
             },
             duration_ms: test.duration_ms,
             error: test.error.clone(),
@@ -162,6 +174,7 @@ impl ReportGenerator {
                     TestStatus::Passed => "✓",
                     TestStatus::Failed => "✗",
                     TestStatus::Skipped => "○",
+                    TestStatus::Flaky => "⚠",
                 };
 
                 output.push_str(&format!(
@@ -180,8 +193,8 @@ impl ReportGenerator {
 
         output.push_str("═══════════════════════════════════════════════════════════════\n");
         output.push_str(&format!(
-            "  SUMMARY: {} passed, {} failed, {} skipped ({}ms)\n",
-            report.passed, report.failed, report.skipped, report.duration_ms
+            "  SUMMARY: {} passed, {} failed, {} flaky, {} skipped ({}ms)\n",
+            report.passed, report.failed, report.flaky, report.skipped, report.duration_ms
         ));
 
         if let Some(ref coverage) = report.coverage {
@@ -224,8 +237,8 @@ impl ReportGenerator {
 
         // Summary
         output.push_str(&format!(
-            "::group::Test Results ({} passed, {} failed, {} skipped)\n",
-            report.passed, report.failed, report.skipped
+            "::group::Test Results ({} passed, {} failed, {} flaky, {} skipped)\n",
+            report.passed, report.failed, report.flaky, report.skipped
         ));
 
         for suite in &report.suites {
@@ -252,6 +265,12 @@ impl ReportGenerator {
                             test.name, test.name
                         ));
                     }
+                    TestStatus::Flaky => {
+                        output.push_str(&format!(
+                            "::warning title={}::{} passed on retry\n",
+                            test.name, test.name
+                        ));
+                    }
                     TestStatus::Passed => {}
                 }
             }
@@ -316,6 +335,8 @@ pub struct JUnitTestCase {
     pub classname: String,
     pub time: f64,
     pub failure: Option<JUnitFailure>,
+    /// Failure from an earlier attempt of a test that passed on a retry
+    pub flaky_failure: Option<JUnitFailure>,
     pub skipped: bool,
 }
 
@@ -373,22 +394,21 @@ impl From<&TestSuite> for JUnitTestSuite {
 
 impl JUnitTestCase {
     fn from_test(test: &TestCase, classname: &str) -> Self {
+        let failure = || JUnitFailure {
+            message: test
+                .error
+                .clone()
+                .unwrap_or_else(|| "Test failed".to_string()),
+            type_name: "AssertionError".to_string(),
+            content: test.error.clone().unwrap_or_default(),
+        };
+
         Self {
             name: test.name.clone(),
             classname: classname.to_string(),
             time: test.duration_ms as f64 / 1000.0,
-            failure: if test.status == TestStatus::Failed {
-                Some(JUnitFailure {
-                    message: test
-                        .error
-                        .clone()
-                        .unwrap_or_else(|| "Test failed".to_string()),
-                    type_name: "AssertionError".to_string(),
-                    content: test.error.clone().unwrap_or_default(),
-                })
-            } else {
-                None
-            },
+            failure: (test.status == TestStatus::Failed).then(failure),
+            flaky_failure: (test.status == TestStatus::Flaky).then(failure),
             skipped: test.status == TestStatus::Skipped,
         }
     }
@@ -436,6 +456,21 @@ impl JUnitReport {
                         escape_xml(&failure.content)
                     ));
                     xml.push_str("    </testcase>\n");
+                } else if let Some(ref failure) = testcase.flaky_failure {
+                    // Surefire's element for a failure that passed on rerun
+                    xml.push_str(&format!(
+                        "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
+                        escape_xml(&testcase.name),
+                        escape_xml(&testcase.classname),
+                        testcase.time
+                    ));
+                    xml.push_str(&format!(
+                        "      <flakyFailure message=\"{}\" type=\"{}\">{}</flakyFailure>\n",
+                        escape_xml(&failure.message),
+                        escape_xml(&failure.type_name),
+                        escape_xml(&failure.content)
+                    ));
+                    xml.push_str("    </testcase>\n");
                 } else if testcase.skipped {
                     xml.push_str(&format!(
                         "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
@@ -482,6 +517,7 @@ mod tests {
             passed: 8,
             failed: 2,
             skipped: 1,
+            flaky: 1,
             duration_ms: 1234,
             suites: vec![TestSuite {
                 name: "unit_tests".to_string(),
@@ -499,6 +535,12 @@ mod tests {
                         duration_ms: 15,
                         error: Some("Expected 5, got 3".to_string()),
                     },
+                    TestCase {
+                        name: "test_network".to_string(),
+                        status: TestStatus::Flaky,
+                        duration_ms: 20,
+                        error: Some("Connection reset".to_string()),
+                    },
                     TestCase {
                         name: "test_pending".to_string(),
                         status: TestStatus::Skipped,
@@ -518,6 +560,8 @@ mod tests {
 
         assert!(json.contains("\"passed\": 8"));
         assert!(json.contains("\"failed\": 2"));
+        assert!(json.contains("\"flaky\": 1"));
+        assert!(json.contains("\"status\": \"flaky\""));
         assert!(json.contains("\"success\": false"));
     }
 
@@ -527,7 +571,8 @@ mod tests {
         let xml = ReportGenerator::generate_junit(&report);
 
         assert!(xml.contains("<?xml version=\"1.0\""));
-        assert!(xml.contains("tests=\"11\""));
+        assert!(xml.contains("tests=\"12\""));
+        assert!(xml.contains("<flakyFailure message=\"Connection reset\""));
         assert!(xml.contains("failures=\"2\""));
         assert!(xml.contains("<failure"));
         assert!(xml.contains("<skipped/>"));
@@ -541,6 +586,7 @@ mod tests {
         assert!(pretty.contains("TEST RESULTS"));
         assert!(pretty.contains("8 passed"));
         assert!(pretty.contains("2 failed"));
+        assert!(pretty.contains("1 flaky"));
         assert!(pretty.contains("1 skipped"));
     }
 
@@ -296,18 +296,21 @@ pub struct TestReport {
     pub passed: usize,
     pub failed: usize,
     pub skipped: usize,
+    /// Tests that failed and then passed on a retry
+    pub flaky: usize,
     pub duration_ms: u64,
     pub suites: Vec<TestSuite>,
     pub coverage: Option<CoverageReport>,
 }
 
 impl TestReport {
+    /// Whether no test failed; flaky tests don't count as failures
     pub fn success(&self) -> bool {
         self.failed == 0
     }
 
     pub fn total(&self) -> usize {
-        self.passed + self.failed + self.skipped
+        self.passed + self.failed + self.skipped + self.flaky
     }
 }
 
@@ -334,6 +337,8 @@ pub enum TestStatus {
     Passed,
     Failed,
     Skipped,
+    /// Failed, then passed on a retry; the error holds the original failure
+    Flaky,
 }
 
 /// Coverage report
@@ -367,10 +372,14 @@ pub struct Screenshot {
 /// Context for distribution
 #[derive(Debug, Clone)]
 pub struct DistributeContext {
+    /// Path to the project root
+    pub path: std::path::PathBuf,
     pub artifact: Artifact,
     pub groups: Vec<String>,
     pub notes: Option<String>,
     pub notify: bool,
+    /// Whether this is a dry run (collect artifacts but don't publish)
+    pub dry_run: bool,
 }
 
 /// Result of distribution
@@ -395,6 +404,53 @@ pub struct OtaContext {
     pub path: std::path::PathBuf,
     pub channel: String,
     pub message: Option<String>,
+    /// Publish to this branch instead of the one linked to `channel`
+    pub branch: Option<String>,
+    /// Runtime version the update must target
+    pub runtime_version: Option<String>,
+    /// Only publish for this platform
+    pub platform: Option<Platform>,
+    /// Whether this is a dry run (validate configuration but don't publish)
+    pub dry_run: bool,
+}
+
+impl OtaContext {
+    pub fn new(path: impl Into<std::path::PathBuf>, channel: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            channel: channel.into(),
+            message: None,
+            branch: None,
+            runtime_version: None,
+            platform: None,
+            dry_run: false,
+        }
+    }
+
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    pub fn with_runtime_version(mut self, runtime_version: impl Into<String>) -> Self {
+        self.runtime_version = Some(runtime_version.into());
+        self
+    }
+
+    pub fn with_platform(mut self, platform: Platform) -> Self {
+        self.platform = Some(platform);
+        self
+    }
+
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
 }
 
 /// Result of OTA operation
@@ -441,6 +497,7 @@ mod tests {
             passed: 10,
             failed: 0,
             skipped: 2,
+            flaky: 0,
             duration_ms: 1000,
             suites: vec![],
             coverage: None,
@@ -0,0 +1,501 @@
+//! Auto-update feeds for desktop apps
+//!
+//! Builds the feeds desktop updaters poll for new releases from the released
+//! artifacts:
+//!
+//! - Sparkle (macOS) and WinSparkle: `appcast.xml`, one `<item>` per macOS or
+//!   Windows artifact, with an EdDSA (ed25519) signature when a key is available
+//! - Squirrel.Windows: `RELEASES`, one `SHA1 filename size` line per `.nupkg`
+//! - Squirrel.Mac: `latest.json`, the `{ url, name, notes, pub_date }` feed
+//!
+//! Artifact URLs are built by joining the base download URL with the
+//! artifact's file name.
+
+use std::path::{Path, PathBuf};
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use ring::signature::{Ed25519KeyPair, KeyPair};
+use serde::Serialize;
+use tracing::debug;
+
+use crate::artifacts::Artifact;
+use crate::error::{FrameworkError, Result};
+use crate::traits::Platform;
+
+/// Env var holding the Sparkle EdDSA private key (as exported by `generate_keys -x`)
+pub const SPARKLE_PRIVATE_KEY_ENV: &str = "SPARKLE_PRIVATE_KEY";
+
+/// File name of the Sparkle appcast
+pub const APPCAST_FILE_NAME: &str = "appcast.xml";
+/// File name of the Squirrel.Windows release list
+pub const SQUIRREL_RELEASES_FILE_NAME: &str = "RELEASES";
+/// File name of the Squirrel.Mac feed
+pub const SQUIRREL_MAC_FILE_NAME: &str = "latest.json";
+
+const SPARKLE_NAMESPACE: &str = "http://www.andymatuschak.org/xml-namespaces/sparkle";
+
+/// Signs update artifacts with a Sparkle EdDSA key
+pub struct EdDsaSigner {
+    key_pair: Ed25519KeyPair,
+}
+
+impl EdDsaSigner {
+    /// Load a base64 private key
+    ///
+    /// Accepts the 32-byte seed exported by Sparkle's `generate_keys -x` and
+    /// the 64-byte seed + public key format used by libsodium.
+    pub fn from_base64(key: &str) -> Result<Self> {
+        let invalid = |message: String| FrameworkError::InvalidConfig {
+            message: format!("invalid EdDSA private key: {}", message),
+        };
+
+        let bytes = BASE64
+            .decode(key.trim())
+            .map_err(|e| invalid(e.to_string()))?;
+        let key_pair = match bytes.len() {
+            32 => Ed25519KeyPair::from_seed_unchecked(&bytes),
+            64 => Ed25519KeyPair::from_seed_and_public_key(&bytes[..32], &bytes[32..]),
+            n => return Err(invalid(format!("expected 32 or 64 bytes, got {}", n))),
+        }
+        .map_err(|e| invalid(e.to_string()))?;
+
+        Ok(Self { key_pair })
+    }
+
+    /// Load the key from [`SPARKLE_PRIVATE_KEY_ENV`], if set
+    pub fn from_env() -> Result<Option<Self>> {
+        match std::env::var(SPARKLE_PRIVATE_KEY_ENV) {
+            Ok(key) if !key.trim().is_empty() => Self::from_base64(&key).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Base64 public key, the `SUPublicEDKey` value in the app's Info.plist
+    pub fn public_key(&self) -> String {
+        BASE64.encode(self.key_pair.public_key().as_ref())
+    }
+
+    /// Base64 signature of `data`
+    pub fn sign(&self, data: &[u8]) -> String {
+        BASE64.encode(self.key_pair.sign(data).as_ref())
+    }
+}
+
+impl std::fmt::Debug for EdDsaSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EdDsaSigner")
+            .field("public_key", &self.public_key())
+            .finish()
+    }
+}
+
+/// One downloadable artifact in an update feed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateEntry {
+    /// Artifact file name
+    pub file_name: String,
+    /// Download URL
+    pub url: String,
+    /// Size in bytes
+    pub length: u64,
+    /// Target OS (`macos`, `windows`, `linux`)
+    pub os: Option<&'static str>,
+    /// SHA1 of the file (hex encoded, uppercase as Squirrel expects)
+    pub sha1: String,
+    /// Base64 EdDSA signature of the file
+    pub ed_signature: Option<String>,
+}
+
+/// Squirrel.Mac feed (`latest.json`)
+#[derive(Debug, Clone, Serialize)]
+struct SquirrelMacFeed<'a> {
+    url: &'a str,
+    name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    notes: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub_date: Option<String>,
+}
+
+/// Update feed for one release
+#[derive(Debug, Clone)]
+pub struct UpdateFeed {
+    /// App name, used as the appcast channel title
+    pub title: String,
+    /// Version shown to users (`sparkle:shortVersionString`)
+    pub version: String,
+    /// Build version compared by Sparkle (`sparkle:version`), defaults to `version`
+    pub build_version: Option<String>,
+    /// Release notes
+    pub notes: Option<String>,
+    /// Publication date
+    pub pub_date: Option<DateTime<Utc>>,
+    /// Base URL the artifacts will be served from
+    pub base_url: String,
+    /// Downloads in this release
+    pub entries: Vec<UpdateEntry>,
+}
+
+impl UpdateFeed {
+    /// Create an empty feed for a release
+    pub fn new(
+        title: impl Into<String>,
+        version: impl Into<String>,
+        base_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            version: version.into(),
+            build_version: None,
+            notes: None,
+            pub_date: None,
+            base_url: base_url.into(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Set the build version Sparkle compares against `CFBundleVersion`
+    pub fn with_build_version(mut self, build_version: impl Into<String>) -> Self {
+        self.build_version = Some(build_version.into());
+        self
+    }
+
+    /// Set release notes
+    pub fn with_notes(mut self, notes: impl Into<String>) -> Self {
+        self.notes = Some(notes.into());
+        self
+    }
+
+    /// Set publication date
+    pub fn with_pub_date(mut self, date: DateTime<Utc>) -> Self {
+        self.pub_date = Some(date);
+        self
+    }
+
+    /// Add released artifacts, signing each with `signer` when given
+    ///
+    /// Lengths and hashes are read from the files on disk.
+    pub fn add_artifacts(
+        &mut self,
+        artifacts: &[Artifact],
+        signer: Option<&EdDsaSigner>,
+    ) -> Result<()> {
+        for artifact in artifacts {
+            let file_name = artifact.filename().ok_or_else(|| {
+                FrameworkError::context(
+                    "assembling update feed",
+                    format!("invalid artifact path {}", artifact.path.display()),
+                )
+            })?;
+            let content = std::fs::read(&artifact.path)?;
+
+            let sha1 = ring::digest::digest(&ring::digest::SHA1_FOR_LEGACY_USE_ONLY, &content)
+                .as_ref()
+                .iter()
+                .map(|b| format!("{:02X}", b))
+                .collect();
+            let os = match artifact.platform {
+                Platform::MacOs => Some("macos"),
+                Platform::Windows => Some("windows"),
+                Platform::Linux => Some("linux"),
+                _ => None,
+            };
+
+            debug!(file = %file_name, signed = signer.is_some(), "adding artifact to update feed");
+            self.entries.push(UpdateEntry {
+                file_name: file_name.to_string(),
+                url: format!("{}/{}", self.base_url.trim_end_matches('/'), file_name),
+                length: content.len() as u64,
+                os,
+                sha1,
+                ed_signature: signer.map(|s| s.sign(&content)),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Render the Sparkle appcast
+    pub fn to_appcast(&self) -> String {
+        let build_version = self.build_version.as_deref().unwrap_or(&self.version);
+
+        let mut xml = String::new();
+        xml.push_str("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n");
+        xml.push_str(&format!(
+            "<rss version=\"2.0\" xmlns:sparkle=\"{}\">\n",
+            SPARKLE_NAMESPACE
+        ));
+        xml.push_str("  <channel>\n");
+        xml.push_str(&format!("    <title>{}</title>\n", xml_escape(&self.title)));
+
+        let entries = self
+            .entries
+            .iter()
+            .filter(|e| matches!(e.os, Some("macos") | Some("windows")));
+        for entry in entries {
+            xml.push_str("    <item>\n");
+            xml.push_str(&format!(
+                "      <title>Version {}</title>\n",
+                xml_escape(&self.version)
+            ));
+            if let Some(date) = self.pub_date {
+                xml.push_str(&format!("      <pubDate>{}</pubDate>\n", date.to_rfc2822()));
+            }
+            xml.push_str(&format!(
+                "      <sparkle:version>{}</sparkle:version>\n",
+                xml_escape(build_version)
+            ));
+            xml.push_str(&format!(
+                "      <sparkle:shortVersionString>{}</sparkle:shortVersionString>\n",
+                xml_escape(&self.version)
+            ));
+            if let Some(ref notes) = self.notes {
+                xml.push_str(&format!(
+                    "      <description>{}</description>\n",
+                    xml_escape(notes)
+                ));
+            }
+
+            xml.push_str(&format!(
+                "      <enclosure url=\"{}\" length=\"{}\" type=\"application/octet-stream\" sparkle:version=\"{}\" sparkle:shortVersionString=\"{}\"",
+                xml_escape(&entry.url),
+                entry.length,
+                xml_escape(build_version),
+                xml_escape(&self.version)
+            ));
+            if let Some(os) = entry.os {
+                xml.push_str(&format!(" sparkle:os=\"{}\"", os));
+            }
+            if let Some(ref signature) = entry.ed_signature {
+                xml.push_str(&format!(
+                    " sparkle:edSignature=\"{}\"",
+                    xml_escape(signature)
+                ));
+            }
+            xml.push_str("/>\n");
+            xml.push_str("    </item>\n");
+        }
+
+        xml.push_str("  </channel>\n");
+        xml.push_str("</rss>\n");
+        xml
+    }
+
+    /// Render the Squirrel.Windows `RELEASES` file, or `None` without
+    /// Windows `.nupkg` packages
+    pub fn to_squirrel_releases(&self) -> Option<String> {
+        let lines: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|e| e.os == Some("windows") && e.file_name.ends_with(".nupkg"))
+            .map(|e| format!("{} {} {}", e.sha1, e.file_name, e.length))
+            .collect();
+        if lines.is_empty() {
+            return None;
+        }
+        Some(lines.join("\n") + "\n")
+    }
+
+    /// Render the Squirrel.Mac `latest.json`, or `None` without a macOS artifact
+    pub fn to_squirrel_mac_json(&self) -> Result<Option<String>> {
+        let Some(entry) = self.entries.iter().find(|e| e.os == Some("macos")) else {
+            return Ok(None);
+        };
+        let feed = SquirrelMacFeed {
+            url: &entry.url,
+            name: &self.version,
+            notes: self.notes.as_deref(),
+            pub_date: self.pub_date.map(|d| d.to_rfc3339()),
+        };
+        serde_json::to_string_pretty(&feed)
+            .map(Some)
+            .map_err(|e| FrameworkError::Serialization(e.to_string()))
+    }
+
+    /// Write the appcast and any Squirrel feeds into `dir`
+    ///
+    /// Returns the paths of the written files.
+    pub fn write(&self, dir: &Path) -> Result<Vec<PathBuf>> {
+        std::fs::create_dir_all(dir)?;
+
+        let mut written = vec![dir.join(APPCAST_FILE_NAME)];
+        std::fs::write(&written[0], self.to_appcast())?;
+
+        if let Some(releases) = self.to_squirrel_releases() {
+            let path = dir.join(SQUIRREL_RELEASES_FILE_NAME);
+            std::fs::write(&path, releases)?;
+            written.push(path);
+        }
+        if let Some(json) = self.to_squirrel_mac_json()? {
+            let path = dir.join(SQUIRREL_MAC_FILE_NAME);
+            std::fs::write(&path, json)?;
+            written.push(path);
+        }
+
+        Ok(written)
+    }
+}
+
+/// Escape text for use in XML content and attribute values
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::ArtifactKind;
+    use chrono::TimeZone;
+    use quick_xml::events::Event;
+    use quick_xml::Reader;
+    use ring::signature::{UnparsedPublicKey, ED25519};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const SEED: &str = "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8=";
+
+    fn two_artifact_feed(temp: &TempDir) -> UpdateFeed {
+        let mac = temp.path().join("Acme-2.1.0.zip");
+        let win = temp.path().join("Acme-2.1.0-full.nupkg");
+        let setup = temp.path().join("AcmeSetup.exe");
+        let linux = temp.path().join("Acme-2.1.0.AppImage");
+        std::fs::write(&mac, b"mac update").unwrap();
+        std::fs::write(&win, b"windows update!").unwrap();
+        std::fs::write(&setup, b"installer").unwrap();
+        std::fs::write(&linux, b"linux").unwrap();
+
+        let artifacts = vec![
+            Artifact::new(&mac, ArtifactKind::Archive, Platform::MacOs),
+            Artifact::new(&win, ArtifactKind::Archive, Platform::Windows),
+            Artifact::new(&setup, ArtifactKind::Exe, Platform::Windows),
+            Artifact::new(&linux, ArtifactKind::AppImage, Platform::Linux),
+        ];
+        let signer = EdDsaSigner::from_base64(SEED).unwrap();
+
+        let mut feed = UpdateFeed::new("Acme", "2.1.0", "https://downloads.example.com/v2.1.0/")
+            .with_build_version("210")
+            .with_notes("Faster sync & <b>fewer</b> crashes")
+            .with_pub_date(Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap());
+        feed.add_artifacts(&artifacts, Some(&signer)).unwrap();
+        feed
+    }
+
+    /// Enclosure attributes by URL, parsed from the appcast
+    fn parse_enclosures(xml: &str) -> Vec<HashMap<String, String>> {
+        let mut reader = Reader::from_str(xml);
+        let mut enclosures = Vec::new();
+        let mut depth = 0i32;
+        loop {
+            match reader.read_event().unwrap() {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Empty(e) if e.name().as_ref() == b"enclosure" => {
+                    enclosures.push(
+                        e.attributes()
+                            .map(|a| {
+                                let a = a.unwrap();
+                                (
+                                    String::from_utf8(a.key.as_ref().to_vec()).unwrap(),
+                                    a.unescape_value().unwrap().into_owned(),
+                                )
+                            })
+                            .collect(),
+                    );
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert_eq!(depth, 0, "unbalanced appcast XML");
+        enclosures
+    }
+
+    #[test]
+    fn test_appcast_for_two_artifacts() {
+        let temp = TempDir::new().unwrap();
+        let feed = two_artifact_feed(&temp);
+        let xml = feed.to_appcast();
+
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"utf-8\"?>"));
+        assert!(xml.contains(&format!("xmlns:sparkle=\"{}\"", SPARKLE_NAMESPACE)));
+        assert!(xml.contains("<pubDate>Fri, 1 Mar 2024 12:00:00 +0000</pubDate>"));
+        assert!(xml.contains("Faster sync &amp; &lt;b&gt;fewer&lt;/b&gt; crashes"));
+        // The Linux AppImage has no Sparkle updater to poll the appcast
+        assert_eq!(xml.matches("<item>").count(), 3);
+        assert!(!xml.contains("AppImage"));
+
+        let enclosures = parse_enclosures(&xml);
+        assert_eq!(enclosures.len(), 3);
+
+        let mac = &enclosures[0];
+        assert_eq!(
+            mac["url"],
+            "https://downloads.example.com/v2.1.0/Acme-2.1.0.zip"
+        );
+        assert_eq!(mac["length"], "10");
+        assert_eq!(mac["sparkle:version"], "210");
+        assert_eq!(mac["sparkle:shortVersionString"], "2.1.0");
+        assert_eq!(mac["sparkle:os"], "macos");
+        assert_eq!(enclosures[1]["length"], "15");
+        assert_eq!(enclosures[1]["sparkle:os"], "windows");
+
+        // Signatures verify against the signer's public key
+        let signer = EdDsaSigner::from_base64(SEED).unwrap();
+        let public_key = BASE64.decode(signer.public_key()).unwrap();
+        let signature = BASE64.decode(&mac["sparkle:edSignature"]).unwrap();
+        UnparsedPublicKey::new(&ED25519, &public_key)
+            .verify(b"mac update", &signature)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_squirrel_feeds() {
+        let temp = TempDir::new().unwrap();
+        let feed = two_artifact_feed(&temp);
+
+        // Only the `.nupkg` is listed, not the setup installer
+        let releases = feed.to_squirrel_releases().unwrap();
+        let fields: Vec<&str> = releases.trim().split(' ').collect();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(fields[0].len(), 40);
+        assert_eq!(fields[0], fields[0].to_uppercase());
+        assert_eq!(&fields[1..], ["Acme-2.1.0-full.nupkg", "15"]);
+
+        let json: serde_json::Value =
+            serde_json::from_str(&feed.to_squirrel_mac_json().unwrap().unwrap()).unwrap();
+        assert_eq!(
+            json["url"],
+            "https://downloads.example.com/v2.1.0/Acme-2.1.0.zip"
+        );
+        assert_eq!(json["name"], "2.1.0");
+        assert_eq!(json["pub_date"], "2024-03-01T12:00:00+00:00");
+
+        let written = feed.write(&temp.path().join("feed")).unwrap();
+        assert_eq!(written.len(), 3);
+    }
+
+    #[test]
+    fn test_unsigned_feed_and_invalid_key() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("Acme.zip");
+        std::fs::write(&path, b"update").unwrap();
+
+        let mut feed = UpdateFeed::new("Acme", "1.0.0", "https://example.com");
+        feed.add_artifacts(
+            &[Artifact::new(&path, ArtifactKind::Archive, Platform::MacOs)],
+            None,
+        )
+        .unwrap();
+        assert!(!feed.to_appcast().contains("edSignature"));
+        assert!(feed.to_squirrel_releases().is_none());
+
+        assert!(EdDsaSigner::from_base64("c2hvcnQ=").is_err());
+        assert!(EdDsaSigner::from_base64("not base64!").is_err());
+    }
+}
@@ -1,11 +1,13 @@
 //! Commit history operations
 
+use std::path::Path;
+
 use chrono::{TimeZone, Utc};
 use git2::{Oid, Sort};
 use tracing::{debug, instrument};
 
 use crate::repository::{GitRepo, Result};
-use crate::types::CommitInfo;
+use crate::types::{CommitInfo, DateRange};
 
 impl GitRepo {
     /// Get commits since a specific commit hash
@@ -26,15 +28,7 @@ impl GitRepo {
         revwalk.push(head.id())?;
         revwalk.hide(since)?;
 
-        let mut commits = Vec::new();
-
-        for oid in revwalk {
-            let oid = oid?;
-            let commit = self.repo.find_commit(oid)?;
-            commits.push(commit_to_info(&commit));
-        }
-
-        Ok(commits)
+        self.collect_full_history(revwalk, "Listing commits since a ref")
     }
 
     /// Get commits since a tag
@@ -53,6 +47,118 @@ impl GitRepo {
         Ok(commits)
     }
 
+    /// Get commits reachable from `to` but not from `from`
+    ///
+    /// Both refs may be tags, branches or commit hashes.
+    #[instrument(skip(self), fields(from, to))]
+    pub fn commits_between(&self, from: &str, to: &str) -> Result<Vec<CommitInfo>> {
+        let from_oid = self.repo.revparse_single(from)?.peel_to_commit()?.id();
+        let to_oid = self.repo.revparse_single(to)?.peel_to_commit()?.id();
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(to_oid)?;
+        revwalk.hide(from_oid)?;
+
+        let commits = self.collect_full_history(revwalk, "Comparing two refs")?;
+
+        debug!(
+            count = commits.len(),
+            from, to, "retrieved commits between refs"
+        );
+        Ok(commits)
+    }
+
+    /// Get commits on the current branch whose commit date falls in `range`
+    ///
+    /// The whole history is walked rather than stopping at the first commit
+    /// outside the window, since rebased or cherry-picked commits can have
+    /// dates out of order with their ancestors.
+    #[instrument(skip(self))]
+    pub fn commits_in_range(&self, range: &DateRange) -> Result<Vec<CommitInfo>> {
+        let mut commits = self.all_commits()?;
+        commits.retain(|c| range.includes(c));
+        debug!(count = commits.len(), "retrieved commits in date range");
+        Ok(commits)
+    }
+
+    /// Commits between two refs (see [`commits_between`](Self::commits_between))
+    /// that also fall in a date range
+    #[instrument(skip(self), fields(from, to))]
+    pub fn commits_between_in_range(
+        &self,
+        from: &str,
+        to: &str,
+        range: &DateRange,
+    ) -> Result<Vec<CommitInfo>> {
+        let mut commits = self.commits_between(from, to)?;
+        commits.retain(|c| range.includes(c));
+        debug!(
+            count = commits.len(),
+            from, to, "retrieved commits between refs in date range"
+        );
+        Ok(commits)
+    }
+
+    /// Paths of all files tracked at a ref
+    pub fn files_at(&self, rev: &str) -> Result<Vec<String>> {
+        let tree = self.repo.revparse_single(rev)?.peel_to_tree()?;
+
+        let mut paths = Vec::new();
+        tree.walk(git2::TreeWalkMode::PreOrder, |dir, entry| {
+            if entry.kind() == Some(git2::ObjectType::Blob) {
+                if let Some(name) = entry.name() {
+                    paths.push(format!("{}{}", dir, name));
+                }
+            }
+            git2::TreeWalkResult::Ok
+        })?;
+        Ok(paths)
+    }
+
+    /// Commit hash a revision resolves to, or `None` if it doesn't exist
+    pub fn resolve_commit(&self, rev: &str) -> Result<Option<String>> {
+        match self.repo.revparse_single(rev) {
+            Ok(object) => Ok(Some(object.peel_to_commit()?.id().to_string())),
+            Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Whether two revisions have the same tree
+    pub fn same_tree(&self, a: &str, b: &str) -> Result<bool> {
+        let tree =
+            |rev: &str| -> Result<Oid> { Ok(self.repo.revparse_single(rev)?.peel_to_tree()?.id()) };
+        Ok(tree(a)? == tree(b)?)
+    }
+
+    /// Paths that differ between the trees of two revisions
+    pub fn changed_files(&self, from: &str, to: &str) -> Result<Vec<String>> {
+        let tree = |rev: &str| -> Result<git2::Tree<'_>> {
+            Ok(self.repo.revparse_single(rev)?.peel_to_tree()?)
+        };
+        let diff = self
+            .repo
+            .diff_tree_to_tree(Some(&tree(from)?), Some(&tree(to)?), None)?;
+        Ok(diff
+            .deltas()
+            .filter_map(|delta| delta.new_file().path().or_else(|| delta.old_file().path()))
+            .map(|path| path.to_string_lossy().replace('\\', "/"))
+            .collect())
+    }
+
+    /// Read a file's contents as of a ref, or `None` if it doesn't exist there
+    pub fn file_at(&self, rev: &str, path: &str) -> Result<Option<String>> {
+        let tree = self.repo.revparse_single(rev)?.peel_to_tree()?;
+        let entry = match tree.get_path(Path::new(path)) {
+            Ok(entry) => entry,
+            Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        let blob = entry.to_object(&self.repo)?.peel_to_blob()?;
+        Ok(Some(String::from_utf8_lossy(blob.content()).into_owned()))
+    }
+
     /// Get all commits on the current branch
     #[instrument(skip(self))]
     pub fn all_commits(&self) -> Result<Vec<CommitInfo>> {
@@ -62,18 +168,120 @@ impl GitRepo {
         revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
         revwalk.push(head.id())?;
 
-        let mut commits = Vec::new();
+        let commits = self.collect_full_history(revwalk, "Listing all commits")?;
+        debug!(count = commits.len(), "retrieved all commits");
+        Ok(commits)
+    }
 
-        for oid in revwalk {
-            let oid = oid?;
-            let commit = self.repo.find_commit(oid)?;
-            commits.push(commit_to_info(&commit));
+    /// Commits since `since_tag` (or on the whole branch when `None`) that
+    /// change something under `path`
+    ///
+    /// `path` is relative to the repository root, such as a monorepo
+    /// package directory; an empty path matches every commit. A commit
+    /// counts when its diff against its first parent touches `path`.
+    #[instrument(skip(self), fields(since_tag, path = %path.display()))]
+    pub fn commits_for_path(
+        &self,
+        since_tag: Option<&str>,
+        path: &Path,
+    ) -> Result<Vec<CommitInfo>> {
+        let commits = match since_tag {
+            Some(tag) => self.commits_since_tag(tag)?,
+            None => self.all_commits()?,
+        };
+        let pathspec = path.to_string_lossy().replace('\\', "/");
+        if pathspec.is_empty() {
+            return Ok(commits);
         }
 
-        debug!(count = commits.len(), "retrieved all commits");
+        let mut options = git2::DiffOptions::new();
+        options.pathspec(&pathspec);
+        let mut touching = Vec::new();
+        for info in commits {
+            let commit = self.repo.find_commit(Oid::from_str(&info.hash)?)?;
+            let parent_tree = match commit.parent(0) {
+                Ok(parent) => Some(parent.tree()?),
+                Err(_) => None,
+            };
+            let diff = self.repo.diff_tree_to_tree(
+                parent_tree.as_ref(),
+                Some(&commit.tree()?),
+                Some(&mut options),
+            )?;
+            if diff.deltas().len() > 0 {
+                touching.push(info);
+            }
+        }
+        debug!(count = touching.len(), "retrieved commits touching path");
+        Ok(touching)
+    }
+
+    /// Get all commits reachable from a ref, which may be a tag, branch or
+    /// commit hash
+    #[instrument(skip(self), fields(to))]
+    pub fn commits_until(&self, to: &str) -> Result<Vec<CommitInfo>> {
+        let to_oid = self.repo.revparse_single(to)?.peel_to_commit()?.id();
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::TIME)?;
+        revwalk.push(to_oid)?;
+
+        let commits = self.collect_full_history(revwalk, "Listing commits up to a ref")?;
+        debug!(count = commits.len(), to, "retrieved commits up to ref");
         Ok(commits)
     }
 
+    /// The first commit of the current branch
+    ///
+    /// Anchors the changelog range of a first release, when no tag exists
+    /// yet. Fails in an empty repository, and in a shallow clone where the
+    /// real first commit was never fetched.
+    #[instrument(skip(self))]
+    pub fn first_commit(&self) -> Result<CommitInfo> {
+        let head = self.head_commit()?;
+        self.require_full_history("Finding the first commit")?;
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk.push(head.id())?;
+
+        // HEAD is in the walk, so it yields at least one commit
+        let oid = revwalk.next().unwrap_or(Ok(head.id()))?;
+        let commit = self.repo.find_commit(oid)?;
+        debug!(hash = %oid, "found first commit");
+        Ok(commit_to_info(&commit))
+    }
+
+    /// The first commit of the current branch that contains `path`
+    ///
+    /// `path` is relative to the repository root and may be a file or a
+    /// directory such as a monorepo package, anchoring that package's first
+    /// changelog. Returns `None` if `path` has no history on this branch.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub fn first_commit_for_path(&self, path: &Path) -> Result<Option<CommitInfo>> {
+        let head = self.head_commit()?;
+        self.require_full_history("Finding the first commit for a path")?;
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE)?;
+        revwalk.push(head.id())?;
+
+        for oid in revwalk {
+            let commit = self.repo.find_commit(oid?)?;
+            match commit.tree()?.get_path(path) {
+                Ok(_) => {
+                    debug!(hash = %commit.id(), "found first commit for path");
+                    return Ok(Some(commit_to_info(&commit)));
+                }
+                Err(e) if e.code() == git2::ErrorCode::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        debug!("path has no history");
+        Ok(None)
+    }
+
     /// Get the most recent N commits
     #[instrument(skip(self), fields(count))]
     pub fn recent_commits(&self, count: usize) -> Result<Vec<CommitInfo>> {
@@ -99,12 +307,51 @@ impl GitRepo {
         Ok(commits)
     }
 
+    /// Number of commits reachable from HEAD
+    ///
+    /// Commonly used as a monotonically increasing build number, so it refuses
+    /// to answer in a shallow clone rather than return a too-small count.
+    #[instrument(skip(self))]
+    pub fn commit_count(&self) -> Result<usize> {
+        self.require_full_history("Counting commits")?;
+
+        let mut revwalk = self.repo.revwalk()?;
+        revwalk.push(self.head_commit()?.id())?;
+        let count = revwalk.count();
+        debug!(count, "counted commits");
+        Ok(count)
+    }
+
     /// Get a specific commit by hash
     pub fn get_commit(&self, hash: &str) -> Result<CommitInfo> {
         let oid = Oid::from_str(hash)?;
         let commit = self.repo.find_commit(oid)?;
         Ok(commit_to_info(&commit))
     }
+
+    /// Collect a revwalk, failing if it runs into the shallow boundary
+    ///
+    /// Reaching a commit whose parents were never fetched means the walk was
+    /// cut short and the result would be silently incomplete.
+    fn collect_full_history(
+        &self,
+        revwalk: git2::Revwalk<'_>,
+        operation: &str,
+    ) -> Result<Vec<CommitInfo>> {
+        let boundary = self.shallow_boundary();
+
+        let mut commits = Vec::new();
+        for oid in revwalk {
+            let oid = oid?;
+            if boundary.contains(&oid) {
+                self.require_full_history(operation)?;
+            }
+            let commit = self.repo.find_commit(oid)?;
+            commits.push(commit_to_info(&commit));
+        }
+
+        Ok(commits)
+    }
 }
 
 /// Convert a git2 Commit to CommitInfo
@@ -173,8 +420,6 @@ mod tests {
         (temp, git_repo)
     }
 
-    use std::path::Path;
-
     #[test]
     fn test_recent_commits() {
         let (_temp, repo) = setup_repo_with_commits();
@@ -189,4 +434,177 @@ mod tests {
         let commits = repo.all_commits().unwrap();
         assert!(!commits.is_empty());
     }
+
+    #[test]
+    fn test_commits_between_and_files_at() {
+        let (_temp, repo) = setup_repo_with_commits();
+        let first = repo.all_commits().unwrap().pop().unwrap();
+
+        let commits = repo.commits_between(&first.hash, "HEAD").unwrap();
+        assert_eq!(commits.len(), 1);
+        assert_eq!(commits[0].message, "feat: add file");
+
+        assert_eq!(repo.files_at("HEAD").unwrap(), vec!["file.txt"]);
+        assert!(repo.files_at(&first.hash).unwrap().is_empty());
+
+        assert_eq!(
+            repo.file_at("HEAD", "file.txt").unwrap().as_deref(),
+            Some("content")
+        );
+        assert!(repo.file_at(&first.hash, "file.txt").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_first_commit_and_first_commit_for_path() {
+        let (temp, repo) = setup_repo_with_commits();
+        let git = repo.inner();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+
+        // A package introduced after the first two commits
+        let package = temp.path().join("packages/app");
+        std::fs::create_dir_all(&package).unwrap();
+        for content in ["v1", "v2"] {
+            std::fs::write(package.join("lib.rs"), content).unwrap();
+            let mut index = git.index().unwrap();
+            index.add_path(Path::new("packages/app/lib.rs")).unwrap();
+            index.write().unwrap();
+            let tree = git.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = git.head().unwrap().peel_to_commit().unwrap();
+            let message = format!("feat(app): {}", content);
+            git.commit(Some("HEAD"), &sig, &sig, &message, &tree, &[&parent])
+                .unwrap();
+        }
+
+        assert_eq!(repo.first_commit().unwrap().message, "Initial commit");
+
+        let first = |path: &str| {
+            repo.first_commit_for_path(Path::new(path))
+                .unwrap()
+                .map(|c| c.message)
+        };
+        assert_eq!(first("file.txt").as_deref(), Some("feat: add file"));
+        assert_eq!(first("packages/app").as_deref(), Some("feat(app): v1"));
+        assert_eq!(
+            first("packages/app/lib.rs").as_deref(),
+            Some("feat(app): v1")
+        );
+        assert_eq!(first("packages/web"), None);
+    }
+
+    #[test]
+    fn test_commits_for_path() {
+        let (temp, repo) = setup_repo_with_commits();
+        let git = repo.inner();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        let commit = |path: &str, message: &str| {
+            let file = temp.path().join(path);
+            std::fs::create_dir_all(file.parent().unwrap()).unwrap();
+            std::fs::write(&file, message).unwrap();
+            let mut index = git.index().unwrap();
+            index.add_path(Path::new(path)).unwrap();
+            index.write().unwrap();
+            let tree = git.find_tree(index.write_tree().unwrap()).unwrap();
+            let parent = git.head().unwrap().peel_to_commit().unwrap();
+            git.commit(Some("HEAD"), &sig, &sig, message, &tree, &[&parent])
+                .unwrap()
+        };
+
+        commit("packages/api/lib.rs", "feat(api): first");
+        let tagged = commit("packages/web/lib.rs", "feat(web): first");
+        git.tag_lightweight("web@1.0.0", &git.find_object(tagged, None).unwrap(), false)
+            .unwrap();
+        commit("packages/api/lib.rs", "fix(api): second");
+        commit("packages/apiary/lib.rs", "feat(apiary): first");
+
+        let messages = |since: Option<&str>, path: &str| -> Vec<String> {
+            repo.commits_for_path(since, Path::new(path))
+                .unwrap()
+                .into_iter()
+                .map(|c| c.message)
+                .collect()
+        };
+        assert_eq!(
+            messages(None, "packages/api"),
+            ["fix(api): second", "feat(api): first"]
+        );
+        assert_eq!(
+            messages(Some("web@1.0.0"), "packages/web"),
+            Vec::<String>::new()
+        );
+        assert_eq!(
+            messages(Some("web@1.0.0"), "packages/api"),
+            ["fix(api): second"]
+        );
+        assert_eq!(messages(Some("web@1.0.0"), "").len(), 2);
+    }
+
+    #[test]
+    fn test_first_commit_in_empty_repo() {
+        let temp = TempDir::new().unwrap();
+        Repository::init(temp.path()).unwrap();
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        assert!(repo.first_commit().is_err());
+        assert!(repo.first_commit_for_path(Path::new("src")).is_err());
+    }
+
+    /// Commit on HEAD with separate author and committer dates (days since
+    /// 2024-01-01, negative for 2023)
+    fn commit_at(repo: &Repository, message: &str, author_day: i64, commit_day: i64) -> Oid {
+        let base = Utc
+            .with_ymd_and_hms(2024, 1, 1, 12, 0, 0)
+            .unwrap()
+            .timestamp();
+        let time = |day: i64| git2::Time::new(base + day * 86_400, 0);
+        let author = Signature::new("Test", "test@example.com", &time(author_day)).unwrap();
+        let committer = Signature::new("Test", "test@example.com", &time(commit_day)).unwrap();
+
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        repo.commit(Some("HEAD"), &author, &committer, message, &tree, &parents)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_commits_in_date_range() {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        commit_at(&repo, "chore: old", -30, -30);
+        let tagged = commit_at(&repo, "feat: december", -5, -5);
+        // Rebased: authored in 2023 but committed in January
+        commit_at(&repo, "fix: rebased", -10, 4);
+        // Committer clock skew: dated before its parent
+        commit_at(&repo, "feat: skewed", 2, 1);
+        commit_at(&repo, "feat: february", 35, 35);
+        let git_repo = GitRepo::open(temp.path()).unwrap();
+
+        let messages = |commits: Vec<CommitInfo>| -> Vec<String> {
+            commits.into_iter().map(|c| c.message).collect()
+        };
+        let jan = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let feb = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+
+        let since = git_repo.commits_in_range(&DateRange::since(jan)).unwrap();
+        assert_eq!(
+            messages(since),
+            vec!["feat: february", "feat: skewed", "fix: rebased"]
+        );
+
+        let january = DateRange::since(jan).with_until(feb);
+        assert_eq!(
+            messages(git_repo.commits_in_range(&january).unwrap()),
+            vec!["feat: skewed", "fix: rebased"]
+        );
+
+        let before = git_repo.commits_in_range(&DateRange::until(jan)).unwrap();
+        assert_eq!(messages(before), vec!["feat: december", "chore: old"]);
+
+        // Intersection with a ref range
+        let between = git_repo
+            .commits_between_in_range(&tagged.to_string(), "HEAD", &DateRange::until(feb))
+            .unwrap();
+        assert_eq!(messages(between), vec!["feat: skewed", "fix: rebased"]);
+    }
 }
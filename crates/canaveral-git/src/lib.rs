@@ -5,12 +5,20 @@
 
 mod commits;
 pub mod hooks;
+mod push;
 mod remote;
 mod repository;
+mod rollback;
+mod shallow;
+mod staging;
 mod status;
 mod tags;
 pub mod types;
+mod worktree;
 
+pub use push::{push_branch_with_retry, push_tag_with_retry, PushOptions};
 pub use remote::{git_push, git_push_tag, git_push_with_tags};
 pub use repository::{GitRepo, Result};
-pub use types::{CommitInfo, TagInfo};
+pub use rollback::{ReleaseRollback, RollbackOutcome};
+pub use types::{CommitInfo, DateRange, TagInfo};
+pub use worktree::Worktree;
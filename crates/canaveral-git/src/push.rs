@@ -0,0 +1,419 @@
+//! Push operations with retry and idempotency
+//!
+//! Pushes shell out to the git CLI (for credential helpers) and classify
+//! failures so that transient network errors are retried with backoff,
+//! non-fast-forward rejections are reported distinctly, and re-pushing a tag
+//! that is already on the remote at the same commit is a no-op.
+
+use std::path::Path;
+use std::process::{Command, Output};
+use std::time::Duration;
+
+use tracing::{debug, info, instrument, warn};
+
+use crate::repository::Result;
+use canaveral_core::error::GitError;
+
+/// Options controlling push retries
+#[derive(Debug, Clone)]
+pub struct PushOptions {
+    /// Maximum number of attempts for transient failures
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled after each attempt
+    pub initial_backoff: Duration,
+    /// On a non-fast-forward branch push, `git pull --rebase` and try again
+    pub rebase_on_conflict: bool,
+    /// Overwrite the remote branch if it is still where the local
+    /// remote-tracking branch says (`--force-with-lease`)
+    pub force_with_lease: bool,
+}
+
+impl Default for PushOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_secs(1),
+            rebase_on_conflict: false,
+            force_with_lease: false,
+        }
+    }
+}
+
+impl PushOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of attempts
+    pub fn with_max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts.max(1);
+        self
+    }
+
+    /// Set the initial backoff delay
+    pub fn with_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Rebase onto the remote branch and retry on non-fast-forward
+    pub fn with_rebase_on_conflict(mut self, rebase: bool) -> Self {
+        self.rebase_on_conflict = rebase;
+        self
+    }
+
+    /// Force-push branches with `--force-with-lease`
+    pub fn with_force_with_lease(mut self, force: bool) -> Self {
+        self.force_with_lease = force;
+        self
+    }
+}
+
+/// Why a push was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PushFailure {
+    /// Network or server hiccup worth retrying
+    Transient,
+    /// Remote branch has commits we don't
+    NonFastForward,
+    /// Remote already has a ref with this tag name
+    TagExists,
+    /// Anything else (auth, hooks, permissions)
+    Other,
+}
+
+impl PushFailure {
+    /// Classify a failed push from git's stderr
+    pub(crate) fn classify(stderr: &str) -> Self {
+        let stderr = stderr.to_lowercase();
+
+        if stderr.contains("(already exists)") {
+            return Self::TagExists;
+        }
+
+        if stderr.contains("non-fast-forward")
+            || stderr.contains("(fetch first)")
+            || stderr.contains("tip of your current branch is behind")
+        {
+            return Self::NonFastForward;
+        }
+
+        const TRANSIENT: &[&str] = &[
+            "could not resolve host",
+            "connection timed out",
+            "connection reset",
+            "connection refused",
+            "operation timed out",
+            "temporary failure",
+            "the remote end hung up unexpectedly",
+            "early eof",
+            "rpc failed",
+            "http 502",
+            "http 503",
+            "http 504",
+        ];
+        if TRANSIENT.iter().any(|p| stderr.contains(p)) {
+            return Self::Transient;
+        }
+
+        Self::Other
+    }
+}
+
+/// Run `git push` with retries for transient failures
+///
+/// Returns the output of the last attempt. `dir` defaults to the current
+/// directory.
+pub(crate) fn run_push(
+    dir: Option<&Path>,
+    args: &[&str],
+    options: &PushOptions,
+) -> std::io::Result<Output> {
+    let mut backoff = options.initial_backoff;
+    let mut attempt = 1;
+
+    loop {
+        let mut cmd = Command::new("git");
+        // Untranslated messages, so PushFailure::classify can read stderr
+        cmd.arg("push").args(args).env("LC_ALL", "C");
+        if let Some(dir) = dir {
+            cmd.current_dir(dir);
+        }
+        let output = cmd.output()?;
+
+        if output.status.success() || attempt >= options.max_attempts.max(1) {
+            return Ok(output);
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if PushFailure::classify(&stderr) != PushFailure::Transient {
+            return Ok(output);
+        }
+
+        warn!(
+            attempt,
+            max_attempts = options.max_attempts,
+            backoff_ms = backoff.as_millis(),
+            error = %stderr.trim(),
+            "transient push failure, retrying"
+        );
+        std::thread::sleep(backoff);
+        backoff *= 2;
+        attempt += 1;
+    }
+}
+
+/// Push a branch, retrying transient failures
+///
+/// A non-fast-forward rejection yields [`GitError::NonFastForward`] unless
+/// `rebase_on_conflict` is set, in which case the branch is rebased onto the
+/// remote and pushed once more.
+#[instrument(skip(options), fields(dir = %dir.display()))]
+pub fn push_branch_with_retry(
+    dir: &Path,
+    remote: &str,
+    branch: &str,
+    options: &PushOptions,
+) -> Result<()> {
+    let mut rebased = false;
+
+    loop {
+        let mut args = vec![remote, branch];
+        if options.force_with_lease {
+            args.insert(0, "--force-with-lease");
+        }
+        let output =
+            run_push(Some(dir), &args, options).map_err(|e| GitError::PushFailed(e.to_string()))?;
+
+        if output.status.success() {
+            info!(remote, branch, rebased, "pushed branch");
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        match PushFailure::classify(&stderr) {
+            PushFailure::NonFastForward if options.rebase_on_conflict && !rebased => {
+                info!(remote, branch, "rebasing onto remote before retry");
+                git(dir, &["pull", "--rebase", remote, branch])?;
+                rebased = true;
+            }
+            PushFailure::NonFastForward => {
+                return Err(GitError::NonFastForward {
+                    remote: remote.to_string(),
+                    branch: branch.to_string(),
+                });
+            }
+            _ => {
+                return Err(GitError::PushFailed(format!(
+                    "Failed to push to {}/{}: {}",
+                    remote, branch, stderr
+                )));
+            }
+        }
+    }
+}
+
+/// Push a tag, retrying transient failures
+///
+/// If the remote already has the tag at the same commit the push is treated
+/// as successful; a tag pointing elsewhere yields [`GitError::TagConflict`].
+#[instrument(skip(options), fields(dir = %dir.display()))]
+pub fn push_tag_with_retry(
+    dir: &Path,
+    remote: &str,
+    tag: &str,
+    options: &PushOptions,
+) -> Result<()> {
+    let refspec = format!("refs/tags/{}", tag);
+    let output = run_push(Some(dir), &[remote, &refspec], options)
+        .map_err(|e| GitError::PushFailed(e.to_string()))?;
+
+    if output.status.success() {
+        info!(remote, tag, "pushed tag");
+        return Ok(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    if PushFailure::classify(&stderr) != PushFailure::TagExists {
+        return Err(GitError::PushFailed(format!(
+            "Failed to push tag {}: {}",
+            tag, stderr
+        )));
+    }
+
+    let local_sha = git(dir, &["rev-parse", &format!("{}^{{commit}}", refspec)])?;
+    let remote_sha = remote_tag_commit(dir, remote, tag)?.unwrap_or_default();
+
+    if local_sha == remote_sha {
+        info!(remote, tag, sha = %local_sha, "tag already on remote at same commit");
+        Ok(())
+    } else {
+        Err(GitError::TagConflict {
+            tag: tag.to_string(),
+            local_sha,
+            remote_sha,
+        })
+    }
+}
+
+/// Commit a remote tag points at, peeling annotated tags
+pub(crate) fn remote_tag_commit(dir: &Path, remote: &str, tag: &str) -> Result<Option<String>> {
+    let refname = format!("refs/tags/{}", tag);
+    let listing = git(dir, &["ls-remote", "--tags", remote, &refname])?;
+    debug!(remote, tag, listing = %listing, "remote tag listing");
+
+    let mut direct = None;
+    for line in listing.lines() {
+        let Some((sha, name)) = line.split_once('\t') else {
+            continue;
+        };
+        if name == format!("{}^{{}}", refname) {
+            return Ok(Some(sha.to_string()));
+        }
+        if name == refname {
+            direct = Some(sha.to_string());
+        }
+    }
+    Ok(direct)
+}
+
+/// Run a git command in `dir` and return trimmed stdout
+pub(crate) fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| GitError::PushFailed(e.to_string()))?;
+
+    if !output.status.success() {
+        return Err(GitError::PushFailed(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    /// Clone a bare remote into `name` with a local identity
+    fn clone(temp: &TempDir, name: &str) -> std::path::PathBuf {
+        let remote = temp.path().join("remote.git");
+        run(
+            temp.path(),
+            &["clone", "-q", remote.to_str().unwrap(), name],
+        );
+        let dir = temp.path().join(name);
+        run(&dir, &["config", "user.name", "Test"]);
+        run(&dir, &["config", "user.email", "test@example.com"]);
+        dir
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        std::fs::write(dir.join(file), file).unwrap();
+        run(dir, &["add", file]);
+        run(dir, &["commit", "-q", "-m", file]);
+    }
+
+    /// Bare remote with one commit on `main`, plus two clones
+    fn setup() -> (TempDir, std::path::PathBuf, std::path::PathBuf) {
+        let temp = TempDir::new().unwrap();
+        run(
+            temp.path(),
+            &["init", "-q", "--bare", "-b", "main", "remote.git"],
+        );
+
+        let first = clone(&temp, "first");
+        run(&first, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+        commit(&first, "a.txt");
+        run(&first, &["push", "-q", "origin", "main"]);
+
+        let second = clone(&temp, "second");
+        (temp, first, second)
+    }
+
+    fn options() -> PushOptions {
+        PushOptions::new().with_backoff(Duration::from_millis(1))
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(
+            PushFailure::classify(" ! [rejected]        v1.0.0 -> v1.0.0 (already exists)"),
+            PushFailure::TagExists
+        );
+        assert_eq!(
+            PushFailure::classify(" ! [rejected]        main -> main (fetch first)"),
+            PushFailure::NonFastForward
+        );
+        assert_eq!(
+            PushFailure::classify("fatal: unable to access: Could not resolve host: github.com"),
+            PushFailure::Transient
+        );
+        assert_eq!(
+            PushFailure::classify("remote: Permission denied"),
+            PushFailure::Other
+        );
+    }
+
+    #[test]
+    fn test_tag_already_on_remote_same_sha() {
+        let (_temp, first, second) = setup();
+
+        run(&first, &["tag", "v1.0.0"]);
+        push_tag_with_retry(&first, "origin", "v1.0.0", &options()).unwrap();
+
+        // Same tag at the same commit from another clone is a no-op
+        run(&second, &["tag", "v1.0.0"]);
+        push_tag_with_retry(&second, "origin", "v1.0.0", &options()).unwrap();
+    }
+
+    #[test]
+    fn test_tag_conflict() {
+        let (_temp, first, second) = setup();
+
+        run(&first, &["tag", "v1.0.0"]);
+        push_tag_with_retry(&first, "origin", "v1.0.0", &options()).unwrap();
+
+        commit(&second, "b.txt");
+        run(&second, &["tag", "v1.0.0"]);
+        let result = push_tag_with_retry(&second, "origin", "v1.0.0", &options());
+        assert!(matches!(result, Err(GitError::TagConflict { .. })));
+    }
+
+    #[test]
+    fn test_non_fast_forward_branch_push() {
+        let (_temp, first, second) = setup();
+
+        commit(&first, "b.txt");
+        push_branch_with_retry(&first, "origin", "main", &options()).unwrap();
+
+        commit(&second, "c.txt");
+        let result = push_branch_with_retry(&second, "origin", "main", &options());
+        assert!(matches!(result, Err(GitError::NonFastForward { .. })));
+
+        let rebase = options().with_rebase_on_conflict(true);
+        push_branch_with_retry(&second, "origin", "main", &rebase).unwrap();
+        assert!(second.join("b.txt").exists());
+    }
+}
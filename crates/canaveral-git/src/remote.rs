@@ -2,6 +2,7 @@
 
 use tracing::{info, instrument};
 
+use crate::push::{run_push, PushOptions};
 use crate::repository::{GitRepo, Result};
 use canaveral_core::error::GitError;
 
@@ -110,12 +111,12 @@ impl GitRepo {
 }
 
 /// Push using git CLI (more reliable for authentication)
+///
+/// Transient network failures are retried with backoff.
 #[instrument(fields(remote, tag))]
 pub fn git_push_tag(remote: &str, tag: &str) -> std::io::Result<std::process::Output> {
     let start = std::time::Instant::now();
-    let output = std::process::Command::new("git")
-        .args(["push", remote, tag])
-        .output()?;
+    let output = run_push(None, &[remote, tag], &PushOptions::default())?;
     info!(
         remote,
         tag,
@@ -127,12 +128,12 @@ pub fn git_push_tag(remote: &str, tag: &str) -> std::io::Result<std::process::Ou
 }
 
 /// Push commits using git CLI
+///
+/// Transient network failures are retried with backoff.
 #[instrument(fields(remote, branch))]
 pub fn git_push(remote: &str, branch: &str) -> std::io::Result<std::process::Output> {
     let start = std::time::Instant::now();
-    let output = std::process::Command::new("git")
-        .args(["push", remote, branch])
-        .output()?;
+    let output = run_push(None, &[remote, branch], &PushOptions::default())?;
     info!(
         remote,
         branch,
@@ -144,12 +145,16 @@ pub fn git_push(remote: &str, branch: &str) -> std::io::Result<std::process::Out
 }
 
 /// Push both commits and tags using git CLI
+///
+/// Transient network failures are retried with backoff.
 #[instrument(fields(remote, branch))]
 pub fn git_push_with_tags(remote: &str, branch: &str) -> std::io::Result<std::process::Output> {
     let start = std::time::Instant::now();
-    let output = std::process::Command::new("git")
-        .args(["push", "--follow-tags", remote, branch])
-        .output()?;
+    let output = run_push(
+        None,
+        &["--follow-tags", remote, branch],
+        &PushOptions::default(),
+    )?;
     info!(
         remote,
         branch,
@@ -0,0 +1,218 @@
+//! Undoing a local release after a failed push
+//!
+//! A release commits and tags locally before pushing. When the push fails
+//! the local repository is left ahead of the remote with a tag that was
+//! never published, which the next run mistakes for a partial release.
+//! [`ReleaseRollback`] records HEAD before the release commit and, after a
+//! failed push, deletes the tag and resets the commit again. Whatever the
+//! remote already has is kept, so a rollback never unpublishes anything.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+use tracing::{debug, info, instrument};
+
+use crate::push::git;
+use crate::repository::Result;
+
+/// What a rollback undid
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RollbackOutcome {
+    /// The local tag was deleted
+    pub tag_deleted: bool,
+    /// The release commit was reset
+    pub commit_reset: bool,
+    /// The tag or commit was already on the remote and was kept
+    pub already_pushed: bool,
+}
+
+/// Local release state to restore when publishing it fails
+#[derive(Debug, Clone)]
+pub struct ReleaseRollback {
+    dir: PathBuf,
+    remote: String,
+    tag: String,
+    base: String,
+}
+
+impl ReleaseRollback {
+    /// Record HEAD in `dir` before the release commit and `tag` are made
+    pub fn capture(dir: &Path, remote: &str, tag: &str) -> Result<Self> {
+        let base = git(dir, &["rev-parse", "HEAD"])?;
+        debug!(dir = %dir.display(), tag, base = %base, "recorded release base");
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            remote: remote.to_string(),
+            tag: tag.to_string(),
+            base,
+        })
+    }
+
+    /// The release tag
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Delete the local tag and reset the release commit
+    ///
+    /// A tag the remote already has at the same commit, or a release commit
+    /// a remote ref points at, is left alone. The reset is hard: the working
+    /// tree only holds the release's own version bump and changelog, which a
+    /// retry writes again.
+    #[instrument(skip(self), fields(dir = %self.dir.display(), tag = %self.tag))]
+    pub fn rollback(&self) -> Result<RollbackOutcome> {
+        let listing = git(&self.dir, &["ls-remote", &self.remote])?;
+        let remote_refs: HashSet<(&str, &str)> = listing
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .collect();
+        let remote_shas: HashSet<&str> = remote_refs.iter().map(|(sha, _)| *sha).collect();
+        let mut outcome = RollbackOutcome::default();
+
+        let refname = format!("refs/tags/{}", self.tag);
+        if let Ok(tagged) = git(
+            &self.dir,
+            &["rev-parse", &format!("{}^{{commit}}", refname)],
+        ) {
+            let peeled = format!("{}^{{}}", refname);
+            let published = remote_refs
+                .iter()
+                .any(|(sha, name)| *sha == tagged && (*name == refname || *name == peeled));
+            if published {
+                info!(tag = %self.tag, "tag already on remote, keeping it");
+                outcome.already_pushed = true;
+            } else {
+                git(&self.dir, &["tag", "-d", &self.tag])?;
+                info!(tag = %self.tag, "deleted unpublished tag");
+                outcome.tag_deleted = true;
+            }
+        }
+
+        let head = git(&self.dir, &["rev-parse", "HEAD"])?;
+        if head != self.base {
+            if remote_shas.contains(head.as_str()) {
+                info!(commit = %head, "release commit already on remote, keeping it");
+                outcome.already_pushed = true;
+            } else {
+                git(&self.dir, &["reset", "-q", "--hard", &self.base])?;
+                info!(commit = %head, base = %self.base, "reset unpublished release commit");
+                outcome.commit_reset = true;
+            }
+        }
+
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::push::{push_branch_with_retry, push_tag_with_retry, PushOptions};
+    use canaveral_core::error::GitError;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn commit(dir: &Path, file: &str) {
+        std::fs::write(dir.join(file), file).unwrap();
+        run(dir, &["add", file]);
+        run(dir, &["commit", "-q", "-m", file]);
+    }
+
+    /// Bare remote with one commit on `main` and a clone of it
+    fn setup() -> (TempDir, PathBuf) {
+        let temp = TempDir::new().unwrap();
+        run(
+            temp.path(),
+            &["init", "-q", "--bare", "-b", "main", "remote.git"],
+        );
+        run(temp.path(), &["clone", "-q", "remote.git", "local"]);
+        let local = temp.path().join("local");
+        run(&local, &["config", "user.name", "Test"]);
+        run(&local, &["config", "user.email", "test@example.com"]);
+        run(&local, &["symbolic-ref", "HEAD", "refs/heads/main"]);
+        commit(&local, "a.txt");
+        run(&local, &["push", "-q", "origin", "main"]);
+        (temp, local)
+    }
+
+    /// Commit and tag a release the way `canaveral release` does
+    fn release(dir: &Path, tag: &str) -> ReleaseRollback {
+        let rollback = ReleaseRollback::capture(dir, "origin", tag).unwrap();
+        commit(dir, "CHANGELOG.md");
+        run(dir, &["tag", "-a", tag, "-m", tag]);
+        rollback
+    }
+
+    fn options() -> PushOptions {
+        PushOptions::new().with_max_attempts(1)
+    }
+
+    #[test]
+    fn test_rollback_after_rejected_push() {
+        let (temp, local) = setup();
+        let base = run(&local, &["rev-parse", "HEAD"]);
+
+        // Someone else pushes first, so the release push is rejected
+        run(temp.path(), &["clone", "-q", "remote.git", "other"]);
+        let other = temp.path().join("other");
+        run(&other, &["config", "user.name", "Other"]);
+        run(&other, &["config", "user.email", "other@example.com"]);
+        commit(&other, "b.txt");
+        run(&other, &["push", "-q", "origin", "main"]);
+
+        let rollback = release(&local, "v1.0.0");
+        let result = push_branch_with_retry(&local, "origin", "main", &options());
+        assert!(matches!(result, Err(GitError::NonFastForward { .. })));
+
+        let outcome = rollback.rollback().unwrap();
+        assert_eq!(
+            outcome,
+            RollbackOutcome {
+                tag_deleted: true,
+                commit_reset: true,
+                already_pushed: false,
+            }
+        );
+        assert_eq!(run(&local, &["tag", "--list"]), "");
+        assert_eq!(run(&local, &["rev-parse", "HEAD"]), base);
+        assert!(!local.join("CHANGELOG.md").exists());
+    }
+
+    #[test]
+    fn test_rollback_keeps_what_was_pushed() {
+        let (_temp, local) = setup();
+
+        // The commit made it to the remote before the tag push failed
+        let rollback = release(&local, "v1.0.0");
+        push_branch_with_retry(&local, "origin", "main", &options()).unwrap();
+        let head = run(&local, &["rev-parse", "HEAD"]);
+
+        let outcome = rollback.rollback().unwrap();
+        assert!(outcome.tag_deleted);
+        assert!(!outcome.commit_reset);
+        assert!(outcome.already_pushed);
+        assert_eq!(run(&local, &["rev-parse", "HEAD"]), head);
+
+        // Everything pushed: nothing is undone
+        run(&local, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+        push_tag_with_retry(&local, "origin", "v1.0.0", &options()).unwrap();
+        let outcome = rollback.rollback().unwrap();
+        assert!(!outcome.tag_deleted && !outcome.commit_reset);
+        assert_eq!(run(&local, &["tag", "--list"]), "v1.0.0");
+    }
+}
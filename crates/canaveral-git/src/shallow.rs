@@ -0,0 +1,153 @@
+//! Shallow clone detection
+//!
+//! CI systems often check out with `--depth 1`. Anything that walks history
+//! (commit counts, commit ranges, contributor lists) silently produces wrong
+//! answers in such a clone, so those operations check for the shallow
+//! boundary and fail with [`GitError::ShallowClone`] instead.
+//!
+//! Like pushes, [`GitRepo::unshallow`] shells out to the git CLI: libgit2
+//! drops the shallow marker on an unshallow fetch without downloading the
+//! missing history, and the CLI also picks up the runner's credential
+//! helpers.
+
+use std::collections::HashSet;
+use std::process::Command;
+
+use git2::Oid;
+use tracing::{info, instrument, warn};
+
+use crate::repository::{GitRepo, Result};
+use canaveral_core::error::GitError;
+
+impl GitRepo {
+    /// Whether this repository is a shallow clone
+    pub fn is_shallow(&self) -> bool {
+        self.repo.is_shallow()
+    }
+
+    /// Fail with [`GitError::ShallowClone`] if this is a shallow clone
+    pub fn require_full_history(&self, operation: &str) -> Result<()> {
+        if self.is_shallow() {
+            warn!(operation, "shallow clone detected");
+            return Err(GitError::ShallowClone(operation.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Fetch the missing history from `remote` (`git fetch --unshallow`)
+    ///
+    /// The repository handle is reopened afterwards so the new history is
+    /// visible.
+    #[instrument(skip(self))]
+    pub fn unshallow(&mut self, remote: &str) -> Result<()> {
+        if !self.is_shallow() {
+            return Ok(());
+        }
+
+        info!(remote, "fetching full history for shallow clone");
+        let output = Command::new("git")
+            .args(["fetch", "--unshallow", "--tags", remote])
+            .current_dir(self.path())
+            .output()
+            .map_err(|e| GitError::FetchFailed(e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(GitError::FetchFailed(format!(
+                "git fetch --unshallow {} failed: {}",
+                remote,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        self.repo = git2::Repository::open(self.repo.path())?;
+        Ok(())
+    }
+
+    /// Commits whose parents were cut off by a shallow fetch
+    pub(crate) fn shallow_boundary(&self) -> HashSet<Oid> {
+        if !self.is_shallow() {
+            return HashSet::new();
+        }
+        std::fs::read_to_string(self.repo.path().join("shallow"))
+            .unwrap_or_default()
+            .lines()
+            .filter_map(|line| Oid::from_str(line.trim()).ok())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+
+    /// Create a three-commit repo and a `--depth 1` clone of it
+    fn shallow_clone() -> (TempDir, GitRepo, GitRepo) {
+        let temp = TempDir::new().unwrap();
+        let origin = temp.path().join("origin");
+        std::fs::create_dir(&origin).unwrap();
+        git(&origin, &["init", "-q"]);
+        git(&origin, &["config", "user.name", "Test"]);
+        git(&origin, &["config", "user.email", "test@example.com"]);
+        for i in 0..3 {
+            std::fs::write(origin.join("file.txt"), i.to_string()).unwrap();
+            git(&origin, &["add", "-A"]);
+            git(&origin, &["commit", "-q", "-m", &format!("commit {}", i)]);
+        }
+
+        let url = format!("file://{}", origin.display());
+        git(
+            temp.path(),
+            &["clone", "-q", "--depth", "1", &url, "shallow"],
+        );
+
+        let full = GitRepo::open(&origin).unwrap();
+        let shallow = GitRepo::open(&temp.path().join("shallow")).unwrap();
+        (temp, full, shallow)
+    }
+
+    #[test]
+    fn test_commit_count_rejects_shallow_clone() {
+        let (_temp, full, shallow) = shallow_clone();
+
+        assert!(!full.is_shallow());
+        assert_eq!(full.commit_count().unwrap(), 3);
+
+        assert!(shallow.is_shallow());
+        assert_eq!(shallow.shallow_boundary().len(), 1);
+        assert!(matches!(
+            shallow.commit_count(),
+            Err(GitError::ShallowClone(_))
+        ));
+        assert!(matches!(
+            shallow.all_commits(),
+            Err(GitError::ShallowClone(_))
+        ));
+        // A bounded walk never needs the missing history
+        assert_eq!(shallow.recent_commits(1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_unshallow() {
+        let (_temp, _full, mut shallow) = shallow_clone();
+
+        shallow.unshallow("origin").unwrap();
+        assert!(!shallow.is_shallow());
+        assert_eq!(shallow.commit_count().unwrap(), 3);
+    }
+}
@@ -0,0 +1,194 @@
+//! Staging files and recording commits
+
+use std::path::Path;
+
+use tracing::{debug, info, instrument};
+
+use crate::repository::{GitRepo, Result};
+
+impl GitRepo {
+    /// Stage `paths` and commit them on top of HEAD
+    ///
+    /// Paths are relative to the repository root; a path that no longer
+    /// exists is staged as a deletion. The commit is authored with the
+    /// repository's `user.name` and `user.email`. Returns the new commit's
+    /// hash.
+    #[instrument(skip(self, paths, message), fields(paths = paths.len()))]
+    pub fn commit_paths<P: AsRef<Path>>(&self, paths: &[P], message: &str) -> Result<String> {
+        let mut index = self.repo.index()?;
+        for path in paths {
+            let path = path.as_ref();
+            if self.path().join(path).exists() {
+                index.add_path(path)?;
+            } else {
+                index.remove_path(path)?;
+            }
+            debug!(path = %path.display(), "staged");
+        }
+        index.write()?;
+
+        let tree = self.repo.find_tree(index.write_tree()?)?;
+        let signature = self.repo.signature()?;
+        let parent = self.head_commit().ok();
+        let parents: Vec<&git2::Commit<'_>> = parent.iter().collect();
+        let oid = self.repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )?;
+
+        info!(hash = %oid, "created commit");
+        Ok(oid.to_string())
+    }
+
+    /// Commit the working tree's `paths` on top of HEAD to `branch`,
+    /// without checking the branch out
+    ///
+    /// The branch is created or reset to the new commit; HEAD, the index
+    /// and the working tree are left alone. Paths are relative to the
+    /// repository root; a path that no longer exists is committed as a
+    /// deletion. Returns the new commit's hash.
+    #[instrument(skip(self, paths, message), fields(paths = paths.len()))]
+    pub fn commit_paths_to_branch<P: AsRef<Path>>(
+        &self,
+        branch: &str,
+        paths: &[P],
+        message: &str,
+    ) -> Result<String> {
+        let head = self.head_commit()?;
+        let mut index = git2::Index::new()?;
+        index.read_tree(&head.tree()?)?;
+        for path in paths {
+            let path = path.as_ref();
+            let Ok(content) = std::fs::read(self.path().join(path)) else {
+                index.remove_path(path)?;
+                continue;
+            };
+            let mode = index.get_path(path, 0).map_or(0o100644, |entry| entry.mode);
+            let entry = git2::IndexEntry {
+                ctime: git2::IndexTime::new(0, 0),
+                mtime: git2::IndexTime::new(0, 0),
+                dev: 0,
+                ino: 0,
+                mode,
+                uid: 0,
+                gid: 0,
+                file_size: content.len() as u32,
+                id: self.repo.blob(&content)?,
+                flags: 0,
+                flags_extended: 0,
+                path: path.to_string_lossy().replace('\\', "/").into_bytes(),
+            };
+            index.add(&entry)?;
+            debug!(path = %path.display(), "staged");
+        }
+
+        let tree = self.repo.find_tree(index.write_tree_to(&self.repo)?)?;
+        let signature = self.repo.signature()?;
+        let oid = self
+            .repo
+            .commit(None, &signature, &signature, message, &tree, &[&head])?;
+        self.repo
+            .reference(&format!("refs/heads/{}", branch), oid, true, message)?;
+
+        info!(hash = %oid, branch, "created commit on branch");
+        Ok(oid.to_string())
+    }
+
+    /// Point `branch` at `commit`, creating it if needed
+    pub fn set_branch(&self, branch: &str, commit: &str) -> Result<()> {
+        let oid = self.repo.revparse_single(commit)?.peel_to_commit()?.id();
+        self.repo
+            .reference(&format!("refs/heads/{}", branch), oid, true, "set branch")?;
+        Ok(())
+    }
+
+    /// Reset tracked files to HEAD and delete untracked ones
+    ///
+    /// Ignored files are kept.
+    pub fn discard_changes(&self) -> Result<()> {
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force().remove_untracked(true);
+        self.repo.checkout_head(Some(&mut checkout))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::Repository;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_commit_paths() {
+        let temp = TempDir::new().unwrap();
+        let git = Repository::init(temp.path()).unwrap();
+        let mut config = git.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let repo = GitRepo::open(temp.path()).unwrap();
+
+        std::fs::write(temp.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp.path().join("b.txt"), "b").unwrap();
+        repo.commit_paths(&["a.txt", "b.txt"], "initial").unwrap();
+        assert!(repo.is_clean().unwrap());
+
+        std::fs::write(temp.path().join("a.txt"), "changed").unwrap();
+        std::fs::remove_file(temp.path().join("b.txt")).unwrap();
+        std::fs::write(temp.path().join("untouched.txt"), "c").unwrap();
+        let hash = repo.commit_paths(&["a.txt", "b.txt"], "update").unwrap();
+
+        let head = repo.head_commit().unwrap();
+        assert_eq!(head.id().to_string(), hash);
+        assert_eq!(head.message(), Some("update"));
+        assert_eq!(head.parent_count(), 1);
+        assert_eq!(repo.files_at("HEAD").unwrap(), vec!["a.txt"]);
+        assert_eq!(repo.untracked_files().unwrap(), vec!["untouched.txt"]);
+    }
+
+    #[test]
+    fn test_commit_paths_to_branch() {
+        let temp = TempDir::new().unwrap();
+        let git = Repository::init(temp.path()).unwrap();
+        let mut config = git.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let repo = GitRepo::open(temp.path()).unwrap();
+        std::fs::write(temp.path().join("a.txt"), "a").unwrap();
+        std::fs::write(temp.path().join("b.txt"), "b").unwrap();
+        let base = repo.commit_paths(&["a.txt", "b.txt"], "initial").unwrap();
+
+        std::fs::write(temp.path().join("a.txt"), "changed").unwrap();
+        std::fs::write(temp.path().join("new.txt"), "new").unwrap();
+        std::fs::remove_file(temp.path().join("b.txt")).unwrap();
+        let hash = repo
+            .commit_paths_to_branch("release", &["a.txt", "b.txt", "new.txt"], "release")
+            .unwrap();
+
+        // HEAD stays put, the branch holds the changes
+        assert_eq!(repo.head_commit().unwrap().id().to_string(), base);
+        assert_eq!(repo.resolve_commit("release").unwrap(), Some(hash.clone()));
+        assert_eq!(
+            repo.file_at("release", "a.txt").unwrap().as_deref(),
+            Some("changed")
+        );
+        assert_eq!(
+            repo.changed_files(&base, &hash).unwrap(),
+            vec!["a.txt", "b.txt", "new.txt"]
+        );
+        assert!(!repo.same_tree(&base, &hash).unwrap());
+
+        repo.discard_changes().unwrap();
+        assert!(repo.is_clean().unwrap());
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("a.txt")).unwrap(),
+            "a"
+        );
+        assert!(!temp.path().join("new.txt").exists());
+        assert_eq!(repo.resolve_commit("missing").unwrap(), None);
+    }
+}
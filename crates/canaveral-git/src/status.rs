@@ -91,6 +91,62 @@ impl GitRepo {
         Ok(files)
     }
 
+    /// Uncommitted changes as short status lines (`M  src/lib.rs`,
+    /// `?? notes.txt`), index column first like `git status --short`
+    #[instrument(skip(self))]
+    pub fn status_summary(&self) -> Result<Vec<String>> {
+        let mut opts = git2::StatusOptions::new();
+        opts.include_untracked(true).recurse_untracked_dirs(true);
+
+        let statuses = self.repo.statuses(Some(&mut opts))?;
+        let mut lines = Vec::new();
+
+        for entry in statuses.iter() {
+            let status = entry.status();
+            let Some(path) = entry.path() else {
+                continue;
+            };
+            if status.is_ignored() {
+                continue;
+            }
+            if status.is_wt_new() {
+                lines.push(format!("?? {}", path));
+                continue;
+            }
+
+            let index = if status.is_index_new() {
+                'A'
+            } else if status.is_index_modified() {
+                'M'
+            } else if status.is_index_deleted() {
+                'D'
+            } else if status.is_index_renamed() {
+                'R'
+            } else if status.is_index_typechange() {
+                'T'
+            } else {
+                ' '
+            };
+            let worktree = if status.is_wt_modified() {
+                'M'
+            } else if status.is_wt_deleted() {
+                'D'
+            } else if status.is_wt_renamed() {
+                'R'
+            } else if status.is_wt_typechange() {
+                'T'
+            } else {
+                ' '
+            };
+            if index != ' ' || worktree != ' ' {
+                lines.push(format!("{}{} {}", index, worktree, path));
+            }
+        }
+
+        debug!(count = lines.len(), "summarized working tree changes");
+        Ok(lines)
+    }
+
     /// Get list of untracked files
     #[instrument(skip(self))]
     pub fn untracked_files(&self) -> Result<Vec<String>> {
@@ -170,4 +226,17 @@ mod tests {
         let modified = repo.modified_files().unwrap();
         assert!(modified.contains(&"file.txt".to_string()));
     }
+
+    #[test]
+    fn test_status_summary() {
+        let (temp, repo) = setup_repo();
+        assert!(repo.status_summary().unwrap().is_empty());
+
+        std::fs::write(temp.path().join("file.txt"), "modified").unwrap();
+        std::fs::write(temp.path().join("notes.txt"), "new").unwrap();
+
+        let mut summary = repo.status_summary().unwrap();
+        summary.sort();
+        assert_eq!(summary, vec![" M file.txt", "?? notes.txt"]);
+    }
 }
@@ -1,8 +1,7 @@
 //! Tag operations
 
-use chrono::{TimeZone, Utc};
+use chrono::{DateTime, TimeZone, Utc};
 use regex::Regex;
-use semver::Version;
 use tracing::{debug, info, instrument};
 
 use crate::repository::{GitRepo, Result};
@@ -22,7 +21,11 @@ impl GitRepo {
 
             // Try to get the commit this tag points to
             if let Ok(commit) = self.repo.find_commit(oid) {
-                tags.push(TagInfo::new(&name, commit.id().to_string()));
+                // Lightweight tag: date it by the commit it points to
+                tags.push(
+                    TagInfo::new(&name, commit.id().to_string())
+                        .with_timestamp(commit_time(&commit)),
+                );
             } else if let Ok(tag) = self.repo.find_tag(oid) {
                 // Annotated tag
                 let target_id = tag.target_id();
@@ -66,29 +69,63 @@ impl GitRepo {
         Ok(matching)
     }
 
-    /// Find the latest tag by semantic version
+    /// Find the latest tag by semantic version precedence
+    ///
+    /// Pre-release tags count, so `v1.3.0-rc.1` is later than `v1.2.0`, but
+    /// a release always outranks its own pre-releases.
     #[instrument(skip(self), fields(pattern))]
     pub fn find_latest_tag(&self, pattern: Option<&str>) -> Result<Option<TagInfo>> {
+        self.find_latest_tag_filtered(pattern, true)
+    }
+
+    /// Find the latest tag by semantic version precedence, optionally
+    /// ignoring pre-release tags
+    ///
+    /// Tags whose version isn't valid semver are ignored. Build metadata
+    /// doesn't affect precedence; ties go to the first tag name in sort order.
+    #[instrument(skip(self), fields(pattern, include_prereleases))]
+    pub fn find_latest_tag_filtered(
+        &self,
+        pattern: Option<&str>,
+        include_prereleases: bool,
+    ) -> Result<Option<TagInfo>> {
         let tags = match pattern {
             Some(p) => self.tags_matching(p)?,
             None => self.tags()?,
         };
 
-        // Filter to tags with valid versions and sort by version
-        let mut versioned_tags: Vec<_> = tags
+        let result = tags
             .into_iter()
-            .filter_map(|t| {
-                t.version
-                    .as_ref()
-                    .and_then(|v| Version::parse(v).ok())
-                    .map(|v| (t, v))
-            })
-            .collect();
+            .filter_map(|t| t.semver().map(|v| (t, v)))
+            .filter(|(_, v)| include_prereleases || v.pre.is_empty())
+            .max_by(|(a, va), (b, vb)| va.cmp_precedence(vb).then_with(|| b.name.cmp(&a.name)))
+            .map(|(t, _)| t);
+        debug!(latest = ?result.as_ref().map(|t| &t.name), "found latest tag");
+        Ok(result)
+    }
 
-        versioned_tags.sort_by(|a, b| b.1.cmp(&a.1));
+    /// Find the latest tag that precedes `name` by semantic version
+    ///
+    /// When `name` is a release, pre-releases are skipped, so the range of a
+    /// release covers all of its release candidates.
+    #[instrument(skip(self), fields(name, pattern))]
+    pub fn find_tag_before(&self, name: &str, pattern: Option<&str>) -> Result<Option<TagInfo>> {
+        let Some(target) = TagInfo::new(name, "").semver() else {
+            return Ok(None);
+        };
+        let tags = match pattern {
+            Some(p) => self.tags_matching(p)?,
+            None => self.tags()?,
+        };
 
-        let result = versioned_tags.into_iter().next().map(|(t, _)| t);
-        debug!(latest = ?result.as_ref().map(|t| &t.name), "found latest tag");
+        let result = tags
+            .into_iter()
+            .filter_map(|t| t.semver().map(|v| (t, v)))
+            .filter(|(_, v)| !target.pre.is_empty() || v.pre.is_empty())
+            .filter(|(_, v)| v.cmp_precedence(&target).is_lt())
+            .max_by(|(a, va), (b, vb)| va.cmp_precedence(vb).then_with(|| b.name.cmp(&a.name)))
+            .map(|(t, _)| t);
+        debug!(previous = ?result.as_ref().map(|t| &t.name), "found preceding tag");
         Ok(result)
     }
 
@@ -99,7 +136,21 @@ impl GitRepo {
         match self.repo.find_reference(&tag_ref) {
             Ok(reference) => {
                 let target = reference.peel_to_commit()?;
-                Ok(Some(TagInfo::new(name, target.id().to_string())))
+                let mut tag_info = TagInfo::new(name, target.id().to_string())
+                    .with_timestamp(commit_time(&target));
+
+                // Prefer the tagger date for annotated tags
+                if let Ok(tag) = reference.peel_to_tag() {
+                    if let Some(tagger) = tag.tagger() {
+                        if let Some(timestamp) =
+                            Utc.timestamp_opt(tagger.when().seconds(), 0).single()
+                        {
+                            tag_info = tag_info.with_timestamp(timestamp);
+                        }
+                    }
+                }
+
+                Ok(Some(tag_info))
             }
             Err(e) if e.code() == git2::ErrorCode::NotFound => Ok(None),
             Err(e) => Err(GitError::Git2(e)),
@@ -126,7 +177,7 @@ impl GitRepo {
         }
 
         info!(name, annotated = message.is_some(), "created tag");
-        Ok(TagInfo::new(name, head.id().to_string()))
+        Ok(TagInfo::new(name, head.id().to_string()).with_timestamp(Utc::now()))
     }
 
     /// Delete a tag
@@ -138,6 +189,13 @@ impl GitRepo {
     }
 }
 
+/// Commit time of a commit as UTC
+fn commit_time(commit: &git2::Commit<'_>) -> DateTime<Utc> {
+    Utc.timestamp_opt(commit.time().seconds(), 0)
+        .single()
+        .unwrap_or_else(Utc::now)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -189,6 +247,18 @@ mod tests {
         assert_eq!(tag.unwrap().version, Some("1.0.0".to_string()));
     }
 
+    #[test]
+    fn test_lightweight_tag_uses_commit_date() {
+        let (_temp, repo) = setup_repo_with_tag();
+        let commit_date = commit_time(&repo.head_commit().unwrap());
+
+        let tag = repo.find_tag("v1.0.0").unwrap().unwrap();
+        assert_eq!(tag.timestamp, Some(commit_date));
+
+        let tags = repo.tags().unwrap();
+        assert_eq!(tags[0].timestamp, Some(commit_date));
+    }
+
     #[test]
     fn test_find_latest_tag() {
         let (_temp, repo) = setup_repo_with_tag();
@@ -196,6 +266,53 @@ mod tests {
         assert!(tag.is_some());
     }
 
+    #[test]
+    fn test_latest_tag_follows_semver_precedence() {
+        let (_temp, repo) = setup_repo_with_tag();
+        let head = repo.head_commit().unwrap();
+        let tag = |name: &str| {
+            repo.inner()
+                .tag_lightweight(name, head.as_object(), false)
+                .unwrap();
+        };
+        let latest = |include_prereleases: bool| {
+            repo.find_latest_tag_filtered(None, include_prereleases)
+                .unwrap()
+                .map(|t| t.name)
+        };
+
+        // String order would put the release candidates after v1.2.0
+        for name in [
+            "v1.2.0-rc.1",
+            "v1.2.0-rc.2",
+            "v1.10.0-beta.1",
+            "v1.9.0",
+            "nightly",
+        ] {
+            tag(name);
+        }
+        assert_eq!(latest(true).as_deref(), Some("v1.10.0-beta.1"));
+        assert_eq!(latest(false).as_deref(), Some("v1.9.0"));
+
+        tag("v1.10.0-rc.1");
+        assert_eq!(latest(true).as_deref(), Some("v1.10.0-rc.1"));
+        // The release outranks its pre-releases either way
+        tag("v1.10.0");
+        assert_eq!(latest(true).as_deref(), Some("v1.10.0"));
+        assert_eq!(latest(false).as_deref(), Some("v1.10.0"));
+        assert_eq!(
+            repo.find_latest_tag(None)
+                .unwrap()
+                .map(|t| t.name)
+                .as_deref(),
+            Some("v1.10.0")
+        );
+
+        // Build metadata doesn't make a tag later
+        tag("v1.10.0+build.5");
+        assert_eq!(latest(false).as_deref(), Some("v1.10.0"));
+    }
+
     #[test]
     fn test_create_tag() {
         let (_temp, repo) = setup_repo_with_tag();
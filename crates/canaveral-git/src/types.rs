@@ -18,7 +18,8 @@ pub struct CommitInfo {
     pub author: String,
     /// Author email
     pub author_email: String,
-    /// Commit timestamp
+    /// Commit timestamp (committer date, which stays in order through
+    /// rebases unlike the author date)
     pub timestamp: DateTime<Utc>,
 }
 
@@ -60,6 +61,58 @@ impl CommitInfo {
     }
 }
 
+/// Commit date window for selecting commits
+///
+/// `since` is inclusive and `until` exclusive, so consecutive windows never
+/// share a commit. Either bound may be open.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DateRange {
+    /// Earliest commit date to include
+    pub since: Option<DateTime<Utc>>,
+    /// Commit date to stop before
+    pub until: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    /// Commits from `since` onwards
+    pub fn since(since: DateTime<Utc>) -> Self {
+        Self {
+            since: Some(since),
+            until: None,
+        }
+    }
+
+    /// Commits before `until`
+    pub fn until(until: DateTime<Utc>) -> Self {
+        Self {
+            since: None,
+            until: Some(until),
+        }
+    }
+
+    /// Set the upper bound
+    pub fn with_until(mut self, until: DateTime<Utc>) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Whether neither bound is set
+    pub fn is_unbounded(&self) -> bool {
+        self.since.is_none() && self.until.is_none()
+    }
+
+    /// Whether a timestamp falls inside the window
+    pub fn contains(&self, timestamp: DateTime<Utc>) -> bool {
+        self.since.map_or(true, |since| timestamp >= since)
+            && self.until.map_or(true, |until| timestamp < until)
+    }
+
+    /// Whether a commit's date falls inside the window
+    pub fn includes(&self, commit: &CommitInfo) -> bool {
+        self.contains(commit.timestamp)
+    }
+}
+
 /// Information about a git tag
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TagInfo {
@@ -110,6 +163,13 @@ impl TagInfo {
         self.timestamp = Some(timestamp);
         self
     }
+
+    /// The tag's version parsed as semver, if it is one
+    pub fn semver(&self) -> Option<semver::Version> {
+        self.version
+            .as_deref()
+            .and_then(|v| semver::Version::parse(v).ok())
+    }
 }
 
 /// Extract version from a tag name
@@ -0,0 +1,274 @@
+//! Temporary worktrees for isolated releases
+//!
+//! A release writes version files, a changelog, a commit and a tag. Doing
+//! that in a separate `git worktree` leaves the user's checkout untouched
+//! (including any uncommitted changes) and guarantees the release is built
+//! from exactly the committed tree. The worktree shares refs with the main
+//! repository, so tags created in it are visible everywhere. It is removed
+//! again when the [`Worktree`] is dropped, whether or not the release
+//! succeeded.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use git2::{WorktreeAddOptions, WorktreePruneOptions};
+
+use tracing::{info, instrument, warn};
+
+use crate::push::{run_push, PushFailure, PushOptions};
+use crate::repository::{GitRepo, Result};
+use canaveral_core::error::GitError;
+
+/// Distinguishes worktrees created by the same process
+static WORKTREE_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// A temporary, detached worktree of a repository
+///
+/// Pruned, working tree included, on [`Worktree::remove`] or drop.
+pub struct Worktree {
+    repo: GitRepo,
+    name: String,
+    path: PathBuf,
+    main_dir: PathBuf,
+    removed: bool,
+}
+
+impl GitRepo {
+    /// Check out `reference` into a new temporary worktree
+    ///
+    /// The worktree has a detached HEAD, so the reference may be a branch
+    /// that is already checked out in the main tree.
+    #[instrument(skip(self))]
+    pub fn with_temp_worktree(&self, reference: &str) -> Result<Worktree> {
+        let commit = self
+            .repo
+            .revparse_single(reference)
+            .and_then(|object| object.peel_to_commit())
+            .map_err(worktree_error)?;
+        let name = format!(
+            "canaveral-worktree-{}-{}",
+            std::process::id(),
+            WORKTREE_COUNTER.fetch_add(1, Ordering::Relaxed)
+        );
+        let path = std::env::temp_dir().join(&name);
+
+        // libgit2 checks out a branch into new worktrees, so add one on a
+        // throwaway branch, detach it and delete the branch again
+        let mut branch = self
+            .repo
+            .branch(&name, &commit, false)
+            .map_err(worktree_error)?;
+        let added = self.repo.worktree(
+            &name,
+            &path,
+            Some(WorktreeAddOptions::new().reference(Some(branch.get()))),
+        );
+        let worktree = Worktree {
+            repo: match added
+                .map_err(worktree_error)
+                .and_then(|_| GitRepo::open(&path))
+            {
+                Ok(repo) => repo,
+                Err(e) => {
+                    let _ = branch.delete();
+                    let _ = prune(self.path(), &name, &path);
+                    return Err(e);
+                }
+            },
+            name,
+            path,
+            main_dir: self.path().to_path_buf(),
+            removed: false,
+        };
+        worktree
+            .repo
+            .repo
+            .set_head_detached(commit.id())
+            .and_then(|_| branch.delete())
+            .map_err(worktree_error)?;
+        info!(reference, path = %worktree.path.display(), "created release worktree");
+
+        Ok(worktree)
+    }
+}
+
+impl Worktree {
+    /// Root directory of the worktree
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Repository handle for the worktree
+    pub fn repo(&self) -> &GitRepo {
+        &self.repo
+    }
+
+    /// Push the worktree's HEAD to `branch` on `remote`
+    ///
+    /// The worktree is detached, so this pushes `HEAD:refs/heads/{branch}`.
+    /// Transient failures are retried; a non-fast-forward rejection yields
+    /// [`GitError::NonFastForward`] without rebasing.
+    #[instrument(skip(self, options))]
+    pub fn push_head(&self, remote: &str, branch: &str, options: &PushOptions) -> Result<()> {
+        let refspec = format!("HEAD:refs/heads/{}", branch);
+        let output = run_push(Some(&self.path), &[remote, &refspec], options)
+            .map_err(|e| GitError::PushFailed(e.to_string()))?;
+        if output.status.success() {
+            info!(remote, branch, "pushed worktree HEAD");
+            return Ok(());
+        }
+
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        match PushFailure::classify(&stderr) {
+            PushFailure::NonFastForward => Err(GitError::NonFastForward {
+                remote: remote.to_string(),
+                branch: branch.to_string(),
+            }),
+            _ => Err(GitError::PushFailed(format!(
+                "Failed to push to {}/{}: {}",
+                remote, branch, stderr
+            ))),
+        }
+    }
+
+    /// Remove the worktree, reporting failures
+    pub fn remove(mut self) -> Result<()> {
+        self.removed = true;
+        self.remove_inner()
+    }
+
+    fn remove_inner(&self) -> Result<()> {
+        prune(&self.main_dir, &self.name, &self.path)?;
+        info!(path = %self.path.display(), "removed release worktree");
+        Ok(())
+    }
+}
+
+impl Drop for Worktree {
+    fn drop(&mut self) {
+        if !self.removed {
+            if let Err(e) = self.remove_inner() {
+                warn!(path = %self.path.display(), error = %e, "failed to remove release worktree");
+            }
+        }
+    }
+}
+
+/// Delete the worktree `name` at `path` and its entry in the repository
+/// at `main_dir`
+fn prune(main_dir: &Path, name: &str, path: &Path) -> Result<()> {
+    let main = git2::Repository::open(main_dir).map_err(worktree_error)?;
+    if let Ok(worktree) = main.find_worktree(name) {
+        worktree
+            .prune(Some(
+                WorktreePruneOptions::new()
+                    .valid(true)
+                    .locked(true)
+                    .working_tree(true),
+            ))
+            .map_err(worktree_error)?;
+    }
+    if path.exists() {
+        std::fs::remove_dir_all(path).map_err(|e| GitError::WorktreeFailed(e.to_string()))?;
+    }
+    Ok(())
+}
+
+fn worktree_error(e: git2::Error) -> GitError {
+    GitError::WorktreeFailed(e.message().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "git {:?}: {}",
+            args,
+            String::from_utf8_lossy(&output.stderr)
+        );
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    fn setup_repo() -> (TempDir, GitRepo) {
+        let temp = TempDir::new().unwrap();
+        git(temp.path(), &["init", "-q", "-b", "main"]);
+        git(temp.path(), &["config", "user.name", "Test"]);
+        git(temp.path(), &["config", "user.email", "test@example.com"]);
+        std::fs::write(temp.path().join("VERSION"), "1.0.0\n").unwrap();
+        git(temp.path(), &["add", "-A"]);
+        git(temp.path(), &["commit", "-q", "-m", "initial"]);
+        let repo = GitRepo::open(temp.path()).unwrap();
+        (temp, repo)
+    }
+
+    #[test]
+    fn test_create_and_remove_worktree() {
+        let (temp, repo) = setup_repo();
+
+        let worktree = repo.with_temp_worktree("main").unwrap();
+        let path = worktree.path().to_path_buf();
+        assert!(path.join("VERSION").exists());
+        assert!(worktree.repo().is_head_detached().unwrap());
+        // The throwaway branch used to add the worktree is gone
+        assert_eq!(git(temp.path(), &["branch", "--list"]), "* main");
+        assert!(git(temp.path(), &["worktree", "list"]).contains(&*path.to_string_lossy()));
+
+        worktree.remove().unwrap();
+        assert!(!path.exists());
+        assert!(!git(temp.path(), &["worktree", "list"]).contains(&*path.to_string_lossy()));
+
+        // Dropping cleans up too
+        let path = repo
+            .with_temp_worktree("HEAD")
+            .unwrap()
+            .path()
+            .to_path_buf();
+        assert!(!path.exists());
+
+        assert!(matches!(
+            repo.with_temp_worktree("no-such-ref"),
+            Err(GitError::WorktreeFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_commit_in_worktree_leaves_main_tree_alone() {
+        let (temp, repo) = setup_repo();
+        // Uncommitted work in the main tree stays out of the release
+        std::fs::write(temp.path().join("notes.txt"), "wip").unwrap();
+        let main_head = git(temp.path(), &["rev-parse", "HEAD"]);
+
+        let worktree = repo.with_temp_worktree("HEAD").unwrap();
+        assert!(!worktree.path().join("notes.txt").exists());
+        std::fs::write(worktree.path().join("VERSION"), "1.1.0\n").unwrap();
+        git(
+            worktree.path(),
+            &["commit", "-q", "-am", "chore(release): 1.1.0"],
+        );
+        worktree.repo().create_tag("v1.1.0", None).unwrap();
+        let release_head = git(worktree.path(), &["rev-parse", "HEAD"]);
+        drop(worktree);
+
+        assert_eq!(git(temp.path(), &["rev-parse", "HEAD"]), main_head);
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("VERSION")).unwrap(),
+            "1.0.0\n"
+        );
+        assert!(temp.path().join("notes.txt").exists());
+        // The tag is shared with the main repository
+        assert_eq!(
+            git(temp.path(), &["rev-parse", "v1.1.0^{commit}"]),
+            release_head
+        );
+    }
+}
@@ -22,6 +22,11 @@
 //!
 //! - [`sync::AppleMetadataSync`]: Sync metadata with App Store Connect
 //!
+//! ## Testing (requires `test-util` feature)
+//!
+//! `InMemoryStorage` implements [`MetadataStorage`] on a `HashMap`, so code
+//! built on the trait can be tested without files on disk.
+//!
 //! ## Example
 //!
 //! ```no_run
@@ -62,7 +67,9 @@
 //! ## Sync Example (requires `sync` feature)
 //!
 //! ```no_run,ignore
-//! use canaveral_metadata::sync::{AppleMetadataSync, AppleSyncConfig, MetadataSync};
+//! use canaveral_metadata::sync::{
+//!     AppleMetadataSync, AppleSyncConfig, ConflictPolicy, MetadataSync,
+//! };
 //! use std::path::PathBuf;
 //!
 //! # async fn example() -> canaveral_metadata::Result<()> {
@@ -80,7 +87,9 @@
 //! println!("{}", diff);
 //!
 //! // Push local changes (dry run first)
-//! let result = sync.push("com.example.app", None, true).await?;
+//! let result = sync
+//!     .push("com.example.app", None, true, ConflictPolicy::Newer)
+//!     .await?;
 //! println!("Would update: {}", result);
 //! # Ok(())
 //! # }
@@ -96,8 +105,11 @@ pub mod utils;
 pub mod validation;
 
 pub use error::MetadataError;
+#[cfg(feature = "test-util")]
+pub use storage::InMemoryStorage;
 pub use storage::{
-    FastlaneStorage, MetadataStorage, MetadataStorageRegistry, StorageFormat, UnifiedStorage,
+    AssetFilter, FastlaneStorage, MetadataStorage, MetadataStorageRegistry, StorageFormat,
+    UnifiedStorage,
 };
 pub use templates::{
     apply_templates_to_apple_metadata, apply_templates_to_google_play_metadata,
@@ -107,7 +119,9 @@ pub use templates::{
 pub use types::apple::{
     AppleAgeRating, AppleCategory, AppleLocalizedMetadata, AppleMetadata, AppleScreenshotSet,
 };
-pub use types::common::{AssetType, Locale, MediaAsset, Platform};
+pub use types::common::{
+    AssetType, Locale, MediaAsset, OrderingIssue, Platform, DEFAULT_LOCALE_REGIONS,
+};
 pub use types::google_play::{
     GooglePlayCategory, GooglePlayContentRating, GooglePlayLocalizedMetadata, GooglePlayMetadata,
     GooglePlayScreenshotSet,
@@ -116,14 +130,15 @@ pub use utils::{
     auto_fix_text, count_chars, format_keywords, get_language_code, get_missing_apple_locales,
     get_missing_google_play_locales, get_region_code, is_apple_recommended_locale,
     is_google_play_recommended_locale, locales_equivalent, normalize_locale, parse_keywords,
-    sanitize_text, truncate_with_ellipsis, APPLE_RECOMMENDED_LOCALES,
-    GOOGLE_PLAY_RECOMMENDED_LOCALES,
+    sanitize_text, truncate_with_ellipsis, APPLE_RECOMMENDED_LOCALES, APPLE_SUPPORTED_LOCALES,
+    GOOGLE_PLAY_RECOMMENDED_LOCALES, GOOGLE_PLAY_SUPPORTED_LOCALES, LOCALE_ALIASES,
 };
 pub use validation::{
+    // Screenshot file validation
+    find_similar_screenshots,
     // Helper functions
     get_apple_valid_dimensions,
     get_google_play_valid_dimensions,
-    // Screenshot file validation
     read_image_dimensions,
     validate_apple_screenshot_file,
     validate_feature_graphic_file,
@@ -131,8 +146,11 @@ pub use validation::{
     validate_localized_google_play_screenshots,
     validate_localized_screenshots,
     validate_screenshot_directory,
+    validate_screenshot_duplicates,
+    validate_screenshot_order,
     AppleValidator,
     GooglePlayValidator,
+    ImageHash,
     Severity,
     ValidationIssue,
     ValidationResult,
@@ -147,6 +165,8 @@ pub use validation::{
     APPLE_IPHONE_6_7_DIMS,
     APPLE_TV_DIMS,
     APPLE_WATCH_SERIES_9_DIMS,
+    // Screenshot similarity
+    DEFAULT_SIMILARITY_THRESHOLD,
     // Google Play dimension constants
     GOOGLE_PLAY_DEVICE_TYPES,
     GOOGLE_PLAY_FEATURE_GRAPHIC_DIMS,
@@ -0,0 +1,153 @@
+//! File selection for media asset scanning.
+//!
+//! Screenshot directories often contain files that must not be uploaded:
+//! `.DS_Store`, AppleDouble `._*` files, exported originals or thumbnails.
+//! [`AssetFilter`] decides which files become [`MediaAsset`](crate::MediaAsset)s.
+//! Globs are matched against the file name, ignoring case.
+
+use crate::AssetType;
+use glob::{MatchOptions, Pattern};
+use std::path::Path;
+use tracing::warn;
+
+/// File name globs included by default for image assets.
+pub const IMAGE_PATTERNS: &[&str] = &["*.png", "*.jpg", "*.jpeg"];
+
+/// File name globs included by default for video previews.
+pub const VIDEO_PATTERNS: &[&str] = &["*.mp4", "*.mov", "*.m4v"];
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: false,
+    require_literal_separator: false,
+    require_literal_leading_dot: false,
+};
+
+/// Include/exclude globs selecting the files that become assets.
+///
+/// A file is an asset if its name matches an include glob and no exclude
+/// glob. Hidden files (names starting with `.`) are skipped unless
+/// [`with_hidden`](Self::with_hidden) is set.
+#[derive(Debug, Clone)]
+pub struct AssetFilter {
+    asset_type: AssetType,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    include_hidden: bool,
+}
+
+impl AssetFilter {
+    /// Creates a filter for `asset_type`, including its usual file types.
+    pub fn new(asset_type: AssetType) -> Self {
+        let defaults = match asset_type {
+            AssetType::Preview => VIDEO_PATTERNS,
+            AssetType::Icon | AssetType::Screenshot | AssetType::FeatureGraphic => IMAGE_PATTERNS,
+        };
+        Self {
+            asset_type,
+            include: compile(defaults.iter().copied()),
+            exclude: Vec::new(),
+            include_hidden: false,
+        }
+    }
+
+    /// Replaces the include globs. Invalid globs are skipped with a warning.
+    pub fn with_includes<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.include = compile(patterns);
+        self
+    }
+
+    /// Adds exclude globs. Invalid globs are skipped with a warning.
+    pub fn with_excludes<I, S>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.exclude.extend(compile(patterns));
+        self
+    }
+
+    /// Sets whether hidden files can be assets.
+    pub fn with_hidden(mut self, include_hidden: bool) -> Self {
+        self.include_hidden = include_hidden;
+        self
+    }
+
+    /// The type given to assets this filter selects.
+    pub fn asset_type(&self) -> AssetType {
+        self.asset_type
+    }
+
+    /// Returns true if the file at `path` should become an asset.
+    pub fn matches(&self, path: &Path) -> bool {
+        let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+            return false;
+        };
+        if name.starts_with('.') && !self.include_hidden {
+            return false;
+        }
+        let matching =
+            |patterns: &[Pattern]| patterns.iter().any(|p| p.matches_with(name, MATCH_OPTIONS));
+        matching(&self.include) && !matching(&self.exclude)
+    }
+}
+
+impl Default for AssetFilter {
+    fn default() -> Self {
+        Self::new(AssetType::Screenshot)
+    }
+}
+
+fn compile<I, S>(patterns: I) -> Vec<Pattern>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    patterns
+        .into_iter()
+        .filter_map(|p| match Pattern::new(p.as_ref()) {
+            Ok(pattern) => Some(pattern),
+            Err(e) => {
+                warn!("Ignoring invalid asset pattern '{}': {}", p.as_ref(), e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_filter_selects_images() {
+        let filter = AssetFilter::default();
+        assert_eq!(filter.asset_type(), AssetType::Screenshot);
+        assert!(filter.matches(Path::new("shots/01_home.png")));
+        assert!(filter.matches(Path::new("shots/02.JPG")));
+        assert!(!filter.matches(Path::new("shots/.DS_Store")));
+        assert!(!filter.matches(Path::new("shots/._01_home.png")));
+        assert!(!filter.matches(Path::new("shots/notes.txt")));
+        assert!(filter
+            .with_hidden(true)
+            .matches(Path::new("shots/.draft.png")));
+    }
+
+    #[test]
+    fn test_includes_and_excludes() {
+        let filter = AssetFilter::new(AssetType::Screenshot)
+            .with_includes(["*.png", "[invalid"])
+            .with_excludes(["*_original.*", "*thumb*"]);
+        assert!(filter.matches(Path::new("01.png")));
+        assert!(!filter.matches(Path::new("01.jpg")));
+        assert!(!filter.matches(Path::new("01_ORIGINAL.png")));
+        assert!(!filter.matches(Path::new("01_thumb.png")));
+
+        let previews = AssetFilter::new(AssetType::Preview);
+        assert!(previews.matches(Path::new("preview.mov")));
+        assert!(!previews.matches(Path::new("preview.png")));
+    }
+}
@@ -21,7 +21,9 @@
 //! │       │   └── privacy_url.txt
 //! │       ├── screenshots/
 //! │       │   └── {locale}/
-//! │       │       └── *.png
+//! │       │       └── {device}/
+//! │       │           ├── 01.png
+//! │       │           └── 02_settings.png
 //! │       └── app_store_info.toml
 //! └── google_play/
 //!     └── {package_name}/
@@ -38,13 +40,17 @@
 //!         │       └── ...
 //!         └── store_info.toml
 //! ```
+//!
+//! Screenshots are shown in the order of their numeric filename prefix.
+//! Which files count as screenshots is decided by an [`AssetFilter`]; by
+//! default PNG and JPEG files, skipping hidden files such as `.DS_Store`.
 
-use super::MetadataStorage;
+use super::{AssetFilter, MetadataStorage};
+use crate::types::common::{display_order_from_path, sort_by_display_order};
 use crate::{
     AppleAgeRating, AppleCategory, AppleLocalizedMetadata, AppleMetadata, AppleScreenshotSet,
-    AssetType, GooglePlayCategory, GooglePlayContentRating, GooglePlayLocalizedMetadata,
-    GooglePlayMetadata, GooglePlayScreenshotSet, Locale, MediaAsset, MetadataError, Platform,
-    Result,
+    GooglePlayCategory, GooglePlayContentRating, GooglePlayLocalizedMetadata, GooglePlayMetadata,
+    GooglePlayScreenshotSet, Locale, MediaAsset, MetadataError, Platform, Result,
 };
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
@@ -61,6 +67,8 @@ use tracing::{debug, warn};
 pub struct FastlaneStorage {
     /// Base path for all metadata.
     base_path: PathBuf,
+    /// Selects the files in screenshot directories that become assets.
+    screenshot_filter: AssetFilter,
 }
 
 impl FastlaneStorage {
@@ -72,9 +80,17 @@ impl FastlaneStorage {
     pub fn new(base_path: impl Into<PathBuf>) -> Self {
         Self {
             base_path: base_path.into(),
+            screenshot_filter: AssetFilter::default(),
         }
     }
 
+    /// Sets the filter selecting which files in screenshot directories
+    /// become assets.
+    pub fn with_screenshot_filter(mut self, filter: AssetFilter) -> Self {
+        self.screenshot_filter = filter;
+        self
+    }
+
     /// Gets the base path for this storage backend.
     pub fn base_path(&self) -> &Path {
         &self.base_path
@@ -394,8 +410,11 @@ impl FastlaneStorage {
 
     /// Discover screenshots in a directory.
     ///
-    /// Scans the given directory for image files (PNG, JPG, JPEG)
-    /// and returns them as `MediaAsset` entries sorted by filename.
+    /// Scans the given directory for files matching the screenshot filter
+    /// (by default PNG, JPG and JPEG, skipping hidden files) and returns
+    /// them as `MediaAsset` entries in display order. The display order is
+    /// taken from a numeric filename prefix (`01.png`, `02_search.png`);
+    /// files without one come last, sorted by name.
     ///
     /// # Arguments
     ///
@@ -415,16 +434,15 @@ impl FastlaneStorage {
 
         while let Some(entry) = entries.next_entry().await? {
             let path = entry.path();
-            if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                let ext_lower = ext.to_lowercase();
-                if ext_lower == "png" || ext_lower == "jpg" || ext_lower == "jpeg" {
-                    screenshots.push(MediaAsset::new(path, AssetType::Screenshot));
-                }
+            if !entry.file_type().await?.is_file() || !self.screenshot_filter.matches(&path) {
+                continue;
             }
+            let mut asset = MediaAsset::new(path, self.screenshot_filter.asset_type());
+            asset.display_order = display_order_from_path(&asset.path);
+            screenshots.push(asset);
         }
 
-        // Sort by filename for consistent ordering
-        screenshots.sort_by(|a, b| a.path.cmp(&b.path));
+        sort_by_display_order(&mut screenshots);
 
         Ok(screenshots)
     }
@@ -962,6 +980,112 @@ impl MetadataStorage for FastlaneStorage {
 }
 
 impl FastlaneStorage {
+    /// Path of the text file backing a localized field.
+    ///
+    /// Field names are the ones used in metadata diffs (e.g. `whats_new`,
+    /// `short_description`). Returns `None` for fields not stored as a file.
+    pub fn field_path(
+        &self,
+        platform: Platform,
+        app_id: &str,
+        locale: &str,
+        field: &str,
+    ) -> Option<PathBuf> {
+        let (app_path, file) = match platform {
+            Platform::Apple => (
+                self.apple_path(app_id),
+                match field {
+                    "name" | "subtitle" | "description" | "keywords" | "promotional_text"
+                    | "support_url" | "marketing_url" => field,
+                    "whats_new" => "release_notes",
+                    "privacy_policy_url" => "privacy_url",
+                    _ => return None,
+                },
+            ),
+            Platform::GooglePlay => (
+                self.google_play_path(app_id),
+                match field {
+                    "title" | "short_description" | "full_description" => field,
+                    "video_url" => "video",
+                    _ => return None,
+                },
+            ),
+            _ => return None,
+        };
+        Some(app_path.join(locale).join(format!("{}.txt", file)))
+    }
+
+    /// Load Google Play screenshots for one locale.
+    ///
+    /// Reads `screenshots/{locale}/{device}/` directories. Device directories
+    /// that don't map to a [`GooglePlayScreenshotSet`] field are skipped.
+    pub async fn load_google_play_locale_screenshots(
+        &self,
+        package_name: &str,
+        locale: &Locale,
+    ) -> Result<GooglePlayScreenshotSet> {
+        let dir = self
+            .google_play_path(package_name)
+            .join("screenshots")
+            .join(locale.code());
+        let mut set = GooglePlayScreenshotSet::default();
+        for (device, assets) in self.discover_device_screenshots(&dir).await? {
+            match set.device_mut(&device) {
+                Some(slot) => *slot = assets,
+                None => debug!(
+                    "Skipping unsupported Google Play device directory '{}'",
+                    device
+                ),
+            }
+        }
+        Ok(set)
+    }
+
+    /// Load Google Play screenshots for each locale.
+    pub async fn load_google_play_localized_screenshots(
+        &self,
+        package_name: &str,
+    ) -> Result<HashMap<Locale, GooglePlayScreenshotSet>> {
+        let base = self.google_play_path(package_name).join("screenshots");
+        let mut screenshots = HashMap::new();
+        for locale in self.list_locale_directories(&base).await? {
+            let set = self
+                .load_google_play_locale_screenshots(package_name, &locale)
+                .await?;
+            screenshots.insert(locale, set);
+        }
+        Ok(screenshots)
+    }
+
+    /// Discover screenshots in each device subdirectory of a locale directory.
+    async fn discover_device_screenshots(
+        &self,
+        dir: &Path,
+    ) -> Result<Vec<(String, Vec<MediaAsset>)>> {
+        let mut devices = Vec::new();
+        let mut entries = match fs::read_dir(dir).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(devices),
+            Err(e) => return Err(MetadataError::Io(e)),
+        };
+
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|s| s.to_str()) {
+                if name.starts_with('.') {
+                    continue;
+                }
+                let screenshots = self.discover_screenshots(&path).await?;
+                devices.push((name.to_string(), screenshots));
+            }
+        }
+
+        Ok(devices)
+    }
+
     /// Recursively copy a directory.
     async fn copy_dir_recursive(&self, src: &Path, dst: &Path) -> Result<()> {
         fs::create_dir_all(dst).await?;
@@ -985,6 +1109,7 @@ impl FastlaneStorage {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::AssetType;
     use tempfile::TempDir;
 
     async fn setup_test_storage() -> (FastlaneStorage, TempDir) {
@@ -1159,6 +1284,70 @@ mod tests {
         assert!(locale_codes.contains(&"ja".to_string()));
     }
 
+    #[tokio::test]
+    async fn test_screenshots_load_in_display_order() {
+        let (storage, _temp) = setup_test_storage().await;
+        let phone = storage
+            .google_play_path("com.example.app")
+            .join("screenshots/en-US/phone");
+        std::fs::create_dir_all(&phone).unwrap();
+        for name in [
+            "10_settings.png",
+            "02.png",
+            "01_home.png",
+            "notes.txt",
+            "promo.png",
+        ] {
+            std::fs::write(phone.join(name), b"").unwrap();
+        }
+        std::fs::create_dir_all(phone.with_file_name("tablet")).unwrap();
+
+        let screenshots = storage
+            .load_google_play_localized_screenshots("com.example.app")
+            .await
+            .unwrap();
+        let set = &screenshots[&Locale::new("en-US").unwrap()];
+        let names: Vec<_> = set
+            .phone
+            .iter()
+            .map(|a| a.path.file_name().unwrap().to_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            ["01_home.png", "02.png", "10_settings.png", "promo.png"]
+        );
+        assert_eq!(set.phone[2].display_order, Some(10));
+        assert_eq!(set.phone[3].display_order, None);
+        assert_eq!(set.total_count(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_screenshots_skip_hidden_and_excluded_files() {
+        let (storage, temp) = setup_test_storage().await;
+        let dir = temp.path().join("shots");
+        std::fs::create_dir_all(dir.join("originals.png")).unwrap();
+        for name in [".DS_Store", "._01.png", "01.png", "02_thumb.png"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let names = |assets: Vec<MediaAsset>| -> Vec<String> {
+            assets
+                .iter()
+                .map(|a| a.path.file_name().unwrap().to_string_lossy().into_owned())
+                .collect()
+        };
+
+        let assets = storage.discover_screenshots(&dir).await.unwrap();
+        assert_eq!(assets[0].asset_type, AssetType::Screenshot);
+        assert_eq!(names(assets), ["01.png", "02_thumb.png"]);
+
+        let storage = storage.with_screenshot_filter(
+            AssetFilter::new(AssetType::Screenshot).with_excludes(["*_thumb.*"]),
+        );
+        let assets = storage.discover_screenshots(&dir).await.unwrap();
+        assert_eq!(names(assets), ["01.png"]);
+    }
+
     #[tokio::test]
     async fn test_load_missing_returns_not_found() {
         let (storage, _temp) = setup_test_storage().await;
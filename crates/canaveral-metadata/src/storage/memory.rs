@@ -0,0 +1,332 @@
+//! In-memory metadata storage for tests.
+//!
+//! [`InMemoryStorage`] keeps metadata in a `HashMap` instead of on disk, so
+//! sync and validation flows can be unit-tested without touching the
+//! filesystem. Clones share the same data.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use async_trait::async_trait;
+
+use super::MetadataStorage;
+use crate::types::apple::{AppleLocalizedMetadata, AppleMetadata};
+use crate::types::google_play::{GooglePlayLocalizedMetadata, GooglePlayMetadata};
+use crate::{Locale, MetadataError, Platform, Result};
+
+#[derive(Debug, Default)]
+struct Apps {
+    apple: HashMap<String, AppleMetadata>,
+    google_play: HashMap<String, GooglePlayMetadata>,
+}
+
+/// Metadata storage backed by a `HashMap`.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStorage {
+    apps: Arc<Mutex<Apps>>,
+}
+
+impl InMemoryStorage {
+    /// Creates an empty storage.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn apps(&self) -> MutexGuard<'_, Apps> {
+        self.apps.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn not_found(platform: &str, app_id: &str) -> MetadataError {
+    MetadataError::NotFound(format!("{} metadata not found for: {}", platform, app_id))
+}
+
+fn unsupported(platform: Platform) -> MetadataError {
+    MetadataError::InvalidFormat(format!(
+        "In-memory storage is not implemented for platform: {:?}",
+        platform
+    ))
+}
+
+/// Parses and sorts locale codes.
+fn sorted_locales<'a>(codes: impl Iterator<Item = &'a String>) -> Vec<Locale> {
+    let mut locales: Vec<Locale> = codes.filter_map(|c| Locale::new(c).ok()).collect();
+    locales.sort_by_key(|l| l.code());
+    locales
+}
+
+/// Adds `locale` to `localizations`, copying from `copy_from` if given.
+fn add_localization<T: Clone + Default>(
+    localizations: &mut HashMap<String, T>,
+    locale: &Locale,
+    copy_from: Option<&Locale>,
+) -> Result<()> {
+    if localizations.contains_key(&locale.code()) {
+        return Err(MetadataError::InvalidFormat(format!(
+            "Locale '{}' already exists",
+            locale.code()
+        )));
+    }
+    let localization = match copy_from {
+        Some(source) => localizations.get(&source.code()).cloned().ok_or_else(|| {
+            MetadataError::NotFound(format!("Source locale '{}' not found", source.code()))
+        })?,
+        None => T::default(),
+    };
+    localizations.insert(locale.code(), localization);
+    Ok(())
+}
+
+fn remove_localization<T>(localizations: &mut HashMap<String, T>, locale: &Locale) -> Result<()> {
+    localizations
+        .remove(&locale.code())
+        .map(|_| ())
+        .ok_or_else(|| MetadataError::NotFound(format!("Locale '{}' not found", locale.code())))
+}
+
+#[async_trait]
+impl MetadataStorage for InMemoryStorage {
+    async fn load_apple(&self, bundle_id: &str) -> Result<AppleMetadata> {
+        self.apps()
+            .apple
+            .get(bundle_id)
+            .cloned()
+            .ok_or_else(|| not_found("Apple", bundle_id))
+    }
+
+    async fn load_google_play(&self, package_name: &str) -> Result<GooglePlayMetadata> {
+        self.apps()
+            .google_play
+            .get(package_name)
+            .cloned()
+            .ok_or_else(|| not_found("Google Play", package_name))
+    }
+
+    async fn save_apple(&self, metadata: &AppleMetadata) -> Result<()> {
+        self.apps()
+            .apple
+            .insert(metadata.bundle_id.clone(), metadata.clone());
+        Ok(())
+    }
+
+    async fn save_google_play(&self, metadata: &GooglePlayMetadata) -> Result<()> {
+        self.apps()
+            .google_play
+            .insert(metadata.package_name.clone(), metadata.clone());
+        Ok(())
+    }
+
+    async fn exists_apple(&self, bundle_id: &str) -> Result<bool> {
+        Ok(self.apps().apple.contains_key(bundle_id))
+    }
+
+    async fn exists_google_play(&self, package_name: &str) -> Result<bool> {
+        Ok(self.apps().google_play.contains_key(package_name))
+    }
+
+    async fn list_locales_apple(&self, bundle_id: &str) -> Result<Vec<Locale>> {
+        Ok(self
+            .apps()
+            .apple
+            .get(bundle_id)
+            .map(|m| sorted_locales(m.localizations.keys()))
+            .unwrap_or_default())
+    }
+
+    async fn list_locales_google_play(&self, package_name: &str) -> Result<Vec<Locale>> {
+        Ok(self
+            .apps()
+            .google_play
+            .get(package_name)
+            .map(|m| sorted_locales(m.localizations.keys()))
+            .unwrap_or_default())
+    }
+
+    fn apple_path(&self, bundle_id: &str) -> PathBuf {
+        PathBuf::from("memory").join("apple").join(bundle_id)
+    }
+
+    fn google_play_path(&self, package_name: &str) -> PathBuf {
+        PathBuf::from("memory")
+            .join("google_play")
+            .join(package_name)
+    }
+
+    async fn init(&self, platform: Platform, app_id: &str, locales: &[Locale]) -> Result<()> {
+        let primary_locale = locales.first().cloned().unwrap_or_default();
+        let mut apps = self.apps();
+
+        match platform {
+            Platform::Apple => {
+                let mut metadata = AppleMetadata::new(app_id);
+                metadata.primary_locale = primary_locale;
+                for locale in locales {
+                    metadata.set_localization(locale.code(), AppleLocalizedMetadata::default());
+                }
+                apps.apple.insert(app_id.to_string(), metadata);
+            }
+            Platform::GooglePlay => {
+                let mut metadata = GooglePlayMetadata::new(app_id);
+                metadata.default_locale = primary_locale;
+                for locale in locales {
+                    metadata
+                        .set_localization(locale.code(), GooglePlayLocalizedMetadata::default());
+                }
+                apps.google_play.insert(app_id.to_string(), metadata);
+            }
+            Platform::Npm | Platform::Crates | Platform::PyPI => {
+                return Err(unsupported(platform));
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn add_locale(
+        &self,
+        platform: Platform,
+        app_id: &str,
+        locale: &Locale,
+        copy_from: Option<&Locale>,
+    ) -> Result<()> {
+        let mut apps = self.apps();
+
+        match platform {
+            Platform::Apple => {
+                let metadata = apps
+                    .apple
+                    .get_mut(app_id)
+                    .ok_or_else(|| not_found("Apple", app_id))?;
+                add_localization(&mut metadata.localizations, locale, copy_from)
+            }
+            Platform::GooglePlay => {
+                let metadata = apps
+                    .google_play
+                    .get_mut(app_id)
+                    .ok_or_else(|| not_found("Google Play", app_id))?;
+                add_localization(&mut metadata.localizations, locale, copy_from)
+            }
+            Platform::Npm | Platform::Crates | Platform::PyPI => Err(unsupported(platform)),
+        }
+    }
+
+    async fn remove_locale(&self, platform: Platform, app_id: &str, locale: &Locale) -> Result<()> {
+        let mut apps = self.apps();
+
+        match platform {
+            Platform::Apple => {
+                let metadata = apps
+                    .apple
+                    .get_mut(app_id)
+                    .ok_or_else(|| not_found("Apple", app_id))?;
+                remove_localization(&mut metadata.localizations, locale)
+            }
+            Platform::GooglePlay => {
+                let metadata = apps
+                    .google_play
+                    .get_mut(app_id)
+                    .ok_or_else(|| not_found("Google Play", app_id))?;
+                remove_localization(&mut metadata.localizations, locale)
+            }
+            Platform::Npm | Platform::Crates | Platform::PyPI => Err(unsupported(platform)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locale(code: &str) -> Locale {
+        Locale::new(code).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_apple_round_trip() {
+        let storage = InMemoryStorage::new();
+        let bundle_id = "com.example.app";
+
+        assert!(!storage.exists_apple(bundle_id).await.unwrap());
+        assert!(storage.load_apple(bundle_id).await.is_err());
+
+        storage
+            .init(
+                Platform::Apple,
+                bundle_id,
+                &[locale("en-US"), locale("de-DE")],
+            )
+            .await
+            .unwrap();
+        assert!(storage.exists_apple(bundle_id).await.unwrap());
+
+        let mut metadata = storage.load_apple(bundle_id).await.unwrap();
+        assert_eq!(metadata.primary_locale.code(), "en-US");
+        metadata.set_localization(
+            "en-US",
+            AppleLocalizedMetadata::new("Example", "An example app"),
+        );
+        storage.save_apple(&metadata).await.unwrap();
+
+        // Clones share the same data
+        let shared = storage.clone();
+        let loaded = shared.load_apple(bundle_id).await.unwrap();
+        assert_eq!(loaded.get_localization("en-US").unwrap().name, "Example");
+
+        shared
+            .add_locale(
+                Platform::Apple,
+                bundle_id,
+                &locale("fr-FR"),
+                Some(&locale("en-US")),
+            )
+            .await
+            .unwrap();
+        storage
+            .remove_locale(Platform::Apple, bundle_id, &locale("de-DE"))
+            .await
+            .unwrap();
+
+        let codes: Vec<String> = storage
+            .list_locales_apple(bundle_id)
+            .await
+            .unwrap()
+            .iter()
+            .map(Locale::code)
+            .collect();
+        assert_eq!(codes, ["en-US", "fr-FR"]);
+        let loaded = storage.load_apple(bundle_id).await.unwrap();
+        assert_eq!(loaded.get_localization("fr-FR").unwrap().name, "Example");
+    }
+
+    #[tokio::test]
+    async fn test_google_play_round_trip() {
+        let storage = InMemoryStorage::new();
+        let package = "com.example.app";
+
+        storage
+            .init(Platform::GooglePlay, package, &[locale("en-US")])
+            .await
+            .unwrap();
+
+        let mut metadata = storage.load_google_play(package).await.unwrap();
+        metadata.set_localization(
+            "en-US",
+            GooglePlayLocalizedMetadata::new("Example", "Short", "Full description"),
+        );
+        storage.save_google_play(&metadata).await.unwrap();
+
+        let loaded = storage.load_google_play(package).await.unwrap();
+        assert_eq!(loaded.get_localization("en-US").unwrap().title, "Example");
+
+        assert!(storage
+            .add_locale(Platform::GooglePlay, package, &locale("en-US"), None)
+            .await
+            .is_err());
+        assert!(storage
+            .remove_locale(Platform::GooglePlay, package, &locale("ja-JP"))
+            .await
+            .is_err());
+        assert!(storage.init(Platform::Npm, "left-pad", &[]).await.is_err());
+    }
+}
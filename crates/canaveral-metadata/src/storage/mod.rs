@@ -10,12 +10,21 @@
 //!
 //! - [`UnifiedStorage`]: A single YAML file per platform per app, providing a more
 //!   compact and manageable alternative to the Fastlane directory structure.
+//!
+//! - `InMemoryStorage` (with the `test-util` feature): keeps metadata in memory
+//!   for unit tests.
 
+mod asset_filter;
 mod fastlane;
+#[cfg(any(test, feature = "test-util"))]
+mod memory;
 mod registry;
 mod unified;
 
+pub use asset_filter::{AssetFilter, IMAGE_PATTERNS, VIDEO_PATTERNS};
 pub use fastlane::FastlaneStorage;
+#[cfg(any(test, feature = "test-util"))]
+pub use memory::InMemoryStorage;
 pub use registry::{MetadataStorageEntry, MetadataStorageRegistry};
 pub use unified::UnifiedStorage;
 
@@ -4,10 +4,13 @@
 //! syncing app metadata between local storage and the App Store.
 
 use super::common::{self, TokenCache};
-use super::{MetadataChange, MetadataDiff, MetadataSync, PushResult};
+use super::{
+    ConflictPolicy, ConflictResolver, MetadataChange, MetadataDiff, MetadataSync, PullSnapshot,
+    PushResult, Side,
+};
 use crate::{
-    AppleLocalizedMetadata, AppleMetadata, FastlaneStorage, Locale, MetadataError, MetadataStorage,
-    Result,
+    AppleLocalizedMetadata, AppleMetadata, AssetFilter, FastlaneStorage, Locale, MetadataError,
+    MetadataStorage, Platform, Result,
 };
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
@@ -115,6 +118,12 @@ impl AppleMetadataSync {
         })
     }
 
+    /// Sets the filter selecting which local screenshot files are pushed.
+    pub fn with_screenshot_filter(mut self, filter: AssetFilter) -> Self {
+        self.storage = self.storage.with_screenshot_filter(filter);
+        self
+    }
+
     /// Generate a JWT token for App Store Connect API authentication.
     fn generate_jwt(&self) -> Result<String> {
         // Check cache first
@@ -418,6 +427,35 @@ impl AppleMetadataSync {
         self.api_patch(&endpoint, body).await
     }
 
+    /// Record the pushable fields of a localization in a snapshot.
+    fn record_snapshot(snapshot: &mut PullSnapshot, locale: &str, loc: &AppleLocalizedMetadata) {
+        snapshot.record(locale, "name", Some(&loc.name));
+        snapshot.record(locale, "subtitle", loc.subtitle.as_deref());
+        snapshot.record(locale, "description", Some(&loc.description));
+        snapshot.record(locale, "keywords", loc.keywords.as_deref());
+        snapshot.record(locale, "whats_new", loc.whats_new.as_deref());
+        snapshot.record(locale, "promotional_text", loc.promotional_text.as_deref());
+        snapshot.record(locale, "support_url", loc.support_url.as_deref());
+        snapshot.record(locale, "marketing_url", loc.marketing_url.as_deref());
+    }
+
+    /// Resolve one field against its remote value, returning true if the
+    /// local value should be pushed.
+    fn keep_local(
+        &self,
+        resolver: &mut ConflictResolver,
+        app_id: &str,
+        locale: &str,
+        field: &str,
+        local: Option<&str>,
+        remote: Option<&str>,
+    ) -> bool {
+        let file = self
+            .storage
+            .field_path(Platform::Apple, app_id, locale, field);
+        resolver.resolve_file(locale, field, local, remote, file.as_deref()) == Side::Local
+    }
+
     /// Convert remote localizations to AppleLocalizedMetadata.
     fn convert_to_local_metadata(
         &self,
@@ -501,6 +539,17 @@ impl MetadataSync for AppleMetadataSync {
         // Save to local storage
         self.storage.save_apple(&metadata).await?;
 
+        // Remember what the remote looked like for conflict detection on push
+        let snapshot_path = PullSnapshot::path_in(&self.storage.apple_path(app_id));
+        let mut snapshot = PullSnapshot::load(&snapshot_path)
+            .await?
+            .unwrap_or_default();
+        snapshot.pulled_at = Utc::now();
+        for (locale_str, loc) in &metadata.localizations {
+            Self::record_snapshot(&mut snapshot, locale_str, loc);
+        }
+        snapshot.save(&snapshot_path).await?;
+
         info!(
             "Successfully pulled metadata for {} locales",
             metadata.localizations.len()
@@ -514,6 +563,7 @@ impl MetadataSync for AppleMetadataSync {
         app_id: &str,
         locales: Option<&[Locale]>,
         dry_run: bool,
+        policy: ConflictPolicy,
     ) -> Result<PushResult> {
         info!(
             "Pushing metadata for {} to App Store Connect{}",
@@ -522,6 +572,8 @@ impl MetadataSync for AppleMetadataSync {
         );
 
         let mut result = PushResult::default();
+        let snapshot_path = PullSnapshot::path_in(&self.storage.apple_path(app_id));
+        let mut resolver = ConflictResolver::new(policy, PullSnapshot::load(&snapshot_path).await?);
 
         // Load local metadata
         let local_metadata = self.storage.load_apple(app_id).await?;
@@ -557,7 +609,7 @@ impl MetadataSync for AppleMetadataSync {
                 }
             }
 
-            let update = LocalizationUpdate {
+            let mut update = LocalizationUpdate {
                 description: Some(local_loc.description.clone()),
                 keywords: local_loc.keywords.clone(),
                 whats_new: local_loc.whats_new.clone(),
@@ -567,6 +619,49 @@ impl MetadataSync for AppleMetadataSync {
             };
 
             if let Some(version_loc) = version_loc_map.get(locale_str) {
+                // Leave fields out of the update where the remote value wins
+                let remote = &version_loc.attributes;
+                let fields: [(&str, &mut Option<String>, Option<&str>); 6] = [
+                    (
+                        "description",
+                        &mut update.description,
+                        remote.description.as_deref(),
+                    ),
+                    ("keywords", &mut update.keywords, remote.keywords.as_deref()),
+                    (
+                        "whats_new",
+                        &mut update.whats_new,
+                        remote.whats_new.as_deref(),
+                    ),
+                    (
+                        "promotional_text",
+                        &mut update.promotional_text,
+                        remote.promotional_text.as_deref(),
+                    ),
+                    (
+                        "marketing_url",
+                        &mut update.marketing_url,
+                        remote.marketing_url.as_deref(),
+                    ),
+                    (
+                        "support_url",
+                        &mut update.support_url,
+                        remote.support_url.as_deref(),
+                    ),
+                ];
+                for (field, local, remote) in fields {
+                    if !self.keep_local(
+                        &mut resolver,
+                        app_id,
+                        locale_str,
+                        field,
+                        local.as_deref(),
+                        remote,
+                    ) {
+                        *local = None;
+                    }
+                }
+
                 // Update existing localization
                 if !dry_run {
                     self.update_version_localization(&version_loc.id, &update)
@@ -586,8 +681,24 @@ impl MetadataSync for AppleMetadataSync {
 
             // Update app info localization (name, subtitle) if it exists
             if let Some(app_info_loc) = app_info_loc_map.get(locale_str) {
-                let name_changed = app_info_loc.attributes.name.as_deref() != Some(&local_loc.name);
-                let subtitle_changed = app_info_loc.attributes.subtitle != local_loc.subtitle;
+                let remote = &app_info_loc.attributes;
+                // Resolve first so the snapshot tracks unchanged fields too
+                let name_changed = self.keep_local(
+                    &mut resolver,
+                    app_id,
+                    locale_str,
+                    "name",
+                    Some(&local_loc.name),
+                    remote.name.as_deref(),
+                ) && remote.name.as_deref() != Some(&local_loc.name);
+                let subtitle_changed = self.keep_local(
+                    &mut resolver,
+                    app_id,
+                    locale_str,
+                    "subtitle",
+                    local_loc.subtitle.as_deref(),
+                    remote.subtitle.as_deref(),
+                ) && remote.subtitle != local_loc.subtitle;
 
                 if name_changed || subtitle_changed {
                     if !dry_run {
@@ -619,6 +730,12 @@ impl MetadataSync for AppleMetadataSync {
             }
         }
 
+        let (snapshot, warnings) = resolver.finish();
+        result.warnings.extend(warnings);
+        if !dry_run {
+            snapshot.save(&snapshot_path).await?;
+        }
+
         info!(
             "{}Pushed metadata: {}",
             if dry_run { "[DRY RUN] Would have " } else { "" },
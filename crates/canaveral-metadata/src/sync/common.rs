@@ -3,9 +3,14 @@
 //! Contains helpers that are used by both Apple and Google Play sync
 //! implementations to avoid duplication.
 
-use crate::{Locale, Result};
+use crate::types::common::{self, OrderingIssue};
+use crate::{Locale, MediaAsset, MetadataError, Result};
 use chrono::{Duration, Utc};
-use std::sync::RwLock;
+use reqwest::{RequestBuilder, Response, StatusCode};
+use std::future::Future;
+use std::path::Path;
+use std::sync::{Arc, RwLock};
+use tokio::sync::Semaphore;
 use tracing::warn;
 
 /// Default retry delay in milliseconds.
@@ -14,6 +19,9 @@ pub const DEFAULT_RETRY_DELAY_MS: u64 = 1000;
 /// Default maximum number of retries for rate-limited requests.
 pub const DEFAULT_MAX_RETRIES: u32 = 3;
 
+/// Default number of screenshot uploads in flight at once.
+pub const DEFAULT_UPLOAD_CONCURRENCY: usize = 4;
+
 /// Compare two optional strings, returning true if they differ.
 ///
 /// Trims whitespace before comparing. Empty strings and None are
@@ -39,6 +47,93 @@ pub fn parse_locale(locale_str: &str) -> Result<Locale> {
     Locale::new(locale_str)
 }
 
+/// Returns screenshots in the order they are pushed.
+///
+/// Screenshots are sorted by display order; ones without an order go last,
+/// by path. Positions claimed twice or skipped make the order ambiguous and
+/// fail with a validation error naming `field`.
+pub fn ordered_screenshots<'a>(
+    field: &str,
+    assets: &'a [MediaAsset],
+) -> Result<Vec<&'a MediaAsset>> {
+    let problems: Vec<String> = common::check_display_order(assets)
+        .into_iter()
+        .filter(|issue| !matches!(issue, OrderingIssue::Unordered(_)))
+        .map(|issue| issue.to_string())
+        .collect();
+    if !problems.is_empty() {
+        return Err(MetadataError::ValidationFailed(format!(
+            "Ambiguous screenshot order for {}: {}",
+            field,
+            problems.join("; ")
+        )));
+    }
+
+    let mut ordered: Vec<_> = assets.iter().collect();
+    common::sort_by_display_order(&mut ordered);
+    Ok(ordered)
+}
+
+/// Content type for uploads whose type can't be determined.
+pub const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Content types the stores accept for screenshots and graphics.
+pub const IMAGE_CONTENT_TYPES: &[&str] = &["image/png", "image/jpeg"];
+
+/// Determine the content type of an upload.
+///
+/// The file's magic bytes take precedence; the extension is only consulted
+/// for formats without a signature. Unknown types are sent as
+/// [`OCTET_STREAM`].
+pub fn detect_content_type(path: &Path, data: &[u8]) -> &'static str {
+    if let Some(kind) = infer::get(data) {
+        return kind.mime_type();
+    }
+    match extension(path).as_deref() {
+        Some("txt") => "text/plain",
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("csv") => "text/csv",
+        _ => OCTET_STREAM,
+    }
+}
+
+/// Lowercased extension of `path`.
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(str::to_lowercase)
+}
+
+/// Determine the content type of an image upload for `field`.
+///
+/// Fails unless the data is a PNG or JPEG, whatever the file is called, so
+/// a misnamed or corrupt file is caught before anything is uploaded.
+pub fn image_content_type(field: &str, path: &Path, data: &[u8]) -> Result<&'static str> {
+    let content_type = detect_content_type(path, data);
+    if !IMAGE_CONTENT_TYPES.contains(&content_type) {
+        return Err(MetadataError::ValidationFailed(format!(
+            "{} for {} is {}, expected a PNG or JPEG image",
+            path.display(),
+            field,
+            content_type
+        )));
+    }
+    let expected = match extension(path).as_deref() {
+        Some("png") => Some("image/png"),
+        Some("jpg" | "jpeg") => Some("image/jpeg"),
+        _ => None,
+    };
+    if expected.is_some_and(|expected| expected != content_type) {
+        warn!(
+            "{} has the wrong extension, uploading it as {}",
+            path.display(),
+            content_type
+        );
+    }
+    Ok(content_type)
+}
+
 /// Generic token cache with expiry tracking.
 ///
 /// Used by both Apple (JWT) and Google Play (OAuth2 access token) sync
@@ -109,10 +204,133 @@ pub fn log_rate_limit_warning(retry_after: u64, attempt: u32, max_retries: u32)
     );
 }
 
+/// Send a request, retrying while the server answers 429 Too Many Requests.
+///
+/// Waits for the `Retry-After` delay between attempts, giving up with
+/// [`MetadataError::RateLimited`] after [`DEFAULT_MAX_RETRIES`] retries.
+/// The request body must be in memory so it can be sent again.
+pub async fn send_with_retry(request: RequestBuilder) -> Result<Response> {
+    let mut retries = 0;
+
+    loop {
+        let attempt = request.try_clone().ok_or_else(|| {
+            MetadataError::SyncError("Request with a streaming body cannot be retried".to_string())
+        })?;
+        let response = attempt
+            .send()
+            .await
+            .map_err(|e| MetadataError::SyncError(format!("Request failed: {}", e)))?;
+
+        if response.status() != StatusCode::TOO_MANY_REQUESTS {
+            return Ok(response);
+        }
+        if retries >= DEFAULT_MAX_RETRIES {
+            return Err(MetadataError::RateLimited("Too many requests".to_string()));
+        }
+
+        let retry_after = parse_retry_after(response.headers(), DEFAULT_RETRY_DELAY_MS / 1000);
+        log_rate_limit_warning(retry_after, retries, DEFAULT_MAX_RETRIES);
+        tokio::time::sleep(std::time::Duration::from_secs(retry_after)).await;
+        retries += 1;
+    }
+}
+
+/// Outcome of [`run_uploads`].
+#[derive(Debug, Default)]
+pub struct UploadSummary {
+    /// Number of items uploaded across all jobs.
+    pub uploaded: usize,
+    /// Errors that stopped a job, one per failed job.
+    pub failures: Vec<String>,
+}
+
+/// Run upload jobs with at most `concurrency` of them in flight.
+///
+/// Each job resolves to the number of items it uploaded and the error that
+/// stopped it, if any, so a failing job still reports what it uploaded
+/// before the failure and doesn't affect the other jobs.
+pub async fn run_uploads<F>(jobs: Vec<F>, concurrency: usize) -> UploadSummary
+where
+    F: Future<Output = (usize, Option<MetadataError>)> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+    let handles: Vec<_> = jobs
+        .into_iter()
+        .map(|job| {
+            let semaphore = semaphore.clone();
+            tokio::spawn(async move {
+                let _permit = semaphore.acquire_owned().await.unwrap();
+                job.await
+            })
+        })
+        .collect();
+
+    let mut summary = UploadSummary::default();
+    for handle in handles {
+        match handle.await {
+            Ok((uploaded, error)) => {
+                summary.uploaded += uploaded;
+                summary.failures.extend(error.map(|e| e.to_string()));
+            }
+            Err(e) => summary.failures.push(format!("Upload task failed: {}", e)),
+        }
+    }
+    summary
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    const PNG_HEADER: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR";
+    const JPEG_HEADER: &[u8] = b"\xff\xd8\xff\xe0\0\x10JFIF\0";
+
+    #[test]
+    fn test_detect_content_type() {
+        assert_eq!(
+            detect_content_type(Path::new("01.png"), PNG_HEADER),
+            "image/png"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("01.jpg"), JPEG_HEADER),
+            "image/jpeg"
+        );
+        // Magic bytes win over the extension
+        assert_eq!(
+            detect_content_type(Path::new("01.png"), JPEG_HEADER),
+            "image/jpeg"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("notes.txt"), b"hello"),
+            "text/plain"
+        );
+        assert_eq!(
+            detect_content_type(Path::new("blob"), b"\0\x01"),
+            OCTET_STREAM
+        );
+    }
+
+    #[test]
+    fn test_image_content_type_rejects_mismatch() {
+        assert_eq!(
+            image_content_type("en-US/phone", Path::new("01.PNG"), PNG_HEADER).unwrap(),
+            "image/png"
+        );
+        assert_eq!(
+            image_content_type("en-US/phone", Path::new("01.png"), JPEG_HEADER).unwrap(),
+            "image/jpeg"
+        );
+
+        let err = image_content_type("en-US/phone", Path::new("01.png"), b"not an image")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("en-US/phone"));
+        assert!(err.contains("expected a PNG or JPEG"));
+
+        let gif = b"GIF89a\x01\0\x01\0";
+        assert!(image_content_type("en-US/phone", Path::new("01.gif"), gif).is_err());
+    }
+
     #[test]
     fn test_strings_differ_both_none() {
         assert!(!strings_differ(None, None));
@@ -190,6 +408,42 @@ mod tests {
         assert!(cache.get(Duration::minutes(5)).is_none());
     }
 
+    fn screenshot(name: &str) -> MediaAsset {
+        MediaAsset::new(name.into(), crate::AssetType::Screenshot)
+    }
+
+    #[test]
+    fn test_ordered_screenshots_follow_display_order() {
+        let assets = [
+            screenshot("03_share.png"),
+            screenshot("promo.png"),
+            screenshot("01_home.png"),
+            screenshot("search.png").with_display_order(2),
+        ];
+        let ordered = ordered_screenshots("en-US/phone", &assets).unwrap();
+        let names: Vec<_> = ordered.iter().map(|a| a.path.to_str().unwrap()).collect();
+        assert_eq!(
+            names,
+            ["01_home.png", "search.png", "03_share.png", "promo.png"]
+        );
+    }
+
+    #[test]
+    fn test_ordered_screenshots_reject_gaps_and_duplicates() {
+        let gap = [screenshot("01.png"), screenshot("03.png")];
+        let err = ordered_screenshots("en-US/phone", &gap).unwrap_err();
+        assert!(matches!(err, MetadataError::ValidationFailed(_)));
+        assert!(err
+            .to_string()
+            .contains("en-US/phone: position 2 is missing"));
+
+        let duplicate = [screenshot("01_a.png"), screenshot("01_b.png")];
+        let err = ordered_screenshots("en-US/tv", &duplicate).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("position 1 is claimed by 01_a.png, 01_b.png"));
+    }
+
     #[test]
     fn test_parse_locale_valid() {
         assert!(parse_locale("en-US").is_ok());
@@ -0,0 +1,348 @@
+//! Conflict resolution for metadata pushes.
+//!
+//! Every pull records the remote field values in a snapshot next to the
+//! local metadata (`.last_pull.json`). On push, a field is in conflict when
+//! the local value differs from the remote one *and* the remote value no
+//! longer matches the snapshot, i.e. someone edited it in the store console
+//! since the last pull. A [`ConflictPolicy`] decides which side wins.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::fs;
+use tracing::debug;
+
+use super::common;
+use crate::{MetadataError, Result};
+
+/// File name of the last-pull snapshot inside an app's metadata directory.
+pub const SNAPSHOT_FILE: &str = ".last_pull.json";
+
+/// How to resolve a field changed both locally and remotely.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConflictPolicy {
+    /// Overwrite the remote value with the local one.
+    #[default]
+    Local,
+    /// Keep the remote value.
+    Remote,
+    /// Keep whichever side was modified last.
+    ///
+    /// The local side is dated by the field file's modification time. Stores
+    /// don't report per-field edit times, so the remote side is dated to the
+    /// last pull: a local edit made after the pull wins.
+    Newer,
+}
+
+impl fmt::Display for ConflictPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConflictPolicy::Local => write!(f, "local"),
+            ConflictPolicy::Remote => write!(f, "remote"),
+            ConflictPolicy::Newer => write!(f, "newer"),
+        }
+    }
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = MetadataError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "local" => Ok(ConflictPolicy::Local),
+            "remote" => Ok(ConflictPolicy::Remote),
+            "newer" => Ok(ConflictPolicy::Newer),
+            _ => Err(MetadataError::InvalidFormat(format!(
+                "Unknown conflict policy '{}' (expected local, remote or newer)",
+                s
+            ))),
+        }
+    }
+}
+
+/// Which side of a field is pushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// Push the local value.
+    Local,
+    /// Leave the remote value in place.
+    Remote,
+}
+
+/// Remote field values as of the last pull (or push).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullSnapshot {
+    /// When the snapshot was taken.
+    pub pulled_at: DateTime<Utc>,
+    /// Field values by locale, then field name.
+    pub fields: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+impl PullSnapshot {
+    /// Creates an empty snapshot taken now.
+    pub fn new() -> Self {
+        Self {
+            pulled_at: Utc::now(),
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Records a field value. Missing values are recorded as empty.
+    pub fn record(&mut self, locale: &str, field: &str, value: Option<&str>) {
+        self.fields
+            .entry(locale.to_string())
+            .or_default()
+            .insert(field.to_string(), value.unwrap_or_default().to_string());
+    }
+
+    /// Gets a recorded field value.
+    pub fn get(&self, locale: &str, field: &str) -> Option<&str> {
+        self.fields.get(locale)?.get(field).map(String::as_str)
+    }
+
+    /// Path of the snapshot for an app's metadata directory.
+    pub fn path_in(app_path: &Path) -> PathBuf {
+        app_path.join(SNAPSHOT_FILE)
+    }
+
+    /// Loads a snapshot, returning `None` if the app was never pulled.
+    pub async fn load(path: &Path) -> Result<Option<Self>> {
+        match fs::read_to_string(path).await {
+            Ok(content) => Ok(Some(serde_json::from_str(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(MetadataError::Io(e)),
+        }
+    }
+
+    /// Saves the snapshot, creating parent directories as needed.
+    pub async fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).await?;
+        }
+        fs::write(path, serde_json::to_string_pretty(self)?).await?;
+        Ok(())
+    }
+}
+
+impl Default for PullSnapshot {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Applies a [`ConflictPolicy`] field by field during a push.
+///
+/// Also tracks the values the remote ends up with, so the snapshot can be
+/// refreshed after a successful push.
+#[derive(Debug)]
+pub struct ConflictResolver {
+    policy: ConflictPolicy,
+    base: Option<PullSnapshot>,
+    synced: PullSnapshot,
+    warnings: Vec<String>,
+}
+
+impl ConflictResolver {
+    /// Creates a resolver against the last-pull snapshot, if any.
+    ///
+    /// Without a snapshot no conflicts can be detected and local values win.
+    pub fn new(policy: ConflictPolicy, base: Option<PullSnapshot>) -> Self {
+        let mut synced = base.clone().unwrap_or_default();
+        synced.pulled_at = Utc::now();
+        Self {
+            policy,
+            base,
+            synced,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Decides which value to push for one field.
+    ///
+    /// `local_modified` is the modification time of the local field, used by
+    /// [`ConflictPolicy::Newer`].
+    pub fn resolve(
+        &mut self,
+        locale: &str,
+        field: &str,
+        local: Option<&str>,
+        remote: Option<&str>,
+        local_modified: Option<DateTime<Utc>>,
+    ) -> Side {
+        let base = self.base.as_ref().and_then(|b| b.get(locale, field));
+        let remote_changed = base.is_some_and(|base| common::strings_differ(Some(base), remote));
+
+        let side = if !common::strings_differ(local, remote) || !remote_changed {
+            Side::Local
+        } else {
+            let side = match self.policy {
+                ConflictPolicy::Local => Side::Local,
+                ConflictPolicy::Remote => Side::Remote,
+                ConflictPolicy::Newer => {
+                    let pulled_at = self.base.as_ref().map(|b| b.pulled_at);
+                    match (local_modified, pulled_at) {
+                        (Some(local_at), Some(pulled_at)) if local_at > pulled_at => Side::Local,
+                        _ => Side::Remote,
+                    }
+                }
+            };
+            debug!(locale, field, policy = %self.policy, ?side, "resolved metadata conflict");
+            self.warnings.push(format!(
+                "{}/{}: remote value changed since last pull; kept {} value ({} policy)",
+                locale,
+                field,
+                match side {
+                    Side::Local => "local",
+                    Side::Remote => "remote",
+                },
+                self.policy
+            ));
+            side
+        };
+
+        let value = match side {
+            Side::Local => local,
+            Side::Remote => remote,
+        };
+        self.synced.record(locale, field, value);
+        side
+    }
+
+    /// Like [`resolve`](Self::resolve), dating the local side by the
+    /// modification time of its field file.
+    pub fn resolve_file(
+        &mut self,
+        locale: &str,
+        field: &str,
+        local: Option<&str>,
+        remote: Option<&str>,
+        file: Option<&Path>,
+    ) -> Side {
+        self.resolve(locale, field, local, remote, modified_at(file))
+    }
+
+    /// Returns the snapshot of the remote after the push, and the warnings
+    /// for resolved conflicts.
+    pub fn finish(self) -> (PullSnapshot, Vec<String>) {
+        (self.synced, self.warnings)
+    }
+}
+
+/// Modification time of a local field file.
+fn modified_at(path: Option<&Path>) -> Option<DateTime<Utc>> {
+    let modified = std::fs::metadata(path?).ok()?.modified().ok()?;
+    Some(modified.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    /// Snapshot where the remote description was "Original" at pull time
+    fn base() -> PullSnapshot {
+        let mut snapshot = PullSnapshot::new();
+        snapshot.pulled_at = Utc::now() - Duration::hours(1);
+        snapshot.record("en-US", "description", Some("Original"));
+        snapshot.record("en-US", "keywords", Some("photo,camera"));
+        snapshot
+    }
+
+    fn resolve_conflict(
+        policy: ConflictPolicy,
+        local_modified: DateTime<Utc>,
+    ) -> (Side, Vec<String>) {
+        let mut resolver = ConflictResolver::new(policy, Some(base()));
+        let side = resolver.resolve(
+            "en-US",
+            "description",
+            Some("Edited locally"),
+            Some("Edited in console"),
+            Some(local_modified),
+        );
+        let (snapshot, warnings) = resolver.finish();
+        let expected = match side {
+            Side::Local => "Edited locally",
+            Side::Remote => "Edited in console",
+        };
+        assert_eq!(snapshot.get("en-US", "description"), Some(expected));
+        (side, warnings)
+    }
+
+    #[test]
+    fn test_local_policy_overwrites_remote() {
+        let (side, warnings) = resolve_conflict(ConflictPolicy::Local, Utc::now());
+        assert_eq!(side, Side::Local);
+        assert_eq!(
+            warnings,
+            vec!["en-US/description: remote value changed since last pull; kept local value (local policy)"]
+        );
+    }
+
+    #[test]
+    fn test_remote_policy_keeps_remote() {
+        let (side, warnings) = resolve_conflict(ConflictPolicy::Remote, Utc::now());
+        assert_eq!(side, Side::Remote);
+        assert!(warnings[0].contains("kept remote value (remote policy)"));
+    }
+
+    #[test]
+    fn test_newer_policy_compares_local_edit_with_pull() {
+        let (side, _) = resolve_conflict(ConflictPolicy::Newer, Utc::now());
+        assert_eq!(side, Side::Local);
+
+        // Local file untouched since before the pull
+        let (side, warnings) =
+            resolve_conflict(ConflictPolicy::Newer, Utc::now() - Duration::hours(2));
+        assert_eq!(side, Side::Remote);
+        assert!(warnings[0].contains("(newer policy)"));
+    }
+
+    #[test]
+    fn test_one_sided_changes_are_not_conflicts() {
+        let mut resolver = ConflictResolver::new(ConflictPolicy::Remote, Some(base()));
+
+        // Only the local side changed
+        let side = resolver.resolve(
+            "en-US",
+            "keywords",
+            Some("photo"),
+            Some("photo,camera"),
+            None,
+        );
+        assert_eq!(side, Side::Local);
+
+        // Never pulled: nothing to compare against
+        let side = resolver.resolve("de-DE", "description", Some("Neu"), Some("Alt"), None);
+        assert_eq!(side, Side::Local);
+
+        let (_, warnings) = resolver.finish();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn test_policy_from_str() {
+        assert_eq!(
+            "Newer".parse::<ConflictPolicy>().unwrap(),
+            ConflictPolicy::Newer
+        );
+        assert!("theirs".parse::<ConflictPolicy>().is_err());
+        assert_eq!(ConflictPolicy::default().to_string(), "local");
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_roundtrip() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = PullSnapshot::path_in(&temp.path().join("apple/com.example.app"));
+        assert!(PullSnapshot::load(&path).await.unwrap().is_none());
+
+        base().save(&path).await.unwrap();
+        let loaded = PullSnapshot::load(&path).await.unwrap().unwrap();
+        assert_eq!(loaded.get("en-US", "description"), Some("Original"));
+    }
+}
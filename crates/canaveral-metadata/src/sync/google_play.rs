@@ -16,10 +16,13 @@
 //! 3. Commit the edit to apply changes (or delete to discard)
 
 use super::common::{self, TokenCache};
-use super::{ChangeType, MetadataChange, MetadataDiff, MetadataSync, PushResult};
+use super::{
+    ChangeType, ConflictPolicy, ConflictResolver, MetadataChange, MetadataDiff, MetadataSync,
+    PullSnapshot, PushResult, Side,
+};
 use crate::{
-    FastlaneStorage, GooglePlayLocalizedMetadata, GooglePlayMetadata, Locale, MetadataError,
-    MetadataStorage, Result,
+    AssetFilter, FastlaneStorage, GooglePlayLocalizedMetadata, GooglePlayMetadata, Locale,
+    MetadataError, MetadataStorage, Platform, Result,
 };
 use async_trait::async_trait;
 use chrono::{Duration, Utc};
@@ -34,6 +37,9 @@ use tracing::{debug, info, warn};
 /// Base URL for Google Play Developer API v3.
 const API_BASE_URL: &str = "https://androidpublisher.googleapis.com/androidpublisher/v3";
 
+/// Base URL for Google Play Developer API v3 media uploads.
+const UPLOAD_BASE_URL: &str = "https://androidpublisher.googleapis.com/upload/androidpublisher/v3";
+
 /// OAuth 2.0 token endpoint for Google.
 const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 
@@ -69,6 +75,10 @@ pub struct GooglePlaySyncConfig {
     pub service_account_key_path: Option<PathBuf>,
     /// Or the JSON content directly.
     pub service_account_key_json: Option<String>,
+    /// Maximum number of screenshot sets uploaded at once.
+    pub upload_concurrency: usize,
+    /// Whether remote screenshots that differ from local ones are replaced.
+    pub replace_screenshots: bool,
 }
 
 impl GooglePlaySyncConfig {
@@ -77,6 +87,8 @@ impl GooglePlaySyncConfig {
         Self {
             service_account_key_path: Some(path.into()),
             service_account_key_json: None,
+            upload_concurrency: common::DEFAULT_UPLOAD_CONCURRENCY,
+            replace_screenshots: false,
         }
     }
 
@@ -85,9 +97,26 @@ impl GooglePlaySyncConfig {
         Self {
             service_account_key_path: None,
             service_account_key_json: Some(json.into()),
+            upload_concurrency: common::DEFAULT_UPLOAD_CONCURRENCY,
+            replace_screenshots: false,
         }
     }
 
+    /// Sets the maximum number of screenshot sets uploaded at once.
+    pub fn with_upload_concurrency(mut self, concurrency: usize) -> Self {
+        self.upload_concurrency = concurrency;
+        self
+    }
+
+    /// Replaces remote screenshots that differ from local ones.
+    ///
+    /// Without this, screenshots are only uploaded for device types that
+    /// have none on Google Play yet.
+    pub fn with_replace_screenshots(mut self, replace: bool) -> Self {
+        self.replace_screenshots = replace;
+        self
+    }
+
     /// Creates a new config from environment variables.
     ///
     /// Looks for:
@@ -187,6 +216,12 @@ impl GooglePlayMetadataSync {
         })
     }
 
+    /// Sets the filter selecting which local screenshot files are pushed.
+    pub fn with_screenshot_filter(mut self, filter: AssetFilter) -> Self {
+        self.storage = self.storage.with_screenshot_filter(filter);
+        self
+    }
+
     /// Authenticate using service account and obtain an access token.
     async fn authenticate(&self) -> Result<String> {
         // Check cache first (with 5 minute buffer before expiry)
@@ -543,37 +578,9 @@ impl GooglePlayMetadataSync {
         content_type: &str,
     ) -> Result<String> {
         let token = self.ensure_authenticated().await?;
+        let url = image_upload_url(package_name, edit_id, locale, image_type);
 
-        let url = format!(
-            "https://androidpublisher.googleapis.com/upload/androidpublisher/v3/applications/{}/edits/{}/listings/{}/{}",
-            package_name, edit_id, locale, image_type
-        );
-
-        debug!("Uploading image to: {}", url);
-
-        let response = self
-            .client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", token))
-            .header("Content-Type", content_type)
-            .body(data)
-            .send()
-            .await
-            .map_err(|e| MetadataError::SyncError(format!("Image upload failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(MetadataError::SyncError(format!(
-                "Image upload failed: {}",
-                error_text
-            )));
-        }
-
-        let upload_response: ImageUploadResponse = response.json().await.map_err(|e| {
-            MetadataError::SyncError(format!("Failed to parse upload response: {}", e))
-        })?;
-
-        Ok(upload_response.image.id)
+        post_image(&self.client, &url, &token, data, content_type).await
     }
 
     /// Delete an image.
@@ -609,10 +616,172 @@ impl GooglePlayMetadataSync {
         self.api_delete(&endpoint).await
     }
 
+    /// Prepare a locale's screenshots for upload in display order.
+    ///
+    /// For each device type with local screenshots, the remote images are
+    /// removed unless they already match (same content in the same order),
+    /// and the local ones are returned for [`Self::upload_screenshots`].
+    /// Differing remote images are only removed with
+    /// [`GooglePlaySyncConfig::replace_screenshots`]; otherwise the device
+    /// type is skipped with a warning. Device types without local
+    /// screenshots are left untouched.
+    async fn push_screenshots(
+        &self,
+        package_name: &str,
+        edit_id: &str,
+        locale_str: &str,
+        dry_run: bool,
+        result: &mut PushResult,
+    ) -> Result<Vec<ScreenshotSet>> {
+        let locale = common::parse_locale(locale_str)?;
+        let screenshots = self
+            .storage
+            .load_google_play_locale_screenshots(package_name, &locale)
+            .await?;
+
+        let mut sets = Vec::new();
+        for (device, assets) in screenshots.device_types() {
+            if assets.is_empty() {
+                continue;
+            }
+            let field = format!("{}/{}", locale_str, device);
+            let image_type = image_type_for_device(device);
+
+            let mut uploads = Vec::with_capacity(assets.len());
+            for asset in common::ordered_screenshots(&field, assets)? {
+                let data = tokio::fs::read(&asset.path).await?;
+                common::image_content_type(&field, &asset.path, &data)?;
+                uploads.push((asset, data));
+            }
+            let local_hashes: Vec<String> =
+                uploads.iter().map(|(_, data)| sha256_hex(data)).collect();
+
+            let remote = self
+                .list_images(package_name, edit_id, locale_str, image_type)
+                .await?;
+            if images_match(&local_hashes, &remote) {
+                debug!("Screenshots for {} are up to date", field);
+                continue;
+            }
+            if !remote.is_empty() && !self.config.replace_screenshots {
+                result.warnings.push(format!(
+                    "Screenshots for {} differ from Google Play; enable screenshot replacement to overwrite them",
+                    field
+                ));
+                continue;
+            }
+
+            result.screenshots_removed += remote.len();
+            if dry_run {
+                result.screenshots_uploaded += uploads.len();
+                continue;
+            }
+
+            if !remote.is_empty() {
+                self.delete_all_images(package_name, edit_id, locale_str, image_type)
+                    .await?;
+            }
+            sets.push(ScreenshotSet {
+                url: image_upload_url(package_name, edit_id, locale_str, image_type),
+                field,
+                images: uploads
+                    .into_iter()
+                    .map(|(asset, _)| asset.path.clone())
+                    .collect(),
+            });
+        }
+
+        Ok(sets)
+    }
+
+    /// Upload screenshot sets prepared by [`Self::push_screenshots`].
+    ///
+    /// Up to `upload_concurrency` sets are uploaded at once. If any set
+    /// fails the push is aborted with an error, so the edit is deleted
+    /// instead of committing a listing with missing screenshots.
+    async fn upload_screenshots(
+        &self,
+        sets: Vec<ScreenshotSet>,
+        result: &mut PushResult,
+    ) -> Result<()> {
+        if sets.is_empty() {
+            return Ok(());
+        }
+
+        let token = self.ensure_authenticated().await?;
+        let summary =
+            upload_screenshot_sets(&self.client, &token, sets, self.config.upload_concurrency)
+                .await;
+
+        result.screenshots_uploaded += summary.uploaded;
+        if !summary.failures.is_empty() {
+            return Err(MetadataError::SyncError(format!(
+                "Screenshot upload failed, discarding the edit: {}",
+                summary.failures.join("; ")
+            )));
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Helper methods
     // ========================================================================
 
+    /// Build the listing update for a locale, resolving each field against
+    /// the remote listing (if the locale exists remotely).
+    fn resolve_listing(
+        &self,
+        resolver: &mut ConflictResolver,
+        app_id: &str,
+        locale: &str,
+        local: &GooglePlayLocalizedMetadata,
+        remote: Option<&Listing>,
+    ) -> ListingUpdate {
+        let mut update = ListingUpdate {
+            language: locale.to_string(),
+            title: local.title.clone(),
+            full_description: local.full_description.clone(),
+            short_description: local.short_description.clone(),
+            video: local.video_url.clone(),
+        };
+        let Some(remote) = remote else {
+            return update;
+        };
+
+        let mut keep_local = |field: &str, local: Option<&str>, remote: Option<&str>| {
+            let file = self
+                .storage
+                .field_path(Platform::GooglePlay, app_id, locale, field);
+            resolver.resolve_file(locale, field, local, remote, file.as_deref()) == Side::Local
+        };
+
+        if !keep_local("title", Some(&update.title), remote.title.as_deref()) {
+            update.title = remote.title.clone().unwrap_or_default();
+        }
+        if !keep_local(
+            "short_description",
+            Some(&update.short_description),
+            remote.short_description.as_deref(),
+        ) {
+            update.short_description = remote.short_description.clone().unwrap_or_default();
+        }
+        if !keep_local(
+            "full_description",
+            Some(&update.full_description),
+            remote.full_description.as_deref(),
+        ) {
+            update.full_description = remote.full_description.clone().unwrap_or_default();
+        }
+        if !keep_local(
+            "video_url",
+            update.video.as_deref(),
+            remote.video.as_deref(),
+        ) {
+            update.video = remote.video.clone();
+        }
+        update
+    }
+
     /// Convert a Listing to GooglePlayLocalizedMetadata.
     fn listing_to_local_metadata(&self, listing: &Listing) -> GooglePlayLocalizedMetadata {
         GooglePlayLocalizedMetadata {
@@ -671,6 +840,24 @@ impl MetadataSync for GooglePlayMetadataSync {
             // Save to local storage
             self.storage.save_google_play(&metadata).await?;
 
+            // Remember what the remote looked like for conflict detection on push
+            let snapshot_path = PullSnapshot::path_in(&self.storage.google_play_path(app_id));
+            let mut snapshot = PullSnapshot::load(&snapshot_path)
+                .await?
+                .unwrap_or_default();
+            snapshot.pulled_at = Utc::now();
+            for (locale_str, loc) in &metadata.localizations {
+                snapshot.record(locale_str, "title", Some(&loc.title));
+                snapshot.record(
+                    locale_str,
+                    "short_description",
+                    Some(&loc.short_description),
+                );
+                snapshot.record(locale_str, "full_description", Some(&loc.full_description));
+                snapshot.record(locale_str, "video_url", loc.video_url.as_deref());
+            }
+            snapshot.save(&snapshot_path).await?;
+
             info!(
                 "Successfully pulled metadata for {} locales",
                 metadata.localizations.len()
@@ -693,6 +880,7 @@ impl MetadataSync for GooglePlayMetadataSync {
         app_id: &str,
         locales: Option<&[Locale]>,
         dry_run: bool,
+        policy: ConflictPolicy,
     ) -> Result<PushResult> {
         info!(
             "Pushing metadata for {} to Google Play Console{}",
@@ -701,6 +889,8 @@ impl MetadataSync for GooglePlayMetadataSync {
         );
 
         let mut result = PushResult::default();
+        let snapshot_path = PullSnapshot::path_in(&self.storage.google_play_path(app_id));
+        let mut resolver = ConflictResolver::new(policy, PullSnapshot::load(&snapshot_path).await?);
 
         // Load local metadata
         let local_metadata = self.storage.load_google_play(app_id).await?;
@@ -724,17 +914,24 @@ impl MetadataSync for GooglePlayMetadataSync {
                         }
                     }
 
-                    if let Some(remote) = remote_map.get(locale_str) {
+                    if let Some(remote) = remote_map.get(locale_str).copied() {
+                        let update = self.resolve_listing(
+                            &mut resolver,
+                            app_id,
+                            locale_str,
+                            local_loc,
+                            Some(remote),
+                        );
                         let title_differs = common::strings_differ_required(
-                            &local_loc.title,
+                            &update.title,
                             remote.title.as_deref().unwrap_or(""),
                         );
                         let short_desc_differs = common::strings_differ_required(
-                            &local_loc.short_description,
+                            &update.short_description,
                             remote.short_description.as_deref().unwrap_or(""),
                         );
                         let full_desc_differs = common::strings_differ_required(
-                            &local_loc.full_description,
+                            &update.full_description,
                             remote.full_description.as_deref().unwrap_or(""),
                         );
 
@@ -758,6 +955,9 @@ impl MetadataSync for GooglePlayMetadataSync {
                         // New locale
                         result.updated_locales.push(locale_str.clone());
                     }
+
+                    self.push_screenshots(app_id, &edit_id, locale_str, true, &mut result)
+                        .await?;
                 }
 
                 Ok::<(), MetadataError>(())
@@ -770,6 +970,7 @@ impl MetadataSync for GooglePlayMetadataSync {
             }
 
             compare_result?;
+            result.warnings.extend(resolver.finish().1);
 
             info!("[DRY RUN] Would have pushed: {}", result);
             return Ok(result);
@@ -787,6 +988,7 @@ impl MetadataSync for GooglePlayMetadataSync {
                 .collect();
 
             // Process each local localization
+            let mut screenshot_sets = Vec::new();
             for (locale_str, local_loc) in &local_metadata.localizations {
                 // Filter by requested locales if specified
                 if let Some(filter_locales) = locales {
@@ -796,27 +998,23 @@ impl MetadataSync for GooglePlayMetadataSync {
                     }
                 }
 
-                let listing_update = ListingUpdate {
-                    language: locale_str.clone(),
-                    title: local_loc.title.clone(),
-                    full_description: local_loc.full_description.clone(),
-                    short_description: local_loc.short_description.clone(),
-                    video: local_loc.video_url.clone(),
-                };
+                let remote = remote_map.get(locale_str).copied();
+                let listing_update =
+                    self.resolve_listing(&mut resolver, app_id, locale_str, local_loc, remote);
 
                 // Check if we need to update
-                let needs_update = if let Some(remote) = remote_map.get(locale_str) {
+                let needs_update = if let Some(remote) = remote {
                     common::strings_differ_required(
-                        &local_loc.title,
+                        &listing_update.title,
                         remote.title.as_deref().unwrap_or(""),
                     ) || common::strings_differ_required(
-                        &local_loc.short_description,
+                        &listing_update.short_description,
                         remote.short_description.as_deref().unwrap_or(""),
                     ) || common::strings_differ_required(
-                        &local_loc.full_description,
+                        &listing_update.full_description,
                         remote.full_description.as_deref().unwrap_or(""),
                     ) || common::strings_differ(
-                        local_loc.video_url.as_deref(),
+                        listing_update.video.as_deref(),
                         remote.video.as_deref(),
                     )
                 } else {
@@ -829,7 +1027,14 @@ impl MetadataSync for GooglePlayMetadataSync {
                     result.updated_locales.push(locale_str.clone());
                     debug!("Updated listing for {}", locale_str);
                 }
+
+                screenshot_sets.extend(
+                    self.push_screenshots(app_id, &edit_id, locale_str, false, &mut result)
+                        .await?,
+                );
             }
+            self.upload_screenshots(screenshot_sets, &mut result)
+                .await?;
 
             // Commit the edit
             self.commit_edit(app_id, &edit_id).await?;
@@ -847,6 +1052,10 @@ impl MetadataSync for GooglePlayMetadataSync {
 
         push_result?;
 
+        let (snapshot, warnings) = resolver.finish();
+        result.warnings.extend(warnings);
+        snapshot.save(&snapshot_path).await?;
+
         info!("Pushed metadata: {}", result);
 
         Ok(result)
@@ -1106,6 +1315,135 @@ struct ImageInfo {
     id: String,
 }
 
+/// A device type's screenshots for one locale, uploaded in display order.
+struct ScreenshotSet {
+    /// `<locale>/<device>`, used in messages.
+    field: String,
+    /// Upload endpoint for the set's image type.
+    url: String,
+    /// Screenshot files in display order.
+    images: Vec<PathBuf>,
+}
+
+/// Upload endpoint for images of `image_type` in a listing.
+fn image_upload_url(package_name: &str, edit_id: &str, locale: &str, image_type: &str) -> String {
+    format!(
+        "{}/applications/{}/edits/{}/listings/{}/{}",
+        UPLOAD_BASE_URL, package_name, edit_id, locale, image_type
+    )
+}
+
+/// Upload one image, retrying when rate limited, and return its ID.
+async fn post_image(
+    client: &Client,
+    url: &str,
+    token: &str,
+    data: Vec<u8>,
+    content_type: &str,
+) -> Result<String> {
+    debug!("Uploading image to: {}", url);
+
+    let request = client
+        .post(url)
+        .header("Authorization", format!("Bearer {}", token))
+        .header("Content-Type", content_type)
+        .body(data);
+    let response = common::send_with_retry(request).await?;
+
+    if !response.status().is_success() {
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(MetadataError::SyncError(format!(
+            "Image upload failed: {}",
+            error_text
+        )));
+    }
+
+    let upload_response: ImageUploadResponse = response
+        .json()
+        .await
+        .map_err(|e| MetadataError::SyncError(format!("Failed to parse upload response: {}", e)))?;
+
+    Ok(upload_response.image.id)
+}
+
+/// Upload a set's screenshots one at a time, keeping their display order.
+///
+/// Stops at the first failure. Returns the number of screenshots uploaded
+/// and the error that stopped the set, if any.
+async fn upload_screenshot_set(
+    client: Client,
+    token: String,
+    set: ScreenshotSet,
+) -> (usize, Option<MetadataError>) {
+    let mut uploaded = 0;
+    for path in &set.images {
+        let upload = async {
+            let data = tokio::fs::read(path).await?;
+            let content_type = common::image_content_type(&set.field, path, &data)?;
+            post_image(&client, &set.url, &token, data, content_type).await
+        };
+        if let Err(e) = upload.await {
+            let error = MetadataError::SyncError(format!(
+                "Failed to upload screenshots for {} ({} of {} uploaded): {}",
+                set.field,
+                uploaded,
+                set.images.len(),
+                e
+            ));
+            return (uploaded, Some(error));
+        }
+        uploaded += 1;
+    }
+
+    debug!("Uploaded {} screenshot(s) for {}", uploaded, set.field);
+    (uploaded, None)
+}
+
+/// Upload screenshot sets, at most `concurrency` sets at a time.
+///
+/// Sets are independent of each other, so they upload in parallel; the
+/// screenshots within a set are uploaded in order because Google Play
+/// displays them in upload order.
+async fn upload_screenshot_sets(
+    client: &Client,
+    token: &str,
+    sets: Vec<ScreenshotSet>,
+    concurrency: usize,
+) -> common::UploadSummary {
+    let jobs = sets
+        .into_iter()
+        .map(|set| upload_screenshot_set(client.clone(), token.to_string(), set))
+        .collect();
+    common::run_uploads(jobs, concurrency).await
+}
+
+/// Google Play image type for a [`GooglePlayScreenshotSet`](crate::GooglePlayScreenshotSet) device type.
+fn image_type_for_device(device: &str) -> &'static str {
+    match device {
+        "tablet_7" => image_types::SEVEN_INCH_SCREENSHOTS,
+        "tablet_10" => image_types::TEN_INCH_SCREENSHOTS,
+        "tv" => image_types::TV_SCREENSHOTS,
+        "wear" => image_types::WEAR_SCREENSHOTS,
+        _ => image_types::PHONE_SCREENSHOTS,
+    }
+}
+
+fn sha256_hex(data: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Whether remote images are the local files, in the same order.
+fn images_match(local_hashes: &[String], remote: &[Image]) -> bool {
+    local_hashes.len() == remote.len()
+        && local_hashes.iter().zip(remote).all(|(local, image)| {
+            image
+                .sha256
+                .as_deref()
+                .is_some_and(|remote| remote.eq_ignore_ascii_case(local))
+        })
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1113,6 +1451,7 @@ struct ImageInfo {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use canaveral_test_support::{MockResponse, MockServer};
 
     #[test]
     fn test_listing_update_serialization() {
@@ -1152,6 +1491,8 @@ mod tests {
         let config = GooglePlaySyncConfig::from_key_file("/path/to/key.json");
         assert!(config.service_account_key_path.is_some());
         assert!(config.service_account_key_json.is_none());
+        assert!(!config.replace_screenshots);
+        assert!(config.with_replace_screenshots(true).replace_screenshots);
     }
 
     #[test]
@@ -1182,6 +1523,44 @@ mod tests {
         assert!(listing.video.is_none());
     }
 
+    #[test]
+    fn test_images_match_requires_same_order() {
+        let image = |hash: &str| Image {
+            id: hash.to_string(),
+            url: None,
+            sha256: Some(hash.to_string()),
+        };
+        let local = vec![sha256_hex(b"home"), sha256_hex(b"search")];
+        let remote = vec![image(&local[0].to_uppercase()), image(&local[1])];
+        assert!(images_match(&local, &remote));
+
+        let reordered = vec![image(&local[1]), image(&local[0])];
+        assert!(!images_match(&local, &reordered));
+        assert!(!images_match(&local, &remote[..1]));
+        assert!(!images_match(
+            &local,
+            &[
+                image(&local[0]),
+                Image {
+                    sha256: None,
+                    ..image("x")
+                }
+            ]
+        ));
+    }
+
+    #[test]
+    fn test_image_type_for_device() {
+        assert_eq!(
+            image_type_for_device("phone"),
+            image_types::PHONE_SCREENSHOTS
+        );
+        assert_eq!(
+            image_type_for_device("tablet_10"),
+            image_types::TEN_INCH_SCREENSHOTS
+        );
+    }
+
     #[test]
     fn test_edit_response_deserialization() {
         let json = r#"{"id": "abc123", "expiryTimeSeconds": "1234567890"}"#;
@@ -1189,4 +1568,91 @@ mod tests {
         let response: EditResponse = serde_json::from_str(json).unwrap();
         assert_eq!(response.id, "abc123");
     }
+
+    /// Serves uploads until `requests` have been answered. The first
+    /// request to `/rate-limited` gets a 429, the second to `/broken` a 500.
+    fn upload_server(requests: usize) -> MockServer {
+        let mut seen: HashMap<String, usize> = HashMap::new();
+        MockServer::with_handler(requests, move |request| {
+            let count = seen.entry(request.path.clone()).or_default();
+            *count += 1;
+            match (request.path.as_str(), *count) {
+                ("/rate-limited", 1) => MockResponse::status("429 Too Many Requests", "")
+                    .with_header("Retry-After", "0"),
+                ("/broken", 2) => MockResponse::status("500 Internal Server Error", "boom"),
+                _ => MockResponse::json(format!(r#"{{"image":{{"id":"img-{}"}}}}"#, count)),
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_upload_screenshot_sets_retries_and_counts() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let images: Vec<PathBuf> = (1..=3)
+            .map(|i| {
+                let path = temp.path().join(format!("0{}.png", i));
+                let mut data = b"\x89PNG\r\n\x1a\n".to_vec();
+                data.push(i);
+                std::fs::write(&path, data).unwrap();
+                path
+            })
+            .collect();
+
+        // 3 uploads plus one rate-limited attempt, then 2 (the second fails)
+        let server = upload_server(6);
+        let url = server.url().to_string();
+        let sets = vec![
+            ScreenshotSet {
+                field: "en-US/phone".to_string(),
+                url: format!("{}/rate-limited", url),
+                images: images.clone(),
+            },
+            ScreenshotSet {
+                field: "de-DE/phone".to_string(),
+                url: format!("{}/broken", url),
+                images,
+            },
+        ];
+
+        let summary = upload_screenshot_sets(&Client::new(), "token", sets, 2).await;
+        server.requests();
+
+        // The rate-limited set is retried; the broken set keeps its first upload
+        assert_eq!(summary.uploaded, 4);
+        assert_eq!(summary.failures.len(), 1);
+        assert!(summary.failures[0].contains("de-DE/phone (1 of 3 uploaded)"));
+    }
+
+    #[tokio::test]
+    async fn test_failed_screenshot_upload_aborts_push() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let image = temp.path().join("01.png");
+        std::fs::write(&image, b"\x89PNG\r\n\x1a\n").unwrap();
+
+        let sync = GooglePlayMetadataSync::new(
+            GooglePlaySyncConfig::from_key_json("{}"),
+            temp.path().to_path_buf(),
+        )
+        .await
+        .unwrap();
+        sync.access_token
+            .set("token".to_string(), Utc::now() + Duration::hours(1));
+
+        // The second upload of the set fails
+        let server = upload_server(2);
+        let sets = vec![ScreenshotSet {
+            field: "de-DE/phone".to_string(),
+            url: format!("{}/broken", server.url()),
+            images: vec![image.clone(), image],
+        }];
+        let mut result = PushResult::default();
+        let err = sync
+            .upload_screenshots(sets, &mut result)
+            .await
+            .unwrap_err();
+        server.requests();
+
+        assert!(err.to_string().contains("de-DE/phone (1 of 2 uploaded)"));
+        assert_eq!(result.screenshots_uploaded, 1);
+    }
 }
@@ -7,7 +7,9 @@
 //! ## Example
 //!
 //! ```no_run
-//! use canaveral_metadata::sync::{AppleMetadataSync, AppleSyncConfig, MetadataSync};
+//! use canaveral_metadata::sync::{
+//!     AppleMetadataSync, AppleSyncConfig, ConflictPolicy, MetadataSync,
+//! };
 //! use std::path::PathBuf;
 //!
 //! # async fn example() -> canaveral_metadata::Result<()> {
@@ -29,8 +31,10 @@
 //!     println!("{}: {} -> {:?}", change.locale, change.field, change.change_type);
 //! }
 //!
-//! // Push local changes
-//! let result = sync.push("com.example.app", None, false).await?;
+//! // Push local changes, keeping remote edits made since the last pull
+//! let result = sync
+//!     .push("com.example.app", None, false, ConflictPolicy::Remote)
+//!     .await?;
 //! println!("Updated {} locales", result.updated_locales.len());
 //! # Ok(())
 //! # }
@@ -38,6 +42,7 @@
 
 mod apple;
 pub mod common;
+mod conflict;
 mod google_play;
 
 pub use apple::{AppleMetadataSync, AppleSyncConfig};
@@ -45,6 +50,7 @@ pub use common::{
     log_rate_limit_warning, parse_locale, parse_retry_after, strings_differ,
     strings_differ_required, TokenCache, DEFAULT_MAX_RETRIES, DEFAULT_RETRY_DELAY_MS,
 };
+pub use conflict::{ConflictPolicy, ConflictResolver, PullSnapshot, Side, SNAPSHOT_FILE};
 pub use google_play::{
     image_types, GooglePlayMetadataSync, GooglePlaySyncConfig, Image, Listing, ListingUpdate,
 };
@@ -76,13 +82,17 @@ pub trait MetadataSync: Send + Sync {
 
     /// Push metadata from local storage to the app store.
     ///
-    /// Uploads local metadata changes to the remote app store.
+    /// Uploads local metadata changes to the remote app store. Fields
+    /// changed both locally and remotely since the last pull are resolved
+    /// with `policy`, and each resolved conflict is reported in
+    /// [`PushResult::warnings`].
     ///
     /// # Arguments
     ///
     /// * `app_id` - The app identifier
     /// * `locales` - Optional list of locales to push. If None, pushes all locales.
     /// * `dry_run` - If true, only validates changes without actually pushing.
+    /// * `policy` - How to resolve conflicting fields.
     ///
     /// # Returns
     ///
@@ -96,6 +106,7 @@ pub trait MetadataSync: Send + Sync {
         app_id: &str,
         locales: Option<&[Locale]>,
         dry_run: bool,
+        policy: ConflictPolicy,
     ) -> Result<PushResult>;
 
     /// Get the diff between local and remote metadata.
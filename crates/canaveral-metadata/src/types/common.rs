@@ -1,8 +1,12 @@
 //! Common types shared across platforms.
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::borrow::Borrow;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
 
+use crate::utils::{locales_equivalent, APPLE_SUPPORTED_LOCALES, GOOGLE_PLAY_SUPPORTED_LOCALES};
 use crate::MetadataError;
 
 /// Supported publishing platforms (app stores and package registries).
@@ -23,6 +27,30 @@ pub enum Platform {
     PyPI,
 }
 
+impl fmt::Display for Platform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Platform::Apple => write!(f, "App Store"),
+            Platform::GooglePlay => write!(f, "Google Play"),
+            Platform::Npm => write!(f, "npm"),
+            Platform::Crates => write!(f, "crates.io"),
+            Platform::PyPI => write!(f, "PyPI"),
+        }
+    }
+}
+
+/// Region assumed for a bare language code when a platform only lists it
+/// with a region, such as `pt` becoming `pt-BR`.
+pub const DEFAULT_LOCALE_REGIONS: &[(&str, &str)] = &[
+    ("de", "de-DE"),
+    ("en", "en-US"),
+    ("es", "es-ES"),
+    ("fr", "fr-FR"),
+    ("nl", "nl-NL"),
+    ("pt", "pt-BR"),
+    ("zh", "zh-Hans"),
+];
+
 /// A locale identifier with validation.
 ///
 /// Represents a BCP 47 language tag (e.g., "en-US", "de-DE", "ja").
@@ -56,24 +84,40 @@ impl Locale {
         let parts: Vec<&str> = code.split(&['-', '_'][..]).collect();
 
         let language = parts[0].to_lowercase();
-        if language.len() < 2 || language.len() > 3 {
+        if language.len() < 2
+            || language.len() > 3
+            || !language.chars().all(|c| c.is_ascii_alphabetic())
+        {
             return Err(MetadataError::InvalidFormat(format!(
                 "Invalid language code: {}",
                 language
             )));
         }
 
-        let region = if parts.len() > 1 {
-            let region = parts[1].to_uppercase();
-            if region.len() != 2 {
+        // A region is a country ("US"), a numeric area ("419") or a script
+        // ("Hans")
+        let region = match parts.get(1) {
+            Some(region)
+                if region.len() == 2 && region.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                Some(region.to_uppercase())
+            }
+            Some(region) if region.len() == 3 && region.chars().all(|c| c.is_ascii_digit()) => {
+                Some(region.to_string())
+            }
+            Some(script)
+                if script.len() == 4 && script.chars().all(|c| c.is_ascii_alphabetic()) =>
+            {
+                let (first, rest) = script.split_at(1);
+                Some(format!("{}{}", first.to_uppercase(), rest.to_lowercase()))
+            }
+            Some(region) => {
                 return Err(MetadataError::InvalidFormat(format!(
                     "Invalid region code: {}",
                     region
-                )));
+                )))
             }
-            Some(region)
-        } else {
-            None
+            None => None,
         };
 
         Ok(Self { language, region })
@@ -96,6 +140,77 @@ impl Locale {
             None => self.language.clone(),
         }
     }
+
+    /// Normalizes a locale code to its canonical form.
+    ///
+    /// Separators become `-`, languages lowercase, regions uppercase and
+    /// scripts title case, so `en_us`, `EN-US` and `en-US` all give `en-US`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the locale code is invalid.
+    pub fn normalize(code: &str) -> Result<String, MetadataError> {
+        Ok(Self::new(code)?.code())
+    }
+
+    /// Returns the locales `platform` accepts for store listings.
+    ///
+    /// `None` for registries that don't restrict locales.
+    pub fn supported_for(platform: Platform) -> Option<&'static [&'static str]> {
+        match platform {
+            Platform::Apple => Some(APPLE_SUPPORTED_LOCALES),
+            Platform::GooglePlay => Some(GOOGLE_PLAY_SUPPORTED_LOCALES),
+            Platform::Npm | Platform::Crates | Platform::PyPI => None,
+        }
+    }
+
+    /// Returns `true` if `platform` accepts this exact locale.
+    pub fn is_valid_for(&self, platform: Platform) -> bool {
+        match Self::supported_for(platform) {
+            Some(supported) => supported.contains(&self.code().as_str()),
+            None => true,
+        }
+    }
+
+    /// Resolves this locale to the one `platform` uses for it.
+    ///
+    /// Tries, in order: the locale itself, an equivalent listed locale (see
+    /// [`locales_equivalent`]), the bare
+    /// language (`ja-JP` to `ja`), and a region for a bare language from
+    /// [`DEFAULT_LOCALE_REGIONS`] or the only listed region (`ja` to
+    /// `ja-JP`). Returns `None` if the platform has no equivalent.
+    pub fn resolve_for(&self, platform: Platform) -> Option<Locale> {
+        let Some(supported) = Self::supported_for(platform) else {
+            return Some(self.clone());
+        };
+        let code = self.code();
+        let mut candidates = std::iter::once(code.clone())
+            .chain(
+                supported
+                    .iter()
+                    .filter(|listed| locales_equivalent(listed, &code))
+                    .map(|listed| listed.to_string()),
+            )
+            .chain(self.region.is_some().then(|| self.language.clone()))
+            .chain(self.region.is_none().then(|| {
+                DEFAULT_LOCALE_REGIONS
+                    .iter()
+                    .find(|(language, _)| *language == self.language)
+                    .map(|(_, locale)| locale.to_string())
+                    .unwrap_or_else(|| {
+                        let prefix = format!("{}-", self.language);
+                        let mut regional = supported.iter().filter(|l| l.starts_with(&prefix));
+                        match (regional.next(), regional.next()) {
+                            (Some(only), None) => only.to_string(),
+                            _ => code.clone(),
+                        }
+                    })
+            }));
+
+        candidates
+            .find(|candidate| supported.contains(&candidate.as_str()))
+            .and_then(|candidate| Locale::new(&candidate).ok())
+    }
 }
 
 impl Default for Locale {
@@ -170,6 +285,12 @@ pub struct MediaAsset {
     pub locale: Option<Locale>,
     /// Asset type.
     pub asset_type: AssetType,
+    /// Position in the store listing, starting at 1.
+    ///
+    /// When unset, the order is taken from a numeric file name prefix
+    /// (`01.png`, `02_settings.png`); see [`MediaAsset::display_order`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_order: Option<u32>,
 }
 
 impl MediaAsset {
@@ -180,6 +301,7 @@ impl MediaAsset {
             dimensions: None,
             locale: None,
             asset_type,
+            display_order: None,
         }
     }
 
@@ -194,6 +316,109 @@ impl MediaAsset {
         self.locale = Some(locale);
         self
     }
+
+    /// Sets an explicit display order for the asset.
+    pub fn with_display_order(mut self, order: u32) -> Self {
+        self.display_order = Some(order);
+        self
+    }
+
+    /// Returns the asset's position in the listing.
+    ///
+    /// An explicit [`display_order`](Self::display_order) wins; otherwise the
+    /// order is parsed from the file name prefix.
+    pub fn display_order(&self) -> Option<u32> {
+        self.display_order
+            .or_else(|| display_order_from_path(&self.path))
+    }
+}
+
+/// Parses the display order from a numeric file name prefix.
+///
+/// `01.png`, `2-home.png` and `010_settings.jpg` give 1, 2 and 10; names
+/// without a leading number give `None`.
+pub fn display_order_from_path(path: &Path) -> Option<u32> {
+    let stem = path.file_stem()?.to_str()?;
+    let digits = stem.bytes().take_while(u8::is_ascii_digit).count();
+    stem[..digits].parse().ok()
+}
+
+/// Sorts assets by display order.
+///
+/// Assets without an order go last, by path. The sort is stable, so assets
+/// sharing an order keep their relative position.
+pub fn sort_by_display_order<A: Borrow<MediaAsset>>(assets: &mut [A]) {
+    assets.sort_by(|a, b| {
+        let (a, b) = (a.borrow(), b.borrow());
+        let key = |asset: &MediaAsset| (asset.display_order().is_none(), asset.display_order());
+        key(a).cmp(&key(b)).then_with(|| a.path.cmp(&b.path))
+    });
+}
+
+/// A problem with the display order of a set of assets.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderingIssue {
+    /// The asset has no explicit order and no numeric file name prefix.
+    Unordered(PathBuf),
+    /// Several assets claim the same position.
+    Duplicate {
+        /// The shared position.
+        order: u32,
+        /// The assets claiming it.
+        paths: Vec<PathBuf>,
+    },
+    /// No asset claims this position, although later ones are taken.
+    Gap(u32),
+}
+
+impl fmt::Display for OrderingIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OrderingIssue::Unordered(path) => {
+                write!(f, "{} has no display order", path.display())
+            }
+            OrderingIssue::Duplicate { order, paths } => {
+                let names: Vec<_> = paths
+                    .iter()
+                    .map(|p| p.file_name().unwrap_or(p.as_os_str()).to_string_lossy())
+                    .collect();
+                write!(f, "position {} is claimed by {}", order, names.join(", "))
+            }
+            OrderingIssue::Gap(order) => write!(f, "position {} is missing", order),
+        }
+    }
+}
+
+/// Checks that assets have distinct display orders without gaps.
+///
+/// Positions are expected to run from 1 (or 0) up to the highest one used.
+/// A lone asset without an order is not ambiguous and isn't reported.
+pub fn check_display_order(assets: &[MediaAsset]) -> Vec<OrderingIssue> {
+    let mut issues = Vec::new();
+    let mut by_order: BTreeMap<u32, Vec<PathBuf>> = BTreeMap::new();
+
+    for asset in assets {
+        match asset.display_order() {
+            Some(order) => by_order.entry(order).or_default().push(asset.path.clone()),
+            None if assets.len() > 1 => issues.push(OrderingIssue::Unordered(asset.path.clone())),
+            None => {}
+        }
+    }
+
+    if let Some(&last) = by_order.keys().next_back() {
+        let first = by_order.keys().next().copied().unwrap_or(1).min(1);
+        for order in first..=last {
+            match by_order.remove(&order) {
+                None => issues.push(OrderingIssue::Gap(order)),
+                Some(paths) if paths.len() > 1 => {
+                    issues.push(OrderingIssue::Duplicate { order, paths })
+                }
+                Some(_) => {}
+            }
+        }
+    }
+
+    issues
 }
 
 impl Default for MediaAsset {
@@ -203,6 +428,7 @@ impl Default for MediaAsset {
             dimensions: None,
             locale: None,
             asset_type: AssetType::default(),
+            display_order: None,
         }
     }
 }
@@ -232,5 +458,117 @@ mod tests {
         assert!(Locale::new("").is_err());
         assert!(Locale::new("x").is_err());
         assert!(Locale::new("en-USA").is_err());
+        assert!(Locale::new("e1-US").is_err());
+    }
+
+    #[test]
+    fn test_locale_normalize() {
+        assert_eq!(Locale::normalize("en_us").unwrap(), "en-US");
+        assert_eq!(Locale::normalize("EN-US").unwrap(), "en-US");
+        assert_eq!(Locale::normalize(" pt_br ").unwrap(), "pt-BR");
+        assert_eq!(Locale::normalize("zh_hans").unwrap(), "zh-Hans");
+        assert_eq!(Locale::normalize("ES-419").unwrap(), "es-419");
+        assert_eq!(Locale::normalize("JA").unwrap(), "ja");
+        assert!(Locale::normalize("en-U").is_err());
+    }
+
+    #[test]
+    fn test_locale_platform_support() {
+        // Apple lists Japanese without a region, Google Play only with one
+        let ja = Locale::new("ja").unwrap();
+        assert!(ja.is_valid_for(Platform::Apple));
+        assert!(!ja.is_valid_for(Platform::GooglePlay));
+        assert!(ja.is_valid_for(Platform::Npm));
+
+        let zh = Locale::new("zh-Hans").unwrap();
+        assert!(zh.is_valid_for(Platform::Apple));
+        assert!(!zh.is_valid_for(Platform::GooglePlay));
+    }
+
+    #[test]
+    fn test_locale_resolve_aliases() {
+        let resolve = |code: &str, platform| {
+            Locale::new(code)
+                .unwrap()
+                .resolve_for(platform)
+                .map(|l| l.code())
+        };
+        assert_eq!(
+            resolve("ja", Platform::GooglePlay).as_deref(),
+            Some("ja-JP")
+        );
+        assert_eq!(resolve("ja-JP", Platform::Apple).as_deref(), Some("ja"));
+        assert_eq!(
+            resolve("zh-Hans", Platform::GooglePlay).as_deref(),
+            Some("zh-CN")
+        );
+        assert_eq!(
+            resolve("zh-CN", Platform::Apple).as_deref(),
+            Some("zh-Hans")
+        );
+        assert_eq!(
+            resolve("he", Platform::GooglePlay).as_deref(),
+            Some("iw-IL")
+        );
+        assert_eq!(resolve("pt", Platform::Apple).as_deref(), Some("pt-BR"));
+        assert_eq!(resolve("en-US", Platform::Apple).as_deref(), Some("en-US"));
+        assert_eq!(resolve("af", Platform::Apple), None);
+    }
+
+    fn screenshot(name: &str) -> MediaAsset {
+        MediaAsset::new(PathBuf::from(name), AssetType::Screenshot)
+    }
+
+    #[test]
+    fn test_display_order_from_file_name() {
+        assert_eq!(screenshot("shots/01.png").display_order(), Some(1));
+        assert_eq!(screenshot("010_settings.jpg").display_order(), Some(10));
+        assert_eq!(screenshot("2-home.png").display_order(), Some(2));
+        assert_eq!(screenshot("home.png").display_order(), None);
+        assert_eq!(
+            screenshot("home.png").with_display_order(3).display_order(),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn test_sort_by_display_order() {
+        let mut assets = vec![
+            screenshot("10.png"),
+            screenshot("extra.png"),
+            screenshot("home.png").with_display_order(1),
+            screenshot("02.png"),
+        ];
+        sort_by_display_order(&mut assets);
+        let names: Vec<_> = assets.iter().map(|a| a.path.to_str().unwrap()).collect();
+        assert_eq!(names, ["home.png", "02.png", "10.png", "extra.png"]);
+    }
+
+    #[test]
+    fn test_check_display_order_gaps_and_duplicates() {
+        let ordered = [screenshot("01.png"), screenshot("02.png")];
+        assert!(check_display_order(&ordered).is_empty());
+
+        let issues = check_display_order(&[
+            screenshot("01_home.png"),
+            screenshot("01_search.png"),
+            screenshot("03.png"),
+            screenshot("promo.png"),
+        ]);
+        assert_eq!(
+            issues,
+            vec![
+                OrderingIssue::Unordered(PathBuf::from("promo.png")),
+                OrderingIssue::Duplicate {
+                    order: 1,
+                    paths: vec![PathBuf::from("01_home.png"), PathBuf::from("01_search.png")],
+                },
+                OrderingIssue::Gap(2),
+            ]
+        );
+        assert_eq!(
+            issues[1].to_string(),
+            "position 1 is claimed by 01_home.png, 01_search.png"
+        );
     }
 }
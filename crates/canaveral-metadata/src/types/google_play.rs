@@ -170,6 +170,30 @@ impl GooglePlayScreenshotSet {
             + self.wear.len()
     }
 
+    /// Returns the screenshots of each device type, keyed by field name.
+    pub fn device_types(&self) -> [(&'static str, &[MediaAsset]); 5] {
+        [
+            ("phone", &self.phone),
+            ("tablet_7", &self.tablet_7),
+            ("tablet_10", &self.tablet_10),
+            ("tv", &self.tv),
+            ("wear", &self.wear),
+        ]
+    }
+
+    /// Returns the screenshots for a device directory name, if it maps to a
+    /// device type of this set.
+    pub fn device_mut(&mut self, name: &str) -> Option<&mut Vec<MediaAsset>> {
+        match name {
+            "phone" => Some(&mut self.phone),
+            "tablet_7" => Some(&mut self.tablet_7),
+            "tablet_10" => Some(&mut self.tablet_10),
+            "tv" => Some(&mut self.tv),
+            "wear" => Some(&mut self.wear),
+            _ => None,
+        }
+    }
+
     /// Checks if any screenshots are present.
     pub fn is_empty(&self) -> bool {
         self.total_count() == 0
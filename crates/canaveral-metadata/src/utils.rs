@@ -5,25 +5,64 @@
 
 use std::collections::HashSet;
 
+use crate::types::common::Locale;
+
+/// Locales accepted by App Store Connect for store listings.
+pub const APPLE_SUPPORTED_LOCALES: &[&str] = &[
+    "ar-SA", "ca", "cs", "da", "de-DE", "el", "en-AU", "en-CA", "en-GB", "en-US", "es-ES", "es-MX",
+    "fi", "fr-CA", "fr-FR", "he", "hi", "hr", "hu", "id", "it", "ja", "ko", "ms", "nl-NL", "no",
+    "pl", "pt-BR", "pt-PT", "ro", "ru", "sk", "sv", "th", "tr", "uk", "vi", "zh-Hans", "zh-Hant",
+];
+
+/// Locales accepted by the Google Play Console for store listings.
+pub const GOOGLE_PLAY_SUPPORTED_LOCALES: &[&str] = &[
+    "af", "am", "ar", "az-AZ", "be", "bg", "bn-BD", "ca", "cs-CZ", "da-DK", "de-DE", "el-GR",
+    "en-AU", "en-CA", "en-GB", "en-IN", "en-SG", "en-US", "en-ZA", "es-419", "es-ES", "es-US",
+    "et", "eu-ES", "fa", "fa-AE", "fa-AF", "fa-IR", "fi-FI", "fil", "fr-CA", "fr-FR", "gl-ES",
+    "gu", "hi-IN", "hr", "hu-HU", "hy-AM", "id", "is-IS", "it-IT", "iw-IL", "ja-JP", "ka-GE", "kk",
+    "km-KH", "kn-IN", "ko-KR", "ky-KG", "lo-LA", "lt", "lv", "mk-MK", "ml-IN", "mn-MN", "mr-IN",
+    "ms", "ms-MY", "my-MM", "ne-NP", "nl-NL", "no-NO", "pa", "pl-PL", "pt-BR", "pt-PT", "rm", "ro",
+    "ru-RU", "si-LK", "sk", "sl", "sq", "sr", "sv-SE", "sw", "ta-IN", "te-IN", "th", "tr-TR", "uk",
+    "ur", "vi", "zh-CN", "zh-HK", "zh-TW", "zu",
+];
+
+/// Pairs of locale codes naming the same listing language on different
+/// platforms, such as Apple's `zh-Hans` and Google Play's `zh-CN`.
+pub const LOCALE_ALIASES: &[(&str, &str)] = &[
+    ("zh-Hans", "zh-CN"),
+    ("zh-Hant", "zh-TW"),
+    ("he", "iw-IL"),
+    ("he-IL", "iw-IL"),
+    ("iw", "he"),
+    ("no", "no-NO"),
+    ("nb", "no"),
+    ("nb-NO", "no-NO"),
+    ("tl", "fil"),
+    ("ar", "ar-SA"),
+    ("es-419", "es-MX"),
+];
+
 /// List of common/recommended locales for Apple App Store.
 ///
 /// These are the most commonly used locales that Apple supports for App Store
-/// listings. Consider supporting at least `en-US` and a few major markets.
+/// listings, a subset of [`APPLE_SUPPORTED_LOCALES`]. Consider supporting at
+/// least `en-US` and a few major markets.
 pub const APPLE_RECOMMENDED_LOCALES: &[&str] = &[
-    "en-US", "en-GB", "en-AU", "en-CA", "de-DE", "fr-FR", "es-ES", "es-MX", "it-IT", "pt-BR",
-    "pt-PT", "nl-NL", "ja", "ko", "zh-Hans", "zh-Hant", "ru", "tr", "ar", "he", "th", "vi", "id",
-    "ms", "pl", "uk", "cs", "el", "hu", "ro", "sk", "da", "fi", "no", "sv",
+    "en-US", "en-GB", "en-AU", "en-CA", "de-DE", "fr-FR", "es-ES", "es-MX", "it", "pt-BR", "pt-PT",
+    "nl-NL", "ja", "ko", "zh-Hans", "zh-Hant", "ru", "tr", "ar-SA", "he", "th", "vi", "id", "ms",
+    "pl", "uk", "cs", "el", "hu", "ro", "sk", "da", "fi", "no", "sv",
 ];
 
 /// List of common/recommended locales for Google Play Store.
 ///
 /// These are the most commonly used locales that Google Play supports for
-/// store listings. Consider supporting at least `en-US` and a few major markets.
+/// store listings, a subset of [`GOOGLE_PLAY_SUPPORTED_LOCALES`]. Consider
+/// supporting at least `en-US` and a few major markets.
 pub const GOOGLE_PLAY_RECOMMENDED_LOCALES: &[&str] = &[
     "en-US", "en-GB", "en-AU", "en-IN", "de-DE", "fr-FR", "es-ES", "es-419", "it-IT", "pt-BR",
-    "pt-PT", "nl-NL", "ja-JP", "ko-KR", "zh-CN", "zh-TW", "ru-RU", "tr-TR", "ar", "he-IL", "th",
+    "pt-PT", "nl-NL", "ja-JP", "ko-KR", "zh-CN", "zh-TW", "ru-RU", "tr-TR", "ar", "iw-IL", "th",
     "vi", "id", "ms", "pl-PL", "uk", "cs-CZ", "el-GR", "hu-HU", "ro", "sk", "da-DK", "fi-FI",
-    "nb-NO", "sv-SE", "hi-IN", "bn-BD", "ta-IN",
+    "no-NO", "sv-SE", "hi-IN", "bn-BD", "ta-IN",
 ];
 
 /// Auto-fix common text issues in metadata.
@@ -67,70 +106,13 @@ pub fn auto_fix_text(text: &str) -> String {
 /// assert_eq!(normalize_locale("zh-hans"), "zh-Hans");
 /// ```
 pub fn normalize_locale(locale: &str) -> String {
-    // Replace underscores with hyphens and normalize
-    let normalized = locale.replace('_', "-");
-    let parts: Vec<&str> = normalized.split('-').collect();
-
-    match parts.len() {
-        1 => parts[0].to_lowercase(),
-        2 => {
-            let lang = parts[0].to_lowercase();
-            let second = parts[1];
-
-            // Check if it's a script (like Hans, Hant) - typically 4 chars
-            if second.len() == 4 {
-                // It's a script like Hans or Hant - title case it
-                let script: String = second
-                    .chars()
-                    .enumerate()
-                    .map(|(i, c)| {
-                        if i == 0 {
-                            c.to_uppercase().next().unwrap()
-                        } else {
-                            c.to_lowercase().next().unwrap()
-                        }
-                    })
-                    .collect();
-                format!("{}-{}", lang, script)
-            } else {
-                // Treat as region - uppercase it
-                format!("{}-{}", lang, second.to_uppercase())
-            }
-        }
-        _ => {
-            // Handle complex locales with more than 2 parts
-            let lang = parts[0].to_lowercase();
-            if parts.len() >= 2 {
-                let script_or_region = parts[1];
-                // Check if it's a script (like Hans, Hant) - typically 4 chars
-                if script_or_region.len() == 4 {
-                    // It's a script like Hans or Hant - title case it
-                    let script: String = script_or_region
-                        .chars()
-                        .enumerate()
-                        .map(|(i, c)| {
-                            if i == 0 {
-                                c.to_uppercase().next().unwrap()
-                            } else {
-                                c.to_lowercase().next().unwrap()
-                            }
-                        })
-                        .collect();
-                    format!("{}-{}", lang, script)
-                } else {
-                    // Treat as region
-                    format!("{}-{}", lang, script_or_region.to_uppercase())
-                }
-            } else {
-                lang
-            }
-        }
-    }
+    Locale::normalize(locale).unwrap_or_else(|_| locale.trim().replace('_', "-"))
 }
 
 /// Check if two locale codes are equivalent.
 ///
-/// Compares two locale codes after normalization, so "en-US" == "en_US" == "EN-us".
+/// Compares two locale codes after normalization, so "en-US" == "en_US" == "EN-us",
+/// and treats the pairs in [`LOCALE_ALIASES`] as the same language.
 ///
 /// # Example
 ///
@@ -139,10 +121,15 @@ pub fn normalize_locale(locale: &str) -> String {
 ///
 /// assert!(locales_equivalent("en-US", "en_US"));
 /// assert!(locales_equivalent("de-DE", "DE-de"));
+/// assert!(locales_equivalent("zh-Hans", "zh_CN"));
 /// assert!(!locales_equivalent("en-US", "en-GB"));
 /// ```
 pub fn locales_equivalent(a: &str, b: &str) -> bool {
-    normalize_locale(a) == normalize_locale(b)
+    let (a, b) = (normalize_locale(a), normalize_locale(b));
+    a == b
+        || LOCALE_ALIASES
+            .iter()
+            .any(|&(x, y)| (x == a && y == b) || (x == b && y == a))
 }
 
 /// Get the language part of a locale code.
@@ -463,6 +450,16 @@ mod tests {
         assert!(!is_google_play_recommended_locale("xy-ZZ"));
     }
 
+    #[test]
+    fn test_recommended_locales_are_supported() {
+        for locale in APPLE_RECOMMENDED_LOCALES {
+            assert!(APPLE_SUPPORTED_LOCALES.contains(locale), "{locale}");
+        }
+        for locale in GOOGLE_PLAY_RECOMMENDED_LOCALES {
+            assert!(GOOGLE_PLAY_SUPPORTED_LOCALES.contains(locale), "{locale}");
+        }
+    }
+
     #[test]
     fn test_get_missing_locales() {
         let supported = vec!["en-US", "de-DE"];
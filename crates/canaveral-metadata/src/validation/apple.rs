@@ -11,12 +11,14 @@ use crate::types::apple::{
     limits, AgeRatingLevel, AppleAgeRating, AppleLocalizedMetadata, AppleMetadata,
     AppleScreenshotSet,
 };
-use crate::types::common::{Dimensions, Locale, MediaAsset};
+use crate::types::common::{Dimensions, Locale, MediaAsset, Platform};
 
 use super::common::{
     char_count, contains_newlines, has_excess_whitespace, is_blank, validate_keywords_format,
-    validate_url,
+    validate_locale_for_platform, validate_url,
 };
+use super::screenshots::validate_screenshot_order;
+use super::similarity::{validate_screenshot_duplicates, DEFAULT_SIMILARITY_THRESHOLD};
 use super::{Severity, ValidationIssue, ValidationResult};
 
 /// Valid screenshot dimensions for iPhone 6.5" display.
@@ -92,6 +94,8 @@ pub struct AppleValidator {
     pub is_update: bool,
     /// When true, requires privacy policy URL.
     pub requires_privacy_policy: bool,
+    /// Hash distance at or below which screenshots count as duplicates.
+    pub similarity_threshold: u32,
 }
 
 impl Default for AppleValidator {
@@ -100,6 +104,7 @@ impl Default for AppleValidator {
             strict: false,
             is_update: false,
             requires_privacy_policy: true,
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
         }
     }
 }
@@ -124,7 +129,7 @@ impl AppleValidator {
         Self {
             strict,
             is_update: true,
-            requires_privacy_policy: true,
+            ..Default::default()
         }
     }
 
@@ -134,6 +139,12 @@ impl AppleValidator {
         self
     }
 
+    /// Sets the hash distance at or below which screenshots count as duplicates.
+    pub fn with_similarity_threshold(mut self, threshold: u32) -> Self {
+        self.similarity_threshold = threshold;
+        self
+    }
+
     /// Validates Apple App Store metadata.
     ///
     /// # Arguments
@@ -190,6 +201,12 @@ impl AppleValidator {
                     continue;
                 }
             };
+            if let Some(issue) = validate_locale_for_platform(&locale, Platform::Apple) {
+                result.issues.push(ValidationIssue {
+                    field: locale_code.clone(),
+                    ..issue
+                });
+            }
             let is_primary = locale_code == &primary_locale_code;
             self.validate_localized(&locale, localized, is_primary, &mut result);
         }
@@ -666,6 +683,9 @@ impl AppleValidator {
                 )),
             );
         }
+
+        validate_screenshot_order(screenshots, field, result);
+        validate_screenshot_duplicates(screenshots, self.similarity_threshold, field, result);
     }
 
     /// Validates age rating configuration.
@@ -858,6 +878,47 @@ mod tests {
             .any(|e| e.field.contains("iphone_6_5") && e.message.contains("Too many screenshots")));
     }
 
+    #[test]
+    fn test_localized_screenshots_warn_on_ambiguous_order() {
+        let screenshot = |name: &str| {
+            MediaAsset::new(PathBuf::from(name), AssetType::Screenshot).with_dimensions(1242, 2688)
+        };
+
+        let ordered = AppleScreenshotSet {
+            iphone_6_5: vec![screenshot("01.png"), screenshot("02.png")],
+            ..Default::default()
+        };
+        let ambiguous = AppleScreenshotSet {
+            iphone_6_5: vec![
+                screenshot("01_home.png"),
+                screenshot("01_search.png"),
+                screenshot("03.png"),
+            ],
+            ..Default::default()
+        };
+
+        let screenshots = HashMap::from([
+            (Locale::new("en-US").unwrap(), ordered),
+            (Locale::new("de-DE").unwrap(), ambiguous),
+        ]);
+        let mut result = ValidationResult::new();
+        validate_localized_screenshots(&screenshots, &mut result);
+
+        let order_warnings: Vec<_> = result
+            .warnings()
+            .into_iter()
+            .filter(|w| w.message.starts_with("Ambiguous screenshot order"))
+            .collect();
+        assert_eq!(order_warnings.len(), 2);
+        assert!(order_warnings
+            .iter()
+            .all(|w| w.field == "de-DE.screenshots.iphone_6_5"));
+        assert!(order_warnings
+            .iter()
+            .any(|w| w.message.contains("position 2 is missing")));
+        assert!(result.is_valid());
+    }
+
     #[test]
     fn test_whitespace_warnings() {
         let mut metadata = create_valid_metadata();
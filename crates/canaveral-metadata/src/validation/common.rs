@@ -2,6 +2,10 @@
 //!
 //! This module provides reusable validation functions for strings, URLs, locales, and more.
 
+use crate::types::common::{Locale, Platform};
+
+use super::ValidationIssue;
+
 /// Validates that a string does not exceed a maximum character count.
 ///
 /// Note: This counts Unicode characters (grapheme clusters approximately), not bytes.
@@ -161,6 +165,36 @@ pub fn validate_locale(locale: &str) -> bool {
     true
 }
 
+/// Validates that a platform accepts a locale for its store listings.
+///
+/// # Arguments
+///
+/// * `locale` - The locale to check
+/// * `platform` - The target platform
+///
+/// # Returns
+///
+/// A warning if the platform doesn't accept the locale, suggesting the
+/// platform's equivalent when there is one, or `None` if it is accepted.
+pub fn validate_locale_for_platform(
+    locale: &Locale,
+    platform: Platform,
+) -> Option<ValidationIssue> {
+    if locale.is_valid_for(platform) {
+        return None;
+    }
+    let message = format!("Locale '{}' is not supported by {}", locale, platform);
+    Some(match locale.resolve_for(platform) {
+        Some(equivalent) => ValidationIssue::with_suggestion(
+            super::Severity::Warning,
+            locale.code(),
+            message,
+            format!("Use '{}' instead", equivalent),
+        ),
+        None => ValidationIssue::warning(locale.code(), message),
+    })
+}
+
 /// Checks if a string has leading or trailing whitespace.
 ///
 /// # Arguments
@@ -362,6 +396,22 @@ mod tests {
         assert!(!validate_locale("123"));
     }
 
+    #[test]
+    fn test_validate_locale_for_platform() {
+        let ja = Locale::new("ja").unwrap();
+        assert!(validate_locale_for_platform(&ja, Platform::Apple).is_none());
+
+        let issue = validate_locale_for_platform(&ja, Platform::GooglePlay).unwrap();
+        assert_eq!(issue.severity, super::super::Severity::Warning);
+        assert_eq!(issue.field, "ja");
+        assert_eq!(issue.message, "Locale 'ja' is not supported by Google Play");
+        assert_eq!(issue.suggestion.as_deref(), Some("Use 'ja-JP' instead"));
+
+        let af = Locale::new("af").unwrap();
+        let issue = validate_locale_for_platform(&af, Platform::Apple).unwrap();
+        assert!(issue.suggestion.is_none());
+    }
+
     #[test]
     fn test_has_excess_whitespace() {
         assert!(!has_excess_whitespace("hello"));
@@ -8,12 +8,16 @@ use std::path::PathBuf;
 
 use tracing::{debug, info};
 
-use crate::types::common::{Dimensions, Locale, MediaAsset};
+use crate::types::common::{Dimensions, Locale, MediaAsset, Platform};
 use crate::types::google_play::{
     limits, GooglePlayLocalizedMetadata, GooglePlayMetadata, GooglePlayScreenshotSet,
 };
 
-use super::common::{char_count, has_excess_whitespace, is_blank, validate_url};
+use super::common::{
+    char_count, has_excess_whitespace, is_blank, validate_locale_for_platform, validate_url,
+};
+use super::screenshots::validate_screenshot_order;
+use super::similarity::{validate_screenshot_duplicates, DEFAULT_SIMILARITY_THRESHOLD};
 use super::{Severity, ValidationIssue, ValidationResult};
 
 /// Maximum characters for changelog entries.
@@ -81,6 +85,8 @@ pub struct GooglePlayValidator {
     pub strict: bool,
     /// When true, requires feature graphic to be present.
     pub requires_feature_graphic: bool,
+    /// Hash distance at or below which screenshots count as duplicates.
+    pub similarity_threshold: u32,
 }
 
 impl Default for GooglePlayValidator {
@@ -88,6 +94,7 @@ impl Default for GooglePlayValidator {
         Self {
             strict: false,
             requires_feature_graphic: true,
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
         }
     }
 }
@@ -111,6 +118,12 @@ impl GooglePlayValidator {
         self
     }
 
+    /// Sets the hash distance at or below which screenshots count as duplicates.
+    pub fn with_similarity_threshold(mut self, threshold: u32) -> Self {
+        self.similarity_threshold = threshold;
+        self
+    }
+
     /// Validates Google Play Store metadata.
     ///
     /// # Arguments
@@ -164,6 +177,12 @@ impl GooglePlayValidator {
                     continue;
                 }
             };
+            if let Some(issue) = validate_locale_for_platform(&locale, Platform::GooglePlay) {
+                result.issues.push(ValidationIssue {
+                    field: locale_code.clone(),
+                    ..issue
+                });
+            }
             let is_default = locale_code == &default_locale_code;
             self.validate_localized(&locale, localized, is_default, &mut result);
         }
@@ -475,6 +494,9 @@ impl GooglePlayValidator {
                 );
             }
         }
+
+        validate_screenshot_order(screenshots, field, result);
+        validate_screenshot_duplicates(screenshots, self.similarity_threshold, field, result);
     }
 
     /// Validates screenshot dimensions based on device type.
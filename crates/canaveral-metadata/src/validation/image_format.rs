@@ -0,0 +1,260 @@
+//! Image format detection and header-only dimension reading.
+//!
+//! PNG, JPEG and WebP are handled by the `image` crate. HEIC/HEIF has no
+//! pure-Rust decoder, so dimensions are read straight from the ISO base media
+//! file format container (the `ispe` property of the image items).
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use crate::types::common::Dimensions;
+use crate::{MetadataError, Result};
+
+/// Maximum number of bytes read when parsing a HEIC container for `ispe`.
+const HEIC_HEADER_LIMIT: u64 = 256 * 1024;
+
+/// ftyp brands identifying HEIC/HEIF files.
+const HEIF_BRANDS: &[&[u8; 4]] = &[
+    b"heic", b"heix", b"hevc", b"hevx", b"heim", b"heis", b"mif1", b"msf1",
+];
+
+/// Image formats accepted in screenshot directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Portable Network Graphics
+    Png,
+    /// JPEG
+    Jpeg,
+    /// WebP
+    WebP,
+    /// HEIC/HEIF
+    Heic,
+}
+
+impl ImageFormat {
+    /// Determines the format from a file extension (case-insensitive).
+    pub fn from_extension(ext: &str) -> Option<Self> {
+        match ext.to_lowercase().as_str() {
+            "png" => Some(Self::Png),
+            "jpg" | "jpeg" => Some(Self::Jpeg),
+            "webp" => Some(Self::WebP),
+            "heic" | "heif" => Some(Self::Heic),
+            _ => None,
+        }
+    }
+
+    /// Determines the format from a path's extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .and_then(Self::from_extension)
+    }
+
+    /// Whether app stores accept this format as-is.
+    ///
+    /// Both App Store Connect and Google Play only take PNG and JPEG; other
+    /// formats need to be converted before upload.
+    pub fn is_store_compatible(&self) -> bool {
+        matches!(self, Self::Png | Self::Jpeg)
+    }
+
+    /// Human-readable format name.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Png => "PNG",
+            Self::Jpeg => "JPEG",
+            Self::WebP => "WebP",
+            Self::Heic => "HEIC",
+        }
+    }
+}
+
+impl std::fmt::Display for ImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// Reads image dimensions without decoding pixel data.
+pub(crate) fn read_dimensions(path: &Path) -> Result<Dimensions> {
+    let mut header = Vec::new();
+    File::open(path)?
+        .take(HEIC_HEADER_LIMIT)
+        .read_to_end(&mut header)?;
+
+    if is_heif(&header) {
+        return heic_dimensions(&header).ok_or_else(|| {
+            invalid_data(format!(
+                "Failed to read image dimensions from {:?}: no image size in HEIC container",
+                path
+            ))
+        });
+    }
+
+    let (width, height) = image::image_dimensions(path).map_err(|e| {
+        invalid_data(format!(
+            "Failed to read image dimensions from {:?}: {}",
+            path, e
+        ))
+    })?;
+
+    Ok(Dimensions { width, height })
+}
+
+pub(super) fn invalid_data(message: String) -> MetadataError {
+    MetadataError::Io(std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        message,
+    ))
+}
+
+/// Checks for an `ftyp` box with a HEIF-family brand.
+fn is_heif(data: &[u8]) -> bool {
+    let Some((b"ftyp", body)) = next_box(data).map(|(kind, body, _)| (kind, body)) else {
+        return false;
+    };
+    if body.len() < 8 {
+        return false;
+    }
+
+    // major brand, minor version, then compatible brands
+    std::iter::once(&body[..4])
+        .chain(body[8..].chunks_exact(4))
+        .any(|brand| HEIF_BRANDS.iter().any(|b| &b[..] == brand))
+}
+
+/// Finds the largest `ispe` (image spatial extent) property.
+///
+/// Files usually carry a full-size primary image alongside thumbnails or
+/// grid tiles; the largest extent is the one reviewers will see.
+fn heic_dimensions(data: &[u8]) -> Option<Dimensions> {
+    let meta = find_box(data, b"meta")?;
+    // meta is a full box: skip version and flags
+    let iprp = find_box(meta.get(4..)?, b"iprp")?;
+    let ipco = find_box(iprp, b"ipco")?;
+
+    let mut best: Option<Dimensions> = None;
+    let mut rest = ipco;
+    while let Some((kind, body, remaining)) = next_box(rest) {
+        if kind == b"ispe" && body.len() >= 12 {
+            let width = u32::from_be_bytes(body[4..8].try_into().ok()?);
+            let height = u32::from_be_bytes(body[8..12].try_into().ok()?);
+            let area = |d: &Dimensions| u64::from(d.width) * u64::from(d.height);
+            let candidate = Dimensions { width, height };
+            if best.map_or(true, |b| area(&candidate) > area(&b)) {
+                best = Some(candidate);
+            }
+        }
+        rest = remaining;
+    }
+
+    best
+}
+
+/// Returns the body of the first top-level box of the given type.
+fn find_box<'a>(mut data: &'a [u8], kind: &[u8; 4]) -> Option<&'a [u8]> {
+    while let Some((found, body, rest)) = next_box(data) {
+        if found == kind {
+            return Some(body);
+        }
+        data = rest;
+    }
+    None
+}
+
+/// Splits the next box off `data`, returning (type, body, remaining).
+fn next_box(data: &[u8]) -> Option<(&[u8; 4], &[u8], &[u8])> {
+    let size = u32::from_be_bytes(data.get(..4)?.try_into().ok()?) as u64;
+    let kind: &[u8; 4] = data.get(4..8)?.try_into().ok()?;
+
+    let (header, size) = match size {
+        // size 0: box extends to end of data
+        0 => (8, data.len() as u64),
+        // size 1: 64-bit largesize follows the type
+        1 => (16, u64::from_be_bytes(data.get(8..16)?.try_into().ok()?)),
+        n => (8, n),
+    };
+
+    let end = usize::try_from(size).ok()?.min(data.len());
+    if end < header {
+        return None;
+    }
+    Some((kind, &data[header..end], &data[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn make_box(kind: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut out = ((body.len() + 8) as u32).to_be_bytes().to_vec();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(body);
+        out
+    }
+
+    fn ispe(width: u32, height: u32) -> Vec<u8> {
+        let mut body = vec![0; 4];
+        body.extend_from_slice(&width.to_be_bytes());
+        body.extend_from_slice(&height.to_be_bytes());
+        make_box(b"ispe", &body)
+    }
+
+    #[test]
+    fn test_format_from_extension() {
+        assert_eq!(ImageFormat::from_extension("PNG"), Some(ImageFormat::Png));
+        assert_eq!(ImageFormat::from_extension("jpeg"), Some(ImageFormat::Jpeg));
+        assert_eq!(ImageFormat::from_extension("webp"), Some(ImageFormat::WebP));
+        assert_eq!(ImageFormat::from_extension("heif"), Some(ImageFormat::Heic));
+        assert_eq!(ImageFormat::from_extension("gif"), None);
+        assert!(ImageFormat::Jpeg.is_store_compatible());
+        assert!(!ImageFormat::WebP.is_store_compatible());
+    }
+
+    #[test]
+    fn test_webp_dimensions() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("screenshot.webp");
+        image::RgbaImage::new(12, 7).save(&path).unwrap();
+
+        let dims = read_dimensions(&path).unwrap();
+        assert_eq!(dims, Dimensions::new(12, 7));
+    }
+
+    #[test]
+    fn test_heic_dimensions_from_container() {
+        let mut ftyp = b"heic".to_vec();
+        ftyp.extend_from_slice(&[0, 0, 0, 0]);
+        ftyp.extend_from_slice(b"mif1heic");
+
+        let ipco = make_box(b"ipco", &[ispe(320, 240), ispe(1290, 2796)].concat());
+        let iprp = make_box(b"iprp", &ipco);
+        let mut meta_body = vec![0; 4];
+        meta_body.extend_from_slice(&make_box(b"hdlr", &[0; 24]));
+        meta_body.extend_from_slice(&iprp);
+
+        let data = [make_box(b"ftyp", &ftyp), make_box(b"meta", &meta_body)].concat();
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("screenshot.heic");
+        std::fs::write(&path, data).unwrap();
+
+        let dims = read_dimensions(&path).unwrap();
+        assert_eq!(dims, Dimensions::new(1290, 2796));
+    }
+
+    #[test]
+    fn test_heic_without_ispe_is_error() {
+        let mut ftyp = b"heic".to_vec();
+        ftyp.extend_from_slice(&[0, 0, 0, 0]);
+        let data = [make_box(b"ftyp", &ftyp), make_box(b"meta", &[0; 4])].concat();
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.heic");
+        std::fs::write(&path, data).unwrap();
+
+        assert!(read_dimensions(&path).is_err());
+    }
+}
@@ -22,11 +22,14 @@
 mod apple;
 mod common;
 mod google_play;
+mod image_format;
 pub mod screenshots;
+mod similarity;
 
 pub use apple::{validate_localized_screenshots, AppleValidator};
 pub use common::*;
 pub use google_play::{validate_localized_google_play_screenshots, GooglePlayValidator};
+pub use image_format::ImageFormat;
 pub use screenshots::{
     // Helper functions
     get_apple_valid_dimensions,
@@ -36,6 +39,7 @@ pub use screenshots::{
     validate_feature_graphic_file,
     validate_google_play_screenshot_file,
     validate_screenshot_directory,
+    validate_screenshot_order,
     // Apple dimension constants
     APPLE_DEVICE_TYPES,
     APPLE_IPAD_10_5_DIMS,
@@ -57,6 +61,10 @@ pub use screenshots::{
     GOOGLE_PLAY_TABLET_7_DIMS,
     GOOGLE_PLAY_TV_DIMS,
 };
+pub use similarity::{
+    find_similar_screenshots, validate_screenshot_duplicates, ImageHash,
+    DEFAULT_SIMILARITY_THRESHOLD,
+};
 
 /// Validation issue severity.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -7,9 +7,10 @@ use std::path::Path;
 
 use tracing::debug;
 
-use crate::types::common::{Dimensions, Platform};
+use crate::types::common::{self, Dimensions, MediaAsset, OrderingIssue, Platform};
+use crate::validation::image_format::{self, ImageFormat};
 use crate::validation::{Severity, ValidationIssue, ValidationResult};
-use crate::{MetadataError, Result};
+use crate::Result;
 
 // =============================================================================
 // Apple Screenshot Dimension Constants
@@ -94,8 +95,9 @@ pub const GOOGLE_PLAY_TV_DIMS: &[(u32, u32)] = &[(1920, 1080), (3840, 2160)];
 
 /// Read dimensions from an image file.
 ///
-/// Uses `image::image_dimensions()` which is fast and doesn't load the full image
-/// into memory - it only reads the header to determine dimensions.
+/// Only the header is read, not the full image. PNG, JPEG and WebP are read
+/// with `image::image_dimensions()`; HEIC/HEIF dimensions come from the
+/// container's image spatial extent property.
 ///
 /// # Arguments
 ///
@@ -115,14 +117,19 @@ pub const GOOGLE_PLAY_TV_DIMS: &[(u32, u32)] = &[(1920, 1080), (3840, 2160)];
 /// println!("Image is {}x{}", dims.width, dims.height);
 /// ```
 pub fn read_image_dimensions(path: &Path) -> Result<Dimensions> {
-    let (width, height) = image::image_dimensions(path).map_err(|e| {
-        MetadataError::Io(std::io::Error::new(
-            std::io::ErrorKind::InvalidData,
-            format!("Failed to read image dimensions from {:?}: {}", path, e),
-        ))
-    })?;
-
-    Ok(Dimensions { width, height })
+    image_format::read_dimensions(path)
+}
+
+/// Warn when a file is in a format the store won't accept without conversion.
+fn check_store_format(result: &mut ValidationResult, field: &str, path: &Path, store: &str) {
+    if let Some(format) = ImageFormat::from_path(path).filter(|f| !f.is_store_compatible()) {
+        result.add(ValidationIssue::with_suggestion(
+            Severity::Warning,
+            field,
+            format!("{} does not accept {} images", store, format),
+            "Convert the image to PNG or JPEG before uploading",
+        ));
+    }
 }
 
 /// Get valid dimensions for an Apple device type.
@@ -229,6 +236,8 @@ pub fn validate_apple_screenshot_file(path: &Path, device_type: &str) -> Validat
         }
     };
 
+    check_store_format(&mut result, &field, path, "the App Store");
+
     // Get valid dimensions for device type
     let valid_dims = match get_apple_valid_dimensions(device_type) {
         Some(dims) => dims,
@@ -314,6 +323,8 @@ pub fn validate_google_play_screenshot_file(path: &Path, device_type: &str) -> V
         }
     };
 
+    check_store_format(&mut result, &field, path, "Google Play");
+
     // Check min/max bounds first (Google Play requirement)
     if dimensions.width < GOOGLE_PLAY_SCREENSHOT_MIN
         || dimensions.height < GOOGLE_PLAY_SCREENSHOT_MIN
@@ -416,6 +427,8 @@ pub fn validate_feature_graphic_file(path: &Path) -> ValidationResult {
         }
     };
 
+    check_store_format(&mut result, &field, path, "Google Play");
+
     // Feature graphic must be exactly 1024x500
     let (required_width, required_height) = GOOGLE_PLAY_FEATURE_GRAPHIC_DIMS;
     if dimensions.width != required_width || dimensions.height != required_height {
@@ -501,6 +514,7 @@ pub async fn validate_screenshot_directory(
 
     // Count valid images
     let mut image_count = 0;
+    let mut images = Vec::new();
 
     // Validate each file
     for entry in entries_vec {
@@ -511,39 +525,38 @@ pub async fn validate_screenshot_directory(
             continue;
         }
 
-        // Check if it's an image file by extension
-        let extension = path
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| e.to_lowercase());
-
-        match extension.as_deref() {
-            Some("png") | Some("jpg") | Some("jpeg") => {
-                image_count += 1;
-                let file_result = match platform {
-                    Platform::Apple => validate_apple_screenshot_file(&path, device_type),
-                    Platform::GooglePlay => {
-                        validate_google_play_screenshot_file(&path, device_type)
-                    }
-                    // Package registries don't use screenshot validation
-                    Platform::Npm | Platform::Crates | Platform::PyPI => ValidationResult::new(),
-                };
-                result.merge(file_result);
-            }
-            _ => {
-                // Skip non-image files silently
-            }
+        // Check if it's an image file by extension, skipping others silently
+        if ImageFormat::from_path(&path).is_none() {
+            continue;
         }
+
+        image_count += 1;
+        images.push(MediaAsset::new(path.clone(), crate::AssetType::Screenshot));
+        let file_result = match platform {
+            Platform::Apple => validate_apple_screenshot_file(&path, device_type),
+            Platform::GooglePlay => validate_google_play_screenshot_file(&path, device_type),
+            // Package registries don't use screenshot validation
+            Platform::Npm | Platform::Crates | Platform::PyPI => ValidationResult::new(),
+        };
+        result.merge(file_result);
     }
 
     // Warn if no images found
     if image_count == 0 {
         result.add(ValidationIssue::warning(
             &field,
-            "No image files (PNG, JPG) found in directory",
+            "No image files (PNG, JPG, WebP, HEIC) found in directory",
         ));
     }
 
+    validate_screenshot_order(&images, &field, &mut result);
+    super::similarity::validate_screenshot_duplicates(
+        &images,
+        super::similarity::DEFAULT_SIMILARITY_THRESHOLD,
+        &field,
+        &mut result,
+    );
+
     // Check screenshot count requirements
     match platform {
         Platform::Apple => {
@@ -575,6 +588,33 @@ pub async fn validate_screenshot_directory(
     result
 }
 
+/// Warns about screenshots whose display order is ambiguous.
+///
+/// Stores show screenshots in upload order, which follows each screenshot's
+/// display order (explicit or from a `01.png`-style file name prefix).
+/// Screenshots without an order, positions claimed twice and gaps in the
+/// numbering are reported as warnings on `field`.
+pub fn validate_screenshot_order(
+    screenshots: &[MediaAsset],
+    field: &str,
+    result: &mut ValidationResult,
+) {
+    for issue in common::check_display_order(screenshots) {
+        let suggestion = match issue {
+            OrderingIssue::Unordered(_) => {
+                "Prefix the file name with its position, e.g. 01_home.png"
+            }
+            OrderingIssue::Duplicate { .. } => "Give each screenshot a unique position",
+            OrderingIssue::Gap(_) => "Renumber the screenshots so positions are consecutive",
+        };
+        result.add_warning(
+            field,
+            &format!("Ambiguous screenshot order: {}", issue),
+            Some(suggestion),
+        );
+    }
+}
+
 /// List of supported Apple device types for screenshots.
 pub const APPLE_DEVICE_TYPES: &[&str] = &[
     "iphone_6_5",
@@ -709,4 +749,23 @@ mod tests {
             .iter()
             .any(|e| e.message.contains("not a directory")));
     }
+
+    #[test]
+    fn test_webp_screenshot_warns_about_conversion() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("screenshot.webp");
+        image::RgbaImage::new(1080, 1920).save(&path).unwrap();
+
+        assert_eq!(
+            read_image_dimensions(&path).unwrap(),
+            Dimensions::new(1080, 1920)
+        );
+
+        let result = validate_google_play_screenshot_file(&path, "phone");
+        assert!(result.is_valid());
+        assert!(result
+            .warnings()
+            .iter()
+            .any(|w| w.message.contains("does not accept WebP")));
+    }
 }
@@ -0,0 +1,254 @@
+//! Near-duplicate screenshot detection.
+//!
+//! Screenshots are compared by a perceptual difference hash (dHash): the
+//! image is shrunk to 9x8 grayscale pixels and each bit records whether a
+//! pixel is brighter than its right neighbour. Re-encoding or recompressing
+//! an image barely changes the hash, so the number of differing bits
+//! (the Hamming distance) tells images with the same content apart from
+//! genuinely different ones. Hashes are cached per file and reused until
+//! the file changes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+use image::imageops::FilterType;
+use image::DynamicImage;
+use tracing::debug;
+
+use crate::types::common::MediaAsset;
+use crate::validation::image_format::invalid_data;
+use crate::validation::ValidationResult;
+use crate::Result;
+
+/// Hamming distance at or below which two screenshots count as duplicates.
+pub const DEFAULT_SIMILARITY_THRESHOLD: u32 = 5;
+
+/// File size, modification time and hash of a cached image.
+type HashEntry = (u64, SystemTime, ImageHash);
+
+/// Perceptual hash of an image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ImageHash(u64);
+
+impl ImageHash {
+    /// Hashes a decoded image.
+    pub fn from_image(image: &DynamicImage) -> Self {
+        let small = image.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+        let mut bits = 0u64;
+        for y in 0..8 {
+            for x in 0..8 {
+                bits <<= 1;
+                if small.get_pixel(x, y)[0] > small.get_pixel(x + 1, y)[0] {
+                    bits |= 1;
+                }
+            }
+        }
+        Self(bits)
+    }
+
+    /// Reads and hashes the image at `path`.
+    pub fn from_path(path: &Path) -> Result<Self> {
+        let image = image::open(path)
+            .map_err(|e| invalid_data(format!("Failed to decode image {:?}: {}", path, e)))?;
+        Ok(Self::from_image(&image))
+    }
+
+    /// Like [`Self::from_path`], reusing the hash from an earlier call while
+    /// the file's size and modification time are unchanged.
+    pub fn cached(path: &Path) -> Result<Self> {
+        static CACHE: OnceLock<Mutex<HashMap<PathBuf, HashEntry>>> = OnceLock::new();
+
+        let metadata = std::fs::metadata(path)?;
+        let stamp = (metadata.len(), metadata.modified()?);
+        let cache = CACHE.get_or_init(Default::default);
+        let lock = || cache.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&(len, modified, hash)) = lock().get(path) {
+            if (len, modified) == stamp {
+                return Ok(hash);
+            }
+        }
+
+        let hash = Self::from_path(path)?;
+        lock().insert(path.to_path_buf(), (stamp.0, stamp.1, hash));
+        Ok(hash)
+    }
+
+    /// Number of bits that differ between the two hashes, from 0 to 64.
+    pub fn distance(&self, other: &ImageHash) -> u32 {
+        (self.0 ^ other.0).count_ones()
+    }
+}
+
+/// Groups images whose hashes are within `threshold` of each other.
+///
+/// Each image joins the first group whose first image is close enough, so
+/// groups keep the order of `paths`. Only groups with more than one image
+/// are returned. Images that can't be decoded are skipped; the format and
+/// dimension checks report those. Hashes come from [`ImageHash::cached`].
+pub fn find_similar_screenshots<P: AsRef<Path>>(paths: &[P], threshold: u32) -> Vec<Vec<PathBuf>> {
+    let mut groups: Vec<(ImageHash, Vec<PathBuf>)> = Vec::new();
+    for path in paths {
+        let path = path.as_ref();
+        let hash = match ImageHash::cached(path) {
+            Ok(hash) => hash,
+            Err(e) => {
+                debug!(path = %path.display(), error = %e, "skipping screenshot in duplicate check");
+                continue;
+            }
+        };
+        match groups
+            .iter_mut()
+            .find(|(first, _)| first.distance(&hash) <= threshold)
+        {
+            Some((_, members)) => members.push(path.to_path_buf()),
+            None => groups.push((hash, vec![path.to_path_buf()])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(_, members)| members)
+        .filter(|members| members.len() > 1)
+        .collect()
+}
+
+/// Warns about screenshots that look like duplicates of each other.
+///
+/// Screenshots within `threshold` bits are reported as one warning per
+/// group on `field`.
+pub fn validate_screenshot_duplicates(
+    screenshots: &[MediaAsset],
+    threshold: u32,
+    field: &str,
+    result: &mut ValidationResult,
+) {
+    let paths: Vec<&Path> = screenshots
+        .iter()
+        .map(|s| s.path.as_path())
+        .filter(|path| path.is_file())
+        .collect();
+    if paths.len() < 2 {
+        return;
+    }
+
+    for group in find_similar_screenshots(&paths, threshold) {
+        let names: Vec<String> = group.iter().map(|p| p.display().to_string()).collect();
+        result.add_warning(
+            field,
+            &format!("Screenshots look like duplicates: {}", names.join(", ")),
+            Some("Remove the redundant copies or replace them with distinct screens"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::common::AssetType;
+    use image::codecs::jpeg::JpegEncoder;
+    use image::{Rgb, RgbImage};
+    use tempfile::TempDir;
+
+    /// A screen-like image: a header bar, a card and a vertical gradient
+    fn screen(accent: Rgb<u8>, card_top: u32) -> RgbImage {
+        RgbImage::from_fn(360, 640, |x, y| {
+            if y < 80 {
+                accent
+            } else if (card_top..card_top + 160).contains(&y) && (24..336).contains(&x) {
+                Rgb([240, 240, 240])
+            } else {
+                let shade = (y * 255 / 640) as u8;
+                Rgb([shade, shade / 2, 255 - shade])
+            }
+        })
+    }
+
+    fn save_jpeg(image: &RgbImage, path: &Path, quality: u8) {
+        let file = std::fs::File::create(path).unwrap();
+        JpegEncoder::new_with_quality(file, quality)
+            .encode_image(image)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_recompressed_images_are_duplicates() {
+        let temp = TempDir::new().unwrap();
+        let home = screen(Rgb([20, 120, 220]), 120);
+        let settings = screen(Rgb([220, 60, 40]), 400);
+
+        let paths = [
+            temp.path().join("01_home.jpg"),
+            temp.path().join("02_settings.jpg"),
+            temp.path().join("03_home_copy.jpg"),
+        ];
+        save_jpeg(&home, &paths[0], 95);
+        save_jpeg(&settings, &paths[1], 95);
+        save_jpeg(&home, &paths[2], 40);
+
+        let hashes: Vec<ImageHash> = paths
+            .iter()
+            .map(|p| ImageHash::from_path(p).unwrap())
+            .collect();
+        assert!(hashes[0].distance(&hashes[2]) <= DEFAULT_SIMILARITY_THRESHOLD);
+        assert!(hashes[0].distance(&hashes[1]) > DEFAULT_SIMILARITY_THRESHOLD);
+
+        assert_eq!(
+            find_similar_screenshots(&paths, DEFAULT_SIMILARITY_THRESHOLD),
+            vec![vec![paths[0].clone(), paths[2].clone()]]
+        );
+    }
+
+    #[test]
+    fn test_cached_hash_follows_file_changes() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("01.jpg");
+        let home = screen(Rgb([20, 120, 220]), 120);
+        save_jpeg(&home, &path, 90);
+        let first = ImageHash::cached(&path).unwrap();
+        assert_eq!(ImageHash::cached(&path).unwrap(), first);
+
+        // A different screen with a different size invalidates the entry
+        let settings = RgbImage::from_fn(360, 640, |x, _| Rgb([(x % 256) as u8, 0, 0]));
+        save_jpeg(&settings, &path, 50);
+        assert_eq!(
+            ImageHash::cached(&path).unwrap(),
+            ImageHash::from_path(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_duplicate_warning() {
+        let temp = TempDir::new().unwrap();
+        let home = screen(Rgb([20, 120, 220]), 120);
+        let mut screenshots = Vec::new();
+        for (name, quality) in [("01.jpg", 90), ("02.jpg", 60)] {
+            let path = temp.path().join(name);
+            save_jpeg(&home, &path, quality);
+            screenshots.push(MediaAsset::new(path, AssetType::Screenshot));
+        }
+        screenshots.push(MediaAsset::new(
+            temp.path().join("missing.png"),
+            AssetType::Screenshot,
+        ));
+
+        let mut result = ValidationResult::new();
+        validate_screenshot_duplicates(
+            &screenshots,
+            DEFAULT_SIMILARITY_THRESHOLD,
+            "screenshots.iphone_6_5",
+            &mut result,
+        );
+        let warnings = result.warnings();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].field, "screenshots.iphone_6_5");
+        assert!(warnings[0].message.contains("01.jpg"));
+        assert!(warnings[0].message.contains("02.jpg"));
+
+        // A threshold of zero only matches identical hashes
+        let mut strict = ValidationResult::new();
+        validate_screenshot_duplicates(&screenshots[..1], 0, "screenshots", &mut strict);
+        assert!(strict.warnings().is_empty());
+    }
+}
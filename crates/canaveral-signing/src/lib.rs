@@ -18,6 +18,7 @@ pub mod providers;
 pub mod registry;
 pub mod sync;
 pub mod team;
+pub mod verification;
 
 pub use error::{Result, SigningError};
 pub use identity::{SigningIdentity, SigningIdentityType};
@@ -34,4 +35,6 @@ pub use providers::windows::WindowsProvider;
 
 pub use profiles::{ProfileManager, ProvisioningProfile};
 pub use providers::android::AndroidProvider;
+pub use providers::{provider_for_artifact, ProviderType};
 pub use registry::SigningProviderRegistry;
+pub use verification::{ReportFormat, VerificationReport};
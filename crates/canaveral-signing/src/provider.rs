@@ -155,6 +155,10 @@ pub struct SignatureInfo {
 
     /// Detailed verification output
     pub details: Option<String>,
+
+    /// Error that stopped the artifact from being verified
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Information about the signer of an artifact
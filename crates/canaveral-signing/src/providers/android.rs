@@ -646,6 +646,7 @@ impl SigningProvider for AndroidProvider {
             algorithm,
             warnings: vec![],
             details: Some(combined_output),
+            error: None,
         })
     }
 
@@ -365,6 +365,7 @@ impl SigningProvider for GpgProvider {
             algorithm: Some("GPG".to_string()),
             warnings: vec![],
             details: Some(stderr),
+            error: None,
         })
     }
 
@@ -410,6 +410,7 @@ impl SigningProvider for MacOSProvider {
             algorithm: None,
             warnings: vec![],
             details,
+            error: None,
         })
     }
 
@@ -10,6 +10,11 @@ pub mod windows;
 
 pub mod android;
 
+use std::io::Read;
+use std::path::Path;
+
+use tracing::debug;
+
 use crate::error::{Result, SigningError};
 use crate::provider::SigningProvider;
 
@@ -54,6 +59,75 @@ impl std::str::FromStr for ProviderType {
     }
 }
 
+/// Extensions signed with macOS codesign/productsign
+const MACOS_EXTENSIONS: &[&str] = &[
+    "app",
+    "framework",
+    "dylib",
+    "bundle",
+    "kext",
+    "xpc",
+    "pkg",
+    "dmg",
+];
+
+/// Extensions signed with Windows signtool
+const WINDOWS_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "sys", "msi", "msix", "appx", "cab", "cat", "ocx",
+];
+
+/// Extensions signed with apksigner
+const ANDROID_EXTENSIONS: &[&str] = &["apk", "aab"];
+
+impl ProviderType {
+    /// Pick the provider type that should sign the given artifact
+    ///
+    /// Known extensions win; extensionless files are classified by their
+    /// executable header (Mach-O or PE). Everything else falls back to GPG.
+    pub fn for_artifact(path: &Path) -> Self {
+        let ext = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+
+        match ext.as_deref() {
+            Some(ext) if MACOS_EXTENSIONS.contains(&ext) => Self::MacOS,
+            Some(ext) if WINDOWS_EXTENSIONS.contains(&ext) => Self::Windows,
+            Some(ext) if ANDROID_EXTENSIONS.contains(&ext) => Self::Android,
+            Some(_) => Self::Gpg,
+            None => Self::from_header(path).unwrap_or(Self::Gpg),
+        }
+    }
+
+    /// Classify an executable by its magic bytes
+    fn from_header(path: &Path) -> Option<Self> {
+        let mut magic = [0u8; 4];
+        std::fs::File::open(path)
+            .ok()?
+            .read_exact(&mut magic)
+            .ok()?;
+
+        match magic {
+            // Mach-O (32/64-bit, either endianness) and universal binaries
+            [0xfe, 0xed, 0xfa, 0xce | 0xcf]
+            | [0xce | 0xcf, 0xfa, 0xed, 0xfe]
+            | [0xca, 0xfe, 0xba, 0xbe] => Some(Self::MacOS),
+            [b'M', b'Z', ..] => Some(Self::Windows),
+            _ => None,
+        }
+    }
+}
+
+/// Create the signing provider appropriate for an artifact
+///
+/// Returns [`SigningError::UnsupportedPlatform`] when the artifact needs a
+/// platform-specific provider that is not available on this OS.
+pub fn provider_for_artifact(path: &Path) -> Result<Box<dyn SigningProvider>> {
+    let provider_type = ProviderType::for_artifact(path);
+    debug!(path = %path.display(), provider = %provider_type, "Selected signing provider for artifact");
+    create_provider(provider_type)
+}
+
 /// Create a signing provider for the given type
 pub fn create_provider(provider_type: ProviderType) -> Result<Box<dyn SigningProvider>> {
     match provider_type {
@@ -96,3 +170,64 @@ pub fn default_provider() -> Result<Box<dyn SigningProvider>> {
         create_provider(ProviderType::Gpg)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_artifact_by_extension() {
+        let cases = [
+            ("MyApp.app", ProviderType::MacOS),
+            ("MyApp.dmg", ProviderType::MacOS),
+            ("installer.pkg", ProviderType::MacOS),
+            ("app-release.apk", ProviderType::Android),
+            ("app-release.aab", ProviderType::Android),
+            ("Setup.msix", ProviderType::Windows),
+            ("setup.EXE", ProviderType::Windows),
+            ("release.tar.gz", ProviderType::Gpg),
+            ("checksums.txt", ProviderType::Gpg),
+        ];
+
+        for (file, expected) in cases {
+            assert_eq!(
+                ProviderType::for_artifact(Path::new(file)),
+                expected,
+                "{}",
+                file
+            );
+        }
+    }
+
+    #[test]
+    fn test_for_artifact_by_header() {
+        let temp = tempfile::TempDir::new().unwrap();
+
+        let macho = temp.path().join("mytool");
+        std::fs::write(&macho, [0xcf, 0xfa, 0xed, 0xfe, 0x07, 0x00]).unwrap();
+        assert_eq!(ProviderType::for_artifact(&macho), ProviderType::MacOS);
+
+        let pe = temp.path().join("mytool-win");
+        std::fs::write(&pe, b"MZ\x90\x00").unwrap();
+        assert_eq!(ProviderType::for_artifact(&pe), ProviderType::Windows);
+
+        let script = temp.path().join("install");
+        std::fs::write(&script, b"#!/bin/sh\n").unwrap();
+        assert_eq!(ProviderType::for_artifact(&script), ProviderType::Gpg);
+    }
+
+    #[test]
+    fn test_provider_for_artifact() {
+        let provider = provider_for_artifact(Path::new("app-release.apk")).unwrap();
+        assert_eq!(provider.name(), "android");
+
+        let provider = provider_for_artifact(Path::new("release.tar.gz")).unwrap();
+        assert_eq!(provider.name(), "gpg");
+
+        #[cfg(not(target_os = "macos"))]
+        assert!(matches!(
+            provider_for_artifact(Path::new("MyApp.dmg")),
+            Err(SigningError::UnsupportedPlatform { .. })
+        ));
+    }
+}
@@ -381,6 +381,7 @@ impl SigningProvider for WindowsProvider {
             algorithm: Some("Authenticode".to_string()),
             warnings: vec![],
             details: Some(combined),
+            error: None,
         })
     }
 
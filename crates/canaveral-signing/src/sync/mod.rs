@@ -4,17 +4,22 @@
 //! and provisioning profiles across a team, similar to fastlane match.
 
 pub mod registry;
+mod rotation;
 pub mod storage;
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use serde::{Deserialize, Serialize};
+use tracing::info;
 
 use crate::error::{Result, SigningError};
 use crate::team::{decrypt_data, encrypt_data, generate_keypair, KeyPair};
 
 pub use registry::{StorageBackendEntry, StorageBackendRegistry};
+pub use rotation::{RotationProgress, RotationReport};
+#[cfg(any(test, feature = "test-util"))]
+pub use storage::InMemoryStorage;
 pub use storage::{GitStorage, S3Storage, StorageBackend, SyncStorage};
 
 /// Certificate type for iOS
@@ -168,6 +173,65 @@ impl SyncManifest {
     }
 }
 
+/// Certificates and profiles removed by a nuke
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NukePlan {
+    /// Certificates to remove (only when nuking everything)
+    pub certificates: Vec<StoredCertificate>,
+
+    /// Profiles to remove
+    pub profiles: Vec<StoredProfile>,
+}
+
+impl NukePlan {
+    /// Collect the items a nuke of `profile_type` (or everything) removes
+    pub fn from_manifest(manifest: &SyncManifest, profile_type: Option<ProfileType>) -> Self {
+        let mut certificates: Vec<StoredCertificate> = match profile_type {
+            Some(_) => Vec::new(),
+            None => manifest.certificates.values().flatten().cloned().collect(),
+        };
+        let mut profiles: Vec<StoredProfile> = manifest
+            .profiles
+            .values()
+            .flat_map(|profiles| profiles.values())
+            .filter(|p| profile_type.map_or(true, |pt| p.profile_type == pt))
+            .cloned()
+            .collect();
+
+        certificates.sort_by(|a, b| a.path.cmp(&b.path));
+        profiles.sort_by(|a, b| a.path.cmp(&b.path));
+        Self {
+            certificates,
+            profiles,
+        }
+    }
+
+    /// Whether nothing would be removed
+    pub fn is_empty(&self) -> bool {
+        self.certificates.is_empty() && self.profiles.is_empty()
+    }
+
+    /// Storage paths of the removed items
+    pub fn storage_paths(&self) -> Vec<&str> {
+        self.certificates
+            .iter()
+            .map(|c| c.path.as_str())
+            .chain(self.profiles.iter().map(|p| p.path.as_str()))
+            .collect()
+    }
+
+    /// One-line summary, e.g. `2 certificates and 3 profiles`
+    pub fn summary(&self) -> String {
+        let plural =
+            |n: usize, noun: &str| format!("{} {}{}", n, noun, if n == 1 { "" } else { "s" });
+        format!(
+            "{} and {}",
+            plural(self.certificates.len(), "certificate"),
+            plural(self.profiles.len(), "profile")
+        )
+    }
+}
+
 /// Match sync configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MatchConfig {
@@ -180,6 +244,10 @@ pub struct MatchConfig {
     /// Encryption key (public key for encrypting)
     pub encryption_key: Option<String>,
 
+    /// Public keys of every team member the storage is encrypted to
+    #[serde(default)]
+    pub recipients: Vec<String>,
+
     /// Read-only mode (don't modify storage)
     pub readonly: bool,
 
@@ -205,6 +273,7 @@ impl Default for MatchConfig {
             },
             team_id: String::new(),
             encryption_key: None,
+            recipients: Vec::new(),
             readonly: false,
             force: false,
             app_ids: Vec::new(),
@@ -262,6 +331,12 @@ impl MatchConfig {
         self
     }
 
+    /// Encrypt to these team member public keys
+    pub fn with_recipients(mut self, recipients: Vec<String>) -> Self {
+        self.recipients = recipients;
+        self
+    }
+
     /// Set profile types to sync
     pub fn with_profile_types(mut self, types: Vec<ProfileType>) -> Self {
         self.profile_types = types;
@@ -338,38 +413,80 @@ impl MatchSync {
         self
     }
 
+    /// Use a custom storage backend instead of the one from the config
+    pub fn with_storage(mut self, storage: Box<dyn StorageBackend>) -> Self {
+        self.storage = storage;
+        self
+    }
+
     /// Initialize a new match repository
     pub async fn init(&self) -> Result<()> {
         // Create manifest
         let manifest = SyncManifest::new(&self.config.team_id);
 
-        // Generate encryption keypair if not provided
-        let keypair = self.keypair.clone().unwrap_or_else(generate_keypair);
+        // Generate encryption keypair if neither a keypair nor recipients are provided
+        let recipients = match self.recipients() {
+            Ok(recipients) => recipients,
+            Err(_) => vec![generate_keypair().public_key],
+        };
 
         // Encrypt and store manifest
         let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| {
             SigningError::Configuration(format!("Failed to serialize manifest: {}", e))
         })?;
 
-        let encrypted = encrypt_data(
-            manifest_json.as_bytes(),
-            std::slice::from_ref(&keypair.public_key),
-        )
-        .map_err(|e| SigningError::Configuration(format!("Failed to encrypt manifest: {}", e)))?;
+        let encrypted = encrypt_data(manifest_json.as_bytes(), &recipients).map_err(|e| {
+            SigningError::Configuration(format!("Failed to encrypt manifest: {}", e))
+        })?;
 
         // Store manifest
         self.storage
             .write("manifest.enc", encrypted.as_bytes())
             .await?;
 
-        // Store public key
+        // Store recipient public keys, one per line
         self.storage
-            .write("encryption_key.pub", keypair.public_key.as_bytes())
+            .write("encryption_key.pub", recipients.join("\n").as_bytes())
             .await?;
 
         Ok(())
     }
 
+    /// Public keys that stored items are encrypted to
+    ///
+    /// Uses the configured team recipients, falling back to the public key of
+    /// the local keypair when none are configured.
+    fn recipients(&self) -> Result<Vec<String>> {
+        if !self.config.recipients.is_empty() {
+            return Ok(self.config.recipients.clone());
+        }
+
+        self.keypair
+            .as_ref()
+            .map(|k| vec![k.public_key.clone()])
+            .ok_or_else(|| SigningError::Configuration("No encryption key provided".to_string()))
+    }
+
+    /// Public keys new uploads are encrypted to
+    ///
+    /// The recipient list stored with the manifest wins over the config, so
+    /// uploads keep following a key rotation even with a stale config.
+    async fn upload_recipients(&self) -> Result<Vec<String>> {
+        if self.storage.exists(rotation::PUBLIC_KEY_PATH).await? {
+            let stored = self.storage.read(rotation::PUBLIC_KEY_PATH).await?;
+            let stored: Vec<String> = String::from_utf8_lossy(&stored)
+                .lines()
+                .map(str::trim)
+                .filter(|l| !l.is_empty())
+                .map(String::from)
+                .collect();
+            if !stored.is_empty() {
+                return Ok(stored);
+            }
+        }
+        self.recipients()
+    }
+
     /// Sync certificates and profiles from storage
     pub async fn sync(&self) -> Result<SyncManifest> {
         // Ensure cache directory exists
@@ -499,16 +616,12 @@ impl MatchSync {
             ));
         }
 
-        let keypair = self
-            .keypair
-            .as_ref()
-            .ok_or_else(|| SigningError::Configuration("No encryption key provided".to_string()))?;
+        let recipients = self.upload_recipients().await?;
 
         // Encrypt certificate
-        let encrypted =
-            encrypt_data(data, std::slice::from_ref(&keypair.public_key)).map_err(|e| {
-                SigningError::Configuration(format!("Failed to encrypt certificate: {}", e))
-            })?;
+        let encrypted = encrypt_data(data, &recipients).map_err(|e| {
+            SigningError::Configuration(format!("Failed to encrypt certificate: {}", e))
+        })?;
 
         // Write to storage
         self.storage
@@ -525,11 +638,10 @@ impl MatchSync {
             SigningError::Configuration(format!("Failed to serialize manifest: {}", e))
         })?;
 
-        let encrypted_manifest = encrypt_data(
-            manifest_json.as_bytes(),
-            std::slice::from_ref(&keypair.public_key),
-        )
-        .map_err(|e| SigningError::Configuration(format!("Failed to encrypt manifest: {}", e)))?;
+        let encrypted_manifest =
+            encrypt_data(manifest_json.as_bytes(), &recipients).map_err(|e| {
+                SigningError::Configuration(format!("Failed to encrypt manifest: {}", e))
+            })?;
 
         self.storage
             .write("manifest.enc", encrypted_manifest.as_bytes())
@@ -546,16 +658,12 @@ impl MatchSync {
             ));
         }
 
-        let keypair = self
-            .keypair
-            .as_ref()
-            .ok_or_else(|| SigningError::Configuration("No encryption key provided".to_string()))?;
+        let recipients = self.upload_recipients().await?;
 
         // Encrypt profile
-        let encrypted =
-            encrypt_data(data, std::slice::from_ref(&keypair.public_key)).map_err(|e| {
-                SigningError::Configuration(format!("Failed to encrypt profile: {}", e))
-            })?;
+        let encrypted = encrypt_data(data, &recipients).map_err(|e| {
+            SigningError::Configuration(format!("Failed to encrypt profile: {}", e))
+        })?;
 
         // Write to storage
         self.storage
@@ -572,11 +680,10 @@ impl MatchSync {
             SigningError::Configuration(format!("Failed to serialize manifest: {}", e))
         })?;
 
-        let encrypted_manifest = encrypt_data(
-            manifest_json.as_bytes(),
-            std::slice::from_ref(&keypair.public_key),
-        )
-        .map_err(|e| SigningError::Configuration(format!("Failed to encrypt manifest: {}", e)))?;
+        let encrypted_manifest =
+            encrypt_data(manifest_json.as_bytes(), &recipients).map_err(|e| {
+                SigningError::Configuration(format!("Failed to encrypt manifest: {}", e))
+            })?;
 
         self.storage
             .write("manifest.enc", encrypted_manifest.as_bytes())
@@ -585,20 +692,29 @@ impl MatchSync {
         Ok(())
     }
 
-    /// Remove all certificates and profiles (nuke)
-    pub async fn nuke(&self, profile_type: Option<ProfileType>) -> Result<()> {
-        if self.config.readonly {
+    /// Remove all certificates and profiles, or only profiles of `profile_type` (nuke)
+    ///
+    /// Returns what was removed. With `dry_run`, returns what would be
+    /// removed and leaves the manifest and storage untouched. Nothing is
+    /// written when no items match.
+    pub async fn nuke(&self, profile_type: Option<ProfileType>, dry_run: bool) -> Result<NukePlan> {
+        if self.config.readonly && !dry_run {
             return Err(SigningError::Configuration(
                 "Cannot nuke in readonly mode".to_string(),
             ));
         }
 
-        let keypair = self
-            .keypair
-            .as_ref()
-            .ok_or_else(|| SigningError::Configuration("No encryption key provided".to_string()))?;
-
         let mut manifest = self.read_manifest().await?;
+        let plan = NukePlan::from_manifest(&manifest, profile_type);
+        if dry_run {
+            return Ok(plan);
+        }
+        if plan.is_empty() {
+            info!("nothing to nuke");
+            return Ok(plan);
+        }
+
+        let recipients = self.upload_recipients().await?;
 
         if let Some(pt) = profile_type {
             // Remove only profiles of specified type
@@ -618,17 +734,17 @@ impl MatchSync {
             SigningError::Configuration(format!("Failed to serialize manifest: {}", e))
         })?;
 
-        let encrypted = encrypt_data(
-            manifest_json.as_bytes(),
-            std::slice::from_ref(&keypair.public_key),
-        )
-        .map_err(|e| SigningError::Configuration(format!("Failed to encrypt manifest: {}", e)))?;
+        let encrypted = encrypt_data(manifest_json.as_bytes(), &recipients).map_err(|e| {
+            SigningError::Configuration(format!("Failed to encrypt manifest: {}", e))
+        })?;
 
         self.storage
             .write("manifest.enc", encrypted.as_bytes())
             .await?;
 
-        Ok(())
+        info!(removed = %plan.summary(), "nuked match storage");
+
+        Ok(plan)
     }
 
     /// Get cache directory
@@ -657,4 +773,134 @@ mod tests {
         assert!(config.readonly);
         assert_eq!(config.app_ids, vec!["com.example.app"]);
     }
+
+    #[tokio::test]
+    async fn test_multiple_recipients_decrypt_independently() {
+        let storage = InMemoryStorage::new();
+        let alice = generate_keypair();
+        let bob = generate_keypair();
+        let recipients = vec![alice.public_key.clone(), bob.public_key.clone()];
+
+        let member = |keypair: &KeyPair| {
+            MatchSync::new(
+                MatchConfig::git("unused", "TEAM123").with_recipients(recipients.clone()),
+            )
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(keypair.clone())
+        };
+
+        let alice_sync = member(&alice);
+        alice_sync.init().await.unwrap();
+        alice_sync
+            .upload_certificate(
+                CertificateType::Development,
+                b"p12-bytes",
+                StoredCertificate {
+                    cert_type: CertificateType::Development,
+                    team_id: "TEAM123".to_string(),
+                    name: "Apple Development".to_string(),
+                    expires: "2030-01-01T00:00:00Z".to_string(),
+                    fingerprint: "ABCDEF".to_string(),
+                    path: "certs/development/ABCDEF.p12.enc".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+
+        let public_keys =
+            String::from_utf8(storage.read("encryption_key.pub").await.unwrap()).unwrap();
+        assert_eq!(public_keys.lines().collect::<Vec<_>>(), recipients);
+
+        let cert = String::from_utf8(
+            storage
+                .read("certs/development/ABCDEF.p12.enc")
+                .await
+                .unwrap(),
+        )
+        .unwrap();
+        for keypair in [&alice, &bob] {
+            assert_eq!(
+                decrypt_data(&cert, &keypair.private_key).unwrap(),
+                b"p12-bytes"
+            );
+
+            let manifest = member(keypair).read_manifest().await.unwrap();
+            assert!(manifest
+                .get_certificate(CertificateType::Development)
+                .is_some());
+        }
+
+        let outsider = generate_keypair();
+        assert!(member(&outsider).read_manifest().await.is_err());
+    }
+
+    fn profile(profile_type: ProfileType) -> StoredProfile {
+        StoredProfile {
+            profile_type,
+            team_id: "TEAM123".to_string(),
+            app_id: "com.example.app".to_string(),
+            name: format!("match {} com.example.app", profile_type),
+            uuid: format!("uuid-{}", profile_type),
+            expires: "2030-01-01T00:00:00Z".to_string(),
+            path: format!(
+                "profiles/{}/com.example.app.mobileprovision.enc",
+                profile_type
+            ),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_nuke_dry_run() {
+        let storage = InMemoryStorage::new();
+        let keypair = generate_keypair();
+        let config =
+            MatchConfig::git("unused", "TEAM123").with_recipients(vec![keypair.public_key.clone()]);
+        let sync = MatchSync::new(config.clone())
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(keypair.clone());
+
+        sync.init().await.unwrap();
+        for profile_type in [ProfileType::Development, ProfileType::AppStore] {
+            sync.upload_profile(b"profile-bytes", profile(profile_type))
+                .await
+                .unwrap();
+        }
+        let manifest_before = storage.read("manifest.enc").await.unwrap();
+
+        let plan = sync.nuke(Some(ProfileType::AppStore), true).await.unwrap();
+        assert!(plan.certificates.is_empty());
+        assert_eq!(
+            plan.storage_paths(),
+            ["profiles/appstore/com.example.app.mobileprovision.enc"]
+        );
+        assert_eq!(plan.summary(), "0 certificates and 1 profile");
+        assert_eq!(storage.read("manifest.enc").await.unwrap(), manifest_before);
+        assert!(storage.exists(plan.storage_paths()[0]).await.unwrap());
+
+        // Dry runs are allowed in readonly mode, real nukes are not
+        let readonly = MatchSync::new(config.readonly())
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(keypair.clone());
+        assert_eq!(readonly.nuke(None, true).await.unwrap().profiles.len(), 2);
+        assert!(readonly.nuke(None, false).await.is_err());
+
+        let removed = sync.nuke(Some(ProfileType::AppStore), false).await.unwrap();
+        assert_eq!(removed.storage_paths(), plan.storage_paths());
+        let manifest = sync.read_manifest().await.unwrap();
+        assert!(manifest
+            .get_profile("com.example.app", ProfileType::Development)
+            .is_some());
+        assert!(manifest
+            .get_profile("com.example.app", ProfileType::AppStore)
+            .is_none());
+
+        // Nothing left to match: the manifest isn't rewritten
+        let manifest_after = storage.read("manifest.enc").await.unwrap();
+        let removed = sync.nuke(Some(ProfileType::AppStore), false).await.unwrap();
+        assert!(removed.is_empty());
+        assert_eq!(storage.read("manifest.enc").await.unwrap(), manifest_after);
+    }
 }
@@ -0,0 +1,408 @@
+//! Key rotation for match storage
+//!
+//! When team membership changes, every stored item has to be re-encrypted to
+//! the new set of recipients so that removed members can no longer decrypt it.
+//!
+//! Rotation runs in two phases so that an interruption never leaves the
+//! store half encrypted to each set. Items are first re-encrypted into a
+//! staging area next to the originals; writing the staged recipient list
+//! marks the staging as complete. The staged copies are then moved over the
+//! originals, manifest and recipient list last. Running the rotation again
+//! with the same recipients after an interruption resumes the move instead
+//! of starting over.
+
+use tracing::{debug, info};
+
+use crate::error::{Result, SigningError};
+use crate::team::{decrypt_data, encrypt_data};
+
+use super::MatchSync;
+
+/// Storage path of the manifest
+const MANIFEST_PATH: &str = "manifest.enc";
+
+/// Storage path of the recipient public keys
+pub(super) const PUBLIC_KEY_PATH: &str = "encryption_key.pub";
+
+/// Prefix of the staging area for re-encrypted items
+const STAGING_PREFIX: &str = "rotation/";
+
+/// Staging path of `path`
+fn staged(path: &str) -> String {
+    format!("{}{}", STAGING_PREFIX, path)
+}
+
+/// Progress update emitted while rotating
+#[derive(Debug, Clone)]
+pub struct RotationProgress {
+    /// Number of items re-encrypted so far (including this one)
+    pub completed: usize,
+    /// Total number of items to re-encrypt
+    pub total: usize,
+    /// Storage path of the item that was just re-encrypted
+    pub path: String,
+}
+
+/// Summary of a completed rotation
+#[derive(Debug, Clone)]
+pub struct RotationReport {
+    /// Number of certificates re-encrypted
+    pub certificates: usize,
+    /// Number of profiles re-encrypted
+    pub profiles: usize,
+    /// Public keys the storage is now encrypted to
+    pub recipients: Vec<String>,
+}
+
+impl MatchSync {
+    /// Re-encrypt the manifest and every stored item to a new recipient set
+    ///
+    /// Items are decrypted with the current keypair and staged one at a
+    /// time, so memory use does not grow with the size of the store, then
+    /// swapped in with the manifest last. An interrupted rotation is resumed
+    /// by calling this again with the same recipients. Keys not in
+    /// `new_recipients` (including the current one, unless listed) lose
+    /// access once this returns. The new set is stored with the manifest and
+    /// later uploads encrypt to it.
+    pub async fn rotate(
+        &self,
+        new_recipients: &[String],
+        mut on_progress: impl FnMut(&RotationProgress),
+    ) -> Result<RotationReport> {
+        if self.config.readonly {
+            return Err(SigningError::Configuration(
+                "Cannot rotate keys in readonly mode".to_string(),
+            ));
+        }
+
+        if new_recipients.is_empty() {
+            return Err(SigningError::Configuration(
+                "Key rotation requires at least one recipient".to_string(),
+            ));
+        }
+
+        // The manifest is swapped last, so it still opens with the current
+        // key when resuming an interrupted rotation
+        let mut manifest = self.read_manifest().await?;
+
+        let cert_paths: Vec<String> = manifest
+            .certificates
+            .values()
+            .flatten()
+            .map(|c| c.path.clone())
+            .collect();
+        let profile_paths: Vec<String> = manifest
+            .profiles
+            .values()
+            .flat_map(|p| p.values())
+            .map(|p| p.path.clone())
+            .collect();
+
+        let total = cert_paths.len() + profile_paths.len();
+        let recipient_list = new_recipients.join("\n");
+        let staged_keys = staged(PUBLIC_KEY_PATH);
+        let resuming = self.storage.exists(&staged_keys).await?;
+        if resuming && self.storage.read(&staged_keys).await? != recipient_list.as_bytes() {
+            return Err(SigningError::Configuration(
+                "An interrupted rotation to other recipients is pending; run it again with the same recipients to finish it".to_string(),
+            ));
+        }
+        info!(
+            items = total,
+            recipients = new_recipients.len(),
+            resuming,
+            "Rotating match storage encryption"
+        );
+
+        if !resuming {
+            for (index, path) in cert_paths.iter().chain(&profile_paths).enumerate() {
+                self.reencrypt_item(path, new_recipients).await?;
+
+                let progress = RotationProgress {
+                    completed: index + 1,
+                    total,
+                    path: path.clone(),
+                };
+                debug!(path = %progress.path, completed = progress.completed, total, "Re-encrypted item");
+                on_progress(&progress);
+            }
+
+            manifest.last_sync = chrono::Utc::now().to_rfc3339();
+            let manifest_json = serde_json::to_string_pretty(&manifest).map_err(|e| {
+                SigningError::Configuration(format!("Failed to serialize manifest: {}", e))
+            })?;
+            let encrypted_manifest = encrypt_data(manifest_json.as_bytes(), new_recipients)
+                .map_err(|e| {
+                    SigningError::Configuration(format!("Failed to encrypt manifest: {}", e))
+                })?;
+
+            self.storage
+                .write(&staged(MANIFEST_PATH), encrypted_manifest.as_bytes())
+                .await?;
+            // Marks the staging as complete
+            self.storage
+                .write(&staged_keys, recipient_list.as_bytes())
+                .await?;
+        }
+
+        for path in cert_paths
+            .iter()
+            .chain(&profile_paths)
+            .map(String::as_str)
+            .chain([MANIFEST_PATH, PUBLIC_KEY_PATH])
+        {
+            self.swap_in(path).await?;
+        }
+
+        info!(items = total, "Match storage rotation complete");
+        Ok(RotationReport {
+            certificates: cert_paths.len(),
+            profiles: profile_paths.len(),
+            recipients: new_recipients.to_vec(),
+        })
+    }
+
+    /// Move the staged copy of `path` over the original
+    ///
+    /// Does nothing when the staged copy is gone, which means an earlier,
+    /// interrupted run already moved it.
+    async fn swap_in(&self, path: &str) -> Result<()> {
+        let staged = staged(path);
+        if !self.storage.exists(&staged).await? {
+            return Ok(());
+        }
+        let data = self.storage.read(&staged).await?;
+        self.storage.write(path, &data).await?;
+        self.storage.delete(&staged).await
+    }
+
+    /// Decrypt a single stored item and stage it for the new recipients
+    async fn reencrypt_item(&self, path: &str, recipients: &[String]) -> Result<()> {
+        let keypair = self
+            .keypair
+            .as_ref()
+            .ok_or_else(|| SigningError::Configuration("No encryption key provided".to_string()))?;
+
+        let encrypted = self.storage.read(path).await?;
+        let encrypted_str = String::from_utf8(encrypted).map_err(|e| {
+            SigningError::Configuration(format!("Invalid encoding for {}: {}", path, e))
+        })?;
+
+        let decrypted = decrypt_data(&encrypted_str, &keypair.private_key).map_err(|e| {
+            SigningError::Configuration(format!("Failed to decrypt {}: {}", path, e))
+        })?;
+
+        let reencrypted = encrypt_data(&decrypted, recipients).map_err(|e| {
+            SigningError::Configuration(format!("Failed to encrypt {}: {}", path, e))
+        })?;
+
+        self.storage
+            .write(&staged(path), reencrypted.as_bytes())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::sync::InMemoryStorage;
+    use crate::sync::{
+        CertificateType, MatchConfig, ProfileType, StorageBackend, StoredCertificate, StoredProfile,
+    };
+    use crate::team::generate_keypair;
+
+    fn certificate() -> StoredCertificate {
+        StoredCertificate {
+            cert_type: CertificateType::Distribution,
+            team_id: "TEAM123".to_string(),
+            name: "Apple Distribution".to_string(),
+            expires: "2030-01-01T00:00:00Z".to_string(),
+            fingerprint: "ABCDEF".to_string(),
+            path: "certs/distribution/ABCDEF.p12.enc".to_string(),
+        }
+    }
+
+    fn profile() -> StoredProfile {
+        StoredProfile {
+            profile_type: ProfileType::AppStore,
+            team_id: "TEAM123".to_string(),
+            app_id: "com.example.app".to_string(),
+            name: "App Store".to_string(),
+            uuid: "1234-5678".to_string(),
+            expires: "2030-01-01T00:00:00Z".to_string(),
+            path: "profiles/appstore/com.example.app.mobileprovision.enc".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotate_revokes_old_key() {
+        let storage = InMemoryStorage::new();
+        let old_key = generate_keypair();
+        let new_key = generate_keypair();
+
+        let sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(old_key.clone());
+
+        sync.init().await.unwrap();
+        sync.upload_certificate(CertificateType::Distribution, b"p12-bytes", certificate())
+            .await
+            .unwrap();
+        sync.upload_profile(b"profile-bytes", profile())
+            .await
+            .unwrap();
+
+        let mut updates = Vec::new();
+        let report = sync
+            .rotate(std::slice::from_ref(&new_key.public_key), |p| {
+                updates.push((p.completed, p.total))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(report.certificates, 1);
+        assert_eq!(report.profiles, 1);
+        assert_eq!(updates, vec![(1, 2), (2, 2)]);
+
+        let cert = String::from_utf8(storage.read(&certificate().path).await.unwrap()).unwrap();
+        assert!(decrypt_data(&cert, &old_key.private_key).is_err());
+        assert_eq!(
+            decrypt_data(&cert, &new_key.private_key).unwrap(),
+            b"p12-bytes"
+        );
+
+        let old_sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(old_key);
+        assert!(old_sync.read_manifest().await.is_err());
+
+        let new_sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(new_key.clone());
+        let manifest = new_sync.read_manifest().await.unwrap();
+        assert!(manifest
+            .get_profile("com.example.app", ProfileType::AppStore)
+            .is_some());
+
+        let public_keys = storage.read(PUBLIC_KEY_PATH).await.unwrap();
+        assert_eq!(public_keys, new_key.public_key.as_bytes());
+        assert!(storage.list(STAGING_PREFIX).await.unwrap().is_empty());
+
+        // Uploads follow the stored recipients, not the stale config
+        new_sync
+            .upload_profile(b"new-profile", profile())
+            .await
+            .unwrap();
+        let uploaded = String::from_utf8(storage.read(&profile().path).await.unwrap()).unwrap();
+        assert_eq!(
+            decrypt_data(&uploaded, &new_key.private_key).unwrap(),
+            b"new-profile"
+        );
+    }
+
+    /// Fails the first write of the manifest
+    struct InterruptedStorage {
+        inner: InMemoryStorage,
+        failed: std::sync::atomic::AtomicBool,
+    }
+
+    #[async_trait::async_trait]
+    impl StorageBackend for InterruptedStorage {
+        async fn sync(&self) -> Result<()> {
+            self.inner.sync().await
+        }
+
+        async fn read(&self, path: &str) -> Result<Vec<u8>> {
+            self.inner.read(path).await
+        }
+
+        async fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+            if path == MANIFEST_PATH && !self.failed.swap(true, std::sync::atomic::Ordering::SeqCst)
+            {
+                return Err(SigningError::Configuration("connection reset".to_string()));
+            }
+            self.inner.write(path, data).await
+        }
+
+        async fn delete(&self, path: &str) -> Result<()> {
+            self.inner.delete(path).await
+        }
+
+        async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+            self.inner.list(prefix).await
+        }
+
+        async fn exists(&self, path: &str) -> Result<bool> {
+            self.inner.exists(path).await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotate_resumes_after_interruption() {
+        let storage = InMemoryStorage::new();
+        let old_key = generate_keypair();
+        let new_key = generate_keypair();
+
+        let setup = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(old_key.clone());
+        setup.init().await.unwrap();
+        setup
+            .upload_certificate(CertificateType::Distribution, b"p12-bytes", certificate())
+            .await
+            .unwrap();
+
+        let sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(InterruptedStorage {
+                inner: storage.clone(),
+                failed: Default::default(),
+            }))
+            .with_keypair(old_key.clone());
+        let recipients = std::slice::from_ref(&new_key.public_key);
+        assert!(sync.rotate(recipients, |_| {}).await.is_err());
+
+        // Interrupted mid-swap: the manifest and key list are still the old ones
+        assert!(sync.read_manifest().await.is_ok());
+        assert!(storage.exists(&staged(PUBLIC_KEY_PATH)).await.unwrap());
+
+        // Resuming with other recipients would mix key sets
+        let other = generate_keypair();
+        assert!(sync
+            .rotate(std::slice::from_ref(&other.public_key), |_| {})
+            .await
+            .is_err());
+
+        let mut updates = 0;
+        let report = sync.rotate(recipients, |_| updates += 1).await.unwrap();
+        assert_eq!(report.certificates, 1);
+        assert_eq!(updates, 0, "staged items are not re-encrypted again");
+        assert!(storage.list(STAGING_PREFIX).await.unwrap().is_empty());
+
+        let new_sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(storage.clone()))
+            .with_keypair(new_key.clone());
+        assert!(new_sync.read_manifest().await.is_ok());
+        let cert = String::from_utf8(storage.read(&certificate().path).await.unwrap()).unwrap();
+        assert_eq!(
+            decrypt_data(&cert, &new_key.private_key).unwrap(),
+            b"p12-bytes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotate_requires_recipients() {
+        let sync = MatchSync::new(MatchConfig::git("unused", "TEAM123"))
+            .unwrap()
+            .with_storage(Box::new(InMemoryStorage::new()))
+            .with_keypair(generate_keypair());
+
+        assert!(sync.rotate(&[], |_| {}).await.is_err());
+    }
+}
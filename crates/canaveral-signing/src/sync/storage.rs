@@ -1,6 +1,7 @@
 //! Storage backends for match sync
 //!
-//! Supports Git, S3, GCS, and Azure Blob storage backends.
+//! Supports Git, S3, GCS, and Azure Blob storage backends, plus an
+//! in-memory backend for tests (`test-util` feature).
 
 use std::path::PathBuf;
 use std::process::Command;
@@ -403,10 +404,95 @@ impl StorageBackend for S3Storage {
     }
 }
 
+/// In-memory storage backend for tests
+///
+/// Files live in a `HashMap`; clones share the same files, so several
+/// [`MatchSync`](super::MatchSync) instances can act as team members on one
+/// store.
+#[cfg(any(test, feature = "test-util"))]
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryStorage {
+    files: std::sync::Arc<std::sync::Mutex<std::collections::HashMap<String, Vec<u8>>>>,
+}
+
+#[cfg(any(test, feature = "test-util"))]
+impl InMemoryStorage {
+    /// Create an empty storage
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Paths of all stored files, sorted
+    pub fn paths(&self) -> Vec<String> {
+        let mut paths: Vec<String> = self.files().keys().cloned().collect();
+        paths.sort();
+        paths
+    }
+
+    fn files(&self) -> std::sync::MutexGuard<'_, std::collections::HashMap<String, Vec<u8>>> {
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(any(test, feature = "test-util"))]
+#[async_trait]
+impl StorageBackend for InMemoryStorage {
+    async fn sync(&self) -> Result<()> {
+        Ok(())
+    }
+
+    async fn read(&self, path: &str) -> Result<Vec<u8>> {
+        self.files().get(path).cloned().ok_or_else(|| {
+            SigningError::Configuration(format!("File not found in storage: {}", path))
+        })
+    }
+
+    async fn write(&self, path: &str, data: &[u8]) -> Result<()> {
+        self.files().insert(path.to_string(), data.to_vec());
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.files().remove(path);
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        Ok(self
+            .paths()
+            .into_iter()
+            .filter(|p| p.starts_with(prefix))
+            .collect())
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        Ok(self.files().contains_key(path))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_in_memory_storage() {
+        let storage = InMemoryStorage::new();
+        let shared = storage.clone();
+
+        storage.write("certs/dev.p12", b"cert").await.unwrap();
+        storage
+            .write("profiles/app.mobileprovision", b"profile")
+            .await
+            .unwrap();
+        assert_eq!(shared.read("certs/dev.p12").await.unwrap(), b"cert");
+        assert_eq!(shared.list("certs/").await.unwrap(), ["certs/dev.p12"]);
+
+        shared.delete("certs/dev.p12").await.unwrap();
+        assert!(!storage.exists("certs/dev.p12").await.unwrap());
+        assert!(storage.read("certs/dev.p12").await.is_err());
+        assert_eq!(storage.paths(), ["profiles/app.mobileprovision"]);
+    }
+
     #[test]
     fn test_git_storage_path() {
         let storage = GitStorage::new(
@@ -0,0 +1,268 @@
+//! Aggregated results of verifying several artifacts
+//!
+//! Each provider verifies one artifact at a time. A [`VerificationReport`]
+//! collects those results, across providers, into a single pass/fail that
+//! CI can gate on, rendered for people or as JSON.
+
+use serde::{Deserialize, Serialize};
+
+use crate::provider::{SignatureInfo, SignatureStatus};
+
+/// How to render a [`VerificationReport`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    /// Human-readable text
+    #[default]
+    Text,
+    /// Pretty-printed JSON
+    Json,
+}
+
+/// Verification results for a set of artifacts
+///
+/// The report passes when it holds at least one artifact and every
+/// artifact has a valid signature.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Whether every artifact verified
+    passed: bool,
+    /// Number of artifacts with a valid signature
+    verified: usize,
+    /// Number of artifacts that failed verification
+    failed: usize,
+    /// Per-artifact results, in the order they were added
+    artifacts: Vec<SignatureInfo>,
+}
+
+impl VerificationReport {
+    /// Create an empty report
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a report from per-artifact results
+    pub fn from_results(results: impl IntoIterator<Item = SignatureInfo>) -> Self {
+        results
+            .into_iter()
+            .fold(Self::new(), |report, info| report.with_result(info))
+    }
+
+    /// Add the result for one artifact
+    pub fn with_result(mut self, info: SignatureInfo) -> Self {
+        self.push(info);
+        self
+    }
+
+    /// Add the result for one artifact
+    pub fn push(&mut self, info: SignatureInfo) {
+        if info.status == SignatureStatus::Valid {
+            self.verified += 1;
+        } else {
+            self.failed += 1;
+        }
+        self.artifacts.push(info);
+        self.passed = self.failed == 0;
+    }
+
+    /// Whether every artifact verified
+    pub fn passed(&self) -> bool {
+        self.passed
+    }
+
+    /// Record an artifact that couldn't be verified at all
+    ///
+    /// It fails the report with an `Unknown` status and the error.
+    pub fn push_error(&mut self, path: impl Into<String>, error: impl std::fmt::Display) {
+        self.push(SignatureInfo {
+            path: path.into(),
+            status: SignatureStatus::Unknown,
+            signer: None,
+            signed_at: None,
+            timestamp_authority: None,
+            notarized: None,
+            stapled: None,
+            algorithm: None,
+            warnings: Vec::new(),
+            details: None,
+            error: Some(error.to_string()),
+        });
+    }
+
+    /// Per-artifact results
+    pub fn artifacts(&self) -> &[SignatureInfo] {
+        &self.artifacts
+    }
+
+    /// Artifacts that failed verification
+    pub fn failures(&self) -> impl Iterator<Item = &SignatureInfo> {
+        self.artifacts
+            .iter()
+            .filter(|info| info.status != SignatureStatus::Valid)
+    }
+
+    /// Render the report in `format`
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Text => self.render_text(),
+            ReportFormat::Json => self.render_json(),
+        }
+    }
+
+    fn render_text(&self) -> String {
+        let mut out = String::new();
+
+        if self.passed {
+            out.push_str(&format!("✓ Verified {} artifact(s)\n", self.verified));
+        } else if self.artifacts.is_empty() {
+            out.push_str("✗ No artifacts verified\n");
+        } else {
+            out.push_str(&format!(
+                "✗ {} of {} artifact(s) failed verification\n",
+                self.failed,
+                self.artifacts.len()
+            ));
+        }
+
+        for info in &self.artifacts {
+            let symbol = if info.status == SignatureStatus::Valid {
+                "✓"
+            } else {
+                "✗"
+            };
+            out.push_str(&format!("\n  {} {}: {}\n", symbol, info.path, info.status));
+            if let Some(signer) = &info.signer {
+                out.push_str(&format!("    Signer: {}", signer.common_name));
+                if let Some(team) = &signer.team_id {
+                    out.push_str(&format!(" ({})", team));
+                }
+                out.push('\n');
+            }
+            if let Some(signed_at) = info.signed_at {
+                out.push_str(&format!(
+                    "    Signed: {}\n",
+                    signed_at.format("%Y-%m-%d %H:%M:%S UTC")
+                ));
+            }
+            if let Some(notarized) = info.notarized {
+                let answer = if notarized { "yes" } else { "no" };
+                out.push_str(&format!("    Notarized: {}\n", answer));
+            }
+            if let Some(error) = &info.error {
+                out.push_str(&format!("    Error: {}\n", error));
+            }
+            for warning in &info.warnings {
+                out.push_str(&format!("    Warning: {}\n", warning));
+            }
+        }
+
+        out
+    }
+
+    fn render_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::SignerInfo;
+    use chrono::TimeZone;
+
+    fn result(path: &str, status: SignatureStatus, signer: Option<&str>) -> SignatureInfo {
+        SignatureInfo {
+            path: path.to_string(),
+            status,
+            signer: signer.map(|name| SignerInfo {
+                common_name: name.to_string(),
+                organization: None,
+                team_id: Some("ABCDE12345".to_string()),
+                fingerprint: None,
+                serial_number: None,
+                expires_at: None,
+                certificate_valid: true,
+            }),
+            signed_at: signer.map(|_| chrono::Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()),
+            timestamp_authority: None,
+            notarized: None,
+            stapled: None,
+            algorithm: None,
+            warnings: Vec::new(),
+            details: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_report_fails_on_unsigned_artifact() {
+        let report = VerificationReport::from_results([
+            result("dist/app.dmg", SignatureStatus::Valid, Some("Acme Corp")),
+            result("dist/app.zip", SignatureStatus::NotSigned, None),
+        ]);
+        assert!(!report.passed());
+        assert_eq!(
+            report
+                .failures()
+                .map(|f| f.path.as_str())
+                .collect::<Vec<_>>(),
+            vec!["dist/app.zip"]
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["passed"], false);
+        assert_eq!(json["verified"], 1);
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["artifacts"][0]["status"], "valid");
+        assert_eq!(json["artifacts"][0]["signer"]["common_name"], "Acme Corp");
+        assert_eq!(json["artifacts"][0]["signed_at"], "2026-03-01T12:00:00Z");
+        assert_eq!(json["artifacts"][1]["status"], "not_signed");
+        assert!(json["artifacts"][1]["signer"].is_null());
+
+        let text = report.render(ReportFormat::Text);
+        assert!(text.starts_with("✗ 1 of 2 artifact(s) failed verification"));
+        assert!(text.contains("Signer: Acme Corp (ABCDE12345)"));
+        assert!(text.contains("Signed: 2026-03-01 12:00:00 UTC"));
+        assert!(text.contains("✗ dist/app.zip: Not Signed"));
+    }
+
+    #[test]
+    fn test_errors_are_recorded_per_artifact() {
+        let mut report = VerificationReport::new();
+        report.push_error("dist/app.apk", "apksigner not found");
+        report.push(result(
+            "dist/app.dmg",
+            SignatureStatus::Valid,
+            Some("Acme Corp"),
+        ));
+
+        assert!(!report.passed());
+        assert_eq!(report.artifacts().len(), 2);
+        let json: serde_json::Value =
+            serde_json::from_str(&report.render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["failed"], 1);
+        assert_eq!(json["artifacts"][0]["status"], "unknown");
+        assert_eq!(json["artifacts"][0]["error"], "apksigner not found");
+        assert!(json["artifacts"][1].get("error").is_none());
+        assert!(report
+            .render(ReportFormat::Text)
+            .contains("Error: apksigner not found"));
+    }
+
+    #[test]
+    fn test_report_passes_when_all_valid() {
+        let report = VerificationReport::new().with_result(result(
+            "dist/app.dmg",
+            SignatureStatus::Valid,
+            Some("Acme Corp"),
+        ));
+        assert!(report.passed());
+        assert!(report
+            .render(ReportFormat::Text)
+            .starts_with("✓ Verified 1 artifact(s)"));
+
+        // Nothing verified is not a pass
+        assert!(!VerificationReport::new().passed());
+    }
+}
@@ -2,7 +2,10 @@
 //!
 //! Provides upload and management capabilities via the App Store Connect API.
 
+use crate::cancel::{cancellable, with_rollback};
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
+use crate::token::TokenTiming;
 use crate::traits::{NotarizationSupport, StoreAdapter};
 use crate::types::*;
 use chrono::{Duration, Utc};
@@ -11,6 +14,7 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::process::Stdio;
+use std::sync::Mutex;
 use tokio::process::Command;
 use tracing::{debug, info, instrument};
 
@@ -35,14 +39,17 @@ pub struct AppStoreConnect {
     /// HTTP client
     client: Client,
 
+    /// API base URL
+    api_base_url: String,
+
     /// Notarizer for macOS apps
     notarizer: Option<Notarizer>,
 
-    /// Cached JWT token
-    jwt_token: Option<String>,
+    /// Cached JWT token and its expiration time
+    jwt_token: Mutex<Option<(String, chrono::DateTime<Utc>)>>,
 
-    /// Token expiration time
-    token_expires: Option<chrono::DateTime<Utc>>,
+    /// When the cached JWT is replaced
+    token_timing: TokenTiming,
 }
 
 impl AppStoreConnect {
@@ -56,18 +63,47 @@ impl AppStoreConnect {
 
         Ok(Self {
             config,
-            client: Client::new(),
+            client: default_client(),
+            api_base_url: API_BASE_URL.to_string(),
             notarizer,
-            jwt_token: None,
-            token_expires: None,
+            jwt_token: Mutex::new(None),
+            token_timing: TokenTiming::default(),
         })
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Send API requests to a different base URL (e.g. an internal proxy)
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set how long before expiry the cached JWT is replaced
+    pub fn with_token_timing(mut self, timing: TokenTiming) -> Self {
+        self.token_timing = timing;
+        self
+    }
+
+    /// Configured app (numeric Apple ID or bundle identifier)
+    pub(super) fn app_id(&self) -> Option<&str> {
+        self.config.app_id.as_deref()
+    }
+
     /// Generate a JWT token for API authentication
-    fn generate_jwt(&mut self) -> Result<String> {
+    fn generate_jwt(&self) -> Result<String> {
+        let mut cache = self
+            .jwt_token
+            .lock()
+            .map_err(|_| StoreError::Other("JWT cache lock poisoned".to_string()))?;
+
         // Check if we have a valid cached token
-        if let (Some(token), Some(expires)) = (&self.jwt_token, self.token_expires) {
-            if Utc::now() < expires - Duration::minutes(5) {
+        if let Some((token, expires)) = cache.as_ref() {
+            if self.token_timing.is_fresh(*expires, Utc::now()) {
                 return Ok(token.clone());
             }
         }
@@ -100,21 +136,20 @@ impl AppStoreConnect {
         let token = encode(&header, &claims, &encoding_key)?;
 
         // Cache the token
-        self.jwt_token = Some(token.clone());
-        self.token_expires = Some(exp);
+        *cache = Some((token.clone(), exp));
 
         Ok(token)
     }
 
     /// Make an authenticated API request
-    async fn api_request<T: serde::de::DeserializeOwned>(
-        &mut self,
+    pub(super) async fn api_request<T: serde::de::DeserializeOwned>(
+        &self,
         method: reqwest::Method,
         endpoint: &str,
         body: Option<serde_json::Value>,
     ) -> Result<T> {
         let token = self.generate_jwt()?;
-        let url = format!("{}{}", API_BASE_URL, endpoint);
+        let url = format!("{}{}", self.api_base_url, endpoint);
 
         let mut request = self
             .client
@@ -142,7 +177,7 @@ impl AppStoreConnect {
     }
 
     /// Get app information by bundle ID
-    pub async fn get_app(&mut self, bundle_id: &str) -> Result<AppInfo> {
+    pub async fn get_app(&self, bundle_id: &str) -> Result<AppInfo> {
         #[derive(Deserialize)]
         struct AppsResponse {
             data: Vec<AppData>,
@@ -189,7 +224,9 @@ impl AppStoreConnect {
         info!("Uploading {} via Transporter", path.display());
 
         // Use xcrun altool for uploads (or iTMSTransporter directly)
+        // Killed if the upload is cancelled
         let mut cmd = Command::new("xcrun");
+        cmd.kill_on_drop(true);
         cmd.args(["altool", "--upload-app"]);
         cmd.args(["-f", path.to_str().unwrap()]);
         cmd.args(["--type", self.detect_platform_type(path)]);
@@ -342,37 +379,43 @@ impl StoreAdapter for AppStoreConnect {
                 let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
                 if matches!(ext.to_lowercase().as_str(), "app" | "pkg" | "dmg" | "zip") {
                     info!("Notarizing before upload...");
-                    notarizer.notarize(path, options.timeout).await?;
+                    cancellable(
+                        &options.cancellation,
+                        "notarization",
+                        notarizer.notarize(path, options.timeout),
+                    )
+                    .await?;
                 }
             }
         }
 
-        // Upload
-        self.upload_with_transporter(path).await
+        // Upload; a cancelled upload that still reached App Store Connect
+        // has its build expired again
+        with_rollback(
+            &options.cancellation,
+            "upload",
+            self.upload_with_transporter(path),
+            || async {
+                let app = super::extract_app_info(path).await?;
+                self.expire_uploaded_build(&app.version, &app.build_number)
+                    .await
+            },
+        )
+        .await
     }
 
     #[instrument(skip(self), fields(store = "App Store Connect"))]
     async fn get_build_status(&self, build_id: &str) -> Result<BuildStatus> {
-        // This would require API calls to get build status
-        // For now, return a placeholder
-        Ok(BuildStatus {
-            build_id: build_id.to_string(),
-            version: "".to_string(),
-            build_number: "".to_string(),
-            status: UploadStatus::Processing,
-            uploaded_at: None,
-            processed_at: None,
-            expires_at: None,
-            track: None,
-            rollout_percentage: None,
-            details: None,
-        })
+        self.fetch_build_status(build_id).await
+    }
+
+    async fn list_builds(&self, limit: Option<usize>) -> Result<Vec<Build>> {
+        self.fetch_builds(limit).await
     }
 
-    async fn list_builds(&self, _limit: Option<usize>) -> Result<Vec<Build>> {
-        // This would require API calls to list builds
-        // For now, return empty
-        Ok(Vec::new())
+    #[instrument(skip(self), fields(store = "App Store Connect"))]
+    async fn withdraw_build(&self, build_id: &str) -> Result<()> {
+        self.expire_build(build_id).await
     }
 
     fn supported_extensions(&self) -> &[&str] {
@@ -435,9 +478,10 @@ mod tests {
         let client = AppStoreConnect {
             config,
             client: Client::new(),
+            api_base_url: API_BASE_URL.to_string(),
             notarizer: None,
-            jwt_token: None,
-            token_expires: None,
+            jwt_token: Mutex::new(None),
+            token_timing: TokenTiming::default(),
         };
 
         assert_eq!(client.detect_platform_type(Path::new("app.ipa")), "ios");
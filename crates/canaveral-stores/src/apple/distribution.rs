@@ -0,0 +1,377 @@
+//! Notarized distributable packaging
+//!
+//! Notarizing an app inside a DMG, zip or installer package only works when
+//! the steps happen in the right order: the app is signed before packaging,
+//! the DMG or package is submitted (a zip can't be stapled, so for zips the
+//! app is), and the ticket is stapled to what users download. A
+//! [`DistributionRequest`] plans that order; [`Notarizer::distribute`]
+//! runs it.
+//!
+//! [`Notarizer::distribute`]: super::Notarizer::distribute
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Result, StoreError};
+use crate::types::NotarizationResult;
+
+/// Container a notarized app is shipped in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DistributionFormat {
+    /// Disk image
+    Dmg,
+    /// Zip archive
+    Zip,
+    /// Installer package
+    Pkg,
+}
+
+impl DistributionFormat {
+    /// Infer the format from a file extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "dmg" => Some(Self::Dmg),
+            "zip" => Some(Self::Zip),
+            "pkg" => Some(Self::Pkg),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for DistributionFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Dmg => write!(f, "dmg"),
+            Self::Zip => write!(f, "zip"),
+            Self::Pkg => write!(f, "pkg"),
+        }
+    }
+}
+
+/// A single step of the distribution workflow
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "step", rename_all = "snake_case")]
+pub enum DistributionStep {
+    /// Sign the app bundle with the hardened runtime
+    SignApp,
+    /// Verify the app's code signature
+    VerifyApp,
+    /// Zip `source` into `archive` with `ditto`
+    Zip { source: PathBuf, archive: PathBuf },
+    /// Build the DMG or installer package from the app
+    Package,
+    /// Sign the DMG
+    SignDmg,
+    /// Submit a file to notarytool and wait for the result
+    Notarize { path: PathBuf },
+    /// Staple the notarization ticket to a file
+    Staple { path: PathBuf },
+    /// Check the staple on a file
+    ValidateStaple { path: PathBuf },
+    /// Remove an intermediate file
+    Remove { path: PathBuf },
+}
+
+/// What to package, sign and notarize
+#[derive(Debug, Clone)]
+pub struct DistributionRequest {
+    /// The `.app` bundle
+    pub app: PathBuf,
+    /// The distributable to produce
+    pub output: PathBuf,
+    /// Container format, from the output extension
+    pub format: DistributionFormat,
+    /// "Developer ID Application" identity; `None` if the app is already signed
+    pub signing_identity: Option<String>,
+    /// "Developer ID Installer" identity, required for installer packages
+    pub installer_identity: Option<String>,
+    /// Also notarize and staple the app before packaging it
+    pub staple_app: bool,
+    /// DMG volume name (defaults to the app name)
+    pub volume_name: Option<String>,
+}
+
+impl DistributionRequest {
+    /// Create a request packaging `app` into `output`
+    ///
+    /// The format is taken from the output extension (`.dmg`, `.zip` or
+    /// `.pkg`).
+    pub fn new(app: impl Into<PathBuf>, output: impl Into<PathBuf>) -> Result<Self> {
+        let app = app.into();
+        let output = output.into();
+        if app.extension().and_then(|e| e.to_str()) != Some("app") {
+            return Err(StoreError::InvalidArtifact(format!(
+                "{} is not an .app bundle",
+                app.display()
+            )));
+        }
+        let format = DistributionFormat::from_path(&output).ok_or_else(|| {
+            StoreError::InvalidArtifact(format!("{} is not a .dmg, .zip or .pkg", output.display()))
+        })?;
+
+        Ok(Self {
+            app,
+            output,
+            format,
+            signing_identity: None,
+            installer_identity: None,
+            staple_app: false,
+            volume_name: None,
+        })
+    }
+
+    /// Sign the app with this identity before packaging
+    pub fn with_signing_identity(mut self, identity: impl Into<String>) -> Self {
+        self.signing_identity = Some(identity.into());
+        self
+    }
+
+    /// Sign the installer package with this identity
+    pub fn with_installer_identity(mut self, identity: impl Into<String>) -> Self {
+        self.installer_identity = Some(identity.into());
+        self
+    }
+
+    /// Also notarize and staple the app itself before packaging it
+    ///
+    /// Lets the app pass Gatekeeper offline once copied out of the DMG or
+    /// installed. Has no effect for zips, whose app is always stapled.
+    pub fn with_staple_app(mut self, staple_app: bool) -> Self {
+        self.staple_app = staple_app;
+        self
+    }
+
+    /// Set the DMG volume name
+    pub fn with_volume_name(mut self, name: impl Into<String>) -> Self {
+        self.volume_name = Some(name.into());
+        self
+    }
+
+    /// DMG volume name, defaulting to the app name
+    pub fn volume_name(&self) -> String {
+        self.volume_name.clone().unwrap_or_else(|| {
+            self.app
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| "App".to_string())
+        })
+    }
+
+    /// Archive the app is zipped into when it is notarized on its own
+    fn app_archive(&self) -> PathBuf {
+        self.output.with_extension("app.zip")
+    }
+
+    /// Steps producing the notarized distributable, in order
+    ///
+    /// # Errors
+    ///
+    /// Returns an error for an installer package without an installer
+    /// identity, which notarization would reject.
+    pub fn plan(&self) -> Result<Vec<DistributionStep>> {
+        if self.format == DistributionFormat::Pkg && self.installer_identity.is_none() {
+            return Err(StoreError::ConfigurationError(
+                "installer packages must be signed with a Developer ID Installer identity to be notarized"
+                    .to_string(),
+            ));
+        }
+
+        let mut steps = Vec::new();
+        if self.signing_identity.is_some() {
+            steps.push(DistributionStep::SignApp);
+        }
+        steps.push(DistributionStep::VerifyApp);
+
+        if self.format == DistributionFormat::Zip {
+            // A zip can't hold a staple: notarize the zip, staple the app,
+            // then zip the stapled app again
+            let zip = DistributionStep::Zip {
+                source: self.app.clone(),
+                archive: self.output.clone(),
+            };
+            steps.push(zip.clone());
+            steps.push(DistributionStep::Notarize {
+                path: self.output.clone(),
+            });
+            steps.extend(self.staple_steps(&self.app));
+            steps.push(zip);
+            return Ok(steps);
+        }
+
+        if self.staple_app {
+            let archive = self.app_archive();
+            steps.push(DistributionStep::Zip {
+                source: self.app.clone(),
+                archive: archive.clone(),
+            });
+            steps.push(DistributionStep::Notarize {
+                path: archive.clone(),
+            });
+            steps.extend(self.staple_steps(&self.app));
+            steps.push(DistributionStep::Remove { path: archive });
+        }
+
+        steps.push(DistributionStep::Package);
+        if self.format == DistributionFormat::Dmg && self.signing_identity.is_some() {
+            steps.push(DistributionStep::SignDmg);
+        }
+        steps.push(DistributionStep::Notarize {
+            path: self.output.clone(),
+        });
+        steps.extend(self.staple_steps(&self.output));
+        Ok(steps)
+    }
+
+    fn staple_steps(&self, path: &Path) -> [DistributionStep; 2] {
+        [
+            DistributionStep::Staple {
+                path: path.to_path_buf(),
+            },
+            DistributionStep::ValidateStaple {
+                path: path.to_path_buf(),
+            },
+        ]
+    }
+}
+
+/// Outcome of [`Notarizer::distribute`](super::Notarizer::distribute)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DistributionReport {
+    /// The notarized distributable
+    pub distributable: PathBuf,
+    /// Container format
+    pub format: DistributionFormat,
+    /// Result of each notarization submission, in order
+    pub submissions: Vec<NotarizationResult>,
+    /// Files a validated ticket was stapled to
+    pub stapled: Vec<PathBuf>,
+    /// Steps that were run
+    pub steps: Vec<DistributionStep>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notarized(steps: &[DistributionStep]) -> Vec<&Path> {
+        steps
+            .iter()
+            .filter_map(|s| match s {
+                DistributionStep::Notarize { path } => Some(path.as_path()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn stapled(steps: &[DistributionStep]) -> Vec<&Path> {
+        steps
+            .iter()
+            .filter_map(|s| match s {
+                DistributionStep::Staple { path } => Some(path.as_path()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_dmg_plan_signs_app_before_packaging() {
+        let request = DistributionRequest::new("build/My App.app", "dist/MyApp.dmg")
+            .unwrap()
+            .with_signing_identity("Developer ID Application: Example (TEAM123)");
+        let steps = request.plan().unwrap();
+
+        assert_eq!(
+            steps,
+            vec![
+                DistributionStep::SignApp,
+                DistributionStep::VerifyApp,
+                DistributionStep::Package,
+                DistributionStep::SignDmg,
+                DistributionStep::Notarize {
+                    path: "dist/MyApp.dmg".into()
+                },
+                DistributionStep::Staple {
+                    path: "dist/MyApp.dmg".into()
+                },
+                DistributionStep::ValidateStaple {
+                    path: "dist/MyApp.dmg".into()
+                },
+            ]
+        );
+        assert_eq!(request.volume_name(), "My App");
+    }
+
+    #[test]
+    fn test_zip_plan_staples_app_and_rezips() {
+        let request = DistributionRequest::new("My.app", "My.zip").unwrap();
+        let steps = request.plan().unwrap();
+
+        // Already signed: verified, not re-signed
+        assert_eq!(steps[0], DistributionStep::VerifyApp);
+        assert_eq!(notarized(&steps), [Path::new("My.zip")]);
+        assert_eq!(stapled(&steps), [Path::new("My.app")]);
+        let staple = steps
+            .iter()
+            .position(|s| matches!(s, DistributionStep::Staple { .. }))
+            .unwrap();
+        assert!(matches!(
+            steps.last(),
+            Some(DistributionStep::Zip { archive, .. }) if archive == Path::new("My.zip")
+        ));
+        assert!(staple < steps.len() - 1);
+    }
+
+    #[test]
+    fn test_inner_app_stapled_before_packaging() {
+        let request = DistributionRequest::new("My.app", "My.dmg")
+            .unwrap()
+            .with_staple_app(true);
+        let steps = request.plan().unwrap();
+
+        assert_eq!(
+            notarized(&steps),
+            [Path::new("My.app.zip"), Path::new("My.dmg")]
+        );
+        assert_eq!(stapled(&steps), [Path::new("My.app"), Path::new("My.dmg")]);
+        let package = steps
+            .iter()
+            .position(|s| *s == DistributionStep::Package)
+            .unwrap();
+        let app_staple = steps
+            .iter()
+            .position(|s| {
+                *s == DistributionStep::Staple {
+                    path: "My.app".into(),
+                }
+            })
+            .unwrap();
+        assert!(app_staple < package);
+        assert!(steps.contains(&DistributionStep::Remove {
+            path: "My.app.zip".into()
+        }));
+    }
+
+    #[test]
+    fn test_pkg_requires_installer_identity() {
+        let request = DistributionRequest::new("My.app", "My.pkg").unwrap();
+        assert!(matches!(
+            request.plan(),
+            Err(StoreError::ConfigurationError(_))
+        ));
+
+        let steps = request
+            .with_installer_identity("Developer ID Installer: Example (TEAM123)")
+            .plan()
+            .unwrap();
+        assert_eq!(notarized(&steps), [Path::new("My.pkg")]);
+        assert_eq!(stapled(&steps), [Path::new("My.pkg")]);
+    }
+
+    #[test]
+    fn test_invalid_paths() {
+        assert!(DistributionRequest::new("My.framework", "My.dmg").is_err());
+        assert!(DistributionRequest::new("My.app", "My.tar.gz").is_err());
+    }
+}
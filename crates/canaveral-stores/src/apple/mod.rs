@@ -7,18 +7,28 @@
 //! ## Notarization
 //!
 //! ```ignore
-//! use canaveral_stores::apple::Notarizer;
+//! use canaveral_stores::apple::{DistributionRequest, Notarizer};
 //!
 //! let notarizer = Notarizer::new(config)?;
 //! let result = notarizer.notarize(&artifact_path, None).await?;
+//!
+//! // Sign, package into a DMG, notarize and staple in the right order
+//! let request = DistributionRequest::new("build/My.app", "dist/My.dmg")?
+//!     .with_signing_identity("Developer ID Application: Example (TEAM123)");
+//! let report = notarizer.distribute(&request, None).await?;
 //! ```
 
+mod builds;
 mod connect;
+mod distribution;
 mod notarize;
 mod testflight;
 
 pub use connect::AppStoreConnect;
-pub use notarize::Notarizer;
+pub use distribution::{
+    DistributionFormat, DistributionReport, DistributionRequest, DistributionStep,
+};
+pub use notarize::{CommandRunner, Notarizer, SystemRunner, ToolOutput};
 pub use testflight::{
     BetaAppReviewSubmission, BetaGroup, BetaReviewState, BetaTester, BuildAudienceType,
     BuildProcessingState, TestFlight, TestFlightBuild, TesterInviteType,
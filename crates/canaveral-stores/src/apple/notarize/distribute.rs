@@ -0,0 +1,323 @@
+//! Running distribution plans
+//!
+//! Executes the steps of a [`DistributionRequest`] with a [`Notarizer`].
+
+use tracing::{debug, info, instrument, warn};
+
+use super::runner::args;
+use super::Notarizer;
+use crate::apple::distribution::{
+    DistributionFormat, DistributionReport, DistributionRequest, DistributionStep,
+};
+use crate::error::{Result, StoreError};
+
+impl Notarizer {
+    /// Package, notarize and staple an app for distribution
+    ///
+    /// Runs the steps of [`DistributionRequest::plan`] in order, checking
+    /// each one: the app is signed and verified before it is packaged, the
+    /// container is notarized, and the ticket is stapled to the file users
+    /// download (the app itself for zips). Stops at the first failure.
+    #[instrument(skip(self, request), fields(app = %request.app.display(), output = %request.output.display()))]
+    pub async fn distribute(
+        &self,
+        request: &DistributionRequest,
+        timeout_secs: Option<u64>,
+    ) -> Result<DistributionReport> {
+        let steps = request.plan()?;
+        info!(
+            format = %request.format,
+            steps = steps.len(),
+            "packaging notarized distributable"
+        );
+
+        let mut report = DistributionReport {
+            distributable: request.output.clone(),
+            format: request.format,
+            submissions: Vec::new(),
+            stapled: Vec::new(),
+            steps: Vec::new(),
+        };
+        for step in steps {
+            debug!(?step, "running distribution step");
+            match &step {
+                DistributionStep::SignApp => {
+                    let identity = request.signing_identity.as_deref().unwrap_or_default();
+                    let cmd = args([
+                        "--force".as_ref(),
+                        "--options".as_ref(),
+                        "runtime".as_ref(),
+                        "--timestamp".as_ref(),
+                        "--sign".as_ref(),
+                        identity.as_ref(),
+                        request.app.as_os_str(),
+                    ]);
+                    self.run_checked("codesign", cmd, "Signing the app failed")
+                        .await?;
+                }
+                DistributionStep::VerifyApp => {
+                    let cmd = args([
+                        "--verify".as_ref(),
+                        "--deep".as_ref(),
+                        "--strict".as_ref(),
+                        request.app.as_os_str(),
+                    ]);
+                    self.run_checked(
+                        "codesign",
+                        cmd,
+                        "The app's signature is invalid; sign it before notarizing",
+                    )
+                    .await?;
+                }
+                DistributionStep::Zip { source, archive } => {
+                    let cmd = args([
+                        "-c".as_ref(),
+                        "-k".as_ref(),
+                        "--keepParent".as_ref(),
+                        source.as_os_str(),
+                        archive.as_os_str(),
+                    ]);
+                    self.run_checked("ditto", cmd, "Creating the zip failed")
+                        .await?;
+                }
+                DistributionStep::Package => self.package(request).await?,
+                DistributionStep::SignDmg => {
+                    let identity = request.signing_identity.as_deref().unwrap_or_default();
+                    let cmd = args([
+                        "--force".as_ref(),
+                        "--timestamp".as_ref(),
+                        "--sign".as_ref(),
+                        identity.as_ref(),
+                        request.output.as_os_str(),
+                    ]);
+                    self.run_checked("codesign", cmd, "Signing the DMG failed")
+                        .await?;
+                }
+                DistributionStep::Notarize { path } => {
+                    let submission_id = self.submit(path).await?;
+                    let result = self.wait(&submission_id, timeout_secs).await?;
+                    report.submissions.push(result);
+                }
+                DistributionStep::Staple { path } => self.staple(path).await?,
+                DistributionStep::ValidateStaple { path } => {
+                    if !self.verify_staple(path).await? {
+                        return Err(StoreError::NotarizationFailed(format!(
+                            "Staple on {} did not validate",
+                            path.display()
+                        )));
+                    }
+                    report.stapled.push(path.clone());
+                }
+                DistributionStep::Remove { path } => {
+                    if let Err(e) = tokio::fs::remove_file(path).await {
+                        warn!(path = %path.display(), error = %e, "could not remove intermediate file");
+                    }
+                }
+            }
+            report.steps.push(step);
+        }
+
+        info!(
+            distributable = %report.distributable.display(),
+            stapled = report.stapled.len(),
+            "distributable notarized"
+        );
+        Ok(report)
+    }
+
+    /// Build the DMG or installer package from the app
+    async fn package(&self, request: &DistributionRequest) -> Result<()> {
+        match request.format {
+            DistributionFormat::Dmg => {
+                let volume_name = request.volume_name();
+                let cmd = args([
+                    "create".as_ref(),
+                    "-volname".as_ref(),
+                    volume_name.as_ref(),
+                    "-srcfolder".as_ref(),
+                    request.app.as_os_str(),
+                    "-ov".as_ref(),
+                    "-format".as_ref(),
+                    "UDZO".as_ref(),
+                    request.output.as_os_str(),
+                ]);
+                self.run_checked("hdiutil", cmd, "Creating the DMG failed")
+                    .await
+            }
+            DistributionFormat::Pkg => {
+                let identity = request.installer_identity.as_deref().unwrap_or_default();
+                let cmd = args([
+                    "--component".as_ref(),
+                    request.app.as_os_str(),
+                    "/Applications".as_ref(),
+                    "--sign".as_ref(),
+                    identity.as_ref(),
+                    request.output.as_os_str(),
+                ]);
+                self.run_checked("productbuild", cmd, "Creating the installer package failed")
+                    .await
+            }
+            DistributionFormat::Zip => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::apple::{CommandRunner, ToolOutput};
+    use crate::types::AppleStoreConfig;
+    use async_trait::async_trait;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Records every invocation and answers like the real tools would
+    #[derive(Default)]
+    struct MockRunner {
+        calls: Mutex<Vec<String>>,
+        /// Invocations containing this fail
+        fail_on: Option<&'static str>,
+    }
+
+    impl MockRunner {
+        fn calls(&self) -> Vec<String> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl CommandRunner for MockRunner {
+        async fn run(&self, program: &str, args: &[String]) -> Result<ToolOutput> {
+            let call = format!("{} {}", program, args.join(" "));
+            self.calls.lock().unwrap().push(call.clone());
+            if self.fail_on.is_some_and(|f| call.contains(f)) {
+                return Ok(ToolOutput {
+                    success: false,
+                    stderr: "mock failure".to_string(),
+                    ..Default::default()
+                });
+            }
+            let stdout = match args.get(1).map(String::as_str) {
+                Some("submit") => r#"{"id": "sub-1", "message": "Successfully uploaded file"}"#,
+                Some("info") => r#"{"id": "sub-1", "status": "Accepted"}"#,
+                _ => "",
+            };
+            Ok(ToolOutput {
+                success: true,
+                stdout: stdout.to_string(),
+                stderr: String::new(),
+            })
+        }
+    }
+
+    fn notarizer(runner: Arc<MockRunner>) -> Notarizer {
+        let config = AppleStoreConfig {
+            api_key_id: "KEY123".to_string(),
+            api_issuer_id: "issuer".to_string(),
+            api_key: "/keys/AuthKey.p8".to_string(),
+            team_id: None,
+            app_id: None,
+            notarize: true,
+            staple: true,
+            primary_locale: None,
+        };
+        Notarizer::with_runner(&config, runner)
+    }
+
+    /// Calls to `tool`, with the program stripped
+    fn calls_to(calls: &[String], tool: &str) -> Vec<String> {
+        calls
+            .iter()
+            .filter_map(|c| c.strip_prefix(&format!("{} ", tool)).map(String::from))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_distribute_dmg_submits_and_staples_dmg() {
+        let runner = Arc::new(MockRunner::default());
+        let request = DistributionRequest::new("build/My.app", "dist/My.dmg")
+            .unwrap()
+            .with_signing_identity("Developer ID Application: Example");
+
+        let report = notarizer(runner.clone())
+            .distribute(&request, None)
+            .await
+            .unwrap();
+        assert_eq!(report.stapled, [PathBuf::from("dist/My.dmg")]);
+        assert_eq!(report.submissions.len(), 1);
+
+        let calls = runner.calls();
+        let programs: Vec<&str> = calls
+            .iter()
+            .map(|c| c.split_whitespace().next().unwrap())
+            .collect();
+        // Sign and verify the app, build and sign the DMG, then notarize
+        assert_eq!(
+            &programs[..4],
+            ["codesign", "codesign", "hdiutil", "codesign"]
+        );
+        let xcrun = calls_to(&calls, "xcrun");
+        assert!(xcrun[0].starts_with("notarytool submit dist/My.dmg"));
+        assert!(xcrun[0].contains("--key-id KEY123"));
+        assert_eq!(
+            &xcrun[2..],
+            ["stapler staple dist/My.dmg", "stapler validate dist/My.dmg"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_distribute_zip_staples_app() {
+        let runner = Arc::new(MockRunner::default());
+        let request = DistributionRequest::new("My.app", "My.zip").unwrap();
+
+        let report = notarizer(runner.clone())
+            .distribute(&request, None)
+            .await
+            .unwrap();
+        assert_eq!(report.stapled, [PathBuf::from("My.app")]);
+
+        let calls = runner.calls();
+        assert!(calls_to(&calls, "xcrun")[0].starts_with("notarytool submit My.zip"));
+        assert_eq!(
+            calls_to(&calls, "ditto"),
+            [
+                "-c -k --keepParent My.app My.zip",
+                "-c -k --keepParent My.app My.zip"
+            ]
+        );
+        // The final zip is made after stapling
+        assert!(calls.last().unwrap().starts_with("ditto"));
+    }
+
+    #[tokio::test]
+    async fn test_distribute_stops_on_invalid_signature() {
+        let runner = Arc::new(MockRunner {
+            fail_on: Some("codesign --verify"),
+            ..Default::default()
+        });
+        let request = DistributionRequest::new("My.app", "My.dmg").unwrap();
+
+        let err = notarizer(runner.clone())
+            .distribute(&request, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("signature is invalid"));
+        // Nothing was packaged or submitted
+        assert_eq!(runner.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_distribute_fails_when_staple_does_not_validate() {
+        let runner = Arc::new(MockRunner {
+            fail_on: Some("stapler validate"),
+            ..Default::default()
+        });
+        let request = DistributionRequest::new("My.app", "My.dmg").unwrap();
+
+        let err = notarizer(runner)
+            .distribute(&request, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("did not validate"));
+    }
+}
@@ -2,13 +2,20 @@
 //!
 //! Provides notarization support for macOS apps using Apple's `notarytool`.
 
+mod distribute;
+mod runner;
+
+pub use runner::{CommandRunner, SystemRunner, ToolOutput};
+
 use crate::error::{Result, StoreError};
 use crate::types::*;
 use std::path::Path;
 use std::process::Stdio;
-use tokio::process::Command;
+use std::sync::Arc;
 use tracing::{debug, info, instrument, warn};
 
+use runner::args;
+
 /// Apple notarization client using notarytool
 pub struct Notarizer {
     /// App Store Connect API Key ID
@@ -25,6 +32,9 @@ pub struct Notarizer {
 
     /// Whether to automatically staple after notarization
     auto_staple: bool,
+
+    /// Runs notarytool, stapler and the packaging tools
+    runner: Arc<dyn CommandRunner>,
 }
 
 impl Notarizer {
@@ -43,9 +53,24 @@ impl Notarizer {
             api_key: config.api_key.clone(),
             team_id: config.team_id.clone(),
             auto_staple: config.staple,
+            runner: Arc::new(SystemRunner),
         })
     }
 
+    /// Create a notarizer running tools through `runner`
+    ///
+    /// Unlike [`Notarizer::new`], doesn't check that notarytool is installed.
+    pub fn with_runner(config: &AppleStoreConfig, runner: Arc<dyn CommandRunner>) -> Self {
+        Self {
+            api_key_id: config.api_key_id.clone(),
+            api_issuer_id: config.api_issuer_id.clone(),
+            api_key: config.api_key.clone(),
+            team_id: config.team_id.clone(),
+            auto_staple: config.staple,
+            runner,
+        }
+    }
+
     /// Check if notarytool is available
     fn is_notarytool_available() -> bool {
         std::process::Command::new("xcrun")
@@ -57,29 +82,34 @@ impl Notarizer {
             .unwrap_or(false)
     }
 
-    /// Build base notarytool command with credentials
-    fn build_command(&self) -> Command {
-        let mut cmd = Command::new("xcrun");
-        cmd.arg("notarytool");
-
-        // Add API key credentials
-        cmd.args(["--key-id", &self.api_key_id]);
-        cmd.args(["--issuer", &self.api_issuer_id]);
-
-        // Key can be a file path or the key contents
-        if Path::new(&self.api_key).exists() {
-            cmd.args(["--key", &self.api_key]);
-        } else {
-            // Write key to temp file
-            // Note: In production, consider using --keychain-profile instead
-            cmd.args(["--key", &self.api_key]);
-        }
+    /// Run a notarytool subcommand with credentials
+    async fn notarytool(&self, subcommand: &[&str]) -> Result<ToolOutput> {
+        let mut cmd = vec!["notarytool".to_string()];
+        cmd.extend(args(subcommand));
+
+        // Add API key credentials; the key is a file path or the key contents
+        cmd.extend(args(["--key-id", &self.api_key_id]));
+        cmd.extend(args(["--issuer", &self.api_issuer_id]));
+        cmd.extend(args(["--key", &self.api_key]));
 
         if let Some(team_id) = &self.team_id {
-            cmd.args(["--team-id", team_id]);
+            cmd.extend(args(["--team-id", team_id]));
         }
 
-        cmd
+        self.runner.run("xcrun", &cmd).await
+    }
+
+    /// Run a tool, failing with `context` if it exits unsuccessfully
+    async fn run_checked(&self, program: &str, cmd: Vec<String>, context: &str) -> Result<()> {
+        let output = self.runner.run(program, &cmd).await?;
+        if !output.success {
+            return Err(StoreError::CommandFailed(format!(
+                "{}: {}",
+                context,
+                output.stderr.trim()
+            )));
+        }
+        Ok(())
     }
 
     /// Submit a file for notarization
@@ -87,24 +117,20 @@ impl Notarizer {
     pub async fn submit(&self, path: &Path) -> Result<String> {
         info!("Submitting {} for notarization", path.display());
 
-        let mut cmd = self.build_command();
-        cmd.args(["submit", path.to_str().unwrap()]);
-        cmd.args(["--output-format", "json"]);
+        let path = path.to_string_lossy();
+        let output = self
+            .notarytool(&["submit", &path, "--output-format", "json"])
+            .await?;
 
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("notarytool submit failed: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let stderr = String::from_utf8_lossy(&output.stderr);
+        let stdout = &output.stdout;
+        let stderr = &output.stderr;
 
         debug!("notarytool submit stdout: {}", stdout);
         if !stderr.is_empty() {
             debug!("notarytool submit stderr: {}", stderr);
         }
 
-        if !output.status.success() {
+        if !output.success {
             return Err(StoreError::NotarizationFailed(format!(
                 "Submission failed: {}",
                 stderr
@@ -112,7 +138,7 @@ impl Notarizer {
         }
 
         // Parse JSON output for submission ID
-        let json: serde_json::Value = serde_json::from_str(&stdout).map_err(|e| {
+        let json: serde_json::Value = serde_json::from_str(stdout).map_err(|e| {
             StoreError::NotarizationFailed(format!("Failed to parse response: {}", e))
         })?;
 
@@ -129,26 +155,18 @@ impl Notarizer {
     pub async fn status(&self, submission_id: &str) -> Result<NotarizationResult> {
         debug!("Checking notarization status for {}", submission_id);
 
-        let mut cmd = self.build_command();
-        cmd.args(["info", submission_id]);
-        cmd.args(["--output-format", "json"]);
-
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("notarytool info failed: {}", e)))?;
-
-        let stdout = String::from_utf8_lossy(&output.stdout);
+        let output = self
+            .notarytool(&["info", submission_id, "--output-format", "json"])
+            .await?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.success {
             return Err(StoreError::NotarizationFailed(format!(
                 "Status check failed: {}",
-                stderr
+                output.stderr
             )));
         }
 
-        let json: serde_json::Value = serde_json::from_str(&stdout).map_err(|e| {
+        let json: serde_json::Value = serde_json::from_str(&output.stdout).map_err(|e| {
             StoreError::NotarizationFailed(format!("Failed to parse response: {}", e))
         })?;
 
@@ -176,23 +194,16 @@ impl Notarizer {
 
     /// Get the notarization log for a submission
     pub async fn get_log(&self, submission_id: &str) -> Result<String> {
-        let mut cmd = self.build_command();
-        cmd.args(["log", submission_id]);
-
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("notarytool log failed: {}", e)))?;
+        let output = self.notarytool(&["log", submission_id]).await?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.success {
             return Err(StoreError::NotarizationFailed(format!(
                 "Failed to get log: {}",
-                stderr
+                output.stderr
             )));
         }
 
-        Ok(String::from_utf8_lossy(&output.stdout).to_string())
+        Ok(output.stdout)
     }
 
     /// Wait for notarization to complete, polling periodically
@@ -246,17 +257,18 @@ impl Notarizer {
     pub async fn staple(&self, path: &Path) -> Result<()> {
         info!("Stapling notarization ticket to {}", path.display());
 
-        let output = Command::new("xcrun")
-            .args(["stapler", "staple", path.to_str().unwrap()])
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("stapler failed: {}", e)))?;
+        let output = self
+            .runner
+            .run(
+                "xcrun",
+                &args(["stapler".as_ref(), "staple".as_ref(), path]),
+            )
+            .await?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.success {
             return Err(StoreError::NotarizationFailed(format!(
                 "Stapling failed: {}",
-                stderr
+                output.stderr
             )));
         }
 
@@ -266,13 +278,15 @@ impl Notarizer {
 
     /// Verify that an artifact has a valid notarization staple
     pub async fn verify_staple(&self, path: &Path) -> Result<bool> {
-        let output = Command::new("xcrun")
-            .args(["stapler", "validate", path.to_str().unwrap()])
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("stapler validate failed: {}", e)))?;
-
-        Ok(output.status.success())
+        let output = self
+            .runner
+            .run(
+                "xcrun",
+                &args(["stapler".as_ref(), "validate".as_ref(), path]),
+            )
+            .await?;
+
+        Ok(output.success)
     }
 
     /// Full notarization workflow: submit, wait, staple
@@ -298,25 +312,18 @@ impl Notarizer {
 
     /// List recent notarization submissions
     pub async fn history(&self, limit: Option<usize>) -> Result<Vec<NotarizationResult>> {
-        let mut cmd = self.build_command();
-        cmd.arg("history");
-        cmd.args(["--output-format", "json"]);
-
-        let output = cmd
-            .output()
-            .await
-            .map_err(|e| StoreError::CommandFailed(format!("notarytool history failed: {}", e)))?;
+        let output = self
+            .notarytool(&["history", "--output-format", "json"])
+            .await?;
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
+        if !output.success {
             return Err(StoreError::NotarizationFailed(format!(
                 "History failed: {}",
-                stderr
+                output.stderr
             )));
         }
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-        let json: serde_json::Value = serde_json::from_str(&stdout).map_err(|e| {
+        let json: serde_json::Value = serde_json::from_str(&output.stdout).map_err(|e| {
             StoreError::NotarizationFailed(format!("Failed to parse response: {}", e))
         })?;
 
@@ -0,0 +1,65 @@
+//! External tool execution for notarization
+
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use crate::error::{Result, StoreError};
+
+/// Output of an external tool
+#[derive(Debug, Clone, Default)]
+pub struct ToolOutput {
+    /// Whether the tool exited successfully
+    pub success: bool,
+    /// Standard output
+    pub stdout: String,
+    /// Standard error
+    pub stderr: String,
+}
+
+/// Runs the external tools notarization shells out to
+///
+/// [`SystemRunner`] spawns real processes; tests substitute a mock.
+#[async_trait]
+pub trait CommandRunner: Send + Sync {
+    /// Run `program` with `args`, failing only if it couldn't be started
+    async fn run(&self, program: &str, args: &[String]) -> Result<ToolOutput>;
+}
+
+/// Runs tools as child processes
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemRunner;
+
+#[async_trait]
+impl CommandRunner for SystemRunner {
+    async fn run(&self, program: &str, args: &[String]) -> Result<ToolOutput> {
+        let output = Command::new(program)
+            .args(args)
+            .kill_on_drop(true)
+            .output()
+            .await
+            .map_err(|e| {
+                let tool = match args.first() {
+                    Some(sub) => format!("{} {}", program, sub),
+                    None => program.to_string(),
+                };
+                StoreError::CommandFailed(format!("{} failed: {}", tool, e))
+            })?;
+        Ok(ToolOutput {
+            success: output.status.success(),
+            stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+            stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+        })
+    }
+}
+
+/// Collect command-line arguments as strings
+pub(super) fn args<I, S>(items: I) -> Vec<String>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    items
+        .into_iter()
+        .map(|s| s.as_ref().to_string_lossy().to_string())
+        .collect()
+}
@@ -11,6 +11,7 @@ use std::path::Path;
 use tracing::{debug, info, instrument};
 
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
 use crate::types::AppleStoreConfig;
 
 const API_BASE_URL: &str = "https://api.appstoreconnect.apple.com/v1";
@@ -37,12 +38,18 @@ impl TestFlight {
     pub fn new(config: AppleStoreConfig) -> Self {
         Self {
             config,
-            client: Client::new(),
+            client: default_client(),
             jwt_token: None,
             token_expires: None,
         }
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Create from environment variables
     pub fn from_env() -> Result<Self> {
         let api_key_id = std::env::var("APP_STORE_CONNECT_API_KEY_ID").map_err(|_| {
@@ -1007,7 +1014,7 @@ pub enum BuildProcessingState {
 }
 
 impl BuildProcessingState {
-    fn from_str(s: &str) -> Self {
+    pub(super) fn from_str(s: &str) -> Self {
         match s.to_uppercase().as_str() {
             "PROCESSING" => Self::Processing,
             "FAILED" => Self::Failed,
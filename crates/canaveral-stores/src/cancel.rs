@@ -0,0 +1,97 @@
+//! Cancellation of in-flight uploads
+//!
+//! Uploads carry a [`CancellationToken`] in [`UploadOptions`](crate::UploadOptions).
+//! Cancelling it drops the current step, which aborts pending HTTP requests
+//! and kills child processes spawned with `kill_on_drop`. Steps that run
+//! after a draft was created on the store (an edit, a submission) go through
+//! [`with_rollback`] so the draft is discarded instead of left half-filled.
+
+use std::future::Future;
+
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use crate::error::{Result, StoreError};
+
+/// Run `fut`, returning [`StoreError::Cancelled`] as soon as `token` is cancelled
+pub async fn cancellable<T>(
+    token: &CancellationToken,
+    step: &str,
+    fut: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    if token.is_cancelled() {
+        return Err(StoreError::Cancelled(step.to_string()));
+    }
+    tokio::select! {
+        biased;
+        _ = token.cancelled() => Err(StoreError::Cancelled(step.to_string())),
+        result = fut => result,
+    }
+}
+
+/// Like [`cancellable`], running `rollback` when the step is cancelled
+///
+/// Rollback failures are logged and don't replace the cancellation error.
+pub async fn with_rollback<T, R>(
+    token: &CancellationToken,
+    step: &str,
+    fut: impl Future<Output = Result<T>>,
+    rollback: impl FnOnce() -> R,
+) -> Result<T>
+where
+    R: Future<Output = Result<()>>,
+{
+    let result = cancellable(token, step, fut).await;
+    if matches!(result, Err(StoreError::Cancelled(_))) {
+        info!(step, "upload cancelled, rolling back");
+        if let Err(e) = rollback().await {
+            warn!(step, error = %e, "rollback after cancellation failed");
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_cancel_mid_step_rolls_back() {
+        let token = CancellationToken::new();
+        let canceller = token.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            canceller.cancel();
+        });
+
+        let rolled_back = AtomicBool::new(false);
+        let result: Result<()> =
+            with_rollback(&token, "package upload", std::future::pending(), || async {
+                rolled_back.store(true, Ordering::SeqCst);
+                Ok(())
+            })
+            .await;
+
+        assert!(matches!(result, Err(StoreError::Cancelled(ref step)) if step == "package upload"));
+        assert!(rolled_back.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn test_completed_step_is_not_rolled_back() {
+        let token = CancellationToken::new();
+        let rolled_back = AtomicBool::new(false);
+        let result = with_rollback(&token, "commit", async { Ok(42) }, || async {
+            rolled_back.store(true, Ordering::SeqCst);
+            Ok(())
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert!(!rolled_back.load(Ordering::SeqCst));
+
+        token.cancel();
+        let result = cancellable(&token, "commit", async { Ok(()) }).await;
+        assert!(matches!(result, Err(StoreError::Cancelled(_))));
+    }
+}
@@ -49,6 +49,10 @@ pub enum StoreError {
     #[error("Configuration error: {0}")]
     ConfigurationError(String),
 
+    /// The store has no API for the requested operation
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+
     /// Tool not found
     #[error("Required tool not found: {0}")]
     ToolNotFound(String),
@@ -61,13 +65,17 @@ pub enum StoreError {
     #[error("Operation timed out: {0}")]
     Timeout(String),
 
+    /// Cancelled before completion (e.g. Ctrl-C)
+    #[error("Cancelled during {0}")]
+    Cancelled(String),
+
     /// IO error
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
     /// HTTP error
     #[error("HTTP error: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
 
     /// JSON error
     #[error("JSON error: {0}")]
@@ -82,5 +90,98 @@ pub enum StoreError {
     Other(String),
 }
 
+impl StoreError {
+    /// Stable error category for exit code mapping
+    ///
+    /// Returns one of the identifiers defined by `canaveral_core::error::ErrorCode`
+    /// (`auth`, `network`, `validation`, `config`, `tool-missing`, `cancelled`,
+    /// `general`).
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AuthenticationFailed(_) | Self::InvalidCredentials(_) | Self::Jwt(_) => "auth",
+            Self::ApiError {
+                status: 401 | 403, ..
+            } => "auth",
+            Self::ApiError { .. }
+            | Self::RateLimited { .. }
+            | Self::UploadFailed(_)
+            | Self::Timeout(_)
+            | Self::Http(_) => "network",
+            Self::InvalidArtifact(_)
+            | Self::ValidationFailed(_)
+            | Self::NotarizationFailed(_)
+            | Self::Unsupported(_) => "validation",
+            Self::ConfigurationError(_) => "config",
+            Self::ToolNotFound(_) => "tool-missing",
+            Self::Cancelled(_) => "cancelled",
+            Self::BuildNotFound(_)
+            | Self::AppNotFound(_)
+            | Self::CommandFailed(_)
+            | Self::Io(_)
+            | Self::Json(_)
+            | Self::Other(_) => "general",
+        }
+    }
+}
+
+impl From<reqwest::Error> for StoreError {
+    fn from(err: reqwest::Error) -> Self {
+        if err.is_timeout() {
+            let target = err
+                .url()
+                .map(|u| u.to_string())
+                .unwrap_or_else(|| "HTTP request".to_string());
+            Self::Timeout(target)
+        } else {
+            Self::Http(err)
+        }
+    }
+}
+
 /// Result type for store operations
 pub type Result<T> = std::result::Result<T, StoreError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_codes() {
+        assert_eq!(
+            StoreError::AuthenticationFailed("expired".to_string()).code(),
+            "auth"
+        );
+        assert_eq!(
+            StoreError::ApiError {
+                status: 401,
+                message: "unauthorized".to_string()
+            }
+            .code(),
+            "auth"
+        );
+        assert_eq!(
+            StoreError::ApiError {
+                status: 503,
+                message: "unavailable".to_string()
+            }
+            .code(),
+            "network"
+        );
+        assert_eq!(
+            StoreError::RateLimited { retry_after: None }.code(),
+            "network"
+        );
+        assert_eq!(
+            StoreError::ToolNotFound("xcrun".to_string()).code(),
+            "tool-missing"
+        );
+        assert_eq!(
+            StoreError::ValidationFailed("bad icon".to_string()).code(),
+            "validation"
+        );
+        assert_eq!(
+            StoreError::Cancelled("upload".to_string()).code(),
+            "cancelled"
+        );
+    }
+}
@@ -9,6 +9,7 @@ use serde::{Deserialize, Serialize};
 use tracing::{debug, info, instrument};
 
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
 
 const FIREBASE_API_BASE: &str = "https://firebaseappdistribution.googleapis.com/v1";
 const FIREBASE_UPLOAD_BASE: &str = "https://firebaseappdistribution.googleapis.com/upload/v1";
@@ -58,12 +59,18 @@ impl Firebase {
     pub fn new(config: FirebaseConfig) -> Self {
         Self {
             config,
-            client: Client::new(),
+            client: default_client(),
             access_token: None,
             token_expires: None,
         }
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Create from environment variables
     pub fn from_env() -> Result<Self> {
         let project_id = std::env::var("FIREBASE_PROJECT_ID")
@@ -0,0 +1,155 @@
+//! APK inspection without the Android SDK
+//!
+//! Used when `aapt2` can't be found. An APK is a zip whose
+//! `AndroidManifest.xml` is compiled to Android's binary XML format, decoded
+//! by [`parse_manifest`]; native ABIs come from the `lib/<abi>/` entries.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+use tracing::debug;
+
+use crate::error::{Result, StoreError};
+
+use super::axml::parse_manifest;
+use super::badging::Badging;
+
+/// Read badging from an APK's zip entries and binary manifest
+pub fn read_apk(path: &Path) -> Result<Badging> {
+    let file = std::fs::File::open(path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| StoreError::InvalidArtifact(format!("Invalid APK: {}", e)))?;
+
+    let mut manifest = Vec::new();
+    archive
+        .by_name("AndroidManifest.xml")
+        .map_err(|_| {
+            StoreError::InvalidArtifact(
+                "APK has no AndroidManifest.xml (app bundles need aapt2 or bundletool)".to_string(),
+            )
+        })?
+        .read_to_end(&mut manifest)?;
+
+    let mut badging = parse_manifest(&manifest)?;
+
+    // Native libraries live under lib/<abi>/
+    for name in archive.file_names() {
+        let mut parts = name.split('/');
+        if let (Some("lib"), Some(abi), Some(_)) = (parts.next(), parts.next(), parts.next()) {
+            if !badging.native_abis.iter().any(|a| a == abi) {
+                badging.native_abis.push(abi.to_string());
+            }
+        }
+    }
+    badging.native_abis.sort();
+
+    debug!(package = %badging.package_name, "read badging from APK manifest");
+    Ok(badging)
+}
+
+/// Locate `aapt2`: the configured path, then `PATH`, then the newest
+/// `build-tools/<version>/aapt2` under `ANDROID_HOME` or `ANDROID_SDK_ROOT`
+pub fn find_aapt2(configured: Option<&Path>) -> Option<PathBuf> {
+    if let Some(path) = configured {
+        if path.is_file() {
+            return Some(path.to_path_buf());
+        }
+        debug!(path = %path.display(), "configured aapt2 not found");
+    }
+
+    let binary = if cfg!(windows) { "aapt2.exe" } else { "aapt2" };
+    if let Some(found) = std::env::var_os("PATH")
+        .iter()
+        .flat_map(std::env::split_paths)
+        .map(|dir| dir.join(binary))
+        .find(|p| p.is_file())
+    {
+        return Some(found);
+    }
+
+    ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+        .iter()
+        .filter_map(std::env::var_os)
+        .find_map(|sdk| find_in_sdk(Path::new(&sdk), binary))
+}
+
+/// Newest `build-tools/<version>/<binary>` in an SDK directory
+pub fn find_in_sdk(sdk: &Path, binary: &str) -> Option<PathBuf> {
+    let entries = std::fs::read_dir(sdk.join("build-tools")).ok()?;
+    let mut candidates: Vec<(Vec<u32>, PathBuf)> = entries
+        .filter_map(|entry| {
+            let dir = entry.ok()?.path();
+            let tool = dir.join(binary);
+            if !tool.is_file() {
+                return None;
+            }
+            let version = dir
+                .file_name()?
+                .to_str()?
+                .split(|c: char| !c.is_ascii_digit())
+                .filter_map(|part| part.parse().ok())
+                .collect();
+            Some((version, tool))
+        })
+        .collect();
+    candidates.sort();
+    candidates.pop().map(|(_, tool)| tool)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::google_play::axml::fixtures::manifest;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    fn write_apk(dir: &Path, manifest: &[u8]) -> PathBuf {
+        let path = dir.join("app.apk");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("AndroidManifest.xml", options).unwrap();
+        zip.write_all(manifest).unwrap();
+        for abi in ["x86_64", "arm64-v8a"] {
+            zip.start_file(format!("lib/{}/libapp.so", abi), options)
+                .unwrap();
+            zip.write_all(b"\x7fELF").unwrap();
+        }
+        zip.start_file("classes.dex", options).unwrap();
+        zip.finish().unwrap();
+        path
+    }
+
+    #[test]
+    fn test_read_apk_fallback() {
+        let temp = TempDir::new().unwrap();
+        let apk = write_apk(temp.path(), &manifest(false));
+
+        let badging = read_apk(&apk).unwrap();
+        assert_eq!(badging.package_name, "com.example.app");
+        assert_eq!(badging.version_code, "21");
+        assert_eq!(badging.version_name, "2.1.0");
+        assert_eq!(badging.min_sdk, Some(24));
+        assert_eq!(badging.target_sdk, Some(34));
+        assert_eq!(badging.permissions, vec!["android.permission.INTERNET"]);
+        assert_eq!(badging.native_abis, vec!["arm64-v8a", "x86_64"]);
+    }
+
+    #[test]
+    fn test_find_newest_build_tools() {
+        let temp = TempDir::new().unwrap();
+        for version in ["30.0.3", "34.0.0", "9.0.0"] {
+            let dir = temp.path().join("build-tools").join(version);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("aapt2"), "").unwrap();
+        }
+        std::fs::create_dir_all(temp.path().join("build-tools/35.0.0-rc1")).unwrap();
+
+        assert_eq!(
+            find_in_sdk(temp.path(), "aapt2"),
+            Some(temp.path().join("build-tools/34.0.0/aapt2"))
+        );
+
+        let configured = temp.path().join("build-tools/30.0.3/aapt2");
+        assert_eq!(find_aapt2(Some(&configured)), Some(configured));
+    }
+}
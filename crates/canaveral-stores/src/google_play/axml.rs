@@ -0,0 +1,395 @@
+//! Android binary XML (AXML) manifest decoding
+//!
+//! A compiled `AndroidManifest.xml` is a string pool, a resource ID map and a
+//! stream of namespace/element chunks. Only the manifest attributes needed
+//! for [`Badging`] are decoded; values that are resource references (such as
+//! a label pointing at `@string/...`) would need `resources.arsc` and are
+//! left unset.
+
+use crate::error::{Result, StoreError};
+
+use super::badging::Badging;
+
+const RES_STRING_POOL_TYPE: u16 = 0x0001;
+const RES_XML_TYPE: u16 = 0x0003;
+const RES_XML_START_ELEMENT_TYPE: u16 = 0x0102;
+const RES_XML_RESOURCE_MAP_TYPE: u16 = 0x0180;
+
+const UTF8_FLAG: u32 = 1 << 8;
+const NO_INDEX: u32 = 0xffff_ffff;
+
+const TYPE_STRING: u8 = 0x03;
+const TYPE_INT_DEC: u8 = 0x10;
+const TYPE_INT_HEX: u8 = 0x11;
+
+/// `android:` attribute resource IDs, for manifests whose attribute names
+/// were stripped from the string pool
+const ATTR_IDS: &[(u32, &str)] = &[
+    (0x0101_0003, "name"),
+    (0x0101_020c, "minSdkVersion"),
+    (0x0101_021b, "versionCode"),
+    (0x0101_021c, "versionName"),
+    (0x0101_0270, "targetSdkVersion"),
+];
+
+/// Decode the manifest fields of a binary `AndroidManifest.xml`
+pub fn parse_manifest(data: &[u8]) -> Result<Badging> {
+    let invalid =
+        |msg: &str| StoreError::InvalidArtifact(format!("Invalid binary manifest: {}", msg));
+
+    if read_u16(data, 0) != Some(RES_XML_TYPE) {
+        return Err(invalid("not an AXML document"));
+    }
+    let header_size = read_u16(data, 2).ok_or_else(|| invalid("truncated header"))? as usize;
+
+    let mut strings = Vec::new();
+    let mut resource_ids = Vec::new();
+    let mut badging = Badging::default();
+
+    let mut offset = header_size;
+    while offset + 8 <= data.len() {
+        let chunk_type = read_u16(data, offset).ok_or_else(|| invalid("truncated chunk"))?;
+        let chunk_size =
+            read_u32(data, offset + 4).ok_or_else(|| invalid("truncated chunk"))? as usize;
+        if chunk_size < 8 || offset + chunk_size > data.len() {
+            return Err(invalid("chunk size out of bounds"));
+        }
+        let chunk = &data[offset..offset + chunk_size];
+
+        match chunk_type {
+            RES_STRING_POOL_TYPE => {
+                strings = parse_string_pool(chunk).ok_or_else(|| invalid("bad string pool"))?
+            }
+            RES_XML_RESOURCE_MAP_TYPE => {
+                resource_ids = chunk[8..]
+                    .chunks_exact(4)
+                    .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+                    .collect();
+            }
+            RES_XML_START_ELEMENT_TYPE => {
+                let element = parse_element(chunk, &strings, &resource_ids)
+                    .ok_or_else(|| invalid("bad element"))?;
+                apply_element(&mut badging, &element);
+            }
+            _ => {}
+        }
+        offset += chunk_size;
+    }
+
+    if badging.package_name.is_empty() {
+        return Err(invalid("manifest has no package attribute"));
+    }
+    Ok(badging)
+}
+
+struct Element {
+    name: String,
+    attributes: Vec<(String, Option<String>)>,
+}
+
+impl Element {
+    fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, v)| v.as_deref())
+    }
+}
+
+fn apply_element(badging: &mut Badging, element: &Element) {
+    match element.name.as_str() {
+        "manifest" => {
+            badging.package_name = element.attr("package").unwrap_or_default().to_string();
+            badging.version_code = element.attr("versionCode").unwrap_or_default().to_string();
+            badging.version_name = element.attr("versionName").unwrap_or_default().to_string();
+        }
+        "uses-sdk" => {
+            badging.min_sdk = element.attr("minSdkVersion").and_then(|v| v.parse().ok());
+            badging.target_sdk = element
+                .attr("targetSdkVersion")
+                .and_then(|v| v.parse().ok());
+        }
+        "uses-permission" | "uses-permission-sdk-23" => {
+            if let Some(name) = element.attr("name") {
+                badging.permissions.push(name.to_string());
+            }
+        }
+        _ => {}
+    }
+}
+
+fn parse_element(chunk: &[u8], strings: &[String], resource_ids: &[u32]) -> Option<Element> {
+    let header_size = read_u16(chunk, 2)? as usize;
+    let name = string_at(strings, read_u32(chunk, header_size + 4)?)?.to_string();
+    let attribute_start = read_u16(chunk, header_size + 8)? as usize;
+    let attribute_size = read_u16(chunk, header_size + 10)? as usize;
+    let attribute_count = read_u16(chunk, header_size + 12)? as usize;
+
+    let mut attributes = Vec::with_capacity(attribute_count);
+    for i in 0..attribute_count {
+        let at = header_size + attribute_start + i * attribute_size;
+        let name_index = read_u32(chunk, at + 4)?;
+        let raw_value = read_u32(chunk, at + 8)?;
+        let data_type = *chunk.get(at + 15)?;
+        let data = read_u32(chunk, at + 16)?;
+
+        let mut attr_name = string_at(strings, name_index)
+            .unwrap_or_default()
+            .to_string();
+        if attr_name.is_empty() {
+            if let Some(id) = resource_ids.get(name_index as usize) {
+                if let Some((_, known)) = ATTR_IDS.iter().find(|(i, _)| i == id) {
+                    attr_name = known.to_string();
+                }
+            }
+        }
+
+        let value = match data_type {
+            TYPE_STRING => string_at(strings, data).map(str::to_string),
+            TYPE_INT_DEC | TYPE_INT_HEX => Some(data.to_string()),
+            _ if raw_value != NO_INDEX => string_at(strings, raw_value).map(str::to_string),
+            _ => None,
+        };
+        attributes.push((attr_name, value));
+    }
+
+    Some(Element { name, attributes })
+}
+
+fn parse_string_pool(chunk: &[u8]) -> Option<Vec<String>> {
+    let count = read_u32(chunk, 8)? as usize;
+    let flags = read_u32(chunk, 16)?;
+    let strings_start = read_u32(chunk, 20)? as usize;
+    let header_size = read_u16(chunk, 2)? as usize;
+
+    (0..count)
+        .map(|i| {
+            let at = strings_start + read_u32(chunk, header_size + i * 4)? as usize;
+            if flags & UTF8_FLAG != 0 {
+                // UTF-16 length, then UTF-8 length, then the bytes
+                let (_, skip) = utf8_length(chunk, at)?;
+                let (len, skip2) = utf8_length(chunk, at + skip)?;
+                let start = at + skip + skip2;
+                let bytes = chunk.get(start..start + len)?;
+                Some(String::from_utf8_lossy(bytes).into_owned())
+            } else {
+                let (len, skip) = utf16_length(chunk, at)?;
+                let units: Vec<u16> = (0..len)
+                    .map(|j| read_u16(chunk, at + skip + j * 2))
+                    .collect::<Option<_>>()?;
+                Some(String::from_utf16_lossy(&units))
+            }
+        })
+        .collect()
+}
+
+fn utf8_length(data: &[u8], at: usize) -> Option<(usize, usize)> {
+    let first = *data.get(at)? as usize;
+    if first & 0x80 != 0 {
+        Some((((first & 0x7f) << 8) | *data.get(at + 1)? as usize, 2))
+    } else {
+        Some((first, 1))
+    }
+}
+
+fn utf16_length(data: &[u8], at: usize) -> Option<(usize, usize)> {
+    let first = read_u16(data, at)? as usize;
+    if first & 0x8000 != 0 {
+        Some((
+            ((first & 0x7fff) << 16) | read_u16(data, at + 2)? as usize,
+            4,
+        ))
+    } else {
+        Some((first, 2))
+    }
+}
+
+fn string_at(strings: &[String], index: u32) -> Option<&str> {
+    strings.get(index as usize).map(String::as_str)
+}
+
+fn read_u16(data: &[u8], at: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(data.get(at..at + 2)?.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(data.get(at..at + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    //! Minimal AXML encoder for building manifest fixtures
+
+    use super::*;
+
+    /// Encodes AXML documents
+    struct AxmlWriter {
+        strings: Vec<String>,
+        resource_ids: Vec<u32>,
+        body: Vec<u8>,
+    }
+
+    enum Value {
+        Str(&'static str),
+        Int(u32),
+    }
+
+    impl AxmlWriter {
+        /// `attr_ids` are the resource IDs of the leading (android:) attribute
+        /// names, which are interned first as the resource map requires
+        fn new(attr_ids: &[(u32, &str)], strip_names: bool) -> Self {
+            let mut writer = Self {
+                strings: Vec::new(),
+                resource_ids: Vec::new(),
+                body: Vec::new(),
+            };
+            for (id, name) in attr_ids {
+                writer.strings.push(if strip_names {
+                    String::new()
+                } else {
+                    name.to_string()
+                });
+                writer.resource_ids.push(*id);
+            }
+            writer
+        }
+
+        fn intern(&mut self, s: &str) -> u32 {
+            if let Some(i) = self
+                .strings
+                .iter()
+                .skip(self.resource_ids.len())
+                .position(|x| x == s)
+            {
+                return (i + self.resource_ids.len()) as u32;
+            }
+            self.strings.push(s.to_string());
+            (self.strings.len() - 1) as u32
+        }
+
+        fn start(&mut self, name: &str, attrs: &[(u32, Value)]) {
+            let name = self.intern(name);
+            let mut chunk = Vec::new();
+            chunk.extend_from_slice(&RES_XML_START_ELEMENT_TYPE.to_le_bytes());
+            chunk.extend_from_slice(&16u16.to_le_bytes());
+            chunk.extend_from_slice(&0u32.to_le_bytes()); // size, patched below
+            chunk.extend_from_slice(&1u32.to_le_bytes()); // line number
+            chunk.extend_from_slice(&NO_INDEX.to_le_bytes()); // comment
+            chunk.extend_from_slice(&NO_INDEX.to_le_bytes()); // namespace
+            chunk.extend_from_slice(&name.to_le_bytes());
+            chunk.extend_from_slice(&20u16.to_le_bytes()); // attribute start
+            chunk.extend_from_slice(&20u16.to_le_bytes()); // attribute size
+            chunk.extend_from_slice(&(attrs.len() as u16).to_le_bytes());
+            chunk.extend_from_slice(&[0u8; 6]); // id/class/style indices
+            for (attr_name, value) in attrs {
+                let (raw, data_type, data) = match value {
+                    Value::Str(s) => {
+                        let i = self.intern(s);
+                        (i, TYPE_STRING, i)
+                    }
+                    Value::Int(n) => (NO_INDEX, TYPE_INT_DEC, *n),
+                };
+                chunk.extend_from_slice(&NO_INDEX.to_le_bytes());
+                chunk.extend_from_slice(&attr_name.to_le_bytes());
+                chunk.extend_from_slice(&raw.to_le_bytes());
+                chunk.extend_from_slice(&8u16.to_le_bytes());
+                chunk.push(0);
+                chunk.push(data_type);
+                chunk.extend_from_slice(&data.to_le_bytes());
+            }
+            let size = chunk.len() as u32;
+            chunk[4..8].copy_from_slice(&size.to_le_bytes());
+            self.body.extend(chunk);
+        }
+
+        fn finish(self) -> Vec<u8> {
+            // UTF-16 string pool
+            let mut data = Vec::new();
+            let mut offsets = Vec::new();
+            for s in &self.strings {
+                offsets.push(data.len() as u32);
+                let units: Vec<u16> = s.encode_utf16().collect();
+                data.extend_from_slice(&(units.len() as u16).to_le_bytes());
+                for u in units {
+                    data.extend_from_slice(&u.to_le_bytes());
+                }
+                data.extend_from_slice(&[0, 0]);
+            }
+            while data.len() % 4 != 0 {
+                data.push(0);
+            }
+            let strings_start = 28 + offsets.len() * 4;
+            let mut pool = Vec::new();
+            pool.extend_from_slice(&RES_STRING_POOL_TYPE.to_le_bytes());
+            pool.extend_from_slice(&28u16.to_le_bytes());
+            pool.extend_from_slice(&((strings_start + data.len()) as u32).to_le_bytes());
+            pool.extend_from_slice(&(self.strings.len() as u32).to_le_bytes());
+            pool.extend_from_slice(&0u32.to_le_bytes()); // styles
+            pool.extend_from_slice(&0u32.to_le_bytes()); // flags: UTF-16
+            pool.extend_from_slice(&(strings_start as u32).to_le_bytes());
+            pool.extend_from_slice(&0u32.to_le_bytes());
+            for o in offsets {
+                pool.extend_from_slice(&o.to_le_bytes());
+            }
+            pool.extend(data);
+
+            let mut map = Vec::new();
+            map.extend_from_slice(&RES_XML_RESOURCE_MAP_TYPE.to_le_bytes());
+            map.extend_from_slice(&8u16.to_le_bytes());
+            map.extend_from_slice(&((8 + self.resource_ids.len() * 4) as u32).to_le_bytes());
+            for id in &self.resource_ids {
+                map.extend_from_slice(&id.to_le_bytes());
+            }
+
+            let total = 8 + pool.len() + map.len() + self.body.len();
+            let mut out = Vec::new();
+            out.extend_from_slice(&RES_XML_TYPE.to_le_bytes());
+            out.extend_from_slice(&8u16.to_le_bytes());
+            out.extend_from_slice(&(total as u32).to_le_bytes());
+            out.extend(pool);
+            out.extend(map);
+            out.extend(self.body);
+            out
+        }
+    }
+
+    /// Manifest for com.example.app 2.1.0 (21), min SDK 24, target SDK 34
+    pub fn manifest(strip_names: bool) -> Vec<u8> {
+        // Indices 0..5 are the android: attribute names, in ATTR_IDS order
+        let mut w = AxmlWriter::new(ATTR_IDS, strip_names);
+        let package = w.intern("package");
+        w.start(
+            "manifest",
+            &[
+                (package, Value::Str("com.example.app")),
+                (2, Value::Int(21)),
+                (3, Value::Str("2.1.0")),
+            ],
+        );
+        w.start("uses-sdk", &[(1, Value::Int(24)), (4, Value::Int(34))]);
+        w.start(
+            "uses-permission",
+            &[(0, Value::Str("android.permission.INTERNET"))],
+        );
+        w.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fixtures::manifest;
+    use super::*;
+
+    #[test]
+    fn test_stripped_attribute_names_use_resource_ids() {
+        let badging = parse_manifest(&manifest(true)).unwrap();
+        assert_eq!(badging.package_name, "com.example.app");
+        assert_eq!(badging.version_code, "21");
+        assert_eq!(badging.target_sdk, Some(34));
+
+        assert!(matches!(
+            parse_manifest(b"<manifest/>"),
+            Err(StoreError::InvalidArtifact(_))
+        ));
+    }
+}
@@ -0,0 +1,197 @@
+//! Parsing of `aapt2 dump badging` output
+//!
+//! Badging lines look like `key: name='value' other='value'` or
+//! `key: 'a' 'b' 'c'`. Values are single-quoted and may contain spaces, so
+//! lines are scanned for quoted strings rather than split on whitespace.
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::AppInfo;
+
+/// Lowest `targetSdkVersion` Google Play accepts for new apps and updates
+/// (API 35 since August 31, 2025)
+pub const MIN_TARGET_SDK: u32 = 35;
+
+/// Locale placeholder aapt2 lists for resources without a qualifier
+const DEFAULT_LOCALE: &str = "--_--";
+
+/// App details reported by `aapt2 dump badging`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Badging {
+    /// Application ID
+    pub package_name: String,
+    /// `versionCode`
+    pub version_code: String,
+    /// `versionName`
+    pub version_name: String,
+    /// Default application label
+    pub label: Option<String>,
+    /// `minSdkVersion`
+    pub min_sdk: Option<u32>,
+    /// `targetSdkVersion`
+    pub target_sdk: Option<u32>,
+    /// Native ABIs (`native-code` and `alt-native-code`)
+    pub native_abis: Vec<String>,
+    /// Resource locales, excluding the default
+    pub locales: Vec<String>,
+    /// Requested permissions
+    pub permissions: Vec<String>,
+}
+
+impl Badging {
+    /// Parse the output of `aapt2 dump badging`
+    pub fn parse(output: &str) -> Self {
+        let mut badging = Self::default();
+
+        for line in output.lines() {
+            let Some((key, rest)) = line.split_once(':') else {
+                continue;
+            };
+            match key {
+                "package" => {
+                    badging.package_name = attr(rest, "name").unwrap_or_default();
+                    badging.version_code = attr(rest, "versionCode").unwrap_or_default();
+                    badging.version_name = attr(rest, "versionName").unwrap_or_default();
+                }
+                "application-label" => badging.label = quoted(rest).into_iter().next(),
+                "sdkVersion" | "minSdkVersion" => badging.min_sdk = first_number(rest),
+                "targetSdkVersion" => badging.target_sdk = first_number(rest),
+                "native-code" | "alt-native-code" => {
+                    for abi in quoted(rest) {
+                        if !badging.native_abis.contains(&abi) {
+                            badging.native_abis.push(abi);
+                        }
+                    }
+                }
+                "locales" => {
+                    badging.locales = quoted(rest)
+                        .into_iter()
+                        .filter(|l| l != DEFAULT_LOCALE)
+                        .collect();
+                }
+                _ if key.starts_with("uses-permission") => {
+                    if let Some(name) = attr(rest, "name") {
+                        if !badging.permissions.contains(&name) {
+                            badging.permissions.push(name);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        badging
+    }
+
+    /// Whether the target SDK is below [`MIN_TARGET_SDK`]
+    pub fn targets_outdated_sdk(&self) -> bool {
+        self.target_sdk.is_some_and(|sdk| sdk < MIN_TARGET_SDK)
+    }
+
+    /// Store-agnostic app info for an artifact of `size` bytes
+    pub fn to_app_info(&self, size: u64) -> AppInfo {
+        AppInfo {
+            identifier: self.package_name.clone(),
+            version: self.version_name.clone(),
+            build_number: self.version_code.clone(),
+            name: self.label.clone(),
+            min_os_version: self.min_sdk.map(|sdk| sdk.to_string()),
+            platforms: vec!["Android".to_string()],
+            size,
+            sha256: None,
+        }
+    }
+}
+
+/// All single-quoted values in `text`, in order
+fn quoted(text: &str) -> Vec<String> {
+    text.split('\'')
+        .skip(1)
+        .step_by(2)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Value of `key='...'` in `text`
+fn attr(text: &str, key: &str) -> Option<String> {
+    let prefix = format!("{}='", key);
+    let start = text
+        .match_indices(&prefix)
+        .map(|(i, _)| i)
+        .find(|&i| i == 0 || text.as_bytes()[i - 1] == b' ')?
+        + prefix.len();
+    let end = text[start..].find('\'')? + start;
+    Some(text[start..end].to_string())
+}
+
+fn first_number(text: &str) -> Option<u32> {
+    quoted(text).first()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BADGING: &str = "\
+package: name='com.example.camera' versionCode='4201' versionName='4.2.0 beta' platformBuildVersionName='14' compileSdkVersion='34'
+sdkVersion:'24'
+targetSdkVersion:'33'
+uses-permission: name='android.permission.INTERNET'
+uses-permission: name='android.permission.CAMERA'
+uses-permission: name='android.permission.WRITE_EXTERNAL_STORAGE' maxSdkVersion='28'
+uses-permission-sdk-23: name='android.permission.ACCESS_FINE_LOCATION'
+application-label:'Example Camera'
+application-label-de:'Beispielkamera'
+application: label='Example Camera' icon='res/mipmap/ic_launcher.png'
+launchable-activity: name='com.example.camera.MainActivity'  label='' icon=''
+locales: '--_--' 'de' 'en-GB' 'fr'
+densities: '160' '240' '320' '480'
+native-code: 'arm64-v8a' 'armeabi-v7a' 'x86_64'
+alt-native-code: 'x86'
+";
+
+    #[test]
+    fn test_parse_badging() {
+        let badging = Badging::parse(BADGING);
+
+        assert_eq!(badging.package_name, "com.example.camera");
+        assert_eq!(badging.version_code, "4201");
+        assert_eq!(badging.version_name, "4.2.0 beta");
+        assert_eq!(badging.label.as_deref(), Some("Example Camera"));
+        assert_eq!(badging.min_sdk, Some(24));
+        assert_eq!(badging.target_sdk, Some(33));
+        assert_eq!(
+            badging.native_abis,
+            vec!["arm64-v8a", "armeabi-v7a", "x86_64", "x86"]
+        );
+        assert_eq!(badging.locales, vec!["de", "en-GB", "fr"]);
+        assert_eq!(
+            badging.permissions,
+            vec![
+                "android.permission.INTERNET",
+                "android.permission.CAMERA",
+                "android.permission.WRITE_EXTERNAL_STORAGE",
+                "android.permission.ACCESS_FINE_LOCATION",
+            ]
+        );
+        assert!(badging.targets_outdated_sdk());
+
+        let info = badging.to_app_info(1024);
+        assert_eq!(info.identifier, "com.example.camera");
+        assert_eq!(info.build_number, "4201");
+        assert_eq!(info.min_os_version.as_deref(), Some("24"));
+    }
+
+    #[test]
+    fn test_current_target_sdk_not_flagged() {
+        let badging = Badging::parse(
+            "package: name='com.example' versionCode='1' versionName='1.0'\ntargetSdkVersion:'35'\n",
+        );
+        assert!(!badging.targets_outdated_sdk());
+        assert!(badging.native_abis.is_empty());
+        assert!(badging.permissions.is_empty());
+
+        // Unknown target SDK is not reported as outdated
+        assert!(!Badging::default().targets_outdated_sdk());
+    }
+}
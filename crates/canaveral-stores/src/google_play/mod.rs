@@ -4,7 +4,11 @@
 //!
 //! ## Authentication
 //!
-//! Uses a Google Cloud service account with Google Play Developer API access.
+//! Uses a Google Cloud service account with Google Play Developer API access,
+//! either through a service account key file or through workload identity
+//! federation (an `external_account` credential file), which lets CI exchange
+//! its own OIDC token instead of storing a long-lived key. Extra OAuth scopes
+//! can be requested with [`GooglePlayConfig::scopes`].
 //!
 //! ## Usage
 //!
@@ -15,42 +19,35 @@
 //! store.upload(&artifact_path, &options).await?;
 //! ```
 
+mod apk;
+mod auth;
+mod axml;
+mod badging;
+mod rollout;
+mod withdraw;
+
+pub use apk::{find_aapt2, find_in_sdk};
+pub use auth::ANDROID_PUBLISHER_SCOPE;
+pub use badging::{Badging, MIN_TARGET_SDK};
+pub use rollout::{RolloutAction, RolloutSchedule, RolloutState, RolloutStep};
+
+use auth::GoogleAuth;
+
+use crate::cancel::{cancellable, with_rollback};
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
+use crate::release_notes::load_release_notes;
+use crate::token::TokenTiming;
 use crate::traits::{StagedRolloutSupport, StoreAdapter, TrackSupport};
 use crate::types::*;
-use chrono::{Duration, Utc};
+use chrono::Utc;
 use reqwest::Client;
-use serde::{Deserialize, Serialize};
+use serde::Deserialize;
+use std::collections::HashMap;
 use std::path::Path;
-use std::sync::Arc;
-use tokio::sync::RwLock;
 use tracing::{debug, info, instrument};
 
 const API_BASE_URL: &str = "https://androidpublisher.googleapis.com/androidpublisher/v3";
-const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
-
-/// Google service account credentials
-#[derive(Debug, Deserialize)]
-struct ServiceAccountKey {
-    client_email: String,
-    private_key: String,
-    #[allow(dead_code)]
-    token_uri: Option<String>,
-}
-
-/// OAuth token response
-#[derive(Debug, Deserialize)]
-struct TokenResponse {
-    access_token: String,
-    expires_in: i64,
-}
-
-/// Token cache for thread-safe access
-#[derive(Debug, Default)]
-struct TokenCache {
-    access_token: Option<String>,
-    expires_at: Option<chrono::DateTime<Utc>>,
-}
 
 /// Google Play Developer API client
 pub struct GooglePlayStore {
@@ -60,105 +57,48 @@ pub struct GooglePlayStore {
     /// HTTP client
     client: Client,
 
-    /// Token cache with interior mutability
-    token_cache: Arc<RwLock<TokenCache>>,
+    /// Developer API base URL
+    api_base_url: String,
 
-    /// Service account credentials
-    service_account: ServiceAccountKey,
+    /// Credentials and cached access token
+    auth: GoogleAuth,
 }
 
 impl GooglePlayStore {
     /// Create a new Google Play Store client
     pub fn new(config: GooglePlayConfig) -> Result<Self> {
-        // Load service account key
-        let key_content = std::fs::read_to_string(&config.service_account_key).map_err(|e| {
-            StoreError::ConfigurationError(format!("Failed to read service account key: {}", e))
-        })?;
-
-        let service_account: ServiceAccountKey =
-            serde_json::from_str(&key_content).map_err(|e| {
-                StoreError::InvalidCredentials(format!("Invalid service account key: {}", e))
-            })?;
+        let auth = GoogleAuth::from_file(&config.service_account_key, &config.scopes)?
+            .with_audience(config.jwt_audience.clone());
 
         Ok(Self {
             config,
-            client: Client::new(),
-            token_cache: Arc::new(RwLock::new(TokenCache::default())),
-            service_account,
+            client: default_client(),
+            api_base_url: API_BASE_URL.to_string(),
+            auth,
         })
     }
 
-    /// Get or refresh OAuth2 access token
-    async fn get_access_token(&self) -> Result<String> {
-        // Check if we have a valid cached token
-        {
-            let cache = self.token_cache.read().await;
-            if let (Some(token), Some(expires)) = (&cache.access_token, cache.expires_at) {
-                if Utc::now() < expires - Duration::minutes(5) {
-                    return Ok(token.clone());
-                }
-            }
-        }
-
-        // Generate JWT for service account authentication
-        let now = Utc::now();
-        let exp = now + Duration::hours(1);
-
-        #[derive(Serialize)]
-        struct Claims {
-            iss: String,
-            scope: String,
-            aud: String,
-            iat: i64,
-            exp: i64,
-        }
-
-        let claims = Claims {
-            iss: self.service_account.client_email.clone(),
-            scope: "https://www.googleapis.com/auth/androidpublisher".to_string(),
-            aud: TOKEN_URL.to_string(),
-            iat: now.timestamp(),
-            exp: exp.timestamp(),
-        };
-
-        let encoding_key =
-            jsonwebtoken::EncodingKey::from_rsa_pem(self.service_account.private_key.as_bytes())
-                .map_err(|e| {
-                    StoreError::InvalidCredentials(format!("Invalid private key: {}", e))
-                })?;
-
-        let jwt = jsonwebtoken::encode(
-            &jsonwebtoken::Header::new(jsonwebtoken::Algorithm::RS256),
-            &claims,
-            &encoding_key,
-        )?;
-
-        // Exchange JWT for access token
-        let response = self
-            .client
-            .post(TOKEN_URL)
-            .form(&[
-                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
-                ("assertion", &jwt),
-            ])
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let error_text = response.text().await.unwrap_or_default();
-            return Err(StoreError::AuthenticationFailed(error_text));
-        }
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
 
-        let token_response: TokenResponse = response.json().await?;
+    /// Use a different Developer API base URL (for testing or proxies)
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
 
-        // Cache the token
-        {
-            let mut cache = self.token_cache.write().await;
-            cache.access_token = Some(token_response.access_token.clone());
-            cache.expires_at = Some(Utc::now() + Duration::seconds(token_response.expires_in));
-        }
+    /// Set when access tokens are refreshed and the clock skew to allow for
+    pub fn with_token_timing(mut self, timing: TokenTiming) -> Self {
+        self.auth = self.auth.with_timing(timing);
+        self
+    }
 
-        Ok(token_response.access_token)
+    /// Get or refresh OAuth2 access token
+    async fn get_access_token(&self) -> Result<String> {
+        self.auth.access_token(&self.client).await
     }
 
     /// Make an authenticated API request
@@ -169,7 +109,7 @@ impl GooglePlayStore {
         body: Option<serde_json::Value>,
     ) -> Result<T> {
         let token = self.get_access_token().await?;
-        let url = format!("{}{}", API_BASE_URL, endpoint);
+        let url = format!("{}{}", self.api_base_url, endpoint);
 
         let mut request = self
             .client
@@ -231,6 +171,64 @@ impl GooglePlayStore {
         Ok(())
     }
 
+    /// Release notes for an upload
+    ///
+    /// Locales without explicit notes are filled from the changelogs in the
+    /// metadata directory: `google_play/<package>/` (as written by metadata
+    /// pulls) or fastlane's `android/`.
+    async fn release_notes_for(
+        &self,
+        options: &UploadOptions,
+        version_code: i64,
+    ) -> Result<HashMap<String, String>> {
+        let mut notes = options.release_notes.clone();
+        let Some(metadata_path) = &options.metadata_path else {
+            return Ok(notes);
+        };
+
+        let candidates = [
+            metadata_path
+                .join("google_play")
+                .join(&self.config.package_name),
+            metadata_path.join("android"),
+        ];
+        if let Some(dir) = candidates.iter().find(|dir| dir.is_dir()) {
+            let loaded = load_release_notes(dir, Some(&version_code.to_string())).await?;
+            debug!(dir = %dir.display(), locales = loaded.len(), "loaded release notes");
+            for (locale, text) in loaded {
+                notes.entry(locale).or_insert(text);
+            }
+        }
+        Ok(notes)
+    }
+
+    /// Delete an uncommitted edit, discarding its changes
+    async fn delete_edit(&self, edit_id: &str) -> Result<()> {
+        let token = self.get_access_token().await?;
+        let url = format!(
+            "{}/applications/{}/edits/{}",
+            self.api_base_url, self.config.package_name, edit_id
+        );
+
+        let response = self
+            .client
+            .delete(&url)
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(StoreError::ApiError {
+                status: status.as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        info!(edit = edit_id, "deleted edit");
+        Ok(())
+    }
+
     /// Upload an APK or AAB to an edit
     async fn upload_binary(&self, edit_id: &str, path: &Path) -> Result<i64> {
         let token = self.get_access_token().await?;
@@ -327,78 +325,48 @@ impl GooglePlayStore {
         Ok(())
     }
 
-    /// Extract app info from APK/AAB
-    async fn extract_android_info(path: &Path) -> Result<AppInfo> {
-        // Use aapt2 to extract info
-        let output = tokio::process::Command::new("aapt2")
-            .args(["dump", "badging", path.to_str().unwrap()])
+    /// Read badging (package, SDK levels, ABIs, locales, permissions) from
+    /// an APK/AAB
+    ///
+    /// Uses `aapt2` when it can be found; otherwise APKs are read directly
+    /// from their zip and binary manifest, which covers the manifest fields
+    /// but not resource-backed ones such as the label or locales.
+    #[instrument(skip(self), fields(path = %path.display()))]
+    pub async fn inspect_artifact(&self, path: &Path) -> Result<Badging> {
+        let badging = match find_aapt2(self.config.aapt2_path.as_deref()) {
+            Some(aapt2) => Self::run_aapt2(&aapt2, path).await?,
+            None => {
+                debug!("aapt2 not found, reading APK manifest directly");
+                apk::read_apk(path)?
+            }
+        };
+
+        debug!(
+            package = %badging.package_name,
+            target_sdk = ?badging.target_sdk,
+            abis = ?badging.native_abis,
+            permissions = badging.permissions.len(),
+            "read artifact badging"
+        );
+        Ok(badging)
+    }
+
+    async fn run_aapt2(aapt2: &Path, path: &Path) -> Result<Badging> {
+        let output = tokio::process::Command::new(aapt2)
+            .arg("dump")
+            .arg("badging")
+            .arg(path)
             .output()
             .await
             .map_err(|e| StoreError::CommandFailed(format!("aapt2 failed: {}", e)))?;
 
-        let stdout = String::from_utf8_lossy(&output.stdout);
-
-        // Parse aapt2 output
-        let mut package_name = String::new();
-        let mut version_name = String::new();
-        let mut version_code = String::new();
-        let mut app_name = None;
-        let mut min_sdk = None;
-
-        for line in stdout.lines() {
-            if line.starts_with("package:") {
-                // package: name='com.example' versionCode='1' versionName='1.0.0'
-                for part in line.split_whitespace() {
-                    if part.starts_with("name='") {
-                        package_name = part
-                            .trim_start_matches("name='")
-                            .trim_end_matches('\'')
-                            .to_string();
-                    } else if part.starts_with("versionCode='") {
-                        version_code = part
-                            .trim_start_matches("versionCode='")
-                            .trim_end_matches('\'')
-                            .to_string();
-                    } else if part.starts_with("versionName='") {
-                        version_name = part
-                            .trim_start_matches("versionName='")
-                            .trim_end_matches('\'')
-                            .to_string();
-                    }
-                }
-            } else if line.starts_with("application-label:") {
-                app_name = Some(
-                    line.trim_start_matches("application-label:'")
-                        .trim_end_matches('\'')
-                        .to_string(),
-                );
-            } else if line.starts_with("sdkVersion:'") {
-                min_sdk = Some(
-                    line.trim_start_matches("sdkVersion:'")
-                        .trim_end_matches('\'')
-                        .to_string(),
-                );
-            }
-        }
-
-        if package_name.is_empty() {
+        let badging = Badging::parse(&String::from_utf8_lossy(&output.stdout));
+        if badging.package_name.is_empty() {
             return Err(StoreError::InvalidArtifact(
                 "Could not determine package name from APK/AAB".to_string(),
             ));
         }
-
-        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
-
-        Ok(AppInfo {
-            identifier: package_name,
-            version: version_name,
-            build_number: version_code,
-            name: app_name,
-            min_os_version: min_sdk,
-            platforms: vec!["Android".to_string()],
-            size,
-            sha256: None,
-        })
+        Ok(badging)
     }
 }
 
@@ -414,13 +382,14 @@ impl StoreAdapter for GooglePlayStore {
 
     fn is_available(&self) -> bool {
         // Check if we have valid credentials
-        !self.service_account.client_email.is_empty()
-            && !self.service_account.private_key.is_empty()
+        self.auth.is_configured()
     }
 
     #[instrument(skip(self), fields(store = "Google Play", path = %path.display()))]
     async fn validate_artifact(&self, path: &Path) -> Result<ValidationResult> {
-        let app_info = Self::extract_android_info(path).await?;
+        let badging = self.inspect_artifact(path).await?;
+        let size = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let app_info = badging.to_app_info(size);
 
         let mut errors = Vec::new();
         let mut warnings = Vec::new();
@@ -441,6 +410,14 @@ impl StoreAdapter for GooglePlayStore {
             warnings.push("Version name is empty".to_string());
         }
 
+        if badging.targets_outdated_sdk() {
+            warnings.push(format!(
+                "targetSdkVersion {} is below Google Play's minimum of {}; the upload may be rejected",
+                badging.target_sdk.unwrap_or_default(),
+                MIN_TARGET_SDK
+            ));
+        }
+
         if errors.is_empty() {
             Ok(ValidationResult {
                 valid: true,
@@ -485,15 +462,12 @@ impl StoreAdapter for GooglePlayStore {
             });
         }
 
+        let cancel = &options.cancellation;
+
         // Create edit session
         info!("Creating edit session...");
-        let edit_id = self.create_edit().await?;
+        let edit_id = cancellable(cancel, "edit", self.create_edit()).await?;
 
-        // Upload binary
-        info!("Uploading {}...", path.display());
-        let version_code = self.upload_binary(&edit_id, path).await?;
-
-        // Assign to track
         let track = options
             .track
             .as_ref()
@@ -501,13 +475,29 @@ impl StoreAdapter for GooglePlayStore {
             .map(|s| s.as_str())
             .unwrap_or("internal");
 
-        info!("Assigning to track '{}'...", track);
-        self.assign_to_track(
-            &edit_id,
-            track,
-            version_code,
-            options.rollout_percentage,
-            &options.release_notes,
+        // Until it is committed, a cancelled edit is deleted again
+        let version_code = with_rollback(
+            cancel,
+            "bundle upload",
+            async {
+                // Upload binary
+                info!("Uploading {}...", path.display());
+                let version_code = self.upload_binary(&edit_id, path).await?;
+                let release_notes = self.release_notes_for(options, version_code).await?;
+
+                // Assign to track
+                info!("Assigning to track '{}'...", track);
+                self.assign_to_track(
+                    &edit_id,
+                    track,
+                    version_code,
+                    options.rollout_percentage,
+                    &release_notes,
+                )
+                .await?;
+                Ok(version_code)
+            },
+            || self.delete_edit(&edit_id),
         )
         .await?;
 
@@ -548,6 +538,7 @@ impl StoreAdapter for GooglePlayStore {
             track: None,
             rollout_percentage: None,
             details: None,
+            console_url: None,
         })
     }
 
@@ -556,6 +547,11 @@ impl StoreAdapter for GooglePlayStore {
         Ok(Vec::new())
     }
 
+    #[instrument(skip(self), fields(store = "Google Play"))]
+    async fn withdraw_build(&self, build_id: &str) -> Result<()> {
+        self.remove_from_tracks(build_id).await
+    }
+
     fn supported_extensions(&self) -> &[&str] {
         &["apk", "aab"]
     }
@@ -567,11 +563,9 @@ impl StagedRolloutSupport for GooglePlayStore {
         let edit_id = self.create_edit().await?;
 
         // Get current track info and update rollout
-        let track = self.config.default_track.as_deref().unwrap_or("production");
-
         self.assign_to_track(
             &edit_id,
-            track,
+            self.rollout_track(),
             build_id.parse().unwrap_or(0),
             Some(percentage),
             &std::collections::HashMap::new(),
@@ -628,12 +622,47 @@ impl TrackSupport for GooglePlayStore {
 mod tests {
     use super::*;
 
+    /// Store authenticating through a workload identity token file, so the
+    /// only token request is a plain STS exchange against the mock
+    pub(super) fn store(temp: &tempfile::TempDir, url: &str) -> GooglePlayStore {
+        let token_file = temp.path().join("oidc.json");
+        std::fs::write(&token_file, r#"{"value": "oidc"}"#).unwrap();
+        let credentials = temp.path().join("credentials.json");
+        let json = serde_json::json!({
+            "type": "external_account",
+            "audience": "//iam.googleapis.com/projects/1/locations/global/workloadIdentityPools/ci/providers/github",
+            "subject_token_type": "urn:ietf:params:oauth:token-type:jwt",
+            "token_url": format!("{}/v1/token", url),
+            "credential_source": {
+                "file": token_file,
+                "format": { "type": "json", "subject_token_field_name": "value" }
+            }
+        });
+        std::fs::write(&credentials, json.to_string()).unwrap();
+
+        GooglePlayStore::new(GooglePlayConfig {
+            package_name: "com.example.app".to_string(),
+            service_account_key: credentials,
+            default_track: None,
+            aapt2_path: None,
+            scopes: Vec::new(),
+            jwt_audience: None,
+        })
+        .unwrap()
+        .with_api_base_url(url)
+    }
+
+    pub(super) const TOKEN: &str = r#"{"access_token": "ya29.test", "expires_in": 3600}"#;
+
     #[test]
     fn test_supported_extensions() {
         let _config = GooglePlayConfig {
             package_name: "com.example.app".to_string(),
             service_account_key: std::path::PathBuf::from("/tmp/key.json"),
             default_track: None,
+            aapt2_path: None,
+            scopes: Vec::new(),
+            jwt_audience: None,
         };
 
         // Can't create without valid key file, so just test extensions directly
@@ -641,4 +670,45 @@ mod tests {
         assert!(extensions.contains(&"apk"));
         assert!(extensions.contains(&"aab"));
     }
+
+    #[tokio::test]
+    async fn test_release_notes_from_metadata_changelogs() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let key = temp.path().join("key.json");
+        std::fs::write(
+            &key,
+            r#"{"client_email": "ci@example.iam.gserviceaccount.com", "private_key": "unused"}"#,
+        )
+        .unwrap();
+        let store = GooglePlayStore::new(GooglePlayConfig {
+            package_name: "com.example.app".to_string(),
+            service_account_key: key,
+            default_track: None,
+            aapt2_path: None,
+            scopes: Vec::new(),
+            jwt_audience: None,
+        })
+        .unwrap();
+
+        let locales = temp.path().join("metadata/android");
+        for (path, text) in [
+            ("en-US/changelogs/42.txt", "Offline mode"),
+            ("de-DE/changelogs/default.txt", "Fehlerbehebungen"),
+        ] {
+            let path = locales.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, text).unwrap();
+        }
+
+        let options = UploadOptions {
+            release_notes: HashMap::from([("de-DE".to_string(), "Neu".to_string())]),
+            metadata_path: Some(temp.path().join("metadata")),
+            ..Default::default()
+        };
+        let notes = store.release_notes_for(&options, 42).await.unwrap();
+
+        assert_eq!(notes["en-US"], "Offline mode");
+        // Explicit notes win over files
+        assert_eq!(notes["de-DE"], "Neu");
+    }
 }
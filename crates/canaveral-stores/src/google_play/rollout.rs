@@ -0,0 +1,427 @@
+//! Scheduled staged rollouts for Google Play
+//!
+//! A [`RolloutSchedule`] describes how a production release should ramp up
+//! (e.g. 5% on day 1, 20% on day 2, 50% on day 3, then 100%). Each call to
+//! [`GooglePlayStore::advance_rollout`] fetches the release's current user
+//! fraction from the API and moves it to the next step once the current step
+//! has been held long enough, or halts it if the crash rate observed by the
+//! caller exceeds the schedule's threshold. The schedule records when each
+//! step went live, so callers persist it between runs.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tracing::{info, instrument, warn};
+
+use super::GooglePlayStore;
+use crate::error::{Result, StoreError};
+use crate::traits::StagedRolloutSupport;
+
+/// Tolerance when comparing user fractions reported by the API
+const FRACTION_EPSILON: f64 = 1e-6;
+
+/// A single step of a rollout schedule
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RolloutStep {
+    /// Fraction of users receiving the release (0.0 - 1.0)
+    pub user_fraction: f64,
+    /// How long to stay at this fraction before advancing
+    pub hold: Duration,
+}
+
+impl RolloutStep {
+    /// Create a step
+    pub fn new(user_fraction: f64, hold: Duration) -> Self {
+        Self {
+            user_fraction,
+            hold,
+        }
+    }
+}
+
+/// Schedule for ramping up a staged rollout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RolloutSchedule {
+    /// When the first step went live
+    pub started_at: DateTime<Utc>,
+    /// When the current step went live; `started_at` until the first advance
+    #[serde(default)]
+    pub step_started_at: Option<DateTime<Utc>>,
+    /// Steps in ascending order of user fraction
+    pub steps: Vec<RolloutStep>,
+    /// Crash rate (0.0 - 1.0) above which the rollout is halted
+    pub max_crash_rate: Option<f64>,
+}
+
+impl RolloutSchedule {
+    /// Create a schedule starting at `started_at`
+    pub fn new(started_at: DateTime<Utc>) -> Self {
+        Self {
+            started_at,
+            step_started_at: None,
+            steps: Vec::new(),
+            max_crash_rate: None,
+        }
+    }
+
+    /// Append a step
+    pub fn with_step(mut self, user_fraction: f64, hold: Duration) -> Self {
+        self.steps.push(RolloutStep::new(user_fraction, hold));
+        self
+    }
+
+    /// Halt the rollout when the observed crash rate exceeds this value
+    pub fn with_max_crash_rate(mut self, max: f64) -> Self {
+        self.max_crash_rate = Some(max);
+        self
+    }
+
+    /// Check that fractions are within range and strictly increasing
+    pub fn validate(&self) -> Result<()> {
+        if self.steps.is_empty() {
+            return Err(StoreError::ConfigurationError(
+                "Rollout schedule has no steps".to_string(),
+            ));
+        }
+
+        let mut previous = 0.0;
+        for step in &self.steps {
+            if !(0.0..=1.0).contains(&step.user_fraction) || step.user_fraction <= previous {
+                return Err(StoreError::ConfigurationError(format!(
+                    "Rollout fractions must increase within 0.0 - 1.0 (got {} after {})",
+                    step.user_fraction, previous
+                )));
+            }
+            previous = step.user_fraction;
+        }
+
+        Ok(())
+    }
+
+    /// Decide what to do given the release's current state and the crash
+    /// rate observed by the caller (e.g. from Android vitals)
+    ///
+    /// The current step is the highest one whose fraction has been reached.
+    /// The next step becomes due once the current step's hold has elapsed
+    /// since it went live; the first step is due immediately.
+    pub fn next_action(
+        &self,
+        state: &RolloutState,
+        crash_rate: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> RolloutAction {
+        if state.halted {
+            return RolloutAction::AlreadyHalted;
+        }
+
+        if let (Some(rate), Some(max)) = (crash_rate, self.max_crash_rate) {
+            if rate > max {
+                return RolloutAction::Halt {
+                    crash_rate: rate,
+                    threshold: max,
+                };
+            }
+        }
+
+        let reached = self
+            .steps
+            .iter()
+            .rposition(|s| state.user_fraction + FRACTION_EPSILON >= s.user_fraction);
+
+        let next = reached.map_or(0, |i| i + 1);
+        let Some(step) = self.steps.get(next) else {
+            return RolloutAction::Complete;
+        };
+
+        let due = match reached {
+            Some(current) => {
+                let hold = chrono::Duration::from_std(self.steps[current].hold)
+                    .unwrap_or(chrono::Duration::MAX);
+                self.step_started_at.unwrap_or(self.started_at) + hold
+            }
+            None => now,
+        };
+
+        if now >= due {
+            RolloutAction::Advance {
+                from: state.user_fraction,
+                to: step.user_fraction,
+            }
+        } else {
+            RolloutAction::Wait { until: due }
+        }
+    }
+}
+
+/// Current state of a release on its track
+#[derive(Debug, Clone, PartialEq)]
+pub struct RolloutState {
+    /// Fraction of users currently receiving the release
+    pub user_fraction: f64,
+    /// Whether the rollout has been halted
+    pub halted: bool,
+}
+
+/// Outcome of evaluating a rollout schedule
+#[derive(Debug, Clone, PartialEq)]
+pub enum RolloutAction {
+    /// Moved to the next step
+    Advance { from: f64, to: f64 },
+    /// Next step is not due yet
+    Wait { until: DateTime<Utc> },
+    /// Halted because the crash rate exceeded the threshold
+    Halt { crash_rate: f64, threshold: f64 },
+    /// The release was already halted; nothing was changed
+    AlreadyHalted,
+    /// Every step has been reached
+    Complete,
+}
+
+impl GooglePlayStore {
+    /// Fetch the rollout state of a version code on the default track
+    pub async fn get_rollout_state(&self, build_id: &str) -> Result<RolloutState> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Release {
+            #[serde(default)]
+            version_codes: Vec<String>,
+            status: Option<String>,
+            user_fraction: Option<f64>,
+        }
+
+        #[derive(Deserialize)]
+        struct Track {
+            #[serde(default)]
+            releases: Vec<Release>,
+        }
+
+        let track = self.rollout_track();
+        let edit_id = self.create_edit().await?;
+        let endpoint = format!(
+            "/applications/{}/edits/{}/tracks/{}",
+            self.config.package_name, edit_id, track
+        );
+        let response = self
+            .api_request::<Track>(reqwest::Method::GET, &endpoint, None)
+            .await;
+        // The edit was only needed for reading
+        if let Err(e) = self.delete_edit(&edit_id).await {
+            warn!(edit = %edit_id, error = %e, "failed to delete rollout state edit");
+        }
+
+        let release = response?
+            .releases
+            .into_iter()
+            .find(|r| r.version_codes.iter().any(|c| c == build_id))
+            .ok_or_else(|| StoreError::BuildNotFound(build_id.to_string()))?;
+
+        let status = release.status.unwrap_or_default();
+        Ok(RolloutState {
+            user_fraction: release.user_fraction.unwrap_or(match status.as_str() {
+                "completed" => 1.0,
+                _ => 0.0,
+            }),
+            halted: status == "halted",
+        })
+    }
+
+    /// Advance a staged rollout along a schedule
+    ///
+    /// Fetches the current state from the API, then either bumps the release
+    /// to the next step, halts it when `crash_rate` is over the schedule's
+    /// threshold, or leaves it alone. Safe to call repeatedly (e.g. from a
+    /// cron job) as long as the schedule is saved after each call, since
+    /// an advance records when the new step went live.
+    #[instrument(skip(self, schedule), fields(store = "Google Play"))]
+    pub async fn advance_rollout(
+        &self,
+        build_id: &str,
+        schedule: &mut RolloutSchedule,
+        crash_rate: Option<f64>,
+        now: DateTime<Utc>,
+    ) -> Result<RolloutAction> {
+        schedule.validate()?;
+
+        let state = self.get_rollout_state(build_id).await?;
+        let action = schedule.next_action(&state, crash_rate, now);
+
+        match &action {
+            RolloutAction::Advance { from, to } if *to >= 1.0 => {
+                info!(build_id, from, "Completing staged rollout");
+                self.complete_rollout(build_id).await?;
+            }
+            RolloutAction::Advance { from, to } => {
+                info!(build_id, from, to, "Advancing staged rollout");
+                self.update_rollout(build_id, *to).await?;
+            }
+            RolloutAction::Halt {
+                crash_rate,
+                threshold,
+            } => {
+                warn!(
+                    build_id,
+                    crash_rate, threshold, "Crash rate over threshold, halting rollout"
+                );
+                self.halt_rollout(build_id).await?;
+            }
+            RolloutAction::Wait { until } => {
+                info!(build_id, until = %until, "Next rollout step not due yet");
+            }
+            RolloutAction::AlreadyHalted | RolloutAction::Complete => {}
+        }
+        if matches!(action, RolloutAction::Advance { .. }) {
+            schedule.step_started_at = Some(now);
+        }
+
+        Ok(action)
+    }
+
+    /// Track that staged rollouts are managed on
+    pub(crate) fn rollout_track(&self) -> &str {
+        self.config.default_track.as_deref().unwrap_or("production")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{store, TOKEN};
+    use super::*;
+    use canaveral_test_support::MockServer;
+
+    const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn schedule(start: DateTime<Utc>) -> RolloutSchedule {
+        RolloutSchedule::new(start)
+            .with_step(0.05, DAY)
+            .with_step(0.2, DAY)
+            .with_step(0.5, DAY)
+            .with_step(1.0, Duration::ZERO)
+    }
+
+    fn state(user_fraction: f64) -> RolloutState {
+        RolloutState {
+            user_fraction,
+            halted: false,
+        }
+    }
+
+    #[test]
+    fn test_advances_when_hold_elapsed() {
+        let start = Utc::now();
+        let schedule = schedule(start);
+        schedule.validate().unwrap();
+
+        assert_eq!(
+            schedule.next_action(&state(0.0), None, start),
+            RolloutAction::Advance {
+                from: 0.0,
+                to: 0.05
+            }
+        );
+
+        let after_one_day = start + chrono::Duration::hours(25);
+        assert_eq!(
+            schedule.next_action(&state(0.05), None, after_one_day),
+            RolloutAction::Advance {
+                from: 0.05,
+                to: 0.2
+            }
+        );
+
+        let mut schedule = schedule;
+        schedule.step_started_at = Some(start + chrono::Duration::days(2));
+        let after_three_days = start + chrono::Duration::days(3);
+        assert_eq!(
+            schedule.next_action(&state(0.5), None, after_three_days),
+            RolloutAction::Advance { from: 0.5, to: 1.0 }
+        );
+        assert_eq!(
+            schedule.next_action(&state(1.0), None, after_three_days),
+            RolloutAction::Complete
+        );
+    }
+
+    #[test]
+    fn test_hold_measured_from_last_step() {
+        let start = Utc::now();
+        let mut schedule = schedule(start);
+        // The 20% step went live late, a day and a half in
+        let advanced_at = start + chrono::Duration::hours(36);
+        schedule.step_started_at = Some(advanced_at);
+
+        let action = schedule.next_action(&state(0.2), None, start + chrono::Duration::days(2));
+        assert_eq!(
+            action,
+            RolloutAction::Wait {
+                until: advanced_at + chrono::Duration::days(1)
+            }
+        );
+    }
+
+    #[test]
+    fn test_halts_over_crash_threshold() {
+        let start = Utc::now();
+        let schedule = schedule(start).with_max_crash_rate(0.02);
+        let due = start + chrono::Duration::days(2);
+
+        assert_eq!(
+            schedule.next_action(&state(0.2), Some(0.035), due),
+            RolloutAction::Halt {
+                crash_rate: 0.035,
+                threshold: 0.02
+            }
+        );
+
+        let halted = RolloutState {
+            user_fraction: 0.2,
+            halted: true,
+        };
+        assert_eq!(
+            schedule.next_action(&halted, Some(0.035), due),
+            RolloutAction::AlreadyHalted
+        );
+
+        assert!(matches!(
+            schedule.next_action(&state(0.2), Some(0.01), due),
+            RolloutAction::Advance { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_rollout_state_deletes_its_edit() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let server = MockServer::serve(vec![
+            TOKEN,
+            r#"{"id": "edit-1"}"#,
+            r#"{"track": "production", "releases": [
+                {"status": "inProgress", "versionCodes": ["42"], "userFraction": 0.2}
+            ]}"#,
+            "",
+        ]);
+
+        let state = store(&temp, server.url())
+            .get_rollout_state("42")
+            .await
+            .unwrap();
+        assert_eq!(state, self::state(0.2));
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[2].line(),
+            "GET /applications/com.example.app/edits/edit-1/tracks/production"
+        );
+        assert_eq!(
+            requests[3].line(),
+            "DELETE /applications/com.example.app/edits/edit-1"
+        );
+    }
+
+    #[test]
+    fn test_validate_rejects_unordered_steps() {
+        let schedule = RolloutSchedule::new(Utc::now())
+            .with_step(0.5, DAY)
+            .with_step(0.2, DAY);
+        assert!(schedule.validate().is_err());
+        assert!(RolloutSchedule::new(Utc::now()).validate().is_err());
+    }
+}
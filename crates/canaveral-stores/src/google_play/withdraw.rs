@@ -0,0 +1,211 @@
+//! Withdrawing a version code from Google Play tracks
+//!
+//! Play has no "delete build" call; an uploaded bundle stays in the app's
+//! library. What can be undone is its assignment: [`remove_version_code`]
+//! takes it out of a track's releases, and the edit is committed with every
+//! track that changed, so the build no longer reaches any testers or users.
+
+use serde::Deserialize;
+use tracing::{info, instrument, warn};
+
+use super::GooglePlayStore;
+use crate::error::{Result, StoreError};
+
+/// Tracks of an edit, kept as raw JSON so unknown fields survive the update
+#[derive(Debug, Deserialize)]
+struct TracksList {
+    #[serde(default)]
+    tracks: Vec<serde_json::Value>,
+}
+
+/// Remove `version_code` from every release of `track`
+///
+/// Releases left without version codes are dropped. Returns whether the
+/// track changed.
+fn remove_version_code(track: &mut serde_json::Value, version_code: &str) -> bool {
+    let Some(releases) = track
+        .get_mut("releases")
+        .and_then(|releases| releases.as_array_mut())
+    else {
+        return false;
+    };
+
+    let mut changed = false;
+    for release in releases.iter_mut() {
+        if let Some(codes) = release
+            .get_mut("versionCodes")
+            .and_then(|codes| codes.as_array_mut())
+        {
+            let before = codes.len();
+            codes.retain(|code| code.as_str() != Some(version_code));
+            changed |= codes.len() != before;
+        }
+    }
+    releases.retain(|release| {
+        release
+            .get("versionCodes")
+            .and_then(|codes| codes.as_array())
+            .map_or(true, |codes| !codes.is_empty())
+    });
+    changed
+}
+
+impl GooglePlayStore {
+    /// Take `version_code` out of every track in a single committed edit
+    ///
+    /// Fails with [`StoreError::BuildNotFound`] when no track holds it. The
+    /// edit is deleted again if anything fails before the commit.
+    #[instrument(skip(self))]
+    pub(super) async fn remove_from_tracks(&self, version_code: &str) -> Result<()> {
+        let edit_id = self.create_edit().await?;
+        let result = self.remove_in_edit(&edit_id, version_code).await;
+        if result.is_err() {
+            if let Err(e) = self.delete_edit(&edit_id).await {
+                warn!(edit = %edit_id, error = %e, "failed to delete edit after withdraw error");
+            }
+        }
+        result
+    }
+
+    async fn remove_in_edit(&self, edit_id: &str, version_code: &str) -> Result<()> {
+        let endpoint = format!(
+            "/applications/{}/edits/{}/tracks",
+            self.config.package_name, edit_id
+        );
+        let list: TracksList = self
+            .api_request(reqwest::Method::GET, &endpoint, None)
+            .await?;
+
+        let mut withdrawn = Vec::new();
+        for mut track in list.tracks {
+            if !remove_version_code(&mut track, version_code) {
+                continue;
+            }
+            let name = track
+                .get("track")
+                .and_then(|name| name.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let _: serde_json::Value = self
+                .api_request(
+                    reqwest::Method::PUT,
+                    &format!("{}/{}", endpoint, name),
+                    Some(track),
+                )
+                .await?;
+            withdrawn.push(name);
+        }
+
+        if withdrawn.is_empty() {
+            return Err(StoreError::BuildNotFound(format!(
+                "version code {} is not on any track",
+                version_code
+            )));
+        }
+
+        self.commit_edit(edit_id).await?;
+        info!(version_code, tracks = ?withdrawn, "withdrew build from tracks");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::tests::{store, TOKEN};
+    use super::*;
+    use crate::traits::StoreAdapter;
+    use canaveral_test_support::MockServer;
+
+    #[test]
+    fn test_remove_version_code() {
+        let mut track = serde_json::json!({
+            "track": "beta",
+            "releases": [
+                { "status": "completed", "versionCodes": ["41"] },
+                { "status": "inProgress", "versionCodes": ["42", "43"], "userFraction": 0.1 },
+                { "status": "draft", "versionCodes": ["42"] }
+            ]
+        });
+        assert!(remove_version_code(&mut track, "42"));
+        assert_eq!(
+            track["releases"],
+            serde_json::json!([
+                { "status": "completed", "versionCodes": ["41"] },
+                { "status": "inProgress", "versionCodes": ["43"], "userFraction": 0.1 }
+            ])
+        );
+        assert!(!remove_version_code(&mut track, "42"));
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_removes_version_from_track() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let server = MockServer::serve(vec![
+            TOKEN,
+            r#"{"id": "edit-1"}"#,
+            r#"{"kind": "androidpublisher#tracksListResponse", "tracks": [
+                {"track": "production", "releases": [{"status": "completed", "versionCodes": ["41"]}]},
+                {"track": "beta", "releases": [
+                    {"status": "completed", "versionCodes": ["41"]},
+                    {"status": "inProgress", "versionCodes": ["42"], "userFraction": 0.2}
+                ]}
+            ]}"#,
+            r#"{"track": "beta"}"#,
+            r#"{"id": "edit-1"}"#,
+        ]);
+
+        store(&temp, server.url())
+            .withdraw_build("42")
+            .await
+            .unwrap();
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line(), "POST /v1/token");
+        assert_eq!(
+            requests[1].line(),
+            "POST /applications/com.example.app/edits"
+        );
+        assert_eq!(
+            requests[2].line(),
+            "GET /applications/com.example.app/edits/edit-1/tracks"
+        );
+        assert_eq!(
+            requests[3].line(),
+            "PUT /applications/com.example.app/edits/edit-1/tracks/beta"
+        );
+        assert_eq!(
+            requests[3].json(),
+            serde_json::json!({
+                "track": "beta",
+                "releases": [{"status": "completed", "versionCodes": ["41"]}]
+            })
+        );
+        assert_eq!(
+            requests[4].line(),
+            "POST /applications/com.example.app/edits/edit-1:commit"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_unknown_version_deletes_edit() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let server = MockServer::serve(vec![
+            TOKEN,
+            r#"{"id": "edit-1"}"#,
+            r#"{"tracks": [{"track": "internal", "releases": [{"status": "completed", "versionCodes": ["41"]}]}]}"#,
+            "",
+        ]);
+
+        let err = store(&temp, server.url())
+            .withdraw_build("99")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, StoreError::BuildNotFound(_)));
+
+        let requests = server.requests();
+        assert_eq!(
+            requests[3].line(),
+            "DELETE /applications/com.example.app/edits/edit-1"
+        );
+    }
+}
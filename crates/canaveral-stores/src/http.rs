@@ -0,0 +1,439 @@
+//! Shared HTTP client configuration for store adapters
+//!
+//! Every HTTP-based adapter starts from [`default_client`], which sets connect
+//! and read timeouts so a stalled proxy or API can't hang a CI job, and honours
+//! `HTTPS_PROXY`/`HTTP_PROXY`/`NO_PROXY` from the environment. Callers that need
+//! custom proxies, a private CA, client certificates or timeouts build a
+//! client with [`HttpClientConfig`] and pass it to the adapter's
+//! `with_http_client`.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use reqwest::{Certificate, Client, Identity, NoProxy, Proxy};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, warn};
+
+use crate::error::{Result, StoreError};
+
+/// Default time allowed to establish a connection
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Default time allowed between reads on an open connection
+pub const DEFAULT_READ_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Settings for building an HTTP client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpClientConfig {
+    /// Connect timeout in seconds
+    pub connect_timeout_secs: u64,
+
+    /// Idle read timeout in seconds
+    pub read_timeout_secs: u64,
+
+    /// Overall request timeout in seconds (none by default, since uploads can be large)
+    pub request_timeout_secs: Option<u64>,
+
+    /// Proxy URL for all requests (overrides `HTTPS_PROXY`/`HTTP_PROXY`)
+    pub proxy: Option<String>,
+
+    /// Comma-separated hosts that bypass `proxy` (defaults to `NO_PROXY`)
+    pub no_proxy: Option<String>,
+
+    /// PEM bundle of CA certificates trusted in addition to the public roots
+    pub ca_bundle: Option<PathBuf>,
+
+    /// PEM client certificate presented for mutual TLS
+    pub client_cert: Option<PathBuf>,
+
+    /// PEM private key of `client_cert`
+    pub client_key: Option<PathBuf>,
+}
+
+impl Default for HttpClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT.as_secs(),
+            read_timeout_secs: DEFAULT_READ_TIMEOUT.as_secs(),
+            request_timeout_secs: None,
+            proxy: None,
+            no_proxy: None,
+            ca_bundle: None,
+            client_cert: None,
+            client_key: None,
+        }
+    }
+}
+
+impl HttpClientConfig {
+    /// Create the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the connect timeout
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    /// Set the idle read timeout
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout_secs = timeout.as_secs();
+        self
+    }
+
+    /// Set an overall per-request timeout
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout_secs = Some(timeout.as_secs());
+        self
+    }
+
+    /// Route all requests through a proxy
+    pub fn with_proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    /// Hosts that bypass the configured proxy
+    pub fn with_no_proxy(mut self, hosts: impl Into<String>) -> Self {
+        self.no_proxy = Some(hosts.into());
+        self
+    }
+
+    /// Trust the CA certificates in a PEM bundle
+    pub fn with_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Present a client certificate and key (PEM) for mutual TLS
+    pub fn with_client_identity(
+        mut self,
+        cert: impl Into<PathBuf>,
+        key: impl Into<PathBuf>,
+    ) -> Self {
+        self.client_cert = Some(cert.into());
+        self.client_key = Some(key.into());
+        self
+    }
+
+    /// Build a `reqwest::Client` from these settings
+    ///
+    /// Without an explicit `proxy`, the environment's proxy variables apply.
+    /// Fails if a configured certificate file is missing or holds no valid
+    /// PEM data.
+    pub fn build(&self) -> Result<Client> {
+        let mut builder = Client::builder()
+            .use_rustls_tls()
+            .user_agent(concat!("canaveral/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .read_timeout(Duration::from_secs(self.read_timeout_secs));
+
+        if let Some(secs) = self.request_timeout_secs {
+            builder = builder.timeout(Duration::from_secs(secs));
+        }
+
+        if let Some(url) = &self.proxy {
+            let no_proxy = match &self.no_proxy {
+                Some(hosts) => NoProxy::from_string(hosts),
+                None => NoProxy::from_env(),
+            };
+            let proxy = Proxy::all(url)
+                .map_err(|e| {
+                    StoreError::ConfigurationError(format!("Invalid proxy URL '{}': {}", url, e))
+                })?
+                .no_proxy(no_proxy);
+            debug!(proxy = %url, "using configured HTTP proxy");
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(path) = &self.ca_bundle {
+            let certificates = Certificate::from_pem_bundle(&read_pem("CA bundle", path)?)
+                .map_err(|e| {
+                    StoreError::ConfigurationError(format!(
+                        "Invalid CA bundle {}: {}",
+                        path.display(),
+                        e
+                    ))
+                })?;
+            if certificates.is_empty() {
+                return Err(StoreError::ConfigurationError(format!(
+                    "CA bundle {} contains no certificates",
+                    path.display()
+                )));
+            }
+            debug!(path = %path.display(), count = certificates.len(), "trusting custom CA certificates");
+            for certificate in certificates {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+
+        match (&self.client_cert, &self.client_key) {
+            (Some(cert), Some(key)) => {
+                let mut pem = read_pem("client certificate", cert)?;
+                pem.push(b'\n');
+                pem.extend(read_pem("client key", key)?);
+                let identity = Identity::from_pem(&pem).map_err(|e| {
+                    StoreError::ConfigurationError(format!(
+                        "Invalid client certificate {} or key {}: {}",
+                        cert.display(),
+                        key.display(),
+                        e
+                    ))
+                })?;
+                debug!(cert = %cert.display(), "using client certificate");
+                builder = builder.identity(identity);
+            }
+            (None, None) => {}
+            _ => {
+                return Err(StoreError::ConfigurationError(
+                    "client_cert and client_key must be set together".to_string(),
+                ))
+            }
+        }
+
+        builder.build().map_err(|e| {
+            StoreError::ConfigurationError(format!("Failed to build HTTP client: {}", e))
+        })
+    }
+}
+
+/// Read a PEM file, naming it in the error if it is missing
+fn read_pem(what: &str, path: &Path) -> Result<Vec<u8>> {
+    std::fs::read(path).map_err(|e| {
+        StoreError::ConfigurationError(format!("Cannot read {} {}: {}", what, path.display(), e))
+    })
+}
+
+/// HTTP client used by adapters unless one is injected
+pub fn default_client() -> Client {
+    HttpClientConfig::default().build().unwrap_or_else(|e| {
+        warn!(error = %e, "falling back to an HTTP client without timeouts");
+        Client::new()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registries::{NpmConfig, NpmRegistry, TagSupport};
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    /// Accept one connection and never respond
+    fn slow_server() -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let mut buf = [0u8; 1024];
+                let _ = stream.read(&mut buf);
+                std::thread::sleep(Duration::from_secs(5));
+            }
+        });
+        format!("http://{}", addr)
+    }
+
+    #[test]
+    fn test_build_default_and_proxy() {
+        assert!(HttpClientConfig::default().build().is_ok());
+        assert!(HttpClientConfig::new()
+            .with_proxy("http://proxy.internal:3128")
+            .with_no_proxy("localhost,.corp")
+            .build()
+            .is_ok());
+        assert!(matches!(
+            HttpClientConfig::new().with_proxy("::not a url::").build(),
+            Err(StoreError::ConfigurationError(_))
+        ));
+    }
+
+    /// A private CA with a `localhost` server certificate and a client
+    /// certificate, written as PEM files
+    struct TestPki {
+        dir: tempfile::TempDir,
+        ca_pem: String,
+        server_cert: String,
+        server_key: String,
+    }
+
+    impl TestPki {
+        fn new() -> Self {
+            use rcgen::{BasicConstraints, CertificateParams, IsCa, KeyPair};
+
+            let ca_key = KeyPair::generate().unwrap();
+            let mut ca_params = CertificateParams::new(Vec::<String>::new()).unwrap();
+            ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+            let ca = ca_params.self_signed(&ca_key).unwrap();
+
+            let issue = |name: &str| {
+                let key = KeyPair::generate().unwrap();
+                let cert = CertificateParams::new(vec![name.to_string()])
+                    .unwrap()
+                    .signed_by(&key, &ca, &ca_key)
+                    .unwrap();
+                (cert.pem(), key.serialize_pem())
+            };
+            let (server_cert, server_key) = issue("localhost");
+            let (client_cert, client_key) = issue("client");
+
+            let dir = tempfile::TempDir::new().unwrap();
+            std::fs::write(dir.path().join("ca.pem"), ca.pem()).unwrap();
+            std::fs::write(dir.path().join("client.pem"), client_cert).unwrap();
+            std::fs::write(dir.path().join("client.key"), client_key).unwrap();
+            Self {
+                dir,
+                ca_pem: ca.pem(),
+                server_cert,
+                server_key,
+            }
+        }
+
+        fn path(&self, name: &str) -> PathBuf {
+            self.dir.path().join(name)
+        }
+
+        /// Serve `200 ok` over TLS to every connection, requiring a client
+        /// certificate signed by the CA when `mutual` is set
+        async fn serve(&self, mutual: bool) -> String {
+            use rustls::pki_types::pem::PemObject;
+            use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+            use std::sync::Arc;
+            use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+            let provider = Arc::new(rustls::crypto::ring::default_provider());
+            let builder = rustls::ServerConfig::builder_with_provider(provider.clone())
+                .with_safe_default_protocol_versions()
+                .unwrap();
+            let builder = if mutual {
+                let mut roots = rustls::RootCertStore::empty();
+                roots
+                    .add(CertificateDer::from_pem_slice(self.ca_pem.as_bytes()).unwrap())
+                    .unwrap();
+                let verifier = rustls::server::WebPkiClientVerifier::builder_with_provider(
+                    roots.into(),
+                    provider,
+                )
+                .build()
+                .unwrap();
+                builder.with_client_cert_verifier(verifier)
+            } else {
+                builder.with_no_client_auth()
+            };
+            let config = builder
+                .with_single_cert(
+                    vec![CertificateDer::from_pem_slice(self.server_cert.as_bytes()).unwrap()],
+                    PrivateKeyDer::from_pem_slice(self.server_key.as_bytes()).unwrap(),
+                )
+                .unwrap();
+
+            let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+            let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            let port = listener.local_addr().unwrap().port();
+            tokio::spawn(async move {
+                while let Ok((stream, _)) = listener.accept().await {
+                    let acceptor = acceptor.clone();
+                    tokio::spawn(async move {
+                        let Ok(mut stream) = acceptor.accept(stream).await else {
+                            return;
+                        };
+                        let mut buf = [0u8; 4096];
+                        let _ = stream.read(&mut buf).await;
+                        let _ = stream
+                            .write_all(
+                                b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok",
+                            )
+                            .await;
+                        let _ = stream.shutdown().await;
+                    });
+                }
+            });
+            format!("https://localhost:{}", port)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_custom_ca_bundle() {
+        let pki = TestPki::new();
+        let url = pki.serve(false).await;
+
+        // Public roots only: the private CA isn't trusted
+        let client = HttpClientConfig::new().build().unwrap();
+        assert!(client.get(&url).send().await.is_err());
+
+        let client = HttpClientConfig::new()
+            .with_ca_bundle(pki.path("ca.pem"))
+            .build()
+            .unwrap();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[tokio::test]
+    async fn test_mutual_tls() {
+        let pki = TestPki::new();
+        let url = pki.serve(true).await;
+
+        let without_identity = HttpClientConfig::new()
+            .with_ca_bundle(pki.path("ca.pem"))
+            .build()
+            .unwrap();
+        assert!(without_identity.get(&url).send().await.is_err());
+
+        let client = HttpClientConfig::new()
+            .with_ca_bundle(pki.path("ca.pem"))
+            .with_client_identity(pki.path("client.pem"), pki.path("client.key"))
+            .build()
+            .unwrap();
+        let response = client.get(&url).send().await.unwrap();
+        assert_eq!(response.text().await.unwrap(), "ok");
+    }
+
+    #[test]
+    fn test_invalid_certificate_files() {
+        let pki = TestPki::new();
+
+        let err = HttpClientConfig::new()
+            .with_ca_bundle(pki.path("missing.pem"))
+            .build()
+            .unwrap_err();
+        assert!(matches!(err, StoreError::ConfigurationError(_)));
+        assert!(err.to_string().contains("missing.pem"), "{}", err);
+
+        std::fs::write(pki.path("empty.pem"), "not a certificate\n").unwrap();
+        assert!(HttpClientConfig::new()
+            .with_ca_bundle(pki.path("empty.pem"))
+            .build()
+            .is_err());
+
+        let half = HttpClientConfig {
+            client_cert: Some(pki.path("client.pem")),
+            ..Default::default()
+        };
+        assert!(half.build().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_injected_client_timeout() {
+        let url = slow_server();
+        let client = Client::builder()
+            .timeout(Duration::from_millis(1))
+            .build()
+            .unwrap();
+
+        let registry = NpmRegistry::new(NpmConfig {
+            registry_url: url,
+            token: Some("token".to_string()),
+        })
+        .unwrap()
+        .with_http_client(client);
+
+        let result = registry.list_tags("left-pad").await;
+        assert!(
+            matches!(result, Err(StoreError::Timeout(_))),
+            "expected timeout, got {:?}",
+            result
+        );
+    }
+}
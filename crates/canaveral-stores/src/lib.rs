@@ -45,9 +45,13 @@
 //! store.upload(&artifact_path, &options).await?;
 //! ```
 
+pub mod cancel;
 pub mod error;
+pub mod http;
 pub mod metadata_integration;
 pub mod registry;
+pub mod release_notes;
+pub mod token;
 pub mod traits;
 pub mod types;
 
@@ -58,7 +62,10 @@ pub mod microsoft;
 pub mod registries;
 
 pub use error::StoreError;
+pub use http::{default_client, HttpClientConfig};
 pub use registry::StoreRegistry;
+pub use token::TokenTiming;
+pub use tokio_util::sync::CancellationToken;
 pub use traits::StoreAdapter;
 pub use types::*;
 
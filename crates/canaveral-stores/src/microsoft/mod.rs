@@ -15,7 +15,10 @@
 //! store.upload(&artifact_path, &options).await?;
 //! ```
 
+use crate::cancel::{cancellable, with_rollback};
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
+use crate::token::TokenTiming;
 use crate::traits::{StoreAdapter, TrackSupport};
 use crate::types::*;
 use chrono::{Duration, Utc};
@@ -29,6 +32,8 @@ use tracing::{debug, info, instrument};
 // Re-export config from types
 pub use crate::types::MicrosoftStoreConfig;
 
+mod submission;
+
 const LOGIN_URL: &str = "https://login.microsoftonline.com";
 const API_BASE_URL: &str = "https://manage.devcenter.microsoft.com/v1.0/my";
 
@@ -54,8 +59,14 @@ pub struct MicrosoftStore {
     /// HTTP client
     client: Client,
 
+    /// Partner Center API base URL
+    api_base_url: String,
+
     /// Token cache with interior mutability
     token_cache: Arc<RwLock<TokenCache>>,
+
+    /// When the cached token is refreshed
+    token_timing: TokenTiming,
 }
 
 impl MicrosoftStore {
@@ -63,18 +74,38 @@ impl MicrosoftStore {
     pub fn new(config: MicrosoftStoreConfig) -> Result<Self> {
         Ok(Self {
             config,
-            client: Client::new(),
+            client: default_client(),
+            api_base_url: API_BASE_URL.to_string(),
             token_cache: Arc::new(RwLock::new(TokenCache::default())),
+            token_timing: TokenTiming::default(),
         })
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Use a different Partner Center API base URL (for testing or proxies)
+    pub fn with_api_base_url(mut self, url: impl Into<String>) -> Self {
+        self.api_base_url = url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Set how long before expiry the cached token is refreshed
+    pub fn with_token_timing(mut self, timing: TokenTiming) -> Self {
+        self.token_timing = timing;
+        self
+    }
+
     /// Get or refresh OAuth2 access token
     async fn get_access_token(&self) -> Result<String> {
         // Check if we have a valid cached token
         {
             let cache = self.token_cache.read().await;
             if let (Some(token), Some(expires)) = (&cache.access_token, cache.expires_at) {
-                if Utc::now() < expires - Duration::minutes(5) {
+                if self.token_timing.is_fresh(expires, Utc::now()) {
                     return Ok(token.clone());
                 }
             }
@@ -120,7 +151,7 @@ impl MicrosoftStore {
         body: Option<serde_json::Value>,
     ) -> Result<T> {
         let token = self.get_access_token().await?;
-        let url = format!("{}{}", API_BASE_URL, endpoint);
+        let url = format!("{}{}", self.api_base_url, endpoint);
 
         let mut request = self
             .client
@@ -287,24 +318,6 @@ impl MicrosoftStore {
         Ok(())
     }
 
-    /// Get submission status
-    async fn get_submission_status(&self, submission_id: &str) -> Result<SubmissionStatus> {
-        let endpoint = format!(
-            "/applications/{}/submissions/{}/status",
-            self.config.app_id, submission_id
-        );
-
-        let response: StatusResponse = self
-            .api_request(reqwest::Method::GET, &endpoint, None)
-            .await?;
-
-        Ok(SubmissionStatus {
-            status: response.status,
-            status_details: response.status_details,
-            certification_reports: response.certification_reports,
-        })
-    }
-
     /// List package flights
     async fn list_flights(&self) -> Result<Vec<FlightInfo>> {
         let endpoint = format!("/applications/{}/listflights", self.config.app_id);
@@ -419,21 +432,13 @@ struct SubmissionResponse {
     status: String,
 }
 
-/// Status response from API
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-struct StatusResponse {
-    status: String,
-    status_details: Option<StatusDetails>,
-    certification_reports: Option<Vec<CertificationReport>>,
-}
-
 /// Status details
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct StatusDetails {
     pub errors: Option<Vec<SubmissionError>>,
     pub warnings: Option<Vec<SubmissionWarning>>,
+    pub certification_reports: Option<Vec<CertificationReport>>,
 }
 
 /// Submission error
@@ -590,42 +595,59 @@ impl StoreAdapter for MicrosoftStore {
             });
         }
 
+        let cancel = &options.cancellation;
+
         // Create submission
         info!("Creating submission...");
-        let submission = if let Some(flight) = options
+        let flight_id = match options
             .track
             .as_ref()
             .or(self.config.default_flight.as_ref())
         {
-            // Find flight ID by name
-            let flights = self.list_flights().await?;
-            let flight_info = flights
-                .iter()
-                .find(|f| f.name.eq_ignore_ascii_case(flight))
-                .ok_or_else(|| StoreError::AppNotFound(format!("Flight '{}' not found", flight)))?;
-            self.create_flight_submission(&flight_info.id).await?
-        } else {
-            self.create_submission().await?
+            Some(flight) => {
+                // Find flight ID by name
+                let flights = cancellable(cancel, "submission", self.list_flights()).await?;
+                let flight_info = flights
+                    .into_iter()
+                    .find(|f| f.name.eq_ignore_ascii_case(flight))
+                    .ok_or_else(|| {
+                        StoreError::AppNotFound(format!("Flight '{}' not found", flight))
+                    })?;
+                Some(flight_info.id)
+            }
+            None => None,
         };
-
-        // Update submission with package info
-        info!("Updating submission...");
-        self.update_submission(&submission.id, path, &options.release_notes)
-            .await?;
-
-        // Upload package to Azure Blob Storage
-        info!("Uploading package...");
-        self.upload_package(&submission.file_upload_url, path)
-            .await?;
+        let submission = cancellable(cancel, "submission", async {
+            match &flight_id {
+                Some(id) => self.create_flight_submission(id).await,
+                None => self.create_submission().await,
+            }
+        })
+        .await?;
+
+        // Until it is committed, a cancelled submission is deleted again
+        with_rollback(
+            cancel,
+            "package upload",
+            async {
+                // Update submission with package info
+                info!("Updating submission...");
+                self.update_submission(&submission.id, path, &options.release_notes)
+                    .await?;
+
+                // Upload package to Azure Blob Storage
+                info!("Uploading package...");
+                self.upload_package(&submission.file_upload_url, path).await
+            },
+            || self.delete_submission(&submission.id, flight_id.as_deref()),
+        )
+        .await?;
 
         // Commit submission
         info!("Committing submission...");
         self.commit_submission(&submission.id).await?;
 
-        let console_url = format!(
-            "https://partner.microsoft.com/dashboard/products/{}/submissions/{}",
-            self.config.app_id, submission.id
-        );
+        let console_url = self.submission_console_url(&submission.id);
 
         Ok(UploadResult {
             success: true,
@@ -639,46 +661,7 @@ impl StoreAdapter for MicrosoftStore {
 
     #[instrument(skip(self), fields(store = "Microsoft Store"))]
     async fn get_build_status(&self, build_id: &str) -> Result<BuildStatus> {
-        let status = self.get_submission_status(build_id).await?;
-
-        let upload_status = match status.status.as_str() {
-            "CommitStarted" | "CommitFailed" => UploadStatus::Processing,
-            "PreProcessing" | "PreProcessingFailed" => UploadStatus::Processing,
-            "Certification" | "CertificationFailed" => UploadStatus::InReview,
-            "Release" | "ReleaseFailed" => UploadStatus::Ready,
-            "Publishing" => UploadStatus::Processing,
-            "Published" => UploadStatus::Live,
-            "Canceled" | "Failed" => UploadStatus::Failed,
-            _ => UploadStatus::Processing,
-        };
-
-        let details = status.status_details.map(|d| {
-            let mut details = String::new();
-            if let Some(errors) = d.errors {
-                for err in errors {
-                    details.push_str(&format!("Error {}: {}\n", err.code, err.details));
-                }
-            }
-            if let Some(warnings) = d.warnings {
-                for warn in warnings {
-                    details.push_str(&format!("Warning {}: {}\n", warn.code, warn.details));
-                }
-            }
-            details
-        });
-
-        Ok(BuildStatus {
-            build_id: build_id.to_string(),
-            version: "".to_string(),
-            build_number: "".to_string(),
-            status: upload_status,
-            uploaded_at: None,
-            processed_at: None,
-            expires_at: None,
-            track: None,
-            rollout_percentage: None,
-            details,
-        })
+        self.fetch_submission_status(build_id).await
     }
 
     async fn list_builds(&self, _limit: Option<usize>) -> Result<Vec<Build>> {
@@ -686,6 +669,19 @@ impl StoreAdapter for MicrosoftStore {
         Ok(Vec::new())
     }
 
+    /// Cancel the submission by deleting it
+    #[instrument(skip(self), fields(store = "Microsoft Store"))]
+    async fn withdraw_build(&self, build_id: &str) -> Result<()> {
+        self.delete_submission(build_id, None)
+            .await
+            .map_err(|e| match e {
+                StoreError::ApiError { status: 404, .. } => {
+                    StoreError::BuildNotFound(build_id.to_string())
+                }
+                other => other,
+            })
+    }
+
     fn supported_extensions(&self) -> &[&str] {
         &[
             "msix",
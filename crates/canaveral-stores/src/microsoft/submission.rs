@@ -0,0 +1,312 @@
+//! Submission details for Partner Center builds
+//!
+//! The submission resource (`/applications/{appId}/submissions/{id}`) carries
+//! the status, certification reports and the `applicationPackages` written by
+//! `update_submission`, so one request is enough to fill a [`BuildStatus`].
+//! Pending submissions are deleted when an upload is cancelled before commit.
+
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tracing::{debug, info, instrument};
+
+use crate::error::{Result, StoreError};
+use crate::types::{BuildStatus, UploadStatus};
+
+use super::{MicrosoftStore, StatusDetails};
+
+/// Submission resource, limited to the fields used for status reporting
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SubmissionDocument {
+    id: String,
+    status: String,
+    status_details: Option<StatusDetails>,
+    #[serde(default)]
+    application_packages: Vec<ApplicationPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ApplicationPackage {
+    file_status: Option<String>,
+    version: Option<String>,
+}
+
+impl SubmissionDocument {
+    /// Version of the package this submission ships
+    ///
+    /// Packages being replaced are marked `PendingDelete`; of the rest, an
+    /// uploaded package wins over one still pending upload.
+    fn package_version(&self) -> Option<&str> {
+        let active = || {
+            self.application_packages
+                .iter()
+                .filter(|p| p.file_status.as_deref() != Some("PendingDelete"))
+                .filter_map(|p| Some((p.file_status.as_deref(), p.version.as_deref()?)))
+        };
+        active()
+            .find(|(status, _)| *status == Some("Uploaded"))
+            .or_else(|| active().next())
+            .map(|(_, version)| version)
+    }
+
+    /// Date of the most recent certification report
+    fn processed_at(&self) -> Option<DateTime<Utc>> {
+        self.status_details
+            .as_ref()?
+            .certification_reports
+            .as_ref()?
+            .iter()
+            .filter_map(|r| DateTime::parse_from_rfc3339(&r.date).ok())
+            .map(|d| d.with_timezone(&Utc))
+            .max()
+    }
+}
+
+/// Split a four-part MSIX version (`Major.Minor.Build.Revision`) into the
+/// marketing version (`Major.Minor.Build`) and the full build number
+fn parse_msix_version(version: &str) -> Option<(String, String)> {
+    let parts: Vec<&str> = version.trim().split('.').collect();
+    if parts.len() != 4 || parts.iter().any(|p| p.parse::<u16>().is_err()) {
+        return None;
+    }
+    Some((parts[..3].join("."), parts.join(".")))
+}
+
+/// Map a Partner Center submission status to an upload status
+fn map_status(status: &str) -> UploadStatus {
+    match status {
+        "CommitStarted" | "CommitFailed" => UploadStatus::Processing,
+        "PreProcessing" | "PreProcessingFailed" => UploadStatus::Processing,
+        "Certification" | "CertificationFailed" => UploadStatus::InReview,
+        "Release" | "ReleaseFailed" => UploadStatus::Ready,
+        "Publishing" => UploadStatus::Processing,
+        "Published" => UploadStatus::Live,
+        "Canceled" | "Failed" => UploadStatus::Failed,
+        _ => UploadStatus::Processing,
+    }
+}
+
+/// Errors and warnings from the status details, one per line
+fn format_details(details: &StatusDetails) -> String {
+    let mut text = String::new();
+    for err in details.errors.iter().flatten() {
+        text.push_str(&format!("Error {}: {}\n", err.code, err.details));
+    }
+    for warn in details.warnings.iter().flatten() {
+        text.push_str(&format!("Warning {}: {}\n", warn.code, warn.details));
+    }
+    text
+}
+
+impl MicrosoftStore {
+    /// Partner Center dashboard URL for a submission
+    pub(super) fn submission_console_url(&self, submission_id: &str) -> String {
+        format!(
+            "https://partner.microsoft.com/dashboard/products/{}/submissions/{}",
+            self.config.app_id, submission_id
+        )
+    }
+
+    /// Delete a pending (uncommitted) submission
+    #[instrument(skip(self))]
+    pub(super) async fn delete_submission(
+        &self,
+        submission_id: &str,
+        flight_id: Option<&str>,
+    ) -> Result<()> {
+        let endpoint = match flight_id {
+            Some(flight_id) => format!(
+                "/applications/{}/flights/{}/submissions/{}",
+                self.config.app_id, flight_id, submission_id
+            ),
+            None => format!(
+                "/applications/{}/submissions/{}",
+                self.config.app_id, submission_id
+            ),
+        };
+        let token = self.get_access_token().await?;
+
+        let response = self
+            .client
+            .delete(format!("{}{}", self.api_base_url, endpoint))
+            .header("Authorization", format!("Bearer {}", token))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(StoreError::ApiError {
+                status: status.as_u16(),
+                message: response.text().await.unwrap_or_default(),
+            });
+        }
+
+        info!(submission = submission_id, "deleted pending submission");
+        Ok(())
+    }
+
+    /// Fetch a submission and fold it into a [`BuildStatus`]
+    #[instrument(skip(self))]
+    pub(super) async fn fetch_submission_status(&self, submission_id: &str) -> Result<BuildStatus> {
+        let endpoint = format!(
+            "/applications/{}/submissions/{}",
+            self.config.app_id, submission_id
+        );
+        let document: SubmissionDocument = self
+            .api_request(reqwest::Method::GET, &endpoint, None)
+            .await
+            .map_err(|e| match e {
+                StoreError::ApiError { status: 404, .. } => {
+                    StoreError::BuildNotFound(submission_id.to_string())
+                }
+                other => other,
+            })?;
+
+        let (version, build_number) = match document.package_version() {
+            Some(raw) => parse_msix_version(raw).unwrap_or_else(|| {
+                debug!(
+                    version = raw,
+                    "package version is not a four-part MSIX version"
+                );
+                (raw.to_string(), raw.to_string())
+            }),
+            None => (String::new(), String::new()),
+        };
+        debug!(
+            submission = %document.id,
+            status = %document.status,
+            version = %version,
+            "fetched submission"
+        );
+
+        Ok(BuildStatus {
+            build_id: document.id.clone(),
+            version,
+            build_number,
+            status: map_status(&document.status),
+            uploaded_at: None,
+            processed_at: document.processed_at(),
+            expires_at: None,
+            track: None,
+            rollout_percentage: None,
+            details: document.status_details.as_ref().map(format_details),
+            console_url: Some(self.submission_console_url(&document.id)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::MicrosoftStoreConfig;
+    use canaveral_test_support::MockServer;
+
+    const SUBMISSION_RESPONSE: &str = r#"{
+        "id": "1152921504621243680",
+        "status": "Certification",
+        "statusDetails": {
+            "errors": [],
+            "warnings": [{ "code": "SalesUnsupportedWarning", "details": "Sale pricing ignored" }],
+            "certificationReports": [
+                { "date": "2024-05-01T10:00:00Z", "reportUrl": "https://example.com/r1" },
+                { "date": "2024-05-02T08:30:00Z", "reportUrl": "https://example.com/r2" }
+            ]
+        },
+        "applicationPackages": [
+            { "fileName": "app_1.1.0.0.msixupload", "fileStatus": "PendingDelete", "version": "1.1.0.0" },
+            { "fileName": "app_1.2.3.0.msixupload", "fileStatus": "Uploaded", "version": "1.2.3.0", "architecture": "X64" }
+        ]
+    }"#;
+
+    async fn store(url: &str) -> MicrosoftStore {
+        let store = MicrosoftStore::new(MicrosoftStoreConfig {
+            tenant_id: "tenant".to_string(),
+            client_id: "client".to_string(),
+            client_secret: "secret".to_string(),
+            app_id: "9NBLGGH4R315".to_string(),
+            default_flight: None,
+        })
+        .unwrap()
+        .with_api_base_url(url);
+
+        // Skip the Azure AD token exchange
+        let mut cache = store.token_cache.write().await;
+        cache.access_token = Some("token".to_string());
+        cache.expires_at = Some(Utc::now() + chrono::Duration::hours(1));
+        drop(cache);
+        store
+    }
+
+    #[tokio::test]
+    async fn test_build_status_from_submission() {
+        let server = MockServer::serve(vec![SUBMISSION_RESPONSE]);
+        let store = store(server.url()).await;
+
+        let status = store
+            .fetch_submission_status("1152921504621243680")
+            .await
+            .unwrap();
+
+        assert_eq!(
+            server.requests()[0].line(),
+            "GET /applications/9NBLGGH4R315/submissions/1152921504621243680"
+        );
+        assert_eq!(status.version, "1.2.3");
+        assert_eq!(status.build_number, "1.2.3.0");
+        assert_eq!(status.status, UploadStatus::InReview);
+        assert_eq!(
+            status.processed_at.unwrap().to_rfc3339(),
+            "2024-05-02T08:30:00+00:00"
+        );
+        assert_eq!(
+            status.console_url.as_deref(),
+            Some("https://partner.microsoft.com/dashboard/products/9NBLGGH4R315/submissions/1152921504621243680")
+        );
+        assert_eq!(
+            status.details.as_deref(),
+            Some("Warning SalesUnsupportedWarning: Sale pricing ignored\n")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_flight_submission() {
+        let server = MockServer::serve(vec![""]);
+        let store = store(server.url()).await;
+
+        store
+            .delete_submission("1152921504621243680", Some("43e448df"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            server.requests()[0].line(),
+            "DELETE /applications/9NBLGGH4R315/flights/43e448df/submissions/1152921504621243680"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_cancels_submission() {
+        use crate::traits::StoreAdapter;
+
+        let server = MockServer::serve(vec![""]);
+        let store = store(server.url()).await;
+
+        store.withdraw_build("1152921504621243680").await.unwrap();
+
+        assert_eq!(
+            server.requests()[0].line(),
+            "DELETE /applications/9NBLGGH4R315/submissions/1152921504621243680"
+        );
+    }
+
+    #[test]
+    fn test_parse_msix_version() {
+        assert_eq!(
+            parse_msix_version("10.2.30.0"),
+            Some(("10.2.30".to_string(), "10.2.30.0".to_string()))
+        );
+        assert_eq!(parse_msix_version("1.2.3"), None);
+        assert_eq!(parse_msix_version("1.2.3.x"), None);
+    }
+}
@@ -16,6 +16,7 @@
 //! ```
 
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
 use crate::traits::StoreAdapter;
 use crate::types::*;
 use chrono::Utc;
@@ -64,10 +65,16 @@ impl CratesIoRegistry {
 
         Ok(Self {
             config,
-            client: Client::new(),
+            client: default_client(),
         })
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Load token from environment or credentials file
     fn load_token() -> Result<Option<String>> {
         // First try environment variable
@@ -363,6 +370,7 @@ impl StoreAdapter for CratesIoRegistry {
             track: None,
             rollout_percentage: None,
             details: Some("Crates.io publishes immediately upon successful upload".to_string()),
+            console_url: None,
         })
     }
 
@@ -18,6 +18,7 @@
 //! ```
 
 use crate::error::{Result, StoreError};
+use crate::http::default_client;
 use crate::traits::StoreAdapter;
 use crate::types::*;
 use async_trait::async_trait;
@@ -111,10 +112,16 @@ impl NpmRegistry {
 
         Ok(Self {
             config,
-            client: Client::new(),
+            client: default_client(),
         })
     }
 
+    /// Use a pre-configured HTTP client (proxy, timeouts, root certificates)
+    pub fn with_http_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
     /// Load NPM token from environment or .npmrc
     fn load_token() -> Result<Option<String>> {
         // First try NPM_TOKEN environment variable
@@ -419,6 +426,7 @@ impl StoreAdapter for NpmRegistry {
             track: Some("latest".to_string()),
             rollout_percentage: None,
             details: Some("NPM packages are immediately available after publishing".to_string()),
+            console_url: None,
         })
     }
 
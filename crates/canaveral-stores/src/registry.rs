@@ -196,6 +196,18 @@ mod tests {
         assert_eq!(available[1].name(), "npm");
     }
 
+    #[tokio::test]
+    async fn test_withdraw_unsupported_by_default() {
+        let store = MockStore::new("npm", StoreType::Npm, true);
+        let err = store.withdraw_build("1.2.3").await.unwrap_err();
+        assert!(matches!(err, crate::error::StoreError::Unsupported(_)));
+        assert_eq!(err.code(), "validation");
+        assert_eq!(
+            err.to_string(),
+            "Not supported: npm has no API to withdraw build 1.2.3"
+        );
+    }
+
     #[test]
     fn test_register_arc() {
         let mut registry = StoreRegistry::new();
@@ -0,0 +1,182 @@
+//! Loading release notes from files
+//!
+//! [`UploadOptions::release_notes`](crate::UploadOptions) maps locales to
+//! notes. Besides building that map in code, notes can be loaded from a
+//! directory in the fastlane layout:
+//!
+//! ```text
+//! <dir>/
+//! ├── en-US/
+//! │   ├── changelogs/
+//! │   │   ├── 42.txt        # notes for version code 42
+//! │   │   └── default.txt   # notes for any other version
+//! │   └── release_notes.txt # App Store "What's New"
+//! └── de-DE/
+//!     └── changelogs/default.txt
+//! ```
+//!
+//! or from a single text applied to every locale.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use tokio::fs;
+use tracing::debug;
+
+use crate::error::Result;
+
+/// Locale used when notes are given for no locale in particular
+pub const DEFAULT_LOCALE: &str = "en-US";
+
+/// Normalize a locale code to `language-REGION` (`en_us` → `en-US`)
+///
+/// Four-letter subtags are scripts and are title-cased (`zh-hans` → `zh-Hans`).
+pub fn normalize_locale(code: &str) -> String {
+    code.trim()
+        .split(['-', '_'])
+        .enumerate()
+        .map(|(i, part)| match (i, part.len()) {
+            (0, _) => part.to_lowercase(),
+            (_, 4) => {
+                let (first, rest) = part.split_at(1);
+                format!("{}{}", first.to_uppercase(), rest.to_lowercase())
+            }
+            _ => part.to_uppercase(),
+        })
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Load per-locale release notes from a fastlane-style directory
+///
+/// For each locale directory, the first existing file wins:
+/// `changelogs/<version_code>.txt`, `changelogs/default.txt`,
+/// `release_notes.txt`. Locales without notes are skipped, as are empty files.
+pub async fn load_release_notes(
+    dir: &Path,
+    version_code: Option<&str>,
+) -> Result<HashMap<String, String>> {
+    let mut notes = HashMap::new();
+
+    let mut entries = match fs::read_dir(dir).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(notes),
+        Err(e) => return Err(e.into()),
+    };
+
+    while let Some(entry) = entries.next_entry().await? {
+        if !entry.file_type().await?.is_dir() {
+            continue;
+        }
+        let Some(locale) = entry.file_name().to_str().map(normalize_locale) else {
+            continue;
+        };
+
+        let path = entry.path();
+        let changelogs = path.join("changelogs");
+        let mut candidates: Vec<PathBuf> = Vec::new();
+        if let Some(code) = version_code {
+            candidates.push(changelogs.join(format!("{}.txt", code)));
+        }
+        candidates.push(changelogs.join("default.txt"));
+        candidates.push(path.join("release_notes.txt"));
+
+        for candidate in candidates {
+            match fs::read_to_string(&candidate).await {
+                Ok(text) if !text.trim().is_empty() => {
+                    debug!(locale = %locale, file = %candidate.display(), "loaded release notes");
+                    notes.insert(locale, text.trim().to_string());
+                    break;
+                }
+                Ok(_) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    Ok(notes)
+}
+
+/// Apply `text` to every locale in `locales` that has no notes yet
+///
+/// With no locales at all, the notes are stored under [`DEFAULT_LOCALE`].
+pub fn apply_default_notes(notes: &mut HashMap<String, String>, text: &str, locales: &[String]) {
+    let text = text.trim();
+    if text.is_empty() {
+        return;
+    }
+    if locales.is_empty() && notes.is_empty() {
+        notes.insert(DEFAULT_LOCALE.to_string(), text.to_string());
+        return;
+    }
+    for locale in locales {
+        notes
+            .entry(normalize_locale(locale))
+            .or_insert_with(|| text.to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, path: &str, content: &str) {
+        let path = dir.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_normalize_locale() {
+        assert_eq!(normalize_locale("en_us"), "en-US");
+        assert_eq!(normalize_locale("DE-de"), "de-DE");
+        assert_eq!(normalize_locale("fr"), "fr");
+        assert_eq!(normalize_locale("zh-hans"), "zh-Hans");
+        assert_eq!(normalize_locale("zh_hant_TW"), "zh-Hant-TW");
+    }
+
+    #[tokio::test]
+    async fn test_load_per_locale_notes() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path();
+        write(dir, "en-US/changelogs/42.txt", "Faster sync\n");
+        write(dir, "en-US/changelogs/default.txt", "Bug fixes");
+        write(dir, "de_de/changelogs/default.txt", "Fehlerbehebungen\n");
+        write(dir, "fr-FR/release_notes.txt", "Corrections");
+        write(dir, "ja-JP/changelogs/default.txt", "  \n");
+        write(dir, "es-ES/title.txt", "Título");
+        write(dir, "README.md", "not a locale");
+
+        let notes = load_release_notes(dir, Some("42")).await.unwrap();
+        assert_eq!(notes.len(), 3);
+        assert_eq!(notes["en-US"], "Faster sync");
+        assert_eq!(notes["de-DE"], "Fehlerbehebungen");
+        assert_eq!(notes["fr-FR"], "Corrections");
+
+        // Other version codes fall back to the default changelog
+        let notes = load_release_notes(dir, Some("43")).await.unwrap();
+        assert_eq!(notes["en-US"], "Bug fixes");
+
+        let missing = load_release_notes(&dir.join("missing"), None)
+            .await
+            .unwrap();
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_apply_default_notes() {
+        let mut notes = HashMap::from([("en-US".to_string(), "Specific".to_string())]);
+        apply_default_notes(
+            &mut notes,
+            "Generic\n",
+            &["en-US".to_string(), "de_DE".to_string()],
+        );
+        assert_eq!(notes["en-US"], "Specific");
+        assert_eq!(notes["de-DE"], "Generic");
+
+        let mut notes = HashMap::new();
+        apply_default_notes(&mut notes, "Generic", &[]);
+        assert_eq!(notes[DEFAULT_LOCALE], "Generic");
+    }
+}
@@ -0,0 +1,154 @@
+//! Expiry and clock-skew handling for API access tokens
+//!
+//! Adapters cache access tokens and refresh them [`TokenTiming::refresh_buffer`]
+//! before they expire, so a token never runs out halfway through an upload.
+//! Signed JWT assertions are backdated by [`TokenTiming::clock_skew`] so that
+//! a runner whose clock is slightly ahead doesn't send an `iat` in the
+//! server's future.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use tracing::warn;
+
+/// Default time before expiry at which a cached token is refreshed
+pub const DEFAULT_REFRESH_BUFFER: Duration = Duration::from_secs(5 * 60);
+
+/// Default clock difference tolerated when signing tokens
+pub const DEFAULT_CLOCK_SKEW: Duration = Duration::from_secs(60);
+
+/// When to refresh cached tokens and how much clock skew to allow for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenTiming {
+    /// Refresh a cached token this long before it expires
+    pub refresh_buffer: Duration,
+    /// Backdate JWT `iat` claims by this much, and warn when a server's
+    /// clock is further off than this
+    pub clock_skew: Duration,
+}
+
+impl Default for TokenTiming {
+    fn default() -> Self {
+        Self {
+            refresh_buffer: DEFAULT_REFRESH_BUFFER,
+            clock_skew: DEFAULT_CLOCK_SKEW,
+        }
+    }
+}
+
+impl TokenTiming {
+    /// Create default timing
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long before expiry cached tokens are refreshed
+    pub fn with_refresh_buffer(mut self, buffer: Duration) -> Self {
+        self.refresh_buffer = buffer;
+        self
+    }
+
+    /// Set the clock skew to tolerate
+    pub fn with_clock_skew(mut self, skew: Duration) -> Self {
+        self.clock_skew = skew;
+        self
+    }
+
+    /// Whether a token expiring at `expires_at` can still be used at `now`
+    pub fn is_fresh(&self, expires_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+        now + chrono_duration(self.refresh_buffer) < expires_at
+    }
+
+    /// `iat` for a JWT signed at `now`
+    pub fn issued_at(&self, now: DateTime<Utc>) -> DateTime<Utc> {
+        now - chrono_duration(self.clock_skew)
+    }
+
+    /// Compare a server's `Date` header with `now`
+    ///
+    /// Returns how far the local clock is ahead of the server (negative when
+    /// behind) if that is more than [`clock_skew`](Self::clock_skew), after
+    /// logging a warning.
+    pub fn check_server_date(
+        &self,
+        server_date: &str,
+        now: DateTime<Utc>,
+    ) -> Option<chrono::Duration> {
+        let server = DateTime::parse_from_rfc2822(server_date).ok()?;
+        let skew = now - server.with_timezone(&Utc);
+        if skew.num_seconds().unsigned_abs() <= self.clock_skew.as_secs() {
+            return None;
+        }
+        warn!(
+            skew_secs = skew.num_seconds(),
+            "local clock differs from the server's; sync it with NTP to avoid auth failures"
+        );
+        Some(skew)
+    }
+
+    /// [`check_server_date`](Self::check_server_date) for a response's `Date` header
+    pub(crate) fn check_response(&self, response: &reqwest::Response) {
+        if let Some(date) = response
+            .headers()
+            .get(reqwest::header::DATE)
+            .and_then(|d| d.to_str().ok())
+        {
+            self.check_server_date(date, Utc::now());
+        }
+    }
+}
+
+/// Whether a token endpoint error rejects the JWT's `iat`/`exp` claims
+pub(crate) fn is_skew_rejection(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("invalid_grant") && (error.contains("iat") || error.contains("timeframe"))
+}
+
+fn chrono_duration(duration: Duration) -> chrono::Duration {
+    chrono::Duration::seconds(duration.as_secs() as i64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_fresh() {
+        let now = Utc::now();
+        let timing = TokenTiming::new();
+        assert!(timing.is_fresh(now + chrono::Duration::minutes(30), now));
+        // Inside the default five minute buffer
+        assert!(!timing.is_fresh(now + chrono::Duration::minutes(4), now));
+        assert!(!timing.is_fresh(now - chrono::Duration::minutes(1), now));
+
+        let short = timing.with_refresh_buffer(Duration::from_secs(60));
+        assert!(short.is_fresh(now + chrono::Duration::minutes(4), now));
+        assert!(!short.is_fresh(now + chrono::Duration::seconds(30), now));
+    }
+
+    #[test]
+    fn test_clock_skew() {
+        let now = DateTime::parse_from_rfc3339("2026-03-02T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let timing = TokenTiming::new();
+        assert_eq!(timing.issued_at(now), now - chrono::Duration::seconds(60));
+
+        assert_eq!(
+            timing.check_server_date("Mon, 02 Mar 2026 11:59:30 GMT", now),
+            None
+        );
+        assert_eq!(
+            timing.check_server_date("Mon, 02 Mar 2026 11:55:00 GMT", now),
+            Some(chrono::Duration::minutes(5))
+        );
+        assert_eq!(timing.check_server_date("not a date", now), None);
+
+        assert!(is_skew_rejection(
+            r#"{"error": "invalid_grant", "error_description": "Invalid JWT: Token must be a short-lived token (60 minutes) and in a reasonable timeframe. Check your iat and exp values in the JWT claim."}"#
+        ));
+        assert!(!is_skew_rejection(
+            r#"{"error": "invalid_grant", "error_description": "Invalid JWT Signature."}"#
+        ));
+    }
+}
@@ -1,6 +1,6 @@
 //! Store adapter traits
 
-use crate::error::Result;
+use crate::error::{Result, StoreError};
 use crate::types::*;
 use std::path::Path;
 
@@ -36,6 +36,19 @@ pub trait StoreAdapter: Send + Sync {
     /// List recent builds for an app
     async fn list_builds(&self, limit: Option<usize>) -> Result<Vec<Build>>;
 
+    /// Withdraw an uploaded build so it can't reach users
+    ///
+    /// Google Play removes the version code from every track, the Microsoft
+    /// Store deletes the submission and App Store Connect expires the build.
+    /// Stores without such an API fail with [`StoreError::Unsupported`].
+    async fn withdraw_build(&self, build_id: &str) -> Result<()> {
+        Err(StoreError::Unsupported(format!(
+            "{} has no API to withdraw build {}",
+            self.name(),
+            build_id
+        )))
+    }
+
     /// Get supported file extensions for this store
     fn supported_extensions(&self) -> &[&str];
 }
@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use tokio_util::sync::CancellationToken;
 
 /// Store type identifier
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -46,6 +47,9 @@ impl std::fmt::Display for StoreType {
 #[derive(Debug, Clone, Default)]
 pub struct UploadOptions {
     /// Release notes/changelog per locale
+    ///
+    /// Stores that support it fill locales missing here from the changelogs
+    /// under `metadata_path` (see [`crate::release_notes`]).
     pub release_notes: HashMap<String, String>,
 
     /// Target track/channel (e.g., "internal", "beta", "production")
@@ -81,6 +85,9 @@ pub struct UploadOptions {
 
     /// Fail upload if metadata validation has errors
     pub require_valid_metadata: bool,
+
+    /// Cancels the upload; drafts created on the store are rolled back
+    pub cancellation: CancellationToken,
 }
 
 /// Result of artifact validation
@@ -264,6 +271,9 @@ pub struct BuildStatus {
 
     /// Additional status details
     pub details: Option<String>,
+
+    /// URL to view the build in the store console
+    pub console_url: Option<String>,
 }
 
 /// Build summary for listing
@@ -387,6 +397,18 @@ pub struct GooglePlayConfig {
 
     /// Default track for releases
     pub default_track: Option<String>,
+
+    /// Path to `aapt2`; falls back to `PATH` and `$ANDROID_HOME/build-tools`
+    #[serde(default)]
+    pub aapt2_path: Option<PathBuf>,
+
+    /// OAuth scopes to request besides `androidpublisher`
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// Audience of the service account JWT; defaults to the key's `token_uri`
+    #[serde(default)]
+    pub jwt_audience: Option<String>,
 }
 
 /// Microsoft Store configuration
@@ -145,7 +145,9 @@ impl VersionStrategy for SemVerStrategy {
         let vb = semver::Version::parse(b)
             .map_err(|e| VersionError::ParseFailed(b.to_string(), e.to_string()))?;
 
-        Ok(va.cmp(&vb))
+        // Precedence ignores build metadata and ranks a release above its
+        // pre-releases
+        Ok(va.cmp_precedence(&vb))
     }
 }
 
@@ -234,6 +236,32 @@ mod tests {
         assert_eq!(next.prerelease, Some("alpha.2".to_string()));
     }
 
+    #[test]
+    fn test_latest_with_prereleases() {
+        let strategy = SemVerStrategy::new();
+        let versions = [
+            "v1.2.0-rc.1",
+            "1.2.0",
+            "1.2.0-rc.2",
+            "1.10.0-alpha.1",
+            "1.9.0",
+            "not-a-version",
+        ];
+        assert_eq!(strategy.latest(&versions, true), Some("1.10.0-alpha.1"));
+        assert_eq!(strategy.latest(&versions, false), Some("1.9.0"));
+        assert_eq!(strategy.latest(&versions[..3], true), Some("1.2.0"));
+        assert_eq!(strategy.latest(&["1.3.0-rc.1"], false), None);
+
+        assert_eq!(
+            strategy.compare("1.2.0-rc.1", "1.2.0").unwrap(),
+            Ordering::Less
+        );
+        assert_eq!(
+            strategy.compare("1.2.0+build.7", "1.2.0").unwrap(),
+            Ordering::Equal
+        );
+    }
+
     #[test]
     fn test_release_from_prerelease() {
         let strategy = SemVerStrategy::new();
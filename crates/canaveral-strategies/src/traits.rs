@@ -38,4 +38,23 @@ pub trait VersionStrategy: Send + Sync {
 
     /// Compare two versions (returns -1, 0, or 1)
     fn compare(&self, a: &str, b: &str) -> Result<std::cmp::Ordering>;
+
+    /// The latest of `versions` by this strategy's ordering
+    ///
+    /// Versions this strategy can't parse are ignored, as are pre-releases
+    /// unless `include_prereleases` is set. A release outranks its own
+    /// pre-releases.
+    fn latest<'a>(&self, versions: &[&'a str], include_prereleases: bool) -> Option<&'a str> {
+        versions
+            .iter()
+            .copied()
+            .filter(|v| {
+                self.parse(v)
+                    .is_ok_and(|c| include_prereleases || c.prerelease.is_none())
+            })
+            .fold(None, |latest, v| match latest {
+                Some(l) if self.compare(v, l).map_or(true, |o| o.is_le()) => Some(l),
+                _ => Some(v),
+            })
+    }
 }
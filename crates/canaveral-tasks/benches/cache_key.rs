@@ -0,0 +1,60 @@
+//! Cache key hashing throughput per algorithm
+//!
+//! Run with `cargo bench -p canaveral-tasks --bench cache_key`.
+
+use std::time::{Duration, Instant};
+
+use canaveral_tasks::{CacheKey, HashAlgorithm, TaskDefinition, TaskId};
+
+const FILES: usize = 2_000;
+const FILE_SIZE: usize = 64 * 1024;
+const RUNS: u32 = 5;
+
+fn main() {
+    let temp = tempfile::TempDir::new().expect("temp dir");
+    let src = temp.path().join("pkg/src");
+    std::fs::create_dir_all(&src).expect("create src");
+
+    // Pseudo-random contents so no file is trivially compressible
+    let mut state = 0x2545_f491_4f6c_dd1du64;
+    for i in 0..FILES {
+        let contents: Vec<u8> = (0..FILE_SIZE)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                state as u8
+            })
+            .collect();
+        std::fs::write(src.join(format!("file_{}.rs", i)), contents).expect("write input");
+    }
+
+    let id = TaskId::new("pkg", "build");
+    let def = TaskDefinition::new("build").with_command("cargo build");
+    let total_mb = (FILES * FILE_SIZE) as f64 / (1024.0 * 1024.0);
+    println!(
+        "{} files, {:.0} MiB, best of {} runs",
+        FILES, total_mb, RUNS
+    );
+
+    for algorithm in [HashAlgorithm::Sha256, HashAlgorithm::Blake3] {
+        // Warm the page cache so both algorithms read from memory
+        CacheKey::compute_with(algorithm, &id, &def, temp.path());
+
+        let best = (0..RUNS)
+            .map(|_| {
+                let start = Instant::now();
+                CacheKey::compute_with(algorithm, &id, &def, temp.path());
+                start.elapsed()
+            })
+            .min()
+            .unwrap_or(Duration::ZERO);
+
+        println!(
+            "{:>8}: {:>8.1} ms  {:>8.1} MiB/s",
+            algorithm.as_str(),
+            best.as_secs_f64() * 1000.0,
+            total_mb / best.as_secs_f64()
+        );
+    }
+}
@@ -6,20 +6,72 @@ use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+pub use canaveral_core::config::HashAlgorithm;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use tracing::{debug, info};
 
 use crate::task::{TaskDefinition, TaskId};
 
-/// Cache key — SHA-256 hash of all inputs
+/// Incremental hasher for the configured [`HashAlgorithm`]
+enum Hasher {
+    Sha256(Sha256),
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(Sha256::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Blake3(h) => {
+                h.update(data);
+            }
+        }
+    }
+
+    fn finalize_hex(self) -> String {
+        match self {
+            Self::Sha256(h) => format!("{:x}", h.finalize()),
+            Self::Blake3(h) => h.finalize().to_hex().to_string(),
+        }
+    }
+
+    fn hash_hex(algorithm: HashAlgorithm, data: &[u8]) -> String {
+        let mut hasher = Self::new(algorithm);
+        hasher.update(data);
+        hasher.finalize_hex()
+    }
+}
+
+/// Cache key — hash of all inputs
+///
+/// SHA-256 keys are bare hex digests; other algorithms prefix the digest
+/// with their name (`blake3-…`) so switching algorithms never hits entries
+/// written by another.
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct CacheKey(pub String);
 
 impl CacheKey {
-    /// Compute a cache key from task inputs
+    /// Compute a SHA-256 cache key from task inputs
     pub fn compute(id: &TaskId, definition: &TaskDefinition, root_dir: &Path) -> Self {
-        let mut hasher = Sha256::new();
+        Self::compute_with(HashAlgorithm::Sha256, id, definition, root_dir)
+    }
+
+    /// Compute a cache key from task inputs with the given algorithm
+    pub fn compute_with(
+        algorithm: HashAlgorithm,
+        id: &TaskId,
+        definition: &TaskDefinition,
+        root_dir: &Path,
+    ) -> Self {
+        let mut hasher = Hasher::new(algorithm);
 
         // Hash task identity
         hasher.update(id.package.as_bytes());
@@ -63,9 +115,7 @@ impl CacheKey {
                     for entry in paths.flatten() {
                         if entry.is_file() {
                             if let Ok(contents) = fs::read(&entry) {
-                                let mut file_hasher = Sha256::new();
-                                file_hasher.update(&contents);
-                                let hash = format!("{:x}", file_hasher.finalize());
+                                let hash = Hasher::hash_hex(algorithm, &contents);
                                 let relative = entry
                                     .strip_prefix(root_dir)
                                     .unwrap_or(&entry)
@@ -84,8 +134,11 @@ impl CacheKey {
             }
         }
 
-        let result = format!("{:x}", hasher.finalize());
-        CacheKey(result)
+        let digest = hasher.finalize_hex();
+        match algorithm {
+            HashAlgorithm::Sha256 => CacheKey(digest),
+            _ => CacheKey(format!("{}-{}", algorithm.as_str(), digest)),
+        }
     }
 }
 
@@ -113,12 +166,23 @@ pub struct CacheEntry {
 pub struct TaskCache {
     /// Cache directory
     cache_dir: PathBuf,
+    /// Hash algorithm for cache keys
+    algorithm: HashAlgorithm,
 }
 
 impl TaskCache {
     /// Create a new task cache
     pub fn new(cache_dir: PathBuf) -> Self {
-        Self { cache_dir }
+        Self {
+            cache_dir,
+            algorithm: HashAlgorithm::default(),
+        }
+    }
+
+    /// Use a different hash algorithm for cache keys
+    pub fn with_hash_algorithm(mut self, algorithm: HashAlgorithm) -> Self {
+        self.algorithm = algorithm;
+        self
     }
 
     /// Create cache with default directory
@@ -133,7 +197,7 @@ impl TaskCache {
         definition: &TaskDefinition,
         root_dir: &Path,
     ) -> Result<Option<CacheEntry>, CacheError> {
-        let key = CacheKey::compute(id, definition, root_dir);
+        let key = CacheKey::compute_with(self.algorithm, id, definition, root_dir);
         let entry_dir = self.cache_dir.join(&key.0);
         let metadata_path = entry_dir.join("metadata.json");
 
@@ -168,7 +232,7 @@ impl TaskCache {
         stderr: &str,
     ) -> Result<CacheKey, CacheError> {
         debug!(task = %id, "storing result in cache");
-        let key = CacheKey::compute(id, definition, root_dir);
+        let key = CacheKey::compute_with(self.algorithm, id, definition, root_dir);
         let entry_dir = self.cache_dir.join(&key.0);
         fs::create_dir_all(&entry_dir).map_err(CacheError::Io)?;
 
@@ -381,6 +445,56 @@ mod tests {
         assert_ne!(key1, key2);
     }
 
+    #[test]
+    fn test_cache_key_per_algorithm() {
+        let temp = TempDir::new().unwrap();
+        let src = temp.path().join("pkg/src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("main.rs"), "fn main() {}").unwrap();
+
+        let id = TaskId::new("pkg", "build");
+        let def = TaskDefinition::new("build").with_command("cargo build");
+
+        let sha = CacheKey::compute(&id, &def, temp.path());
+        let blake = CacheKey::compute_with(HashAlgorithm::Blake3, &id, &def, temp.path());
+
+        // Stable within an algorithm
+        assert_eq!(
+            sha,
+            CacheKey::compute_with(HashAlgorithm::Sha256, &id, &def, temp.path())
+        );
+        assert_eq!(
+            blake,
+            CacheKey::compute_with(HashAlgorithm::Blake3, &id, &def, temp.path())
+        );
+
+        // Distinct namespaces across algorithms
+        assert_ne!(sha, blake);
+        assert_eq!(sha.0.len(), 64);
+        assert!(blake.0.starts_with("blake3-"));
+
+        // Inputs still matter
+        fs::write(src.join("main.rs"), "fn main() { todo!() }").unwrap();
+        assert_ne!(
+            blake,
+            CacheKey::compute_with(HashAlgorithm::Blake3, &id, &def, temp.path())
+        );
+    }
+
+    #[test]
+    fn test_cache_lookup_ignores_other_algorithm() {
+        let temp = TempDir::new().unwrap();
+        let cache = TaskCache::new(temp.path().join("cache"));
+        let id = TaskId::new("pkg", "build");
+        let def = TaskDefinition::new("build").with_command("echo hello");
+
+        cache.store(&id, &def, temp.path(), "hello\n", "").unwrap();
+
+        let blake3 = cache.clone().with_hash_algorithm(HashAlgorithm::Blake3);
+        assert!(blake3.lookup(&id, &def, temp.path()).unwrap().is_none());
+        assert!(cache.lookup(&id, &def, temp.path()).unwrap().is_some());
+    }
+
     #[test]
     fn test_cache_store_and_lookup() {
         let temp = TempDir::new().unwrap();
@@ -1,6 +1,7 @@
 //! Task DAG construction and management
 
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::time::Duration;
 
 use serde::{Deserialize, Serialize};
 use tracing::{info, instrument};
@@ -286,6 +287,52 @@ impl TaskDag {
         &self.sorted_order
     }
 
+    /// Longest dependency chain, weighted by `durations`
+    ///
+    /// Returns the chain in execution order and its total duration. Tasks
+    /// missing from `durations` count as taking no time.
+    pub fn critical_path(&self, durations: &HashMap<TaskId, Duration>) -> (Vec<TaskId>, Duration) {
+        // Finish time of the longest chain ending at each task, and the
+        // dependency it came through
+        let mut finish: HashMap<&TaskId, (Duration, Option<&TaskId>)> = HashMap::new();
+        for id in &self.sorted_order {
+            let Some(node) = self.nodes.get(id) else {
+                continue;
+            };
+            let before = longest(
+                self.sorted_order
+                    .iter()
+                    .filter(|dep| node.dependencies.contains(*dep))
+                    .filter_map(|dep| finish.get(dep).map(|(time, _)| (*time, dep))),
+            );
+            let own = durations.get(id).copied().unwrap_or_default();
+            finish.insert(
+                id,
+                (
+                    before.map(|(t, _)| t).unwrap_or_default() + own,
+                    before.map(|(_, dep)| dep),
+                ),
+            );
+        }
+
+        let ends = self
+            .sorted_order
+            .iter()
+            .filter_map(|id| finish.get(id).map(|(time, _)| (*time, id)));
+        let Some((_, mut current)) = longest(ends) else {
+            return (Vec::new(), Duration::ZERO);
+        };
+
+        let total = finish[current].0;
+        let mut path = vec![current.clone()];
+        while let Some(dep) = finish[current].1 {
+            path.push(dep.clone());
+            current = dep;
+        }
+        path.reverse();
+        (path, total)
+    }
+
     /// Get a human-readable summary of the execution plan
     pub fn execution_plan(&self) -> String {
         let mut plan = String::new();
@@ -317,6 +364,16 @@ impl TaskDag {
     }
 }
 
+/// The longest of `chains`, the first one on ties
+fn longest<'a>(
+    chains: impl Iterator<Item = (Duration, &'a TaskId)>,
+) -> Option<(Duration, &'a TaskId)> {
+    chains.fold(None, |best, (time, id)| match best {
+        Some((best_time, _)) if best_time >= time => best,
+        _ => Some((time, id)),
+    })
+}
+
 /// Errors during DAG construction
 #[derive(Debug, thiserror::Error)]
 pub enum DagError {
@@ -388,6 +445,48 @@ mod tests {
         pipeline
     }
 
+    #[test]
+    fn test_critical_path() {
+        let graph = create_test_graph();
+        let pipeline = create_pipeline();
+        let packages = vec!["core".to_string(), "utils".to_string(), "app".to_string()];
+        let dag = TaskDag::build(
+            &graph,
+            &pipeline,
+            &["build".to_string(), "test".to_string()],
+            &packages,
+        )
+        .unwrap();
+
+        let secs = |package: &str, task: &str, secs: u64| {
+            (TaskId::new(package, task), Duration::from_secs(secs))
+        };
+        let mut durations = HashMap::from([
+            secs("core", "build", 3),
+            secs("utils", "build", 2),
+            secs("app", "build", 4),
+            secs("core", "test", 1),
+            secs("utils", "test", 1),
+            secs("app", "test", 1),
+        ]);
+
+        // core -> utils -> app builds, then app's tests
+        let (path, total) = dag.critical_path(&durations);
+        let names: Vec<String> = path.iter().map(|id| id.to_string()).collect();
+        assert_eq!(
+            names,
+            vec!["core:build", "utils:build", "app:build", "app:test"]
+        );
+        assert_eq!(total, Duration::from_secs(10));
+
+        // A slow test suite on a short chain takes over
+        durations.insert(TaskId::new("core", "test"), Duration::from_secs(8));
+        let (path, total) = dag.critical_path(&durations);
+        let names: Vec<String> = path.iter().map(|id| id.to_string()).collect();
+        assert_eq!(names, vec!["core:build", "core:test"]);
+        assert_eq!(total, Duration::from_secs(11));
+    }
+
     #[test]
     fn test_build_dag() {
         let graph = create_test_graph();
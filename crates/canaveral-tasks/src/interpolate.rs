@@ -0,0 +1,280 @@
+//! Variable interpolation in task commands
+//!
+//! Commands may reference `${NAME}` to stay portable across machines. Names
+//! resolve from the task's `env`, then the context variables
+//! (`workspace.root`, `package.name`, `package.dir`, `task.name` and any
+//! extra scheduler variables), then the process environment. `${NAME:-text}`
+//! falls back to `text` when the name is undefined, and `$$` is a literal
+//! `$`. A bare `$NAME` and other shell expansions such as `${#NAME}` or
+//! `${NAME%.txt}` are left alone for the shell to expand.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use thiserror::Error;
+
+use crate::task::{TaskDefinition, TaskId};
+
+/// Interpolation failures
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InterpolationError {
+    /// `${NAME}` without a value or fallback
+    #[error("undefined variable '{name}' in '{input}' (use ${{{name}:-default}} for a fallback)")]
+    Undefined { name: String, input: String },
+
+    /// `${` without a closing brace
+    #[error("unterminated '${{' in '{0}'")]
+    Unterminated(String),
+}
+
+/// Variables available to a task's command
+#[derive(Debug, Clone, Default)]
+pub struct InterpolationContext {
+    env: HashMap<String, String>,
+    vars: HashMap<String, String>,
+    use_process_env: bool,
+}
+
+impl InterpolationContext {
+    /// Context with no variables, not reading the process environment
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Context for running `definition` as task `id` from `root_dir`
+    ///
+    /// `extra` holds additional context variables, which the built-in ones
+    /// override.
+    pub fn for_task(
+        id: &TaskId,
+        definition: &TaskDefinition,
+        root_dir: &Path,
+        extra: &HashMap<String, String>,
+    ) -> Self {
+        let mut context = Self::new()
+            .with_process_env(true)
+            .with_vars(extra.clone())
+            .with_var("workspace.root", root_dir.display().to_string())
+            .with_var("package.name", &id.package)
+            .with_var("task.name", &definition.name);
+        if let Some(dir) = &definition.package_dir {
+            context = context.with_var("package.dir", root_dir.join(dir).display().to_string());
+        }
+        context.env = definition.env.clone();
+        context
+    }
+
+    /// Add a context variable
+    pub fn with_var(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.vars.insert(name.into(), value.into());
+        self
+    }
+
+    /// Add context variables
+    pub fn with_vars(mut self, vars: HashMap<String, String>) -> Self {
+        self.vars.extend(vars);
+        self
+    }
+
+    /// Add a task environment variable, which wins over context variables
+    pub fn with_env(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.insert(name.into(), value.into());
+        self
+    }
+
+    /// Fall back to the process environment for unknown names
+    pub fn with_process_env(mut self, enabled: bool) -> Self {
+        self.use_process_env = enabled;
+        self
+    }
+
+    /// Value of `name`, with task environment values interpolated the same
+    /// way [`resolve_env`](Self::resolve_env) does
+    fn lookup(&self, name: &str) -> Result<Option<String>, InterpolationError> {
+        if let Some(value) = self.env.get(name) {
+            return self.without_env().interpolate(value).map(Some);
+        }
+        Ok(self.vars.get(name).cloned().or_else(|| {
+            self.use_process_env
+                .then(|| std::env::var(name).ok())
+                .flatten()
+        }))
+    }
+
+    /// This context without the task environment
+    fn without_env(&self) -> Self {
+        Self {
+            env: HashMap::new(),
+            ..self.clone()
+        }
+    }
+
+    /// The task environment with its values interpolated
+    ///
+    /// Values see the context variables and process environment, not each
+    /// other.
+    pub fn resolve_env(&self) -> Result<HashMap<String, String>, InterpolationError> {
+        let values = self.without_env();
+        self.env
+            .iter()
+            .map(|(name, value)| Ok((name.clone(), values.interpolate(value)?)))
+            .collect()
+    }
+
+    /// Expand every `${...}` and `$$` in `input`
+    pub fn interpolate(&self, input: &str) -> Result<String, InterpolationError> {
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+
+        while let Some(pos) = rest.find('$') {
+            output.push_str(&rest[..pos]);
+            let after = &rest[pos + 1..];
+
+            if let Some(tail) = after.strip_prefix('$') {
+                output.push('$');
+                rest = tail;
+            } else if let Some(body) = after.strip_prefix('{') {
+                let end = body
+                    .find('}')
+                    .ok_or_else(|| InterpolationError::Unterminated(input.to_string()))?;
+                let (name, fallback) = match body[..end].split_once(":-") {
+                    Some((name, fallback)) => (name, Some(fallback)),
+                    None => (&body[..end], None),
+                };
+                if !is_variable_name(name) {
+                    // Some other shell expansion, e.g. `${#NAME}`
+                    output.push_str("${");
+                    rest = body;
+                    continue;
+                }
+                let value = self
+                    .lookup(name)?
+                    .filter(|value| fallback.is_none() || !value.is_empty())
+                    .or_else(|| fallback.map(str::to_string))
+                    .ok_or_else(|| InterpolationError::Undefined {
+                        name: name.to_string(),
+                        input: input.to_string(),
+                    })?;
+                output.push_str(&value);
+                rest = &body[end + 1..];
+            } else {
+                output.push('$');
+                rest = after;
+            }
+        }
+
+        output.push_str(rest);
+        Ok(output)
+    }
+}
+
+/// Whether `name` is a variable name: letters, digits, `_` and `.`, not
+/// starting with a digit or `.`
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context() -> InterpolationContext {
+        InterpolationContext::new()
+            .with_var("workspace.root", "/repo")
+            .with_var("TARGET", "from-context")
+            .with_env("TARGET", "x86_64-unknown-linux-gnu")
+            .with_env("EMPTY", "")
+    }
+
+    #[test]
+    fn test_simple_interpolation() {
+        let context = context();
+        assert_eq!(
+            context
+                .interpolate(
+                    "cargo build --target ${TARGET} --manifest-path ${workspace.root}/Cargo.toml"
+                )
+                .unwrap(),
+            "cargo build --target x86_64-unknown-linux-gnu --manifest-path /repo/Cargo.toml"
+        );
+        // Bare `$NAME` is the shell's, `$$` is a literal dollar
+        assert_eq!(
+            context.interpolate("echo $HOME $${TARGET} $$ 5$").unwrap(),
+            "echo $HOME ${TARGET} $ 5$"
+        );
+        // Other shell expansions are the shell's too
+        assert_eq!(
+            context
+                .interpolate("echo ${#TARGET} ${FILE%.txt} ${A:+set} ${!REF} ${X/a/b}")
+                .unwrap(),
+            "echo ${#TARGET} ${FILE%.txt} ${A:+set} ${!REF} ${X/a/b}"
+        );
+    }
+
+    #[test]
+    fn test_default_fallback() {
+        let context = context();
+        assert_eq!(
+            context
+                .interpolate("--profile ${PROFILE:-release}")
+                .unwrap(),
+            "--profile release"
+        );
+        assert_eq!(
+            context.interpolate("${TARGET:-native}").unwrap(),
+            "x86_64-unknown-linux-gnu"
+        );
+        // Like the shell, `:-` also covers empty values
+        assert_eq!(context.interpolate("${EMPTY:-none}").unwrap(), "none");
+        assert_eq!(context.interpolate("[${EMPTY}]").unwrap(), "[]");
+        assert_eq!(context.interpolate("${OUT:-}").unwrap(), "");
+    }
+
+    #[test]
+    fn test_undefined_variable() {
+        let err = context()
+            .interpolate("deploy --token ${DEPLOY_TOKEN}")
+            .unwrap_err();
+        assert_eq!(
+            err,
+            InterpolationError::Undefined {
+                name: "DEPLOY_TOKEN".to_string(),
+                input: "deploy --token ${DEPLOY_TOKEN}".to_string(),
+            }
+        );
+        assert!(err.to_string().contains("${DEPLOY_TOKEN:-default}"));
+
+        assert!(matches!(
+            context().interpolate("echo ${TARGET"),
+            Err(InterpolationError::Unterminated(_))
+        ));
+    }
+
+    #[test]
+    fn test_task_context() {
+        let mut definition = TaskDefinition::new("build").with_command("make");
+        definition.package_dir = Some("packages/core".to_string());
+        definition
+            .env
+            .insert("OUT".to_string(), "${workspace.root}/dist".to_string());
+        let context = InterpolationContext::for_task(
+            &TaskId::new("core", "build"),
+            &definition,
+            Path::new("/repo"),
+            &HashMap::from([("package.name".to_string(), "ignored".to_string())]),
+        );
+        assert_eq!(
+            context
+                .interpolate("${task.name} ${package.name} ${package.dir}")
+                .unwrap(),
+            "build core /repo/packages/core"
+        );
+        assert_eq!(context.resolve_env().unwrap()["OUT"], "/repo/dist");
+        // The command sees the same resolved value as the environment
+        assert_eq!(context.interpolate("ls ${OUT}").unwrap(), "ls /repo/dist");
+    }
+}
@@ -5,14 +5,16 @@
 
 pub mod cache;
 pub mod dag;
+pub mod interpolate;
 pub mod reporter;
 pub mod scheduler;
 pub mod task;
 pub mod test_selection;
 
-pub use cache::{CacheEntry, CacheKey, TaskCache};
+pub use cache::{CacheEntry, CacheKey, HashAlgorithm, TaskCache};
 pub use dag::{TaskDag, TaskNode};
-pub use reporter::{TaskEvent, TaskReporter, TaskReporterRegistry};
+pub use interpolate::{InterpolationContext, InterpolationError};
+pub use reporter::{TaskEvent, TaskReporter, TaskReporterRegistry, TimingReport};
 pub use scheduler::{TaskResult, TaskScheduler, TaskStatus};
 pub use task::{TaskCommand, TaskDefinition, TaskId};
 pub use test_selection::{SelectedTest, SelectionReason, TestMap, TestSelector};
@@ -1,8 +1,11 @@
 //! Task execution reporting
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use crate::dag::TaskDag;
+use crate::scheduler::TaskResult;
 use crate::task::TaskId;
 
 /// Events emitted during task execution
@@ -40,6 +43,63 @@ pub enum TaskEvent {
         cached: usize,
         duration: Duration,
     },
+    /// Where the time went, after all tasks completed
+    Timing(TimingReport),
+}
+
+/// Critical path and time spent across a run
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimingReport {
+    /// Wall-clock time of the whole run
+    pub wall_time: Duration,
+    /// Sum of all task durations
+    pub total_task_time: Duration,
+    /// Task durations, longest first
+    pub tasks: Vec<(TaskId, Duration)>,
+    /// Longest dependency chain, in execution order
+    pub critical_path: Vec<TaskId>,
+    /// Total duration of the critical path
+    pub critical_path_time: Duration,
+}
+
+impl TimingReport {
+    /// Build the report from the results of running `dag`
+    pub fn new(dag: &TaskDag, results: &[TaskResult], wall_time: Duration) -> Self {
+        let durations: HashMap<TaskId, Duration> =
+            results.iter().map(|r| (r.id.clone(), r.duration)).collect();
+        let (critical_path, critical_path_time) = dag.critical_path(&durations);
+
+        let mut tasks: Vec<(TaskId, Duration)> =
+            results.iter().map(|r| (r.id.clone(), r.duration)).collect();
+        tasks.sort_by_key(|(_, duration)| std::cmp::Reverse(*duration));
+
+        Self {
+            wall_time,
+            total_task_time: tasks.iter().map(|(_, d)| *d).sum(),
+            tasks,
+            critical_path,
+            critical_path_time,
+        }
+    }
+
+    /// Parallelism efficiency: task time divided by wall-clock time
+    ///
+    /// 1.0 means tasks ran one after another; higher means they overlapped.
+    pub fn efficiency(&self) -> f64 {
+        if self.wall_time.is_zero() {
+            return 0.0;
+        }
+        self.total_task_time.as_secs_f64() / self.wall_time.as_secs_f64()
+    }
+
+    /// Critical path as `a -> b -> c`
+    pub fn critical_path_display(&self) -> String {
+        self.critical_path
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    }
 }
 
 /// Trait for reporting task execution progress
@@ -118,6 +178,16 @@ impl TaskReporter for TracingReporter {
                     duration.as_secs_f64()
                 );
             }
+            TaskEvent::Timing(timing) => {
+                tracing::info!(
+                    "Critical path {} ({:.1}s), {:.1}s of task time in {:.1}s ({:.2}x parallelism)",
+                    timing.critical_path_display(),
+                    timing.critical_path_time.as_secs_f64(),
+                    timing.total_task_time.as_secs_f64(),
+                    timing.wall_time.as_secs_f64(),
+                    timing.efficiency()
+                );
+            }
         }
     }
 }
@@ -222,6 +292,70 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_timing_report() {
+        use crate::scheduler::TaskStatus;
+        use crate::task::TaskDefinition;
+        use canaveral_core::monorepo::discovery::DiscoveredPackage;
+        use canaveral_core::monorepo::graph::DependencyGraph;
+
+        let package = |name: &str, deps: &[&str]| DiscoveredPackage {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            path: name.into(),
+            manifest_path: format!("{}/package.json", name).into(),
+            package_type: "npm".to_string(),
+            private: false,
+            workspace_dependencies: deps.iter().map(|d| d.to_string()).collect(),
+        };
+        let graph =
+            DependencyGraph::build(&[package("core", &[]), package("app", &["core"])]).unwrap();
+        let pipeline = HashMap::from([
+            (
+                "build".to_string(),
+                TaskDefinition::new("build")
+                    .with_command("make")
+                    .with_depends_on_packages(true),
+            ),
+            (
+                "lint".to_string(),
+                TaskDefinition::new("lint").with_command("make lint"),
+            ),
+        ]);
+        let dag = TaskDag::build(
+            &graph,
+            &pipeline,
+            &["build".to_string(), "lint".to_string()],
+            &["core".to_string(), "app".to_string()],
+        )
+        .unwrap();
+
+        let result = |package: &str, task: &str, secs: u64| TaskResult {
+            id: TaskId::new(package, task),
+            status: TaskStatus::Success,
+            duration: Duration::from_secs(secs),
+            stdout: String::new(),
+            stderr: String::new(),
+        };
+        // Lints run alongside the 5s build chain
+        let results = vec![
+            result("core", "build", 2),
+            result("app", "build", 3),
+            result("core", "lint", 4),
+            result("app", "lint", 1),
+        ];
+
+        let timing = TimingReport::new(&dag, &results, Duration::from_secs(5));
+        assert_eq!(timing.critical_path_display(), "core:build -> app:build");
+        assert_eq!(timing.critical_path_time, Duration::from_secs(5));
+        assert_eq!(timing.total_task_time, Duration::from_secs(10));
+        assert_eq!(timing.efficiency(), 2.0);
+        assert_eq!(
+            timing.tasks[0],
+            (TaskId::new("core", "lint"), Duration::from_secs(4))
+        );
+    }
+
     #[test]
     fn test_empty_registry() {
         let registry = TaskReporterRegistry::empty();
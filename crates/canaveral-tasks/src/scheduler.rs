@@ -12,7 +12,8 @@ use tracing::{debug, info, instrument};
 
 use crate::cache::TaskCache;
 use crate::dag::TaskDag;
-use crate::reporter::{TaskEvent, TaskReporter};
+use crate::interpolate::InterpolationContext;
+use crate::reporter::{TaskEvent, TaskReporter, TimingReport};
 use crate::task::{TaskCommand, TaskId};
 
 /// Result of a single task execution
@@ -63,6 +64,8 @@ pub struct SchedulerOptions {
     pub dry_run: bool,
     /// Working directory root
     pub root_dir: std::path::PathBuf,
+    /// Extra `${name}` variables for task commands
+    pub vars: HashMap<String, String>,
 }
 
 impl Default for SchedulerOptions {
@@ -73,6 +76,7 @@ impl Default for SchedulerOptions {
             use_cache: true,
             dry_run: false,
             root_dir: std::env::current_dir().unwrap_or_default(),
+            vars: HashMap::new(),
         }
     }
 }
@@ -157,23 +161,13 @@ impl TaskScheduler {
                 let permit = semaphore.clone().acquire_owned().await.unwrap();
                 let id = task_id.clone();
                 let definition = node.definition.clone();
-                let root_dir = self.options.root_dir.clone();
-                let dry_run = self.options.dry_run;
+                let options = self.options.clone();
                 let reporter = self.reporter.clone();
-                let use_cache = self.options.use_cache;
                 let cache = self.cache.clone();
 
                 let handle = tokio::spawn(async move {
-                    let result = execute_task(
-                        &id,
-                        &definition,
-                        &root_dir,
-                        dry_run,
-                        use_cache,
-                        cache.as_ref(),
-                        &*reporter,
-                    )
-                    .await;
+                    let result =
+                        execute_task(&id, &definition, &options, cache.as_ref(), &*reporter).await;
                     drop(permit);
                     result
                 });
@@ -221,19 +215,25 @@ impl TaskScheduler {
             .filter(|r| matches!(r.status, TaskStatus::CacheHit))
             .count();
 
+        let wall_time = start.elapsed();
         self.reporter.report(&TaskEvent::AllCompleted {
             total,
             succeeded,
             failed: failed_count,
             cached,
-            duration: start.elapsed(),
+            duration: wall_time,
         });
 
         // Return results in topological order
-        dag.sorted()
+        let results: Vec<TaskResult> = dag
+            .sorted()
             .iter()
             .filter_map(|id| all_results.remove(id))
-            .collect()
+            .collect();
+        self.reporter.report(&TaskEvent::Timing(TimingReport::new(
+            dag, &results, wall_time,
+        )));
+        results
     }
 }
 
@@ -241,15 +241,41 @@ impl TaskScheduler {
 async fn execute_task(
     id: &TaskId,
     definition: &crate::task::TaskDefinition,
-    root_dir: &std::path::Path,
-    dry_run: bool,
-    use_cache: bool,
+    options: &SchedulerOptions,
     cache: Option<&TaskCache>,
     reporter: &dyn TaskReporter,
 ) -> TaskResult {
+    let root_dir = options.root_dir.as_path();
+    let (dry_run, use_cache) = (options.dry_run, options.use_cache);
     debug!(task = %id, dry_run, use_cache, "executing task");
     let start = Instant::now();
-    let command = definition.effective_command();
+
+    // Resolve `${...}` variables before anything runs
+    let context = InterpolationContext::for_task(id, definition, root_dir, &options.vars);
+    let resolved = match definition.effective_command() {
+        TaskCommand::Shell(cmd) => context
+            .interpolate(&cmd)
+            .and_then(|cmd| Ok((TaskCommand::Shell(cmd), context.resolve_env()?))),
+        TaskCommand::FrameworkAdapter => Ok((TaskCommand::FrameworkAdapter, HashMap::new())),
+    };
+    let (command, env) = match resolved {
+        Ok(resolved) => resolved,
+        Err(e) => {
+            let error = e.to_string();
+            reporter.report(&TaskEvent::Failed {
+                id: id.clone(),
+                duration: start.elapsed(),
+                error: error.clone(),
+            });
+            return TaskResult {
+                id: id.clone(),
+                status: TaskStatus::Failed(error),
+                duration: start.elapsed(),
+                stdout: String::new(),
+                stderr: String::new(),
+            };
+        }
+    };
     let cmd_str = match &command {
         TaskCommand::Shell(s) => s.clone(),
         TaskCommand::FrameworkAdapter => format!("<framework:{}>", definition.name),
@@ -298,7 +324,7 @@ async fn execute_task(
     // Execute the command
     match command {
         TaskCommand::Shell(ref cmd) => {
-            let result = run_shell_command(id, cmd, root_dir, reporter).await;
+            let result = run_shell_command(id, cmd, &env, root_dir, reporter).await;
             let duration = start.elapsed();
 
             match result {
@@ -360,12 +386,14 @@ async fn execute_task(
 async fn run_shell_command(
     id: &TaskId,
     cmd: &str,
+    env: &HashMap<String, String>,
     root_dir: &std::path::Path,
     reporter: &dyn TaskReporter,
 ) -> Result<(String, String), String> {
     let mut child = Command::new("sh")
         .arg("-c")
         .arg(cmd)
+        .envs(env)
         .current_dir(root_dir)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -488,5 +516,70 @@ mod tests {
 
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].status, TaskStatus::Skipped);
+        assert!(reporter.events().iter().any(
+            |e| matches!(e, TaskEvent::Timing(t) if t.critical_path == [results[0].id.clone()])
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_execute_interpolates_command() {
+        use crate::dag::TaskDag;
+        use crate::reporter::CollectingReporter;
+        use crate::task::TaskDefinition;
+        use canaveral_core::monorepo::discovery::DiscoveredPackage;
+        use canaveral_core::monorepo::graph::DependencyGraph;
+
+        let packages = vec![DiscoveredPackage {
+            name: "test-pkg".to_string(),
+            version: "1.0.0".to_string(),
+            path: "test-pkg".into(),
+            manifest_path: "test-pkg/package.json".into(),
+            package_type: "npm".to_string(),
+            private: false,
+            workspace_dependencies: vec![],
+        }];
+        let graph = DependencyGraph::build(&packages).unwrap();
+        let mut build = TaskDefinition::new("build")
+            .with_command("echo ${package.name} ${CHANNEL:-stable} $GREETING '$${literal}'");
+        build
+            .env
+            .insert("GREETING".to_string(), "hi-${channel}".to_string());
+        let pipeline = HashMap::from([
+            ("build".to_string(), build),
+            (
+                "deploy".to_string(),
+                TaskDefinition::new("deploy").with_command("deploy ${DEPLOY_TARGET_UNSET}"),
+            ),
+        ]);
+        let dag = TaskDag::build(
+            &graph,
+            &pipeline,
+            &["build".to_string(), "deploy".to_string()],
+            &["test-pkg".to_string()],
+        )
+        .unwrap();
+
+        let temp = tempfile::TempDir::new().unwrap();
+        let opts = SchedulerOptions {
+            use_cache: false,
+            continue_on_error: true,
+            root_dir: temp.path().to_path_buf(),
+            vars: HashMap::from([("channel".to_string(), "beta".to_string())]),
+            ..Default::default()
+        };
+        let scheduler = TaskScheduler::new(opts, None, Arc::new(CollectingReporter::default()));
+        let results = scheduler.execute(&dag).await;
+
+        let build = results.iter().find(|r| r.id.task_name == "build").unwrap();
+        assert_eq!(build.status, TaskStatus::Success);
+        assert_eq!(build.stdout, "test-pkg stable hi-beta ${literal}");
+
+        let deploy = results.iter().find(|r| r.id.task_name == "deploy").unwrap();
+        match &deploy.status {
+            TaskStatus::Failed(e) => {
+                assert!(e.contains("undefined variable 'DEPLOY_TARGET_UNSET'"))
+            }
+            other => panic!("expected failure, got {:?}", other),
+        }
     }
 }
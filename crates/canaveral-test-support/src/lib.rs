@@ -0,0 +1,255 @@
+//! Test helpers shared by the Canaveral crates
+//!
+//! [`MockServer`] is a minimal HTTP/1.1 server on a random local port. It
+//! answers each request with a canned [`MockResponse`] and records the
+//! [`Request`]s it received, so store, registry and webhook clients can be
+//! tested against their real request code.
+
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::thread::JoinHandle;
+
+/// A response served by [`MockServer`]
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    status: String,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MockResponse {
+    /// `200 OK` with a JSON body
+    pub fn json(body: impl Into<String>) -> Self {
+        Self::status("200 OK", body).with_header("Content-Type", "application/json")
+    }
+
+    /// A response with the given status line (e.g. `"404 Not Found"`) and body
+    pub fn status(status: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            status: status.into(),
+            headers: Vec::new(),
+            body: body.into().into_bytes(),
+        }
+    }
+
+    /// `204 No Content`
+    pub fn no_content() -> Self {
+        Self::status("204 No Content", "")
+    }
+
+    /// Add a response header
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        let mut head = format!("HTTP/1.1 {}\r\n", self.status);
+        for (name, value) in &self.headers {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        head.push_str(&format!(
+            "Content-Length: {}\r\nConnection: close\r\n\r\n",
+            self.body.len()
+        ));
+        let mut bytes = head.into_bytes();
+        bytes.extend_from_slice(&self.body);
+        bytes
+    }
+}
+
+impl From<&str> for MockResponse {
+    fn from(body: &str) -> Self {
+        Self::json(body)
+    }
+}
+
+/// A request received by [`MockServer`]
+#[derive(Debug, Clone)]
+pub struct Request {
+    /// HTTP method
+    pub method: String,
+    /// Path including the query string
+    pub path: String,
+    /// Headers, names lowercased
+    pub headers: Vec<(String, String)>,
+    /// Raw body
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    /// `"<method> <path>"`
+    pub fn line(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+
+    /// Value of the header `name` (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        let name = name.to_lowercase();
+        self.headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Body as (lossy) UTF-8
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).to_string()
+    }
+
+    /// Body parsed as JSON
+    ///
+    /// Panics if the body isn't JSON.
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).expect("request body is not JSON")
+    }
+}
+
+/// A local HTTP server answering a fixed number of requests
+pub struct MockServer {
+    url: String,
+    handle: JoinHandle<Vec<Request>>,
+}
+
+impl MockServer {
+    /// Serve `responses` in order, one per request
+    pub fn serve<R: Into<MockResponse>>(responses: Vec<R>) -> Self {
+        let responses: Vec<MockResponse> = responses.into_iter().map(Into::into).collect();
+        let count = responses.len();
+        let mut responses = responses.into_iter();
+        Self::with_handler(count, move |_| responses.next().unwrap())
+    }
+
+    /// Answer `requests` requests with `handler`
+    pub fn with_handler(
+        requests: usize,
+        mut handler: impl FnMut(&Request) -> MockResponse + Send + 'static,
+    ) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = std::thread::spawn(move || {
+            let mut received = Vec::new();
+            for _ in 0..requests {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                let response = handler(&request);
+                stream.write_all(&response.to_bytes()).unwrap();
+                received.push(request);
+            }
+            received
+        });
+        Self { url, handle }
+    }
+
+    /// Base URL of the server, without a trailing slash
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Wait for all requests to be answered and return them in order
+    ///
+    /// Panics if the server thread panicked.
+    pub fn requests(self) -> Vec<Request> {
+        self.handle.join().unwrap()
+    }
+}
+
+/// Read the headers and the announced body of one request
+fn read_request(stream: &mut impl Read) -> Request {
+    let mut data = Vec::new();
+    let mut buf = [0u8; 8192];
+    let (head_end, length) = loop {
+        let n = stream.read(&mut buf).unwrap();
+        data.extend_from_slice(&buf[..n]);
+        if let Some(end) = data.windows(4).position(|w| w == b"\r\n\r\n") {
+            let length = String::from_utf8_lossy(&data[..end])
+                .lines()
+                .find_map(|l| {
+                    l.to_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|v| v.trim().parse().unwrap())
+                })
+                .unwrap_or(0usize);
+            break (end, length);
+        }
+        if n == 0 {
+            break (data.len(), 0);
+        }
+    };
+    while data.len() < head_end + 4 + length {
+        let n = stream.read(&mut buf).unwrap();
+        if n == 0 {
+            break;
+        }
+        data.extend_from_slice(&buf[..n]);
+    }
+
+    let head = String::from_utf8_lossy(&data[..head_end]).to_string();
+    let mut lines = head.lines();
+    let mut start = lines.next().unwrap_or_default().split_whitespace();
+    let method = start.next().unwrap_or_default().to_string();
+    let path = start.next().unwrap_or_default().to_string();
+    let headers = lines
+        .filter_map(|l| l.split_once(':'))
+        .map(|(n, v)| (n.trim().to_lowercase(), v.trim().to_string()))
+        .collect();
+    let body = data.get(head_end + 4..).unwrap_or_default().to_vec();
+
+    Request {
+        method,
+        path,
+        headers,
+        body,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Send a raw request and return the raw response
+    fn send(url: &str, request: &str) -> String {
+        let mut stream = std::net::TcpStream::connect(url.trim_start_matches("http://")).unwrap();
+        stream.write_all(request.as_bytes()).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        response
+    }
+
+    #[test]
+    fn test_serves_responses_in_order() {
+        let server = MockServer::serve(vec![
+            MockResponse::json(r#"{"ok":true}"#),
+            MockResponse::status("404 Not Found", "missing").with_header("Retry-After", "0"),
+        ]);
+        let url = server.url().to_string();
+
+        let first = send(
+            &url,
+            "POST /v1/items?x=1 HTTP/1.1\r\nHost: x\r\nContent-Type: application/json\r\nContent-Length: 11\r\n\r\n{\"id\": 42}\n",
+        );
+        assert!(first.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(first.ends_with("{\"ok\":true}"));
+        let second = send(&url, "GET /v1/missing HTTP/1.1\r\nHost: x\r\n\r\n");
+        assert!(second.starts_with("HTTP/1.1 404 Not Found\r\n"));
+        assert!(second.contains("Retry-After: 0\r\n"));
+
+        let requests = server.requests();
+        assert_eq!(requests[0].line(), "POST /v1/items?x=1");
+        assert_eq!(requests[0].header("Content-Type"), Some("application/json"));
+        assert_eq!(requests[0].json()["id"], 42);
+        assert_eq!(requests[1].line(), "GET /v1/missing");
+        assert!(requests[1].body.is_empty());
+    }
+
+    #[test]
+    fn test_handler_sees_request() {
+        let server = MockServer::with_handler(2, |request| {
+            MockResponse::status("200 OK", request.path.clone())
+        });
+        let url = server.url().to_string();
+        assert!(send(&url, "GET /a HTTP/1.1\r\n\r\n").ends_with("/a"));
+        assert!(send(&url, "GET /b HTTP/1.1\r\n\r\n").ends_with("/b"));
+        assert_eq!(server.requests().len(), 2);
+    }
+}
@@ -0,0 +1,283 @@
+//! Monorepo version bumps
+//!
+//! [`apply_bumps`] writes computed version bumps to every package's
+//! manifest and version mirrors through its detected adapter, several
+//! packages at a time, and [`commit_bumps`] records all of them in a single
+//! release commit.
+
+use std::collections::HashMap;
+
+use canaveral_adapters::{AdapterRegistry, MirroredAdapter};
+use canaveral_core::config::Config;
+use canaveral_core::error::{CanaveralError, Result};
+use canaveral_core::monorepo::{BumpResult, DiscoveredPackage, VersionApplier, VersionBump};
+use canaveral_core::workflow::ReleaseCommitMessage;
+use canaveral_git::GitRepo;
+use tracing::info;
+
+/// Write `bumps` to the packages' manifests, at most `concurrency` at once
+///
+/// Each package's configured version mirrors are written with it. Every
+/// adapter locks the files it rewrites, so packages sharing a file (a
+/// workspace root manifest or a common version mirror) don't lose writes.
+pub fn apply_bumps(
+    config: &Config,
+    packages: &[DiscoveredPackage],
+    bumps: &[VersionBump],
+    concurrency: usize,
+) -> BumpResult {
+    // A broken version source fails each package's bump, not the batch
+    let registry = AdapterRegistry::from_config(config);
+    let mirrors: HashMap<String, _> = config
+        .packages
+        .iter()
+        .map(|package| (package.name.clone(), package.version_mirrors.clone()))
+        .collect();
+    VersionApplier::new(Box::new(move |package, version| {
+        let registry = registry
+            .as_ref()
+            .map_err(|e| CanaveralError::other(e.to_string()))?;
+        let adapter = registry
+            .for_package(&package.name, &package.package_type)
+            .or_else(|| registry.detect(&package.path))
+            .ok_or_else(|| {
+                CanaveralError::other(format!(
+                    "no adapter for {} at {}",
+                    package.name,
+                    package.path.display()
+                ))
+            })?;
+        let mirrors = mirrors
+            .get(&package.name)
+            .map(Vec::as_slice)
+            .unwrap_or_default();
+        MirroredAdapter::from_config(adapter, mirrors)?.set_version(&package.path, version)
+    }))
+    .with_concurrency(concurrency)
+    .apply(packages, bumps)
+}
+
+/// Commit the files written for `result` as one release commit
+///
+/// Stages every file that changed since `baseline`, the repository's
+/// [`modified_files`](GitRepo::modified_files) from before the bump, so
+/// shared files and changelogs are committed along with the manifests. The
+/// message renders `template` (see [`ReleaseCommitMessage`]) with every
+/// bumped package. Fails without committing if any package failed to bump,
+/// so a partial bump is never recorded.
+pub fn commit_bumps(
+    repo: &GitRepo,
+    result: &BumpResult,
+    baseline: &[String],
+    template: &str,
+) -> Result<()> {
+    ensure_bumped(result)?;
+
+    let mut files: Vec<String> = repo
+        .modified_files()?
+        .into_iter()
+        .filter(|file| !baseline.contains(file))
+        .collect();
+    for manifest in result.changed_files() {
+        let manifest = manifest
+            .strip_prefix(repo.path())
+            .unwrap_or(manifest)
+            .to_string_lossy()
+            .replace('\\', "/");
+        if !files.contains(&manifest) {
+            files.push(manifest);
+        }
+    }
+    if files.is_empty() {
+        return Ok(());
+    }
+    let message = ReleaseCommitMessage::for_packages(
+        result
+            .successful()
+            .into_iter()
+            .map(|p| (p.package.as_str(), p.version.as_str())),
+    )
+    .render(template);
+    repo.commit_paths(&files, &message)?;
+    info!(files = files.len(), "committed version bumps");
+    Ok(())
+}
+
+/// Fail with every package's error if any package failed to bump
+pub fn ensure_bumped(result: &BumpResult) -> Result<()> {
+    if result.success() {
+        return Ok(());
+    }
+    let failed: Vec<_> = result
+        .failed()
+        .iter()
+        .map(|p| {
+            format!(
+                "{}: {}",
+                p.package,
+                p.error.as_deref().unwrap_or("unknown error")
+            )
+        })
+        .collect();
+    Err(CanaveralError::other(format!(
+        "not committing, {} package(s) failed to bump:\n{}",
+        failed.len(),
+        failed.join("\n")
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_core::config::{PackageConfig, VersionMirrorConfig};
+    use canaveral_core::types::ReleaseType;
+    use tempfile::TempDir;
+
+    /// Repository with `count` npm packages under `packages/`, committed
+    fn workspace(count: usize) -> (TempDir, GitRepo, Vec<DiscoveredPackage>) {
+        let temp = TempDir::new().unwrap();
+        let git = git2::Repository::init(temp.path()).unwrap();
+        let mut config = git.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+
+        let packages: Vec<_> = (0..count)
+            .map(|i| {
+                let name = format!("pkg-{}", i);
+                let path = temp.path().join("packages").join(&name);
+                std::fs::create_dir_all(&path).unwrap();
+                std::fs::write(
+                    path.join("package.json"),
+                    format!(r#"{{"name": "{}", "version": "1.0.0"}}"#, name),
+                )
+                .unwrap();
+                DiscoveredPackage {
+                    name,
+                    version: "1.0.0".to_string(),
+                    manifest_path: path.join("package.json"),
+                    path,
+                    package_type: "npm".to_string(),
+                    private: false,
+                    workspace_dependencies: vec![],
+                }
+            })
+            .collect();
+        let versions: String = packages
+            .iter()
+            .map(|p| format!("{} = \"1.0.0\"\n", p.name))
+            .collect();
+        std::fs::write(temp.path().join("VERSIONS"), versions).unwrap();
+
+        let repo = GitRepo::open(temp.path()).unwrap();
+        repo.commit_paths(&repo.modified_files().unwrap(), "initial")
+            .unwrap();
+        (temp, repo, packages)
+    }
+
+    fn bumps(packages: &[DiscoveredPackage], version: &str) -> Vec<VersionBump> {
+        packages
+            .iter()
+            .map(|p| VersionBump {
+                package: p.name.clone(),
+                current_version: "1.0.0".to_string(),
+                new_version: version.to_string(),
+                release_type: ReleaseType::Minor,
+                reason: "direct changes".to_string(),
+            })
+            .collect()
+    }
+
+    fn commit_count(repo: &GitRepo) -> usize {
+        repo.all_commits().unwrap().len()
+    }
+
+    #[test]
+    fn test_bump_many_packages_in_one_commit() {
+        let (_temp, repo, packages) = workspace(12);
+        let baseline = repo.modified_files().unwrap();
+
+        let result = apply_bumps(&Config::default(), &packages, &bumps(&packages, "1.1.0"), 4);
+        assert!(result.success(), "{:?}", result.failed());
+        for package in &packages {
+            let manifest = std::fs::read_to_string(&package.manifest_path).unwrap();
+            assert!(manifest.contains(r#""version": "1.1.0""#), "{}", manifest);
+        }
+
+        commit_bumps(
+            &repo,
+            &result,
+            &baseline,
+            "chore(release): {version}\n\n{#packages}- {name}@{version}\n{/packages}",
+        )
+        .unwrap();
+        assert_eq!(commit_count(&repo), 2);
+        let head = repo.head_commit().unwrap();
+        let message = head.message().unwrap();
+        assert!(message.starts_with("chore(release): 1.1.0\n\n- pkg-0@1.1.0\n"));
+        assert!(message.contains("- pkg-11@1.1.0\n"));
+        assert!(message.trim_end().ends_with("Release-Tool: canaveral"));
+        let git = repo.inner();
+        let diff = git
+            .diff_tree_to_tree(
+                Some(&head.parent(0).unwrap().tree().unwrap()),
+                Some(&head.tree().unwrap()),
+                None,
+            )
+            .unwrap();
+        assert_eq!(diff.deltas().len(), 12);
+        assert!(repo.is_clean().unwrap());
+    }
+
+    #[test]
+    fn test_shared_mirror_is_written_and_committed() {
+        let (temp, repo, packages) = workspace(8);
+        let config = Config {
+            packages: packages
+                .iter()
+                .map(|p| PackageConfig {
+                    name: p.name.clone(),
+                    path: p.path.clone(),
+                    package_type: "npm".to_string(),
+                    publish: true,
+                    registry: None,
+                    tag_format: None,
+                    version_files: Vec::new(),
+                    version_mirrors: vec![VersionMirrorConfig {
+                        file: "../../VERSIONS".into(),
+                        pattern: Some(format!(r#"{} = "([^"]+)""#, p.name)),
+                        json_path: None,
+                        template: None,
+                    }],
+                    version_source: None,
+                    release_assets: None,
+                })
+                .collect(),
+            ..Config::default()
+        };
+        // Unrelated local edits stay out of the release commit
+        std::fs::write(temp.path().join("notes.txt"), "wip").unwrap();
+        let baseline = repo.modified_files().unwrap();
+
+        let result = apply_bumps(&config, &packages, &bumps(&packages, "2.0.0"), 8);
+        assert!(result.success(), "{:?}", result.failed());
+        commit_bumps(&repo, &result, &baseline, "chore(release): bump").unwrap();
+
+        // No package's write to the shared file was lost
+        let versions = repo.file_at("HEAD", "VERSIONS").unwrap().unwrap();
+        assert_eq!(versions.matches("\"2.0.0\"").count(), 8, "{}", versions);
+        assert_eq!(repo.untracked_files().unwrap(), ["notes.txt"]);
+        assert_eq!(commit_count(&repo), 2);
+    }
+
+    #[test]
+    fn test_failed_bump_is_not_committed() {
+        let (_temp, repo, packages) = workspace(2);
+        let mut bumps = bumps(&packages[..1], "1.1.0");
+        bumps[0].package = "pkg-missing".to_string();
+
+        let result = apply_bumps(&Config::default(), &packages, &bumps, 4);
+        let err = commit_bumps(&repo, &result, &[], "chore(release): bump").unwrap_err();
+        assert!(err.to_string().contains("pkg-missing"));
+        assert_eq!(commit_count(&repo), 1);
+    }
+}
@@ -0,0 +1,34 @@
+//! Ctrl-C handling for long-running commands
+
+use std::sync::OnceLock;
+
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use crate::exit_codes;
+
+/// Token cancelled by the first Ctrl-C; a second Ctrl-C exits immediately
+///
+/// The signal listener is installed on the first call; later calls return
+/// the same token. Must be called from within a Tokio runtime.
+pub fn ctrl_c_token() -> CancellationToken {
+    static TOKEN: OnceLock<CancellationToken> = OnceLock::new();
+    TOKEN
+        .get_or_init(|| {
+            let token = CancellationToken::new();
+            let cancel = token.clone();
+            tokio::spawn(async move {
+                if tokio::signal::ctrl_c().await.is_err() {
+                    return;
+                }
+                warn!("interrupted, cancelling (press Ctrl-C again to force quit)");
+                cancel.cancel();
+
+                if tokio::signal::ctrl_c().await.is_ok() {
+                    std::process::exit(exit_codes::CANCELLED);
+                }
+            });
+            token
+        })
+        .clone()
+}
@@ -18,6 +18,7 @@ use canaveral_frameworks::{
     Orchestrator, OrchestratorConfig, OutputFormat as FrameworkOutputFormat,
 };
 
+use crate::cli::cancel::ctrl_c_token;
 use crate::cli::output::Ui;
 use crate::cli::{Cli, OutputFormat};
 
@@ -286,7 +287,8 @@ impl ArchiveCommand {
             ..Default::default()
         };
 
-        let orchestrator = Orchestrator::with_config(orchestrator_config);
+        let orchestrator =
+            Orchestrator::with_config(orchestrator_config).with_cancellation(ctrl_c_token());
 
         let output_format = match cli.format {
             OutputFormat::Text => FrameworkOutputFormat::Text,
@@ -298,7 +300,11 @@ impl ArchiveCommand {
         if exit_code != 0 {
             if ui.is_text() {
                 ui.blank();
-                ui.error("Archive failed");
+                if output.cancelled {
+                    ui.warning("Archive cancelled");
+                } else {
+                    ui.error("Archive failed");
+                }
             }
             std::process::exit(exit_code);
         }
@@ -12,6 +12,7 @@ use canaveral_frameworks::{
     OutputFormat as FrameworkOutputFormat,
 };
 
+use crate::cli::cancel::ctrl_c_token;
 use crate::cli::output::Ui;
 use crate::cli::{Cli, OutputFormat};
 
@@ -105,6 +106,11 @@ pub struct BuildCommand {
     #[arg(long)]
     pub skip_signing: bool,
 
+    /// Previous release's artifact; writes an update patch from it next to
+    /// the primary artifact when that is smaller than the full artifact
+    #[arg(long, value_name = "PATH")]
+    pub delta_from: Option<PathBuf>,
+
     /// Extra arguments to pass to the underlying build tool
     #[arg(last = true)]
     pub extra_args: Vec<String>,
@@ -241,6 +247,10 @@ impl BuildCommand {
             ctx = ctx.with_build_number(build_number);
         }
 
+        if let Some(ref base) = self.delta_from {
+            ctx = ctx.with_delta_base(base);
+        }
+
         // iOS-specific: merge CLI flags with config for scheme, destination, etc.
         let is_ios = matches!(platform, Platform::Ios | Platform::MacOs);
         if is_ios {
@@ -361,7 +371,8 @@ impl BuildCommand {
             ..Default::default()
         };
 
-        let orchestrator = Orchestrator::with_config(orchestrator_config);
+        let orchestrator =
+            Orchestrator::with_config(orchestrator_config).with_cancellation(ctrl_c_token());
 
         // If framework is specified, validate it
         if let Some(ref framework) = self.framework {
@@ -419,7 +430,11 @@ impl BuildCommand {
         if exit_code != 0 {
             if ui.is_text() {
                 ui.blank();
-                ui.error("Build failed");
+                if output.cancelled {
+                    ui.warning("Build cancelled");
+                } else {
+                    ui.error("Build failed");
+                }
             }
             std::process::exit(exit_code);
         }
@@ -0,0 +1,49 @@
+//! Capabilities command - report what a project's detected adapters can do
+
+use std::path::PathBuf;
+
+use clap::Args;
+use tracing::info;
+
+use canaveral_frameworks::{FrameworkRegistry, Output, OutputFormat as FrameworkOutputFormat};
+
+use crate::cli::{Cli, OutputFormat};
+
+/// Report the capabilities of a project's detected adapters
+#[derive(Debug, Args)]
+pub struct CapabilitiesCommand {
+    /// Path to the project (defaults to current directory)
+    #[arg(default_value = ".")]
+    pub path: PathBuf,
+}
+
+impl CapabilitiesCommand {
+    /// Print each capability with its adapter and prerequisite status
+    ///
+    /// With `--format json` this is the framework [`Output`], whose `outputs`
+    /// hold a `can_<capability>` flag for CI to gate steps on.
+    pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        info!(path = %self.path.display(), "executing capabilities command");
+        let runtime = tokio::runtime::Runtime::new()?;
+        let statuses =
+            runtime.block_on(FrameworkRegistry::with_builtins().capability_statuses(&self.path));
+
+        let output = if statuses.is_empty() {
+            Output::success("capabilities", "No adapters detected")
+        } else {
+            Output::success(
+                "capabilities",
+                format!("{} capabilities detected", statuses.len()),
+            )
+            .with_capabilities(statuses)
+        };
+        let format = match cli.format {
+            OutputFormat::Text => FrameworkOutputFormat::Text,
+            OutputFormat::Json => FrameworkOutputFormat::Json,
+        };
+        if !cli.quiet {
+            output.print(format);
+        }
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
 //! Changelog command
 
-use clap::Args;
+use chrono::NaiveTime;
+use clap::{Args, ValueEnum};
 use console::style;
 use tracing::info;
 
-use canaveral_changelog::ChangelogGenerator;
-use canaveral_core::config::load_config_or_default;
-use canaveral_git::GitRepo;
+use canaveral_changelog::formatter::start_of_date;
+use canaveral_changelog::{
+    ChangelogGenerator, ChangelogHistory, Collapse, GithubNotesOptions, HtmlMode,
+    MarkdownFormatter, ReleaseNotesGenerator,
+};
+use canaveral_core::config::{load_config_or_default, Config};
+use canaveral_core::fs::{prepend_atomic, write_atomic};
+use canaveral_core::workflow::{format_tag, tag_pattern};
+use canaveral_git::{DateRange, GitRepo, TagInfo};
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
@@ -26,9 +33,84 @@ pub struct ChangelogCommand {
     #[arg(short, long)]
     pub output: Option<std::path::PathBuf>,
 
+    /// Write the generated text to this file, replacing it, instead of
+    /// printing it
+    #[arg(long, value_name = "PATH", conflicts_with = "write")]
+    pub output_file: Option<std::path::PathBuf>,
+
+    /// Generate release notes instead of a changelog entry
+    #[arg(long, conflicts_with = "write")]
+    pub notes: bool,
+
+    /// Format the release notes as a GitHub Release body, with collapsible
+    /// sections and a compare link
+    #[arg(long, requires = "notes")]
+    pub github: bool,
+
+    /// Format the release notes as markdown (default), an HTML fragment, or
+    /// a standalone HTML email
+    #[arg(
+        long,
+        value_enum,
+        value_name = "FORMAT",
+        requires = "notes",
+        conflicts_with = "github"
+    )]
+    pub notes_format: Option<NotesFormat>,
+
     /// Include all commits (don't filter by type)
     #[arg(long)]
     pub all: bool,
+
+    /// Release date for the version header (YYYY-MM-DD). Defaults to the
+    /// version's tag date when it has been tagged, otherwise today
+    #[arg(long, value_name = "DATE")]
+    pub date: Option<chrono::NaiveDate>,
+
+    /// Only include commits committed on or after this date (YYYY-MM-DD).
+    /// Selects from the whole history instead of since the latest tag
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<chrono::NaiveDate>,
+
+    /// Only include commits committed before this date (YYYY-MM-DD)
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<chrono::NaiveDate>,
+
+    /// Regenerate the whole changelog, one entry per version tag, followed
+    /// by compare links. With --write, replaces the changelog file
+    #[arg(long, conflicts_with_all = ["for_version", "notes", "since", "until"])]
+    pub full: bool,
+
+    /// Preview the Unreleased entry for the commits since the latest tag,
+    /// e.g. for a pull request comment
+    #[arg(
+        long,
+        conflicts_with_all = ["for_version", "write", "notes", "since", "until", "full"]
+    )]
+    pub unreleased: bool,
+}
+
+/// Release notes output format
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum NotesFormat {
+    /// Markdown, as for a changelog
+    Markdown,
+    /// HTML fragment for embedding in a page
+    Html,
+    /// Standalone HTML document with inline styles, for email
+    #[value(name = "email-html")]
+    EmailHtml,
+}
+
+impl NotesFormat {
+    /// The HTML rendering for this format, if it is one
+    fn html_mode(self) -> Option<HtmlMode> {
+        match self {
+            Self::Markdown => None,
+            Self::Html => Some(HtmlMode::Plain),
+            Self::EmailHtml => Some(HtmlMode::Email),
+        }
+    }
 }
 
 impl ChangelogCommand {
@@ -40,9 +122,24 @@ impl ChangelogCommand {
         let (config, _) = load_config_or_default(&cwd);
 
         let repo = GitRepo::discover(&cwd)?;
+        let formatter =
+            MarkdownFormatter::new().with_tag_format(format_tag(&config, "{version}", None));
+        if self.full {
+            return self.execute_full(&ui, &cwd, &repo, &config, &formatter);
+        }
 
         // Find the latest tag
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag = repo.find_latest_tag_filtered(
+            Some(&tag_pattern(&config, None)),
+            config.versioning.include_prereleases,
+        )?;
+
+        if self.unreleased {
+            let since = latest_tag.as_ref().map(|t| t.name.as_str());
+            let generator =
+                ChangelogGenerator::new(config.changelog.clone()).with_formatter(formatter);
+            return self.output_unreleased(&ui, &repo, &generator, since);
+        }
 
         // Determine version
         let version = self.for_version.clone().unwrap_or_else(|| {
@@ -53,42 +150,112 @@ impl ChangelogCommand {
                 .unwrap_or_else(|| "Unreleased".to_string())
         });
 
-        // Get commits
-        let commits = if let Some(tag) = &latest_tag {
+        // Get commits: by commit date when a window is given, otherwise
+        // since the latest tag
+        let range = DateRange {
+            since: self.since.map(|d| d.and_time(NaiveTime::MIN).and_utc()),
+            until: self.until.map(|d| d.and_time(NaiveTime::MIN).and_utc()),
+        };
+        let commits = if !range.is_unbounded() {
+            repo.commits_in_range(&range)?
+        } else if let Some(tag) = &latest_tag {
             repo.commits_since_tag(&tag.name)?
         } else {
             repo.all_commits()?
         };
 
         if commits.is_empty() {
-            ui.warning("No commits found since last release.");
+            if range.is_unbounded() {
+                ui.warning("No commits found since last release.");
+            } else {
+                ui.warning("No commits found in the given date range.");
+            }
             return Ok(());
         }
 
-        // Generate changelog
-        let generator = ChangelogGenerator::new(config.changelog.clone());
-        let changelog = generator.generate_formatted(&version, &commits);
+        // Generate changelog, dated by the explicit date or the version's tag
+        let mut generator =
+            ChangelogGenerator::new(config.changelog.clone()).with_formatter(formatter.clone());
+        if let Some(version) = &self.for_version {
+            if let Some(tag) = repo.find_tag(&format_tag(&config, version, None))? {
+                generator = generator.with_tag(&tag);
+            }
+        }
+        if let Some(date) = self.date {
+            generator = generator
+                .with_release_date(start_of_date(date, config.changelog.timezone.as_deref()));
+        }
+        let changelog = if self.github {
+            let notes_generator = ReleaseNotesGenerator::new(config.release_notes.clone());
+            let notes = notes_generator.generate(&version, &commits);
+            let mut options = GithubNotesOptions::new().with_collapse(
+                config
+                    .release_notes
+                    .github_collapse_over
+                    .map_or(Collapse::Never, Collapse::Over),
+            );
+            // A first release compares against the repository's first commit
+            let base = match &latest_tag {
+                Some(previous) => Some(previous.name.clone()),
+                None => repo.first_commit().ok().map(|c| c.hash),
+            };
+            if let (Some(repo_url), Some(base), Some(version)) =
+                (&config.changelog.repository_url, &base, &self.for_version)
+            {
+                let tag = format_tag(&config, version, None);
+                options = options.with_compare(repo_url, base, &tag);
+            }
+            notes_generator.format_github(&notes, &options)
+        } else if self.notes {
+            let notes_generator = ReleaseNotesGenerator::new(config.release_notes.clone());
+            match self.notes_format.and_then(NotesFormat::html_mode) {
+                Some(mode) => {
+                    notes_generator.format_html(&notes_generator.generate(&version, &commits), mode)
+                }
+                None => notes_generator.generate_formatted(&version, &commits),
+            }
+        } else {
+            generator.generate_formatted(&version, &commits)
+        };
 
         // Output
-        if self.write {
+        if let Some(output_file) = &self.output_file {
+            write_atomic(output_file, &changelog)?;
+            ui.success(&format!(
+                "{} written to {}",
+                if self.notes {
+                    "Release notes"
+                } else {
+                    "Changelog"
+                },
+                style(output_file.display()).cyan()
+            ));
+        } else if self.write {
             let output_path = self
                 .output
                 .clone()
                 .unwrap_or_else(|| cwd.join(&config.changelog.file));
 
-            // Prepend to existing file or create new
-            if output_path.exists() {
-                let existing = std::fs::read_to_string(&output_path)?;
-                let combined = format!("{}\n{}", changelog, existing);
-                std::fs::write(&output_path, combined)?;
+            if config.changelog.preserve_existing && !self.notes {
+                // Only add the new version, keeping past versions as written
+                let history = ChangelogHistory::read(&output_path, &config.changelog)?;
+                let entry = generator.generate_after(&history, &version, &commits);
+                write_atomic(
+                    &output_path,
+                    history.insert(&version, &generator.format(&entry)),
+                )?;
             } else {
-                std::fs::write(&output_path, &changelog)?;
+                // Prepend to existing file or create new
+                prepend_atomic(&output_path, &changelog)?;
             }
+            refresh_links(&output_path, &formatter, &config)?;
 
             ui.success(&format!(
                 "Changelog written to {}",
                 style(output_path.display()).cyan()
             ));
+        } else if ui.is_json() && self.notes {
+            ui.json(&serde_json::json!({ "version": version, "notes": changelog }))?;
         } else if ui.is_json() {
             let entry = generator.generate(&version, &commits);
             ui.json(&entry)?;
@@ -98,4 +265,123 @@ impl ChangelogCommand {
 
         Ok(())
     }
+
+    /// Output the Unreleased entry for the commits after `since`
+    fn output_unreleased(
+        &self,
+        ui: &Ui,
+        repo: &GitRepo,
+        generator: &ChangelogGenerator,
+        since: Option<&str>,
+    ) -> anyhow::Result<()> {
+        if ui.is_json() && self.output_file.is_none() {
+            return ui.json(&generator.unreleased(repo, since)?);
+        }
+        let preview = generator.preview_unreleased(repo, since)?;
+        match &self.output_file {
+            Some(path) => {
+                write_atomic(path, &preview)?;
+                ui.success(&format!(
+                    "Unreleased changes written to {}",
+                    style(path.display()).cyan()
+                ));
+            }
+            None => println!("{}", preview),
+        }
+        Ok(())
+    }
+
+    /// Generate an entry for every version tag and output them as one
+    /// changelog
+    fn execute_full(
+        &self,
+        ui: &Ui,
+        cwd: &std::path::Path,
+        repo: &GitRepo,
+        config: &Config,
+        formatter: &MarkdownFormatter,
+    ) -> anyhow::Result<()> {
+        // The version is whatever the tag format wraps
+        let format = format_tag(config, "{version}", None);
+        let (prefix, suffix) = format.split_once("{version}").unwrap_or((&format, ""));
+        let mut tags: Vec<(semver::Version, TagInfo)> = repo
+            .tags_matching(&tag_pattern(config, None))?
+            .into_iter()
+            .filter_map(|tag| {
+                let version = tag.name.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                Some((semver::Version::parse(version).ok()?, tag))
+            })
+            .collect();
+        if tags.is_empty() {
+            ui.warning("No version tags found.");
+            return Ok(());
+        }
+        tags.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut entries = Vec::new();
+        let mut previous: Option<&TagInfo> = None;
+        for (version, tag) in &tags {
+            let commits = match previous {
+                Some(previous) => repo.commits_between(&previous.name, &tag.name)?,
+                None => repo.commits_until(&tag.name)?,
+            };
+            entries.push(
+                ChangelogGenerator::new(config.changelog.clone())
+                    .with_tag(tag)
+                    .generate(&version.to_string(), &commits),
+            );
+            previous = Some(tag);
+        }
+        entries.reverse();
+        let changelog = formatter.format_all(&entries, &config.changelog);
+
+        let output_path = match (&self.output_file, self.write) {
+            (Some(path), _) => Some(path.clone()),
+            (None, true) => Some(
+                self.output
+                    .clone()
+                    .unwrap_or_else(|| cwd.join(&config.changelog.file)),
+            ),
+            (None, false) => None,
+        };
+        match output_path {
+            Some(path) => {
+                // Keep the title and any introduction above the entries
+                let history = ChangelogHistory::read(&path, &config.changelog)?;
+                write_atomic(&path, format!("{}{}", history.preamble(), changelog))?;
+                ui.success(&format!(
+                    "Changelog with {} version(s) written to {}",
+                    entries.len(),
+                    style(path.display()).cyan()
+                ));
+            }
+            None if ui.is_json() => ui.json(&entries)?,
+            None => println!("{}", changelog),
+        }
+        Ok(())
+    }
+}
+
+/// Rewrite the compare links at the end of the changelog at `path` for the
+/// versions it lists
+///
+/// Leaves the file alone when links are disabled or no repository URL is
+/// configured.
+fn refresh_links(
+    path: &std::path::Path,
+    formatter: &MarkdownFormatter,
+    config: &Config,
+) -> anyhow::Result<()> {
+    let history = ChangelogHistory::read(path, &config.changelog)?;
+    // Skip placeholder headings like `1.2.0 (unreleased)`
+    let versions: Vec<&str> = history
+        .released_versions()
+        .into_iter()
+        .filter(|v| !v.contains(char::is_whitespace))
+        .collect();
+    let links = formatter.format_links(&versions, &config.changelog);
+    if !links.is_empty() {
+        write_atomic(path, history.with_links(&links))?;
+    }
+    Ok(())
 }
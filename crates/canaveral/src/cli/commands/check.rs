@@ -6,9 +6,9 @@ use clap::Args;
 use console::style;
 use tracing::info;
 
-use canaveral_adapters::AdapterRegistry;
+use canaveral_adapters::{AdapterRegistry, CheckStatus, HygieneReport, DEFAULT_CHECK_CONCURRENCY};
 
-use crate::cli::output::Ui;
+use crate::cli::output::{BadgeStyle, Ui};
 use crate::cli::Cli;
 
 /// Run all checks: format verification, linting, and tests
@@ -29,6 +29,15 @@ pub struct CheckCommand {
     /// Steps to skip (fmt, lint, test)
     #[arg(long = "skip", value_delimiter = ',')]
     pub skip: Vec<String>,
+
+    /// Check formatting and lints of every package in the workspace and
+    /// report them together
+    #[arg(long, conflicts_with_all = ["affected", "skip"])]
+    pub all: bool,
+
+    /// Packages checked at once with --all
+    #[arg(long, default_value_t = DEFAULT_CHECK_CONCURRENCY, requires = "all")]
+    pub concurrency: usize,
 }
 
 impl CheckCommand {
@@ -47,6 +56,10 @@ impl CheckCommand {
         }
 
         let registry = AdapterRegistry::new();
+        if self.all {
+            let report = registry.check_workspace(&path, self.concurrency)?;
+            return print_report(&ui, &report);
+        }
 
         let adapter = registry
             .detect(&path)
@@ -106,6 +119,64 @@ impl CheckCommand {
     }
 }
 
+/// Print the workspace report, failing when any package failed a check
+fn print_report(ui: &Ui, report: &HygieneReport) -> anyhow::Result<()> {
+    if ui.is_json() {
+        let status = |status: &CheckStatus| match status {
+            CheckStatus::Passed => serde_json::json!({ "status": "passed" }),
+            CheckStatus::Failed(output) => {
+                serde_json::json!({ "status": "failed", "output": output })
+            }
+            CheckStatus::Skipped => serde_json::json!({ "status": "skipped" }),
+        };
+        let packages: Vec<serde_json::Value> = report
+            .packages
+            .iter()
+            .map(|package| {
+                serde_json::json!({
+                    "name": package.name,
+                    "path": package.path,
+                    "adapter": package.adapter,
+                    "fmt": status(&package.fmt),
+                    "lint": status(&package.lint),
+                })
+            })
+            .collect();
+        ui.json(&serde_json::json!({ "passed": report.passed(), "packages": packages }))?;
+    } else {
+        ui.blank();
+        for package in &report.packages {
+            let (badge, label) = if !package.passed() {
+                (BadgeStyle::Fail, "FAIL")
+            } else if matches!(
+                (&package.fmt, &package.lint),
+                (CheckStatus::Skipped, CheckStatus::Skipped)
+            ) {
+                (BadgeStyle::Skip, "SKIP")
+            } else {
+                (BadgeStyle::Ok, "OK")
+            };
+            ui.badge_line(
+                badge,
+                label,
+                &package.name,
+                package.adapter.unwrap_or("no adapter"),
+            );
+        }
+        ui.blank();
+    }
+
+    if !report.passed() {
+        anyhow::bail!(
+            "{} package(s) failed checks:\n{}",
+            report.failures().count(),
+            report.diagnostics().join("\n")
+        );
+    }
+    ui.success("All packages passed!");
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +188,8 @@ mod tests {
             affected: false,
             base: "main".to_string(),
             skip: vec![],
+            all: false,
+            concurrency: DEFAULT_CHECK_CONCURRENCY,
         };
         assert!(cmd.skip.is_empty());
     }
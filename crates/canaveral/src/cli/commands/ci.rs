@@ -222,6 +222,7 @@ impl CIRunCommand {
             affected: self.affected,
             base: self.base.clone(),
             filter: Vec::new(),
+            exclude: Vec::new(),
             concurrency: None,
             dry_run: self.dry_run,
             continue_on_error: false,
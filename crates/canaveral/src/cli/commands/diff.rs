@@ -0,0 +1,115 @@
+//! Diff command - what changed between two versions
+
+use clap::Args;
+use console::style;
+use tracing::info;
+
+use canaveral_changelog::{diff_versions_with_config, ChangelogGenerator};
+use canaveral_core::config::{load_config_or_default, Config};
+use canaveral_core::workflow::format_tag;
+use canaveral_git::GitRepo;
+
+use crate::cli::output::Ui;
+use crate::cli::Cli;
+
+/// Show what changed between two versions
+#[derive(Debug, Args)]
+pub struct DiffCommand {
+    /// Version or git ref to diff from
+    pub from: String,
+
+    /// Version or git ref to diff to
+    #[arg(default_value = "HEAD")]
+    pub to: String,
+}
+
+impl DiffCommand {
+    /// Execute the diff command
+    pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        info!(from = %self.from, to = %self.to, "executing diff command");
+        let ui = Ui::new(cli);
+        let cwd = std::env::current_dir()?;
+        let (config, _) = load_config_or_default(&cwd);
+        let repo = GitRepo::discover(&cwd)?;
+
+        let from = resolve_ref(&repo, &config, &self.from);
+        let to = resolve_ref(&repo, &config, &self.to);
+        let diff = diff_versions_with_config(&repo, &from, &to, &config.changelog)?;
+
+        if ui.is_json() {
+            return ui.json(&diff);
+        }
+        if !ui.is_text() {
+            return Ok(());
+        }
+
+        ui.header(&format!(
+            "Changes from {} to {}",
+            ui.fmt_tag(&diff.from),
+            ui.fmt_tag(&diff.to)
+        ));
+        ui.blank();
+        if diff.is_empty() {
+            ui.info("Nothing changed.");
+            return Ok(());
+        }
+
+        if !diff.packages.is_empty() {
+            ui.section("Packages:");
+            for package in &diff.packages {
+                println!(
+                    "  {} {} {} {}",
+                    style(&package.name).cyan(),
+                    package.from_version.as_deref().unwrap_or("(new)"),
+                    style("→").dim(),
+                    package.to_version.as_deref().unwrap_or("(removed)")
+                );
+            }
+            ui.blank();
+        }
+
+        ui.key_value("Commits", &style(diff.commits.len()).cyan().to_string());
+        ui.blank();
+        println!(
+            "{}",
+            ChangelogGenerator::new(config.changelog.clone()).format(&diff.changelog)
+        );
+        Ok(())
+    }
+}
+
+/// The version's tag when `reference` is a released version, else
+/// `reference` itself
+fn resolve_ref(repo: &GitRepo, config: &Config, reference: &str) -> String {
+    // Refs like `HEAD~3` don't make valid tag names
+    match repo.find_tag(&format_tag(config, reference, None)) {
+        Ok(Some(tag)) => tag.name,
+        _ => reference.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_ref_prefers_version_tags() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+        let tree_id = repo.index().unwrap().write_tree().unwrap();
+        let tree = repo.find_tree(tree_id).unwrap();
+        let commit = repo
+            .commit(Some("HEAD"), &sig, &sig, "feat: initial", &tree, &[])
+            .unwrap();
+        repo.tag_lightweight("v1.0.0", &repo.find_object(commit, None).unwrap(), false)
+            .unwrap();
+
+        let repo = GitRepo::open(temp.path()).unwrap();
+        let config = Config::default();
+        assert_eq!(resolve_ref(&repo, &config, "1.0.0"), "v1.0.0");
+        assert_eq!(resolve_ref(&repo, &config, "v1.0.0"), "v1.0.0");
+        assert_eq!(resolve_ref(&repo, &config, "main"), "main");
+        assert_eq!(resolve_ref(&repo, &config, "HEAD~3"), "HEAD~3");
+    }
+}
@@ -1,6 +1,6 @@
 //! Doctor command - check environment for required tools and configurations
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use clap::Args;
@@ -10,6 +10,7 @@ use tracing::info;
 
 use crate::cli::output::{BadgeStyle, Ui};
 use crate::cli::Cli;
+use crate::prerequisites::{PrerequisitesCheck, ToolCategory};
 
 /// Check environment for required tools and configurations
 #[derive(Debug, Args)]
@@ -29,6 +30,10 @@ pub struct DoctorCommand {
     /// Check for a specific framework
     #[arg(long)]
     pub framework: Option<FrameworkCheck>,
+
+    /// Report the tools a project needs instead of checking the environment
+    #[arg(long, value_name = "PATH", num_args = 0..=1, default_missing_value = ".")]
+    pub project: Option<PathBuf>,
 }
 
 /// Categories of checks
@@ -98,6 +103,9 @@ impl DoctorCommand {
     pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
         info!(fix = self.fix, "executing doctor command");
         let ui = Ui::new(cli);
+        if let Some(project) = &self.project {
+            return self.execute_project(&ui, project);
+        }
         let mut checks = Vec::new();
 
         // Determine which categories to check
@@ -181,6 +189,69 @@ impl DoctorCommand {
         Ok(())
     }
 
+    /// Report the tools the project at `path` needs
+    fn execute_project(&self, ui: &Ui, path: &Path) -> anyhow::Result<()> {
+        let runtime = tokio::runtime::Runtime::new()?;
+        let report = runtime.block_on(PrerequisitesCheck::new().run(path));
+        let missing = report.missing().count();
+
+        if ui.is_json() {
+            ui.json(&report)?;
+        } else {
+            ui.header(&format!("Checking prerequisites for {}...", path.display()));
+            for category in [
+                ToolCategory::Build,
+                ToolCategory::Test,
+                ToolCategory::Store,
+                ToolCategory::Signing,
+                ToolCategory::Package,
+            ] {
+                let tools: Vec<_> = report
+                    .tools
+                    .iter()
+                    .filter(|t| t.category == category)
+                    .collect();
+                if tools.is_empty() {
+                    continue;
+                }
+                ui.blank();
+                ui.section(category.as_str());
+                for tool in tools {
+                    let (status, detail) = if tool.available {
+                        (CheckStatus::Ok, tool.version.clone().unwrap_or_default())
+                    } else {
+                        (
+                            CheckStatus::Fail,
+                            tool.install_hint.clone().unwrap_or_default(),
+                        )
+                    };
+                    let name = format!("{} ({})", tool.name, tool.required_by);
+                    ui.badge_line(
+                        badge_for_status(status),
+                        status_label(status),
+                        &name,
+                        &detail,
+                    );
+                }
+            }
+            for warning in &report.warnings {
+                ui.warning(warning);
+            }
+
+            ui.blank();
+            if report.tools.is_empty() {
+                ui.info("No adapters detected, nothing to check");
+            } else if missing == 0 {
+                ui.success(&format!("All {} required tools found", report.tools.len()));
+            }
+        }
+
+        if missing > 0 {
+            anyhow::bail!("{} required tool(s) missing", missing);
+        }
+        Ok(())
+    }
+
     fn get_categories(&self) -> Vec<CheckCategory> {
         let all_categories = vec![
             CheckCategory::Rust,
@@ -33,6 +33,9 @@ pub enum MatchSubcommand {
 
     /// Show match status
     Status(StatusCommand),
+
+    /// Re-encrypt the storage to a new set of team members
+    Rotate(RotateCommand),
 }
 
 /// Initialize match repository
@@ -69,6 +72,10 @@ pub struct InitCommand {
     /// Output directory for keys
     #[arg(short, long, default_value = ".canaveral/match")]
     pub output: PathBuf,
+
+    /// Additional team member public key to encrypt to (repeatable)
+    #[arg(long = "recipient")]
+    pub recipients: Vec<String>,
 }
 
 /// Sync certificates and profiles
@@ -117,6 +124,10 @@ pub struct NukeCommand {
     /// Skip confirmation prompt
     #[arg(long)]
     pub yes: bool,
+
+    /// Show what would be removed without removing anything
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 /// Show status
@@ -131,6 +142,23 @@ pub struct StatusCommand {
     pub config: PathBuf,
 }
 
+/// Rotate the storage encryption to new recipients
+#[derive(Debug, Args)]
+pub struct RotateCommand {
+    /// Team member public key to encrypt to (repeatable); your own key is
+    /// always included
+    #[arg(long = "recipient", visible_alias = "recipients", required = true)]
+    pub recipients: Vec<String>,
+
+    /// Private key file
+    #[arg(long, default_value = ".canaveral/match/match.key")]
+    pub keyfile: PathBuf,
+
+    /// Storage configuration file
+    #[arg(short, long, default_value = ".canaveral/match/config.toml")]
+    pub config: PathBuf,
+}
+
 /// Storage type argument
 #[derive(Debug, Clone, Copy, ValueEnum)]
 pub enum StorageType {
@@ -171,6 +199,7 @@ impl MatchCommand {
             MatchSubcommand::Sync(_) => "sync",
             MatchSubcommand::Nuke(_) => "nuke",
             MatchSubcommand::Status(_) => "status",
+            MatchSubcommand::Rotate(_) => "rotate",
         };
         info!(subcommand = subcommand_name, "executing match command");
         let runtime = tokio::runtime::Runtime::new()?;
@@ -183,6 +212,7 @@ impl MatchCommand {
             MatchSubcommand::Sync(cmd) => cmd.execute(cli).await,
             MatchSubcommand::Nuke(cmd) => cmd.execute(cli).await,
             MatchSubcommand::Status(cmd) => cmd.execute(cli).await,
+            MatchSubcommand::Rotate(cmd) => cmd.execute(cli).await,
         }
     }
 }
@@ -255,11 +285,21 @@ impl InitCommand {
         let pubfile = self.output.join("match.pub");
         std::fs::write(&pubfile, &keypair.public_key)?;
 
+        // Encrypt to every team member, including ourselves
+        let recipients = if self.recipients.is_empty() {
+            Vec::new()
+        } else {
+            std::iter::once(keypair.public_key.clone())
+                .chain(self.recipients.iter().cloned())
+                .collect()
+        };
+
         // Save configuration
         let config = MatchConfig {
             storage: storage.clone(),
             team_id: self.team_id.clone(),
             encryption_key: Some(keypair.public_key.clone()),
+            recipients,
             ..Default::default()
         };
 
@@ -398,26 +438,6 @@ impl NukeCommand {
     async fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
         let ui = Ui::new(cli);
 
-        // Confirmation prompt
-        if !self.yes {
-            let target = if self.profile_type.is_some() {
-                format!("{:?} profiles", self.profile_type.unwrap())
-            } else {
-                "ALL certificates and profiles".to_string()
-            };
-
-            ui.warning(&format!(
-                "This will remove {} from the match repository. This action cannot be undone.",
-                target
-            ));
-
-            let confirmed = ui.confirm("Are you sure?", false)?;
-            if !confirmed {
-                ui.info("Aborted.");
-                return Ok(());
-            }
-        }
-
         // Load configuration
         let config_content = std::fs::read_to_string(&self.config)?;
         let config: MatchConfig = toml::from_str(&config_content)?;
@@ -434,14 +454,50 @@ impl NukeCommand {
             private_key,
         };
 
+        let sync = MatchSync::new(config)?.with_keypair(keypair);
+        let profile_type = self.profile_type.map(|p| p.into());
+
+        // Preview what would be removed
+        let plan = sync.nuke(profile_type, true).await?;
+        if ui.is_json() && self.dry_run {
+            return ui.json(&plan);
+        }
+        if plan.is_empty() {
+            ui.info("Nothing to remove.");
+            return Ok(());
+        }
+
+        ui.section(&format!("Would remove {}:", plan.summary()));
+        let paths = plan.storage_paths();
+        ui.list(&paths);
+        ui.blank();
+
+        if self.dry_run {
+            ui.hint("Dry run, nothing was removed");
+            return Ok(());
+        }
+
+        // Confirmation prompt
+        if !self.yes {
+            ui.warning(&format!(
+                "This will remove {} from the match repository. This action cannot be undone.",
+                plan.summary()
+            ));
+
+            let confirmed = ui.confirm("Are you sure?", false)?;
+            if !confirmed {
+                ui.info("Aborted.");
+                return Ok(());
+            }
+        }
+
         ui.blank();
         ui.header("Removing certificates and profiles...");
 
         // Run nuke
-        let sync = MatchSync::new(config)?.with_keypair(keypair);
-        sync.nuke(self.profile_type.map(|p| p.into())).await?;
+        let removed = sync.nuke(profile_type, false).await?;
 
-        ui.success("Certificates and profiles removed");
+        ui.success(&format!("Removed {}", removed.summary()));
         ui.blank();
         ui.hint(&format!(
             "Run {} to regenerate",
@@ -554,6 +610,88 @@ impl StatusCommand {
     }
 }
 
+impl RotateCommand {
+    async fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        let ui = Ui::new(cli);
+
+        // Load configuration
+        let config_content = std::fs::read_to_string(&self.config)
+            .map_err(|_| anyhow::anyhow!("Config not found. Run 'canaveral match init' first."))?;
+        let mut config: MatchConfig = toml::from_str(&config_content)?;
+
+        // Load keypair
+        let private_key = std::fs::read_to_string(&self.keyfile)
+            .map_err(|_| anyhow::anyhow!("Key file not found: {}", self.keyfile.display()))?;
+
+        let public_key = config
+            .encryption_key
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("No encryption key in config"))?;
+
+        // Keep access for ourselves, like `match init`
+        let mut recipients = vec![public_key.clone()];
+        for recipient in &self.recipients {
+            if !recipients.contains(recipient) {
+                recipients.push(recipient.clone());
+            }
+        }
+
+        let keypair = canaveral_signing::team::KeyPair {
+            public_key,
+            private_key,
+        };
+
+        ui.blank();
+        ui.header("Rotating match encryption...");
+        ui.key_value("Recipients", &style(recipients.len()).cyan().to_string());
+        ui.blank();
+
+        let sync = MatchSync::new(config.clone())?.with_keypair(keypair);
+        let spinner = ui.spinner("Re-encrypting certificates and profiles...");
+        let report = sync
+            .rotate(&recipients, |progress| {
+                spinner.set_message(format!(
+                    "Re-encrypted {}/{} {}",
+                    progress.completed, progress.total, progress.path
+                ));
+            })
+            .await;
+        let report = match report {
+            Ok(report) => {
+                spinner.finish_and_clear();
+                report
+            }
+            Err(e) => {
+                spinner.fail("Rotation failed");
+                ui.hint("Run the same command again to resume the rotation");
+                return Err(e.into());
+            }
+        };
+
+        // Later uploads from this machine encrypt to the new set too
+        config.recipients = report.recipients.clone();
+        std::fs::write(&self.config, toml::to_string_pretty(&config)?)?;
+
+        if ui.is_json() {
+            ui.json(&serde_json::json!({
+                "certificates": report.certificates,
+                "profiles": report.profiles,
+                "recipients": report.recipients,
+            }))?;
+        } else {
+            ui.success(&format!(
+                "Re-encrypted {} certificates and {} profiles to {} recipients",
+                style(report.certificates).cyan(),
+                style(report.profiles).cyan(),
+                style(report.recipients.len()).cyan()
+            ));
+            ui.hint("Removed team members can no longer decrypt the storage");
+        }
+
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -563,4 +701,25 @@ mod tests {
         let dev: ProfileType = ProfileTypeArg::Development.into();
         assert!(matches!(dev, ProfileType::Development));
     }
+
+    #[test]
+    fn test_rotate_requires_recipients() {
+        use clap::Parser;
+
+        #[derive(Parser)]
+        struct TestCli {
+            #[command(subcommand)]
+            command: MatchSubcommand,
+        }
+
+        let cli =
+            TestCli::try_parse_from(["test", "rotate", "--recipient", "a", "--recipients", "b"])
+                .unwrap();
+        let MatchSubcommand::Rotate(rotate) = cli.command else {
+            panic!("expected rotate");
+        };
+        assert_eq!(rotate.recipients, ["a", "b"]);
+
+        assert!(TestCli::try_parse_from(["test", "rotate"]).is_err());
+    }
 }
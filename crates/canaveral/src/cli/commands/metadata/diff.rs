@@ -11,6 +11,7 @@ use canaveral_metadata::sync::{
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::preflight::screenshot_filter;
 
 use super::{parse_locales, truncate_str, AppleAuthOptions, GooglePlayAuthOptions, SinglePlatform};
 
@@ -53,6 +54,8 @@ pub async fn execute(cmd: &DiffArgs, cli: &Cli) -> anyhow::Result<()> {
         .clone()
         .unwrap_or_else(|| config.metadata.storage.path.clone());
 
+    let filter = screenshot_filter(&config.metadata.screenshots);
+
     let store_name = match cmd.platform {
         SinglePlatform::Apple => "App Store Connect",
         SinglePlatform::GooglePlay => "Google Play Console",
@@ -65,12 +68,16 @@ pub async fn execute(cmd: &DiffArgs, cli: &Cli) -> anyhow::Result<()> {
     let diff = match cmd.platform {
         SinglePlatform::Apple => {
             let config = cmd.apple_auth.to_config()?;
-            let sync = AppleMetadataSync::new(config, metadata_path).await?;
+            let sync = AppleMetadataSync::new(config, metadata_path)
+                .await?
+                .with_screenshot_filter(filter);
             sync.diff(&cmd.app_id).await?
         }
         SinglePlatform::GooglePlay => {
             let config = cmd.google_auth.to_config()?;
-            let sync = GooglePlayMetadataSync::new(config, metadata_path).await?;
+            let sync = GooglePlayMetadataSync::new(config, metadata_path)
+                .await?
+                .with_screenshot_filter(filter);
             sync.diff(&cmd.app_id).await?
         }
     };
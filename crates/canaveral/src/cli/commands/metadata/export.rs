@@ -9,6 +9,7 @@ use canaveral_metadata::{FastlaneStorage, MetadataStorage};
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::preflight::screenshot_filter;
 
 use super::{escape_csv, SinglePlatform};
 
@@ -59,7 +60,8 @@ pub async fn execute(cmd: &ExportArgs, cli: &Cli) -> anyhow::Result<()> {
         .clone()
         .unwrap_or_else(|| config.metadata.storage.path.clone());
 
-    let storage = FastlaneStorage::new(&metadata_path);
+    let storage = FastlaneStorage::new(&metadata_path)
+        .with_screenshot_filter(screenshot_filter(&config.metadata.screenshots));
 
     ui.step(&format!("Exporting metadata for {}", &cmd.app_id));
 
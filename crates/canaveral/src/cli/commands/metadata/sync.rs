@@ -1,16 +1,18 @@
 //! metadata sync (pull/push) subcommand
 
-use clap::{Args, Subcommand};
+use clap::{Args, Subcommand, ValueEnum};
 use console::style;
 use std::path::PathBuf;
 
 use canaveral_core::config::load_config_or_default;
 use canaveral_metadata::sync::{
-    AppleMetadataSync, AppleSyncConfig, GooglePlayMetadataSync, GooglePlaySyncConfig, MetadataSync,
+    AppleMetadataSync, AppleSyncConfig, ConflictPolicy, GooglePlayMetadataSync,
+    GooglePlaySyncConfig, MetadataSync,
 };
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::preflight::screenshot_filter;
 
 use super::{parse_locales, SinglePlatform};
 
@@ -188,10 +190,18 @@ pub struct SyncPushArgs {
     #[arg(long)]
     pub skip_screenshots: bool,
 
+    /// Replace Google Play screenshots that differ from local ones
+    #[arg(long)]
+    pub replace_screenshots: bool,
+
     /// Preview changes without actually pushing
     #[arg(long)]
     pub dry_run: bool,
 
+    /// Which side wins for fields also changed remotely since the last pull
+    #[arg(long, value_enum, default_value = "local")]
+    pub on_conflict: ConflictPolicyArg,
+
     /// Path to metadata directory
     #[arg(long)]
     pub path: Option<PathBuf>,
@@ -205,6 +215,27 @@ pub struct SyncPushArgs {
     pub google_auth: GooglePlayAuthOptions,
 }
 
+/// Conflict resolution policy for push
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ConflictPolicyArg {
+    /// Overwrite remote edits with local values
+    Local,
+    /// Keep remote edits
+    Remote,
+    /// Keep local values edited after the last pull, otherwise remote
+    Newer,
+}
+
+impl From<ConflictPolicyArg> for ConflictPolicy {
+    fn from(policy: ConflictPolicyArg) -> Self {
+        match policy {
+            ConflictPolicyArg::Local => ConflictPolicy::Local,
+            ConflictPolicyArg::Remote => ConflictPolicy::Remote,
+            ConflictPolicyArg::Newer => ConflictPolicy::Newer,
+        }
+    }
+}
+
 pub async fn execute(cmd: &SyncCommand, cli: &Cli) -> anyhow::Result<()> {
     match &cmd.command {
         SyncSubcommand::Pull(args) => execute_pull(args, cli).await,
@@ -280,6 +311,7 @@ async fn execute_push(cmd: &SyncPushArgs, cli: &Cli) -> anyhow::Result<()> {
         .unwrap_or_else(|| config.metadata.storage.path.clone());
 
     let locales = parse_locales(&cmd.locales)?;
+    let filter = screenshot_filter(&config.metadata.screenshots);
 
     let store_name = match cmd.platform {
         SinglePlatform::Apple => "App Store Connect",
@@ -305,15 +337,32 @@ async fn execute_push(cmd: &SyncPushArgs, cli: &Cli) -> anyhow::Result<()> {
     let result = match cmd.platform {
         SinglePlatform::Apple => {
             let config = cmd.apple_auth.to_config()?;
-            let sync = AppleMetadataSync::new(config, metadata_path).await?;
-            sync.push(&cmd.app_id, locales.as_deref(), cmd.dry_run)
+            let sync = AppleMetadataSync::new(config, metadata_path)
                 .await?
+                .with_screenshot_filter(filter);
+            sync.push(
+                &cmd.app_id,
+                locales.as_deref(),
+                cmd.dry_run,
+                cmd.on_conflict.into(),
+            )
+            .await?
         }
         SinglePlatform::GooglePlay => {
-            let config = cmd.google_auth.to_config()?;
-            let sync = GooglePlayMetadataSync::new(config, metadata_path).await?;
-            sync.push(&cmd.app_id, locales.as_deref(), cmd.dry_run)
+            let config = cmd
+                .google_auth
+                .to_config()?
+                .with_replace_screenshots(cmd.replace_screenshots);
+            let sync = GooglePlayMetadataSync::new(config, metadata_path)
                 .await?
+                .with_screenshot_filter(filter);
+            sync.push(
+                &cmd.app_id,
+                locales.as_deref(),
+                cmd.dry_run,
+                cmd.on_conflict.into(),
+            )
+            .await?
         }
     };
 
@@ -346,17 +395,18 @@ async fn execute_push(cmd: &SyncPushArgs, cli: &Cli) -> anyhow::Result<()> {
                     println!("    {} {}", style("-").dim(), locale);
                 }
             }
-
-            if !result.warnings.is_empty() {
-                println!();
-                println!("  {}:", style("Warnings").yellow());
-                for warning in &result.warnings {
-                    println!("    {} {}", style("!").yellow(), warning);
-                }
-            }
         } else {
             println!("{}", style("No changes to push.").dim());
         }
+
+        // Resolved conflicts are reported even when nothing was pushed
+        if !result.warnings.is_empty() {
+            println!();
+            println!("  {}:", style("Warnings").yellow());
+            for warning in &result.warnings {
+                println!("    {} {}", style("!").yellow(), warning);
+            }
+        }
     }
 
     Ok(())
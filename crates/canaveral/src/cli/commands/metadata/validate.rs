@@ -11,6 +11,7 @@ use canaveral_metadata::{
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::preflight::screenshot_filter;
 
 use super::TargetPlatform;
 
@@ -50,7 +51,8 @@ pub async fn execute(cmd: &ValidateArgs, cli: &Cli) -> anyhow::Result<()> {
         .unwrap_or_else(|| config.metadata.storage.path.clone());
     let strict = cmd.strict.unwrap_or(config.metadata.validation.strict);
 
-    let storage = FastlaneStorage::new(&metadata_path);
+    let storage = FastlaneStorage::new(&metadata_path)
+        .with_screenshot_filter(screenshot_filter(&config.metadata.screenshots));
 
     ui.step(&format!("Validating metadata for {}", &cmd.app_id));
 
@@ -63,7 +65,10 @@ pub async fn execute(cmd: &ValidateArgs, cli: &Cli) -> anyhow::Result<()> {
                 );
             }
             let metadata = storage.load_apple(&cmd.app_id).await?;
-            let validator = AppleValidator::new(strict);
+            let mut validator = AppleValidator::new(strict);
+            if let Some(threshold) = config.metadata.validation.similarity_threshold {
+                validator = validator.with_similarity_threshold(threshold);
+            }
             validator.validate(&metadata)
         }
         TargetPlatform::GooglePlay => {
@@ -74,7 +79,10 @@ pub async fn execute(cmd: &ValidateArgs, cli: &Cli) -> anyhow::Result<()> {
                 );
             }
             let metadata = storage.load_google_play(&cmd.app_id).await?;
-            let validator = GooglePlayValidator::new(strict);
+            let mut validator = GooglePlayValidator::new(strict);
+            if let Some(threshold) = config.metadata.validation.similarity_threshold {
+                validator = validator.with_similarity_threshold(threshold);
+            }
             validator.validate(&metadata)
         }
         TargetPlatform::Both => {
@@ -0,0 +1,94 @@
+//! Migrate command - Convert another release tool's configuration
+
+use clap::Args;
+use console::style;
+use tracing::info;
+
+use canaveral_core::{auto_migrate, preview_migration, MigrationPreview};
+
+use crate::cli::output::Ui;
+use crate::cli::Cli;
+
+/// Migrate from semantic-release, release-please, standard-version or lerna
+#[derive(Debug, Args)]
+pub struct MigrateCommand {
+    /// Show the configuration that would be written, without writing it
+    #[arg(long, visible_alias = "preview")]
+    pub dry_run: bool,
+}
+
+impl MigrateCommand {
+    /// Execute the migrate command
+    pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        info!(dry_run = self.dry_run, "executing migrate command");
+        let ui = Ui::new(cli);
+        let cwd = std::env::current_dir()?;
+
+        if self.dry_run {
+            let preview = preview_migration(&cwd)?;
+            if ui.is_json() {
+                return ui.json(&preview);
+            }
+            print_preview(&ui, &preview);
+            ui.hint("Dry run, nothing was written");
+            return Ok(());
+        }
+
+        let result = auto_migrate(&cwd)?;
+        let path = result.write(&cwd)?;
+        let preview = result.preview()?;
+        if ui.is_json() {
+            return ui.json(&serde_json::json!({
+                "path": path,
+                "migration": preview,
+            }));
+        }
+        print_notes(&ui, &preview);
+        ui.success(&format!(
+            "Migrated from {} to {}",
+            style(preview.source.as_str()).cyan(),
+            ui.fmt_path(&path.display())
+        ));
+        Ok(())
+    }
+}
+
+/// Print the would-be configuration, its sources and what needs attention
+fn print_preview(ui: &Ui, preview: &MigrationPreview) {
+    if !ui.is_text() {
+        return;
+    }
+    ui.header(&format!(
+        "Migration from {}",
+        style(preview.source.as_str()).cyan()
+    ));
+    ui.blank();
+    ui.section("Read:");
+    for source in &preview.sources {
+        ui.step(&source.display().to_string());
+    }
+    ui.blank();
+    ui.section("canaveral.toml:");
+    println!("{}", preview.config);
+    print_notes(ui, preview);
+}
+
+/// Print the settings that didn't translate cleanly
+fn print_notes(ui: &Ui, preview: &MigrationPreview) {
+    if !ui.is_text() {
+        return;
+    }
+    for warning in &preview.warnings {
+        ui.warning(warning);
+    }
+    for setting in &preview.unsupported {
+        ui.warning(&format!("Not migrated: {}", setting));
+    }
+    if !preview.manual_steps.is_empty() {
+        ui.section("Manual steps:");
+        for step in &preview.manual_steps {
+            ui.step(step);
+        }
+        ui.blank();
+    }
+}
@@ -3,10 +3,12 @@
 mod archive;
 mod build;
 mod cache;
+mod capabilities;
 mod changelog;
 mod check;
 mod ci;
 mod completions;
+mod diff;
 mod doctor;
 mod firebase;
 mod fmt;
@@ -15,9 +17,12 @@ mod init;
 mod lint;
 mod match_cmd;
 mod metadata;
+mod migrate;
 mod pr;
 mod publish;
 mod release;
+mod release_steps;
+mod release_workspace;
 mod run;
 mod scaffold;
 mod screenshots;
@@ -33,10 +38,12 @@ mod version;
 pub use archive::ArchiveCommand;
 pub use build::BuildCommand;
 pub use cache::CacheCommand;
+pub use capabilities::CapabilitiesCommand;
 pub use changelog::ChangelogCommand;
 pub use check::CheckCommand;
 pub use ci::CICommand;
 pub use completions::CompletionsCommand;
+pub use diff::DiffCommand;
 pub use doctor::DoctorCommand;
 pub use firebase::FirebaseCommand;
 pub use fmt::FmtCommand;
@@ -45,6 +52,7 @@ pub use init::InitCommand;
 pub use lint::LintCommand;
 pub use match_cmd::MatchCommand;
 pub use metadata::MetadataCommand;
+pub use migrate::MigrateCommand;
 pub use pr::PrCommand;
 pub use publish::PublishCommand;
 pub use release::ReleaseCommand;
@@ -10,6 +10,7 @@ use canaveral_git::GitRepo;
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::release_pr::{prepare_release_pr, GitHubCli, PullRequestHost, ReleasePrOptions};
 
 /// PR validation and preview
 #[derive(Debug, Args)]
@@ -27,6 +28,8 @@ pub enum PrAction {
     Preview(PrPreviewCommand),
     /// Check for version conflicts with base branch
     CheckConflicts(PrCheckConflictsCommand),
+    /// Update the release branch with the pending version and changelog
+    PrepareRelease(PrPrepareReleaseCommand),
 }
 
 /// Validate PR requirements
@@ -61,18 +64,36 @@ pub struct PrCheckConflictsCommand {
     pub base: String,
 }
 
+/// Prepare the release PR branch
+#[derive(Debug, Args)]
+pub struct PrPrepareReleaseCommand {
+    /// Release branch (default: `pr.release_branch` from the config)
+    #[arg(long)]
+    pub branch: Option<String>,
+
+    /// Force-push the release branch to the configured remote
+    #[arg(long)]
+    pub push: bool,
+
+    /// Open or update the pull request with the GitHub CLI (implies --push)
+    #[arg(long)]
+    pub open_pr: bool,
+}
+
 impl PrCommand {
     pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
         let action_name = match &self.action {
             PrAction::Validate(_) => "validate",
             PrAction::Preview(_) => "preview",
             PrAction::CheckConflicts(_) => "check-conflicts",
+            PrAction::PrepareRelease(_) => "prepare-release",
         };
         info!(action = action_name, "executing pr command");
         match &self.action {
             PrAction::Validate(cmd) => cmd.execute(cli),
             PrAction::Preview(cmd) => cmd.execute(cli),
             PrAction::CheckConflicts(cmd) => cmd.execute(cli),
+            PrAction::PrepareRelease(cmd) => cmd.execute(cli),
         }
     }
 }
@@ -130,7 +151,8 @@ impl PrValidateCommand {
                 }
                 "version-conflict" => {
                     // Check for version tag conflicts
-                    let latest_tag = repo.find_latest_tag(None)?;
+                    let latest_tag =
+                        repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
                     if latest_tag.is_some() {
                         passed.push("version-conflict: no version conflicts detected".to_string());
                     } else {
@@ -196,7 +218,8 @@ impl PrPreviewCommand {
         let (config, _) = load_config_or_default(&cwd);
         let repo = GitRepo::discover(&cwd)?;
 
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag =
+            repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
         let current_version = latest_tag
             .as_ref()
             .and_then(|t| t.version.clone())
@@ -274,7 +297,7 @@ impl PrCheckConflictsCommand {
     fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
         let ui = Ui::new(cli);
         let cwd = std::env::current_dir()?;
-        let (_config, _) = load_config_or_default(&cwd);
+        let (config, _) = load_config_or_default(&cwd);
         let repo = GitRepo::discover(&cwd)?;
 
         ui.info(&format!(
@@ -284,7 +307,8 @@ impl PrCheckConflictsCommand {
 
         // Check if current branch has tag conflicts
         let current_branch = repo.current_branch()?;
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag =
+            repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
 
         if ui.is_json() {
             let result = serde_json::json!({
@@ -308,3 +332,65 @@ impl PrCheckConflictsCommand {
         Ok(())
     }
 }
+
+impl PrPrepareReleaseCommand {
+    fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+        let ui = Ui::new(cli);
+        let cwd = std::env::current_dir()?;
+        let (config, _) = load_config_or_default(&cwd);
+
+        let mut options = ReleasePrOptions::new();
+        if let Some(branch) = &self.branch {
+            options = options.with_branch(branch);
+        }
+        if self.push || self.open_pr {
+            options = options.with_push(&config.git.remote);
+        }
+
+        let Some(release) = prepare_release_pr(&cwd, &config, &options)? else {
+            if ui.is_json() {
+                ui.json(&serde_json::json!({ "pending": false }))?;
+            } else {
+                ui.info("No pending release, nothing to prepare.");
+            }
+            return Ok(());
+        };
+
+        let url = if self.open_pr {
+            Some(GitHubCli::new(&cwd).upsert_pull_request(&release)?)
+        } else {
+            None
+        };
+
+        if ui.is_json() {
+            let mut value = serde_json::to_value(&release)?;
+            value["pending"] = true.into();
+            value["url"] = serde_json::json!(url);
+            ui.json(&value)?;
+            return Ok(());
+        }
+
+        ui.key_value("Version", &ui.fmt_version(&release.version));
+        ui.key_value("Branch", &style(&release.branch).cyan().to_string());
+        for file in &release.files {
+            ui.hint(file);
+        }
+        if release.updated {
+            ui.success(&format!(
+                "Updated {} for {}",
+                style(&release.branch).cyan(),
+                ui.fmt_version(&release.version)
+            ));
+        } else {
+            ui.success(&format!(
+                "{} is already up to date",
+                style(&release.branch).cyan()
+            ));
+        }
+        if let Some(url) = url {
+            ui.success(&format!("Release PR: {}", url));
+        }
+
+        Ok(())
+    }
+}
@@ -2,12 +2,16 @@
 //!
 //! Unified publishing interface for app stores and package registries.
 
+use anyhow::Context;
 use clap::{Args, Subcommand};
 use console::style;
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::time::Duration;
 use tracing::info;
 
+use canaveral_core::config::load_config_or_default;
+use canaveral_core::summary::{ReleaseSummary, StoreUploadSummary};
 use canaveral_stores::{
     apple::AppStoreConnect,
     google_play::GooglePlayStore,
@@ -16,13 +20,43 @@ use canaveral_stores::{
         crates_io::CratesIoRegistry,
         npm::{NpmRegistry, TagSupport},
     },
-    AppleStoreConfig, CratesIoConfig, GooglePlayConfig, MicrosoftStoreConfig, NpmConfig,
-    StoreAdapter, UploadOptions,
+    release_notes::{apply_default_notes, normalize_locale, DEFAULT_LOCALE},
+    AppleStoreConfig, CratesIoConfig, GooglePlayConfig, HttpClientConfig, MicrosoftStoreConfig,
+    NpmConfig, StoreAdapter, TokenTiming, UploadOptions, UploadResult,
 };
 
+use crate::cli::cancel::ctrl_c_token;
 use crate::cli::output::Ui;
 use crate::cli::Cli;
 
+/// HTTP client settings trusting the CA bundle and presenting the client
+/// certificate from the `tls` config section
+fn http_client_config() -> anyhow::Result<HttpClientConfig> {
+    let (config, _) = load_config_or_default(&std::env::current_dir()?);
+    config.tls.validate()?;
+    let mut http = HttpClientConfig::new();
+    if let Some(ca_bundle) = config.tls.ca_bundle {
+        http = http.with_ca_bundle(ca_bundle);
+    }
+    if let (Some(cert), Some(key)) = (config.tls.client_cert, config.tls.client_key) {
+        http = http.with_client_identity(cert, key);
+    }
+    Ok(http)
+}
+
+/// Token refresh and clock skew settings from `stores` in the config
+fn token_timing() -> anyhow::Result<TokenTiming> {
+    let (config, _) = load_config_or_default(&std::env::current_dir()?);
+    let mut timing = TokenTiming::new();
+    if let Some(secs) = config.stores.token_refresh_buffer_secs {
+        timing = timing.with_refresh_buffer(Duration::from_secs(secs));
+    }
+    if let Some(secs) = config.stores.clock_skew_secs {
+        timing = timing.with_clock_skew(Duration::from_secs(secs));
+    }
+    Ok(timing)
+}
+
 /// Publish to app stores or package registries
 #[derive(Debug, Args)]
 pub struct PublishCommand {
@@ -136,6 +170,9 @@ pub struct ApplePublishCommand {
     #[arg(long)]
     pub submit_for_review: bool,
 
+    #[command(flatten)]
+    pub summary: SummaryArgs,
+
     /// Dry run - validate but don't upload
     #[arg(long)]
     pub dry_run: bool,
@@ -156,7 +193,7 @@ pub struct GooglePlayPublishCommand {
     #[arg(long, required = true)]
     pub package_name: String,
 
-    /// Path to service account JSON key
+    /// Path to service account JSON key or workload identity credential file
     #[arg(long, env = "GOOGLE_PLAY_SERVICE_ACCOUNT")]
     pub service_account: PathBuf,
 
@@ -164,13 +201,31 @@ pub struct GooglePlayPublishCommand {
     #[arg(long, default_value = "internal")]
     pub track: String,
 
+    /// Path to aapt2 (defaults to PATH, then $ANDROID_HOME/build-tools)
+    #[arg(long, env = "AAPT2")]
+    pub aapt2: Option<PathBuf>,
+
+    /// Additional OAuth scope to request (repeatable)
+    #[arg(long)]
+    pub scope: Vec<String>,
+
+    /// JWT audience for service account keys (defaults to the key's token_uri)
+    #[arg(long)]
+    pub jwt_audience: Option<String>,
+
+    /// Metadata directory; locales without release notes use its changelogs
+    #[arg(long)]
+    pub metadata_path: Option<PathBuf>,
+
     /// Staged rollout percentage (0.0-1.0)
     #[arg(long)]
     pub rollout: Option<f64>,
 
-    /// Release notes (format: "en-US:notes,de-DE:notes")
-    #[arg(long)]
-    pub release_notes: Option<String>,
+    #[command(flatten)]
+    pub notes: ReleaseNotesArgs,
+
+    #[command(flatten)]
+    pub summary: SummaryArgs,
 
     /// Dry run - validate but don't upload
     #[arg(long)]
@@ -208,9 +263,11 @@ pub struct MicrosoftPublishCommand {
     #[arg(long)]
     pub flight: Option<String>,
 
-    /// Release notes (format: "en-US:notes,de-DE:notes")
-    #[arg(long)]
-    pub release_notes: Option<String>,
+    #[command(flatten)]
+    pub notes: ReleaseNotesArgs,
+
+    #[command(flatten)]
+    pub summary: SummaryArgs,
 
     /// Dry run - validate but don't upload
     #[arg(long)]
@@ -221,6 +278,100 @@ pub struct MicrosoftPublishCommand {
     pub verbose: bool,
 }
 
+/// Release notes options shared by store uploads
+#[derive(Debug, Args)]
+pub struct ReleaseNotesArgs {
+    /// Release notes (format: "en-US:notes,de-DE:notes")
+    #[arg(long)]
+    pub release_notes: Option<String>,
+
+    /// Read release notes from a file ("-" for stdin)
+    #[arg(long)]
+    pub release_notes_file: Option<PathBuf>,
+
+    /// Locales the notes from --release-notes-file apply to (default: en-US)
+    #[arg(long, value_delimiter = ',')]
+    pub release_notes_locales: Vec<String>,
+}
+
+impl ReleaseNotesArgs {
+    /// Per-locale notes; `--release-notes` wins over the file
+    fn load(&self) -> anyhow::Result<HashMap<String, String>> {
+        let mut notes: HashMap<String, String> = self
+            .release_notes
+            .as_deref()
+            .map(|notes| {
+                notes
+                    .split(',')
+                    .filter_map(|pair| {
+                        let (lang, text) = pair.split_once(':')?;
+                        Some((normalize_locale(lang), text.to_string()))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(path) = &self.release_notes_file {
+            let text = if path.as_os_str() == "-" {
+                std::io::read_to_string(std::io::stdin())?
+            } else {
+                std::fs::read_to_string(path).with_context(|| {
+                    format!("failed to read release notes from {}", path.display())
+                })?
+            };
+            let locales = if self.release_notes_locales.is_empty() {
+                vec![DEFAULT_LOCALE.to_string()]
+            } else {
+                self.release_notes_locales.clone()
+            };
+            apply_default_notes(&mut notes, &text, &locales);
+        }
+
+        Ok(notes)
+    }
+}
+
+/// Release summary options shared by store uploads
+#[derive(Debug, Args)]
+pub struct SummaryArgs {
+    /// Record the upload in the release summary at this path
+    /// (written by `canaveral release --summary`)
+    #[arg(long)]
+    pub summary: Option<PathBuf>,
+}
+
+impl SummaryArgs {
+    /// Append `result` to the release summary, if one was given
+    ///
+    /// Dry runs upload nothing, so they aren't recorded.
+    fn record(&self, store: &str, result: &UploadResult, dry_run: bool) -> anyhow::Result<()> {
+        let Some(path) = self.summary.as_deref().filter(|_| !dry_run) else {
+            return Ok(());
+        };
+        let summary = ReleaseSummary::read_from(path)
+            .with_context(|| format!("failed to read release summary {}", path.display()))?;
+        summary
+            .with_store_upload(store_upload_summary(store, result))
+            .write_to(path)?;
+        Ok(())
+    }
+}
+
+/// Plain summary record for a store upload result
+fn store_upload_summary(store: &str, result: &UploadResult) -> StoreUploadSummary {
+    StoreUploadSummary {
+        store: store.to_string(),
+        success: result.success,
+        build_id: result.build_id.clone(),
+        console_url: result.console_url.clone(),
+        status: serde_json::to_value(result.status)
+            .ok()
+            .and_then(|status| status.as_str().map(str::to_string))
+            .unwrap_or_else(|| result.status.to_string()),
+        warnings: result.warnings.clone(),
+    }
+}
+
 impl PublishCommand {
     pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
         let target_name = match &self.target {
@@ -250,7 +401,7 @@ impl NpmPublishCommand {
             token: self.token.clone(),
         };
 
-        let registry = NpmRegistry::new(config)?;
+        let registry = NpmRegistry::new(config)?.with_http_client(http_client_config()?.build()?);
 
         ui.info(&format!(
             "{} {} to NPM{}",
@@ -317,7 +468,8 @@ impl CratesPublishCommand {
             token: self.token.clone(),
         };
 
-        let registry = CratesIoRegistry::new(config)?;
+        let registry =
+            CratesIoRegistry::new(config)?.with_http_client(http_client_config()?.build()?);
 
         ui.info(&format!(
             "{} {} to Crates.io",
@@ -452,7 +604,9 @@ impl ApplePublishCommand {
             primary_locale: None,
         };
 
-        let store = AppStoreConnect::new(config)?;
+        let store = AppStoreConnect::new(config)?
+            .with_http_client(http_client_config()?.build()?)
+            .with_token_timing(token_timing()?);
 
         // Step 1: Validate the artifact
         if ui.is_text() {
@@ -481,10 +635,13 @@ impl ApplePublishCommand {
         let options = UploadOptions {
             dry_run: self.dry_run,
             verbose: self.verbose || cli.verbose,
+            cancellation: ctrl_c_token(),
             ..Default::default()
         };
 
         let result = store.upload(&artifact, &options).await?;
+        self.summary
+            .record("app-store-connect", &result, self.dry_run)?;
 
         if ui.is_json() {
             ui.json(&result)?;
@@ -528,9 +685,14 @@ impl GooglePlayPublishCommand {
             package_name: self.package_name.clone(),
             service_account_key: self.service_account.clone(),
             default_track: Some(self.track.clone()),
+            aapt2_path: self.aapt2.clone(),
+            scopes: self.scope.clone(),
+            jwt_audience: self.jwt_audience.clone(),
         };
 
-        let store = GooglePlayStore::new(config)?;
+        let store = GooglePlayStore::new(config)?
+            .with_http_client(http_client_config()?.build()?)
+            .with_token_timing(token_timing()?);
 
         ui.info(&format!(
             "{} {} to Google Play ({})",
@@ -543,34 +705,21 @@ impl GooglePlayPublishCommand {
             self.track
         ));
 
-        // Parse release notes
-        let release_notes = self
-            .release_notes
-            .as_ref()
-            .map(|notes| {
-                notes
-                    .split(',')
-                    .filter_map(|pair| {
-                        let mut parts = pair.splitn(2, ':');
-                        match (parts.next(), parts.next()) {
-                            (Some(lang), Some(text)) => Some((lang.to_string(), text.to_string())),
-                            _ => None,
-                        }
-                    })
-                    .collect::<HashMap<String, String>>()
-            })
-            .unwrap_or_default();
+        let release_notes = self.notes.load()?;
 
         let options = UploadOptions {
             track: Some(self.track.clone()),
             rollout_percentage: self.rollout,
             release_notes,
+            metadata_path: self.metadata_path.clone(),
             dry_run: self.dry_run,
             verbose: self.verbose || cli.verbose,
+            cancellation: ctrl_c_token(),
             ..Default::default()
         };
 
         let result = store.upload(&self.artifact, &options).await?;
+        self.summary.record("google-play", &result, self.dry_run)?;
 
         if ui.is_json() {
             ui.json(&result)?;
@@ -607,7 +756,9 @@ impl MicrosoftPublishCommand {
             default_flight: self.flight.clone(),
         };
 
-        let store = MicrosoftStore::new(config)?;
+        let store = MicrosoftStore::new(config)?
+            .with_http_client(http_client_config()?.build()?)
+            .with_token_timing(token_timing()?);
 
         ui.info(&format!(
             "{} {} to Microsoft Store",
@@ -622,33 +773,20 @@ impl MicrosoftPublishCommand {
             ui.key_value("Flight", &style(flight).dim().to_string());
         }
 
-        // Parse release notes
-        let release_notes = self
-            .release_notes
-            .as_ref()
-            .map(|notes| {
-                notes
-                    .split(',')
-                    .filter_map(|pair| {
-                        let mut parts = pair.splitn(2, ':');
-                        match (parts.next(), parts.next()) {
-                            (Some(lang), Some(text)) => Some((lang.to_string(), text.to_string())),
-                            _ => None,
-                        }
-                    })
-                    .collect::<HashMap<String, String>>()
-            })
-            .unwrap_or_default();
+        let release_notes = self.notes.load()?;
 
         let options = UploadOptions {
             track: self.flight.clone(),
             release_notes,
             dry_run: self.dry_run,
             verbose: self.verbose || cli.verbose,
+            cancellation: ctrl_c_token(),
             ..Default::default()
         };
 
         let result = store.upload(&self.artifact, &options).await?;
+        self.summary
+            .record("microsoft-store", &result, self.dry_run)?;
 
         if ui.is_json() {
             ui.json(&result)?;
@@ -1,24 +1,42 @@
 //! Release command
 
-use clap::Args;
+use clap::{ArgGroup, Args, ValueEnum};
 use console::style;
-use std::process::Command;
+use std::path::{Path, PathBuf};
 use tracing::info;
 
-use canaveral_adapters::AdapterRegistry;
-use canaveral_changelog::ChangelogGenerator;
-use canaveral_changelog::{CommitParser, ConventionalParser};
-use canaveral_core::config::load_config_or_default;
-use canaveral_core::types::ReleaseType;
-use canaveral_core::workflow::{format_tag, ReleaseOptions, ReleaseWorkflow};
-use canaveral_git::GitRepo;
+use canaveral_adapters::sbom::SbomFormat;
+use canaveral_adapters::{
+    check_export_ignored, check_publish_files, default_secret_patterns, latest_release,
+    AdapterRegistry, MirroredAdapter, PackageAdapter,
+};
+use canaveral_core::config::{load_config_or_default, Config, RollbackMode};
+use canaveral_core::hooks::HookStage;
+use canaveral_core::monorepo::DEFAULT_BUMP_CONCURRENCY;
+use canaveral_core::notify;
+use canaveral_core::summary::ReleaseSummary;
+use canaveral_core::types::{ReleaseCommit, ReleaseResult, ReleaseType};
+use canaveral_core::workflow::{
+    cadence_skip_reason, check_release_cadence, format_tag, preflight_release, resume_release,
+    tag_pattern, CadenceState, ExistingRelease, ReleaseOptions, ReleaseOutcome, ReleaseResumption,
+    ReleaseStep, ReleaseWorkflow, RepoState, SkipReason,
+};
+use canaveral_git::{push_tag_with_retry, GitRepo, PushOptions, ReleaseRollback, Worktree};
 use canaveral_strategies::{BumpType, SemVerStrategy, VersionStrategy};
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::exit_codes;
+use crate::notify::notifiers_from_config;
+use crate::regenerate::{regenerate_release, RegenerateOptions};
+use crate::release_pr::GitHubCli;
+use crate::version::{infer_bump, parse_commits, INITIAL_VERSION};
+
+use super::release_steps::ReleasePlan;
 
 /// Create a new release
 #[derive(Debug, Args)]
+#[command(group = ArgGroup::new("regenerate").args(["changelog_only", "notes_only"]))]
 pub struct ReleaseCommand {
     /// Release type (major, minor, patch)
     #[arg(short, long)]
@@ -48,18 +66,228 @@ pub struct ReleaseCommand {
     #[arg(short = 'y', long)]
     pub yes: bool,
 
+    /// Write, commit and tag the release in a temporary git worktree
+    #[arg(long)]
+    pub worktree: bool,
+
+    /// On a failed push, delete the local tag and reset the release commit
+    /// without asking
+    #[arg(long, conflicts_with = "no_rollback")]
+    pub rollback: bool,
+
+    /// On a failed push, keep the local tag and release commit
+    #[arg(long)]
+    pub no_rollback: bool,
+
     /// Allow release from non-release branch
     #[arg(long)]
     pub allow_branch: bool,
 
+    /// Release even if the release cadence would refuse it; with --filter,
+    /// also release matching packages that have no changes
+    #[arg(long)]
+    pub force: bool,
+
     /// Package to release (for monorepos)
     #[arg(short, long)]
     pub package: Option<String>,
+
+    /// Ignore pre-release tags when finding the previous release
+    #[arg(long)]
+    pub exclude_prereleases: bool,
+
+    /// Release every workspace package with releasable commits in one
+    /// release commit
+    #[arg(
+        long,
+        conflicts_with_all = ["package", "as_version", "changelog_only", "notes_only", "worktree", "summary", "sbom"]
+    )]
+    pub workspace: bool,
+
+    /// Release only workspace packages whose name or path matches this glob
+    /// (can be repeated; implies --workspace)
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        conflicts_with_all = ["package", "as_version", "changelog_only", "notes_only", "worktree", "summary", "sbom"]
+    )]
+    pub filter: Vec<String>,
+
+    /// Skip workspace packages whose name or path matches this glob (can be
+    /// repeated; implies --workspace)
+    #[arg(
+        long,
+        value_name = "PATTERN",
+        conflicts_with_all = ["package", "as_version", "changelog_only", "notes_only", "worktree", "summary", "sbom"]
+    )]
+    pub exclude: Vec<String>,
+
+    /// With --workspace, the maximum number of packages bumped at once
+    #[arg(long, value_name = "N", default_value_t = DEFAULT_BUMP_CONCURRENCY)]
+    pub concurrency: usize,
+
+    /// Write a JSON release summary to this path
+    #[arg(long, value_name = "PATH")]
+    pub summary: Option<PathBuf>,
+
+    /// Write a software bill of materials into this directory
+    #[arg(long, value_name = "DIR")]
+    pub sbom: Option<PathBuf>,
+
+    /// SBOM document format
+    #[arg(long, value_enum, default_value = "cyclonedx", requires = "sbom")]
+    pub sbom_format: SbomFormatArg,
+
+    /// Regenerate the changelog entry of an existing tag, without bumping,
+    /// committing or tagging
+    #[arg(long, conflicts_with = "release_type")]
+    pub changelog_only: bool,
+
+    /// Regenerate the release notes of an existing tag, without bumping,
+    /// committing or tagging
+    #[arg(long, conflicts_with = "release_type")]
+    pub notes_only: bool,
+
+    /// Tag to regenerate (default: the tag of --as-version, or the latest tag)
+    #[arg(long, value_name = "TAG", requires = "regenerate")]
+    pub tag: Option<String>,
+
+    /// Replace the GitHub Release body with the regenerated notes
+    #[arg(long, requires = "notes_only")]
+    pub update_release: bool,
+
+    /// Exit with code 9 instead of 0 when there is nothing to release
+    #[arg(long)]
+    pub exit_code_on_skip: bool,
+}
+
+/// SBOM format argument
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SbomFormatArg {
+    /// CycloneDX JSON
+    #[value(name = "cyclonedx")]
+    CycloneDx,
+    /// SPDX JSON
+    Spdx,
+}
+
+impl From<SbomFormatArg> for SbomFormat {
+    fn from(arg: SbomFormatArg) -> Self {
+        match arg {
+            SbomFormatArg::CycloneDx => SbomFormat::CycloneDx,
+            SbomFormatArg::Spdx => SbomFormat::Spdx,
+        }
+    }
 }
 
 impl ReleaseCommand {
     /// Execute the release command
-    pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+    ///
+    /// Returns the process exit code. A skipped release says why and exits
+    /// with `0`, or with [`exit_codes::RELEASE_SKIPPED`] under
+    /// `--exit-code-on-skip`.
+    pub fn execute(&self, cli: &Cli) -> anyhow::Result<i32> {
+        let ui = Ui::new(cli);
+        if self.changelog_only || self.notes_only {
+            self.regenerate(&ui)?;
+            return Ok(exit_codes::SUCCESS);
+        }
+        let outcome = if self.is_workspace_release() {
+            match self.run_workspace(&ui)? {
+                Some(reason) => ReleaseOutcome::Skipped(reason),
+                None => return Ok(exit_codes::SUCCESS),
+            }
+        } else {
+            self.run(&ui)?
+        };
+        if let ReleaseOutcome::Skipped(reason) = &outcome {
+            info!(reason = %reason, "release skipped");
+            if ui.is_json() {
+                ui.json(&outcome)?;
+            } else {
+                ui.warning(&format!("No release: {}", reason));
+                if let Some(hint) = reason.hint() {
+                    ui.hint(hint);
+                }
+            }
+        }
+        Ok(exit_codes::from_outcome(&outcome, self.exit_code_on_skip))
+    }
+
+    /// Whether every changed workspace package is released, rather than
+    /// the package in the current directory
+    fn is_workspace_release(&self) -> bool {
+        self.workspace || !self.filter.is_empty() || !self.exclude.is_empty()
+    }
+
+    /// Load the configuration with this command's overrides applied
+    pub(super) fn load_config(&self, cwd: &Path) -> (Config, Option<PathBuf>) {
+        let (mut config, config_path) = load_config_or_default(cwd);
+        if self.exclude_prereleases {
+            config.versioning.include_prereleases = false;
+        }
+        (config, config_path)
+    }
+
+    /// Regenerate the changelog or release notes of an existing tag
+    fn regenerate(&self, ui: &Ui) -> anyhow::Result<()> {
+        let cwd = std::env::current_dir()?;
+        let (config, _) = self.load_config(&cwd);
+        let tag = match (&self.tag, &self.as_version) {
+            (Some(tag), _) => tag.clone(),
+            (None, Some(version)) => format_tag(&config, version, self.package.as_deref()),
+            (None, None) => GitRepo::discover(&cwd)?
+                .find_latest_tag_filtered(
+                    Some(&tag_pattern(&config, self.package.as_deref())),
+                    config.versioning.include_prereleases,
+                )?
+                .map(|tag| tag.name)
+                .ok_or_else(|| anyhow::anyhow!("no release tag to regenerate"))?,
+        };
+        let options = if self.changelog_only {
+            RegenerateOptions::changelog_only()
+        } else {
+            RegenerateOptions::notes_only().with_release_update(self.update_release)
+        };
+        info!(tag = %tag, ?options, "regenerating release");
+
+        let result = regenerate_release(
+            &cwd,
+            &config,
+            &tag,
+            self.package.as_deref(),
+            &options,
+            &GitHubCli::new(&cwd),
+        )?;
+        if ui.is_json() {
+            return ui.json(&result);
+        }
+        if let Some(path) = &result.changelog_path {
+            ui.success(&format!(
+                "Regenerated {} in {} from {} commit(s)",
+                ui.fmt_version(&result.version),
+                ui.fmt_path(&path.display()),
+                result.commits
+            ));
+        }
+        if let Some(path) = &result.notes_path {
+            ui.success(&format!(
+                "Wrote release notes to {}",
+                ui.fmt_path(&path.display())
+            ));
+        }
+        if let Some(url) = &result.release_url {
+            ui.success(&format!("Updated release notes at {}", url));
+        } else if let (Some(notes), None) = (&result.notes, &result.notes_path) {
+            println!("{}", notes);
+        }
+        Ok(())
+    }
+
+    /// Run the release, returning what it released or why it didn't
+    ///
+    /// A failure at any step is reported to the configured notifiers.
+    fn run(&self, ui: &Ui) -> anyhow::Result<ReleaseOutcome> {
         info!(
             release_type = ?self.release_type,
             version = ?self.as_version,
@@ -70,12 +298,8 @@ impl ReleaseCommand {
             package = ?self.package,
             "executing release command"
         );
-        let ui = Ui::new(cli);
         let cwd = std::env::current_dir()?;
-        let (config, config_path) = load_config_or_default(&cwd);
-        let adapter_registry = AdapterRegistry::new();
-        let adapter = adapter_registry.detect(&cwd);
-
+        let (config, config_path) = self.load_config(&cwd);
         if config_path.is_none() {
             ui.warning(&format!(
                 "No configuration found, using defaults. Run {} to create one.",
@@ -83,37 +307,129 @@ impl ReleaseCommand {
             ));
         }
 
-        let repo = GitRepo::discover(&cwd)?;
+        // Filled in as the release works out its versions, so a failure can
+        // say what was being released
+        let mut result = ReleaseResult {
+            tag: String::new(),
+            ..ReleaseResult::new(
+                config.name.clone().unwrap_or_else(|| "package".to_string()),
+                "",
+            )
+        };
+        self.release(&cwd, &config, &mut result, ui).map_err(|e| {
+            self.notify_failure(&config, &result, &e);
+            e
+        })
+    }
 
-        // Check repository state
-        if config.git.require_clean && !repo.is_clean()? && !self.dry_run {
-            anyhow::bail!("Working directory has uncommitted changes. Commit or stash them first.");
+    /// Tell the configured notifiers the release failed with `error`
+    fn notify_failure(&self, config: &Config, result: &ReleaseResult, error: &anyhow::Error) {
+        let workflow = ReleaseWorkflow::new(config, self.release_options(None))
+            .with_notifiers(notifiers_from_config(&config.notifications));
+        let summary = ReleaseSummary::from_result(result).with_dry_run(workflow.is_dry_run());
+        workflow.notify(
+            &summary,
+            &notify::ReleaseOutcome::Failure(format!("{:#}", error)),
+        );
+    }
+
+    /// Workflow options for this invocation, releasing `version`
+    fn release_options(&self, version: Option<String>) -> ReleaseOptions {
+        ReleaseOptions {
+            release_type: self.release_type,
+            version,
+            prerelease: None,
+            dry_run: self.dry_run,
+            skip_changelog: self.no_changelog,
+            skip_publish: self.no_publish,
+            skip_git: self.no_git,
+            allow_branch: self.allow_branch,
+            package: self.package.clone(),
         }
+    }
 
-        // Check branch
-        let current_branch = repo.current_branch()?;
-        if let Some(branch) = &current_branch {
-            if branch != &config.git.branch && !self.allow_branch && !self.dry_run {
+    /// Release the package in `cwd`, updating `result` as the versions and
+    /// tag are worked out
+    fn release(
+        &self,
+        cwd: &Path,
+        config: &Config,
+        result: &mut ReleaseResult,
+        ui: &Ui,
+    ) -> anyhow::Result<ReleaseOutcome> {
+        // A group member's version moves with the rest of its group
+        if let Some(package) = &self.package {
+            if let Some(group) = config.versioning.fixed_group(package) {
                 anyhow::bail!(
-                    "Not on release branch '{}'. Current branch: '{}'. Use --allow-branch to override.",
-                    config.git.branch,
-                    branch
+                    "{} shares its version with the '{}' group; release the group with --workspace",
+                    package,
+                    group.name
                 );
             }
         }
+        let adapter_registry = AdapterRegistry::from_config(config)?;
+        let mirrors = config
+            .package_config(self.package.as_deref())
+            .map(|package| package.version_mirrors.as_slice())
+            .unwrap_or_default();
+        let adapter = adapter_registry
+            .detect(cwd)
+            .map(|adapter| MirroredAdapter::from_config(adapter, mirrors))
+            .transpose()?;
+
+        let repo = self.open_repo(cwd, config, ui)?;
 
         // Find current version
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag =
+            repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
         let tag_version = latest_tag
             .as_ref()
             .and_then(|t| t.version.clone())
-            .unwrap_or_else(|| "0.0.0".to_string());
-        let current_version = adapter
-            .as_ref()
-            .and_then(|a| a.get_version(&cwd).ok())
-            .unwrap_or(tag_version);
+            .unwrap_or_else(|| INITIAL_VERSION.to_string());
+        let current_version = match &adapter {
+            Some(adapter) => match adapter.get_version(cwd) {
+                Ok(version) if is_placeholder_version(&version) => self
+                    .published_version(adapter.as_ref(), cwd, ui)
+                    .unwrap_or(tag_version),
+                Ok(version) => version,
+                Err(_) => tag_version,
+            },
+            None => tag_version,
+        };
+
+        result.previous_version = Some(current_version.clone());
+
+        // Refuse releases that come too soon after the last one
+        let cadence = &config.cadence;
+        if cadence.min_commits.is_some() || cadence.min_interval.is_some() {
+            let commits = if let Some(tag) = &latest_tag {
+                repo.commits_since_tag(&tag.name)?
+            } else {
+                repo.all_commits()?
+            };
+            let state = CadenceState {
+                last_release: latest_tag.as_ref().and_then(|t| t.timestamp),
+                releasable_commits: infer_bump(&commits, config).releasable_commits,
+            };
+            // Too soon is a skip, not a failure; a dry run only warns
+            if !self.force && !self.dry_run {
+                if let Some(reason) = cadence_skip_reason(config, &state, chrono::Utc::now())? {
+                    return Ok(ReleaseOutcome::Skipped(reason));
+                }
+            }
+            match check_release_cadence(config, &state, chrono::Utc::now(), self.force) {
+                Ok(result) => {
+                    for warning in &result.warnings {
+                        ui.warning(warning);
+                    }
+                }
+                Err(e) if self.dry_run => ui.warning(&e.to_string()),
+                Err(e) => return Err(e.into()),
+            }
+        }
 
-        // Determine next version
+        // Determine next version, keeping the commits that decided the bump
+        let mut parsed_commits = Vec::new();
         let next_version = if let Some(v) = &self.as_version {
             v.clone()
         } else {
@@ -124,22 +440,10 @@ impl ReleaseCommand {
                 repo.all_commits()?
             };
 
-            let parser = ConventionalParser::new();
-            let mut bump_type = BumpType::None;
-
-            for commit in &commits {
-                if let Some(parsed) = parser.parse(commit) {
-                    let commit_bump = if parsed.breaking {
-                        BumpType::Major
-                    } else if parsed.is_minor() {
-                        BumpType::Minor
-                    } else if parsed.is_patch() {
-                        BumpType::Patch
-                    } else {
-                        BumpType::None
-                    };
-                    bump_type = bump_type.max(commit_bump);
-                }
+            let inference = infer_bump(&commits, config);
+            let mut bump_type = inference.bump;
+            if self.release_type.is_none() {
+                parsed_commits = parse_commits(&commits, config);
             }
 
             // Override with explicit release type
@@ -153,18 +457,77 @@ impl ReleaseCommand {
                 };
             }
 
-            if bump_type == BumpType::None {
-                ui.warning("No version bump required - no relevant commits found.");
-                return Ok(());
-            }
+            // A Release-As footer wins unless a release type was requested
+            let forced = match self.release_type {
+                Some(_) => None,
+                None => inference.forced_version(&current_version)?,
+            };
 
-            let strategy = SemVerStrategy::new();
-            let current = strategy.parse(&current_version)?;
-            let next = strategy.bump(&current, bump_type)?;
-            strategy.format(&next)
+            if let Some(forced) = forced {
+                info!(version = %forced.version, reason = %forced.reason, "version forced by commit footer");
+                forced.version.clone()
+            } else {
+                if bump_type == BumpType::None {
+                    // A re-run after a partly failed release has nothing to
+                    // bump but may still have steps to finish
+                    let unfinished = match latest_tag.as_ref().and_then(|t| t.version.clone()) {
+                        Some(version) => {
+                            let tag = format_tag(config, &version, self.package.as_deref());
+                            let existing = self.existing_release(
+                                &repo,
+                                adapter.as_deref(),
+                                cwd,
+                                &tag,
+                                &version,
+                            )?;
+                            matches!(resume_release(&existing), ReleaseResumption::Resume(_))
+                                .then_some(version)
+                        }
+                        None => None,
+                    };
+                    match unfinished {
+                        Some(version) => version,
+                        None => {
+                            let since_tag = latest_tag.as_ref().map(|t| t.name.as_str());
+                            return Ok(ReleaseOutcome::Skipped(SkipReason::no_bump(
+                                since_tag,
+                                commits.len(),
+                            )));
+                        }
+                    }
+                } else {
+                    let strategy = SemVerStrategy::new();
+                    let current = strategy.parse(&current_version)?;
+                    let next = strategy.bump(&current, bump_type)?;
+                    strategy.format(&next)
+                }
+            }
         };
 
-        let tag = format_tag(&config, &next_version, self.package.as_deref());
+        let tag = format_tag(config, &next_version, self.package.as_deref());
+        result.new_version = next_version.clone();
+        result.tag = tag.clone();
+
+        // Re-running a release only finishes what an earlier run left undone
+        let existing =
+            self.existing_release(&repo, adapter.as_deref(), cwd, &tag, &next_version)?;
+        let resumption = resume_release(&existing);
+        if let Some(reason) = SkipReason::from_resumption(&resumption, &next_version, &tag) {
+            return Ok(ReleaseOutcome::Skipped(reason));
+        }
+        match &resumption {
+            ReleaseResumption::Resume(steps) => {
+                let steps: Vec<String> = steps.iter().map(ToString::to_string).collect();
+                ui.info(&format!(
+                    "Version {} is partly released, resuming with: {}",
+                    ui.fmt_version(&next_version),
+                    steps.join(", ")
+                ));
+            }
+            ReleaseResumption::Fresh | ReleaseResumption::AlreadyReleased => {}
+        }
+        // The version bump, changelog and commit belong to the tagged release
+        let write_release = resumption.runs(&ReleaseStep::Tag);
 
         // Show release preview
         ui.header("Release Preview");
@@ -172,6 +535,19 @@ impl ReleaseCommand {
         ui.key_value("Current version", &ui.fmt_path(&current_version));
         ui.key_value("Next version", &ui.fmt_version(&next_version));
         ui.key_value("Tag", &ui.fmt_tag(&tag));
+        // Breaking changes before 1.0 only count as minor releases
+        let pre_1_0 = SemVerStrategy::new()
+            .parse(&current_version)
+            .is_ok_and(|version| version.major == 0);
+        if let Some(release_type) = ReleaseType::from_parsed_commits(&parsed_commits, pre_1_0) {
+            ui.key_value("Release type", release_type.as_str());
+            let reasons: Vec<String> = release_type
+                .triggered_by(&parsed_commits, pre_1_0)
+                .map(ReleaseCommit::summary)
+                .collect();
+            let reasons: Vec<&str> = reasons.iter().map(String::as_str).collect();
+            ui.list(&reasons);
+        }
         ui.blank();
 
         if self.dry_run {
@@ -183,186 +559,356 @@ impl ReleaseCommand {
         if !self.yes && !self.dry_run {
             let confirmed = ui.confirm("Proceed with release?", true)?;
             if !confirmed {
-                ui.warning("Aborted.");
-                return Ok(());
+                return Ok(ReleaseOutcome::Skipped(SkipReason::Declined));
             }
         }
 
         // Execute release workflow
-        let options = ReleaseOptions {
-            release_type: self.release_type,
-            version: Some(next_version.clone()),
-            prerelease: None,
-            dry_run: self.dry_run,
-            skip_changelog: self.no_changelog,
-            skip_publish: self.no_publish,
-            skip_git: self.no_git,
-            allow_branch: self.allow_branch,
-            package: self.package.clone(),
+        let mut workflow =
+            ReleaseWorkflow::new(config, self.release_options(Some(next_version.clone())))
+                .with_notifiers(notifiers_from_config(&config.notifications));
+        let notes_path = config
+            .release_notes
+            .file
+            .as_ref()
+            .map(|file| cwd.join(file));
+        if let Some(path) = &notes_path {
+            workflow = workflow.with_release_notes_path(path);
+        }
+
+        // Files are written, committed and tagged in a clean worktree of HEAD
+        // when isolation is on; it's removed again on drop, even on failure
+        let worktree = if (self.worktree || config.git.release_in_worktree)
+            && !self.no_git
+            && !self.dry_run
+            && write_release
+        {
+            // The worktree's commit never moves the local branch, so without
+            // pushing it the release would only be reachable from its tag
+            if !config.git.push_commits {
+                anyhow::bail!(
+                    "Releasing in a worktree needs git.push_commits; the release commit \
+                     would otherwise never reach a branch"
+                );
+            }
+            let worktree = repo.with_temp_worktree("HEAD")?;
+            ui.info(&format!(
+                "Releasing from worktree at {}",
+                ui.fmt_path(&worktree.path().display())
+            ));
+            Some(worktree)
+        } else {
+            None
+        };
+        let release_dir = match &worktree {
+            Some(worktree) => worktree
+                .path()
+                .join(cwd.strip_prefix(repo.path()).unwrap_or(Path::new(""))),
+            None => cwd.to_path_buf(),
         };
 
-        let workflow = ReleaseWorkflow::new(&config, options);
-        let mut result = workflow.execute()?;
+        *result = workflow.execute()?.with_commits(&parsed_commits, pre_1_0);
         result.previous_version = Some(current_version.clone());
         result.new_version = next_version.clone();
         result.tag = tag.clone();
 
-        // Update package version via detected adapter
-        if let Some(adapter) = &adapter {
-            if !self.dry_run {
-                adapter.set_version(&cwd, &next_version)?;
+        let plan = ReleasePlan {
+            config,
+            cwd,
+            repo: &repo,
+            worktree: worktree.as_ref(),
+            release_dir,
+            adapter: adapter.as_deref(),
+            latest_tag: latest_tag.as_ref(),
+            next_version: next_version.clone(),
+            tag,
+            resumption,
+            notes_path,
+        };
+        let summary = self.run_steps(&plan, &workflow, result, ui)?;
+        // Post-release hooks also get the files the release wrote
+        let workflow =
+            workflow.with_artifacts(summary.sboms.iter().map(|s| s.path.clone()).collect());
+        self.run_hooks(
+            &workflow,
+            HookStage::PostRelease,
+            result,
+            &plan.release_dir,
+            ui,
+        )?;
+        if let Some(worktree) = worktree {
+            worktree.remove()?;
+        }
+
+        if let Some(path) = &self.summary {
+            summary.write_to(path)?;
+            ui.success(&format!(
+                "Wrote release summary to {}",
+                ui.fmt_path(&path.display())
+            ));
+        }
+        if workflow.notify(&summary, &notify::ReleaseOutcome::Success) > 0 {
+            ui.success("Sent release notifications");
+        }
+
+        // Final output
+        if ui.is_json() {
+            ui.json(result)?;
+        } else {
+            ui.blank();
+            if self.dry_run {
                 ui.success(&format!(
-                    "Updated {} version to {}",
-                    style(adapter.name()).cyan(),
+                    "Dry run complete. Version {} would be released.",
                     ui.fmt_version(&next_version)
                 ));
             } else {
-                ui.info(&format!(
-                    "Would update {} version to {}",
-                    style(adapter.name()).cyan(),
+                ui.success(&format!(
+                    "Released version {}",
                     ui.fmt_version(&next_version)
                 ));
             }
         }
 
-        // Generate changelog if not skipped
-        if !self.no_changelog && config.changelog.enabled {
-            let commits = if let Some(tag_info) = &latest_tag {
-                repo.commits_since_tag(&tag_info.name)?
-            } else {
-                repo.all_commits()?
-            };
+        Ok(ReleaseOutcome::Released(Box::new(summary)))
+    }
 
-            let generator = ChangelogGenerator::new(config.changelog.clone());
-            let changelog = generator.generate_formatted(&next_version, &commits);
+    /// Open the repository at `cwd` and check its branch and working tree
+    ///
+    /// A dry run only reports problems.
+    pub(super) fn open_repo(
+        &self,
+        cwd: &Path,
+        config: &Config,
+        ui: &Ui,
+    ) -> anyhow::Result<GitRepo> {
+        let mut repo = GitRepo::discover(cwd)?;
+        if config.git.auto_unshallow && repo.is_shallow() {
+            repo.unshallow(&config.git.remote)?;
+        }
 
-            if !self.dry_run {
-                let changelog_path = cwd.join(&config.changelog.file);
-                if changelog_path.exists() {
-                    let existing = std::fs::read_to_string(&changelog_path)?;
-                    let combined = format!("{}\n{}", changelog, existing);
-                    std::fs::write(&changelog_path, combined)?;
-                } else {
-                    std::fs::write(&changelog_path, &changelog)?;
+        let state = RepoState {
+            branch: repo.current_branch()?,
+            changes: if config.git.require_clean {
+                repo.status_summary()?
+            } else {
+                Vec::new()
+            },
+        };
+        match preflight_release(config, &state, self.allow_branch) {
+            Ok(preflight) => {
+                for warning in &preflight.warnings {
+                    ui.warning(warning);
                 }
-
-                ui.success(&format!(
-                    "Updated changelog at {}",
-                    ui.fmt_path(&config.changelog.file.display())
-                ));
             }
+            Err(e) if self.dry_run => ui.warning(&e.to_string()),
+            Err(e) => return Err(e.into()),
         }
+        Ok(repo)
+    }
 
-        // Publish package using detected adapter
-        let mut published = false;
-        if !self.no_publish {
-            if let Some(adapter) = &adapter {
-                let validation = adapter.validate_publishable(&cwd)?;
-                if !validation.passed {
-                    // If this is a workspace root, skip publish instead of failing
-                    let is_workspace_error = validation
-                        .errors
-                        .iter()
-                        .any(|e| e.contains("No [package] section"));
-                    if is_workspace_error {
-                        ui.warning("Workspace root detected — skipping publish (use --package to publish individual crates)");
-                    } else {
-                        anyhow::bail!(
-                            "Publish validation failed:\n{}",
-                            validation.errors.join("\n")
-                        );
-                    }
-                } else {
-                    for warning in &validation.warnings {
-                        ui.warning(warning);
-                    }
+    /// Run the hooks configured for `stage` in `dir`
+    pub(super) fn run_hooks(
+        &self,
+        workflow: &ReleaseWorkflow,
+        stage: HookStage,
+        result: &ReleaseResult,
+        dir: &Path,
+        ui: &Ui,
+    ) -> anyhow::Result<()> {
+        self.run_hooks_with_artifacts(workflow, stage, result, dir, &[], ui)
+    }
 
-                    if !self.dry_run {
-                        adapter.publish(&cwd, false)?;
-                        published = true;
-                        ui.success(&format!(
-                            "Published package via {}",
-                            style(adapter.name()).cyan()
-                        ));
-                    } else {
-                        ui.info(&format!(
-                            "Would publish package via {}",
-                            style(adapter.name()).cyan()
-                        ));
-                    }
-                }
-            } else {
-                ui.warning(&format!(
-                    "No publish adapter detected in {}, skipping publish.",
-                    style(cwd.display()).dim()
-                ));
-            }
+    /// Run the hooks of `stage`, with `artifacts` added to the workflow's
+    pub(super) fn run_hooks_with_artifacts(
+        &self,
+        workflow: &ReleaseWorkflow,
+        stage: HookStage,
+        result: &ReleaseResult,
+        dir: &Path,
+        artifacts: &[PathBuf],
+        ui: &Ui,
+    ) -> anyhow::Result<()> {
+        let count = workflow.run_hooks_with_artifacts(stage, result, dir, artifacts)?;
+        if count > 0 {
+            ui.success(&format!("Ran {} {} hook(s)", count, stage.as_str()));
         }
-        result.published = published;
-
-        // Git operations
-        if !self.no_git && !self.dry_run {
-            if !repo.is_clean()? {
-                let commit_message = config
-                    .git
-                    .commit_message
-                    .replace("{version}", &next_version);
-                let add_output = Command::new("git")
-                    .args(["add", "-A"])
-                    .current_dir(&cwd)
-                    .output()?;
-                if !add_output.status.success() {
-                    anyhow::bail!(
-                        "Failed to stage release changes: {}",
-                        String::from_utf8_lossy(&add_output.stderr)
-                    );
-                }
+        Ok(())
+    }
 
-                let commit_output = Command::new("git")
-                    .args(["commit", "-m", &commit_message])
-                    .current_dir(&cwd)
-                    .output()?;
-                if !commit_output.status.success() {
-                    anyhow::bail!(
-                        "Failed to commit release changes: {}",
-                        String::from_utf8_lossy(&commit_output.stderr)
-                    );
-                }
+    /// Push the release commit and tag made in `worktree`
+    pub(super) fn push_release(
+        &self,
+        repo: &GitRepo,
+        worktree: &Worktree,
+        config: &Config,
+        tag: &str,
+        ui: &Ui,
+    ) -> anyhow::Result<()> {
+        // The worktree is detached, so the release only reaches the branch
+        // by pushing it
+        let options = PushOptions::new();
+        if config.git.push_commits {
+            let branch = repo
+                .current_branch()?
+                .unwrap_or_else(|| config.git.branch.clone());
+            worktree.push_head(&config.git.remote, &branch, &options)?;
+            ui.success(&format!(
+                "Pushed release commit to {}/{}",
+                config.git.remote, branch
+            ));
+        }
+        if config.git.push_tags {
+            push_tag_with_retry(worktree.path(), &config.git.remote, tag, &options)?;
+            ui.success(&format!("Pushed tag {}", ui.fmt_tag(tag)));
+        }
+        Ok(())
+    }
 
-                ui.success("Committed release changes");
-            }
+    /// Undo the local tag and release commit after a failed push, so a
+    /// retry starts clean
+    pub(super) fn roll_back(
+        &self,
+        rollback: &ReleaseRollback,
+        config: &Config,
+        ui: &Ui,
+    ) -> anyhow::Result<()> {
+        let mode = if self.rollback {
+            RollbackMode::Always
+        } else if self.no_rollback {
+            RollbackMode::Never
+        } else {
+            config.git.rollback_on_push_failure
+        };
+        let roll_back = match mode {
+            RollbackMode::Always => true,
+            RollbackMode::Never => false,
+            RollbackMode::Prompt => ui.confirm(
+                &format!(
+                    "Delete the local tag {} and release commit?",
+                    rollback.tag()
+                ),
+                true,
+            )?,
+        };
+        if !roll_back {
+            ui.hint(&format!(
+                "Local tag {} was kept; delete it with: git tag -d {}",
+                rollback.tag(),
+                rollback.tag()
+            ));
+            return Ok(());
+        }
 
-            // Create tag
-            repo.create_tag(&tag, Some(&format!("Release {}", next_version)))?;
-            ui.success(&format!("Created tag {}", ui.fmt_tag(&tag)));
+        let outcome = rollback.rollback()?;
+        if outcome.tag_deleted {
+            ui.info(&format!("Deleted local tag {}", ui.fmt_tag(rollback.tag())));
+        }
+        if outcome.commit_reset {
+            ui.info("Reset the release commit");
+        }
+        if outcome.already_pushed {
+            ui.warning("Parts of the release were already pushed and were kept");
+        }
+        Ok(())
+    }
 
-            // Push hint
-            if config.git.push_tags {
-                ui.info(&format!(
-                    "To push, run: {}",
-                    style(format!("git push {} {}", config.git.remote, tag)).cyan()
-                ));
+    /// Whether `tag` exists and `version` is on the adapter's registry
+    ///
+    /// Registries that can't be queried are left out.
+    fn existing_release(
+        &self,
+        repo: &GitRepo,
+        adapter: Option<&dyn PackageAdapter>,
+        path: &Path,
+        tag: &str,
+        version: &str,
+    ) -> anyhow::Result<ExistingRelease> {
+        let mut existing = ExistingRelease::new(repo.find_tag(tag)?.is_some());
+        let Some(adapter) = adapter.filter(|_| !self.no_publish) else {
+            return Ok(existing);
+        };
+        let Ok(package) = adapter.get_info(path) else {
+            return Ok(existing);
+        };
+        if package.private {
+            return Ok(existing);
+        }
+        match adapter.published_versions(path, &package.name) {
+            Ok(versions) => {
+                let published = versions.iter().any(|v| v.to_string() == version);
+                existing = existing.with_registry(adapter.name(), published);
+            }
+            Err(e) => {
+                info!(package = %package.name, error = %e, "could not check published versions");
             }
         }
+        Ok(existing)
+    }
 
-        // Final output
-        if ui.is_json() {
-            ui.json(&result)?;
-        } else {
-            ui.blank();
-            if self.dry_run {
-                ui.success(&format!(
-                    "Dry run complete. Version {} would be released.",
-                    ui.fmt_version(&next_version)
-                ));
-            } else {
-                ui.success(&format!(
-                    "Released version {}",
-                    ui.fmt_version(&next_version)
+    /// Latest version published to the adapter's registry, for packages
+    /// whose manifest only holds a placeholder version
+    fn published_version(
+        &self,
+        adapter: &dyn PackageAdapter,
+        path: &Path,
+        ui: &Ui,
+    ) -> Option<String> {
+        let name = adapter.get_info(path).ok()?.name;
+        match adapter.published_versions(path, &name) {
+            Ok(versions) => {
+                let latest = latest_release(&versions).map(|v| v.to_string());
+                info!(package = %name, latest = ?latest, "read previous version from registry");
+                latest
+            }
+            Err(e) => {
+                ui.warning(&format!(
+                    "Could not read published versions of {}: {}",
+                    name, e
                 ));
+                None
             }
         }
+    }
 
-        Ok(())
+    /// Warn about publish files that look like secrets or are marked
+    /// `export-ignore`; in dry-run mode, also show the full file list
+    pub(super) fn check_publish_files(
+        &self,
+        adapter: &dyn PackageAdapter,
+        path: &Path,
+        config: &Config,
+        ui: &Ui,
+    ) {
+        let files = match adapter.list_publish_files(path) {
+            Ok(files) => files,
+            Err(e) => {
+                ui.warning(&format!("Could not list files to publish: {}", e));
+                return;
+            }
+        };
+
+        if self.dry_run && !files.is_empty() {
+            ui.info(&format!("Would publish {} files:", files.len()));
+            for file in &files {
+                ui.hint(&file.display().to_string());
+            }
+        }
+
+        let patterns = config
+            .publish
+            .secret_file_patterns
+            .clone()
+            .unwrap_or_else(default_secret_patterns);
+        let mut result = check_publish_files(&files, &patterns);
+        result.merge(check_export_ignored(path, &files));
+        for warning in result.warnings {
+            ui.warning(&warning);
+        }
     }
 }
+
+/// Whether a manifest version is a placeholder filled in at publish time,
+/// like `0.0.0` or `0.0.0-dev`
+fn is_placeholder_version(version: &str) -> bool {
+    version == INITIAL_VERSION || version.starts_with(&format!("{}-", INITIAL_VERSION))
+}
@@ -0,0 +1,336 @@
+//! The steps of `canaveral release`, from the pre-release hooks to the
+//! release summary
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use console::style;
+
+use canaveral_adapters::sbom::generate_sbom;
+use canaveral_adapters::{PackageAdapter, PublishOptions};
+use canaveral_changelog::{ChangelogGenerator, ChangelogHistory, ReleaseNotesGenerator};
+use canaveral_core::config::Config;
+use canaveral_core::fs::{prepend_atomic, write_atomic};
+use canaveral_core::hooks::HookStage;
+use canaveral_core::summary::{
+    ArtifactSummary, ChangelogSummary, PublishedPackage, ReleaseSummary,
+};
+use canaveral_core::types::ReleaseResult;
+use canaveral_core::workflow::{
+    release_commit_message, ReleaseResumption, ReleaseStep, ReleaseWorkflow,
+};
+use canaveral_git::{GitRepo, ReleaseRollback, TagInfo, Worktree};
+
+use crate::cli::output::Ui;
+
+use super::release::ReleaseCommand;
+
+/// What a release does, decided before any of its steps run
+pub(super) struct ReleasePlan<'a> {
+    pub config: &'a Config,
+    /// Directory the release was started from
+    pub cwd: &'a Path,
+    pub repo: &'a GitRepo,
+    /// Worktree the release is written in, if isolated
+    pub worktree: Option<&'a Worktree>,
+    /// `cwd`, or its counterpart in the worktree
+    pub release_dir: PathBuf,
+    pub adapter: Option<&'a dyn PackageAdapter>,
+    /// Tag of the previous release
+    pub latest_tag: Option<&'a TagInfo>,
+    pub next_version: String,
+    pub tag: String,
+    /// Steps left to run, for a re-run of a partly finished release
+    pub resumption: ReleaseResumption,
+    pub notes_path: Option<PathBuf>,
+}
+
+impl ReleaseCommand {
+    /// Run the release steps of `plan` and summarize what they did
+    ///
+    /// Bumps the version, writes the changelog, publishes, commits, tags and
+    /// pushes, writes release notes and the SBOM, running the hooks of each
+    /// stage along the way. `result` is updated as steps complete.
+    pub(super) fn run_steps(
+        &self,
+        plan: &ReleasePlan,
+        workflow: &ReleaseWorkflow,
+        result: &mut ReleaseResult,
+        ui: &Ui,
+    ) -> anyhow::Result<ReleaseSummary> {
+        let ReleasePlan {
+            config,
+            cwd,
+            repo,
+            worktree,
+            release_dir,
+            adapter,
+            latest_tag,
+            next_version,
+            tag,
+            resumption,
+            notes_path,
+        } = plan;
+        let release_repo = worktree.map_or(*repo, Worktree::repo);
+        // The version bump, changelog and commit belong to the tagged release
+        let write_release = resumption.runs(&ReleaseStep::Tag);
+
+        self.run_hooks(workflow, HookStage::PreRelease, result, release_dir, ui)?;
+
+        // Update package version via detected adapter
+        if let Some(adapter) = adapter.as_ref().filter(|_| write_release) {
+            if !self.dry_run {
+                self.run_hooks(workflow, HookStage::PreVersion, result, release_dir, ui)?;
+                adapter.set_version(release_dir, next_version)?;
+                ui.success(&format!(
+                    "Updated {} version to {}",
+                    style(adapter.name()).cyan(),
+                    ui.fmt_version(next_version)
+                ));
+                self.run_hooks(workflow, HookStage::PostVersion, result, release_dir, ui)?;
+            } else {
+                ui.info(&format!(
+                    "Would update {} version to {}",
+                    style(adapter.name()).cyan(),
+                    ui.fmt_version(next_version)
+                ));
+            }
+        }
+
+        // Generate changelog if not skipped
+        let mut changelog_summary = None;
+        if !self.no_changelog && config.changelog.enabled && write_release {
+            self.run_hooks(workflow, HookStage::PreChangelog, result, release_dir, ui)?;
+            let commits = if let Some(tag_info) = &latest_tag {
+                repo.commits_since_tag(&tag_info.name)?
+            } else {
+                repo.all_commits()?
+            };
+
+            let generator = ChangelogGenerator::new(config.changelog.clone());
+            let changelog_path = release_dir.join(&config.changelog.file);
+            // Past versions are trusted as written rather than regenerated
+            let history = if config.changelog.preserve_existing {
+                Some(ChangelogHistory::read(&changelog_path, &config.changelog)?)
+            } else {
+                None
+            };
+            let entry = match &history {
+                Some(history) => generator.generate_after(history, next_version, &commits),
+                None => generator.generate(next_version, &commits),
+            };
+            let changelog = generator.format(&entry);
+            changelog_summary = Some(ChangelogSummary::from(&entry));
+
+            if !self.dry_run {
+                match &history {
+                    Some(history) => {
+                        write_atomic(&changelog_path, history.insert(next_version, &changelog))?
+                    }
+                    None => prepend_atomic(&changelog_path, &changelog)?,
+                }
+
+                ui.success(&format!(
+                    "Updated changelog at {}",
+                    ui.fmt_path(&config.changelog.file.display())
+                ));
+            }
+            self.run_hooks(workflow, HookStage::PostChangelog, result, release_dir, ui)?;
+        }
+
+        // Publish package using detected adapter
+        let mut published = false;
+        let mut published_packages = Vec::new();
+        if !self.no_publish {
+            if let Some(adapter) = adapter.as_ref().filter(|adapter| {
+                resumption.runs(&ReleaseStep::Publish(adapter.name().to_string()))
+            }) {
+                let validation = adapter.validate_publishable(release_dir)?;
+                if !validation.passed {
+                    // If this is a workspace root, skip publish instead of failing
+                    let is_workspace_error = validation
+                        .errors
+                        .iter()
+                        .any(|e| e.contains("No [package] section"));
+                    if is_workspace_error {
+                        ui.warning("Workspace root detected — skipping publish (use --package to publish individual crates)");
+                    } else {
+                        anyhow::bail!(
+                            "Publish validation failed:\n{}",
+                            validation.errors.join("\n")
+                        );
+                    }
+                } else {
+                    for warning in &validation.warnings {
+                        ui.warning(warning);
+                    }
+
+                    self.check_publish_files(*adapter, release_dir, config, ui);
+
+                    if !self.dry_run {
+                        self.run_hooks(workflow, HookStage::PrePublish, result, release_dir, ui)?;
+                        let provenance =
+                            config.stores.npm.as_ref().is_some_and(|npm| npm.provenance);
+                        config.tls.validate()?;
+                        adapter.publish_with_options(
+                            release_dir,
+                            &PublishOptions::new()
+                                .provenance(provenance)
+                                .with_env(config.tls.command_env(&std::env::temp_dir())?),
+                        )?;
+                        published = true;
+                        published_packages.push(PublishedPackage {
+                            name: result.package.clone(),
+                            version: next_version.clone(),
+                            registry: adapter.name().to_string(),
+                            url: None,
+                        });
+                        ui.success(&format!(
+                            "Published package via {}",
+                            style(adapter.name()).cyan()
+                        ));
+                        // Post-publish hooks get the files that were published
+                        let files: Vec<PathBuf> = adapter
+                            .list_publish_files(release_dir)
+                            .unwrap_or_default()
+                            .into_iter()
+                            .map(|file| release_dir.join(file))
+                            .collect();
+                        self.run_hooks_with_artifacts(
+                            workflow,
+                            HookStage::PostPublish,
+                            result,
+                            release_dir,
+                            &files,
+                            ui,
+                        )?;
+                    } else {
+                        ui.info(&format!(
+                            "Would publish package via {}",
+                            style(adapter.name()).cyan()
+                        ));
+                    }
+                }
+            } else {
+                ui.warning(&format!(
+                    "No publish adapter detected in {}, skipping publish.",
+                    style(cwd.display()).dim()
+                ));
+            }
+        }
+        result.published = published;
+
+        // Git operations
+        if !self.no_git && !self.dry_run && write_release {
+            // Only releases made in a worktree are pushed from here
+            let rollback = worktree
+                .as_ref()
+                .map(|w| ReleaseRollback::capture(w.path(), &config.git.remote, tag))
+                .transpose()?;
+            self.run_hooks(workflow, HookStage::PreCommit, result, release_dir, ui)?;
+
+            if !release_repo.is_clean()? {
+                let commit_message = release_commit_message(
+                    &config.git.commit_message,
+                    &result.package,
+                    next_version,
+                );
+                let add_output = Command::new("git")
+                    .args(["add", "-A"])
+                    .current_dir(release_dir)
+                    .output()?;
+                if !add_output.status.success() {
+                    anyhow::bail!(
+                        "Failed to stage release changes: {}",
+                        String::from_utf8_lossy(&add_output.stderr)
+                    );
+                }
+
+                let commit_output = Command::new("git")
+                    .args(["commit", "-m", &commit_message])
+                    .current_dir(release_dir)
+                    .output()?;
+                if !commit_output.status.success() {
+                    anyhow::bail!(
+                        "Failed to commit release changes: {}",
+                        String::from_utf8_lossy(&commit_output.stderr)
+                    );
+                }
+
+                ui.success("Committed release changes");
+            }
+
+            // Create tag
+            release_repo.create_tag(tag, Some(&format!("Release {}", next_version)))?;
+            ui.success(&format!("Created tag {}", ui.fmt_tag(tag)));
+
+            if let Some(worktree) = &worktree {
+                if let Err(err) = self.push_release(repo, worktree, config, tag, ui) {
+                    if let Some(rollback) = &rollback {
+                        if let Err(e) = self.roll_back(rollback, config, ui) {
+                            ui.warning(&format!("Could not roll back the release: {}", e));
+                        }
+                    }
+                    return Err(err);
+                }
+            } else if config.git.push_tags {
+                // Push hint
+                ui.info(&format!(
+                    "To push, run: {}",
+                    style(format!("git push {} {}", config.git.remote, tag)).cyan()
+                ));
+            }
+            self.run_hooks(workflow, HookStage::PostTag, result, release_dir, ui)?;
+        }
+
+        // Release notes are an output, not part of the release commit
+        if let Some(path) = notes_path.as_ref().filter(|_| write_release) {
+            if self.dry_run {
+                ui.info(&format!(
+                    "Would write release notes to {}",
+                    ui.fmt_path(&path.display())
+                ));
+            } else {
+                let commits = match &latest_tag {
+                    Some(tag_info) => repo.commits_since_tag(&tag_info.name)?,
+                    None => repo.all_commits()?,
+                };
+                let notes = ReleaseNotesGenerator::new(config.release_notes.clone())
+                    .generate_formatted(next_version, &commits);
+                write_atomic(path, notes)?;
+                ui.success(&format!(
+                    "Wrote release notes to {}",
+                    ui.fmt_path(&path.display())
+                ));
+            }
+        }
+
+        let mut sbom = None;
+        if let (Some(dir), Some(adapter)) = (&self.sbom, &adapter) {
+            if self.dry_run {
+                ui.info(&format!(
+                    "Would write SBOM to {}",
+                    ui.fmt_path(&dir.display())
+                ));
+            } else {
+                let path = generate_sbom(release_dir, adapter.name())?
+                    .write(dir, self.sbom_format.into())?;
+                ui.success(&format!("Wrote SBOM to {}", ui.fmt_path(&path.display())));
+                sbom = Some(ArtifactSummary::from_path(path)?);
+            }
+        }
+
+        let mut summary = workflow.summary(result)?;
+        if let Some(sbom) = sbom {
+            // Released alongside the built artifacts, and listed on its own
+            summary = summary.with_artifact(sbom.clone()).with_sbom(sbom);
+        }
+        if let Some(changelog) = changelog_summary {
+            summary = summary.with_changelog(changelog);
+        }
+        for package in published_packages {
+            summary = summary.with_package(package);
+        }
+        Ok(summary)
+    }
+}
@@ -0,0 +1,139 @@
+//! `canaveral release --workspace`
+
+use console::style;
+use tracing::info;
+
+use canaveral_adapters::{AdapterRegistry, PublishOptions, PublishStatus, WorkspacePublishOptions};
+use canaveral_core::monorepo::PackageFilter;
+use canaveral_core::workflow::SkipReason;
+
+use crate::cli::output::Ui;
+use crate::workspace_release::{
+    commit_workspace_release, plan_workspace_release, write_workspace_release,
+    WorkspaceReleaseOptions,
+};
+
+use super::release::ReleaseCommand;
+
+impl ReleaseCommand {
+    /// Release every workspace package with releasable commits
+    ///
+    /// Returns why nothing was released, if it wasn't.
+    pub(super) fn run_workspace(&self, ui: &Ui) -> anyhow::Result<Option<SkipReason>> {
+        info!(
+            release_type = ?self.release_type,
+            dry_run = self.dry_run,
+            concurrency = self.concurrency,
+            "executing workspace release"
+        );
+        let cwd = std::env::current_dir()?;
+        let (config, _) = self.load_config(&cwd);
+        let repo = self.open_repo(&cwd, &config, ui)?;
+
+        let options = WorkspaceReleaseOptions::new()
+            .with_release_type(self.release_type)
+            .with_concurrency(self.concurrency)
+            .with_changelog(!self.no_changelog)
+            .with_filter(PackageFilter {
+                include: self.filter.clone(),
+                exclude: self.exclude.clone(),
+                force: self.force,
+            });
+        let plan = plan_workspace_release(&repo, &cwd, &config, &options)?;
+        if plan.is_empty() {
+            return Ok(Some(SkipReason::no_bump(None, plan.examined_commits)));
+        }
+        let tags = plan.tags(&config);
+
+        ui.header("Workspace Release Preview");
+        ui.blank();
+        for bump in &plan.bumps {
+            ui.key_value(
+                &bump.package,
+                &format!(
+                    "{} -> {} ({})",
+                    bump.current_version,
+                    ui.fmt_version(&bump.new_version),
+                    bump.reason
+                ),
+            );
+        }
+        let tag_list: Vec<String> = tags.iter().map(|t| ui.fmt_tag(t)).collect();
+        ui.key_value("Tags", &tag_list.join(", "));
+        ui.blank();
+
+        if self.dry_run {
+            ui.warning("[DRY RUN - no changes will be made]");
+            if ui.is_json() {
+                ui.json(&plan.bumps)?;
+            }
+            return Ok(None);
+        }
+        if !self.yes && !ui.confirm("Proceed with release?", true)? {
+            return Ok(Some(SkipReason::Declined));
+        }
+
+        let baseline = repo.modified_files()?;
+        let result = write_workspace_release(&config, &plan, &options)?;
+        ui.success(&format!("Updated {} package(s)", plan.bumps.len()));
+
+        if !self.no_git {
+            commit_workspace_release(&repo, &config, &plan, &result, &baseline)?;
+            ui.success("Committed release changes");
+            for tag in &tags {
+                ui.success(&format!("Created tag {}", ui.fmt_tag(tag)));
+            }
+            if config.git.push_tags {
+                ui.info(&format!(
+                    "To push, run: {}",
+                    style(format!("git push {} --follow-tags", config.git.remote)).cyan()
+                ));
+            }
+        }
+
+        if !self.no_publish {
+            let provenance = config.stores.npm.as_ref().is_some_and(|npm| npm.provenance);
+            config.tls.validate()?;
+            let publish = WorkspacePublishOptions::new().with_publish_options(
+                PublishOptions::new()
+                    .provenance(provenance)
+                    .with_env(config.tls.command_env(&std::env::temp_dir())?),
+            );
+            let outcomes =
+                AdapterRegistry::new().publish_packages(&plan.released_packages(), &publish)?;
+            let mut failures = Vec::new();
+            for outcome in &outcomes {
+                match &outcome.status {
+                    PublishStatus::Published => ui.success(&format!(
+                        "Published {}@{}",
+                        outcome.name,
+                        ui.fmt_version(&outcome.version)
+                    )),
+                    PublishStatus::Skipped(reason) => {
+                        ui.info(&format!("Skipped publishing {}: {}", outcome.name, reason))
+                    }
+                    PublishStatus::ValidationFailed(errors) => {
+                        failures.push(format!("{}: {}", outcome.name, errors.join("; ")))
+                    }
+                    PublishStatus::Failed(error) => {
+                        failures.push(format!("{}: {}", outcome.name, error))
+                    }
+                    PublishStatus::Aborted => {
+                        failures.push(format!("{}: not attempted", outcome.name))
+                    }
+                }
+            }
+            if !failures.is_empty() {
+                anyhow::bail!("Publishing failed:\n{}", failures.join("\n"));
+            }
+        }
+
+        if ui.is_json() {
+            ui.json(&plan.bumps)?;
+        } else {
+            ui.blank();
+            ui.success(&format!("Released {} package(s)", plan.bumps.len()));
+        }
+        Ok(None)
+    }
+}
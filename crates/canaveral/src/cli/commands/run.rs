@@ -8,8 +8,9 @@ use clap::Args;
 use console::style;
 use tracing::info;
 
+use canaveral_adapters::AdapterRegistry;
 use canaveral_core::config::load_config_or_default;
-use canaveral_core::monorepo::{ChangeDetector, DependencyGraph, PackageDiscovery, Workspace};
+use canaveral_core::monorepo::{ChangeDetector, DependencyGraph, PackageFilter, Workspace};
 use canaveral_tasks::scheduler::SchedulerOptions;
 use canaveral_tasks::{TaskCache, TaskDag, TaskDefinition, TaskEvent, TaskReporter, TaskScheduler};
 
@@ -31,10 +32,14 @@ pub struct RunCommand {
     #[arg(long, default_value = "main")]
     pub base: String,
 
-    /// Filter to specific packages (can be repeated)
+    /// Only include packages whose name or path matches this glob (can be repeated)
     #[arg(long)]
     pub filter: Vec<String>,
 
+    /// Skip packages whose name or path matches this glob (can be repeated)
+    #[arg(long)]
+    pub exclude: Vec<String>,
+
     /// Maximum concurrent tasks
     #[arg(long)]
     pub concurrency: Option<usize>,
@@ -71,10 +76,8 @@ impl RunCommand {
         let (config, _) = load_config_or_default(&cwd);
 
         // Discover workspace
-        let workspace =
-            Workspace::detect(&cwd)?.context("No workspace found in current directory")?;
-        let discovery = PackageDiscovery::new(workspace);
-        let discovered = discovery.discover()?;
+        Workspace::detect(&cwd)?.context("No workspace found in current directory")?;
+        let discovered = AdapterRegistry::new().discover_packages(&cwd)?;
 
         if discovered.is_empty() {
             anyhow::bail!("No packages found in workspace");
@@ -84,11 +87,16 @@ impl RunCommand {
         let graph = DependencyGraph::build(&discovered)?;
         graph.validate()?;
 
+        let filter = PackageFilter {
+            include: self.filter.clone(),
+            exclude: self.exclude.clone(),
+            force: false,
+        };
+        filter.validate()?;
+
         // Determine which packages to include
-        let mut packages: Vec<String> = if !self.filter.is_empty() {
-            self.filter.clone()
-        } else if self.affected {
-            let detector = ChangeDetector::new(cwd.clone());
+        let mut packages: Vec<String> = if self.affected {
+            let detector = ChangeDetector::new(cwd.clone()).with_filter(filter);
             let changed_files = detector.get_changed_files_git(Some(&self.base), "HEAD")?;
             let changed = detector.detect_changes(&discovered, &changed_files, Some(&graph))?;
 
@@ -102,7 +110,11 @@ impl RunCommand {
 
             changed.iter().map(|c| c.name.clone()).collect()
         } else {
-            graph.sorted().to_vec()
+            let selected = filter.apply(&discovered, &cwd);
+            if selected.is_empty() {
+                anyhow::bail!("No packages match the given filters");
+            }
+            selected.iter().map(|p| p.name.clone()).collect()
         };
 
         // Sort packages in topological order
@@ -176,7 +188,7 @@ impl RunCommand {
 
         // Set up cache
         let cache = if !self.no_cache && config.tasks.cache.enabled {
-            Some(TaskCache::default_dir(&cwd))
+            Some(TaskCache::default_dir(&cwd).with_hash_algorithm(config.tasks.cache.hash))
         } else {
             None
         };
@@ -196,6 +208,7 @@ impl RunCommand {
             use_cache: !self.no_cache && config.tasks.cache.enabled,
             dry_run: false,
             root_dir: cwd,
+            vars: Default::default(),
         };
 
         let scheduler = TaskScheduler::new(options, cache, reporter);
@@ -453,6 +466,31 @@ impl TaskReporter for ConsoleReporter {
                     duration.as_secs_f64()
                 );
             }
+            TaskEvent::Timing(timing) => {
+                if timing.critical_path.is_empty() {
+                    return;
+                }
+                println!(
+                    "  {} {} {}",
+                    style("Critical path:").dim(),
+                    timing.critical_path_display(),
+                    style(format!(
+                        "({:.1}s, {:.1}x parallelism)",
+                        timing.critical_path_time.as_secs_f64(),
+                        timing.efficiency()
+                    ))
+                    .dim()
+                );
+                if self.verbose {
+                    for (id, duration) in timing.tasks.iter().take(5) {
+                        println!(
+                            "    {} {}",
+                            style(format!("{:>6.1}s", duration.as_secs_f64())).dim(),
+                            id
+                        );
+                    }
+                }
+            }
         }
     }
 }
@@ -10,7 +10,7 @@ use canaveral_signing::{
     profiles::ProfileManager,
     providers::{create_provider, ProviderType},
     sync::ProfileType,
-    SignOptions, VerifyOptions,
+    ReportFormat, SignOptions, VerificationReport, VerifyOptions,
 };
 
 use crate::cli::output::Ui;
@@ -144,7 +144,7 @@ pub struct SignCommand {
     #[arg(short, long)]
     pub identity: Option<String>,
 
-    /// Signing provider (macos, windows, android, gpg)
+    /// Signing provider (macos, windows, android, gpg); detected from the artifact by default
     #[arg(short, long)]
     pub provider: Option<String>,
 
@@ -212,9 +212,9 @@ pub struct SignCommand {
 /// Verify a signature
 #[derive(Debug, Args)]
 pub struct VerifyCommand {
-    /// Path to artifact to verify
-    #[arg(required = true)]
-    pub artifact: PathBuf,
+    /// Paths to artifacts to verify
+    #[arg(required = true, num_args = 1..)]
+    pub artifacts: Vec<PathBuf>,
 
     /// Signing provider (macos, windows, android, gpg)
     #[arg(short, long)]
@@ -494,30 +494,7 @@ impl SignCommand {
             .or(config.signing.provider.as_ref())
             .map(|p| p.parse::<ProviderType>())
             .transpose()?
-            .unwrap_or_else(|| {
-                // Auto-detect based on file extension
-                if let Some(ext) = self.artifact.extension().and_then(|e| e.to_str()) {
-                    match ext.to_lowercase().as_str() {
-                        "app" | "framework" | "dylib" | "pkg" | "dmg" => ProviderType::MacOS,
-                        "exe" | "dll" | "msi" | "msix" => ProviderType::Windows,
-                        "apk" | "aab" => ProviderType::Android,
-                        _ => ProviderType::Gpg,
-                    }
-                } else {
-                    #[cfg(target_os = "macos")]
-                    {
-                        ProviderType::MacOS
-                    }
-                    #[cfg(target_os = "windows")]
-                    {
-                        ProviderType::Windows
-                    }
-                    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
-                    {
-                        ProviderType::Gpg
-                    }
-                }
-            });
+            .unwrap_or_else(|| ProviderType::for_artifact(&self.artifact));
 
         let provider = create_provider(provider_type)?;
 
@@ -655,29 +632,6 @@ impl VerifyCommand {
         let cwd = std::env::current_dir()?;
         let (config, _) = load_config_or_default(&cwd);
 
-        // Determine provider
-        let provider_type = self
-            .provider
-            .as_ref()
-            .or(config.signing.provider.as_ref())
-            .map(|p| p.parse::<ProviderType>())
-            .transpose()?
-            .unwrap_or_else(|| {
-                // Auto-detect based on file extension
-                if let Some(ext) = self.artifact.extension().and_then(|e| e.to_str()) {
-                    match ext.to_lowercase().as_str() {
-                        "app" | "framework" | "dylib" | "pkg" | "dmg" => ProviderType::MacOS,
-                        "exe" | "dll" | "msi" | "msix" => ProviderType::Windows,
-                        "apk" | "aab" => ProviderType::Android,
-                        _ => ProviderType::Gpg,
-                    }
-                } else {
-                    ProviderType::Gpg
-                }
-            });
-
-        let provider = create_provider(provider_type)?;
-
         let options = VerifyOptions {
             deep: self.deep,
             strict: self.strict,
@@ -685,88 +639,59 @@ impl VerifyCommand {
             check_notarization: self.check_notarization,
         };
 
-        let info = provider.verify(&self.artifact, &options).await?;
+        let configured = self
+            .provider
+            .as_ref()
+            .or(config.signing.provider.as_ref())
+            .map(|p| p.parse::<ProviderType>())
+            .transpose()?;
 
-        if ui.is_json() {
-            ui.json(&info)?;
-        } else if ui.is_text() {
-            ui.info(&format!(
-                "Verifying {}",
-                style(self.artifact.display()).bold()
-            ));
-            ui.blank();
+        let mut report = VerificationReport::new();
+        for artifact in &self.artifacts {
+            let provider_type = configured.unwrap_or_else(|| ProviderType::for_artifact(artifact));
 
-            let status_style = match info.status {
-                canaveral_signing::SignatureStatus::Valid => style("VALID").green().bold(),
-                canaveral_signing::SignatureStatus::Invalid => style("INVALID").red().bold(),
-                canaveral_signing::SignatureStatus::Expired => style("EXPIRED").yellow().bold(),
-                canaveral_signing::SignatureStatus::Revoked => style("REVOKED").red().bold(),
-                canaveral_signing::SignatureStatus::NotSigned => style("NOT SIGNED").dim(),
-                canaveral_signing::SignatureStatus::Unknown => style("UNKNOWN").yellow(),
+            let verified = match create_provider(provider_type) {
+                Ok(provider) => provider.verify(artifact, &options).await,
+                Err(e) => Err(e),
             };
-
-            ui.key_value_styled("Status", status_style);
-
-            if let Some(signer) = &info.signer {
-                ui.key_value("Signer", &style(&signer.common_name).cyan().to_string());
-                if let Some(team) = &signer.team_id {
-                    ui.key_value("Team", team);
-                }
-                if let Some(expires) = &signer.expires_at {
-                    let exp_str = expires.format("%Y-%m-%d").to_string();
-                    if signer.certificate_valid {
-                        ui.key_value("Certificate Expires", &style(&exp_str).green().to_string());
-                    } else {
-                        ui.key_value(
-                            "Certificate Expires",
-                            &style(format!("{} (EXPIRED)", exp_str)).red().to_string(),
-                        );
-                    }
-                }
-            }
-
-            if let Some(signed_at) = info.signed_at {
-                ui.key_value(
-                    "Signed",
-                    &signed_at.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
-                );
-            }
-
-            if let Some(notarized) = info.notarized {
-                let notary_status = if notarized {
-                    style("Yes").green()
-                } else {
-                    style("No").yellow()
-                };
-                ui.key_value_styled("Notarized", notary_status);
-            }
-
-            if !info.warnings.is_empty() {
-                ui.blank();
-                ui.section("Warnings");
-                for warning in &info.warnings {
-                    ui.warning(warning);
-                }
+            match verified {
+                Ok(info) => report.push(info),
+                Err(e) => report.push_error(artifact.display().to_string(), e),
             }
+        }
 
-            if self.verbose {
-                if let Some(details) = &info.details {
-                    ui.blank();
-                    ui.section("Details");
-                    for line in details.lines().take(20) {
-                        println!("    {}", line);
-                    }
-                }
+        if ui.is_json() {
+            ui.json(&report)?;
+        } else if ui.is_text() {
+            print!("{}", report.render(ReportFormat::Text));
+            if options.verbose {
+                self.print_details(&ui, &report);
             }
         }
 
         // Return error if not valid
-        if info.status != canaveral_signing::SignatureStatus::Valid {
-            anyhow::bail!("Signature verification failed: {}", info.status);
+        if !report.passed() {
+            let failed: Vec<_> = report
+                .failures()
+                .map(|info| format!("{} ({})", info.path, info.status))
+                .collect();
+            anyhow::bail!("Signature verification failed: {}", failed.join(", "));
         }
 
         Ok(())
     }
+
+    fn print_details(&self, ui: &Ui, report: &VerificationReport) {
+        for info in report.artifacts() {
+            if let Some(details) = &info.details {
+                ui.blank();
+                ui.section(&format!("Details: {}", info.path));
+                for line in details.lines().take(20) {
+                    println!("    {}", line);
+                }
+            }
+        }
+    }
 }
 
 impl InfoCommand {
@@ -32,7 +32,8 @@ impl StatusCommand {
         // Gather status information
         let is_clean = repo.is_clean()?;
         let current_branch = repo.current_branch()?;
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag =
+            repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
         let packages = detect_packages_recursive(&cwd, 3)?;
 
         let commits_since = if let Some(tag) = &latest_tag {
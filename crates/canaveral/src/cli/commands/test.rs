@@ -55,6 +55,10 @@ pub struct TestCommand {
     #[arg(long, default_value = "0")]
     pub retry: usize,
 
+    /// Fail when a test only passes on a retry
+    #[arg(long)]
+    pub fail_on_flaky: bool,
+
     /// Test timeout in seconds
     #[arg(long)]
     pub timeout: Option<u64>,
@@ -270,6 +274,7 @@ impl TestCommand {
         let config = TestRunnerConfig::new()
             .with_fail_fast(self.fail_fast)
             .with_retry(self.retry)
+            .with_fail_on_flaky(self.fail_on_flaky)
             .with_verbose(cli.verbose);
 
         let config = if let Some(ref framework) = self.framework {
@@ -343,11 +348,12 @@ impl TestCommand {
         }
 
         // Exit with error if tests failed
-        if !report.success() {
+        if !runner.passed(&report) {
             anyhow::bail!(
-                "Tests failed: {} passed, {} failed, {} skipped",
+                "Tests failed: {} passed, {} failed, {} flaky, {} skipped",
                 report.passed,
                 report.failed,
+                report.flaky,
                 report.skipped
             );
         }
@@ -414,6 +420,7 @@ impl TestCommand {
                     TestStatus::Passed => (style("✓").green(), style(&test.name).dim()),
                     TestStatus::Failed => (style("✗").red(), style(&test.name).red()),
                     TestStatus::Skipped => (style("○").yellow(), style(&test.name).yellow()),
+                    TestStatus::Flaky => (style("⚠").yellow(), style(&test.name).yellow()),
                 };
 
                 println!("      {} {} ({}ms)", icon, name_style, test.duration_ms);
@@ -433,11 +440,13 @@ impl TestCommand {
 
         println!("{}", style("═".repeat(70)).dim());
         println!(
-            "  {} {} passed, {} {} failed, {} {} skipped ({}ms)",
+            "  {} {} passed, {} {} failed, {} {} flaky, {} {} skipped ({}ms)",
             style(report.passed).green().bold(),
             style("passed").dim(),
             style(report.failed).red().bold(),
             style("failed").dim(),
+            style(report.flaky).yellow().bold(),
+            style("flaky").dim(),
             style(report.skipped).yellow().bold(),
             style("skipped").dim(),
             report.duration_ms
@@ -459,7 +468,14 @@ impl TestCommand {
 
         println!("{}", style("═".repeat(70)).dim());
 
-        if report.success() {
+        if report.success() && report.flaky > 0 {
+            println!();
+            println!(
+                "  {} {}",
+                style("⚠").yellow().bold(),
+                style(format!("All tests passed, {} only on retry.", report.flaky)).yellow()
+            );
+        } else if report.success() {
             println!();
             println!(
                 "  {} {}",
@@ -11,6 +11,7 @@ use canaveral_stores::apple::{
 };
 use canaveral_stores::types::AppleStoreConfig;
 
+use crate::cli::cancel::ctrl_c_token;
 use crate::cli::output::Ui;
 use crate::cli::Cli;
 
@@ -330,6 +331,7 @@ impl TestFlightCommand {
             dry_run: false,
             verbose: cli.verbose,
             release_notes,
+            cancellation: ctrl_c_token(),
             ..Default::default()
         };
 
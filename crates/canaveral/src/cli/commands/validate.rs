@@ -5,11 +5,16 @@ use console::style;
 use tracing::info;
 
 use canaveral_adapters::detect_packages;
-use canaveral_core::config::{load_config_from_dir, validation::validate_config};
+use canaveral_core::config::{
+    load_config_from_dir, load_config_or_default, validation::validate_config,
+};
+use canaveral_core::workflow::IssueSeverity;
 use canaveral_git::GitRepo;
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::exit_codes;
+use crate::preflight::validate_release;
 
 /// Validate configuration and repository state
 #[derive(Debug, Args)]
@@ -21,18 +26,27 @@ pub struct ValidateCommand {
     /// Strict mode - treat warnings as errors
     #[arg(long)]
     pub strict: bool,
+
+    /// Run every release pre-flight check for each workspace package,
+    /// without releasing
+    #[arg(long, conflicts_with = "config_only")]
+    pub release: bool,
 }
 
 impl ValidateCommand {
-    /// Execute the validate command
-    pub fn execute(&self, cli: &Cli) -> anyhow::Result<()> {
+    /// Execute the validate command, returning the process exit code
+    pub fn execute(&self, cli: &Cli) -> anyhow::Result<i32> {
         info!(
             config_only = self.config_only,
             strict = self.strict,
+            release = self.release,
             "executing validate command"
         );
         let ui = Ui::new(cli);
         let cwd = std::env::current_dir()?;
+        if self.release {
+            return self.validate_release(&ui, &cwd);
+        }
 
         let mut errors: Vec<String> = Vec::new();
         let mut warnings: Vec<String> = Vec::new();
@@ -173,9 +187,60 @@ impl ValidateCommand {
         }
 
         if !passed {
-            std::process::exit(1);
+            return Ok(exit_codes::ERROR);
+        }
+
+        Ok(exit_codes::SUCCESS)
+    }
+
+    /// Run the release pre-flight checks and report them by check
+    fn validate_release(&self, ui: &Ui, cwd: &std::path::Path) -> anyhow::Result<i32> {
+        let (config, _) = load_config_or_default(cwd);
+        let mut summary = validate_release(cwd, &config);
+        if self.strict {
+            summary = summary.into_strict();
+        }
+
+        if ui.is_json() {
+            ui.json(&summary)?;
+            return Ok(summary.exit_code());
         }
 
-        Ok(())
+        ui.header("Release Pre-flight");
+        ui.blank();
+        for check in &summary.checks {
+            ui.section(check);
+            let issues: Vec<_> = summary
+                .issues
+                .iter()
+                .filter(|i| &i.check == check)
+                .collect();
+            if issues.is_empty() {
+                ui.success("ok");
+            }
+            for issue in issues {
+                let message = match &issue.target {
+                    Some(target) => format!("{}: {}", target, issue.message),
+                    None => issue.message.clone(),
+                };
+                match issue.severity {
+                    IssueSeverity::Error => ui.error(&message),
+                    IssueSeverity::Warning => ui.warning(&message),
+                    IssueSeverity::Info => ui.info(&message),
+                }
+            }
+            ui.blank();
+        }
+
+        let errors = summary.errors().count();
+        let warnings = summary.warnings().count();
+        if errors > 0 {
+            ui.error(&format!("Pre-flight failed with {} error(s)", errors));
+        } else if warnings > 0 {
+            ui.success(&format!("Pre-flight passed with {} warning(s)", warnings));
+        } else {
+            ui.success("All pre-flight checks passed");
+        }
+        Ok(summary.exit_code())
     }
 }
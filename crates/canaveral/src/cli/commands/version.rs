@@ -4,7 +4,6 @@ use clap::Args;
 use console::style;
 use tracing::info;
 
-use canaveral_changelog::{CommitParser, ConventionalParser};
 use canaveral_core::config::load_config_or_default;
 use canaveral_core::types::ReleaseType;
 use canaveral_git::GitRepo;
@@ -12,6 +11,7 @@ use canaveral_strategies::{BumpType, SemVerStrategy, VersionStrategy};
 
 use crate::cli::output::Ui;
 use crate::cli::Cli;
+use crate::version::{infer_bump, BumpInference, INITIAL_VERSION};
 
 /// Calculate the next version
 #[derive(Debug, Args)]
@@ -27,6 +27,10 @@ pub struct VersionCommand {
     /// Package name (for monorepos)
     #[arg(short, long)]
     pub package: Option<String>,
+
+    /// Ignore pre-release tags when finding the current version
+    #[arg(long)]
+    pub exclude_prereleases: bool,
 }
 
 impl VersionCommand {
@@ -35,16 +39,23 @@ impl VersionCommand {
         info!(release_type = ?self.release_type, current = self.current, package = ?self.package, "executing version command");
         let ui = Ui::new(cli);
         let cwd = std::env::current_dir()?;
-        let (config, _) = load_config_or_default(&cwd);
+        let (mut config, _) = load_config_or_default(&cwd);
+        if self.exclude_prereleases {
+            config.versioning.include_prereleases = false;
+        }
 
-        let repo = GitRepo::discover(&cwd)?;
+        let mut repo = GitRepo::discover(&cwd)?;
+        if config.git.auto_unshallow && repo.is_shallow() {
+            repo.unshallow(&config.git.remote)?;
+        }
 
         // Find the latest tag
-        let latest_tag = repo.find_latest_tag(None)?;
+        let latest_tag =
+            repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
         let current_version = latest_tag
             .as_ref()
             .and_then(|t| t.version.clone())
-            .unwrap_or_else(|| "0.0.0".to_string());
+            .unwrap_or_else(|| INITIAL_VERSION.to_string());
 
         if self.current {
             self.output_current(&current_version, &ui)?;
@@ -59,23 +70,8 @@ impl VersionCommand {
         };
 
         // Determine bump type from commits
-        let parser = ConventionalParser::new();
-        let mut bump_type = BumpType::None;
-
-        for commit in &commits {
-            if let Some(parsed) = parser.parse(commit) {
-                let commit_bump = if parsed.breaking {
-                    BumpType::Major
-                } else if parsed.is_minor() {
-                    BumpType::Minor
-                } else if parsed.is_patch() {
-                    BumpType::Patch
-                } else {
-                    BumpType::None
-                };
-                bump_type = bump_type.max(commit_bump);
-            }
-        }
+        let inference = infer_bump(&commits, &config);
+        let mut bump_type = inference.bump;
 
         // Override with explicit release type
         if let Some(rt) = self.release_type {
@@ -88,18 +84,27 @@ impl VersionCommand {
             };
         }
 
-        // Calculate next version
-        let strategy = SemVerStrategy::new();
-        let current = strategy.parse(&current_version)?;
-        let next = strategy.bump(&current, bump_type)?;
-        let next_version = strategy.format(&next);
+        // Calculate next version; a Release-As footer wins unless a
+        // release type was requested
+        let forced = match self.release_type {
+            Some(_) => None,
+            None => inference.forced_version(&current_version)?,
+        };
+        let next_version = match forced {
+            Some(forced) => forced.version.clone(),
+            None => {
+                let strategy = SemVerStrategy::new();
+                let current = strategy.parse(&current_version)?;
+                strategy.format(&strategy.bump(&current, bump_type)?)
+            }
+        };
 
         self.output_result(
             &current_version,
             &next_version,
             bump_type,
+            &inference,
             commits.len(),
-            &config,
             &ui,
         )?;
 
@@ -124,16 +129,28 @@ impl VersionCommand {
         current: &str,
         next: &str,
         bump_type: BumpType,
+        inference: &BumpInference,
         commit_count: usize,
-        _config: &canaveral_core::config::Config,
         ui: &Ui,
     ) -> anyhow::Result<()> {
+        // An explicit release type overrides whatever the commits required
+        let forced = inference.release_as.as_ref().filter(|r| r.version == next);
+        let reason = if let (None, Some(forced)) = (self.release_type, forced) {
+            Some(forced.reason.as_str())
+        } else if bump_type == inference.bump {
+            inference.reason.as_deref()
+        } else {
+            Some("requested release type")
+        };
+
         if ui.is_json() {
             let output = serde_json::json!({
                 "current": current,
                 "next": next,
                 "bump_type": bump_type.to_string(),
-                "commits": commit_count
+                "reason": reason,
+                "commits": commit_count,
+                "releasable_commits": inference.releasable_commits
             });
             ui.json(&output)?;
         } else if ui.is_quiet() {
@@ -147,7 +164,14 @@ impl VersionCommand {
                 "Bump type",
                 &style(bump_type.to_string()).yellow().to_string(),
             );
+            if let Some(reason) = reason {
+                ui.key_value("Reason", reason);
+            }
             ui.key_value("Commits analyzed", &commit_count.to_string());
+            ui.key_value(
+                "Releasable commits",
+                &inference.releasable_commits.to_string(),
+            );
         }
         Ok(())
     }
@@ -1,5 +1,6 @@
 //! CLI definition and command handling
 
+pub mod cancel;
 pub mod commands;
 pub mod output;
 
@@ -8,11 +9,12 @@ use std::fmt::Write;
 use tracing::info;
 
 use commands::{
-    ArchiveCommand, BuildCommand, CICommand, CacheCommand, ChangelogCommand, CheckCommand,
-    CompletionsCommand, DoctorCommand, FirebaseCommand, FmtCommand, HooksCommand, InitCommand,
-    LintCommand, MatchCommand, MetadataCommand, PrCommand, PublishCommand, ReleaseCommand,
-    RunCommand, ScaffoldCommand, ScreenshotsCommand, SigningCommand, StatusCommand, TestCommand,
-    TestFlightCommand, ToolsCommand, ValidateCommand, VersionCommand,
+    ArchiveCommand, BuildCommand, CICommand, CacheCommand, CapabilitiesCommand, ChangelogCommand,
+    CheckCommand, CompletionsCommand, DiffCommand, DoctorCommand, FirebaseCommand, FmtCommand,
+    HooksCommand, InitCommand, LintCommand, MatchCommand, MetadataCommand, MigrateCommand,
+    PrCommand, PublishCommand, ReleaseCommand, RunCommand, ScaffoldCommand, ScreenshotsCommand,
+    SigningCommand, StatusCommand, TestCommand, TestFlightCommand, ToolsCommand, ValidateCommand,
+    VersionCommand,
 };
 
 /// Canaveral - Build, release, and ship software from a single CLI
@@ -61,9 +63,15 @@ pub enum Commands {
     /// Initialize a new Canaveral configuration
     Init(InitCommand),
 
+    /// Migrate another release tool's configuration
+    Migrate(MigrateCommand),
+
     /// Check environment for required tools and configurations
     Doctor(DoctorCommand),
 
+    /// Report what the project's detected adapters can do
+    Capabilities(CapabilitiesCommand),
+
     /// Manage tool versions (bun, node, etc.)
     Tools(ToolsCommand),
 
@@ -113,6 +121,9 @@ pub enum Commands {
     /// Generate changelog
     Changelog(ChangelogCommand),
 
+    /// Show what changed between two versions
+    Diff(DiffCommand),
+
     /// Create a new release
     Release(ReleaseCommand),
 
@@ -148,13 +159,26 @@ pub enum Commands {
 }
 
 const COMMAND_GROUPS: &[(&str, &[&str])] = &[
-    ("Setup", &["scaffold", "init", "doctor", "tools"]),
+    (
+        "Setup",
+        &[
+            "scaffold",
+            "init",
+            "migrate",
+            "doctor",
+            "capabilities",
+            "tools",
+        ],
+    ),
     (
         "Develop",
         &["fmt", "lint", "build", "archive", "test", "run", "check"],
     ),
     ("Code Quality", &["hooks", "validate", "status", "ci", "pr"]),
-    ("Release", &["version", "changelog", "release", "publish"]),
+    (
+        "Release",
+        &["version", "changelog", "diff", "release", "publish"],
+    ),
     (
         "Distribute",
         &["signing", "match", "test-flight", "firebase"],
@@ -187,7 +211,7 @@ pub fn grouped_help_template(cmd: &clap::Command) -> String {
 
 impl Cli {
     /// Execute the CLI command
-    pub fn execute(self) -> anyhow::Result<()> {
+    pub fn execute(self) -> anyhow::Result<i32> {
         // Change to specified directory if provided
         if let Some(dir) = &self.directory {
             info!(directory = %dir.display(), "changing working directory");
@@ -198,7 +222,9 @@ impl Cli {
             // Setup
             Commands::Scaffold(_) => "scaffold",
             Commands::Init(_) => "init",
+            Commands::Migrate(_) => "migrate",
             Commands::Doctor(_) => "doctor",
+            Commands::Capabilities(_) => "capabilities",
             Commands::Tools(_) => "tools",
             // Develop
             Commands::Fmt(_) => "fmt",
@@ -217,6 +243,7 @@ impl Cli {
             // Release
             Commands::Version(_) => "version",
             Commands::Changelog(_) => "changelog",
+            Commands::Diff(_) => "diff",
             Commands::Release(_) => "release",
             Commands::Publish(_) => "publish",
             // Distribute
@@ -238,11 +265,13 @@ impl Cli {
             "executing command"
         );
 
-        match self.command {
+        let result = match self.command {
             // Setup
             Commands::Scaffold(ref cmd) => cmd.execute(&self),
             Commands::Init(ref cmd) => cmd.execute(&self),
+            Commands::Migrate(ref cmd) => cmd.execute(&self),
             Commands::Doctor(ref cmd) => cmd.execute(&self),
+            Commands::Capabilities(ref cmd) => cmd.execute(&self),
             Commands::Tools(ref cmd) => cmd.execute(&self),
             // Develop
             Commands::Fmt(ref cmd) => cmd.execute(&self),
@@ -254,14 +283,15 @@ impl Cli {
             Commands::Check(ref cmd) => cmd.execute(&self),
             // Code Quality
             Commands::Hooks(ref cmd) => cmd.execute(&self),
-            Commands::Validate(ref cmd) => cmd.execute(&self),
+            Commands::Validate(ref cmd) => return cmd.execute(&self),
             Commands::Status(ref cmd) => cmd.execute(&self),
             Commands::CI(ref cmd) => cmd.execute(&self),
             Commands::Pr(ref cmd) => cmd.execute(&self),
             // Release
             Commands::Version(ref cmd) => cmd.execute(&self),
             Commands::Changelog(ref cmd) => cmd.execute(&self),
-            Commands::Release(ref cmd) => cmd.execute(&self),
+            Commands::Diff(ref cmd) => cmd.execute(&self),
+            Commands::Release(ref cmd) => return cmd.execute(&self),
             Commands::Publish(ref cmd) => cmd.execute(&self),
             // Distribute
             Commands::Signing(ref cmd) => cmd.execute(&self),
@@ -274,6 +304,7 @@ impl Cli {
             // Utility
             Commands::Cache(ref cmd) => cmd.execute(&self),
             Commands::Completions(ref cmd) => cmd.execute(&self),
-        }
+        };
+        result.map(|()| crate::exit_codes::SUCCESS)
     }
 }
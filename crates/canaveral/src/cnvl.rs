@@ -1,5 +1,8 @@
 //! cnvl - Short alias for the canaveral CLI
 
 fn main() -> anyhow::Result<()> {
-    canaveral::run()
+    match canaveral::run()? {
+        canaveral::exit_codes::SUCCESS => Ok(()),
+        code => std::process::exit(code),
+    }
 }
@@ -1,7 +1,17 @@
 //! Exit codes for the CLI
+//!
+//! Codes mirror [`ErrorCode::exit_code`] so that CI can branch on the kind
+//! of failure regardless of which crate raised it.
 
 #![allow(dead_code)]
 
+use canaveral_core::error::{
+    AdapterError, CanaveralError, ConfigError, ErrorCode, GitError, VersionError, WorkflowError,
+};
+use canaveral_core::workflow::ReleaseOutcome;
+use canaveral_frameworks::FrameworkError;
+use canaveral_stores::StoreError;
+
 /// Success
 pub const SUCCESS: i32 = 0;
 
@@ -20,5 +30,159 @@ pub const VERSION_ERROR: i32 = 4;
 /// Validation error
 pub const VALIDATION_ERROR: i32 = 5;
 
+/// Authentication error
+pub const AUTH_ERROR: i32 = 6;
+
+/// Network or registry error
+pub const NETWORK_ERROR: i32 = 7;
+
+/// Required tool not installed
+pub const TOOL_MISSING: i32 = 8;
+
+/// Release skipped, nothing to release
+pub const RELEASE_SKIPPED: i32 = 9;
+
+/// Build failed
+pub const BUILD_ERROR: i32 = 10;
+
+/// Tests failed
+pub const TEST_ERROR: i32 = 11;
+
 /// User cancelled
 pub const CANCELLED: i32 = 130;
+
+/// Find the error category of the first classified error in the chain
+pub fn error_code(err: &anyhow::Error) -> ErrorCode {
+    for cause in err.chain() {
+        if let Some(e) = cause.downcast_ref::<CanaveralError>() {
+            return e.code();
+        }
+        if let Some(e) = cause.downcast_ref::<AdapterError>() {
+            return e.code();
+        }
+        if let Some(e) = cause.downcast_ref::<WorkflowError>() {
+            return e.code();
+        }
+        if cause.is::<ConfigError>() {
+            return ErrorCode::Config;
+        }
+        if cause.is::<GitError>() {
+            return ErrorCode::Git;
+        }
+        if cause.is::<VersionError>() {
+            return ErrorCode::Version;
+        }
+        if let Some(e) = cause.downcast_ref::<FrameworkError>() {
+            return e.code();
+        }
+        if let Some(e) = cause.downcast_ref::<StoreError>() {
+            return e.code().parse().unwrap_or(ErrorCode::General);
+        }
+    }
+    ErrorCode::General
+}
+
+/// Map an error to the process exit code
+pub fn from_error(err: &anyhow::Error) -> i32 {
+    error_code(err).exit_code()
+}
+
+/// Map a release outcome to the process exit code
+///
+/// A skipped release is a success unless `exit_code_on_skip` asks for
+/// [`RELEASE_SKIPPED`].
+pub fn from_outcome(outcome: &ReleaseOutcome, exit_code_on_skip: bool) -> i32 {
+    match outcome {
+        ReleaseOutcome::Skipped(_) if exit_code_on_skip => RELEASE_SKIPPED,
+        ReleaseOutcome::Released(_) | ReleaseOutcome::Skipped(_) => SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_core::types::{ReleaseResult, ReleaseType};
+    use canaveral_core::workflow::SkipReason;
+    use canaveral_core::ReleaseSummary;
+
+    #[test]
+    fn test_constants_match_error_codes() {
+        assert_eq!(ErrorCode::General.exit_code(), ERROR);
+        assert_eq!(ErrorCode::Config.exit_code(), CONFIG_ERROR);
+        assert_eq!(ErrorCode::Git.exit_code(), GIT_ERROR);
+        assert_eq!(ErrorCode::Version.exit_code(), VERSION_ERROR);
+        assert_eq!(ErrorCode::Validation.exit_code(), VALIDATION_ERROR);
+        assert_eq!(ErrorCode::Auth.exit_code(), AUTH_ERROR);
+        assert_eq!(ErrorCode::Network.exit_code(), NETWORK_ERROR);
+        assert_eq!(ErrorCode::ToolMissing.exit_code(), TOOL_MISSING);
+        assert_eq!(ErrorCode::Build.exit_code(), BUILD_ERROR);
+        assert_eq!(ErrorCode::Test.exit_code(), TEST_ERROR);
+        assert_eq!(ErrorCode::Cancelled.exit_code(), CANCELLED);
+    }
+
+    #[test]
+    fn test_from_error() {
+        let err = anyhow::Error::from(CanaveralError::from(ConfigError::MissingField(
+            "name".to_string(),
+        )));
+        assert_eq!(from_error(&err), CONFIG_ERROR);
+
+        let err = anyhow::Error::from(WorkflowError::Cancelled);
+        assert_eq!(from_error(&err), CANCELLED);
+
+        let err = anyhow::Error::from(ConfigError::MissingField("name".to_string()))
+            .context("loading config");
+        assert_eq!(from_error(&err), CONFIG_ERROR);
+
+        let err = anyhow::Error::from(GitError::NoCommits);
+        assert_eq!(from_error(&err), GIT_ERROR);
+
+        let err = anyhow::Error::from(CanaveralError::from(WorkflowError::Cancelled));
+        assert_eq!(from_error(&err), CANCELLED);
+
+        let err = anyhow::Error::from(FrameworkError::tool_not_found(
+            "xcodebuild",
+            "Install Xcode",
+        ))
+        .context("build failed");
+        assert_eq!(from_error(&err), TOOL_MISSING);
+
+        let err = anyhow::Error::from(StoreError::InvalidCredentials("expired".to_string()));
+        assert_eq!(from_error(&err), AUTH_ERROR);
+
+        let err = anyhow::anyhow!("something unexpected");
+        assert_eq!(from_error(&err), ERROR);
+    }
+
+    #[test]
+    fn test_from_outcome() {
+        let skipped = ReleaseOutcome::Skipped(SkipReason::no_bump(Some("v1.0.0"), 0));
+        assert_eq!(from_outcome(&skipped, false), SUCCESS);
+        assert_eq!(from_outcome(&skipped, true), RELEASE_SKIPPED);
+
+        let released =
+            ReleaseOutcome::Released(Box::new(ReleaseSummary::from_result(&ReleaseResult {
+                package: "app".to_string(),
+                previous_version: Some("1.0.0".to_string()),
+                new_version: "1.1.0".to_string(),
+                release_type: ReleaseType::Minor,
+                triggering_commits: Vec::new(),
+                tag: "v1.1.0".to_string(),
+                published: true,
+                changelog: None,
+                notes: Vec::new(),
+            })));
+        assert_eq!(from_outcome(&released, false), SUCCESS);
+        assert_eq!(from_outcome(&released, true), SUCCESS);
+
+        // An opted-in skip is neither success nor any failure
+        let failure = anyhow::Error::from(CanaveralError::from(WorkflowError::CadenceNotMet(
+            "too soon".to_string(),
+        )));
+        assert_ne!(from_outcome(&skipped, true), from_error(&failure));
+        assert_ne!(
+            from_outcome(&skipped, true),
+            from_error(&anyhow::anyhow!("boom"))
+        );
+    }
+}
@@ -1,8 +1,20 @@
 //! Canaveral - Build, release, and ship software from a single CLI
 
+pub mod bump;
 pub mod cli;
 pub mod exit_codes;
+pub mod notify;
+pub mod preflight;
+pub mod prerequisites;
+pub mod regenerate;
+pub mod release_pr;
 pub mod scaffold;
+pub mod version;
+pub mod workspace_release;
+
+pub use regenerate::{regenerate_release, RegenerateOptions, Regenerated};
+pub use release_pr::{prepare_release_pr, ReleasePr, ReleasePrOptions};
+pub use version::{compute_next_version, NextVersion};
 
 use clap::{CommandFactory, FromArgMatches};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
@@ -10,7 +22,7 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilte
 use cli::Cli;
 
 /// Run the CLI. Called by both `canaveral` and `cnvl` binaries.
-pub fn run() -> anyhow::Result<()> {
+pub fn run() -> anyhow::Result<i32> {
     let _guard = init_tracing();
 
     let mut cmd = Cli::command();
@@ -1,5 +1,12 @@
 //! Canaveral - Universal release management CLI
 
-fn main() -> anyhow::Result<()> {
-    canaveral::run()
+fn main() {
+    match canaveral::run() {
+        Ok(canaveral::exit_codes::SUCCESS) => {}
+        Ok(code) => std::process::exit(code),
+        Err(err) => {
+            eprintln!("Error: {:?}", err);
+            std::process::exit(canaveral::exit_codes::from_error(&err));
+        }
+    }
 }
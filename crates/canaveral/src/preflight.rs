@@ -0,0 +1,410 @@
+//! Release pre-flight checks
+//!
+//! [`validate_release`] runs everything a release would check, without
+//! bumping versions, writing the changelog, tagging or publishing: package
+//! publishability, store metadata, changelog generation and next-version
+//! computation. In a workspace every package is checked. CI runs it, as
+//! `canaveral validate --release`, before opening a release PR.
+
+use std::path::{Path, PathBuf};
+
+use canaveral_adapters::AdapterRegistry;
+use canaveral_changelog::ChangelogGenerator;
+use canaveral_core::config::{Config, MetadataConfig, MetadataScreenshotsConfig};
+use canaveral_core::error::{CanaveralError, Result};
+use canaveral_core::workflow::{
+    IssueSeverity, PreflightCheck, ReleaseOptions, ReleaseWorkflow, ValidationIssue,
+    ValidationSummary,
+};
+use canaveral_git::GitRepo;
+use canaveral_metadata::{
+    AppleValidator, AssetFilter, FastlaneStorage, GooglePlayValidator, MetadataStorage, Severity,
+    DEFAULT_SIMILARITY_THRESHOLD,
+};
+use tracing::debug;
+
+use crate::version::compute_next_version;
+use crate::workspace_release::{plan_workspace_release, WorkspaceReleaseOptions};
+
+/// Run all release pre-flight checks for the repository at `root`
+pub fn validate_release(root: &Path, config: &Config) -> ValidationSummary {
+    ReleaseWorkflow::new(config, ReleaseOptions::dry_run())
+        .with_check(PackageCheck::new(root))
+        .with_check(MetadataCheck::new(root.join(&config.metadata.storage.path)))
+        .with_check(ChangelogCheck::new(root))
+        .with_check(VersionCheck::new(root))
+        .validate()
+}
+
+fn other(err: impl std::fmt::Display) -> CanaveralError {
+    CanaveralError::other(err.to_string())
+}
+
+/// Each non-private package of the workspace must pass its adapter's
+/// publish validation
+#[derive(Debug, Clone)]
+pub struct PackageCheck {
+    root: PathBuf,
+}
+
+impl PackageCheck {
+    /// Check the packages in `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl PreflightCheck for PackageCheck {
+    fn name(&self) -> &str {
+        "packages"
+    }
+
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        let registry = AdapterRegistry::from_config(config)?;
+        let packages = registry.discover_packages(&self.root)?;
+        if packages.is_empty() {
+            return Ok(vec![ValidationIssue::warning("No packages detected")]);
+        }
+
+        let mut issues = Vec::new();
+        for package in packages {
+            if package.private {
+                debug!(package = %package.name, "skipping private package");
+                continue;
+            }
+            let Some(adapter) = registry
+                .for_package(&package.name, &package.package_type)
+                .or_else(|| registry.detect(&package.path))
+            else {
+                continue;
+            };
+            let result = adapter.validate_publishable(&package.path)?;
+            let target = format!("{} ({})", package.name, package.package_type);
+            issues.extend(
+                result
+                    .errors
+                    .iter()
+                    .map(ValidationIssue::error)
+                    .chain(result.warnings.iter().map(ValidationIssue::warning))
+                    .map(|issue| issue.with_target(&target)),
+            );
+        }
+        Ok(issues)
+    }
+}
+
+/// Store metadata for every app in the metadata directory must pass the
+/// store's validator
+///
+/// Apps are found as `apple/<bundle id>` and `google_play/<package name>`
+/// directories; nothing is checked when the directory doesn't exist.
+#[derive(Debug, Clone)]
+pub struct MetadataCheck {
+    path: PathBuf,
+}
+
+impl MetadataCheck {
+    /// Check the Fastlane-layout metadata under `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    async fn validate(&self, config: &MetadataConfig) -> Result<Vec<ValidationIssue>> {
+        let storage = FastlaneStorage::new(&self.path)
+            .with_screenshot_filter(screenshot_filter(&config.screenshots));
+        let validation = &config.validation;
+        let threshold = validation
+            .similarity_threshold
+            .unwrap_or(DEFAULT_SIMILARITY_THRESHOLD);
+        let mut issues = Vec::new();
+        for app_id in app_dirs(&self.path.join("apple"))? {
+            let metadata = storage.load_apple(&app_id).await.map_err(other)?;
+            let result = AppleValidator::new(validation.strict)
+                .with_similarity_threshold(threshold)
+                .validate(&metadata);
+            issues.extend(metadata_issues(&format!("apple/{}", app_id), &result));
+        }
+        for app_id in app_dirs(&self.path.join("google_play"))? {
+            let metadata = storage.load_google_play(&app_id).await.map_err(other)?;
+            let result = GooglePlayValidator::new(validation.strict)
+                .with_similarity_threshold(threshold)
+                .validate(&metadata);
+            issues.extend(metadata_issues(&format!("google_play/{}", app_id), &result));
+        }
+        Ok(issues)
+    }
+}
+
+impl PreflightCheck for MetadataCheck {
+    fn name(&self) -> &str {
+        "metadata"
+    }
+
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        if !self.path.is_dir() {
+            debug!(path = %self.path.display(), "no metadata directory");
+            return Ok(Vec::new());
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(self.validate(&config.metadata))
+    }
+}
+
+/// The screenshot file filter for the configured include/exclude globs
+pub fn screenshot_filter(config: &MetadataScreenshotsConfig) -> AssetFilter {
+    let filter = AssetFilter::default();
+    let filter = if config.include.is_empty() {
+        filter
+    } else {
+        filter.with_includes(&config.include)
+    };
+    filter.with_excludes(&config.exclude)
+}
+
+/// App directories under a platform directory, sorted
+fn app_dirs(platform_dir: &Path) -> Result<Vec<String>> {
+    if !platform_dir.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut apps: Vec<String> = std::fs::read_dir(platform_dir)?
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().to_str().map(String::from))
+        .collect();
+    apps.sort();
+    Ok(apps)
+}
+
+fn metadata_issues(
+    target: &str,
+    result: &canaveral_metadata::ValidationResult,
+) -> Vec<ValidationIssue> {
+    result
+        .issues
+        .iter()
+        .map(|issue| {
+            let severity = match issue.severity {
+                Severity::Error => IssueSeverity::Error,
+                Severity::Warning => IssueSeverity::Warning,
+                Severity::Info => IssueSeverity::Info,
+            };
+            ValidationIssue::new(severity, format!("{}: {}", issue.field, issue.message))
+                .with_target(target)
+        })
+        .collect()
+}
+
+/// The changelog for the unreleased commits must generate and not be empty
+#[derive(Debug, Clone)]
+pub struct ChangelogCheck {
+    root: PathBuf,
+}
+
+impl ChangelogCheck {
+    /// Check the changelog for the repository at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl PreflightCheck for ChangelogCheck {
+    fn name(&self) -> &str {
+        "changelog"
+    }
+
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        if !config.changelog.enabled {
+            return Ok(Vec::new());
+        }
+        let repo = GitRepo::discover(&self.root).map_err(other)?;
+        let latest_tag = repo
+            .find_latest_tag_filtered(None, config.versioning.include_prereleases)
+            .map_err(other)?;
+        let commits = match &latest_tag {
+            Some(tag) => repo.commits_since_tag(&tag.name),
+            None => repo.all_commits(),
+        }
+        .map_err(other)?;
+
+        // The version only labels the entry, which is never written
+        let entry =
+            ChangelogGenerator::new(config.changelog.clone()).generate("unreleased", &commits);
+        if entry.is_empty() {
+            let since = latest_tag.map_or_else(|| "the first commit".to_string(), |t| t.name);
+            return Ok(vec![ValidationIssue::warning(format!(
+                "No changelog entries since {}",
+                since
+            ))]);
+        }
+        Ok(Vec::new())
+    }
+}
+
+/// The next version must be computable from the commit history
+///
+/// In a workspace, each package's next version is computed from the commits
+/// touching it since its own last tag.
+#[derive(Debug, Clone)]
+pub struct VersionCheck {
+    root: PathBuf,
+}
+
+impl VersionCheck {
+    /// Check the next version of the repository at `root`
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl PreflightCheck for VersionCheck {
+    fn name(&self) -> &str {
+        "version"
+    }
+
+    fn run(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        let packages = AdapterRegistry::new().discover_packages(&self.root)?;
+        if packages.len() > 1 {
+            return self.run_workspace(config);
+        }
+        let issue = match compute_next_version(&self.root, config).map_err(other)? {
+            Some(next) => ValidationIssue::new(
+                IssueSeverity::Info,
+                format!("Next version would be {} ({})", next.next, next.reason),
+            ),
+            None => ValidationIssue::warning("No releasable commits since the latest version"),
+        };
+        Ok(vec![issue])
+    }
+}
+
+impl VersionCheck {
+    fn run_workspace(&self, config: &Config) -> Result<Vec<ValidationIssue>> {
+        let repo = GitRepo::discover(&self.root).map_err(other)?;
+        let plan =
+            plan_workspace_release(&repo, &self.root, config, &WorkspaceReleaseOptions::new())
+                .map_err(other)?;
+        if plan.is_empty() {
+            return Ok(vec![ValidationIssue::warning(
+                "No package has releasable commits since its latest version",
+            )]);
+        }
+        Ok(plan
+            .bumps
+            .iter()
+            .map(|bump| {
+                ValidationIssue::new(
+                    IssueSeverity::Info,
+                    format!(
+                        "Next version would be {} ({})",
+                        bump.new_version, bump.reason
+                    ),
+                )
+                .with_target(&bump.package)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_metadata::{Locale, Platform};
+
+    #[test]
+    fn test_passing_package_and_failing_metadata() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "web", "version": "1.0.0", "description": "Web app", "main": "index.js", "license": "MIT"}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("README.md"), "# web").unwrap();
+
+        // Template metadata has no title or descriptions yet
+        let metadata = root.join("metadata");
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        runtime
+            .block_on(FastlaneStorage::new(&metadata).init(
+                Platform::GooglePlay,
+                "com.example.web",
+                &[Locale::new("en-US").unwrap()],
+            ))
+            .unwrap();
+        drop(runtime);
+
+        let config = Config::default();
+        let summary = ReleaseWorkflow::new(&config, ReleaseOptions::dry_run())
+            .with_check(PackageCheck::new(root))
+            .with_check(MetadataCheck::new(&metadata))
+            .validate();
+
+        assert_eq!(summary.checks, ["config", "packages", "metadata"]);
+        assert!(summary
+            .issues
+            .iter()
+            .all(|i| i.check != "packages" || i.severity != IssueSeverity::Error));
+        assert!(!summary.passed());
+        assert_ne!(summary.exit_code(), 0);
+        let errors: Vec<_> = summary.errors().collect();
+        assert!(errors.iter().all(|e| e.check == "metadata"));
+        assert_eq!(
+            errors[0].target.as_deref(),
+            Some("google_play/com.example.web")
+        );
+    }
+
+    #[test]
+    fn test_checks_every_workspace_package() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"name": "root", "private": true, "workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        for (name, manifest) in [
+            (
+                "ok",
+                r#"{"name": "ok", "version": "1.0.0", "description": "Fine", "main": "index.js", "license": "MIT"}"#,
+            ),
+            ("broken", r#"{"name": "broken", "version": "one"}"#),
+        ] {
+            let dir = root.join("packages").join(name);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("package.json"), manifest).unwrap();
+            std::fs::write(dir.join("README.md"), "# readme").unwrap();
+        }
+
+        let issues = PackageCheck::new(root).run(&Config::default()).unwrap();
+        let errors: Vec<_> = issues
+            .iter()
+            .filter(|i| i.severity == IssueSeverity::Error)
+            .collect();
+        assert!(!errors.is_empty());
+        assert!(errors
+            .iter()
+            .all(|i| i.target.as_deref() == Some("broken (npm)")));
+        assert!(issues
+            .iter()
+            .any(|i| i.target.as_deref() == Some("ok (npm)")));
+    }
+
+    #[test]
+    fn test_screenshot_filter_from_config() {
+        let filter = screenshot_filter(&MetadataScreenshotsConfig::default());
+        assert!(filter.matches(Path::new("01_home.png")));
+        assert!(!filter.matches(Path::new(".DS_Store")));
+
+        let config = MetadataScreenshotsConfig {
+            include: vec!["*.png".to_string(), "*.webp".to_string()],
+            exclude: vec!["*_original.*".to_string()],
+        };
+        let filter = screenshot_filter(&config);
+        assert!(filter.matches(Path::new("01_home.webp")));
+        assert!(!filter.matches(Path::new("01_home_original.png")));
+        assert!(!filter.matches(Path::new("01_home.jpg")));
+    }
+}
@@ -0,0 +1,459 @@
+//! Project prerequisites report
+//!
+//! A release shells out to many tools: the framework's build toolchain,
+//! test runners, store upload tools, signing tools and package managers.
+//! [`PrerequisitesCheck`] detects which of them a project needs and whether
+//! each one is installed, so a missing tool shows up before a release fails
+//! on it.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use serde::Serialize;
+use tracing::{debug, info, warn};
+
+use canaveral_adapters::{detect_packages_recursive, AdapterRegistry, RequiredTool};
+use canaveral_frameworks::traits::{Platform, PrerequisiteStatus};
+use canaveral_frameworks::FrameworkRegistry;
+use canaveral_stores::google_play::find_in_sdk;
+
+/// Directory depth searched for packages
+const PACKAGE_DEPTH: usize = 3;
+
+const BUILD_TOOLS_HINT: &str =
+    "Install the Android SDK build-tools (sdkmanager \"build-tools;34.0.0\") and set ANDROID_HOME";
+const XCODE_HINT: &str = "Install the Xcode command line tools (xcode-select --install)";
+
+/// What a required tool is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ToolCategory {
+    /// Building the app
+    Build,
+    /// Running tests
+    Test,
+    /// Uploading to an app store
+    Store,
+    /// Code signing
+    Signing,
+    /// Versioning and publishing packages
+    Package,
+}
+
+impl ToolCategory {
+    /// Lowercase name of the category
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Build => "build",
+            Self::Test => "test",
+            Self::Store => "store",
+            Self::Signing => "signing",
+            Self::Package => "package",
+        }
+    }
+}
+
+/// Availability of one required tool
+#[derive(Debug, Clone, Serialize)]
+pub struct ToolReport {
+    /// Tool name
+    pub name: String,
+    /// What the tool is used for
+    pub category: ToolCategory,
+    /// Adapter, store or signer that needs the tool
+    pub required_by: String,
+    /// Whether the tool was found
+    pub available: bool,
+    /// Detected version, if the tool reports one
+    pub version: Option<String>,
+    /// How to install the tool
+    pub install_hint: Option<String>,
+}
+
+/// Every tool a project needs, with its availability
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct PrerequisitesReport {
+    /// Required tools, grouped by category in detection order
+    pub tools: Vec<ToolReport>,
+    /// Problems that don't block a release, such as unset SDK variables
+    pub warnings: Vec<String>,
+}
+
+impl PrerequisitesReport {
+    /// Whether every required tool is available
+    pub fn satisfied(&self) -> bool {
+        self.tools.iter().all(|t| t.available)
+    }
+
+    /// Required tools that weren't found
+    pub fn missing(&self) -> impl Iterator<Item = &ToolReport> {
+        self.tools.iter().filter(|t| !t.available)
+    }
+
+    /// The first tool named `name`
+    pub fn tool(&self, name: &str) -> Option<&ToolReport> {
+        self.tools.iter().find(|t| t.name == name)
+    }
+
+    /// Add a tool unless one with the same name and category is listed
+    fn push(&mut self, tool: ToolReport) {
+        if !self
+            .tools
+            .iter()
+            .any(|t| t.name == tool.name && t.category == tool.category)
+        {
+            self.tools.push(tool);
+        }
+    }
+
+    fn push_status(
+        &mut self,
+        category: ToolCategory,
+        required_by: &str,
+        status: PrerequisiteStatus,
+    ) {
+        for tool in status.tools {
+            self.push(ToolReport {
+                name: tool.name,
+                category,
+                required_by: required_by.to_string(),
+                available: tool.available,
+                version: tool.version,
+                install_hint: Some(tool.install_hint).filter(|h| !h.is_empty()),
+            });
+        }
+        self.warnings.extend(
+            status
+                .warnings
+                .into_iter()
+                .map(|w| format!("{}: {}", required_by, w)),
+        );
+    }
+}
+
+/// Finds tools on a search path and asks them for their version
+#[derive(Debug, Clone)]
+pub struct ToolProbe {
+    search_path: Option<OsString>,
+    android_sdks: Vec<PathBuf>,
+}
+
+impl ToolProbe {
+    /// Search `PATH`, then the Android SDK's build-tools
+    pub fn system() -> Self {
+        Self {
+            search_path: std::env::var_os("PATH"),
+            android_sdks: ["ANDROID_HOME", "ANDROID_SDK_ROOT"]
+                .iter()
+                .filter_map(std::env::var_os)
+                .map(PathBuf::from)
+                .collect(),
+        }
+    }
+
+    /// Search only `dirs`
+    pub fn with_search_path(dirs: impl IntoIterator<Item = PathBuf>) -> Self {
+        Self {
+            search_path: std::env::join_paths(dirs).ok(),
+            android_sdks: Vec::new(),
+        }
+    }
+
+    /// Path of the executable `command`, if installed
+    pub fn find(&self, command: &str) -> Option<PathBuf> {
+        let cwd = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+        if let Some(path) = self
+            .search_path
+            .as_ref()
+            .and_then(|search| which::which_in(command, Some(search), &cwd).ok())
+        {
+            return Some(path);
+        }
+        let binary = if cfg!(windows) {
+            format!("{}.exe", command)
+        } else {
+            command.to_string()
+        };
+        self.android_sdks
+            .iter()
+            .find_map(|sdk| find_in_sdk(sdk, &binary))
+    }
+
+    /// Availability and version of `tool`
+    fn check(&self, tool: &RequiredTool, category: ToolCategory, required_by: &str) -> ToolReport {
+        let path = self.find(tool.command);
+        let version = path
+            .as_deref()
+            .filter(|_| !tool.version_args.is_empty())
+            .and_then(|path| command_version(path, tool.version_args));
+        debug!(tool = tool.command, found = path.is_some(), version = ?version, "probed tool");
+        ToolReport {
+            name: tool.command.to_string(),
+            category,
+            required_by: required_by.to_string(),
+            available: path.is_some(),
+            version,
+            install_hint: path.is_none().then(|| tool.install_hint.to_string()),
+        }
+    }
+}
+
+impl Default for ToolProbe {
+    fn default() -> Self {
+        Self::system()
+    }
+}
+
+/// First line a tool prints for its version (some print to stderr)
+fn command_version(path: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new(path).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    stdout
+        .lines()
+        .chain(stderr.lines())
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(str::to_string)
+}
+
+/// Tools app store uploads need, by target platform
+fn store_tools(platform: Platform) -> &'static [(&'static str, RequiredTool)] {
+    const ANDROID: &[(&str, RequiredTool)] = &[(
+        "google-play",
+        RequiredTool::new("aapt2", &["version"], BUILD_TOOLS_HINT),
+    )];
+    const APPLE: &[(&str, RequiredTool)] = &[(
+        "app-store",
+        RequiredTool::new("xcrun", &["--version"], XCODE_HINT),
+    )];
+    match platform {
+        Platform::Android => ANDROID,
+        Platform::Ios | Platform::MacOs => APPLE,
+        _ => &[],
+    }
+}
+
+/// Tools code signing needs, by target platform
+fn signing_tools(platform: Platform) -> &'static [(&'static str, RequiredTool)] {
+    const ANDROID: &[(&str, RequiredTool)] = &[(
+        "android",
+        RequiredTool::new("apksigner", &["--version"], BUILD_TOOLS_HINT),
+    )];
+    const APPLE: &[(&str, RequiredTool)] =
+        &[("macos", RequiredTool::new("codesign", &[], XCODE_HINT))];
+    const WINDOWS: &[(&str, RequiredTool)] = &[(
+        "windows",
+        RequiredTool::new("signtool", &[], "Install the Windows SDK"),
+    )];
+    match platform {
+        Platform::Android => ANDROID,
+        Platform::Ios | Platform::MacOs => APPLE,
+        Platform::Windows => WINDOWS,
+        _ => &[],
+    }
+}
+
+/// Detects the tools a project needs and checks each one
+pub struct PrerequisitesCheck {
+    frameworks: FrameworkRegistry,
+    probe: ToolProbe,
+}
+
+impl PrerequisitesCheck {
+    /// Check with the built-in framework adapters and the system `PATH`
+    pub fn new() -> Self {
+        Self {
+            frameworks: FrameworkRegistry::with_builtins(),
+            probe: ToolProbe::system(),
+        }
+    }
+
+    /// Detect frameworks with `registry` instead of the built-in adapters
+    pub fn with_frameworks(mut self, registry: FrameworkRegistry) -> Self {
+        self.frameworks = registry;
+        self
+    }
+
+    /// Look tools up with `probe`
+    pub fn with_probe(mut self, probe: ToolProbe) -> Self {
+        self.probe = probe;
+        self
+    }
+
+    /// Report every tool the project at `root` needs
+    ///
+    /// Framework adapters check their own toolchains. Store and signing
+    /// tools follow from the platforms the project builds for, package
+    /// tools from the packages detected under `root`.
+    pub async fn run(&self, root: &Path) -> PrerequisitesReport {
+        info!(root = %root.display(), "checking project prerequisites");
+        let mut report = PrerequisitesReport::default();
+
+        for adapter in self.frameworks.adapter_prerequisites(root).await {
+            let category = match adapter.kind {
+                "test" => ToolCategory::Test,
+                _ => ToolCategory::Build,
+            };
+            report.push_status(category, &adapter.adapter_id, adapter.prerequisites);
+        }
+
+        let platforms = self.platforms(root);
+        for (category, tools) in [
+            (ToolCategory::Store, store_tools as fn(Platform) -> _),
+            (ToolCategory::Signing, signing_tools),
+        ] {
+            for platform in &platforms {
+                for (required_by, tool) in tools(*platform) {
+                    report.push(self.probe.check(tool, category, required_by));
+                }
+            }
+        }
+
+        match detect_packages_recursive(root, PACKAGE_DEPTH) {
+            Ok(packages) => {
+                let registry = AdapterRegistry::new();
+                for package in packages {
+                    let Some(adapter) = registry.get(&package.package_type) else {
+                        continue;
+                    };
+                    for tool in adapter.required_tools() {
+                        report.push(
+                            self.probe
+                                .check(tool, ToolCategory::Package, adapter.name()),
+                        );
+                    }
+                }
+            }
+            Err(e) => {
+                warn!(error = %e, "package detection failed");
+                report
+                    .warnings
+                    .push(format!("Package detection failed: {}", e));
+            }
+        }
+
+        info!(
+            tools = report.tools.len(),
+            missing = report.missing().count(),
+            "prerequisites checked"
+        );
+        report
+    }
+
+    /// Platforms the project builds for
+    ///
+    /// A framework targeting several platforms only counts those with a
+    /// platform directory (`android/`, `ios/`, ...) in the project.
+    fn platforms(&self, root: &Path) -> Vec<Platform> {
+        let Some(adapter) = self.frameworks.detect_build_best(root) else {
+            return Vec::new();
+        };
+        match adapter.supported_platforms() {
+            [platform] => vec![*platform],
+            platforms => platforms
+                .iter()
+                .copied()
+                .filter(|p| root.join(p.as_str()).is_dir())
+                .collect(),
+        }
+    }
+}
+
+impl Default for PrerequisitesCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_docker_present_and_aapt2_missing() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let project = TempDir::new().unwrap();
+        let root = project.path();
+        std::fs::write(
+            root.join("Dockerfile"),
+            "FROM alpine\nLABEL version=\"1.0.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("build.gradle"), "").unwrap();
+        std::fs::create_dir_all(root.join("app/src/main")).unwrap();
+        std::fs::write(root.join("app/src/main/AndroidManifest.xml"), "<manifest/>").unwrap();
+
+        let bin = TempDir::new().unwrap();
+        let docker = bin.path().join("docker");
+        std::fs::write(
+            &docker,
+            "#!/bin/sh\necho 'Docker version 27.3.1, build ce12230'\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&docker, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let report = PrerequisitesCheck::new()
+            .with_probe(ToolProbe::with_search_path([bin.path().to_path_buf()]))
+            .run(root)
+            .await;
+
+        let docker = report.tool("docker").unwrap();
+        assert_eq!(docker.category, ToolCategory::Package);
+        assert_eq!(docker.required_by, "docker");
+        assert!(docker.available);
+        assert_eq!(
+            docker.version.as_deref(),
+            Some("Docker version 27.3.1, build ce12230")
+        );
+        assert_eq!(docker.install_hint, None);
+
+        let aapt2 = report.tool("aapt2").unwrap();
+        assert_eq!(aapt2.category, ToolCategory::Store);
+        assert_eq!(aapt2.required_by, "google-play");
+        assert!(!aapt2.available);
+        assert_eq!(aapt2.version, None);
+        assert!(aapt2
+            .install_hint
+            .as_deref()
+            .unwrap()
+            .contains("build-tools"));
+
+        assert!(!report.satisfied());
+        assert!(report.missing().any(|t| t.name == "apksigner"));
+        // No Apple tools for an Android-only project
+        assert!(report.tool("xcrun").is_none());
+
+        let json = serde_json::to_value(&report).unwrap();
+        let aapt2 = json["tools"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|t| t["name"] == "aapt2")
+            .unwrap();
+        assert_eq!(aapt2["category"], "store");
+        assert_eq!(aapt2["available"], false);
+    }
+
+    #[tokio::test]
+    async fn test_empty_project_needs_nothing() {
+        let project = TempDir::new().unwrap();
+        let report = PrerequisitesCheck::new()
+            .with_probe(ToolProbe::with_search_path(Vec::new()))
+            .run(project.path())
+            .await;
+
+        assert!(report.tools.is_empty());
+        assert!(report.satisfied());
+        assert!(ToolProbe::with_search_path(Vec::new())
+            .find("docker")
+            .is_none());
+    }
+}
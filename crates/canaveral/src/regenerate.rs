@@ -0,0 +1,486 @@
+//! Regenerating the changelog and release notes of a past release
+//!
+//! [`regenerate_release`] recomputes the changelog entry and release notes
+//! of an existing tag from the commits between the tag before it and that
+//! tag, with the current config. It is how a typo or a missing contributor
+//! gets fixed after the fact: only the changelog, the release notes file and
+//! optionally the GitHub Release body are written. The version, commits and
+//! tags are left alone.
+
+use std::path::{Path, PathBuf};
+
+use canaveral_changelog::{
+    ChangelogGenerator, ChangelogHistory, Collapse, GithubNotesOptions, ReleaseNotesGenerator,
+};
+use canaveral_core::config::Config;
+use canaveral_core::fs::write_atomic;
+use canaveral_core::workflow::tag_pattern;
+use canaveral_git::{CommitInfo, GitRepo};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use crate::release_pr::GitHubCli;
+
+/// What [`regenerate_release`] rewrites
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegenerateOptions {
+    /// Rewrite the version's entry in the changelog
+    pub changelog: bool,
+    /// Regenerate the release notes
+    pub notes: bool,
+    /// Replace the GitHub Release body with the release notes
+    pub update_release: bool,
+}
+
+impl RegenerateOptions {
+    /// Regenerate only the changelog entry
+    pub fn changelog_only() -> Self {
+        Self {
+            changelog: true,
+            notes: false,
+            update_release: false,
+        }
+    }
+
+    /// Regenerate only the release notes
+    pub fn notes_only() -> Self {
+        Self {
+            changelog: false,
+            notes: true,
+            update_release: false,
+        }
+    }
+
+    /// Also replace the GitHub Release body with the regenerated notes
+    pub fn with_release_update(mut self, update: bool) -> Self {
+        self.update_release = update;
+        self
+    }
+}
+
+/// Result of regenerating a release
+#[derive(Debug, Clone, Serialize)]
+pub struct Regenerated {
+    /// Tag that was regenerated
+    pub tag: String,
+    /// Version of the tag
+    pub version: String,
+    /// Tag the commit range starts after (`None` for the first release)
+    pub previous_tag: Option<String>,
+    /// Commits in the range
+    pub commits: usize,
+    /// Changelog file the entry was written to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog_path: Option<PathBuf>,
+    /// The regenerated changelog entry
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changelog: Option<String>,
+    /// Release notes file that was written
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes_path: Option<PathBuf>,
+    /// The regenerated GitHub Release body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// URL of the updated GitHub Release
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub release_url: Option<String>,
+}
+
+/// Updates the release notes of a published release
+pub trait ReleaseHost {
+    /// Replace the body of the release for `tag`. Returns the release URL.
+    fn update_release_notes(&self, tag: &str, body: &str) -> anyhow::Result<String>;
+}
+
+impl ReleaseHost for GitHubCli {
+    fn update_release_notes(&self, tag: &str, body: &str) -> anyhow::Result<String> {
+        self.gh(&["release", "edit", tag, "--notes", body])?;
+        let url = self.gh(&["release", "view", tag, "--json", "url", "--jq", ".url"])?;
+        info!(tag, url = %url, "updated release notes");
+        Ok(url)
+    }
+}
+
+/// Regenerate the changelog entry and release notes of the existing `tag`
+/// in the repository at `root`
+///
+/// The commits are those after the preceding tag of the same `package` (the
+/// preceding release, for a release tag) up to `tag`, and the entry is
+/// dated by the tag. A first release starts at the package directory's
+/// first commit. The version's entry in the changelog is replaced, keeping
+/// every other entry as written. `host` is only used when `options.update_release` is set.
+///
+/// Fails if `tag` doesn't exist or has no version in its name.
+pub fn regenerate_release(
+    root: &Path,
+    config: &Config,
+    tag: &str,
+    package: Option<&str>,
+    options: &RegenerateOptions,
+    host: &dyn ReleaseHost,
+) -> anyhow::Result<Regenerated> {
+    let repo = GitRepo::discover(root)?;
+    let root = repo.workdir().unwrap_or(root).to_path_buf();
+    let tag_info = repo
+        .find_tag(tag)?
+        .ok_or_else(|| anyhow::anyhow!("tag '{}' does not exist", tag))?;
+    let version = tag_info
+        .version
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("tag '{}' has no version in its name", tag))?;
+
+    let previous = repo.find_tag_before(tag, Some(&tag_pattern(config, package)))?;
+    // A first release has no preceding tag; its range starts at the first
+    // commit (of the package's directory, for a package)
+    let first = match &previous {
+        Some(_) => None,
+        None => first_release_commit(&repo, config, package),
+    };
+    let commits = match (&previous, &first) {
+        (Some(previous), _) => repo.commits_between(&previous.name, tag)?,
+        (None, first) => {
+            let mut commits = repo.commits_until(tag)?;
+            if let Some(at) = first
+                .as_ref()
+                .and_then(|first| commits.iter().position(|c| c.hash == first.hash))
+            {
+                commits.truncate(at + 1);
+            }
+            commits
+        }
+    };
+    info!(
+        tag,
+        previous = ?previous.as_ref().map(|t| &t.name),
+        commits = commits.len(),
+        "regenerating release"
+    );
+
+    let mut result = Regenerated {
+        tag: tag.to_string(),
+        version: version.clone(),
+        previous_tag: previous.as_ref().map(|t| t.name.clone()),
+        commits: commits.len(),
+        changelog_path: None,
+        changelog: None,
+        notes_path: None,
+        notes: None,
+        release_url: None,
+    };
+
+    if options.changelog {
+        let generator = ChangelogGenerator::new(config.changelog.clone()).with_tag(&tag_info);
+        let entry = generator.format(&generator.generate(&version, &commits));
+        let path = root.join(&config.changelog.file);
+        let history = ChangelogHistory::read(&path, &config.changelog)?;
+        write_atomic(&path, history.replace(&version, &entry))?;
+        result.changelog_path = Some(path);
+        result.changelog = Some(entry);
+    }
+
+    if options.notes || options.update_release {
+        let generator = ReleaseNotesGenerator::new(config.release_notes.clone());
+        let notes = generator.generate(&version, &commits);
+        if let Some(file) = &config.release_notes.file {
+            let path = root.join(file);
+            write_atomic(&path, generator.generate_formatted(&version, &commits))?;
+            result.notes_path = Some(path);
+        }
+
+        let mut github = GithubNotesOptions::new().with_collapse(
+            config
+                .release_notes
+                .github_collapse_over
+                .map_or(Collapse::Never, Collapse::Over),
+        );
+        let base = previous
+            .as_ref()
+            .map(|t| t.name.as_str())
+            .or(first.as_ref().map(|c| c.hash.as_str()));
+        if let (Some(repo_url), Some(base)) = (&config.changelog.repository_url, base) {
+            github = github.with_compare(repo_url, base, tag);
+        }
+        let body = generator.format_github(&notes, &github);
+        if options.update_release {
+            result.release_url = Some(host.update_release_notes(tag, &body)?);
+        }
+        result.notes = Some(body);
+    }
+
+    Ok(result)
+}
+
+/// The commit a first release of `package` starts from
+///
+/// For a package in a subdirectory, the first commit touching that
+/// directory; otherwise the repository's first commit. `None` when it can't
+/// be found, such as in a shallow clone.
+fn first_release_commit(
+    repo: &GitRepo,
+    config: &Config,
+    package: Option<&str>,
+) -> Option<CommitInfo> {
+    let path = package
+        .and_then(|name| config.package_config(Some(name)))
+        .map(|package| package.path.as_path())
+        .filter(|path| !matches!(path.to_str(), Some("" | ".")));
+    let first = match path {
+        Some(path) => repo.first_commit_for_path(path),
+        None => repo.first_commit().map(Some),
+    };
+    first.unwrap_or_else(|e| {
+        debug!(error = %e, "no first commit to anchor the first release");
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_core::config::PackageConfig;
+    use std::cell::RefCell;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    const CHANGELOG: &str = "# Changelog\n\n## [1.1.0] - 2024-05-01\n\n### Features\n\n- add serach (abc1234)\n\n## [1.0.0] - 2024-04-01\n\n### Features\n\n- initial release (def5678)\n";
+
+    /// Records release updates instead of calling GitHub
+    #[derive(Default)]
+    struct FakeHost {
+        updates: RefCell<Vec<(String, String)>>,
+    }
+
+    impl ReleaseHost for FakeHost {
+        fn update_release_notes(&self, tag: &str, body: &str) -> anyhow::Result<String> {
+            self.updates
+                .borrow_mut()
+                .push((tag.to_string(), body.to_string()));
+            Ok(format!("https://github.com/acme/demo/releases/tag/{}", tag))
+        }
+    }
+
+    fn git(root: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// `v1.0.0` and `v1.1.0` released, with unreleased work after them
+    fn released_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.name", "Test"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        std::fs::write(
+            root.join("package.json"),
+            "{\n  \"name\": \"demo\",\n  \"version\": \"1.1.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("CHANGELOG.md"), CHANGELOG).unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "feat: initial release"]);
+        git(root, &["tag", "v1.0.0"]);
+        for message in ["feat: add search", "fix: handle empty input"] {
+            git(root, &["commit", "-q", "--allow-empty", "-m", message]);
+        }
+        git(root, &["tag", "-a", "v1.1.0", "-m", "v1.1.0"]);
+        git(
+            root,
+            &[
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "feat: unreleased work",
+            ],
+        );
+        temp
+    }
+
+    #[test]
+    fn test_regenerate_changelog_for_existing_tag() {
+        let temp = released_repo();
+        let root = temp.path();
+        let head = git(root, &["rev-parse", "HEAD"]);
+        let tags = git(root, &["tag", "--list"]);
+        let host = FakeHost::default();
+
+        let result = regenerate_release(
+            root,
+            &Config::default(),
+            "v1.1.0",
+            None,
+            &RegenerateOptions::changelog_only(),
+            &host,
+        )
+        .unwrap();
+        assert_eq!(result.version, "1.1.0");
+        assert_eq!(result.previous_tag.as_deref(), Some("v1.0.0"));
+        assert_eq!(result.commits, 2);
+        assert!(result.notes.is_none());
+
+        let changelog = std::fs::read_to_string(root.join("CHANGELOG.md")).unwrap();
+        let (v110, v100) = changelog.split_once("## [1.0.0]").unwrap();
+        assert!(v110.contains("## [1.1.0]"));
+        assert!(v110.contains("add search"));
+        assert!(v110.contains("handle empty input"));
+        assert!(!changelog.contains("serach"));
+        assert!(!changelog.contains("unreleased work"));
+        assert_eq!(
+            v100,
+            " - 2024-04-01\n\n### Features\n\n- initial release (def5678)\n"
+        );
+
+        // No version, commit or tag changes
+        assert_eq!(git(root, &["rev-parse", "HEAD"]), head);
+        assert_eq!(git(root, &["tag", "--list"]), tags);
+        assert_eq!(git(root, &["status", "--porcelain"]), "M CHANGELOG.md");
+        assert!(host.updates.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_regenerate_notes_updates_release() {
+        let temp = released_repo();
+        let root = temp.path();
+        let host = FakeHost::default();
+
+        let options = RegenerateOptions::notes_only().with_release_update(true);
+        let result =
+            regenerate_release(root, &Config::default(), "v1.1.0", None, &options, &host).unwrap();
+
+        let notes = result.notes.unwrap();
+        assert!(notes.contains("add search"));
+        assert!(!notes.contains("unreleased work"));
+        assert_eq!(
+            result.release_url.as_deref(),
+            Some("https://github.com/acme/demo/releases/tag/v1.1.0")
+        );
+        assert_eq!(
+            *host.updates.borrow(),
+            [("v1.1.0".to_string(), notes.clone())]
+        );
+        // The changelog is left alone
+        assert_eq!(git(root, &["status", "--porcelain"]), "");
+        assert!(result.changelog.is_none());
+
+        // A tag that doesn't exist is an error, not a new release
+        let err = regenerate_release(root, &Config::default(), "v9.9.9", None, &options, &host)
+            .unwrap_err();
+        assert!(err.to_string().contains("does not exist"));
+    }
+
+    #[test]
+    fn test_regenerate_skips_other_packages_tags() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.name", "Test"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        std::fs::write(root.join("CHANGELOG.md"), "# Changelog\n").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "feat: initial release"]);
+        git(root, &["tag", "api@1.0.0"]);
+        git(
+            root,
+            &[
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "feat(api): add search",
+            ],
+        );
+        // Another package released in between, at a version in range
+        git(root, &["tag", "web@1.0.5"]);
+        git(
+            root,
+            &[
+                "commit",
+                "-q",
+                "--allow-empty",
+                "-m",
+                "fix(api): handle empty input",
+            ],
+        );
+        git(root, &["tag", "api@1.1.0"]);
+
+        let mut config = Config::default();
+        config.versioning.independent = true;
+        let result = regenerate_release(
+            root,
+            &config,
+            "api@1.1.0",
+            Some("api"),
+            &RegenerateOptions::changelog_only(),
+            &FakeHost::default(),
+        )
+        .unwrap();
+        assert_eq!(result.previous_tag.as_deref(), Some("api@1.0.0"));
+        assert_eq!(result.commits, 2);
+        let changelog = result.changelog.unwrap();
+        assert!(changelog.contains("add search"));
+        assert!(changelog.contains("handle empty input"));
+    }
+
+    #[test]
+    fn test_first_package_release_starts_at_package_directory() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "-b", "main"]);
+        git(root, &["config", "user.name", "Test"]);
+        git(root, &["config", "user.email", "test@example.com"]);
+        std::fs::write(root.join("CHANGELOG.md"), "# Changelog\n").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "feat(web): add landing page"]);
+        let core = root.join("packages/core");
+        std::fs::create_dir_all(&core).unwrap();
+        std::fs::write(core.join("lib.rs"), "").unwrap();
+        git(root, &["add", "-A"]);
+        git(root, &["commit", "-q", "-m", "feat(core): add core"]);
+        let first = git(root, &["rev-parse", "HEAD"]);
+        std::fs::write(core.join("lib.rs"), "// empty input\n").unwrap();
+        git(
+            root,
+            &["commit", "-q", "-am", "fix(core): handle empty input"],
+        );
+        git(root, &["tag", "core@1.0.0"]);
+
+        let mut config = Config::default();
+        config.versioning.independent = true;
+        config.changelog.repository_url = Some("https://github.com/acme/demo".to_string());
+        config.packages.push(PackageConfig {
+            name: "core".to_string(),
+            path: "packages/core".into(),
+            package_type: "cargo".to_string(),
+            publish: true,
+            registry: None,
+            tag_format: None,
+            version_files: Vec::new(),
+            version_mirrors: Vec::new(),
+            version_source: None,
+            release_assets: None,
+        });
+        let result = regenerate_release(
+            root,
+            &config,
+            "core@1.0.0",
+            Some("core"),
+            &RegenerateOptions::notes_only(),
+            &FakeHost::default(),
+        )
+        .unwrap();
+        assert_eq!(result.previous_tag, None);
+        assert_eq!(result.commits, 2);
+        let notes = result.notes.unwrap();
+        assert!(!notes.contains("landing page"));
+        assert!(notes.contains(&format!(
+            "https://github.com/acme/demo/compare/{}...core@1.0.0",
+            first
+        )));
+    }
+}
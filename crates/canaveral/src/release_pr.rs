@@ -0,0 +1,521 @@
+//! Release PR preparation
+//!
+//! [`prepare_release_pr`] keeps a standing "release PR" up to date: it
+//! computes the pending version and changelog from the current branch,
+//! writes them on a dedicated release branch and records them as a single
+//! commit. The branch is rebuilt from the base branch on every run, and the
+//! release date is taken from the newest commit, so running it again on the
+//! same history reproduces the same files and leaves the branch untouched.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use canaveral_adapters::{AdapterRegistry, MirroredAdapter};
+use canaveral_changelog::{ChangelogGenerator, ChangelogHistory};
+use canaveral_core::config::Config;
+use canaveral_core::fs::{prepend_atomic, write_atomic};
+use canaveral_core::workflow::release_commit_message;
+use canaveral_git::{push_branch_with_retry, GitRepo, PushOptions};
+use serde::Serialize;
+use tracing::{debug, info};
+
+use crate::version::compute_next_version;
+
+/// Options for [`prepare_release_pr`]
+#[derive(Debug, Clone)]
+pub struct ReleasePrOptions {
+    /// Release branch; defaults to `pr.release_branch` from the config
+    pub branch: Option<String>,
+    /// Force-push the release branch to this remote
+    pub push_to: Option<String>,
+}
+
+impl ReleasePrOptions {
+    /// Prepare the release branch locally, without pushing
+    pub fn new() -> Self {
+        Self {
+            branch: None,
+            push_to: None,
+        }
+    }
+
+    /// Use `branch` instead of the configured release branch
+    pub fn with_branch(mut self, branch: impl Into<String>) -> Self {
+        self.branch = Some(branch.into());
+        self
+    }
+
+    /// Push the release branch to `remote` once prepared
+    pub fn with_push(mut self, remote: impl Into<String>) -> Self {
+        self.push_to = Some(remote.into());
+        self
+    }
+}
+
+impl Default for ReleasePrOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A prepared release branch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ReleasePr {
+    /// Version of the latest release
+    pub current_version: String,
+    /// Version the release PR releases
+    pub version: String,
+    /// Branch the release commit is on
+    pub branch: String,
+    /// Branch the PR merges into
+    pub base: String,
+    /// Hash of the release commit
+    pub commit: String,
+    /// Files the release commit changes, relative to the repository root
+    pub files: Vec<String>,
+    /// Whether the branch changed; `false` when it already held this release
+    pub updated: bool,
+    /// PR title
+    pub title: String,
+    /// PR body: the pending changelog entry
+    pub body: String,
+}
+
+/// Opens or updates the pull request for a release branch
+pub trait PullRequestHost {
+    /// Open a PR from `release.branch` into `release.base`, or update the
+    /// title and body of the one already open. Returns the PR URL.
+    fn upsert_pull_request(&self, release: &ReleasePr) -> anyhow::Result<String>;
+}
+
+/// [`PullRequestHost`] backed by the GitHub CLI (`gh`)
+#[derive(Debug, Clone)]
+pub struct GitHubCli {
+    dir: PathBuf,
+}
+
+impl GitHubCli {
+    /// Run `gh` in the repository at `dir`
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub(crate) fn gh(&self, args: &[&str]) -> anyhow::Result<String> {
+        let output = Command::new("gh")
+            .args(args)
+            .current_dir(&self.dir)
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "gh {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+impl PullRequestHost for GitHubCli {
+    fn upsert_pull_request(&self, release: &ReleasePr) -> anyhow::Result<String> {
+        let existing = self.gh(&[
+            "pr",
+            "list",
+            "--head",
+            &release.branch,
+            "--base",
+            &release.base,
+            "--state",
+            "open",
+            "--json",
+            "url",
+            "--jq",
+            ".[0].url // empty",
+        ])?;
+
+        if existing.is_empty() {
+            let url = self.gh(&[
+                "pr",
+                "create",
+                "--head",
+                &release.branch,
+                "--base",
+                &release.base,
+                "--title",
+                &release.title,
+                "--body",
+                &release.body,
+            ])?;
+            info!(url = %url, "opened release PR");
+            Ok(url)
+        } else {
+            self.gh(&[
+                "pr",
+                "edit",
+                &existing,
+                "--title",
+                &release.title,
+                "--body",
+                &release.body,
+            ])?;
+            info!(url = %existing, "updated release PR");
+            Ok(existing)
+        }
+    }
+}
+
+/// Prepare the release branch for the pending release of the repository
+/// at `root`
+///
+/// The current branch is the base. The next version is written through the
+/// detected package adapter and the changelog entry is prepended to the
+/// changelog; both are committed on top of the base as the release branch's
+/// one release commit, and the working tree is restored. If the release
+/// branch already held exactly those changes it is left at its existing
+/// commit, so nothing is pushed twice. When pushing, that branch is the
+/// remote's, fetched first, so fresh clones in CI don't push again.
+///
+/// Returns `None` when no commit since the latest release requires one.
+/// Fails if the working tree has uncommitted changes.
+pub fn prepare_release_pr(
+    root: &Path,
+    config: &Config,
+    options: &ReleasePrOptions,
+) -> anyhow::Result<Option<ReleasePr>> {
+    let repo = GitRepo::discover(root)?;
+    let root = repo
+        .workdir()
+        .ok_or_else(|| anyhow::anyhow!("cannot prepare a release PR in a bare repository"))?
+        .to_path_buf();
+    if !repo.is_clean()? {
+        anyhow::bail!("working tree has uncommitted changes; commit or stash them first");
+    }
+    let base = repo
+        .current_branch()?
+        .ok_or_else(|| anyhow::anyhow!("HEAD is detached; check out the base branch first"))?;
+    let branch = options
+        .branch
+        .clone()
+        .unwrap_or_else(|| config.pr.release_branch.clone());
+    if branch == base {
+        anyhow::bail!("the release branch '{}' is the current branch", branch);
+    }
+
+    let Some(next) = compute_next_version(&root, config)? else {
+        info!(base = %base, "no pending release");
+        return Ok(None);
+    };
+
+    // Entry generated from the base branch, dated by its newest commit
+    let latest_tag = repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
+    let commits = match &latest_tag {
+        Some(tag) => repo.commits_since_tag(&tag.name)?,
+        None => repo.all_commits()?,
+    };
+    let mut generator = ChangelogGenerator::new(config.changelog.clone());
+    if let Some(newest) = commits.first() {
+        generator = generator.with_release_date(newest.timestamp);
+    }
+    // Commits an earlier release already lists are left out of the entry
+    let history = if config.changelog.enabled && config.changelog.preserve_existing {
+        Some(ChangelogHistory::read(
+            &root.join(&config.changelog.file),
+            &config.changelog,
+        )?)
+    } else {
+        None
+    };
+    let entry = match &history {
+        Some(history) => generator.generate_after(history, &next.next, &commits),
+        None => generator.generate(&next.next, &commits),
+    };
+    let entry = generator.format(&entry);
+
+    // The release branch another run already pushed, or the local one
+    let previous = match &options.push_to {
+        Some(remote) => {
+            let tracking = format!("refs/remotes/{}/{}", remote, branch);
+            if let Err(e) = repo.fetch(remote, &[&format!("+refs/heads/{}:{}", branch, tracking)]) {
+                debug!(remote = %remote, error = %e, "release branch not fetched");
+            }
+            repo.resolve_commit(&tracking)?
+        }
+        None => repo.resolve_commit(&format!("refs/heads/{}", branch))?,
+    };
+    let committed = commit_release(
+        &repo,
+        &branch,
+        &root,
+        config,
+        &next.next,
+        &entry,
+        history.as_ref(),
+    );
+    // The tree was clean before, so only the release files are discarded
+    repo.discard_changes()?;
+    let mut commit = committed?;
+
+    let updated = match &previous {
+        Some(previous) if repo.same_tree(previous, &commit)? => {
+            debug!(branch = %branch, "release branch already up to date");
+            repo.set_branch(&branch, previous)?;
+            commit = previous.clone();
+            false
+        }
+        _ => true,
+    };
+
+    if let Some(remote) = options.push_to.as_ref().filter(|_| updated) {
+        push_branch_with_retry(
+            &root,
+            remote,
+            &branch,
+            &PushOptions::new().with_force_with_lease(true),
+        )?;
+        info!(remote = %remote, branch = %branch, "pushed release branch");
+    }
+
+    let files = repo.changed_files(&base, &commit)?;
+    info!(version = %next.next, branch = %branch, updated, "prepared release PR");
+    Ok(Some(ReleasePr {
+        current_version: next.current,
+        title: release_commit_message(
+            &config.git.commit_message,
+            &package_name(&root, config),
+            &next.next,
+        )
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string(),
+        version: next.next,
+        branch,
+        base,
+        commit,
+        files,
+        updated,
+        body: entry,
+    }))
+}
+
+/// Write the version and changelog in the working tree and commit them to
+/// the release branch, returning the commit hash
+///
+/// With `history`, the entry is inserted into the existing changelog rather
+/// than prepended.
+fn commit_release(
+    repo: &GitRepo,
+    branch: &str,
+    root: &Path,
+    config: &Config,
+    version: &str,
+    entry: &str,
+    history: Option<&ChangelogHistory>,
+) -> anyhow::Result<String> {
+    if let Some(adapter) = AdapterRegistry::from_config(config)?.detect(root) {
+        let mirrors = config
+            .package_config(None)
+            .map(|package| package.version_mirrors.as_slice())
+            .unwrap_or_default();
+        MirroredAdapter::from_config(adapter, mirrors)?.set_version(root, version)?;
+    }
+
+    if config.changelog.enabled {
+        let path = root.join(&config.changelog.file);
+        match history {
+            Some(history) => write_atomic(&path, history.insert(version, entry))?,
+            None => prepend_atomic(&path, entry)?,
+        }
+    }
+
+    let message = release_commit_message(
+        &config.git.commit_message,
+        &package_name(root, config),
+        version,
+    );
+    Ok(repo.commit_paths_to_branch(branch, &repo.modified_files()?, &message)?)
+}
+
+/// Name of the released package: the configured project name, else the
+/// name in the detected manifest, else the directory name
+fn package_name(root: &Path, config: &Config) -> String {
+    config
+        .name
+        .clone()
+        .or_else(|| {
+            AdapterRegistry::new()
+                .detect(root)
+                .and_then(|adapter| adapter.get_info(root).ok())
+                .map(|info| info.name)
+        })
+        .or_else(|| {
+            root.file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .unwrap_or_else(|| "package".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(root: &Path, args: &[&str]) -> anyhow::Result<()> {
+        git_output(root, args).map(|_| ())
+    }
+
+    fn git_output(root: &Path, args: &[&str]) -> anyhow::Result<String> {
+        let output = Command::new("git").args(args).current_dir(root).output()?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// npm package released as `v1.0.0`, followed by `messages` as commits
+    fn repo_with_history(messages: &[&str]) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        git(root, &["init", "-q", "-b", "main"]).unwrap();
+        git(root, &["config", "user.name", "Test"]).unwrap();
+        git(root, &["config", "user.email", "test@example.com"]).unwrap();
+        std::fs::write(
+            root.join("package.json"),
+            "{\n  \"name\": \"demo\",\n  \"version\": \"1.0.0\"\n}\n",
+        )
+        .unwrap();
+        std::fs::write(root.join("CHANGELOG.md"), "## 1.0.0\n\n- Initial release\n").unwrap();
+        git(root, &["add", "-A"]).unwrap();
+        git(root, &["commit", "-q", "-m", "feat: initial release"]).unwrap();
+        git(root, &["tag", "v1.0.0"]).unwrap();
+        for message in messages {
+            git(root, &["commit", "-q", "--allow-empty", "-m", message]).unwrap();
+        }
+        temp
+    }
+
+    fn file_at(root: &Path, rev: &str, path: &str) -> String {
+        git_output(root, &["show", &format!("{}:{}", rev, path)]).unwrap()
+    }
+
+    #[test]
+    fn test_prepare_is_idempotent() {
+        let temp = repo_with_history(&["feat: add search", "fix: handle empty input"]);
+        let root = temp.path();
+        let config = Config::default();
+
+        let first = prepare_release_pr(root, &config, &ReleasePrOptions::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.current_version, "1.0.0");
+        assert_eq!(first.version, "1.1.0");
+        assert_eq!(first.base, "main");
+        assert_eq!(first.branch, "canaveral/release");
+        assert!(first.updated);
+        assert_eq!(first.files, ["CHANGELOG.md", "package.json"]);
+        assert!(first.body.contains("add search"));
+
+        // Back on the base branch, which is untouched
+        assert_eq!(
+            git_output(root, &["branch", "--show-current"]).unwrap(),
+            "main"
+        );
+        assert!(file_at(root, "main", "package.json").contains("1.0.0"));
+        let manifest = file_at(root, &first.branch, "package.json");
+        let changelog = file_at(root, &first.branch, "CHANGELOG.md");
+        assert!(manifest.contains("\"version\": \"1.1.0\""));
+        assert!(changelog.ends_with("## 1.0.0\n\n- Initial release"));
+
+        let second = prepare_release_pr(root, &config, &ReleasePrOptions::new())
+            .unwrap()
+            .unwrap();
+        assert!(!second.updated);
+        assert_eq!(second.commit, first.commit);
+        assert_eq!(file_at(root, &second.branch, "package.json"), manifest);
+        assert_eq!(file_at(root, &second.branch, "CHANGELOG.md"), changelog);
+    }
+
+    #[test]
+    fn test_fresh_clone_reuses_pushed_branch() {
+        let temp = repo_with_history(&["feat: add search"]);
+        let root = temp.path();
+        let remote = TempDir::new().unwrap();
+        git(remote.path(), &["init", "-q", "--bare"]).unwrap();
+        let url = remote.path().display().to_string();
+        git(root, &["remote", "add", "origin", &url]).unwrap();
+        git(root, &["push", "-q", "origin", "main", "--tags"]).unwrap();
+        let config = Config::default();
+        let options = ReleasePrOptions::new().with_push("origin");
+
+        let first = prepare_release_pr(root, &config, &options)
+            .unwrap()
+            .unwrap();
+        assert!(first.updated);
+        assert_eq!(
+            git_output(remote.path(), &["rev-parse", &first.branch]).unwrap(),
+            first.commit
+        );
+
+        // A CI checkout has no local release branch
+        let clone = TempDir::new().unwrap();
+        git(clone.path(), &["clone", "-q", "-b", "main", &url, "."]).unwrap();
+        git(clone.path(), &["config", "user.name", "Test"]).unwrap();
+        git(clone.path(), &["config", "user.email", "test@example.com"]).unwrap();
+        let second = prepare_release_pr(clone.path(), &config, &options)
+            .unwrap()
+            .unwrap();
+        assert!(!second.updated);
+        assert_eq!(second.commit, first.commit);
+        assert_eq!(second.files, ["CHANGELOG.md", "package.json"]);
+    }
+
+    #[test]
+    fn test_new_commits_update_branch() {
+        let temp = repo_with_history(&["fix: handle empty input"]);
+        let root = temp.path();
+        let config = Config::default();
+
+        let first = prepare_release_pr(root, &config, &ReleasePrOptions::new())
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.version, "1.0.1");
+
+        git(
+            root,
+            &["commit", "-q", "--allow-empty", "-m", "feat: add export"],
+        )
+        .unwrap();
+        let second = prepare_release_pr(root, &config, &ReleasePrOptions::new())
+            .unwrap()
+            .unwrap();
+        assert!(second.updated);
+        assert_eq!(second.version, "1.1.0");
+        assert_ne!(second.commit, first.commit);
+        // Rebuilt from the base: one release commit, one changelog entry
+        let log = git_output(root, &["log", "--format=%s", "main..canaveral/release"]).unwrap();
+        assert_eq!(log, "chore(release): 1.1.0");
+        let changelog = file_at(root, &second.branch, "CHANGELOG.md");
+        assert!(!changelog.contains("1.0.1"));
+    }
+
+    #[test]
+    fn test_no_pending_release() {
+        let temp = repo_with_history(&["docs: fix typo"]);
+        let result =
+            prepare_release_pr(temp.path(), &Config::default(), &ReleasePrOptions::new()).unwrap();
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_dirty_tree_is_rejected() {
+        let temp = repo_with_history(&["feat: add search"]);
+        std::fs::write(temp.path().join("notes.txt"), "wip").unwrap();
+        let err = prepare_release_pr(temp.path(), &Config::default(), &ReleasePrOptions::new())
+            .unwrap_err();
+        assert!(err.to_string().contains("uncommitted changes"));
+    }
+}
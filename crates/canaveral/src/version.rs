@@ -0,0 +1,310 @@
+//! Next-version computation for embedding
+//!
+//! [`compute_next_version`] answers "what would the next release be" from
+//! the commit history alone: it reads tags and commits but writes no files
+//! and runs no git operations (no fetch, unshallow, commit or tag).
+
+use std::path::Path;
+
+use canaveral_changelog::{
+    CommitParser, ConventionalParser, ParsedCommit, ParserConfig, ReleaseAs,
+};
+use canaveral_core::config::Config;
+use canaveral_core::types::{ReleaseCommit, ReleaseType};
+use canaveral_core::workflow::check_version_increase;
+use canaveral_git::{CommitInfo, GitRepo};
+use canaveral_strategies::{BumpType, SemVerStrategy, VersionStrategy};
+use serde::Serialize;
+use tracing::debug;
+
+/// Version used when the repository has no version tag yet
+pub const INITIAL_VERSION: &str = "0.0.0";
+
+/// Bump inferred from a list of commits
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BumpInference {
+    /// Highest bump required by any commit
+    pub bump: BumpType,
+    /// The commit that requires `bump`, e.g. `feature in a1b2c3d: feat: add search`
+    pub reason: Option<String>,
+    /// Number of commits requiring a bump
+    pub releasable_commits: usize,
+    /// Version forced by the newest `Release-As` footer
+    pub release_as: Option<ReleaseAsVersion>,
+}
+
+/// A version forced by a `Release-As: x.y.z` footer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseAsVersion {
+    /// The forced version
+    pub version: String,
+    /// The commit carrying the footer, e.g. `Release-As in a1b2c3d: chore: release 2.0`
+    pub reason: String,
+}
+
+impl BumpInference {
+    /// The forced version, checked to be greater than `current`
+    pub fn forced_version(&self, current: &str) -> anyhow::Result<Option<&ReleaseAsVersion>> {
+        let Some(release_as) = &self.release_as else {
+            return Ok(None);
+        };
+        check_version_increase(current, &release_as.version)
+            .map_err(|e| anyhow::anyhow!("{} ({})", e, release_as.reason))?;
+        Ok(Some(release_as))
+    }
+}
+
+/// The outcome of [`compute_next_version`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NextVersion {
+    /// Version of the latest version tag, or [`INITIAL_VERSION`]
+    pub current: String,
+    /// Bump applied to `current`
+    pub bump: BumpType,
+    /// Why `bump` was chosen
+    pub reason: String,
+    /// Version the next release would get
+    pub next: String,
+    /// Number of commits since the latest tag requiring a bump
+    pub releasable_commits: usize,
+}
+
+/// Parse `commits` as conventional commits, dropping those that don't parse
+pub fn parse_commits(commits: &[CommitInfo], config: &Config) -> Vec<ParsedCommit> {
+    let parser = ConventionalParser::with_config(ParserConfig::from_changelog(&config.changelog));
+    commits.iter().filter_map(|c| parser.parse(c)).collect()
+}
+
+/// Infer the version bump from conventional commits
+///
+/// Breaking changes require a major bump, `feat` a minor bump and
+/// `fix`/`perf` a patch bump. Commits that don't parse are ignored, as are
+/// commits with a `Release-As: skip` footer. The newest `Release-As: x.y.z`
+/// footer is kept as [`BumpInference::release_as`].
+pub fn infer_bump(commits: &[CommitInfo], config: &Config) -> BumpInference {
+    let parser = ConventionalParser::with_config(ParserConfig::from_changelog(&config.changelog));
+    let mut inference = BumpInference {
+        bump: BumpType::None,
+        reason: None,
+        releasable_commits: 0,
+        release_as: None,
+    };
+
+    // Commits are listed newest first
+    for commit in commits {
+        let Some(parsed) = parser.parse(commit) else {
+            continue;
+        };
+        match parsed.release_as() {
+            Some(ReleaseAs::Version(version)) if inference.release_as.is_none() => {
+                inference.release_as = Some(ReleaseAsVersion {
+                    version,
+                    reason: format!("Release-As in {}: {}", commit.short_hash, commit.message),
+                });
+            }
+            Some(ReleaseAs::Skip) => {
+                debug!(commit = %commit.short_hash, "skipping commit marked Release-As: skip");
+            }
+            _ => {}
+        }
+        let (bump, kind) = match parsed.release_type() {
+            Some(ReleaseType::Major) => (BumpType::Major, "breaking change"),
+            Some(ReleaseType::Minor) => (BumpType::Minor, "feature"),
+            Some(ReleaseType::Patch) => (BumpType::Patch, "fix"),
+            _ => continue,
+        };
+
+        inference.releasable_commits += 1;
+        if inference.reason.is_none() || bump.max(inference.bump) != inference.bump {
+            inference.bump = bump;
+            inference.reason = Some(format!(
+                "{} in {}: {}",
+                kind, commit.short_hash, commit.message
+            ));
+        }
+    }
+
+    inference
+}
+
+/// Compute the next version of the repository at `repo`
+///
+/// Reads the latest version tag and the commits since it, infers the bump
+/// from conventional commits and applies it with the semver strategy. A
+/// `Release-As: x.y.z` footer replaces the computed version, and errors
+/// when it isn't greater than the current version. Returns `None` when no
+/// commit since the tag requires a release.
+pub fn compute_next_version(repo: &Path, config: &Config) -> anyhow::Result<Option<NextVersion>> {
+    let repo = GitRepo::discover(repo)?;
+
+    let latest_tag = repo.find_latest_tag_filtered(None, config.versioning.include_prereleases)?;
+    let current = latest_tag
+        .as_ref()
+        .and_then(|t| t.version.clone())
+        .unwrap_or_else(|| INITIAL_VERSION.to_string());
+    let commits = match &latest_tag {
+        Some(tag) => repo.commits_since_tag(&tag.name)?,
+        None => repo.all_commits()?,
+    };
+
+    let inference = infer_bump(&commits, config);
+    if let Some(forced) = inference.forced_version(&current)? {
+        debug!(current = %current, next = %forced.version, "version forced by Release-As");
+        return Ok(Some(NextVersion {
+            current,
+            bump: inference.bump,
+            reason: forced.reason.clone(),
+            next: forced.version.clone(),
+            releasable_commits: inference.releasable_commits,
+        }));
+    }
+    let Some(reason) = inference.reason else {
+        debug!(current = %current, commits = commits.len(), "no releasable commits");
+        return Ok(None);
+    };
+
+    let strategy = SemVerStrategy::new();
+    let next = strategy.format(&strategy.bump(&strategy.parse(&current)?, inference.bump)?);
+    debug!(current = %current, next = %next, bump = %inference.bump, "computed next version");
+
+    Ok(Some(NextVersion {
+        current,
+        bump: inference.bump,
+        reason,
+        next,
+        releasable_commits: inference.releasable_commits,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{Repository, Signature};
+    use tempfile::TempDir;
+
+    /// Repository tagged `v1.2.0`, followed by `messages` as commits
+    fn repo_with_history(messages: &[&str]) -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let repo = Repository::init(temp.path()).unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+
+        let commit = |message: &str| {
+            let tree_id = repo.index().unwrap().write_tree().unwrap();
+            let tree = repo.find_tree(tree_id).unwrap();
+            let parent = repo.head().ok().map(|h| h.peel_to_commit().unwrap());
+            let parents: Vec<_> = parent.iter().collect();
+            repo.commit(Some("HEAD"), &sig, &sig, message, &tree, &parents)
+                .unwrap()
+        };
+
+        let released = commit("feat: initial release");
+        repo.tag_lightweight(
+            "v1.2.0",
+            repo.find_commit(released).unwrap().as_object(),
+            false,
+        )
+        .unwrap();
+        for message in messages {
+            commit(message);
+        }
+        temp
+    }
+
+    #[test]
+    fn test_minor_bump_from_feature() {
+        let temp = repo_with_history(&[
+            "fix: handle empty input",
+            "feat(api): add search endpoint",
+            "docs: describe search",
+        ]);
+
+        let next = compute_next_version(temp.path(), &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.current, "1.2.0");
+        assert_eq!(next.next, "1.3.0");
+        assert_eq!(next.bump, BumpType::Minor);
+        assert_eq!(next.releasable_commits, 2);
+        assert!(next.reason.starts_with("feature in "));
+        assert!(next.reason.ends_with("feat(api): add search endpoint"));
+    }
+
+    #[test]
+    fn test_release_as_overrides_version() {
+        let temp = repo_with_history(&[
+            "feat: add export",
+            "chore: prepare major release\n\nRelease-As: 2.0.0",
+            "fix: handle empty input",
+        ]);
+
+        let next = compute_next_version(temp.path(), &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.current, "1.2.0");
+        assert_eq!(next.next, "2.0.0");
+        assert!(next.reason.starts_with("Release-As in "));
+    }
+
+    #[test]
+    fn test_release_as_backward_is_error() {
+        let temp = repo_with_history(&["fix: handle empty input\n\nRelease-As: 1.1.0"]);
+        let err = compute_next_version(temp.path(), &Config::default()).unwrap_err();
+        assert!(err.to_string().contains("not greater than"));
+    }
+
+    #[test]
+    fn test_release_as_skip() {
+        let temp = repo_with_history(&[
+            "feat: experimental search\n\nRelease-As: skip",
+            "fix: handle empty input",
+        ]);
+        let next = compute_next_version(temp.path(), &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.next, "1.2.1");
+        assert_eq!(next.releasable_commits, 1);
+
+        let temp = repo_with_history(&["feat: experimental search\n\nRelease-As: skip"]);
+        let next = compute_next_version(temp.path(), &Config::default()).unwrap();
+        assert_eq!(next, None);
+    }
+
+    #[test]
+    fn test_exclude_prereleases_from_current_version() {
+        let temp = repo_with_history(&["feat: add export"]);
+        let repo = Repository::open(temp.path()).unwrap();
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.tag_lightweight("v1.3.0-rc.1", head.as_object(), false)
+            .unwrap();
+        let sig = Signature::now("Test", "test@example.com").unwrap();
+        let tree = head.tree().unwrap();
+        repo.commit(
+            Some("HEAD"),
+            &sig,
+            &sig,
+            "fix: handle empty input",
+            &tree,
+            &[&head],
+        )
+        .unwrap();
+
+        let next = compute_next_version(temp.path(), &Config::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.current, "1.3.0-rc.1");
+
+        let mut config = Config::default();
+        config.versioning.include_prereleases = false;
+        let next = compute_next_version(temp.path(), &config).unwrap().unwrap();
+        assert_eq!(next.current, "1.2.0");
+        assert_eq!(next.next, "1.3.0");
+        assert_eq!(next.releasable_commits, 2);
+    }
+
+    #[test]
+    fn test_no_releasable_commits() {
+        let temp = repo_with_history(&["docs: fix typo", "chore: update tooling"]);
+        let next = compute_next_version(temp.path(), &Config::default()).unwrap();
+        assert_eq!(next, None);
+    }
+}
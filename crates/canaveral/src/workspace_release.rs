@@ -0,0 +1,410 @@
+//! Releasing every changed package of a workspace
+//!
+//! [`plan_workspace_release`] finds the packages with releasable commits
+//! since their last tag and computes their bumps with the configured
+//! versioning strategy. [`write_workspace_release`] writes the bumps several
+//! packages at a time along with each package's changelog entry, and
+//! [`commit_workspace_release`] records all of it in one release commit and
+//! tags the released packages.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use canaveral_adapters::AdapterRegistry;
+use canaveral_changelog::ChangelogGenerator;
+use canaveral_core::config::Config;
+use canaveral_core::fs::prepend_atomic;
+use canaveral_core::monorepo::{
+    BumpResult, ChangeReason, ChangedPackage, DependencyGraph, DiscoveredPackage, PackageFilter,
+    VersionBump, VersioningStrategy, DEFAULT_BUMP_CONCURRENCY,
+};
+use canaveral_core::types::ReleaseType;
+use canaveral_core::workflow::{format_tag, tag_pattern};
+use canaveral_git::{CommitInfo, GitRepo};
+use canaveral_strategies::BumpType;
+use tracing::{debug, info};
+
+use crate::bump::{apply_bumps, commit_bumps, ensure_bumped};
+use crate::version::infer_bump;
+
+/// Options for a workspace release
+#[derive(Debug, Clone)]
+pub struct WorkspaceReleaseOptions {
+    /// Release type for every changed package, instead of inferring it
+    /// from conventional commits
+    pub release_type: Option<ReleaseType>,
+    /// Maximum number of packages bumped at once
+    pub concurrency: usize,
+    /// Prepend a changelog entry to each released package's changelog
+    pub changelog: bool,
+    /// Packages to release, out of those with releasable commits
+    pub filter: PackageFilter,
+}
+
+impl Default for WorkspaceReleaseOptions {
+    fn default() -> Self {
+        Self {
+            release_type: None,
+            concurrency: DEFAULT_BUMP_CONCURRENCY,
+            changelog: true,
+            filter: PackageFilter::default(),
+        }
+    }
+}
+
+impl WorkspaceReleaseOptions {
+    /// Create default options
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use `release_type` for every changed package
+    pub fn with_release_type(mut self, release_type: Option<ReleaseType>) -> Self {
+        self.release_type = release_type;
+        self
+    }
+
+    /// Set the maximum number of packages bumped at once
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency;
+        self
+    }
+
+    /// Set whether changelogs are written
+    pub fn with_changelog(mut self, changelog: bool) -> Self {
+        self.changelog = changelog;
+        self
+    }
+
+    /// Release only the packages selected by `filter`
+    pub fn with_filter(mut self, filter: PackageFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+}
+
+/// The packages a workspace release bumps, and why
+#[derive(Debug, Clone)]
+pub struct WorkspaceReleasePlan {
+    /// Every package in the workspace
+    pub packages: Vec<DiscoveredPackage>,
+    /// Version bumps, in the versioning strategy's order
+    pub bumps: Vec<VersionBump>,
+    /// Commits since its last tag, by changed package name
+    pub commits: HashMap<String, Vec<CommitInfo>>,
+    /// Number of distinct commits examined across all packages
+    pub examined_commits: usize,
+}
+
+impl WorkspaceReleasePlan {
+    /// Whether no package needs a release
+    pub fn is_empty(&self) -> bool {
+        self.bumps.is_empty()
+    }
+
+    /// Tags the release creates, one per package or shared version
+    pub fn tags(&self, config: &Config) -> Vec<String> {
+        let mut tags: Vec<String> = Vec::new();
+        for bump in &self.bumps {
+            let tag = format_tag(config, &bump.new_version, Some(&bump.package));
+            if !tags.contains(&tag) {
+                tags.push(tag);
+            }
+        }
+        tags
+    }
+
+    /// The bumped packages at their new versions, for publishing
+    pub fn released_packages(&self) -> Vec<DiscoveredPackage> {
+        self.packages
+            .iter()
+            .filter_map(|package| {
+                let bump = self.bumps.iter().find(|b| b.package == package.name)?;
+                let mut package = package.clone();
+                package.version = bump.new_version.clone();
+                Some(package)
+            })
+            .collect()
+    }
+}
+
+/// Plan a release of every package under `root` with releasable commits
+///
+/// Each package's commits are those since its last tag that touch its
+/// directory. Without a release type in `options`, a package is released
+/// when its commits call for a bump, as [`infer_bump`] decides. The
+/// options' filter then narrows the changed packages, and may force the
+/// release of included packages without changes.
+pub fn plan_workspace_release(
+    repo: &GitRepo,
+    root: &Path,
+    config: &Config,
+    options: &WorkspaceReleaseOptions,
+) -> anyhow::Result<WorkspaceReleasePlan> {
+    options.filter.validate()?;
+    let packages = AdapterRegistry::new().discover_packages(root)?;
+    let graph = DependencyGraph::build(&packages)?;
+
+    let mut changes = Vec::new();
+    let mut release_types = HashMap::new();
+    let mut commits = HashMap::new();
+    let mut examined = HashSet::new();
+    for package in &packages {
+        let last_tag = repo.find_latest_tag_filtered(
+            Some(&tag_pattern(config, Some(&package.name))),
+            config.versioning.include_prereleases,
+        )?;
+        let path = package
+            .path
+            .strip_prefix(repo.path())
+            .unwrap_or(&package.path);
+        let package_commits =
+            repo.commits_for_path(last_tag.as_ref().map(|t| t.name.as_str()), path)?;
+        examined.extend(package_commits.iter().map(|c| c.hash.clone()));
+
+        let release_type = match options.release_type {
+            Some(release_type) if !package_commits.is_empty() => Some(release_type),
+            Some(_) => None,
+            None => release_type_of(infer_bump(&package_commits, config).bump),
+        };
+        let Some(release_type) = release_type else {
+            debug!(package = %package.name, commits = package_commits.len(), "nothing to release");
+            continue;
+        };
+        changes.push(ChangedPackage {
+            name: package.name.clone(),
+            path: package.path.clone(),
+            changed_files: Vec::new(),
+            change_reason: ChangeReason::ConventionalCommit,
+            commits: package_commits.iter().map(|c| c.hash.clone()).collect(),
+        });
+        release_types.insert(package.name.clone(), release_type);
+        commits.insert(package.name.clone(), package_commits);
+    }
+    let changes = options.filter.select_changed(&packages, changes, root);
+    if let Some(release_type) = options.release_type {
+        for change in &changes {
+            release_types
+                .entry(change.name.clone())
+                .or_insert(release_type);
+        }
+    }
+
+    let bumps = VersioningStrategy::from_config(&config.versioning).calculate_bumps_by_package(
+        &packages,
+        &changes,
+        &release_types,
+        Some(&graph),
+    )?;
+    info!(
+        packages = packages.len(),
+        bumps = bumps.len(),
+        "planned workspace release"
+    );
+    Ok(WorkspaceReleasePlan {
+        packages,
+        bumps,
+        commits,
+        examined_commits: examined.len(),
+    })
+}
+
+/// Write the plan's version bumps and changelog entries
+///
+/// Fails if any package failed to bump; changelogs are only written once
+/// every manifest was.
+pub fn write_workspace_release(
+    config: &Config,
+    plan: &WorkspaceReleasePlan,
+    options: &WorkspaceReleaseOptions,
+) -> anyhow::Result<BumpResult> {
+    let result = apply_bumps(config, &plan.packages, &plan.bumps, options.concurrency);
+    ensure_bumped(&result)?;
+
+    if options.changelog && config.changelog.enabled {
+        let generator = ChangelogGenerator::new(config.changelog.clone());
+        for bump in &plan.bumps {
+            let package = plan.packages.iter().find(|p| p.name == bump.package);
+            let (Some(package), Some(commits)) = (package, plan.commits.get(&bump.package)) else {
+                continue;
+            };
+            let entry = generator.format(&generator.generate(&bump.new_version, commits));
+            prepend_atomic(&package.path.join(&config.changelog.file), &entry)?;
+        }
+    }
+    Ok(result)
+}
+
+/// Commit the written release and create its tags
+///
+/// `baseline` is the repository's modified files from before the release
+/// was written (see [`commit_bumps`]). Returns the created tags.
+pub fn commit_workspace_release(
+    repo: &GitRepo,
+    config: &Config,
+    plan: &WorkspaceReleasePlan,
+    result: &BumpResult,
+    baseline: &[String],
+) -> anyhow::Result<Vec<String>> {
+    commit_bumps(repo, result, baseline, &config.git.commit_message)?;
+    let tags = plan.tags(config);
+    for tag in &tags {
+        repo.create_tag(tag, Some(&format!("Release {}", tag)))?;
+    }
+    Ok(tags)
+}
+
+/// The release type for a bump inferred from commits
+fn release_type_of(bump: BumpType) -> Option<ReleaseType> {
+    match bump {
+        BumpType::Major => Some(ReleaseType::Major),
+        BumpType::Minor => Some(ReleaseType::Minor),
+        BumpType::Patch => Some(ReleaseType::Patch),
+        BumpType::Prerelease => Some(ReleaseType::Prerelease),
+        BumpType::None => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use canaveral_core::config::FixedGroupConfig;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    fn commit_all(repo: &GitRepo, message: &str) {
+        repo.commit_paths(&repo.modified_files().unwrap(), message)
+            .unwrap();
+    }
+
+    /// An npm workspace with packages `a` and `b`, only `a` having a feature
+    fn workspace() -> (TempDir, GitRepo) {
+        let temp = TempDir::new().unwrap();
+        let git = git2::Repository::init(temp.path()).unwrap();
+        let mut config = git.config().unwrap();
+        config.set_str("user.name", "Test").unwrap();
+        config.set_str("user.email", "test@example.com").unwrap();
+        let root = temp.path();
+        write(
+            root,
+            "package.json",
+            r#"{"name": "root", "private": true, "workspaces": ["packages/*"]}"#,
+        );
+        for name in ["a", "b"] {
+            write(
+                root,
+                &format!("packages/{}/package.json", name),
+                &format!(r#"{{"name": "{}", "version": "1.0.0"}}"#, name),
+            );
+        }
+        let repo = GitRepo::open(root).unwrap();
+        commit_all(&repo, "chore: initial");
+        write(root, "packages/a/index.js", "export const search = 1;\n");
+        commit_all(&repo, "feat: add search");
+        (temp, repo)
+    }
+
+    #[test]
+    fn test_releases_only_changed_packages() {
+        let (temp, repo) = workspace();
+        let mut config = Config::default();
+        config.versioning.independent = true;
+        let options = WorkspaceReleaseOptions::new().with_concurrency(2);
+
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        assert_eq!(plan.bumps.len(), 1);
+        assert_eq!(plan.bumps[0].package, "a");
+        assert_eq!(plan.bumps[0].new_version, "1.1.0");
+        assert_eq!(plan.examined_commits, 2);
+        assert_eq!(plan.tags(&config), vec!["a@1.1.0"]);
+
+        let baseline = repo.modified_files().unwrap();
+        let result = write_workspace_release(&config, &plan, &options).unwrap();
+        let tags = commit_workspace_release(&repo, &config, &plan, &result, &baseline).unwrap();
+        assert_eq!(tags, vec!["a@1.1.0"]);
+        assert!(repo.find_tag("a@1.1.0").unwrap().is_some());
+        assert!(repo.is_clean().unwrap());
+        assert_eq!(repo.all_commits().unwrap().len(), 3);
+
+        let changelog =
+            std::fs::read_to_string(temp.path().join("packages/a/CHANGELOG.md")).unwrap();
+        assert!(changelog.contains("add search"), "{}", changelog);
+        let b = std::fs::read_to_string(temp.path().join("packages/b/package.json")).unwrap();
+        assert!(b.contains("1.0.0"));
+
+        // Nothing is left to release once the package is tagged
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_group_members_are_released_together() {
+        let (temp, repo) = workspace();
+        let mut config = Config::default();
+        config.versioning.fixed_groups = vec![FixedGroupConfig::new(
+            "core",
+            vec!["a".to_string(), "b".to_string()],
+        )];
+        let options = WorkspaceReleaseOptions::new();
+
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        let mut bumps: Vec<_> = plan
+            .bumps
+            .iter()
+            .map(|b| (b.package.as_str(), b.new_version.as_str()))
+            .collect();
+        bumps.sort();
+        assert_eq!(bumps, vec![("a", "1.1.0"), ("b", "1.1.0")]);
+        assert_eq!(plan.tags(&config), vec!["core@1.1.0"]);
+
+        let baseline = repo.modified_files().unwrap();
+        let result = write_workspace_release(&config, &plan, &options).unwrap();
+        commit_workspace_release(&repo, &config, &plan, &result, &baseline).unwrap();
+        let b = std::fs::read_to_string(temp.path().join("packages/b/package.json")).unwrap();
+        assert!(b.contains("1.1.0"), "{}", b);
+
+        // Both members find the group tag as their last release
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        assert!(plan.is_empty());
+    }
+
+    #[test]
+    fn test_filter_selects_and_forces_packages() {
+        let (temp, repo) = workspace();
+        let mut config = Config::default();
+        config.versioning.independent = true;
+
+        let options = WorkspaceReleaseOptions::new().with_filter(PackageFilter::new().exclude("a"));
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        assert!(plan.is_empty());
+
+        let options = WorkspaceReleaseOptions::new()
+            .with_filter(PackageFilter::new().include("packages/b").with_force(true));
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        assert_eq!(plan.bumps.len(), 1);
+        assert_eq!(plan.bumps[0].package, "b");
+        assert_eq!(plan.bumps[0].new_version, "1.0.1");
+    }
+
+    #[test]
+    fn test_release_type_applies_to_packages_with_commits() {
+        let (temp, repo) = workspace();
+        let mut config = Config::default();
+        config.versioning.independent = true;
+        repo.create_tag("b@1.0.0", None).unwrap();
+        let options = WorkspaceReleaseOptions::new().with_release_type(Some(ReleaseType::Major));
+
+        let plan = plan_workspace_release(&repo, temp.path(), &config, &options).unwrap();
+        let bumps: Vec<_> = plan
+            .bumps
+            .iter()
+            .map(|b| (b.package.as_str(), b.new_version.as_str()))
+            .collect();
+        assert_eq!(bumps, vec![("a", "2.0.0")]);
+    }
+}